@@ -4,7 +4,7 @@
 //! for analysis. Optionally uses `--json-schema` for structured output.
 
 use super::{
-    extract_json, parse_rate_limit_info, wait_with_timeout, AgentBackend, BackendError,
+    extract_json, model_args, parse_rate_limit_info, wait_with_timeout, AgentBackend, BackendError,
     BackendResult, RawMarker, MARKER_JSON_SCHEMA,
 };
 use crate::analyzer::TokenBudget;
@@ -20,43 +20,37 @@ use std::time::Duration;
 pub struct ClaudeBackend {
     /// Extra CLI arguments to pass before the stdin passthrough args.
     extra_args: Vec<String>,
+    /// Model to request (`None` = the CLI's default).
+    model: Option<String>,
 }
 
 impl ClaudeBackend {
     /// Create a new Claude backend with no extra arguments.
     pub fn new() -> Self {
-        Self {
-            extra_args: Vec::new(),
-        }
+        Self::default()
     }
 
     /// Create a new Claude backend with extra CLI arguments.
     pub fn with_extra_args(extra_args: Vec<String>) -> Self {
-        Self { extra_args }
+        Self {
+            extra_args,
+            model: None,
+        }
+    }
+
+    /// Request a specific model.
+    pub fn with_model(mut self, model: Option<String>) -> Self {
+        self.model = model;
+        self
     }
 
     /// Get the CLI command name.
     fn command() -> &'static str {
         "claude"
     }
-}
-
-impl AgentBackend for ClaudeBackend {
-    fn name(&self) -> &'static str {
-        "Claude"
-    }
-
-    fn is_available(&self) -> bool {
-        super::command_exists(Self::command())
-    }
-
-    fn invoke(&self, prompt: &str, timeout: Duration, use_schema: bool) -> BackendResult<String> {
-        if !self.is_available() {
-            return Err(BackendError::NotAvailable(
-                "claude CLI not found in PATH".to_string(),
-            ));
-        }
 
+    /// Build the CLI invocation (without stdio setup).
+    fn build_command(&self, use_schema: bool) -> Command {
         // Build command with --tools "" to disable tool execution
         let mut cmd = Command::new(Self::command());
         cmd.args(["--print", "--output-format", "json"]);
@@ -66,6 +60,12 @@ impl AgentBackend for ClaudeBackend {
             cmd.args(["--json-schema", MARKER_JSON_SCHEMA]);
         }
 
+        cmd.args(model_args(
+            "--model",
+            self.model.as_deref(),
+            &self.extra_args,
+        ));
+
         // Append extra args from per-agent config BEFORE the stdin passthrough
         for arg in &self.extra_args {
             cmd.arg(arg);
@@ -74,21 +74,35 @@ impl AgentBackend for ClaudeBackend {
         // Disable tools for read-only analysis
         // Use "-p -" to read prompt from stdin (avoids ARG_MAX limits)
         cmd.args(["--tools", "", "-p", "-"]);
+        cmd
+    }
+}
+
+impl AgentBackend for ClaudeBackend {
+    fn name(&self) -> &'static str {
+        "Claude"
+    }
+
+    fn is_available(&self) -> bool {
+        super::command_exists(Self::command())
+    }
+
+    fn invoke(&self, prompt: &str, timeout: Duration, use_schema: bool) -> BackendResult<String> {
+        if !self.is_available() {
+            return Err(BackendError::NotAvailable(
+                "claude CLI not found in PATH".to_string(),
+            ));
+        }
+
+        let mut cmd = self.build_command(use_schema);
         cmd.stdin(Stdio::piped());
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
         let mut child = cmd.spawn()?;
 
-        // Write prompt to stdin and close it
-        if let Some(mut stdin) = child.stdin.take() {
-            use std::io::Write;
-            stdin.write_all(prompt.as_bytes())?;
-            // stdin is dropped here, closing the pipe
-        }
-
-        // Wait with timeout
-        let result = wait_with_timeout(&mut child, timeout.as_secs());
+        // Write prompt to stdin and wait with timeout
+        let result = wait_with_timeout(&mut child, prompt.as_bytes(), timeout);
 
         match result {
             Ok(output) => {
@@ -112,10 +126,7 @@ impl AgentBackend for ClaudeBackend {
                     })
                 }
             }
-            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
-                Err(BackendError::Timeout(timeout))
-            }
-            Err(e) => Err(BackendError::Io(e)),
+            Err(e) => Err(BackendError::from_wait(e, timeout)),
         }
     }
 
@@ -197,4 +208,36 @@ mod tests {
         let result = backend.parse_response(response);
         assert!(result.is_err());
     }
+
+    fn args_of(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn claude_command_includes_model_when_set() {
+        let backend = ClaudeBackend::new().with_model(Some("opus".to_string()));
+        let args = args_of(&backend.build_command(false));
+        let pos = args.iter().position(|a| a == "--model").unwrap();
+        assert_eq!(args[pos + 1], "opus");
+        // Tool restrictions still come last
+        assert_eq!(args[args.len() - 4..], ["--tools", "", "-p", "-"]);
+    }
+
+    #[test]
+    fn claude_command_omits_model_when_none() {
+        let args = args_of(&ClaudeBackend::new().build_command(true));
+        assert!(!args.iter().any(|a| a == "--model"));
+    }
+
+    #[test]
+    fn claude_extra_args_model_wins_over_config_model() {
+        let backend = ClaudeBackend::with_extra_args(vec!["--model".into(), "haiku".into()])
+            .with_model(Some("opus".to_string()));
+        let args = args_of(&backend.build_command(false));
+        assert_eq!(args.iter().filter(|a| *a == "--model").count(), 1);
+        assert!(args.contains(&"haiku".to_string()));
+        assert!(!args.contains(&"opus".to_string()));
+    }
 }
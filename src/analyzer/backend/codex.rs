@@ -4,8 +4,8 @@
 //! Optionally uses `--output-schema` for structured JSON output.
 
 use super::{
-    extract_json, get_schema_file_path, parse_rate_limit_info, wait_with_timeout, AgentBackend,
-    BackendError, BackendResult, RawMarker,
+    extract_json, get_schema_file_path, model_args, parse_rate_limit_info, wait_with_timeout,
+    AgentBackend, BackendError, BackendResult, RawMarker,
 };
 use crate::analyzer::TokenBudget;
 use std::process::{Command, Stdio};
@@ -19,43 +19,37 @@ use std::time::Duration;
 pub struct CodexBackend {
     /// Extra CLI arguments to pass to the codex command.
     extra_args: Vec<String>,
+    /// Model to request (`None` = the CLI's default).
+    model: Option<String>,
 }
 
 impl CodexBackend {
     /// Create a new Codex backend with no extra arguments.
     pub fn new() -> Self {
-        Self {
-            extra_args: Vec::new(),
-        }
+        Self::default()
     }
 
     /// Create a new Codex backend with extra CLI arguments.
     pub fn with_extra_args(extra_args: Vec<String>) -> Self {
-        Self { extra_args }
+        Self {
+            extra_args,
+            model: None,
+        }
+    }
+
+    /// Request a specific model.
+    pub fn with_model(mut self, model: Option<String>) -> Self {
+        self.model = model;
+        self
     }
 
     /// Get the CLI command name.
     fn command() -> &'static str {
         "codex"
     }
-}
-
-impl AgentBackend for CodexBackend {
-    fn name(&self) -> &'static str {
-        "Codex"
-    }
-
-    fn is_available(&self) -> bool {
-        super::command_exists(Self::command())
-    }
-
-    fn invoke(&self, prompt: &str, timeout: Duration, use_schema: bool) -> BackendResult<String> {
-        if !self.is_available() {
-            return Err(BackendError::NotAvailable(
-                "codex CLI not found in PATH".to_string(),
-            ));
-        }
 
+    /// Build the CLI invocation (without stdio setup).
+    fn build_command(&self, use_schema: bool) -> BackendResult<Command> {
         // Build command: run in /tmp to avoid loading project skills/context.
         // --skip-git-repo-check prevents git repo discovery errors.
         // --sandbox read-only prevents any writes (placed AFTER extra_args
@@ -72,6 +66,12 @@ impl AgentBackend for CodexBackend {
             cmd.arg(&schema_path);
         }
 
+        cmd.args(model_args(
+            "--model",
+            self.model.as_deref(),
+            &self.extra_args,
+        ));
+
         // Append extra args from per-agent config BEFORE safety flags
         for arg in &self.extra_args {
             cmd.arg(arg);
@@ -79,6 +79,27 @@ impl AgentBackend for CodexBackend {
 
         // Safety-critical: sandbox must come last to prevent override by extra_args
         cmd.args(["--sandbox", "read-only"]);
+        Ok(cmd)
+    }
+}
+
+impl AgentBackend for CodexBackend {
+    fn name(&self) -> &'static str {
+        "Codex"
+    }
+
+    fn is_available(&self) -> bool {
+        super::command_exists(Self::command())
+    }
+
+    fn invoke(&self, prompt: &str, timeout: Duration, use_schema: bool) -> BackendResult<String> {
+        if !self.is_available() {
+            return Err(BackendError::NotAvailable(
+                "codex CLI not found in PATH".to_string(),
+            ));
+        }
+
+        let mut cmd = self.build_command(use_schema)?;
 
         // Pass prompt via stdin to avoid ARG_MAX limits
         cmd.stdin(Stdio::piped());
@@ -87,15 +108,8 @@ impl AgentBackend for CodexBackend {
 
         let mut child = cmd.spawn()?;
 
-        // Write prompt to stdin and close it
-        if let Some(mut stdin) = child.stdin.take() {
-            use std::io::Write;
-            stdin.write_all(prompt.as_bytes())?;
-            // stdin is dropped here, closing the pipe
-        }
-
-        // Wait with timeout
-        let result = wait_with_timeout(&mut child, timeout.as_secs());
+        // Write prompt to stdin and wait with timeout
+        let result = wait_with_timeout(&mut child, prompt.as_bytes(), timeout);
 
         match result {
             Ok(output) => {
@@ -117,10 +131,7 @@ impl AgentBackend for CodexBackend {
                     })
                 }
             }
-            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
-                Err(BackendError::Timeout(timeout))
-            }
-            Err(e) => Err(BackendError::Io(e)),
+            Err(e) => Err(BackendError::from_wait(e, timeout)),
         }
     }
 
@@ -239,4 +250,26 @@ Analysis complete."#;
         let result = backend.parse_response(response);
         assert!(result.is_err());
     }
+
+    fn args_of(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn codex_command_includes_model_when_set() {
+        let backend = CodexBackend::new().with_model(Some("gpt-5.2-codex".to_string()));
+        let args = args_of(&backend.build_command(false).unwrap());
+        let pos = args.iter().position(|a| a == "--model").unwrap();
+        assert_eq!(args[pos + 1], "gpt-5.2-codex");
+        // The sandbox flag stays last
+        assert_eq!(args[args.len() - 2..], ["--sandbox", "read-only"]);
+    }
+
+    #[test]
+    fn codex_command_omits_model_when_none() {
+        let args = args_of(&CodexBackend::new().build_command(false).unwrap());
+        assert!(!args.iter().any(|a| a == "--model" || a == "-m"));
+    }
 }
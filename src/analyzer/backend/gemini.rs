@@ -5,7 +5,7 @@
 //! Note: Gemini CLI does not support JSON schema enforcement.
 
 use super::{
-    extract_json, parse_rate_limit_info, wait_with_timeout, AgentBackend, BackendError,
+    extract_json, model_args, parse_rate_limit_info, wait_with_timeout, AgentBackend, BackendError,
     BackendResult, RawMarker,
 };
 use crate::analyzer::TokenBudget;
@@ -21,25 +21,54 @@ use std::time::Duration;
 pub struct GeminiBackend {
     /// Extra CLI arguments to pass to the gemini command.
     extra_args: Vec<String>,
+    /// Model to request (`None` = the CLI's default).
+    model: Option<String>,
 }
 
 impl GeminiBackend {
     /// Create a new Gemini backend with no extra arguments.
     pub fn new() -> Self {
-        Self {
-            extra_args: Vec::new(),
-        }
+        Self::default()
     }
 
     /// Create a new Gemini backend with extra CLI arguments.
     pub fn with_extra_args(extra_args: Vec<String>) -> Self {
-        Self { extra_args }
+        Self {
+            extra_args,
+            model: None,
+        }
+    }
+
+    /// Request a specific model.
+    pub fn with_model(mut self, model: Option<String>) -> Self {
+        self.model = model;
+        self
     }
 
     /// Get the CLI command name.
     fn command() -> &'static str {
         "gemini"
     }
+
+    /// Build the CLI invocation (without stdio setup).
+    fn build_command(&self) -> Command {
+        // Use --approval-mode plan for read-only operation (no tool execution)
+        // Safety flags placed AFTER extra_args to prevent override.
+        // Pass prompt via stdin to avoid ARG_MAX limits
+        let mut cmd = Command::new(Self::command());
+        cmd.args(["--output-format", "json"]);
+
+        cmd.args(model_args("-m", self.model.as_deref(), &self.extra_args));
+
+        // Append extra args from per-agent config BEFORE safety flags
+        for arg in &self.extra_args {
+            cmd.arg(arg);
+        }
+
+        // Safety-critical: approval-mode and prompt source must come last
+        cmd.args(["--approval-mode", "plan", "--prompt", "-"]);
+        cmd
+    }
 }
 
 impl AgentBackend for GeminiBackend {
@@ -61,35 +90,15 @@ impl AgentBackend for GeminiBackend {
         // Note: Gemini CLI does not support JSON schema enforcement
         // use_schema parameter is ignored
 
-        // Use --approval-mode plan for read-only operation (no tool execution)
-        // Safety flags placed AFTER extra_args to prevent override.
-        // Pass prompt via stdin to avoid ARG_MAX limits
-        let mut cmd = Command::new(Self::command());
-        cmd.args(["--output-format", "json"]);
-
-        // Append extra args from per-agent config BEFORE safety flags
-        for arg in &self.extra_args {
-            cmd.arg(arg);
-        }
-
-        // Safety-critical: approval-mode and prompt source must come last
-        cmd.args(["--approval-mode", "plan", "--prompt", "-"]);
-
-        let mut child = cmd
+        let mut child = self
+            .build_command()
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
 
-        // Write prompt to stdin and close it
-        if let Some(mut stdin) = child.stdin.take() {
-            use std::io::Write;
-            stdin.write_all(prompt.as_bytes())?;
-            // stdin is dropped here, closing the pipe
-        }
-
-        // Wait with timeout
-        let result = wait_with_timeout(&mut child, timeout.as_secs());
+        // Write prompt to stdin and wait with timeout
+        let result = wait_with_timeout(&mut child, prompt.as_bytes(), timeout);
 
         match result {
             Ok(output) => {
@@ -109,10 +118,7 @@ impl AgentBackend for GeminiBackend {
                     })
                 }
             }
-            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
-                Err(BackendError::Timeout(timeout))
-            }
-            Err(e) => Err(BackendError::Io(e)),
+            Err(e) => Err(BackendError::from_wait(e, timeout)),
         }
     }
 
@@ -205,4 +211,29 @@ mod tests {
         let available = budget.available_for_content();
         assert!(available > 800_000); // Should be around 841K
     }
+
+    fn args_of(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn gemini_command_includes_model_when_set() {
+        let backend = GeminiBackend::new().with_model(Some("gemini-2.5-pro".to_string()));
+        let args = args_of(&backend.build_command());
+        let pos = args.iter().position(|a| a == "-m").unwrap();
+        assert_eq!(args[pos + 1], "gemini-2.5-pro");
+        // Read-only approval mode and stdin prompt stay last
+        assert_eq!(
+            args[args.len() - 4..],
+            ["--approval-mode", "plan", "--prompt", "-"]
+        );
+    }
+
+    #[test]
+    fn gemini_command_omits_model_when_none() {
+        let args = args_of(&GeminiBackend::new().build_command());
+        assert!(!args.iter().any(|a| a == "-m" || a == "--model"));
+    }
 }
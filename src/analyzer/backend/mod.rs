@@ -22,6 +22,7 @@ pub use claude::ClaudeBackend;
 pub use codex::CodexBackend;
 pub use gemini::GeminiBackend;
 
+use crate::analyzer::cancel::CancelToken;
 use crate::analyzer::chunk::TokenBudget;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -47,47 +48,67 @@ pub fn get_schema_file_path() -> std::io::Result<PathBuf> {
     Ok(schema_path)
 }
 
-/// Wait for child process with timeout, draining pipes concurrently.
+/// Feed `input` to a child's stdin and wait for it with a timeout, draining
+/// pipes concurrently.
 ///
-/// Spawns reader threads for stdout/stderr to prevent pipe buffer deadlocks.
-/// Without concurrent reading, a child that produces more than the OS pipe
-/// buffer (~16KB on macOS) will block on write while the parent blocks
-/// waiting for exit.
+/// Stdin is written and stdout/stderr are read on their own threads while
+/// this thread polls for exit. Writing the prompt up front would deadlock
+/// with a child that answers before it has read all of its input, and
+/// reading only after exit would deadlock with a child that produces more
+/// than the OS pipe buffer (~16KB on macOS, 64KB on Linux): either way both
+/// sides end up blocked on a full pipe.
+///
+/// On timeout, or when the analysis is cancelled through
+/// [`CancelToken::process`], the child is killed and reaped; the pipe
+/// threads finish on their own once the pipes close. Cancellation returns
+/// an `Interrupted` error.
 pub(crate) fn wait_with_timeout(
     child: &mut std::process::Child,
-    timeout_secs: u64,
+    input: &[u8],
+    timeout: Duration,
 ) -> std::io::Result<std::process::Output> {
-    use std::io::Read;
+    use std::io::{Read, Write};
     use std::thread;
     use std::time::Instant;
 
-    // Take ownership of pipes and drain them in background threads.
-    // This prevents the child from blocking on a full pipe buffer.
-    let stdout_handle = child.stdout.take().map(|pipe| {
-        thread::spawn(move || {
-            let mut buf = Vec::new();
-            let mut pipe = pipe;
-            pipe.read_to_end(&mut buf).ok();
-            buf
+    fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> Option<thread::JoinHandle<Vec<u8>>> {
+        pipe.map(|mut pipe| {
+            thread::spawn(move || {
+                let mut buf = Vec::new();
+                pipe.read_to_end(&mut buf).ok();
+                buf
+            })
         })
-    });
+    }
 
-    let stderr_handle = child.stderr.take().map(|pipe| {
-        thread::spawn(move || {
-            let mut buf = Vec::new();
-            let mut pipe = pipe;
-            pipe.read_to_end(&mut buf).ok();
-            buf
+    // Take ownership of pipes and drain them in background threads.
+    // This prevents the child from blocking on a full pipe buffer.
+    let stdout_handle = drain(child.stdout.take());
+    let stderr_handle = drain(child.stderr.take());
+
+    // Write the input from a thread too; dropping stdin afterwards sends
+    // EOF. A child that exits without reading everything breaks the pipe,
+    // which is not an error here: its exit status tells what happened.
+    let stdin_handle = child.stdin.take().map(|mut stdin| {
+        let input = input.to_vec();
+        thread::spawn(move || match stdin.write_all(&input) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e),
+            _ => Ok(()),
         })
     });
 
     let start = Instant::now();
-    let poll_interval = Duration::from_millis(100);
+    // Poll quickly at first so short-lived children return promptly
+    let mut poll_interval = Duration::from_millis(5);
+    let max_poll_interval = Duration::from_millis(100);
 
     loop {
         match child.try_wait() {
             Ok(Some(status)) => {
-                // Process finished — join reader threads
+                // Process finished — join pipe threads
+                if let Some(Ok(Err(e))) = stdin_handle.map(|h| h.join()) {
+                    return Err(e);
+                }
                 let stdout = stdout_handle
                     .and_then(|h| h.join().ok())
                     .unwrap_or_default();
@@ -102,8 +123,16 @@ pub(crate) fn wait_with_timeout(
                 });
             }
             Ok(None) => {
-                // Still running — check timeout
-                if start.elapsed().as_secs() >= timeout_secs {
+                // Still running — check cancellation, then timeout
+                if CancelToken::process().is_cancelled() {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::Interrupted,
+                        "Analysis cancelled",
+                    ));
+                }
+                if start.elapsed() >= timeout {
                     // Kill and reap to prevent zombie process
                     let _ = child.kill();
                     let _ = child.wait(); // Reap the zombie
@@ -113,6 +142,7 @@ pub(crate) fn wait_with_timeout(
                     ));
                 }
                 thread::sleep(poll_interval);
+                poll_interval = (poll_interval * 2).min(max_poll_interval);
             }
             Err(e) => return Err(e),
         }
@@ -165,12 +195,29 @@ pub enum AgentType {
 }
 
 impl AgentType {
+    /// All agents in preference order, used to pick a fallback when the
+    /// configured agent is not installed.
+    pub const PREFERENCE: [AgentType; 3] = [AgentType::Claude, AgentType::Codex, AgentType::Gemini];
+
     /// Create the appropriate backend for this agent type.
-    pub fn create_backend(&self, extra_args: Vec<String>) -> Box<dyn AgentBackend> {
+    ///
+    /// `model` is passed with the agent's own model flag unless
+    /// `extra_args` already select a model.
+    pub fn create_backend(
+        &self,
+        extra_args: Vec<String>,
+        model: Option<String>,
+    ) -> Box<dyn AgentBackend> {
         match self {
-            AgentType::Claude => Box::new(ClaudeBackend::with_extra_args(extra_args)),
-            AgentType::Codex => Box::new(CodexBackend::with_extra_args(extra_args)),
-            AgentType::Gemini => Box::new(GeminiBackend::with_extra_args(extra_args)),
+            AgentType::Claude => {
+                Box::new(ClaudeBackend::with_extra_args(extra_args).with_model(model))
+            }
+            AgentType::Codex => {
+                Box::new(CodexBackend::with_extra_args(extra_args).with_model(model))
+            }
+            AgentType::Gemini => {
+                Box::new(GeminiBackend::with_extra_args(extra_args).with_model(model))
+            }
         }
     }
 
@@ -183,6 +230,13 @@ impl AgentType {
         }
     }
 
+    /// Look up an agent by its CLI command name (case-insensitive).
+    pub fn from_command_name(name: &str) -> Option<Self> {
+        Self::PREFERENCE
+            .into_iter()
+            .find(|agent| agent.command_name().eq_ignore_ascii_case(name))
+    }
+
     /// Get the token budget for this agent type.
     pub fn token_budget(&self) -> TokenBudget {
         match self {
@@ -226,6 +280,20 @@ pub enum BackendError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Analysis cancelled")]
+    Cancelled,
+}
+
+impl BackendError {
+    /// Map an error from [`wait_with_timeout`] for a call with `timeout`.
+    pub(crate) fn from_wait(error: std::io::Error, timeout: Duration) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::TimedOut => BackendError::Timeout(timeout),
+            std::io::ErrorKind::Interrupted => BackendError::Cancelled,
+            _ => BackendError::Io(error),
+        }
+    }
 }
 
 /// Rate limit information extracted from agent response.
@@ -531,6 +599,23 @@ fn truncate_stderr(stderr: &str) -> String {
     }
 }
 
+/// Arguments selecting `model` with the agent's model `flag`.
+///
+/// Empty when no model is configured, or when `extra_args` already contain a
+/// model flag (`--model`, `--model=...` or `-m`): explicit extra args take
+/// precedence over the `model` setting.
+pub(crate) fn model_args(flag: &str, model: Option<&str>, extra_args: &[String]) -> Vec<String> {
+    let overridden = extra_args
+        .iter()
+        .any(|arg| arg == "--model" || arg == "-m" || arg.starts_with("--model="));
+    match model {
+        Some(model) if !overridden && !model.is_empty() => {
+            vec![flag.to_string(), model.to_string()]
+        }
+        _ => Vec::new(),
+    }
+}
+
 /// Check if a command is available in PATH.
 ///
 /// Uses platform-specific command lookup:
@@ -892,8 +977,94 @@ Done."#;
     #[test]
     fn agent_type_create_backend() {
         // Just verify it creates without panic
-        let _ = AgentType::Claude.create_backend(vec![]);
-        let _ = AgentType::Codex.create_backend(vec![]);
-        let _ = AgentType::Gemini.create_backend(vec![]);
+        let _ = AgentType::Claude.create_backend(vec![], None);
+        let _ = AgentType::Codex.create_backend(vec![], None);
+        let _ = AgentType::Gemini.create_backend(vec![], Some("m".to_string()));
+    }
+
+    #[test]
+    fn model_args_uses_flag_when_set() {
+        assert_eq!(
+            model_args("-m", Some("gemini-2.5-pro"), &[]),
+            vec!["-m", "gemini-2.5-pro"]
+        );
+        assert!(model_args("--model", None, &[]).is_empty());
+        assert!(model_args("--model", Some(""), &[]).is_empty());
+    }
+
+    #[test]
+    fn model_args_defers_to_model_in_extra_args() {
+        for extra in [["--model", "x"], ["-m", "x"], ["--model=x", "--verbose"]] {
+            let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+            assert!(model_args("--model", Some("y"), &extra).is_empty());
+        }
+        let unrelated = vec!["--verbose".to_string()];
+        assert_eq!(model_args("--model", Some("y"), &unrelated).len(), 2);
+    }
+
+    #[cfg(unix)]
+    fn spawn_piped(script: &str) -> std::process::Child {
+        use std::process::{Command, Stdio};
+        Command::new("sh")
+            .args(["-c", script])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wait_with_timeout_drains_output_larger_than_pipe_buffer() {
+        // 1 MiB on stdout and 256 KiB on stderr, well past any pipe buffer
+        let mut child =
+            spawn_piped("head -c 1048576 /dev/zero; head -c 262144 /dev/zero >&2; exit 3");
+
+        let output = wait_with_timeout(&mut child, b"", Duration::from_secs(30)).unwrap();
+
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(output.stdout.len(), 1_048_576);
+        assert_eq!(output.stderr.len(), 262_144);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wait_with_timeout_feeds_large_input_to_echoing_child() {
+        // `cat` writes while it is still reading, so stdin and stdout must
+        // be serviced at the same time
+        let input = vec![b'x'; 1_048_576];
+        let mut child = spawn_piped("cat");
+
+        let output = wait_with_timeout(&mut child, &input, Duration::from_secs(30)).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, input);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wait_with_timeout_ignores_unread_input() {
+        let input = vec![b'x'; 1_048_576];
+        let mut child = spawn_piped("echo done");
+
+        let output = wait_with_timeout(&mut child, &input, Duration::from_secs(30)).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"done\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wait_with_timeout_kills_and_reaps_on_timeout() {
+        let mut child = spawn_piped("exec sleep 30");
+        let start = std::time::Instant::now();
+
+        let err = wait_with_timeout(&mut child, b"", Duration::from_millis(200)).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(10));
+        // Already reaped: the exit status is available without blocking
+        assert!(child.try_wait().unwrap().is_some());
     }
 }
@@ -0,0 +1,78 @@
+//! Cancelling a running analysis on Ctrl-C.
+//!
+//! A [`CancelToken`] is a shared flag. The executor checks it before each
+//! chunk and stops dispatching once it is set; [`super::backend`] checks the
+//! process-wide token while waiting for an agent CLI and kills the child
+//! when it is set, so no agent outlives `agr analyze`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// Exit code used when a second Ctrl-C forces an immediate exit.
+pub const FORCED_EXIT_CODE: i32 = 130;
+
+/// Shared cancellation flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The token set by Ctrl-C once [`Self::register_signal_handlers`] ran.
+    ///
+    /// Running agent CLIs are killed when this token is cancelled.
+    pub fn process() -> &'static CancelToken {
+        static PROCESS: OnceLock<CancelToken> = OnceLock::new();
+        PROCESS.get_or_init(CancelToken::new)
+    }
+
+    /// Request cancellation.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Cancel this token on SIGINT (Ctrl-C) and SIGHUP.
+    ///
+    /// The first signal only sets the flag so the analysis can stop and
+    /// clean up; a second one exits immediately with [`FORCED_EXIT_CODE`].
+    pub fn register_signal_handlers(&self) {
+        #[cfg(unix)]
+        {
+            use signal_hook::flag::{register, register_conditional_shutdown};
+            for signal in [libc::SIGINT, libc::SIGHUP] {
+                let _ = register_conditional_shutdown(
+                    signal,
+                    FORCED_EXIT_CODE,
+                    Arc::clone(&self.cancelled),
+                );
+                let _ = register(signal, Arc::clone(&self.cancelled));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_flag() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert!(!CancelToken::new().is_cancelled());
+    }
+}
@@ -9,6 +9,8 @@
 //! - `ChunkCalculator` divides content into appropriately-sized chunks
 //! - `AnalysisChunk` represents a chunk ready for LLM analysis
 //! - Overlap strategy ensures context continuity between chunks
+//! - Chunk ends snap back to idle gaps or blank lines near the token
+//!   budget, so a tool call or error block is not cut in half
 
 use crate::analyzer::backend::AgentType;
 use crate::analyzer::types::{AnalysisContent, AnalysisSegment};
@@ -180,6 +182,11 @@ pub struct ChunkConfig {
     pub overlap_pct: f64,
     /// Minimum overlap in tokens
     pub min_overlap_tokens: usize,
+    /// Minimum overlap in seconds of recording time
+    pub min_overlap_secs: f64,
+    /// How far before the token budget a chunk may end to land on an idle
+    /// gap or blank line, as percentage of chunk size (0.0 = split exactly)
+    pub boundary_slack_pct: f64,
 }
 
 impl Default for ChunkConfig {
@@ -187,6 +194,8 @@ impl Default for ChunkConfig {
         Self {
             overlap_pct: 0.10,
             min_overlap_tokens: 500,
+            min_overlap_secs: 5.0,
+            boundary_slack_pct: 0.10,
         }
     }
 }
@@ -240,13 +249,18 @@ impl ChunkCalculator {
     }
 
     /// Create overlapping chunks for large content.
+    ///
+    /// Each chunk ends at the budget or at a natural break shortly before
+    /// it (see [`Self::snap_boundary`]). The next chunk starts early enough
+    /// to repeat both the token and the time overlap, but never before the
+    /// midpoint of the previous chunk so chunking always moves forward.
     fn create_overlapping_chunks(
         &self,
         content: &AnalysisContent,
         available: usize,
     ) -> Vec<AnalysisChunk> {
         let overlap = self.calculate_overlap(available);
-        let step = available.saturating_sub(overlap);
+        let slack = (available as f64 * self.config.boundary_slack_pct) as usize;
 
         let mut chunks = Vec::new();
         let mut token_offset = 0;
@@ -254,27 +268,71 @@ impl ChunkCalculator {
 
         while token_offset < content.total_tokens {
             let target_end = (token_offset + available).min(content.total_tokens);
+            let chunk_end = if target_end >= content.total_tokens {
+                target_end
+            } else {
+                let earliest = target_end.saturating_sub(slack).max(token_offset + 1);
+                self.snap_boundary(content, earliest, target_end)
+            };
 
             // Find segments that fit in this token range
             let (segments, time_range) =
-                self.find_segments_for_range(content, token_offset, target_end);
+                self.find_segments_for_range(content, token_offset, chunk_end);
+            let end_time = time_range.end;
 
             if !segments.is_empty() {
                 chunks.push(AnalysisChunk::new(chunk_id, time_range, segments));
                 chunk_id += 1;
             }
 
-            token_offset += step;
-
             // Prevent infinite loop on last chunk
-            if target_end >= content.total_tokens {
+            if chunk_end >= content.total_tokens {
                 break;
             }
+
+            let by_tokens = chunk_end.saturating_sub(overlap);
+            let by_time = if self.config.min_overlap_secs > 0.0 {
+                token_offset_at_time(content, end_time - self.config.min_overlap_secs)
+            } else {
+                chunk_end
+            };
+            let midpoint = token_offset + (chunk_end - token_offset) / 2;
+            token_offset = by_tokens.min(by_time).max(midpoint).max(token_offset + 1);
         }
 
         chunks
     }
 
+    /// Pick where a chunk that should end at `target` ends.
+    ///
+    /// Looks back as far as `earliest` for a natural break: the nearest
+    /// segment boundary (the extractor splits segments at idle gaps), else
+    /// the nearest blank line. Falls back to `target` when neither is in
+    /// reach.
+    fn snap_boundary(&self, content: &AnalysisContent, earliest: usize, target: usize) -> usize {
+        let mut gap = None;
+        let mut blank_line = None;
+        let mut segment_start = 0;
+
+        for segment in &content.segments {
+            let segment_end = segment_start + segment.estimated_tokens;
+            if segment_start >= target {
+                break;
+            }
+            if segment_end >= earliest && segment_end <= target {
+                gap = Some(segment_end);
+            }
+            if segment_end > earliest {
+                if let Some(offset) = last_blank_line(segment, segment_start, earliest, target) {
+                    blank_line = Some(offset);
+                }
+            }
+            segment_start = segment_end;
+        }
+
+        gap.or(blank_line).unwrap_or(target)
+    }
+
     /// Calculate overlap tokens based on configuration.
     fn calculate_overlap(&self, available: usize) -> usize {
         let pct_overlap = (available as f64 * self.config.overlap_pct) as usize;
@@ -393,6 +451,58 @@ impl ChunkCalculator {
     }
 }
 
+/// Token offset of the last blank line in `segment` between `earliest` and
+/// `target`, where `segment_start` is the segment's own offset.
+///
+/// Uses the same proportional token-to-character mapping as chunk splitting.
+fn last_blank_line(
+    segment: &AnalysisSegment,
+    segment_start: usize,
+    earliest: usize,
+    target: usize,
+) -> Option<usize> {
+    let total_chars = segment.content.chars().count();
+    if total_chars == 0 || segment.estimated_tokens == 0 {
+        return None;
+    }
+    let ratio = total_chars as f64 / segment.estimated_tokens as f64;
+    let to_token = |char_pos: usize| segment_start + (char_pos as f64 / ratio).round() as usize;
+
+    let mut found = None;
+    let mut previous = None;
+    for (pos, ch) in segment.content.chars().enumerate() {
+        if ch == '\n' && previous == Some('\n') {
+            // Split after the blank line, at the start of the next paragraph
+            let offset = to_token(pos + 1);
+            if offset > target {
+                break;
+            }
+            if offset >= earliest && offset > segment_start {
+                found = Some(offset);
+            }
+        }
+        previous = Some(ch);
+    }
+    found
+}
+
+/// Token offset of recording time `time`, mapping proportionally within
+/// segments. Times before a segment map to its start.
+fn token_offset_at_time(content: &AnalysisContent, time: f64) -> usize {
+    let mut segment_start = 0;
+    for segment in &content.segments {
+        if time <= segment.start_time {
+            return segment_start;
+        }
+        if time < segment.end_time {
+            let fraction = (time - segment.start_time) / (segment.end_time - segment.start_time);
+            return segment_start + (fraction * segment.estimated_tokens as f64) as usize;
+        }
+        segment_start += segment.estimated_tokens;
+    }
+    segment_start
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -571,6 +681,7 @@ mod tests {
             total_tokens,
             segments,
             stats: Default::default(),
+            idle_gaps: Vec::new(),
         }
     }
 
@@ -622,6 +733,7 @@ mod tests {
         let config = ChunkConfig {
             overlap_pct: 0.10,
             min_overlap_tokens: 500,
+            ..Default::default()
         };
         let calculator = ChunkCalculator::new(budget, config);
 
@@ -675,6 +787,149 @@ mod tests {
         let config = ChunkConfig::default();
         assert!((config.overlap_pct - 0.10).abs() < 0.001);
         assert_eq!(config.min_overlap_tokens, 500);
+        assert!((config.min_overlap_secs - 5.0).abs() < 0.001);
+        assert!((config.boundary_slack_pct - 0.10).abs() < 0.001);
+    }
+
+    // ============================================
+    // Boundary Alignment Tests
+    // ============================================
+
+    /// Content whose segments (separated by idle gaps) have the given sizes.
+    fn content_with_segment_tokens(sizes: &[usize]) -> AnalysisContent {
+        let segments: Vec<AnalysisSegment> = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &tokens)| AnalysisSegment {
+                start_time: i as f64 * 20.0,
+                end_time: i as f64 * 20.0 + 10.0,
+                content: "x".repeat(tokens * 4),
+                estimated_tokens: tokens,
+                event_range: (i, i + 1),
+            })
+            .collect();
+        AnalysisContent {
+            total_duration: sizes.len() as f64 * 20.0,
+            total_tokens: sizes.iter().sum(),
+            segments,
+            stats: Default::default(),
+            idle_gaps: Vec::new(),
+        }
+    }
+
+    /// Calculator with exactly `available` content tokens and no overlap.
+    fn aligned_calculator(available: usize, slack_pct: f64) -> ChunkCalculator {
+        let budget = TokenBudget::new(available, 0, 0, 0.0);
+        let config = ChunkConfig {
+            overlap_pct: 0.0,
+            min_overlap_tokens: 0,
+            min_overlap_secs: 0.0,
+            boundary_slack_pct: slack_pct,
+        };
+        ChunkCalculator::new(budget, config)
+    }
+
+    fn chunk_tokens(chunks: &[AnalysisChunk]) -> Vec<usize> {
+        chunks.iter().map(|c| c.estimated_tokens).collect()
+    }
+
+    #[test]
+    fn chunk_boundary_snaps_to_nearest_gap_within_slack() {
+        // Gaps at 4_000, 9_300 and 10_400; the budget ends at 10_000
+        let content = content_with_segment_tokens(&[4_000, 5_300, 1_100, 5_000]);
+        let chunks = aligned_calculator(10_000, 0.10).calculate_chunks(&content);
+
+        assert_eq!(chunk_tokens(&chunks)[0], 9_300);
+        // The chunk ends exactly at the end of the second segment
+        assert_eq!(chunks[0].segments.len(), 2);
+        assert!((chunks[0].time_range.end - 30.0).abs() < 0.001);
+        assert!((chunks[1].time_range.start - 40.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn chunk_boundary_ignores_gaps_outside_slack() {
+        // The nearest gap (8_000) is 2_000 tokens before the budget
+        let content = content_with_segment_tokens(&[8_000, 8_000]);
+        let chunks = aligned_calculator(10_000, 0.10).calculate_chunks(&content);
+
+        assert_eq!(chunk_tokens(&chunks), vec![10_000, 6_000]);
+    }
+
+    #[test]
+    fn chunk_boundary_without_slack_splits_at_budget() {
+        let content = content_with_segment_tokens(&[4_000, 5_300, 1_100, 5_000]);
+        let chunks = aligned_calculator(10_000, 0.0).calculate_chunks(&content);
+
+        assert_eq!(chunk_tokens(&chunks), vec![10_000, 5_400]);
+    }
+
+    #[test]
+    fn chunk_boundary_snaps_to_blank_line_inside_segment() {
+        // One long burst of output with a blank line at character 38_000
+        // (token 9_500); 4 characters per token
+        let mut text = "x".repeat(37_998);
+        text.push_str("\n\n");
+        text.push_str(&"y".repeat(42_000));
+        let content = AnalysisContent {
+            total_duration: 100.0,
+            total_tokens: 20_000,
+            segments: vec![AnalysisSegment {
+                start_time: 0.0,
+                end_time: 100.0,
+                content: text,
+                estimated_tokens: 20_000,
+                event_range: (0, 1),
+            }],
+            stats: Default::default(),
+            idle_gaps: Vec::new(),
+        };
+        let chunks = aligned_calculator(10_000, 0.10).calculate_chunks(&content);
+
+        assert_eq!(chunk_tokens(&chunks)[0], 9_500);
+        assert!(chunks[0].text.ends_with("x\n\n"));
+        assert!(chunks[1].text.starts_with('y'));
+    }
+
+    #[test]
+    fn chunk_boundary_prefers_gap_over_blank_line() {
+        let mut second = "x".repeat(4_000 * 4 - 400);
+        second.push_str("\n\n");
+        second.push_str(&"x".repeat(398));
+        let mut content = content_with_segment_tokens(&[5_500, 4_000, 6_000]);
+        // Blank line at token 9_400, gap at 9_500
+        content.segments[1].content = second;
+        let chunks = aligned_calculator(10_000, 0.10).calculate_chunks(&content);
+
+        assert_eq!(chunk_tokens(&chunks)[0], 9_500);
+    }
+
+    #[test]
+    fn adjacent_chunks_overlap_by_time() {
+        // A single 100s burst of 20K tokens: 200 tokens per second
+        let content = AnalysisContent {
+            total_duration: 100.0,
+            total_tokens: 20_000,
+            segments: vec![AnalysisSegment {
+                start_time: 0.0,
+                end_time: 100.0,
+                content: "x".repeat(80_000),
+                estimated_tokens: 20_000,
+                event_range: (0, 1),
+            }],
+            stats: Default::default(),
+            idle_gaps: Vec::new(),
+        };
+        let budget = TokenBudget::new(10_000, 0, 0, 0.0);
+        let config = ChunkConfig {
+            overlap_pct: 0.0,
+            min_overlap_tokens: 0,
+            min_overlap_secs: 5.0,
+            boundary_slack_pct: 0.0,
+        };
+        let chunks = ChunkCalculator::new(budget, config).calculate_chunks(&content);
+
+        assert!((chunks[0].time_range.end - 50.0).abs() < 0.001);
+        assert!((chunks[1].time_range.start - 45.0).abs() < 0.001);
     }
 
     // ============================================
@@ -742,6 +997,7 @@ mod tests {
                 event_range: (0, 100),
             }],
             stats: Default::default(),
+            idle_gaps: Vec::new(),
         };
 
         let chunks = calculator.calculate_chunks(&content);
@@ -810,6 +1066,7 @@ mod tests {
                 event_range: (0, 50),
             }],
             stats: Default::default(),
+            idle_gaps: Vec::new(),
         };
 
         let chunks = calculator.calculate_chunks(&content);
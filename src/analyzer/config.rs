@@ -1,7 +1,57 @@
 //! Configuration for the content extraction pipeline.
 
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::config::AnalysisExtractionConfig;
+
+/// How aggressively the extraction pipeline condenses a recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ExtractionLevel {
+    /// Keep most content: no global dedup or block truncation
+    Light,
+    /// Balanced defaults
+    #[default]
+    Normal,
+    /// Every transform with tight thresholds
+    Aggressive,
+}
+
+impl ExtractionLevel {
+    /// Config names of all levels.
+    pub const NAMES: &'static [&'static str] = &["light", "normal", "aggressive"];
+}
+
+impl FromStr for ExtractionLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "light" => Ok(ExtractionLevel::Light),
+            "normal" => Ok(ExtractionLevel::Normal),
+            "aggressive" => Ok(ExtractionLevel::Aggressive),
+            _ => Err(format!(
+                "Unknown extraction level '{}'. Valid: {}",
+                s,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ExtractionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ExtractionLevel::Light => "light",
+            ExtractionLevel::Normal => "normal",
+            ExtractionLevel::Aggressive => "aggressive",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Configuration for the content extraction pipeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractionConfig {
@@ -33,6 +83,8 @@ pub struct ExtractionConfig {
     pub coalesce_time_threshold: f64,
     /// Enable truncation of large output blocks
     pub truncate_large_blocks: bool,
+    /// Enable the global line-frequency cap and redraw-frame hashing
+    pub global_dedupe: bool,
     /// Max times a specific line can repeat globally across the session
     pub max_line_repeats: usize,
     /// Window size for event hashing (number of events to check for redraws)
@@ -43,6 +95,11 @@ pub struct ExtractionConfig {
     pub max_block_size: usize,
     /// Number of lines to keep at head/tail during truncation
     pub truncation_context_lines: usize,
+    /// Collapse pauses longer than this many seconds (None = keep all time)
+    pub idle_gap_threshold: Option<f64>,
+    /// Fraction (0.0 to 1.0) of non-printable characters above which an
+    /// output event is replaced with a binary placeholder
+    pub binary_threshold: f64,
 }
 
 impl Default for ExtractionConfig {
@@ -65,8 +122,157 @@ impl Default for ExtractionConfig {
             event_window_size: 50,
             max_burst_lines: 500,
             truncate_large_blocks: true,
+            global_dedupe: true,
             max_block_size: 8 * 1024, // 8KB
             truncation_context_lines: 50,
+            idle_gap_threshold: Some(60.0),
+            binary_threshold: 0.3,
+        }
+    }
+}
+
+impl ExtractionConfig {
+    /// Preset configuration for an extraction level.
+    ///
+    /// `Normal` is the default configuration. `Light` skips the global
+    /// deduplicator and block truncation and only collapses near-identical
+    /// lines; `Aggressive` runs everything with tight thresholds.
+    pub fn preset(level: ExtractionLevel) -> Self {
+        let normal = Self::default();
+        match level {
+            ExtractionLevel::Light => Self {
+                global_dedupe: false,
+                truncate_large_blocks: false,
+                similarity_threshold: 0.95,
+                max_burst_lines: 2000,
+                ..normal
+            },
+            ExtractionLevel::Normal => normal,
+            ExtractionLevel::Aggressive => Self {
+                similarity_threshold: 0.6,
+                coalesce_time_threshold: 0.5,
+                max_line_repeats: 3,
+                event_window_size: 200,
+                max_burst_lines: 100,
+                max_block_size: 2 * 1024,
+                truncation_context_lines: 20,
+                ..normal
+            },
+        }
+    }
+
+    /// Resolve the extraction config for a run.
+    ///
+    /// The level comes from the CLI, then the `[analysis.extraction]` config,
+    /// then `Normal`; explicit tuning values are applied on top of the preset.
+    pub fn resolve(level: Option<ExtractionLevel>, tuning: &AnalysisExtractionConfig) -> Self {
+        let level = level
+            .or_else(|| tuning.level.as_deref().and_then(|name| name.parse().ok()))
+            .unwrap_or_default();
+        Self::preset(level).with_tuning(tuning)
+    }
+
+    /// Apply the user's `[analysis.extraction]` overrides.
+    ///
+    /// The window size drives both the windowed line deduplicator and the
+    /// global event-hash window; the similarity threshold drives both the
+    /// similar-line filter and event coalescing.
+    pub fn with_tuning(mut self, tuning: &AnalysisExtractionConfig) -> Self {
+        if let Some(window_size) = tuning.window_size {
+            self.event_window_size = window_size;
         }
+        if let Some(threshold) = tuning.similarity_threshold {
+            self.similarity_threshold = threshold;
+        }
+        if let Some(max_block_size) = tuning.max_block_size {
+            self.max_block_size = max_block_size;
+        }
+        if let Some(threshold) = tuning.binary_threshold {
+            self.binary_threshold = threshold;
+        }
+        self
+    }
+
+    /// Set the idle gap threshold in seconds (0 disables idle collapsing).
+    pub fn with_idle_gap(mut self, secs: u64) -> Self {
+        self.idle_gap_threshold = (secs > 0).then_some(secs as f64);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_tuning_overrides_only_set_fields() {
+        let tuning = AnalysisExtractionConfig {
+            window_size: Some(10),
+            max_block_size: Some(1024),
+            ..Default::default()
+        };
+
+        let config = ExtractionConfig::default().with_tuning(&tuning);
+
+        assert_eq!(config.event_window_size, 10);
+        assert_eq!(config.max_block_size, 1024);
+        assert_eq!(
+            config.similarity_threshold,
+            ExtractionConfig::default().similarity_threshold
+        );
+    }
+
+    #[test]
+    fn with_idle_gap_zero_disables_collapsing() {
+        assert_eq!(
+            ExtractionConfig::default()
+                .with_idle_gap(90)
+                .idle_gap_threshold,
+            Some(90.0)
+        );
+        assert_eq!(
+            ExtractionConfig::default()
+                .with_idle_gap(0)
+                .idle_gap_threshold,
+            None
+        );
+    }
+
+    #[test]
+    fn extraction_level_round_trips_names() {
+        for name in ExtractionLevel::NAMES {
+            let level: ExtractionLevel = name.parse().unwrap();
+            assert_eq!(level.to_string(), *name);
+        }
+        assert!("extreme".parse::<ExtractionLevel>().is_err());
+    }
+
+    #[test]
+    fn light_preset_skips_global_dedupe_and_truncation() {
+        let light = ExtractionConfig::preset(ExtractionLevel::Light);
+        assert!(!light.global_dedupe);
+        assert!(!light.truncate_large_blocks);
+
+        let aggressive = ExtractionConfig::preset(ExtractionLevel::Aggressive);
+        assert!(aggressive.global_dedupe && aggressive.truncate_large_blocks);
+        assert!(aggressive.max_block_size < ExtractionConfig::default().max_block_size);
+    }
+
+    #[test]
+    fn resolve_prefers_cli_level_over_config() {
+        let tuning = AnalysisExtractionConfig {
+            level: Some("light".to_string()),
+            max_block_size: Some(4096),
+            ..Default::default()
+        };
+
+        let from_config = ExtractionConfig::resolve(None, &tuning);
+        assert!(!from_config.global_dedupe);
+
+        let from_cli = ExtractionConfig::resolve(Some(ExtractionLevel::Aggressive), &tuning);
+        assert!(from_cli.global_dedupe);
+        assert_eq!(from_cli.max_line_repeats, 3);
+        // Explicit tuning still wins over the preset
+        assert_eq!(from_cli.max_block_size, 4096);
     }
 }
@@ -59,6 +59,12 @@ pub enum AnalysisError {
         errors: Vec<(usize, String)>,
     },
 
+    /// Some chunks failed and `--fail-on-error` asked for all or nothing.
+    ChunksFailed {
+        /// Failure summary with the failed time ranges
+        summary: String,
+    },
+
     /// Rate limited by the agent.
     RateLimited {
         /// Suggested retry delay (if provided)
@@ -77,6 +83,14 @@ pub enum AnalysisError {
 
     /// No content to analyze after extraction.
     NoContent,
+
+    /// The analysis was cancelled (Ctrl-C) before it finished.
+    Cancelled {
+        /// Chunks that finished before the cancellation
+        completed_chunks: usize,
+        /// Total number of chunks
+        total_chunks: usize,
+    },
 }
 
 impl fmt::Display for AnalysisError {
@@ -126,6 +140,13 @@ impl fmt::Display for AnalysisError {
                 }
                 Ok(())
             }
+            AnalysisError::ChunksFailed { summary } => {
+                write!(
+                    f,
+                    "{}\nNo markers were written because --fail-on-error is set.",
+                    summary
+                )
+            }
             AnalysisError::RateLimited {
                 retry_after,
                 message,
@@ -150,6 +171,16 @@ impl fmt::Display for AnalysisError {
                     "No content to analyze. The recording may be empty or contain only noise."
                 )
             }
+            AnalysisError::Cancelled {
+                completed_chunks,
+                total_chunks,
+            } => {
+                write!(
+                    f,
+                    "Analysis cancelled after {} of {} chunks. No markers were written (use --write-partial to keep them).",
+                    completed_chunks, total_chunks
+                )
+            }
         }
     }
 }
@@ -196,6 +227,10 @@ impl AnalysisError {
                 chunk_id,
                 reason: format!("IO error: {}", e),
             },
+            BackendError::Cancelled => AnalysisError::Cancelled {
+                completed_chunks: 0,
+                total_chunks: 0,
+            },
         }
     }
 
@@ -371,6 +406,31 @@ mod tests {
         assert!(msg.contains("file not found"));
     }
 
+    #[test]
+    fn cancelled_message() {
+        let err = AnalysisError::Cancelled {
+            completed_chunks: 2,
+            total_chunks: 5,
+        };
+
+        let msg = format!("{}", err);
+        assert!(msg.contains("cancelled after 2 of 5 chunks"));
+        assert!(msg.contains("--write-partial"));
+    }
+
+    #[test]
+    fn chunks_failed_message() {
+        let err = AnalysisError::ChunksFailed {
+            summary: "1 of 3 chunks failed:\n  - 60.0s - 120.0s: Agent timed out after 60s"
+                .to_string(),
+        };
+
+        let msg = format!("{}", err);
+        assert!(msg.starts_with("1 of 3 chunks failed:"));
+        assert!(msg.contains("60.0s - 120.0s"));
+        assert!(msg.ends_with("--fail-on-error is set."));
+    }
+
     #[test]
     fn no_content_message() {
         let err = AnalysisError::NoContent;
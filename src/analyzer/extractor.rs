@@ -3,15 +3,19 @@
 //! The [`ContentExtractor`] coordinates the transform pipeline and creates
 //! [`AnalysisSegment`]s from cleaned events.
 
-use crate::asciicast::{Event, Transform};
+use crate::asciicast::{Event, EventType, Transform};
 
+use super::chunk::TimeRange;
 use super::config::ExtractionConfig;
 use super::transforms::{
-    BlockTruncator, ContentCleaner, EmptyLineFilter, EventCoalescer, FileDumpFilter,
-    FilterEmptyEvents, GlobalDeduplicator, NormalizeWhitespace, SimilarityFilter,
+    BinaryOutputFilter, BlockTruncator, ContentCleaner, EmptyLineFilter, EventCoalescer,
+    FileDumpFilter, FilterEmptyEvents, GlobalDeduplicator, NormalizeWhitespace, SimilarityFilter,
     TerminalTransform, WindowedLineDeduplicator,
 };
-use super::types::{AnalysisContent, AnalysisSegment, ExtractionStats, TokenEstimator};
+use super::types::{AnalysisContent, AnalysisSegment, ExtractionStats, IdleGap, TokenEstimator};
+
+/// Start of the note that marks a collapsed idle gap, e.g. `(idle 300s)`.
+const IDLE_NOTE_PREFIX: &str = "(idle ";
 
 /// Extracts analysis content from asciicast events using the transform pipeline.
 pub struct ContentExtractor {
@@ -31,7 +35,15 @@ impl ContentExtractor {
         let original_bytes: usize = events.iter().map(|e| e.data.len()).sum();
         let original_event_count = events.len();
 
-        let stats = self.apply_transforms(events, cols, rows, original_bytes, original_event_count);
+        // Cut long pauses down before any time-based transform sees them
+        let idle_gaps = match self.config.idle_gap_threshold {
+            Some(threshold) => Self::collapse_idle_gaps(events, threshold),
+            None => Vec::new(),
+        };
+
+        let mut stats =
+            self.apply_transforms(events, cols, rows, original_bytes, original_event_count);
+        stats.idle_gaps_collapsed = idle_gaps.len();
 
         // Redistribute artificially concentrated time from the transform pipeline.
         // TerminalTransform accumulates time from filtered events and dumps it on the
@@ -40,7 +52,84 @@ impl ContentExtractor {
         Self::redistribute_time(events, self.config.segment_time_gap);
 
         // Create segments from events
-        self.create_segments(events, stats)
+        self.create_segments(events, stats, idle_gaps)
+    }
+
+    /// Extract analysis content from the events inside `range` only.
+    ///
+    /// Timestamps in the returned content are relative to `range.start`,
+    /// so callers must add it back to place markers in the full recording.
+    pub fn extract_range(
+        &self,
+        events: &mut Vec<Event>,
+        cols: usize,
+        rows: usize,
+        range: &TimeRange,
+    ) -> AnalysisContent {
+        Self::window_events(events, range);
+        self.extract(events, cols, rows)
+    }
+
+    /// Keep only the events inside `range`, re-based to start at zero.
+    ///
+    /// Resize events before the window are kept (at time zero) so the
+    /// terminal has the right size when the window starts.
+    fn window_events(events: &mut Vec<Event>, range: &TimeRange) {
+        let mut cumulative = 0.0;
+        let mut previous = range.start;
+        let mut windowed = Vec::with_capacity(events.len());
+
+        for mut event in events.drain(..) {
+            cumulative += event.time;
+            if cumulative >= range.end {
+                break;
+            }
+            if cumulative < range.start {
+                if event.event_type == EventType::Resize {
+                    event.time = 0.0;
+                    windowed.push(event);
+                }
+                continue;
+            }
+            event.time = cumulative - previous;
+            previous = cumulative;
+            windowed.push(event);
+        }
+
+        *events = windowed;
+    }
+
+    /// Shorten every gap longer than `threshold` to exactly `threshold`.
+    ///
+    /// An `(idle Ns)` note is inserted where each gap was. Notes are marker
+    /// events so every transform keeps them in place; [`Self::create_segments`]
+    /// turns them back into text. Returns the collapsed gaps with their
+    /// position on the new timeline, for mapping back to recording time.
+    fn collapse_idle_gaps(events: &mut Vec<Event>, threshold: f64) -> Vec<IdleGap> {
+        let mut gaps = Vec::new();
+        let mut cumulative = 0.0;
+        let mut collapsed = Vec::with_capacity(events.len());
+
+        for mut event in events.drain(..) {
+            if event.time > threshold {
+                cumulative += threshold;
+                gaps.push(IdleGap {
+                    at: cumulative,
+                    duration: event.time,
+                    removed: event.time - threshold,
+                });
+                collapsed.push(Event::marker(
+                    threshold,
+                    format!("{}{:.0}s)", IDLE_NOTE_PREFIX, event.time),
+                ));
+                event.time = 0.0;
+            }
+            cumulative += event.time;
+            collapsed.push(event);
+        }
+
+        *events = collapsed;
+        gaps
     }
 
     /// Apply all configured cleaning and deduplication transforms.
@@ -52,6 +141,10 @@ impl ContentExtractor {
         original_bytes: usize,
         original_event_count: usize,
     ) -> ExtractionStats {
+        // 0. Binary Output (before the terminal renders it as garbage)
+        let mut binary_filter = BinaryOutputFilter::new(self.config.binary_threshold);
+        binary_filter.transform(events);
+
         // 1. Terminal Rendering (Layout preservation, ANSI stripping, Redraw reduction)
         let mut term_transform = TerminalTransform::new(cols, rows);
         term_transform.transform(events);
@@ -99,6 +192,8 @@ impl ContentExtractor {
             lines_collapsed,
             blocks_truncated,
             bursts_collapsed,
+            idle_gaps_collapsed: 0, // Counted in extract(), before the transforms run
+            binary_events_replaced: binary_filter.replaced_count(),
             events_processed: original_event_count,
             events_retained: events.len(),
         }
@@ -124,10 +219,16 @@ impl ContentExtractor {
     }
 
     fn apply_global_dedupe(&self, events: &mut Vec<Event>) -> (usize, usize) {
-        let mut global_deduper =
-            GlobalDeduplicator::new(self.config.max_line_repeats, self.config.event_window_size);
-        global_deduper.transform(events);
-        global_deduper.stats()
+        if self.config.global_dedupe {
+            let mut global_deduper = GlobalDeduplicator::new(
+                self.config.max_line_repeats,
+                self.config.event_window_size,
+            );
+            global_deduper.transform(events);
+            global_deduper.stats()
+        } else {
+            (0, 0)
+        }
     }
 
     fn apply_file_dump_filter(&self, events: &mut Vec<Event>) -> usize {
@@ -221,7 +322,12 @@ impl ContentExtractor {
     ///
     /// Events in asciicast use relative timestamps (time since previous event).
     /// A new segment starts when an event's relative time exceeds the gap threshold.
-    fn create_segments(&self, events: &[Event], stats: ExtractionStats) -> AnalysisContent {
+    fn create_segments(
+        &self,
+        events: &[Event],
+        stats: ExtractionStats,
+        idle_gaps: Vec<IdleGap>,
+    ) -> AnalysisContent {
         let estimator = TokenEstimator::default();
         let mut segments = Vec::new();
         let mut current_segment_start = 0;
@@ -248,11 +354,15 @@ impl ContentExtractor {
                 segment_start_time = cumulative_time;
             }
 
-            if event.is_output() {
+            let is_idle_note = event.is_marker() && event.data.starts_with(IDLE_NOTE_PREFIX);
+            if event.is_output() || is_idle_note {
                 if current_segment_content.is_empty() {
                     segment_start_time = cumulative_time;
                 }
                 current_segment_content.push_str(&event.data);
+                if is_idle_note {
+                    current_segment_content.push('\n');
+                }
             }
         }
 
@@ -276,6 +386,7 @@ impl ContentExtractor {
             total_duration,
             total_tokens,
             stats,
+            idle_gaps,
         }
     }
 }
@@ -289,6 +400,171 @@ impl Default for ContentExtractor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::AnalysisExtractionConfig;
+
+    fn status_lines_kept(window_size: usize) -> usize {
+        let tuning = AnalysisExtractionConfig {
+            window_size: Some(window_size),
+            ..Default::default()
+        };
+        let extractor = ContentExtractor::new(ExtractionConfig::default().with_tuning(&tuning));
+        let mut events: Vec<Event> = (0..6)
+            .map(|i| Event::output(0.1, format!("step {}\nstatus: busy\n", i)))
+            .collect();
+
+        extractor.apply_windowed_dedupe(&mut events);
+
+        events
+            .iter()
+            .map(|e| e.data.matches("status: busy").count())
+            .sum()
+    }
+
+    #[test]
+    fn windowed_dedupe_window_size_is_configurable() {
+        // A window of two lines never sees a repeat; twelve lines sees them all
+        assert_eq!(status_lines_kept(2), 6);
+        assert_eq!(status_lines_kept(12), 1);
+    }
+
+    #[test]
+    fn extract_collapses_and_annotates_idle_gap() {
+        let extractor = ContentExtractor::default();
+        let mut events = vec![
+            Event::output(0.5, "$ cargo build\r\n"),
+            Event::output(0.5, "Finished dev profile\r\n"),
+            Event::output(300.0, "$ cargo test\r\n"), // 5 minutes of dead air
+            Event::output(0.5, "test result: ok\r\n"),
+        ];
+
+        let content = extractor.extract(&mut events, 80, 24);
+
+        let text = content.text();
+        let build = text.find("cargo build").unwrap();
+        let idle = text.find("(idle 300s)").unwrap();
+        let test = text.find("cargo test").unwrap();
+        assert!(build < idle && idle < test);
+        assert!(text.contains("Finished dev profile"));
+        assert!(text.contains("test result: ok"));
+
+        assert_eq!(content.stats.idle_gaps_collapsed, 1);
+        assert_eq!(content.idle_gaps.len(), 1);
+        assert!((content.total_duration - 61.5).abs() < 1e-6);
+        assert!((content.real_time(content.total_duration) - 301.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn extract_keeps_idle_time_when_disabled() {
+        let extractor = ContentExtractor::new(ExtractionConfig {
+            idle_gap_threshold: None,
+            ..Default::default()
+        });
+        let mut events = vec![
+            Event::output(0.5, "before\r\n"),
+            Event::output(300.0, "after\r\n"),
+        ];
+
+        let content = extractor.extract(&mut events, 80, 24);
+
+        assert!(!content.text().contains("(idle"));
+        assert!(content.idle_gaps.is_empty());
+        assert!((content.total_duration - 300.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn collapse_idle_gaps_records_position_on_new_timeline() {
+        let mut events = vec![
+            Event::output(1.0, "a"),
+            Event::output(100.0, "b"),
+            Event::output(2.0, "c"),
+            Event::output(70.0, "d"),
+        ];
+
+        let gaps = ContentExtractor::collapse_idle_gaps(&mut events, 60.0);
+
+        let data: Vec<&str> = events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, vec!["a", "(idle 100s)", "b", "c", "(idle 70s)", "d"]);
+        let times: Vec<f64> = events.iter().map(|e| e.time).collect();
+        assert_eq!(times, vec![1.0, 60.0, 0.0, 2.0, 60.0, 0.0]);
+        assert_eq!(
+            gaps,
+            vec![
+                IdleGap {
+                    at: 61.0,
+                    duration: 100.0,
+                    removed: 40.0,
+                },
+                IdleGap {
+                    at: 123.0,
+                    duration: 70.0,
+                    removed: 10.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn window_events_keeps_only_events_in_range() {
+        let mut events = vec![
+            Event::output(1.0, "before"),
+            Event::output(2.0, "first"),
+            Event::output(3.0, "second"),
+            Event::output(4.0, "after"),
+        ];
+
+        ContentExtractor::window_events(&mut events, &TimeRange::new(2.5, 10.0));
+
+        let data: Vec<&str> = events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, vec!["first", "second"]);
+        // "first" is at 3.0s absolute, 0.5s into the window
+        assert!((events[0].time - 0.5).abs() < 1e-9);
+        assert!((events[1].time - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn window_events_keeps_earlier_resizes() {
+        let mut events = vec![
+            Event::new(1.0, EventType::Resize, "120x40"),
+            Event::output(1.0, "before"),
+            Event::output(5.0, "inside"),
+        ];
+
+        ContentExtractor::window_events(&mut events, &TimeRange::new(4.0, f64::INFINITY));
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, EventType::Resize);
+        assert_eq!(events[0].time, 0.0);
+        assert_eq!(events[1].data, "inside");
+        assert!((events[1].time - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn extract_range_segments_fall_inside_window() {
+        let extractor = ContentExtractor::default();
+        let mut events = vec![
+            Event::output(1.0, "setup\n"),
+            Event::output(10.0, "cargo build\n"),
+            Event::output(1.0, "compiled\n"),
+            Event::output(10.0, "cargo test\n"),
+        ];
+        let range = TimeRange::new(5.0, 15.0);
+
+        let content = extractor.extract_range(&mut events, 80, 24, &range);
+
+        let all_content: String = content
+            .segments
+            .iter()
+            .map(|s| s.content.as_str())
+            .collect();
+        assert!(all_content.contains("cargo build"));
+        assert!(all_content.contains("compiled"));
+        assert!(!all_content.contains("setup"));
+        assert!(!all_content.contains("cargo test"));
+        for segment in &content.segments {
+            assert!(segment.start_time >= 0.0);
+            assert!(segment.end_time <= range.duration());
+        }
+    }
 
     #[test]
     fn extractor_creates_segments() {
@@ -334,6 +610,31 @@ mod tests {
         assert!(all_content.contains("hello"));
     }
 
+    #[test]
+    fn extractor_summarizes_binary_output() {
+        let extractor = ContentExtractor::default();
+        let bytes: Vec<u8> = (0..2048u32).map(|i| (i * 7919 % 251) as u8).collect();
+        let garbage = String::from_utf8_lossy(&bytes).into_owned();
+        let garbage_len = garbage.len();
+        let mut events = vec![
+            Event::output(0.1, "$ cat build/agr\n"),
+            Event::output(0.1, garbage),
+            Event::output(0.1, "$ ls\n"),
+        ];
+
+        let content = extractor.extract(&mut events, 80, 24);
+
+        let all_content: String = content
+            .segments
+            .iter()
+            .map(|s| s.content.as_str())
+            .collect();
+        assert_eq!(content.stats.binary_events_replaced, 1);
+        assert!(all_content.contains(&format!("<binary output: {} bytes>", garbage_len)));
+        assert!(!all_content.contains('\u{FFFD}'));
+        assert!(all_content.contains("$ ls"));
+    }
+
     #[test]
     fn extractor_estimates_tokens() {
         let extractor = ContentExtractor::default();
@@ -22,11 +22,14 @@
 //! - [`chunk`] - Token budget and chunking for parallel analysis
 //! - [`backend`] - Agent backend implementations (Strategy pattern)
 //! - [`worker`] - Parallel execution using Rayon
+//! - [`cancel`] - Stopping an analysis on Ctrl-C
 //! - [`progress`] - Progress reporting for analysis
 //! - [`result`] - Result aggregation and marker writing
+//! - [`review`] - Interactive approval of markers before writing
 //! - [`service`] - AnalyzerService facade (main entry point)
 
 pub mod backend;
+pub mod cancel;
 pub mod chunk;
 mod config;
 pub mod error;
@@ -34,6 +37,7 @@ mod extractor;
 pub mod progress;
 mod prompt;
 pub mod result;
+pub mod review;
 mod service;
 pub mod tracker;
 mod transforms;
@@ -49,23 +53,26 @@ pub use backend::{
 // Re-export chunk types (AgentType moved to backend)
 pub use chunk::{AnalysisChunk, ChunkCalculator, ChunkConfig, TimeRange, TokenBudget};
 
+// Re-export cancellation types
+pub use cancel::CancelToken;
+
 // Re-export other types
 pub use crate::config::{AgentAnalysisConfig, AnalysisConfig};
-pub use config::ExtractionConfig;
+pub use config::{ExtractionConfig, ExtractionLevel};
 pub use extractor::ContentExtractor;
 pub use progress::DefaultProgressReporter;
 pub use transforms::{
     ContentCleaner, DeduplicateProgressLines, FilterEmptyEvents, NormalizeWhitespace,
 };
-pub use types::{AnalysisContent, AnalysisSegment, ExtractionStats, TokenEstimator};
+pub use types::{AnalysisContent, AnalysisSegment, ExtractionStats, IdleGap, TokenEstimator};
 pub use worker::{
     ChunkResult, ParallelExecutor, ProgressReporter, RetryExecutor, WorkerConfig, WorkerScaler,
 };
 
 // Re-export result types
 pub use result::{
-    resolve_timestamp, AggregationReport, MarkerWriter, ResultAggregator, ValidatedMarker,
-    WriteReport,
+    resolve_timestamp, AggregationReport, ChunkSummary, MarkerWriter, ResultAggregator,
+    ValidatedMarker, WriteReport,
 };
 
 // Re-export error types
@@ -75,5 +82,6 @@ pub use error::AnalysisError;
 pub use tracker::{ChunkUsage, RetryCoordinator, RetryPolicy, TokenTracker, UsageSummary};
 
 // Re-export service types (main entry point)
-pub use prompt::build_analyze_prompt;
+pub use prompt::{build_analyze_prompt, build_summary_prompt, PromptTemplate};
+pub use review::{MarkerReview, ReviewDecision};
 pub use service::{AnalysisResult, AnalyzeOptions, AnalyzerService};
@@ -86,6 +86,19 @@ impl DefaultProgressReporter {
         }
     }
 
+    /// Report that all chunks have completed, with markers not yet written.
+    pub fn finish_found(&self, markers_found: usize) {
+        if self.show_output {
+            // Clear the progress line
+            eprint!("\r                                                    \r");
+            eprintln!(
+                "Found {} marker{}",
+                markers_found,
+                if markers_found == 1 { "" } else { "s" }
+            );
+        }
+    }
+
     /// Report partial success with some failures.
     pub fn finish_partial(
         &self,
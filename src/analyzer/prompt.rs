@@ -1,10 +1,14 @@
 //! Prompt building and response parsing for LLM analysis.
 //!
 //! Contains all template rendering, token math, and response extraction
-//! for the analyze, rename, and curate prompts.
+//! for the analyze, summarize, rename, and curate prompts.
+
+use std::path::Path;
+
+use anyhow::Context;
 
 use super::chunk::AnalysisChunk;
-use super::result::ValidatedMarker;
+use super::result::{ChunkSummary, ValidatedMarker};
 
 /// Maximum tokens for prompt content (safety net for edge cases).
 /// This should be higher than the chunk calculator's available_for_content()
@@ -20,24 +24,81 @@ const CHARS_PER_TOKEN: usize = 4;
 const TARGET_TOTAL_MARKERS_MIN: usize = 10;
 const TARGET_TOTAL_MARKERS_MAX: usize = 20;
 
+/// Built-in analysis prompt, used when no template is configured.
+const DEFAULT_ANALYZE_TEMPLATE: &str = include_str!("prompts/analyze.txt");
+
+/// A user-supplied analysis prompt from `[analysis] prompt_template`.
+///
+/// Placeholders are replaced per chunk:
+/// - `{content}` - cleaned session content (required)
+/// - `{duration}` - total recording duration in seconds (required)
+/// - `{chunk_start_time}`, `{chunk_end_time}` - chunk time range in seconds
+/// - `{min_markers}`, `{max_markers}` - how many markers to ask for
+///
+/// The built-in names `{cleaned_content}` and `{total_duration}` work too,
+/// so the default prompt can be copied as a starting point. The template
+/// must still ask for the JSON marker format of the default prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptTemplate {
+    text: String,
+}
+
+impl PromptTemplate {
+    /// Placeholders a template must contain, with their accepted aliases.
+    const REQUIRED: [(&'static str, &'static str); 2] = [
+        ("{content}", "{cleaned_content}"),
+        ("{duration}", "{total_duration}"),
+    ];
+
+    /// Create a template, checking that the required placeholders exist.
+    pub fn new(text: impl Into<String>) -> Result<Self, String> {
+        let text = text.into();
+        let missing: Vec<&str> = Self::REQUIRED
+            .iter()
+            .filter(|(name, alias)| !text.contains(name) && !text.contains(alias))
+            .map(|(name, _)| *name)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Prompt template is missing required placeholder(s): {}",
+                missing.join(", ")
+            ));
+        }
+        Ok(Self { text })
+    }
+
+    /// Read and check a template file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read prompt template: {}", path.display()))?;
+        Self::new(text).map_err(|e| anyhow::anyhow!("{} ({})", e, path.display()))
+    }
+
+    /// The template text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
 /// Build the analysis prompt for a chunk.
 ///
-/// Uses the template from `src/analyzer/prompts/analyze.txt`.
-/// If the resulting prompt exceeds token limits, the content is truncated
-/// with a warning logged.
+/// Uses `template` when given, else the built-in template from
+/// `src/analyzer/prompts/analyze.txt`. If the resulting prompt exceeds
+/// token limits, the content is truncated with a warning logged.
 ///
 /// # Arguments
 ///
 /// * `chunk` - The chunk to analyze
 /// * `total_duration` - Total duration of the recording
 /// * `total_chunks` - Total number of chunks (for calculating markers per chunk)
+/// * `template` - Custom prompt template, if configured
 pub fn build_analyze_prompt(
     chunk: &AnalysisChunk,
     total_duration: f64,
     total_chunks: usize,
+    template: Option<&PromptTemplate>,
 ) -> String {
-    // Include the template at compile time
-    const TEMPLATE: &str = include_str!("prompts/analyze.txt");
+    let template = template.map_or(DEFAULT_ANALYZE_TEMPLATE, PromptTemplate::text);
 
     // Calculate markers per chunk to achieve target total
     let (min_markers, max_markers) = calculate_markers_per_chunk(total_chunks);
@@ -45,16 +106,19 @@ pub fn build_analyze_prompt(
     // Validate and potentially truncate content if too large
     let content = truncate_content_if_needed(&chunk.text, chunk.estimated_tokens);
 
-    TEMPLATE
+    // Content goes in last so placeholders inside the recording stay as typed
+    template
+        .replace("{cleaned_content}", "{content}")
+        .replace("{total_duration}", "{duration}")
         .replace(
             "{chunk_start_time}",
             &format!("{:.1}", chunk.time_range.start),
         )
         .replace("{chunk_end_time}", &format!("{:.1}", chunk.time_range.end))
-        .replace("{total_duration}", &format!("{:.1}", total_duration))
+        .replace("{duration}", &format!("{:.1}", total_duration))
         .replace("{min_markers}", &min_markers.to_string())
         .replace("{max_markers}", &max_markers.to_string())
-        .replace("{cleaned_content}", &content)
+        .replace("{content}", &content)
 }
 
 /// Calculate how many markers to request per chunk.
@@ -96,6 +160,98 @@ fn truncate_content_if_needed(content: &str, estimated_tokens: usize) -> String
     format!("{}\n\n[Content truncated due to size limits]", truncated)
 }
 
+/// Build the summary prompt for a chunk.
+///
+/// Uses the template from `src/analyzer/prompts/summarize.txt`. For a
+/// single-chunk recording the response is the session summary; otherwise
+/// chunk summaries are combined with [`build_combine_summary_prompt`].
+///
+/// # Arguments
+///
+/// * `chunk` - The chunk to summarize
+/// * `total_duration` - Total duration of the recording
+/// * `total_chunks` - Total number of chunks
+pub fn build_summary_prompt(
+    chunk: &AnalysisChunk,
+    total_duration: f64,
+    total_chunks: usize,
+) -> String {
+    const TEMPLATE: &str = include_str!("prompts/summarize.txt");
+
+    let content = truncate_content_if_needed(&chunk.text, chunk.estimated_tokens);
+
+    TEMPLATE
+        .replace(
+            "{chunk_start_time}",
+            &format!("{:.1}", chunk.time_range.start),
+        )
+        .replace("{chunk_end_time}", &format!("{:.1}", chunk.time_range.end))
+        .replace("{total_duration}", &format!("{:.1}", total_duration))
+        .replace("{chunk_number}", &(chunk.id + 1).to_string())
+        .replace("{total_chunks}", &total_chunks.to_string())
+        .replace("{cleaned_content}", &content)
+}
+
+/// Build the prompt that combines chunk summaries into one session summary.
+pub(super) fn build_combine_summary_prompt(
+    summaries: &[ChunkSummary],
+    total_duration: f64,
+) -> String {
+    const TEMPLATE: &str = include_str!("prompts/summarize_combine.txt");
+
+    let chunk_summaries = summaries
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            format!(
+                "### Part {} ({:.1}s - {:.1}s)\n\n{}",
+                i + 1,
+                s.time_range.start,
+                s.time_range.end,
+                s.text.trim()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    TEMPLATE
+        .replace("{total_duration}", &format!("{:.1}", total_duration))
+        .replace(
+            "{duration_minutes}",
+            &format!("{:.1}", total_duration / 60.0),
+        )
+        .replace("{chunk_summaries}", &chunk_summaries)
+}
+
+/// Extract the summary text from an LLM summary response.
+///
+/// Handles Claude wrapper format and plain text. Unlike rename responses,
+/// the whole text is kept (summaries span several lines).
+pub(super) fn extract_summary_response(response: &str) -> Option<String> {
+    let text = unwrap_result_text(response).unwrap_or_else(|| response.trim().to_string());
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+/// Get the `result` text from a Claude wrapper response, if it is one.
+fn unwrap_result_text(response: &str) -> Option<String> {
+    let wrapper = serde_json::from_str::<serde_json::Value>(response.trim()).ok()?;
+    // Claude wrapper: {"type":"result","result":"the text",...}
+    if wrapper.get("type").and_then(|t| t.as_str()) != Some("result") {
+        return None;
+    }
+    let result = wrapper.get("result").and_then(|r| r.as_str())?.trim();
+    if result.is_empty() {
+        None
+    } else {
+        Some(result.to_string())
+    }
+}
+
 /// Build the rename prompt for filename suggestion.
 pub(super) fn build_rename_prompt(
     markers: &[ValidatedMarker],
@@ -133,22 +289,13 @@ pub(super) fn build_rename_prompt(
 ///
 /// Handles Claude wrapper format and plain text.
 pub(super) fn extract_rename_response(response: &str) -> Option<String> {
-    let trimmed = response.trim();
-
     // Try Claude wrapper format
-    if let Ok(wrapper) = serde_json::from_str::<serde_json::Value>(trimmed) {
-        // Claude wrapper: {"type":"result","result":"the-filename",...}
-        if wrapper.get("type").and_then(|t| t.as_str()) == Some("result") {
-            if let Some(result) = wrapper.get("result").and_then(|r| r.as_str()) {
-                let name = result.trim();
-                if !name.is_empty() {
-                    return Some(name.to_string());
-                }
-            }
-        }
+    if let Some(name) = unwrap_result_text(response) {
+        return Some(name);
     }
 
     // Plain text response - take first line
+    let trimmed = response.trim();
     let first_line = trimmed.lines().next()?.trim();
     if !first_line.is_empty() {
         Some(first_line.to_string())
@@ -205,7 +352,7 @@ mod tests {
             }],
         );
 
-        let prompt = build_analyze_prompt(&chunk, 120.0, 3); // 3 chunks total
+        let prompt = build_analyze_prompt(&chunk, 120.0, 3, None); // 3 chunks total
 
         assert!(prompt.contains("10.0s - 50.0s"));
         assert!(prompt.contains("120.0s"));
@@ -218,4 +365,157 @@ mod tests {
         // With 3 chunks, target 10-20 markers total = 3-6 per chunk
         assert!(prompt.contains("3-6"));
     }
+
+    fn test_chunk(content: &str) -> AnalysisChunk {
+        AnalysisChunk::new(
+            0,
+            TimeRange::new(10.0, 50.0),
+            vec![crate::analyzer::types::AnalysisSegment {
+                start_time: 10.0,
+                end_time: 50.0,
+                content: content.to_string(),
+                estimated_tokens: 100,
+                event_range: (0, 10),
+            }],
+        )
+    }
+
+    #[test]
+    fn custom_template_replaces_placeholders() {
+        let template = PromptTemplate::new(
+            "Flag security issues first.\n\
+             Session of {duration}s, chunk {chunk_start_time}-{chunk_end_time}s, \
+             {min_markers}-{max_markers} markers:\n{content}",
+        )
+        .unwrap();
+
+        let prompt = build_analyze_prompt(&test_chunk("$ curl | sh"), 120.0, 1, Some(&template));
+
+        assert_eq!(
+            prompt,
+            "Flag security issues first.\n\
+             Session of 120.0s, chunk 10.0-50.0s, 10-20 markers:\n$ curl | sh"
+        );
+    }
+
+    #[test]
+    fn custom_template_accepts_builtin_placeholder_names() {
+        let template = PromptTemplate::new("{total_duration}: {cleaned_content}").unwrap();
+        let prompt = build_analyze_prompt(&test_chunk("ok"), 30.0, 1, Some(&template));
+        assert_eq!(prompt, "30.0: ok");
+    }
+
+    #[test]
+    fn placeholders_inside_content_are_left_alone() {
+        let template = PromptTemplate::new("{duration}\n{content}").unwrap();
+        let prompt = build_analyze_prompt(&test_chunk("echo {duration}"), 5.0, 1, Some(&template));
+        assert_eq!(prompt, "5.0\necho {duration}");
+    }
+
+    #[test]
+    fn no_template_falls_back_to_builtin_prompt() {
+        let chunk = test_chunk("Test content here");
+        let prompt = build_analyze_prompt(&chunk, 120.0, 1, None);
+
+        assert!(prompt.starts_with("You are analyzing a terminal session recording"));
+        assert!(prompt.contains("10.0s - 50.0s (within full recording of 120.0s)"));
+        assert!(prompt.contains("Test content here"));
+        assert!(!prompt.contains("{content}"));
+    }
+
+    #[test]
+    fn template_without_required_placeholders_is_rejected() {
+        assert_eq!(
+            PromptTemplate::new("Analyze this session"),
+            Err(
+                "Prompt template is missing required placeholder(s): {content}, {duration}"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            PromptTemplate::new("{content}"),
+            Err("Prompt template is missing required placeholder(s): {duration}".to_string())
+        );
+        assert!(PromptTemplate::new(DEFAULT_ANALYZE_TEMPLATE).is_ok());
+    }
+
+    #[test]
+    fn load_reads_and_checks_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.txt");
+        std::fs::write(&good, "{duration} {content}").unwrap();
+        assert_eq!(
+            PromptTemplate::load(&good).unwrap().text(),
+            "{duration} {content}"
+        );
+
+        let bad = dir.path().join("bad.txt");
+        std::fs::write(&bad, "{content}").unwrap();
+        let err = PromptTemplate::load(&bad).unwrap_err().to_string();
+        assert!(err.contains("{duration}"));
+        assert!(err.contains("bad.txt"));
+
+        assert!(PromptTemplate::load(&dir.path().join("missing.txt")).is_err());
+    }
+
+    #[test]
+    fn build_summary_prompt_substitutes_values() {
+        let chunk = AnalysisChunk::new(
+            1,
+            TimeRange::new(60.0, 120.0),
+            vec![crate::analyzer::types::AnalysisSegment {
+                start_time: 60.0,
+                end_time: 120.0,
+                content: "$ cargo test".to_string(),
+                estimated_tokens: 10,
+                event_range: (0, 1),
+            }],
+        );
+
+        let prompt = build_summary_prompt(&chunk, 180.0, 3);
+
+        assert!(prompt.contains("60.0s - 120.0s"));
+        assert!(prompt.contains("180.0s"));
+        assert!(prompt.contains("part 2 of 3"));
+        assert!(prompt.contains("$ cargo test"));
+        assert!(prompt.contains("3-5 sentence"));
+    }
+
+    #[test]
+    fn build_combine_summary_prompt_lists_parts_in_order() {
+        let summaries = vec![
+            ChunkSummary {
+                time_range: TimeRange::new(0.0, 60.0),
+                text: "The agent planned the parser.\n".to_string(),
+            },
+            ChunkSummary {
+                time_range: TimeRange::new(60.0, 120.0),
+                text: "Tests passed.".to_string(),
+            },
+        ];
+
+        let prompt = build_combine_summary_prompt(&summaries, 120.0);
+
+        let first = prompt
+            .find("### Part 1 (0.0s - 60.0s)\n\nThe agent planned the parser.")
+            .unwrap();
+        let second = prompt
+            .find("### Part 2 (60.0s - 120.0s)\n\nTests passed.")
+            .unwrap();
+        assert!(first < second);
+        assert!(prompt.contains("2.0 minutes"));
+    }
+
+    #[test]
+    fn extract_summary_response_keeps_all_lines() {
+        assert_eq!(
+            extract_summary_response("  First sentence.\nSecond sentence.\n"),
+            Some("First sentence.\nSecond sentence.".to_string())
+        );
+        assert_eq!(
+            extract_summary_response(r#"{"type":"result","result":" Wrapped summary. "}"#),
+            Some("Wrapped summary.".to_string())
+        );
+        assert_eq!(extract_summary_response("   "), None);
+    }
 }
@@ -5,18 +5,31 @@
 //! - `ResultAggregator` - Builder pattern for collecting and processing chunk results
 //! - `MarkerWriter` - integrates with existing `MarkerManager` to write markers
 //!
+//! # Generated Markers
+//!
+//! Markers written by analysis are recognized by their category prefix
+//! (e.g. `[SUCCESS] Tests pass`). Writing a new set replaces the markers of
+//! a previous analysis; markers without such a prefix, like those added with
+//! `agr marker add`, are left alone.
+//!
 //! # Deduplication Algorithm
 //!
 //! Overlapping chunks may produce duplicate markers. The deduplication algorithm:
 //! 1. Sort all markers by timestamp
-//! 2. For markers within 2.0s window with same category, keep only the first
+//! 2. Merge markers within the merge epsilon (1.0s) whose labels are highly
+//!    similar, whatever their category, keeping the better-phrased label
+//! 3. For markers within the dedup window with same category, keep only the first
 //!
 //! # Timestamp Resolution
 //!
 //! LLM markers use relative timestamps (seconds from chunk start).
 //! Resolution formula: `absolute = chunk.time_range.start + marker.relative_timestamp`
+//!
+//! Resolved timestamps are fitted into `[0, duration]` (see
+//! [`clamp_timestamp`]) both when aggregating and when writing, so an
+//! out-of-range marker never reaches the file.
 
-use crate::analyzer::backend::{MarkerCategory, RawMarker};
+use crate::analyzer::backend::MarkerCategory;
 use crate::analyzer::chunk::TimeRange;
 use crate::analyzer::worker::ChunkResult;
 use crate::asciicast::{AsciicastFile, MarkerManager};
@@ -33,6 +46,39 @@ const DEDUP_WINDOW_MIN_SECS: f64 = 5.0;
 /// Maximum absolute dedup window in seconds (cap for very long recordings).
 const DEDUP_WINDOW_MAX_SECS: f64 = 60.0;
 
+/// Default time window for merging markers with similar labels (seconds).
+const MERGE_EPSILON_SECS: f64 = 1.0;
+
+/// Minimum word overlap (Jaccard index) for two labels to count as the same.
+const LABEL_SIMILARITY_THRESHOLD: f64 = 0.6;
+
+/// How far past the recording end a marker is clamped rather than dropped (seconds).
+const END_CLAMP_TOLERANCE_SECS: f64 = 10.0;
+
+/// Fit a marker timestamp into `[0, duration]`.
+///
+/// Negative and non-finite timestamps are dropped (`None`). Timestamps up to
+/// 10s past the end are clamped to the end, since agents tend to round the
+/// last moments of a recording up; anything later is dropped.
+pub fn clamp_timestamp(timestamp: f64, duration: f64) -> Option<f64> {
+    if !timestamp.is_finite() || timestamp < 0.0 {
+        return None;
+    }
+    if timestamp <= duration {
+        return Some(timestamp);
+    }
+    (timestamp <= duration + END_CLAMP_TOLERANCE_SECS).then_some(duration)
+}
+
+/// Every category, for recognizing the label prefix of generated markers.
+const MARKER_CATEGORIES: [MarkerCategory; 5] = [
+    MarkerCategory::Planning,
+    MarkerCategory::Design,
+    MarkerCategory::Implementation,
+    MarkerCategory::Success,
+    MarkerCategory::Failure,
+];
+
 /// A validated marker with absolute timestamp.
 ///
 /// Created from `RawMarker` after timestamp resolution and validation.
@@ -56,6 +102,18 @@ impl ValidatedMarker {
         }
     }
 
+    /// Create a marker fitted into `[0, duration]`.
+    ///
+    /// Returns `None` if the timestamp is dropped by [`clamp_timestamp`].
+    pub fn bounded(
+        timestamp: f64,
+        label: String,
+        category: MarkerCategory,
+        duration: f64,
+    ) -> Option<Self> {
+        clamp_timestamp(timestamp, duration).map(|ts| Self::new(ts, label, category))
+    }
+
     /// Format the marker label with category prefix.
     ///
     /// Format: "[CATEGORY] description"
@@ -63,17 +121,38 @@ impl ValidatedMarker {
         format!("[{}] {}", category, description)
     }
 
+    /// Whether a marker label was written by analysis ("[CATEGORY] description").
+    pub fn is_generated_label(label: &str) -> bool {
+        MARKER_CATEGORIES.iter().any(|category| {
+            label
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_prefix(category.to_string().as_str()))
+                .is_some_and(|rest| rest.starts_with("] "))
+        })
+    }
+
     /// Get the marker text for writing to cast file.
     pub fn to_marker_text(&self) -> String {
         self.label.clone()
     }
 }
 
+/// Prose summary of one chunk, used to build the session summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkSummary {
+    /// Time range the summary covers (seconds from recording start)
+    pub time_range: TimeRange,
+    /// Summary text returned by the agent
+    pub text: String,
+}
+
 /// Information about a failed chunk for error reporting.
 #[derive(Debug, Clone)]
 pub struct FailedChunkInfo {
     /// Chunk ID
     pub chunk_id: usize,
+    /// Time range the chunk covered
+    pub time_range: TimeRange,
     /// Error message describing the failure
     pub error: String,
 }
@@ -85,16 +164,55 @@ pub struct AggregationReport {
     pub total_collected: usize,
     /// Markers filtered as invalid
     pub invalid_filtered: usize,
+    /// Markers moved back to the recording end
+    pub clamped: usize,
+    /// Invalid markers that lay outside the recording
+    pub out_of_range: usize,
     /// Markers removed by deduplication
     pub duplicates_removed: usize,
     /// Final marker count after processing
     pub final_count: usize,
+    /// Number of chunk results aggregated
+    pub total_chunks: usize,
     /// Failed chunk IDs (for backward compatibility)
     pub failed_chunks: Vec<usize>,
     /// Detailed failure information for each failed chunk
     pub failed_chunk_details: Vec<FailedChunkInfo>,
 }
 
+impl AggregationReport {
+    /// Whether at least one chunk failed.
+    pub fn has_failures(&self) -> bool {
+        !self.failed_chunk_details.is_empty()
+    }
+
+    /// Whether every chunk failed (and there was at least one).
+    pub fn all_failed(&self) -> bool {
+        self.total_chunks > 0 && self.failed_chunk_details.len() == self.total_chunks
+    }
+
+    /// Warning text listing failed chunks, or `None` if all succeeded.
+    ///
+    /// Reads "N of M chunks failed:" followed by one
+    /// `  - start - end: error` line per failed chunk, in time order.
+    pub fn failure_summary(&self) -> Option<String> {
+        if !self.has_failures() {
+            return None;
+        }
+        let mut failed: Vec<&FailedChunkInfo> = self.failed_chunk_details.iter().collect();
+        failed.sort_by(|a, b| a.time_range.start.total_cmp(&b.time_range.start));
+
+        let mut summary = format!("{} of {} chunks failed:", failed.len(), self.total_chunks);
+        for info in failed {
+            summary.push_str(&format!(
+                "\n  - {:.1}s - {:.1}s: {}",
+                info.time_range.start, info.time_range.end, info.error
+            ));
+        }
+        Some(summary)
+    }
+}
+
 /// Result aggregator for collecting and processing chunk results.
 ///
 /// Uses Builder pattern to configure aggregation behavior.
@@ -102,6 +220,8 @@ pub struct AggregationReport {
 pub struct ResultAggregator {
     /// Time window for deduplication
     dedup_window: f64,
+    /// Time window for merging markers with similar labels
+    merge_epsilon: f64,
     /// Maximum timestamp (recording duration)
     max_timestamp: f64,
 }
@@ -118,6 +238,7 @@ impl ResultAggregator {
 
         Self {
             dedup_window: window,
+            merge_epsilon: MERGE_EPSILON_SECS,
             max_timestamp,
         }
     }
@@ -128,13 +249,20 @@ impl ResultAggregator {
         self
     }
 
+    /// Set custom window for merging markers with similar labels.
+    pub fn with_merge_epsilon(mut self, epsilon: f64) -> Self {
+        self.merge_epsilon = epsilon;
+        self
+    }
+
     /// Aggregate results from multiple chunks.
     ///
     /// This method:
-    /// 1. Collects markers from all successful chunks
+    /// 1. Collects markers from all successful chunks; failed chunks are
+    ///    recorded in the report and contribute no markers
     /// 2. Resolves relative timestamps to absolute
-    /// 3. Validates markers (non-empty labels, in-range timestamps)
-    /// 4. Deduplicates markers within time window
+    /// 3. Validates markers (non-empty labels, timestamps fitted into the recording)
+    /// 4. Merges near-identical markers and deduplicates within time window
     /// 5. Sorts by timestamp
     pub fn aggregate(
         &self,
@@ -142,6 +270,7 @@ impl ResultAggregator {
     ) -> (Vec<ValidatedMarker>, AggregationReport) {
         let mut report = AggregationReport::default();
         let mut all_markers = Vec::new();
+        report.total_chunks = results.len();
 
         // Collect markers from successful chunks
         for result in results {
@@ -153,21 +282,34 @@ impl ResultAggregator {
                         // Resolve timestamp
                         let absolute_ts = resolve_timestamp(&result.time_range, raw.timestamp);
 
-                        // Validate marker
-                        if !self.is_valid(&raw, absolute_ts) {
-                            report.invalid_filtered += 1;
-                            continue;
-                        }
-
-                        // Create validated marker with formatted label
+                        // Validate marker, fitting its timestamp into the recording
                         let label = ValidatedMarker::format_label(raw.category, &raw.label);
-                        all_markers.push(ValidatedMarker::new(absolute_ts, label, raw.category));
+                        let marker = ValidatedMarker::bounded(
+                            absolute_ts,
+                            label,
+                            raw.category,
+                            self.max_timestamp,
+                        );
+                        match marker {
+                            _ if raw.label.trim().is_empty() => report.invalid_filtered += 1,
+                            Some(marker) => {
+                                if marker.timestamp != absolute_ts {
+                                    report.clamped += 1;
+                                }
+                                all_markers.push(marker);
+                            }
+                            None => {
+                                report.invalid_filtered += 1;
+                                report.out_of_range += 1;
+                            }
+                        }
                     }
                 }
                 Err(e) => {
                     report.failed_chunks.push(result.chunk_id);
                     report.failed_chunk_details.push(FailedChunkInfo {
                         chunk_id: result.chunk_id,
+                        time_range: result.time_range,
                         error: format!("{}", e),
                     });
                 }
@@ -183,31 +325,41 @@ impl ResultAggregator {
 
         // Deduplicate
         let before_dedup = all_markers.len();
-        let deduplicated = self.deduplicate(all_markers);
+        let deduplicated = self.deduplicate(self.merge_similar(all_markers));
         report.duplicates_removed = before_dedup - deduplicated.len();
         report.final_count = deduplicated.len();
 
         (deduplicated, report)
     }
 
-    /// Check if a marker is valid.
-    fn is_valid(&self, raw: &RawMarker, absolute_ts: f64) -> bool {
-        // Empty labels are invalid
-        if raw.label.trim().is_empty() {
-            return false;
-        }
-
-        // Negative timestamps are invalid
-        if absolute_ts < 0.0 {
-            return false;
-        }
+    /// Merge markers that describe the same moment in different words.
+    ///
+    /// Markers must already be sorted by timestamp. A marker within the merge
+    /// epsilon of a kept marker with a similar label is folded into it: the
+    /// earlier timestamp stays, and the better-phrased label (with its
+    /// category) wins.
+    fn merge_similar(&self, markers: Vec<ValidatedMarker>) -> Vec<ValidatedMarker> {
+        let mut result: Vec<ValidatedMarker> = Vec::with_capacity(markers.len());
 
-        // Timestamps beyond recording duration are invalid
-        if absolute_ts > self.max_timestamp {
-            return false;
+        for marker in markers {
+            let similar = result
+                .iter_mut()
+                .rev()
+                .take_while(|m| marker.timestamp - m.timestamp <= self.merge_epsilon)
+                .find(|m| labels_similar(&m.label, &marker.label));
+
+            match similar {
+                Some(kept) => {
+                    if phrasing_score(&marker.label) > phrasing_score(&kept.label) {
+                        kept.label = marker.label;
+                        kept.category = marker.category;
+                    }
+                }
+                None => result.push(marker),
+            }
         }
 
-        true
+        result
     }
 
     /// Deduplicate markers within time window.
@@ -242,9 +394,45 @@ impl ResultAggregator {
     }
 }
 
+/// Lowercase words of a label's description (category prefix removed).
+fn label_words(label: &str) -> std::collections::BTreeSet<String> {
+    let description = match label.strip_prefix('[') {
+        Some(rest) => rest.split_once("] ").map_or(label, |(_, text)| text),
+        None => label,
+    };
+    description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether two labels share most of their words.
+fn labels_similar(a: &str, b: &str) -> bool {
+    let (a, b) = (label_words(a), label_words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return false;
+    }
+    let shared = a.intersection(&b).count();
+    shared as f64 / union as f64 >= LABEL_SIMILARITY_THRESHOLD
+}
+
+/// How informative a label reads: its number of distinct words, with
+/// truncated labels (ending in an ellipsis) ranked below complete ones.
+fn phrasing_score(label: &str) -> (bool, usize) {
+    let trimmed = label.trim_end();
+    let complete = !(trimmed.ends_with("...") || trimmed.ends_with('\u{2026}'));
+    (complete, label_words(label).len())
+}
+
 /// Resolve relative timestamp to absolute.
 ///
 /// Formula: `absolute = chunk.time_range.start + relative`
+///
+/// The result is on the extraction timeline, where idle gaps are collapsed;
+/// use [`AnalysisContent::real_time`](super::AnalysisContent::real_time) to
+/// map it to recording time.
 pub fn resolve_timestamp(time_range: &TimeRange, relative: f64) -> f64 {
     time_range.start + relative
 }
@@ -254,6 +442,12 @@ pub fn resolve_timestamp(time_range: &TimeRange, relative: f64) -> f64 {
 pub struct WriteReport {
     /// Number of markers written
     pub markers_written: usize,
+    /// Markers moved back to the recording end before writing
+    pub markers_clamped: usize,
+    /// Markers not written because they lie outside the recording
+    pub markers_dropped: usize,
+    /// Markers from a previous analysis that were removed
+    pub markers_replaced: usize,
     /// Whether existing markers were found
     pub had_existing_markers: bool,
     /// Number of existing markers
@@ -272,8 +466,41 @@ impl MarkerWriter {
         (count > 0, count)
     }
 
+    /// Count existing markers as (from a previous analysis, added by hand).
+    pub fn count_existing_markers(cast: &AsciicastFile) -> (usize, usize) {
+        let (generated, manual): (Vec<_>, Vec<_>) = cast
+            .events
+            .iter()
+            .filter(|e| e.is_marker())
+            .partition(|e| ValidatedMarker::is_generated_label(&e.data));
+        (generated.len(), manual.len())
+    }
+
+    /// Fit markers into `[0, duration]` in place (see [`clamp_timestamp`]).
+    ///
+    /// Returns the number of markers clamped and dropped.
+    pub fn fit_markers(markers: &mut Vec<ValidatedMarker>, duration: f64) -> (usize, usize) {
+        let before = markers.len();
+        let mut clamped = 0;
+        markers.retain_mut(|marker| match clamp_timestamp(marker.timestamp, duration) {
+            Some(ts) => {
+                if ts != marker.timestamp {
+                    marker.timestamp = ts;
+                    clamped += 1;
+                }
+                true
+            }
+            None => false,
+        });
+        (clamped, before - markers.len())
+    }
+
     /// Write validated markers to a cast file in memory.
     ///
+    /// Markers from a previous analysis are removed first; markers added by
+    /// hand are kept. Markers outside the recording are clamped or dropped,
+    /// so the recording never grows past its last event.
+    ///
     /// # Arguments
     ///
     /// * `cast` - The cast file to modify
@@ -287,15 +514,22 @@ impl MarkerWriter {
         markers: &[ValidatedMarker],
     ) -> WriteReport {
         let (had_existing, existing_count) = Self::has_existing_markers(cast);
+        let replaced =
+            MarkerManager::remove_markers_from_cast(cast, ValidatedMarker::is_generated_label);
+        let mut markers = markers.to_vec();
+        let (clamped, dropped) = Self::fit_markers(&mut markers, cast.duration());
 
         // Write each marker using MarkerManager
-        for marker in markers {
+        for marker in &markers {
             // MarkerManager::add_marker_to_cast handles positioning
             let _ = MarkerManager::add_marker_to_cast(cast, marker.timestamp, &marker.label);
         }
 
         WriteReport {
             markers_written: markers.len(),
+            markers_clamped: clamped,
+            markers_dropped: dropped,
+            markers_replaced: replaced,
             had_existing_markers: had_existing,
             existing_marker_count: existing_count,
         }
@@ -325,7 +559,7 @@ impl MarkerWriter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analyzer::backend::BackendError;
+    use crate::analyzer::backend::{BackendError, RawMarker};
     use crate::asciicast::{Event, Header};
     use std::time::Duration;
 
@@ -560,6 +794,79 @@ mod tests {
         assert!(report.failed_chunk_details[0].error.contains("timed out"));
     }
 
+    #[test]
+    fn aggregate_mixed_results_keeps_only_successful_markers() {
+        let aggregator = ResultAggregator::new(1000.0);
+        let marker = |timestamp: f64, label: &str| RawMarker {
+            timestamp,
+            label: label.to_string(),
+            category: MarkerCategory::Implementation,
+        };
+
+        let results = vec![
+            ChunkResult::success(0, TimeRange::new(0.0, 100.0), vec![marker(10.0, "Parser")]),
+            ChunkResult::failure(
+                2,
+                TimeRange::new(200.0, 300.0),
+                BackendError::Timeout(Duration::from_secs(60)),
+            ),
+            ChunkResult::failure(
+                1,
+                TimeRange::new(100.0, 200.0),
+                BackendError::JsonExtraction {
+                    response: "I could not find any markers".to_string(),
+                },
+            ),
+            ChunkResult::success(3, TimeRange::new(300.0, 400.0), vec![marker(5.0, "Lexer")]),
+        ];
+
+        let (markers, report) = aggregator.aggregate(results);
+
+        let timestamps: Vec<f64> = markers.iter().map(|m| m.timestamp).collect();
+        assert_eq!(timestamps, vec![10.0, 305.0]);
+        assert!(report.has_failures());
+        assert!(!report.all_failed());
+        assert_eq!(report.total_chunks, 4);
+
+        let summary = report.failure_summary().unwrap();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "2 of 4 chunks failed:");
+        assert_eq!(
+            lines[1],
+            "  - 100.0s - 200.0s: Failed to extract JSON from response"
+        );
+        assert!(lines[2].starts_with("  - 200.0s - 300.0s: "));
+        assert!(lines[2].contains("timed out"));
+    }
+
+    #[test]
+    fn aggregate_without_failures_has_no_failure_summary() {
+        let aggregator = ResultAggregator::new(1000.0);
+        let results = vec![ChunkResult::success(0, TimeRange::new(0.0, 100.0), vec![])];
+
+        let (_, report) = aggregator.aggregate(results);
+
+        assert!(!report.has_failures());
+        assert!(!report.all_failed());
+        assert_eq!(report.failure_summary(), None);
+    }
+
+    #[test]
+    fn aggregate_all_failed_is_reported() {
+        let aggregator = ResultAggregator::new(1000.0);
+        let results = vec![ChunkResult::failure(
+            0,
+            TimeRange::new(0.0, 100.0),
+            BackendError::Timeout(Duration::from_secs(60)),
+        )];
+
+        let (markers, report) = aggregator.aggregate(results);
+
+        assert!(markers.is_empty());
+        assert!(report.all_failed());
+    }
+
     // ============================================
     // Deduplication Tests
     // ============================================
@@ -696,6 +1003,132 @@ mod tests {
         assert_eq!(report.duplicates_removed, 1);
     }
 
+    fn raw(timestamp: f64, label: &str, category: MarkerCategory) -> RawMarker {
+        RawMarker {
+            timestamp,
+            label: label.to_string(),
+            category,
+        }
+    }
+
+    #[test]
+    fn merge_similar_markers_across_chunk_boundary() {
+        // Window 0 disables the same-category dedup, isolating the merge
+        let aggregator = ResultAggregator::new(1000.0).with_dedup_window(0.0);
+
+        // Chunks 0-100 and 90-190 both see the test run at ~95s
+        let results = vec![
+            ChunkResult::success(
+                0,
+                TimeRange::new(0.0, 100.0),
+                vec![
+                    raw(50.0, "Started refactor", MarkerCategory::Planning),
+                    raw(95.0, "Tests pass after fix", MarkerCategory::Success),
+                ],
+            ),
+            ChunkResult::success(
+                1,
+                TimeRange::new(90.0, 190.0),
+                vec![
+                    raw(
+                        5.6,
+                        "All tests pass after auth fix",
+                        MarkerCategory::Success,
+                    ),
+                    raw(60.0, "Deployed", MarkerCategory::Success),
+                ],
+            ),
+        ];
+
+        let (markers, report) = aggregator.aggregate(results);
+
+        assert_eq!(markers.len(), 3);
+        assert_eq!(report.duplicates_removed, 1);
+        assert_eq!(markers[1].label, "[SUCCESS] All tests pass after auth fix");
+        // The earlier timestamp is kept
+        assert!((markers[1].timestamp - 95.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn merge_keeps_better_label_and_its_category() {
+        let aggregator = ResultAggregator::new(1000.0).with_dedup_window(0.0);
+        let markers = vec![
+            raw(
+                20.0,
+                "Build failed on missing import",
+                MarkerCategory::Failure,
+            ),
+            raw(
+                20.4,
+                "Build failed on missing import in lib.rs",
+                MarkerCategory::Design,
+            ),
+            raw(
+                20.8,
+                "Build failed on missing import in...",
+                MarkerCategory::Failure,
+            ),
+        ];
+
+        let chunk_result = ChunkResult::success(0, TimeRange::new(0.0, 100.0), markers);
+        let (markers, _) = aggregator.aggregate(vec![chunk_result]);
+
+        assert_eq!(markers.len(), 1);
+        assert_eq!(
+            markers[0].label,
+            "[DESIGN] Build failed on missing import in lib.rs"
+        );
+        assert_eq!(markers[0].category, MarkerCategory::Design);
+    }
+
+    #[test]
+    fn no_merge_for_different_labels_or_outside_epsilon() {
+        let aggregator = ResultAggregator::new(1000.0)
+            .with_dedup_window(0.0)
+            .with_merge_epsilon(1.0);
+        let markers = vec![
+            raw(10.0, "Tests pass", MarkerCategory::Success),
+            raw(10.5, "Switched to sqlite backend", MarkerCategory::Success),
+            raw(12.0, "Tests pass", MarkerCategory::Success),
+        ];
+
+        let chunk_result = ChunkResult::success(0, TimeRange::new(0.0, 100.0), markers);
+        let (markers, report) = aggregator.aggregate(vec![chunk_result]);
+
+        assert_eq!(markers.len(), 3);
+        assert_eq!(report.duplicates_removed, 0);
+    }
+
+    #[test]
+    fn custom_merge_epsilon() {
+        let aggregator = ResultAggregator::new(1000.0)
+            .with_dedup_window(0.0)
+            .with_merge_epsilon(3.0);
+        let markers = vec![
+            raw(10.0, "Tests pass", MarkerCategory::Success),
+            raw(12.5, "All tests pass", MarkerCategory::Success),
+        ];
+
+        let chunk_result = ChunkResult::success(0, TimeRange::new(0.0, 100.0), markers);
+        let (markers, _) = aggregator.aggregate(vec![chunk_result]);
+
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].label, "[SUCCESS] All tests pass");
+    }
+
+    #[test]
+    fn label_similarity_ignores_case_punctuation_and_prefix() {
+        assert!(labels_similar(
+            "[SUCCESS] Tests pass!",
+            "[FAILURE] tests: PASS"
+        ));
+        assert!(!labels_similar(
+            "[SUCCESS] Tests pass",
+            "[SUCCESS] Lint fails"
+        ));
+        assert!(!labels_similar("[SUCCESS] ", "[SUCCESS] "));
+    }
+
     // ============================================
     // Invalid Marker Filtering Tests
     // ============================================
@@ -771,6 +1204,48 @@ mod tests {
         assert_eq!(report.invalid_filtered, 2);
     }
 
+    #[test]
+    fn clamp_timestamp_policy() {
+        assert_eq!(clamp_timestamp(-1.0, 100.0), None);
+        assert_eq!(clamp_timestamp(f64::NAN, 100.0), None);
+        assert_eq!(clamp_timestamp(0.0, 100.0), Some(0.0));
+        assert_eq!(clamp_timestamp(42.0, 100.0), Some(42.0));
+        assert_eq!(clamp_timestamp(105.0, 100.0), Some(100.0));
+        assert_eq!(clamp_timestamp(110.0, 100.0), Some(100.0));
+        assert_eq!(clamp_timestamp(110.5, 100.0), None);
+    }
+
+    #[test]
+    fn aggregate_drops_negative_and_clamps_slightly_late_markers() {
+        let aggregator = ResultAggregator::new(100.0).with_dedup_window(0.0);
+
+        let chunk_markers = vec![
+            raw(-1.0, "Before start", MarkerCategory::Planning),
+            raw(50.0, "Valid", MarkerCategory::Implementation),
+            raw(105.0, "Final result", MarkerCategory::Success),
+        ];
+
+        let chunk_result = ChunkResult::success(0, TimeRange::new(0.0, 100.0), chunk_markers);
+        let (markers, report) = aggregator.aggregate(vec![chunk_result]);
+
+        assert_eq!(markers.len(), 2);
+        assert_eq!(report.invalid_filtered, 1);
+        assert_eq!(report.out_of_range, 1);
+        assert_eq!(report.clamped, 1);
+        assert!((markers[0].timestamp - 50.0).abs() < 0.001);
+        assert_eq!(markers[0].label, "[IMPL] Valid");
+        assert!((markers[1].timestamp - 100.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn validated_marker_bounded() {
+        let make =
+            |ts| ValidatedMarker::bounded(ts, "m".to_string(), MarkerCategory::Success, 10.0);
+        assert!(make(-1.0).is_none());
+        assert_eq!(make(4.0).unwrap().timestamp, 4.0);
+        assert_eq!(make(15.0).unwrap().timestamp, 10.0);
+    }
+
     // ============================================
     // MarkerWriter Tests
     // ============================================
@@ -860,6 +1335,72 @@ mod tests {
         assert_eq!(report.existing_marker_count, 1);
     }
 
+    #[test]
+    fn marker_writer_never_writes_past_recording_end() {
+        // Recording lasts 0.6s
+        let mut cast = create_test_cast();
+        let markers = vec![
+            ValidatedMarker::new(-1.0, "[PLAN] early".to_string(), MarkerCategory::Planning),
+            ValidatedMarker::new(
+                0.5,
+                "[IMPL] valid".to_string(),
+                MarkerCategory::Implementation,
+            ),
+            ValidatedMarker::new(5.6, "[SUCCESS] late".to_string(), MarkerCategory::Success),
+            ValidatedMarker::new(60.0, "[FAILURE] wild".to_string(), MarkerCategory::Failure),
+        ];
+
+        let report = MarkerWriter::write_markers_to_cast(&mut cast, &markers);
+
+        assert_eq!(report.markers_written, 2);
+        assert_eq!(report.markers_clamped, 1);
+        assert_eq!(report.markers_dropped, 2);
+        assert!((cast.duration() - 0.6).abs() < 1e-9);
+        let written = MarkerManager::list_markers_from_cast(&cast).unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(written[1].label, "[SUCCESS] late");
+        assert!((written[1].timestamp - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn generated_labels_are_recognized_by_category_prefix() {
+        assert!(ValidatedMarker::is_generated_label("[SUCCESS] Tests pass"));
+        assert!(ValidatedMarker::is_generated_label("[IMPL] Wrote parser"));
+        assert!(!ValidatedMarker::is_generated_label("Tests pass"));
+        assert!(!ValidatedMarker::is_generated_label("[TODO] revisit"));
+        assert!(!ValidatedMarker::is_generated_label("[SUCCESS]no space"));
+        assert!(!ValidatedMarker::is_generated_label("see [PLAN] later"));
+    }
+
+    #[test]
+    fn second_write_replaces_generated_markers_and_keeps_manual_ones() {
+        let mut cast = create_test_cast();
+        MarkerManager::add_marker_to_cast(&mut cast, 0.2, "my note").unwrap();
+
+        let first = vec![
+            ValidatedMarker::new(0.1, "[PLAN] Start".to_string(), MarkerCategory::Planning),
+            ValidatedMarker::new(0.5, "[SUCCESS] Done".to_string(), MarkerCategory::Success),
+        ];
+        let report = MarkerWriter::write_markers_to_cast(&mut cast, &first);
+        assert_eq!(report.markers_replaced, 0);
+        assert_eq!(MarkerWriter::count_existing_markers(&cast), (2, 1));
+
+        let second = vec![ValidatedMarker::new(
+            0.4,
+            "[FAILURE] Build broke".to_string(),
+            MarkerCategory::Failure,
+        )];
+        let report = MarkerWriter::write_markers_to_cast(&mut cast, &second);
+        assert_eq!(report.markers_replaced, 2);
+        assert_eq!(report.existing_marker_count, 3);
+
+        let markers = MarkerManager::list_markers_from_cast(&cast).unwrap();
+        let labels: Vec<&str> = markers.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels, vec!["my note", "[FAILURE] Build broke"]);
+        assert!((markers[0].timestamp - 0.2).abs() < 1e-9);
+        assert!((cast.duration() - 0.6).abs() < 1e-9);
+    }
+
     #[test]
     fn marker_writer_empty_markers() {
         let mut cast = create_test_cast();
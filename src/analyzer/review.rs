@@ -0,0 +1,213 @@
+//! Interactive review of proposed markers before they are written.
+//!
+//! [`MarkerReview`] walks the markers one at a time and records a
+//! [`ReviewDecision`] for each. It holds no terminal state, so the prompt
+//! loop lives with the command and the transitions can be tested directly.
+
+use std::collections::VecDeque;
+
+use super::result::ValidatedMarker;
+
+/// What to do with the marker under review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewDecision {
+    /// Keep the marker as proposed
+    Accept,
+    /// Keep the marker with a new description (the category prefix stays)
+    Edit(String),
+    /// Drop the marker
+    Skip,
+    /// Keep this marker and every remaining one
+    AcceptAll,
+}
+
+impl ReviewDecision {
+    /// Parse a reply to the review prompt.
+    ///
+    /// Accepts `a`/`accept` (or an empty reply), `s`/`skip`, `all` and
+    /// `e <description>`/`edit <description>`. A bare `e` is not a decision
+    /// yet: see [`Self::is_edit_request`].
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let (command, rest) = match input.split_once(char::is_whitespace) {
+            Some((command, rest)) => (command, rest.trim()),
+            None => (input, ""),
+        };
+        match command.to_lowercase().as_str() {
+            "" | "a" | "accept" | "y" | "yes" if rest.is_empty() => Some(Self::Accept),
+            "s" | "skip" | "n" | "no" if rest.is_empty() => Some(Self::Skip),
+            "all" if rest.is_empty() => Some(Self::AcceptAll),
+            "e" | "edit" if !rest.is_empty() => Some(Self::Edit(rest.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Whether the reply asks to edit without giving the new description.
+    pub fn is_edit_request(input: &str) -> bool {
+        matches!(input.trim().to_lowercase().as_str(), "e" | "edit")
+    }
+}
+
+/// Review state: the markers still to decide and those approved so far.
+#[derive(Debug)]
+pub struct MarkerReview {
+    pending: VecDeque<ValidatedMarker>,
+    approved: Vec<ValidatedMarker>,
+    total: usize,
+}
+
+impl MarkerReview {
+    /// Start reviewing `markers` in order.
+    pub fn new(markers: Vec<ValidatedMarker>) -> Self {
+        Self {
+            total: markers.len(),
+            pending: markers.into(),
+            approved: Vec::new(),
+        }
+    }
+
+    /// The marker awaiting a decision, or `None` when the review is done.
+    pub fn current(&self) -> Option<&ValidatedMarker> {
+        self.pending.front()
+    }
+
+    /// 1-based position of the current marker and the total count.
+    pub fn position(&self) -> (usize, usize) {
+        (self.total - self.pending.len() + 1, self.total)
+    }
+
+    /// Whether every marker has been decided.
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Apply a decision to the current marker. Does nothing when done.
+    ///
+    /// An edit with a blank description keeps the proposed label.
+    pub fn decide(&mut self, decision: ReviewDecision) {
+        let Some(mut marker) = self.pending.pop_front() else {
+            return;
+        };
+        match decision {
+            ReviewDecision::Accept => self.approved.push(marker),
+            ReviewDecision::Edit(description) => {
+                if !description.trim().is_empty() {
+                    marker.label =
+                        ValidatedMarker::format_label(marker.category, description.trim());
+                }
+                self.approved.push(marker);
+            }
+            ReviewDecision::Skip => {}
+            ReviewDecision::AcceptAll => {
+                self.approved.push(marker);
+                self.approved.extend(self.pending.drain(..));
+            }
+        }
+    }
+
+    /// Skip every marker not yet decided (e.g. when input ends).
+    pub fn skip_rest(&mut self) {
+        self.pending.clear();
+    }
+
+    /// The approved markers, in their original order.
+    pub fn into_approved(self) -> Vec<ValidatedMarker> {
+        self.approved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::backend::MarkerCategory;
+
+    fn markers() -> Vec<ValidatedMarker> {
+        [
+            (10.0, "[PLAN] Outline fix", MarkerCategory::Planning),
+            (20.0, "[IMPL] Patch parser", MarkerCategory::Implementation),
+            (30.0, "[FAILURE] Tests fail", MarkerCategory::Failure),
+            (40.0, "[SUCCESS] Tests pass", MarkerCategory::Success),
+        ]
+        .into_iter()
+        .map(|(ts, label, category)| ValidatedMarker::new(ts, label.to_string(), category))
+        .collect()
+    }
+
+    fn labels(markers: &[ValidatedMarker]) -> Vec<&str> {
+        markers.iter().map(|m| m.label.as_str()).collect()
+    }
+
+    #[test]
+    fn accept_and_skip_advance_one_marker() {
+        let mut review = MarkerReview::new(markers());
+        assert_eq!(review.position(), (1, 4));
+
+        review.decide(ReviewDecision::Accept);
+        assert_eq!(review.position(), (2, 4));
+        review.decide(ReviewDecision::Skip);
+        assert_eq!(review.current().unwrap().label, "[FAILURE] Tests fail");
+        review.decide(ReviewDecision::Skip);
+        review.decide(ReviewDecision::Accept);
+
+        assert!(review.is_done());
+        assert!(review.current().is_none());
+        assert_eq!(
+            labels(&review.into_approved()),
+            vec!["[PLAN] Outline fix", "[SUCCESS] Tests pass"]
+        );
+    }
+
+    #[test]
+    fn edit_replaces_description_and_keeps_category() {
+        let mut review = MarkerReview::new(markers());
+        review.decide(ReviewDecision::Edit("Plan the parser fix".to_string()));
+        review.decide(ReviewDecision::Edit("   ".to_string()));
+        review.skip_rest();
+
+        let approved = review.into_approved();
+        assert_eq!(
+            labels(&approved),
+            vec!["[PLAN] Plan the parser fix", "[IMPL] Patch parser"]
+        );
+        assert_eq!(approved[0].category, MarkerCategory::Planning);
+        assert_eq!(approved[0].timestamp, 10.0);
+    }
+
+    #[test]
+    fn accept_all_approves_current_and_remaining() {
+        let mut review = MarkerReview::new(markers());
+        review.decide(ReviewDecision::Skip);
+        review.decide(ReviewDecision::AcceptAll);
+
+        assert!(review.is_done());
+        assert_eq!(review.into_approved().len(), 3);
+    }
+
+    #[test]
+    fn decisions_after_done_are_ignored() {
+        let mut review = MarkerReview::new(Vec::new());
+        assert!(review.is_done());
+        review.decide(ReviewDecision::Accept);
+        assert!(review.into_approved().is_empty());
+    }
+
+    #[test]
+    fn parse_review_input() {
+        assert_eq!(ReviewDecision::parse(""), Some(ReviewDecision::Accept));
+        assert_eq!(ReviewDecision::parse(" A "), Some(ReviewDecision::Accept));
+        assert_eq!(ReviewDecision::parse("skip"), Some(ReviewDecision::Skip));
+        assert_eq!(
+            ReviewDecision::parse("ALL"),
+            Some(ReviewDecision::AcceptAll)
+        );
+        assert_eq!(
+            ReviewDecision::parse("e  Fix the  parser "),
+            Some(ReviewDecision::Edit("Fix the  parser".to_string()))
+        );
+        assert_eq!(ReviewDecision::parse("e"), None);
+        assert_eq!(ReviewDecision::parse("a extra"), None);
+        assert_eq!(ReviewDecision::parse("maybe"), None);
+        assert!(ReviewDecision::is_edit_request(" edit "));
+        assert!(!ReviewDecision::is_edit_request("e label"));
+    }
+}
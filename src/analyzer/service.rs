@@ -17,18 +17,20 @@
 use std::path::Path;
 use std::time::Duration;
 
-use crate::asciicast::AsciicastFile;
+use crate::asciicast::{AsciicastFile, Header};
 
 use super::backend::{AgentBackend, AgentType};
-use super::chunk::{ChunkCalculator, ChunkConfig};
+use super::cancel::CancelToken;
+use super::chunk::{ChunkCalculator, ChunkConfig, TimeRange};
 use super::config::ExtractionConfig;
 use super::error::AnalysisError;
 use super::extractor::ContentExtractor;
 use super::progress::DefaultProgressReporter;
 use super::prompt::{
-    build_analyze_prompt, build_curation_prompt, build_rename_prompt, extract_rename_response,
+    build_analyze_prompt, build_combine_summary_prompt, build_curation_prompt, build_rename_prompt,
+    build_summary_prompt, extract_rename_response, extract_summary_response, PromptTemplate,
 };
-use super::result::{MarkerWriter, ResultAggregator, ValidatedMarker, WriteReport};
+use super::result::{ChunkSummary, MarkerWriter, ResultAggregator, ValidatedMarker, WriteReport};
 use super::tracker::UsageSummary;
 use super::worker::{ProgressReporter, RetryExecutor, WorkerConfig, WorkerScaler};
 
@@ -60,8 +62,26 @@ pub struct AnalyzeOptions {
     pub curate_extra_args: Vec<String>,
     /// Extra CLI arguments for rename (falls back to `extra_args` if empty)
     pub rename_extra_args: Vec<String>,
+    /// Model for every task (a model flag in the task's extra args wins)
+    pub model: Option<String>,
     /// Override the token budget for chunk calculation
     pub token_budget_override: Option<usize>,
+    /// Only analyze events within this time window (None = whole recording)
+    pub time_range: Option<TimeRange>,
+    /// Collapse pauses longer than this many seconds (None = extraction default, 0 = off)
+    pub idle_gap_secs: Option<u64>,
+    /// Content extraction settings
+    pub extraction: ExtractionConfig,
+    /// Return markers without writing them (the caller writes, e.g. after review)
+    pub defer_write: bool,
+    /// Stops dispatching chunks once cancelled
+    pub cancel: CancelToken,
+    /// On cancellation, keep the markers of the chunks that finished
+    pub write_partial: bool,
+    /// Write nothing if any chunk fails (default: keep the successful chunks)
+    pub fail_on_error: bool,
+    /// Custom analysis prompt (None = built-in prompt)
+    pub prompt_template: Option<PromptTemplate>,
 }
 
 impl Default for AnalyzeOptions {
@@ -78,7 +98,16 @@ impl Default for AnalyzeOptions {
             extra_args: Vec::new(),
             curate_extra_args: Vec::new(),
             rename_extra_args: Vec::new(),
+            model: None,
             token_budget_override: None,
+            time_range: None,
+            idle_gap_secs: None,
+            extraction: ExtractionConfig::default(),
+            defer_write: false,
+            cancel: CancelToken::new(),
+            write_partial: false,
+            fail_on_error: false,
+            prompt_template: None,
         }
     }
 }
@@ -92,6 +121,12 @@ impl AnalyzeOptions {
         }
     }
 
+    /// Return markers without writing them to the file.
+    pub fn defer_write(mut self) -> Self {
+        self.defer_write = true;
+        self
+    }
+
     /// Set worker count override.
     pub fn workers(mut self, count: usize) -> Self {
         self.workers = Some(count);
@@ -152,11 +187,59 @@ impl AnalyzeOptions {
         self
     }
 
+    /// Set the model to request from the agent.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
     /// Set token budget override for chunk calculation.
     pub fn token_budget_override(mut self, budget: usize) -> Self {
         self.token_budget_override = Some(budget);
         self
     }
+
+    /// Set the content extraction config.
+    pub fn extraction(mut self, config: ExtractionConfig) -> Self {
+        self.extraction = config;
+        self
+    }
+
+    /// Set the idle gap threshold in seconds (0 disables idle collapsing).
+    pub fn idle_gap(mut self, secs: u64) -> Self {
+        self.idle_gap_secs = Some(secs);
+        self
+    }
+
+    /// Restrict analysis to a time window of the recording.
+    pub fn time_range(mut self, range: TimeRange) -> Self {
+        self.time_range = Some(range);
+        self
+    }
+
+    /// Stop the analysis when `cancel` is cancelled.
+    pub fn cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Keep the markers of finished chunks when the analysis is cancelled.
+    pub fn write_partial(mut self, enabled: bool) -> Self {
+        self.write_partial = enabled;
+        self
+    }
+
+    /// Fail the whole analysis, writing nothing, if any chunk fails.
+    pub fn fail_on_error(mut self, enabled: bool) -> Self {
+        self.fail_on_error = enabled;
+        self
+    }
+
+    /// Use a custom analysis prompt instead of the built-in one.
+    pub fn prompt_template(mut self, template: PromptTemplate) -> Self {
+        self.prompt_template = Some(template);
+        self
+    }
 }
 
 /// Result of an analysis operation.
@@ -204,7 +287,9 @@ pub struct AnalyzerService {
 impl AnalyzerService {
     /// Create a new analyzer service with options.
     pub fn new(options: AnalyzeOptions) -> Self {
-        let backend = options.agent.create_backend(options.extra_args.clone());
+        let backend = options
+            .agent
+            .create_backend(options.extra_args.clone(), options.model.clone());
         Self { options, backend }
     }
 
@@ -222,7 +307,32 @@ impl AnalyzerService {
         } else {
             task_args.to_vec()
         };
-        self.options.agent.create_backend(args)
+        self.options
+            .agent
+            .create_backend(args, self.options.model.clone())
+    }
+
+    /// Infer which agent produced a recording.
+    ///
+    /// Checks the storage subdirectory first (`claude/session.cast`), then
+    /// the words of the header `command` (`codex exec`, `/usr/bin/gemini`)
+    /// and finally the header `title`. Returns `None` if nothing names a
+    /// supported agent.
+    pub fn infer_agent(path: &Path, header: Option<&Header>) -> Option<AgentType> {
+        let from_dir = path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| name.to_str())
+            .and_then(AgentType::from_command_name);
+        let from_text = |text: &Option<String>| {
+            text.as_deref()?
+                .split(|c: char| c.is_whitespace() || c == '/' || c == '"' || c == '\'')
+                .find_map(AgentType::from_command_name)
+        };
+        from_dir.or_else(|| {
+            let header = header?;
+            from_text(&header.command).or_else(|| from_text(&header.title))
+        })
     }
 
     /// Check if the configured agent is available.
@@ -230,6 +340,49 @@ impl AnalyzerService {
         self.backend.is_available()
     }
 
+    /// The agent analysis runs with.
+    pub fn agent(&self) -> AgentType {
+        self.options.agent
+    }
+
+    /// Switch to another installed agent if the configured one is missing.
+    ///
+    /// Agents are tried in [`AgentType::PREFERENCE`] order. Extra args, model
+    /// and token budget were configured for the original agent, so they are
+    /// dropped on fallback. Returns the agent switched to, `None` if the
+    /// configured agent is installed, or `AgentNotAvailable` if no agent is.
+    pub fn fall_back_if_unavailable(&mut self) -> Result<Option<AgentType>, AnalysisError> {
+        self.fall_back_with(|agent| agent.create_backend(Vec::new(), None))
+    }
+
+    /// [`Self::fall_back_if_unavailable`] with a custom backend factory.
+    fn fall_back_with(
+        &mut self,
+        create_backend: impl Fn(AgentType) -> Box<dyn AgentBackend>,
+    ) -> Result<Option<AgentType>, AnalysisError> {
+        if self.backend.is_available() {
+            return Ok(None);
+        }
+        let requested = self.options.agent;
+        for agent in AgentType::PREFERENCE {
+            if agent == requested {
+                continue;
+            }
+            let backend = create_backend(agent);
+            if backend.is_available() {
+                self.options.agent = agent;
+                self.options.extra_args.clear();
+                self.options.curate_extra_args.clear();
+                self.options.rename_extra_args.clear();
+                self.options.model = None;
+                self.options.token_budget_override = None;
+                self.backend = backend;
+                return Ok(Some(agent));
+            }
+        }
+        Err(AnalysisError::AgentNotAvailable { agent: requested })
+    }
+
     /// Analyze a cast file and add markers.
     ///
     /// # Arguments
@@ -251,22 +404,39 @@ impl AnalyzerService {
         // 2. Check for existing markers
         let (had_existing_markers, existing_marker_count) =
             MarkerWriter::has_existing_markers(&cast);
-        if had_existing_markers && !self.options.quiet {
+        let (generated_count, manual_count) = MarkerWriter::count_existing_markers(&cast);
+        if generated_count > 0 && !self.options.quiet {
             eprintln!(
-                "Warning: File already contains {} marker(s). New markers will be added alongside existing ones.",
-                existing_marker_count
+                "Replacing {} marker(s) from a previous analysis; keeping {} added by hand.",
+                generated_count, manual_count
             );
         }
 
+        // Extraction rewrites the events, so measure the recording first
+        let recording_duration = cast.duration();
+
         // 3. Extract content (Stage 1)
-        let config = ExtractionConfig::default();
-        let extractor = ContentExtractor::new(config);
+        let extractor = ContentExtractor::new(self.extraction_config());
         let (cols, rows) = cast.terminal_size();
-        let content = extractor.extract(&mut cast.events, cols as usize, rows as usize);
+        let (cols, rows) = (cols as usize, rows as usize);
+        let content = match &self.options.time_range {
+            Some(range) => extractor.extract_range(&mut cast.events, cols, rows, range),
+            None => extractor.extract(&mut cast.events, cols, rows),
+        };
+        // Extracted timestamps are on a collapsed timeline relative to the
+        // window start; this maps them back to recording time.
+        let time_offset = self.options.time_range.as_ref().map_or(0.0, |r| r.start);
+        let to_recording_time = |t: f64| content.real_time(t) + time_offset;
 
         // Show extraction stats (before NoContent check so --debug always sees them)
         if !self.options.quiet {
             let stats = &content.stats;
+            if stats.binary_events_replaced > 0 {
+                eprintln!(
+                    "Warning: skipped {} event(s) of binary output.",
+                    stats.binary_events_replaced
+                );
+            }
             let compression = if stats.original_bytes > 0 {
                 100.0 - (stats.extracted_bytes as f64 / stats.original_bytes as f64 * 100.0)
             } else {
@@ -318,6 +488,14 @@ impl AnalyzerService {
                 "                     {:>8} control characters removed",
                 stats.control_chars_stripped
             );
+            eprintln!(
+                "                     {:>8} binary outputs skipped",
+                stats.binary_events_replaced
+            );
+            eprintln!(
+                "  Idle Time:         {:>8} idle gaps collapsed",
+                stats.idle_gaps_collapsed
+            );
             eprintln!(
                 "──────────────────────────────────────────────────────────────────────────────\n"
             );
@@ -356,23 +534,7 @@ impl AnalyzerService {
         }
 
         // 4. Calculate chunks (Stage 2)
-        let calculator = if let Some(budget_tokens) = self.options.token_budget_override {
-            if budget_tokens < 10000 {
-                eprintln!(
-                    "Warning: token_budget {} is below minimum (10000). Using default budget.",
-                    budget_tokens
-                );
-                ChunkCalculator::for_agent(self.options.agent)
-            } else {
-                // Use overridden token budget from per-agent config
-                let mut budget = self.options.agent.token_budget();
-                budget.max_input_tokens = budget_tokens;
-                ChunkCalculator::new(budget, ChunkConfig::default())
-            }
-        } else {
-            ChunkCalculator::for_agent(self.options.agent)
-        };
-        let chunks = calculator.calculate_chunks(&content);
+        let chunks = self.chunk_calculator().calculate_chunks(&content);
 
         // 5. Execute analysis (Stage 3+4)
         let timeout = Duration::from_secs(self.options.timeout_secs);
@@ -394,7 +556,7 @@ impl AnalyzerService {
                 usage_summary: UsageSummary::default(),
                 had_existing_markers,
                 existing_marker_count,
-                total_duration: content.total_duration,
+                total_duration: to_recording_time(content.total_duration),
             });
         }
 
@@ -409,54 +571,101 @@ impl AnalyzerService {
         // Build prompt builder with template
         let total_duration = content.total_duration;
         let total_chunks = chunks.len();
+        let template = self.options.prompt_template.as_ref();
         let prompt_builder = |chunk: &super::chunk::AnalysisChunk| -> String {
-            build_analyze_prompt(chunk, total_duration, total_chunks)
+            build_analyze_prompt(chunk, total_duration, total_chunks, template)
         };
 
         // Execute with retry
         // use_schema = true unless --fast flag was passed
         let use_schema = !self.options.fast;
         let worker_progress = ProgressReporter::new(chunks.len());
-        let executor = RetryExecutor::new(self.backend.as_ref(), timeout, worker_count, use_schema);
+        let executor = RetryExecutor::new(self.backend.as_ref(), timeout, worker_count, use_schema)
+            .with_cancel(self.options.cancel.clone());
         let (results, tracker) =
             executor.execute_with_retry(chunks.clone(), &worker_progress, prompt_builder);
 
+        // A cancelled run writes nothing unless partial results were requested
+        if self.options.cancel.is_cancelled() && !self.options.write_partial {
+            return Err(AnalysisError::Cancelled {
+                completed_chunks: results.iter().filter(|r| r.is_success()).count(),
+                total_chunks: results.len(),
+            });
+        }
+
         // 6. Aggregate results (Stage 5)
         let aggregator = ResultAggregator::new(content.total_duration);
-        let (markers, agg_report) = aggregator.aggregate(results);
+        let (mut markers, mut agg_report) = aggregator.aggregate(results);
+        for marker in &mut markers {
+            marker.timestamp = to_recording_time(marker.timestamp);
+        }
+        for failed in &mut agg_report.failed_chunk_details {
+            failed.time_range = TimeRange::new(
+                to_recording_time(failed.time_range.start),
+                to_recording_time(failed.time_range.end),
+            );
+        }
 
-        // 7. Write markers to file
-        let write_report =
+        // Failed chunks contribute no markers; the others are still written
+        // unless every chunk failed or --fail-on-error asks for all or nothing
+        if !self.options.cancel.is_cancelled() {
+            if agg_report.all_failed() {
+                return Err(AnalysisError::AllChunksFailed {
+                    total_chunks: agg_report.total_chunks,
+                    errors: agg_report
+                        .failed_chunk_details
+                        .iter()
+                        .map(|f| (f.chunk_id, f.error.clone()))
+                        .collect(),
+                });
+            }
+            if self.options.fail_on_error {
+                if let Some(summary) = agg_report.failure_summary() {
+                    return Err(AnalysisError::ChunksFailed { summary });
+                }
+            }
+        }
+        let (clamped, dropped) = MarkerWriter::fit_markers(&mut markers, recording_duration);
+        let clamped = agg_report.clamped + clamped;
+        let dropped = agg_report.out_of_range + dropped;
+        if (clamped > 0 || dropped > 0) && !self.options.quiet {
+            eprintln!(
+                "Adjusted out-of-range marker timestamps: {} clamped to the recording end, {} dropped.",
+                clamped, dropped
+            );
+        }
+
+        // 7. Write markers to file (unless the caller writes them)
+        let write_report = if self.options.defer_write {
+            WriteReport {
+                markers_written: 0,
+                markers_clamped: 0,
+                markers_dropped: 0,
+                markers_replaced: 0,
+                had_existing_markers,
+                existing_marker_count,
+            }
+        } else {
             MarkerWriter::write_markers(path, &markers).map_err(|e| AnalysisError::IoError {
                 operation: "writing markers".to_string(),
                 message: e.to_string(),
-            })?;
+            })?
+        };
 
         // 8. Report summary (Stage 6)
         let usage_summary = tracker.summary();
 
         if !self.options.quiet {
-            if agg_report.failed_chunks.is_empty() {
+            if agg_report.failed_chunks.is_empty() && self.options.defer_write {
+                progress.finish_found(markers.len());
+            } else if agg_report.failed_chunks.is_empty() {
                 progress.finish(write_report.markers_written);
             } else {
-                let failed_ranges: Vec<_> = chunks
+                let (failed_ranges, error_messages): (Vec<_>, Vec<_>) = agg_report
+                    .failed_chunk_details
                     .iter()
-                    .filter(|c| agg_report.failed_chunks.contains(&c.id))
-                    .map(|c| (c.time_range.start, c.time_range.end))
-                    .collect();
-                // Collect error messages in same order as failed_ranges
-                let error_messages: Vec<_> = chunks
-                    .iter()
-                    .filter(|c| agg_report.failed_chunks.contains(&c.id))
-                    .map(|c| {
-                        agg_report
-                            .failed_chunk_details
-                            .iter()
-                            .find(|f| f.chunk_id == c.id)
-                            .map(|f| f.error.clone())
-                            .unwrap_or_default()
-                    })
-                    .collect();
+                    .map(|f| ((f.time_range.start, f.time_range.end), f.error.clone()))
+                    .unzip();
                 progress.finish_partial_with_errors(
                     usage_summary.successful_chunks,
                     usage_summary.chunks_processed,
@@ -473,10 +682,120 @@ impl AnalyzerService {
             usage_summary,
             had_existing_markers,
             existing_marker_count,
-            total_duration,
+            total_duration: to_recording_time(total_duration),
+        })
+    }
+
+    /// Summarize a cast file as a short prose paragraph.
+    ///
+    /// Each chunk is summarized separately; for multi-chunk recordings the
+    /// chunk summaries are then combined into one summary (map-reduce).
+    /// The file is not modified.
+    pub fn summarize<P: AsRef<Path>>(&self, path: P) -> Result<String, AnalysisError> {
+        let mut cast = AsciicastFile::parse(path.as_ref()).map_err(|e| AnalysisError::IoError {
+            operation: "reading cast file".to_string(),
+            message: e.to_string(),
+        })?;
+
+        let extractor = ContentExtractor::new(self.extraction_config());
+        let (cols, rows) = cast.terminal_size();
+        let (cols, rows) = (cols as usize, rows as usize);
+        let content = match &self.options.time_range {
+            Some(range) => extractor.extract_range(&mut cast.events, cols, rows, range),
+            None => extractor.extract(&mut cast.events, cols, rows),
+        };
+        if content.total_tokens == 0 || content.segments.is_empty() {
+            return Err(AnalysisError::NoContent);
+        }
+
+        let chunks = self.chunk_calculator().calculate_chunks(&content);
+        let timeout = Duration::from_secs(self.options.timeout_secs);
+        let total_chunks = chunks.len();
+
+        let mut summaries = Vec::with_capacity(total_chunks);
+        for chunk in &chunks {
+            if !self.options.quiet && total_chunks > 1 {
+                eprintln!("Summarizing chunk {}/{}...", chunk.id + 1, total_chunks);
+            }
+            let prompt = build_summary_prompt(chunk, content.total_duration, total_chunks);
+            summaries.push(ChunkSummary {
+                time_range: chunk.time_range.clone(),
+                text: self.invoke_for_text(&prompt, timeout, "summarizing chunk")?,
+            });
+        }
+
+        self.combine_summaries(&summaries, content.total_duration, timeout)
+    }
+
+    /// Reduce chunk summaries to a single session summary.
+    ///
+    /// A single summary is returned as-is; several are combined by the LLM.
+    fn combine_summaries(
+        &self,
+        summaries: &[ChunkSummary],
+        total_duration: f64,
+        timeout: Duration,
+    ) -> Result<String, AnalysisError> {
+        match summaries {
+            [] => Err(AnalysisError::NoContent),
+            [single] => Ok(single.text.clone()),
+            _ => {
+                let prompt = build_combine_summary_prompt(summaries, total_duration);
+                self.invoke_for_text(&prompt, timeout, "combining summaries")
+            }
+        }
+    }
+
+    /// Invoke the backend for a plain text response.
+    fn invoke_for_text(
+        &self,
+        prompt: &str,
+        timeout: Duration,
+        operation: &str,
+    ) -> Result<String, AnalysisError> {
+        // Never use schema for summaries (plain text response)
+        let response =
+            self.backend
+                .invoke(prompt, timeout, false)
+                .map_err(|e| AnalysisError::IoError {
+                    operation: operation.to_string(),
+                    message: format!("{}", e),
+                })?;
+        extract_summary_response(&response).ok_or_else(|| AnalysisError::IoError {
+            operation: operation.to_string(),
+            message: "agent returned an empty response".to_string(),
         })
     }
 
+    /// Extraction config with the idle gap threshold from the options.
+    fn extraction_config(&self) -> ExtractionConfig {
+        let config = self.options.extraction.clone();
+        match self.options.idle_gap_secs {
+            Some(secs) => config.with_idle_gap(secs),
+            None => config,
+        }
+    }
+
+    /// Chunk calculator for the configured agent and token budget.
+    fn chunk_calculator(&self) -> ChunkCalculator {
+        match self.options.token_budget_override {
+            Some(budget_tokens) if budget_tokens < 10000 => {
+                eprintln!(
+                    "Warning: token_budget {} is below minimum (10000). Using default budget.",
+                    budget_tokens
+                );
+                ChunkCalculator::for_agent(self.options.agent)
+            }
+            Some(budget_tokens) => {
+                // Use overridden token budget from per-agent config
+                let mut budget = self.options.agent.token_budget();
+                budget.max_input_tokens = budget_tokens;
+                ChunkCalculator::new(budget, ChunkConfig::default())
+            }
+            None => ChunkCalculator::for_agent(self.options.agent),
+        }
+    }
+
     /// Calculate worker count based on options and content.
     fn calculate_worker_count(&self, chunk_count: usize, total_tokens: usize) -> usize {
         if self.options.no_parallel {
@@ -600,9 +919,9 @@ mod tests {
     use super::*;
     use crate::analyzer::backend::{BackendError, RawMarker};
     use crate::analyzer::chunk::TokenBudget;
-    use crate::asciicast::{Event, Header};
+    use crate::asciicast::{Event, Header, MarkerManager};
     use std::io::Write;
-    use std::sync::Mutex;
+    use std::sync::{Arc, Mutex};
     use tempfile::NamedTempFile;
 
     // ============================================
@@ -612,6 +931,8 @@ mod tests {
     struct MockBackend {
         responses: Mutex<Vec<Result<String, BackendError>>>,
         available: bool,
+        /// Timeout of every invocation, in call order
+        timeouts: Arc<Mutex<Vec<Duration>>>,
     }
 
     impl MockBackend {
@@ -619,6 +940,7 @@ mod tests {
             Self {
                 responses: Mutex::new(responses),
                 available: true,
+                timeouts: Arc::default(),
             }
         }
 
@@ -626,6 +948,7 @@ mod tests {
             Self {
                 responses: Mutex::new(vec![]),
                 available: false,
+                timeouts: Arc::default(),
             }
         }
     }
@@ -642,9 +965,10 @@ mod tests {
         fn invoke(
             &self,
             _prompt: &str,
-            _timeout: Duration,
+            timeout: Duration,
             _use_schema: bool,
         ) -> Result<String, BackendError> {
+            self.timeouts.lock().unwrap().push(timeout);
             let mut responses = self.responses.lock().unwrap();
             if responses.is_empty() {
                 Ok(r#"{"markers": []}"#.to_string())
@@ -776,6 +1100,8 @@ mod tests {
         assert_eq!(opts.timeout_secs, DEFAULT_TIMEOUT_SECS);
         assert!(!opts.no_parallel);
         assert!(!opts.quiet);
+        assert!(opts.time_range.is_none());
+        assert!(opts.idle_gap_secs.is_none());
     }
 
     #[test]
@@ -803,6 +1129,52 @@ mod tests {
     // AnalyzerService Tests
     // ============================================
 
+    #[test]
+    fn infer_agent_from_storage_directory() {
+        let path = Path::new("/home/dev/recorded_agent_sessions/claude/session.cast");
+        assert_eq!(
+            AnalyzerService::infer_agent(path, None),
+            Some(AgentType::Claude)
+        );
+        assert_eq!(
+            AnalyzerService::infer_agent(Path::new("/tmp/session.cast"), None),
+            None
+        );
+    }
+
+    fn header(json: &str) -> Header {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn infer_agent_from_header_command_and_title() {
+        let path = Path::new("/tmp/session.cast");
+        let infer = |json| AnalyzerService::infer_agent(path, Some(&header(json)));
+
+        assert_eq!(
+            infer(r#"{"version":3,"command":"codex exec"}"#),
+            Some(AgentType::Codex)
+        );
+        assert_eq!(
+            infer(r#"{"version":3,"command":"/usr/local/bin/gemini --yolo","title":"claude"}"#),
+            Some(AgentType::Gemini)
+        );
+        assert_eq!(
+            infer(r#"{"version":3,"command":"bash","title":"Claude fixing the parser"}"#),
+            Some(AgentType::Claude)
+        );
+        assert_eq!(infer(r#"{"version":3,"command":"vim"}"#), None);
+    }
+
+    #[test]
+    fn storage_directory_wins_over_header() {
+        let codex = header(r#"{"version":3,"command":"codex exec"}"#);
+        assert_eq!(
+            AnalyzerService::infer_agent(Path::new("gemini/session.cast"), Some(&codex)),
+            Some(AgentType::Gemini)
+        );
+    }
+
     #[test]
     fn analyzer_service_is_agent_available() {
         let opts = AnalyzeOptions::default().quiet();
@@ -821,6 +1193,75 @@ mod tests {
         assert!(!service.is_agent_available());
     }
 
+    /// Backend factory that reports only `installed` agents as available.
+    fn stub_backends(installed: &[AgentType]) -> impl Fn(AgentType) -> Box<dyn AgentBackend> {
+        let installed = installed.to_vec();
+        move |agent| {
+            let backend = if installed.contains(&agent) {
+                MockBackend::new(vec![])
+            } else {
+                MockBackend::unavailable()
+            };
+            Box::new(backend) as Box<dyn AgentBackend>
+        }
+    }
+
+    #[test]
+    fn fallback_keeps_available_agent() {
+        let opts = AnalyzeOptions::with_agent(AgentType::Codex).model("o3");
+        let mut service = AnalyzerService::with_backend(opts, Box::new(MockBackend::new(vec![])));
+
+        let fallback = service.fall_back_with(stub_backends(&[AgentType::Claude]));
+        assert_eq!(fallback.unwrap(), None);
+        assert_eq!(service.agent(), AgentType::Codex);
+        assert_eq!(service.options.model.as_deref(), Some("o3"));
+    }
+
+    #[test]
+    fn fallback_selects_first_available_agent() {
+        let opts = AnalyzeOptions::with_agent(AgentType::Claude)
+            .extra_args(vec!["--verbose".to_string()])
+            .model("opus")
+            .token_budget_override(5000);
+        let mut service = AnalyzerService::with_backend(opts, Box::new(MockBackend::unavailable()));
+
+        let fallback =
+            service.fall_back_with(stub_backends(&[AgentType::Gemini, AgentType::Codex]));
+        assert_eq!(fallback.unwrap(), Some(AgentType::Codex));
+        assert_eq!(service.agent(), AgentType::Codex);
+        assert!(service.is_agent_available());
+        // Settings for the original agent do not carry over
+        assert!(service.options.extra_args.is_empty());
+        assert_eq!(service.options.model, None);
+        assert_eq!(service.options.token_budget_override, None);
+    }
+
+    #[test]
+    fn fallback_skips_the_unavailable_requested_agent() {
+        let opts = AnalyzeOptions::with_agent(AgentType::Gemini);
+        let mut service = AnalyzerService::with_backend(opts, Box::new(MockBackend::unavailable()));
+
+        // The factory claims gemini is installed, but the current backend says otherwise
+        let fallback =
+            service.fall_back_with(stub_backends(&[AgentType::Gemini, AgentType::Claude]));
+        assert_eq!(fallback.unwrap(), Some(AgentType::Claude));
+    }
+
+    #[test]
+    fn fallback_errors_when_no_agent_is_installed() {
+        let opts = AnalyzeOptions::with_agent(AgentType::Codex);
+        let mut service = AnalyzerService::with_backend(opts, Box::new(MockBackend::unavailable()));
+
+        let err = service.fall_back_with(stub_backends(&[])).unwrap_err();
+        assert!(matches!(
+            err,
+            AnalysisError::AgentNotAvailable {
+                agent: AgentType::Codex
+            }
+        ));
+        assert_eq!(service.agent(), AgentType::Codex);
+    }
+
     #[test]
     fn analyzer_service_analyze_small_file() {
         let file = create_test_cast_file();
@@ -844,6 +1285,183 @@ mod tests {
         );
     }
 
+    /// Timeouts the backend saw when analyzing with `agent` under `toml`.
+    fn invoke_timeouts(toml: &str, agent: AgentType) -> Vec<Duration> {
+        let config = crate::Config::from_toml(toml, None).unwrap();
+        let mut opts = AnalyzeOptions::with_agent(agent).quiet();
+        if let Some(secs) = config.analysis_timeout(agent.command_name()) {
+            opts = opts.timeout(secs);
+        }
+        let backend = MockBackend::new(vec![Ok(mock_response_with_markers())]);
+        let timeouts = Arc::clone(&backend.timeouts);
+        let file = create_test_cast_file();
+
+        AnalyzerService::with_backend(opts, Box::new(backend))
+            .analyze(file.path())
+            .unwrap();
+        let seen = timeouts.lock().unwrap().clone();
+        seen
+    }
+
+    #[test]
+    fn backend_receives_per_agent_timeout_when_set() {
+        let toml = "[analysis]\ntimeout = 90\n[agents.codex]\ntimeout = 300\n";
+        let timeouts = invoke_timeouts(toml, AgentType::Codex);
+        assert!(!timeouts.is_empty());
+        assert!(timeouts.iter().all(|t| *t == Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn backend_receives_global_timeout_without_agent_override() {
+        let toml = "[analysis]\ntimeout = 90\n[agents.codex]\ntimeout = 300\n";
+        let timeouts = invoke_timeouts(toml, AgentType::Claude);
+        assert!(!timeouts.is_empty());
+        assert!(timeouts.iter().all(|t| *t == Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn cancelled_analysis_writes_nothing() {
+        let file = create_test_cast_file();
+        let original = std::fs::read_to_string(file.path()).unwrap();
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let opts = AnalyzeOptions::default().quiet().cancel(cancel.clone());
+        let backend = MockBackend::new(vec![Ok(mock_response_with_markers())]);
+        let timeouts = Arc::clone(&backend.timeouts);
+        let service = AnalyzerService::with_backend(opts, Box::new(backend));
+
+        let result = service.analyze(file.path());
+
+        assert!(matches!(
+            result,
+            Err(AnalysisError::Cancelled {
+                completed_chunks: 0,
+                ..
+            })
+        ));
+        assert!(timeouts.lock().unwrap().is_empty());
+        assert_eq!(std::fs::read_to_string(file.path()).unwrap(), original);
+
+        let opts = AnalyzeOptions::default()
+            .quiet()
+            .cancel(cancel)
+            .write_partial(true);
+        let service = AnalyzerService::with_backend(opts, Box::new(MockBackend::new(vec![])));
+        let result = service.analyze(file.path()).unwrap();
+        assert!(result.markers.is_empty());
+    }
+
+    #[test]
+    fn analysis_with_every_chunk_failed_writes_nothing() {
+        let file = create_test_cast_file();
+        let original = std::fs::read_to_string(file.path()).unwrap();
+        let backend = MockBackend::new(vec![Err(BackendError::Timeout(Duration::from_secs(1)))]);
+        let opts = AnalyzeOptions::default().quiet().fail_on_error(false);
+        let service = AnalyzerService::with_backend(opts, Box::new(backend));
+
+        let result = service.analyze(file.path());
+
+        assert!(matches!(
+            result,
+            Err(AnalysisError::AllChunksFailed {
+                total_chunks: 1,
+                ..
+            })
+        ));
+        assert_eq!(std::fs::read_to_string(file.path()).unwrap(), original);
+    }
+
+    #[test]
+    fn analyzer_service_offsets_markers_by_time_window() {
+        // Phases 3 (git commit, t=17.7) and 4 (git push, t=37.9) fall in this window
+        let file = create_test_cast_file();
+        let opts = AnalyzeOptions::default()
+            .quiet()
+            .time_range(TimeRange::new(15.0, 40.0));
+        let backend = Box::new(MockBackend::new(vec![Ok(mock_response_with_markers())]));
+        let service = AnalyzerService::with_backend(opts, backend);
+
+        let result = service.analyze(file.path()).unwrap();
+
+        assert!(!result.markers.is_empty());
+        for marker in &result.markers {
+            assert!(
+                (15.0..40.0).contains(&marker.timestamp),
+                "Marker at {} is outside the window",
+                marker.timestamp
+            );
+        }
+
+        let cast = AsciicastFile::parse(file.path()).unwrap();
+        let mut cumulative = 0.0;
+        let mut marker_times = Vec::new();
+        for event in &cast.events {
+            cumulative += event.time;
+            if event.is_marker() {
+                marker_times.push(cumulative);
+            }
+        }
+        assert_eq!(marker_times.len(), result.markers.len());
+        assert!(marker_times.iter().all(|t| (15.0..40.0).contains(t)));
+    }
+
+    #[test]
+    fn analyzer_service_maps_markers_past_idle_gaps() {
+        let mut file = NamedTempFile::new().unwrap();
+        let mut cast =
+            AsciicastFile::parse_str(r#"{"version":3,"term":{"cols":120,"rows":10}}"#).unwrap();
+        let phases: &[(&[&str], f64)] = &[
+            (
+                &[
+                    "$ cargo build --release\r\n",
+                    "   Compiling agr v0.1.0 (/home/user/project)\r\n",
+                    "    Finished release [optimized] target(s) in 14.32s\r\n",
+                ],
+                0.0,
+            ),
+            // Five minutes of dead air before the tests run
+            (
+                &[
+                    "$ cargo test --lib\r\n",
+                    "running 42 tests\r\n",
+                    "test config::tests::load_default_config ... ok\r\n",
+                    "test parser::tests::parse_asciicast_header ... ok\r\n",
+                    "test result: ok. 42 passed; 0 failed; 0 ignored\r\n",
+                    "$ git push origin main\r\n",
+                    "To github.com:user/project.git\r\n",
+                ],
+                300.0,
+            ),
+        ];
+        for (lines, phase_start) in phases {
+            for (i, line) in lines.iter().enumerate() {
+                let time = if i == 0 { *phase_start } else { 0.1 };
+                cast.events.push(Event::output(time, *line));
+            }
+        }
+        file.write_all(cast.to_string().unwrap().as_bytes())
+            .unwrap();
+
+        // On the collapsed timeline the tests start at 0.2 + 60 = 60.2s
+        let response = r#"{"markers": [
+            {"timestamp": 60.4, "label": "Tests passed", "category": "success"}
+        ]}"#;
+        let opts = AnalyzeOptions::default().quiet().idle_gap(60);
+        let backend = Box::new(MockBackend::new(vec![Ok(response.to_string())]));
+        let service = AnalyzerService::with_backend(opts, backend);
+
+        let result = service.analyze(file.path()).unwrap();
+
+        assert!(result.total_duration > 300.2 && result.total_duration <= 300.8);
+        assert_eq!(result.markers.len(), 1);
+        let timestamp = result.markers[0].timestamp;
+        assert!(
+            timestamp > 300.2 && timestamp < result.total_duration,
+            "Marker at {} should land after the idle gap",
+            timestamp
+        );
+    }
+
     #[test]
     fn analyzer_service_analyze_with_codex_agent() {
         let file = create_test_cast_file();
@@ -936,6 +1554,34 @@ mod tests {
         assert_eq!(result.existing_marker_count, 1);
     }
 
+    #[test]
+    fn analyzer_service_reanalysis_replaces_generated_markers() {
+        let file = create_test_cast_file();
+        MarkerManager::add_marker(file.path(), 0.5, "my own note").unwrap();
+
+        for _ in 0..2 {
+            let backend = Box::new(MockBackend::new(vec![Ok(mock_response_with_markers())]));
+            let service = AnalyzerService::with_backend(AnalyzeOptions::default().quiet(), backend);
+            service.analyze(file.path()).unwrap();
+        }
+
+        let markers = MarkerManager::list_markers(file.path()).unwrap();
+        let labels: Vec<&str> = markers.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels.iter().filter(|l| **l == "my own note").count(), 1);
+        assert_eq!(
+            labels
+                .iter()
+                .filter(|l| l.starts_with("[IMPL] Started build"))
+                .count(),
+            1,
+            "second analysis should replace the first one's markers: {:?}",
+            labels
+        );
+        assert_eq!(markers.len(), 3);
+        let manual = markers.iter().find(|m| m.label == "my own note").unwrap();
+        assert!((manual.timestamp - 0.5).abs() < 1e-6);
+    }
+
     #[test]
     fn analyzer_service_sequential_mode() {
         let file = create_test_cast_file();
@@ -980,6 +1626,100 @@ mod tests {
         assert!(matches!(result, Err(AnalysisError::NoContent)));
     }
 
+    // ============================================
+    // Summarize Tests
+    // ============================================
+
+    fn chunk_summary(start: f64, end: f64, text: &str) -> ChunkSummary {
+        ChunkSummary {
+            time_range: TimeRange::new(start, end),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn summarize_small_file_returns_agent_text() {
+        let file = create_test_cast_file();
+        let original = std::fs::read_to_string(file.path()).unwrap();
+        let opts = AnalyzeOptions::default().quiet();
+        let backend = Box::new(MockBackend::new(vec![Ok(
+            "The agent built, tested and deployed the project.\n".to_string(),
+        )]));
+        let service = AnalyzerService::with_backend(opts, backend);
+
+        let summary = service.summarize(file.path()).unwrap();
+
+        assert_eq!(summary, "The agent built, tested and deployed the project.");
+        let after = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(original, after, "summarize must not modify the file");
+    }
+
+    #[test]
+    fn combine_summaries_single_chunk_skips_reduce_step() {
+        // An invocation would return the mock's default `{"markers": []}`
+        let service = AnalyzerService::with_backend(
+            AnalyzeOptions::default().quiet(),
+            Box::new(MockBackend::new(vec![])),
+        );
+
+        let summary = service
+            .combine_summaries(
+                &[chunk_summary(0.0, 30.0, "Only one part.")],
+                30.0,
+                Duration::from_secs(1),
+            )
+            .unwrap();
+
+        assert_eq!(summary, "Only one part.");
+    }
+
+    #[test]
+    fn combine_summaries_reduces_multiple_chunks() {
+        let backend = Box::new(MockBackend::new(vec![Ok(
+            r#"{"type":"result","result":"The agent planned, then shipped the parser."}"#
+                .to_string(),
+        )]));
+        let service = AnalyzerService::with_backend(AnalyzeOptions::default().quiet(), backend);
+        let summaries = vec![
+            chunk_summary(0.0, 60.0, "The agent planned the parser."),
+            chunk_summary(60.0, 120.0, "The parser was implemented and tests passed."),
+        ];
+
+        let summary = service
+            .combine_summaries(&summaries, 120.0, Duration::from_secs(1))
+            .unwrap();
+
+        assert_eq!(summary, "The agent planned, then shipped the parser.");
+    }
+
+    #[test]
+    fn combine_summaries_empty_is_no_content() {
+        let service = AnalyzerService::with_backend(
+            AnalyzeOptions::default().quiet(),
+            Box::new(MockBackend::new(vec![])),
+        );
+
+        let result = service.combine_summaries(&[], 0.0, Duration::from_secs(1));
+
+        assert!(matches!(result, Err(AnalysisError::NoContent)));
+    }
+
+    #[test]
+    fn combine_summaries_propagates_backend_failure() {
+        let backend = Box::new(MockBackend::new(vec![Err(BackendError::Timeout(
+            Duration::from_secs(1),
+        ))]));
+        let service = AnalyzerService::with_backend(AnalyzeOptions::default().quiet(), backend);
+        let summaries = vec![chunk_summary(0.0, 1.0, "a"), chunk_summary(1.0, 2.0, "b")];
+
+        let result = service.combine_summaries(&summaries, 2.0, Duration::from_secs(1));
+
+        assert!(matches!(
+            result,
+            Err(AnalysisError::IoError { ref operation, .. }) if operation == "combining summaries"
+        ));
+    }
+
     #[test]
     fn analyzer_service_file_not_found_error() {
         let opts = AnalyzeOptions::default().quiet();
@@ -1001,6 +1741,9 @@ mod tests {
             markers: vec![],
             write_report: WriteReport {
                 markers_written: 5,
+                markers_clamped: 0,
+                markers_dropped: 0,
+                markers_replaced: 0,
                 had_existing_markers: false,
                 existing_marker_count: 0,
             },
@@ -1031,6 +1774,9 @@ mod tests {
             markers: vec![],
             write_report: WriteReport {
                 markers_written: 3,
+                markers_clamped: 0,
+                markers_dropped: 0,
+                markers_replaced: 0,
                 had_existing_markers: false,
                 existing_marker_count: 0,
             },
@@ -0,0 +1,162 @@
+//! Placeholder for binary output dumped to the terminal.
+//!
+//! `cat`-ing an image or a compiled file fills the recording with bytes
+//! that are not text. They mean nothing to the LLM, survive the cleaners as
+//! thousands of stray characters and eat the token budget. The
+//! [`BinaryOutputFilter`] replaces such events with a short
+//! `<binary output: N bytes>` note before any other transform sees them.
+
+use crate::asciicast::{Event, Transform};
+
+/// Events shorter than this are never treated as binary.
+///
+/// A few stray control bytes in a short event are common (bells, cursor
+/// keys echoed raw) and not worth summarising.
+const MIN_BINARY_CHARS: usize = 32;
+
+/// Whether a character counts as non-printable for binary detection.
+///
+/// ESC and the usual layout controls (`\n`, `\r`, `\t`, backspace, bell)
+/// are normal in terminal output and do not count. The Unicode replacement
+/// character does: invalid UTF-8 in the recording decodes to it.
+fn is_non_printable(c: char) -> bool {
+    match c {
+        '\n' | '\r' | '\t' | '\x08' | '\x07' | '\x1b' => false,
+        '\u{FFFD}' => true,
+        c => c.is_control(),
+    }
+}
+
+/// Fraction of non-printable characters in `data` (0.0 for empty data).
+pub fn non_printable_ratio(data: &str) -> f64 {
+    let mut total = 0usize;
+    let mut non_printable = 0usize;
+    for c in data.chars() {
+        total += 1;
+        if is_non_printable(c) {
+            non_printable += 1;
+        }
+    }
+    if total == 0 {
+        0.0
+    } else {
+        non_printable as f64 / total as f64
+    }
+}
+
+/// Replaces output events that look like binary data with a placeholder.
+pub struct BinaryOutputFilter {
+    /// Fraction of non-printable characters above which an event is binary
+    threshold: f64,
+    replaced: usize,
+}
+
+impl BinaryOutputFilter {
+    /// Create a filter with the given non-printable fraction threshold.
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            replaced: 0,
+        }
+    }
+
+    /// Number of events replaced with a placeholder.
+    pub fn replaced_count(&self) -> usize {
+        self.replaced
+    }
+
+    fn is_binary(&self, data: &str) -> bool {
+        data.chars().count() >= MIN_BINARY_CHARS && non_printable_ratio(data) > self.threshold
+    }
+}
+
+impl Transform for BinaryOutputFilter {
+    fn transform(&mut self, events: &mut Vec<Event>) {
+        for event in events.iter_mut().filter(|e| e.is_output()) {
+            if self.is_binary(&event.data) {
+                event.data = format!("<binary output: {} bytes>\n", event.data.len());
+                self.replaced += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bytes, decoded the way a cast file's
+    /// invalid UTF-8 would be.
+    fn random_bytes(count: usize) -> String {
+        let mut state: u32 = 0x2545_f491;
+        let bytes: Vec<u8> = (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state >> 24) as u8
+            })
+            .collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    #[test]
+    fn random_bytes_are_replaced_with_a_placeholder() {
+        let garbage = random_bytes(4096);
+        let len = garbage.len();
+        let mut events = vec![
+            Event::output(0.1, "$ cat logo.png\r\n"),
+            Event::output(0.1, garbage),
+            Event::output(0.1, "$ "),
+        ];
+
+        let mut filter = BinaryOutputFilter::new(0.3);
+        filter.transform(&mut events);
+
+        assert_eq!(filter.replaced_count(), 1);
+        assert_eq!(events[0].data, "$ cat logo.png\r\n");
+        assert_eq!(events[1].data, format!("<binary output: {} bytes>\n", len));
+        assert_eq!(events[2].data, "$ ");
+    }
+
+    #[test]
+    fn colored_and_unicode_text_is_kept() {
+        let text = "\x1b[1;32m✓ 42 tests passed\x1b[0m\r\n\x1b[2K\r⠋ Compiling agr v0.1.0\r\n\
+                    Résumé: ファイルを保存しました\t\x07\r\n";
+        let mut events = vec![Event::output(0.1, text)];
+
+        let mut filter = BinaryOutputFilter::new(0.3);
+        filter.transform(&mut events);
+
+        assert_eq!(filter.replaced_count(), 0);
+        assert_eq!(events[0].data, text);
+    }
+
+    #[test]
+    fn short_events_and_markers_are_never_replaced() {
+        let mut events = vec![
+            Event::output(0.1, "\u{FFFD}\u{FFFD}\x00\x01"),
+            Event::marker(0.1, random_bytes(256)),
+        ];
+
+        let mut filter = BinaryOutputFilter::new(0.3);
+        filter.transform(&mut events);
+
+        assert_eq!(filter.replaced_count(), 0);
+        assert!(events[1].is_marker());
+    }
+
+    #[test]
+    fn threshold_controls_detection() {
+        // 16 of 64 characters (25%) are control bytes
+        let mixed = "abc\x00".repeat(16);
+        assert_eq!(non_printable_ratio(&mixed), 0.25);
+
+        let mut events = vec![Event::output(0.1, mixed.clone())];
+        BinaryOutputFilter::new(0.3).transform(&mut events);
+        assert_eq!(events[0].data, mixed);
+
+        BinaryOutputFilter::new(0.2).transform(&mut events);
+        assert_eq!(events[0].data, "<binary output: 64 bytes>\n");
+    }
+}
@@ -3,6 +3,7 @@
 //! These transforms implement the [`crate::asciicast::Transform`] trait and
 //! can be composed into a pipeline for cleaning asciicast event data.
 //!
+//! - [`BinaryOutputFilter`] - Replaces binary dumps with a size placeholder
 //! - [`ContentCleaner`] - Single-pass ANSI/control/spinner stripping
 //! - [`DeduplicateProgressLines`] - Keeps only final state of `\r`-rewritten lines
 //! - [`NormalizeWhitespace`] - Collapses excessive whitespace
@@ -13,6 +14,7 @@
 //! - [`GlobalDeduplicator`] - Caps global line frequency and hashes redundant redraws
 
 mod aggressive;
+mod binary;
 mod cleaner;
 mod dedupe;
 mod noise;
@@ -23,6 +25,7 @@ pub use aggressive::{
     BlockTruncator, EventCoalescer, FileDumpFilter, GlobalDeduplicator, SimilarityFilter,
     WindowedLineDeduplicator,
 };
+pub use binary::BinaryOutputFilter;
 pub use cleaner::ContentCleaner;
 pub use dedupe::DeduplicateProgressLines;
 pub use normalize::{EmptyLineFilter, FilterEmptyEvents, NormalizeWhitespace};
@@ -3,6 +3,8 @@
 //! These types represent the cleaned content extracted from asciicast recordings,
 //! organized into segments with timestamp ranges and token estimates.
 
+use serde::Serialize;
+
 /// A segment of analysis content with time range mapping.
 ///
 /// Created from transformed events for chunking and LLM analysis.
@@ -25,6 +27,17 @@ pub struct AnalysisSegment {
     pub event_range: (usize, usize),
 }
 
+/// A long pause collapsed out of the analysis timeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdleGap {
+    /// Time activity resumes, on the collapsed timeline (seconds)
+    pub at: f64,
+    /// Length of the original pause (seconds)
+    pub duration: f64,
+    /// Seconds removed from the timeline
+    pub removed: f64,
+}
+
 /// Complete analysis content extracted from a cast file.
 #[derive(Debug)]
 pub struct AnalysisContent {
@@ -39,9 +52,27 @@ pub struct AnalysisContent {
 
     /// Extraction statistics for transparency
     pub stats: ExtractionStats,
+
+    /// Idle gaps collapsed out of the timeline, in chronological order
+    pub idle_gaps: Vec<IdleGap>,
 }
 
 impl AnalysisContent {
+    /// Map a timestamp on the collapsed timeline back to recording time.
+    ///
+    /// Segment times, chunk ranges and resolved marker timestamps are all on
+    /// the collapsed timeline; this adds back the idle time removed before
+    /// `timestamp`.
+    pub fn real_time(&self, timestamp: f64) -> f64 {
+        let removed: f64 = self
+            .idle_gaps
+            .iter()
+            .take_while(|gap| gap.at <= timestamp)
+            .map(|gap| gap.removed)
+            .sum();
+        timestamp + removed
+    }
+
     /// Find the segment containing a given timestamp.
     pub fn segment_at_time(&self, timestamp: f64) -> Option<&AnalysisSegment> {
         self.segments
@@ -68,7 +99,7 @@ impl AnalysisContent {
 }
 
 /// Extraction statistics for transparency.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct ExtractionStats {
     /// Original content size in bytes
     pub original_bytes: usize,
@@ -94,6 +125,10 @@ pub struct ExtractionStats {
     pub blocks_truncated: usize,
     /// Number of output bursts collapsed (file dumps/log explosions)
     pub bursts_collapsed: usize,
+    /// Number of idle gaps collapsed (dead air)
+    pub idle_gaps_collapsed: usize,
+    /// Number of binary output events replaced with a placeholder
+    pub binary_events_replaced: usize,
     /// Number of events processed
     pub events_processed: usize,
     /// Number of events retained after filtering
@@ -113,6 +148,11 @@ impl ExtractionStats {
     pub fn reduction_percentage(&self) -> f64 {
         1.0 - self.compression_ratio()
     }
+
+    /// Number of events removed by the pipeline.
+    pub fn events_dropped(&self) -> usize {
+        self.events_processed.saturating_sub(self.events_retained)
+    }
 }
 
 /// Estimate token count from text content.
@@ -264,6 +304,7 @@ mod tests {
             total_duration: 20.0,
             total_tokens: 2,
             stats: ExtractionStats::default(),
+            idle_gaps: Vec::new(),
         };
 
         assert_eq!(content.segment_at_time(5.0).unwrap().content, "first");
@@ -300,6 +341,7 @@ mod tests {
             total_duration: 30.0,
             total_tokens: 3,
             stats: ExtractionStats::default(),
+            idle_gaps: Vec::new(),
         };
 
         // Range overlapping first two segments
@@ -333,8 +375,39 @@ mod tests {
             total_duration: 20.0,
             total_tokens: 2,
             stats: ExtractionStats::default(),
+            idle_gaps: Vec::new(),
         };
 
         assert_eq!(content.text(), "first\nsecond");
     }
+
+    #[test]
+    fn analysis_content_real_time_adds_back_idle_gaps() {
+        let content = AnalysisContent {
+            segments: vec![],
+            total_duration: 130.0,
+            total_tokens: 0,
+            stats: ExtractionStats::default(),
+            idle_gaps: vec![
+                IdleGap {
+                    at: 70.0,
+                    duration: 300.0,
+                    removed: 240.0,
+                },
+                IdleGap {
+                    at: 120.0,
+                    duration: 90.0,
+                    removed: 30.0,
+                },
+            ],
+        };
+
+        assert_eq!(content.real_time(5.0), 5.0);
+        // Inside the kept part of the pause
+        assert_eq!(content.real_time(69.0), 69.0);
+        // Resumption after the first gap
+        assert_eq!(content.real_time(70.0), 310.0);
+        assert_eq!(content.real_time(100.0), 340.0);
+        assert_eq!(content.real_time(130.0), 400.0);
+    }
 }
@@ -20,6 +20,7 @@
 //! 4. Each chunk retried up to 3 times with exponential backoff
 
 use crate::analyzer::backend::{AgentBackend, BackendError, RawMarker};
+use crate::analyzer::cancel::CancelToken;
 use crate::analyzer::chunk::{AnalysisChunk, TimeRange};
 use crate::analyzer::tracker::TokenTracker;
 use rayon::prelude::*;
@@ -209,6 +210,7 @@ pub struct ParallelExecutor<'a, B: AgentBackend + ?Sized> {
     timeout: Duration,
     worker_count: usize,
     use_schema: bool,
+    cancel: CancelToken,
 }
 
 impl<'a, B: AgentBackend + ?Sized> ParallelExecutor<'a, B> {
@@ -219,9 +221,18 @@ impl<'a, B: AgentBackend + ?Sized> ParallelExecutor<'a, B> {
             timeout,
             worker_count,
             use_schema,
+            cancel: CancelToken::new(),
         }
     }
 
+    /// Stop dispatching chunks once `cancel` is cancelled.
+    ///
+    /// Chunks not started by then fail with [`BackendError::Cancelled`].
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
     /// Execute analysis on chunks, returning results for each.
     ///
     /// For a single chunk, processes directly without creating a thread pool.
@@ -308,6 +319,13 @@ impl<'a, B: AgentBackend + ?Sized> ParallelExecutor<'a, B> {
         chunk: &AnalysisChunk,
         prompt_builder: &impl Fn(&AnalysisChunk) -> String,
     ) -> ChunkResult {
+        if self.cancel.is_cancelled() {
+            return ChunkResult::failure(
+                chunk.id,
+                chunk.time_range.clone(),
+                BackendError::Cancelled,
+            );
+        }
         let prompt = prompt_builder(chunk);
 
         match self.backend.invoke(&prompt, self.timeout, self.use_schema) {
@@ -330,6 +348,7 @@ pub struct RetryExecutor<'a, B: AgentBackend + ?Sized> {
     timeout: Duration,
     worker_count: usize,
     use_schema: bool,
+    cancel: CancelToken,
 }
 
 impl<'a, B: AgentBackend + ?Sized> RetryExecutor<'a, B> {
@@ -340,9 +359,16 @@ impl<'a, B: AgentBackend + ?Sized> RetryExecutor<'a, B> {
             timeout,
             worker_count,
             use_schema,
+            cancel: CancelToken::new(),
         }
     }
 
+    /// Stop dispatching chunks once `cancel` is cancelled.
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
     /// Execute analysis with tracking.
     ///
     /// Returns tuple of (results, tracker) for visibility.
@@ -369,7 +395,8 @@ impl<'a, B: AgentBackend + ?Sized> RetryExecutor<'a, B> {
             self.timeout,
             self.worker_count,
             self.use_schema,
-        );
+        )
+        .with_cancel(self.cancel.clone());
 
         let results = parallel_executor.execute(chunks, progress, &prompt_builder);
 
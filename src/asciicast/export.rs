@@ -0,0 +1,194 @@
+//! Exporting recordings for tools that only understand plain asciicast v3.
+//!
+//! Marker events (`"m"`) are part of the v3 format, but some downstream
+//! players and converters reject or mishandle them. The [`FlattenMarkers`]
+//! transform either rewrites each marker as a dimmed `# marker: label`
+//! output line or drops it altogether.
+//!
+//! Event times are relative, so a marker replaced by an output event keeps
+//! its time unchanged. A dropped marker passes its relative time on to the
+//! next event, which keeps every remaining event at the same absolute time.
+//!
+//! # Example
+//!
+//! ```
+//! use agr::asciicast::{Event, FlattenMarkers, MarkerFlattening, Transform};
+//!
+//! let mut events = vec![
+//!     Event::output(0.5, "$ cargo test\r\n"),
+//!     Event::marker(1.0, "Tests start"),
+//!     Event::output(0.5, "ok\r\n"),
+//! ];
+//!
+//! let mut flatten = FlattenMarkers::new(MarkerFlattening::Strip);
+//! flatten.transform(&mut events);
+//!
+//! assert_eq!(flatten.flattened(), 1);
+//! assert_eq!(events.len(), 2);
+//! assert_eq!(events[1].time, 1.5);
+//! ```
+
+use super::{Event, EventType, Transform};
+
+/// Output format of `agr export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Plain asciicast v3 (.cast)
+    #[default]
+    Cast,
+}
+
+/// What happens to marker events on export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkerFlattening {
+    /// Keep markers as marker events
+    #[default]
+    Keep,
+    /// Replace each marker with a dimmed `# marker: label` output line
+    Comment,
+    /// Drop markers entirely
+    Strip,
+}
+
+/// Output data that stands in for a flattened marker.
+pub fn marker_comment(label: &str) -> String {
+    format!("\x1b[2m# marker: {}\x1b[0m\r\n", label)
+}
+
+/// Transform that rewrites or removes marker events.
+#[derive(Debug, Clone, Default)]
+pub struct FlattenMarkers {
+    mode: MarkerFlattening,
+    flattened: usize,
+}
+
+impl FlattenMarkers {
+    /// Create a transform with the given marker handling.
+    pub fn new(mode: MarkerFlattening) -> Self {
+        Self { mode, flattened: 0 }
+    }
+
+    /// Number of markers rewritten or removed so far.
+    pub fn flattened(&self) -> usize {
+        self.flattened
+    }
+}
+
+impl Transform for FlattenMarkers {
+    fn transform(&mut self, events: &mut Vec<Event>) {
+        match self.mode {
+            MarkerFlattening::Keep => {}
+            MarkerFlattening::Comment => {
+                for event in events.iter_mut().filter(|e| e.is_marker()) {
+                    event.event_type = EventType::Output;
+                    event.data = marker_comment(&event.data);
+                    self.flattened += 1;
+                }
+            }
+            MarkerFlattening::Strip => {
+                let mut carry_time = 0.0f64;
+                let mut kept = Vec::with_capacity(events.len());
+
+                for mut event in events.drain(..) {
+                    if event.is_marker() {
+                        carry_time += event.time;
+                        self.flattened += 1;
+                        continue;
+                    }
+                    event.time += carry_time;
+                    carry_time = 0.0;
+                    kept.push(event);
+                }
+                // Trailing markers: keep the total duration
+                if carry_time > 0.0 {
+                    if let Some(last) = kept.last_mut() {
+                        last.time += carry_time;
+                    }
+                }
+                *events = kept;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events() -> Vec<Event> {
+        vec![
+            Event::output(0.5, "$ cargo test\r\n"),
+            Event::marker(1.0, "[PLAN] Run tests"),
+            Event::output(0.25, "running 3 tests\r\n"),
+            Event::marker(0.0, "[SUCCESS] Tests pass"),
+            Event::output(2.0, "ok\r\n"),
+        ]
+    }
+
+    fn absolute_times(events: &[Event]) -> Vec<f64> {
+        let mut time = 0.0;
+        events
+            .iter()
+            .map(|e| {
+                time += e.time;
+                time
+            })
+            .collect()
+    }
+
+    #[test]
+    fn comment_replaces_markers_with_dimmed_output_at_the_same_time() {
+        let original = events();
+        let mut flattened = events();
+        let mut flatten = FlattenMarkers::new(MarkerFlattening::Comment);
+        flatten.transform(&mut flattened);
+
+        assert_eq!(flatten.flattened(), 2);
+        assert!(flattened.iter().all(|e| e.is_output()));
+        assert_eq!(
+            flattened[1].data,
+            "\x1b[2m# marker: [PLAN] Run tests\x1b[0m\r\n"
+        );
+        assert_eq!(
+            flattened[3].data,
+            "\x1b[2m# marker: [SUCCESS] Tests pass\x1b[0m\r\n"
+        );
+        assert_eq!(flattened[2].data, original[2].data);
+        assert_eq!(absolute_times(&flattened), absolute_times(&original));
+    }
+
+    #[test]
+    fn strip_drops_markers_and_keeps_absolute_times() {
+        let mut stripped = events();
+        let mut flatten = FlattenMarkers::new(MarkerFlattening::Strip);
+        flatten.transform(&mut stripped);
+
+        assert_eq!(flatten.flattened(), 2);
+        let data: Vec<&str> = stripped.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(
+            data,
+            vec!["$ cargo test\r\n", "running 3 tests\r\n", "ok\r\n"]
+        );
+        assert_eq!(absolute_times(&stripped), vec![0.5, 1.75, 3.75]);
+    }
+
+    #[test]
+    fn strip_carries_trailing_marker_time_to_the_last_event() {
+        let mut events = vec![Event::output(0.5, "done"), Event::marker(1.5, "End")];
+        FlattenMarkers::new(MarkerFlattening::Strip).transform(&mut events);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].time, 2.0);
+    }
+
+    #[test]
+    fn keep_leaves_events_untouched() {
+        let mut kept = events();
+        let mut flatten = FlattenMarkers::new(MarkerFlattening::Keep);
+        flatten.transform(&mut kept);
+
+        assert_eq!(flatten.flattened(), 0);
+        assert_eq!(kept.len(), 5);
+        assert!(kept[1].is_marker());
+    }
+}
@@ -0,0 +1,421 @@
+//! Sensitive input suppression for recordings with captured keystrokes.
+//!
+//! When asciinema captures input, passwords typed at a prompt end up in the
+//! cast even though the terminal never echoed them. This module provides
+//! the [`SensitiveInputFilter`] transform that drops `Input` events while a
+//! sensitive window is open. Windows are derived from the output stream,
+//! because the pty's echo state is not recorded:
+//!
+//! - **Password prompts**: output whose last line looks like
+//!   `Password:` / `Enter passphrase for key:` opens a window that closes
+//!   with the first Enter (or Ctrl-C/Ctrl-D) keystroke.
+//! - **Concealed text**: SGR 8 (`ESC[8m`) opens a window until SGR 28 or a
+//!   reset (`ESC[0m`, `ESC[m`).
+//! - **Explicit escapes**: [`INPUT_CAPTURE_OFF`] and [`INPUT_CAPTURE_ON`]
+//!   pause and resume input capture. Terminals ignore the unknown OSC, so
+//!   scripts can print them around anything sensitive.
+//!
+//! Dropped events pass their relative time on to the next event, so the
+//! timing of the remaining events is unchanged.
+//!
+//! For recordings in storage the filter runs on the finished recording;
+//! while asciinema is still recording, the keystrokes are in the live cast
+//! file on disk. `agr record --output` applies the same [`InputEchoState`]
+//! to each event before streaming it.
+//!
+//! # Example
+//!
+//! ```
+//! use agr::asciicast::{Event, EventType, SensitiveInputFilter, Transform};
+//!
+//! let mut events = vec![
+//!     Event::output(0.1, "[sudo] password for dev: "),
+//!     Event::new(0.5, EventType::Input, "hunter2"),
+//!     Event::new(0.2, EventType::Input, "\r"),
+//!     Event::output(0.1, "\r\n$ "),
+//!     Event::new(0.4, EventType::Input, "ls"),
+//! ];
+//!
+//! let mut filter = SensitiveInputFilter::new();
+//! filter.transform(&mut events);
+//!
+//! assert_eq!(filter.dropped(), 2);
+//! assert_eq!(events.len(), 3);
+//! assert_eq!(events[2].data, "ls");
+//! ```
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::{Event, EventType, Transform};
+
+/// Output sequence that pauses input capture until [`INPUT_CAPTURE_ON`].
+pub const INPUT_CAPTURE_OFF: &str = "\x1b]9999;agr:input-off\x07";
+
+/// Output sequence that resumes input capture after [`INPUT_CAPTURE_OFF`].
+pub const INPUT_CAPTURE_ON: &str = "\x1b]9999;agr:input-on\x07";
+
+/// OSC prefix shared by both capture escapes.
+const CAPTURE_OSC_PREFIX: &str = "\x1b]9999;agr:input-";
+
+/// Maximum characters of the current output line kept for prompt matching.
+const MAX_LINE_TAIL: usize = 256;
+
+fn password_prompt() -> &'static Regex {
+    static PROMPT: OnceLock<Regex> = OnceLock::new();
+    PROMPT.get_or_init(|| {
+        Regex::new(r"(?i)(password|passphrase|passcode|\bpin\b)[^:]*:\s*$")
+            .expect("password prompt pattern is valid")
+    })
+}
+
+/// Decides, event by event, whether captured input should be recorded.
+///
+/// Feed every output event to [`observe_output`](Self::observe_output) and
+/// ask [`should_record_input`](Self::should_record_input) for every input
+/// event, in recording order.
+#[derive(Debug, Clone, Default)]
+pub struct InputEchoState {
+    /// Paused by [`INPUT_CAPTURE_OFF`]
+    paused: bool,
+    /// Inside SGR 8 concealed text
+    concealed: bool,
+    /// A password prompt is waiting for Enter
+    prompt: bool,
+    /// Visible text of the current output line
+    line: String,
+}
+
+impl InputEchoState {
+    /// Create a state with capture enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether input is currently being suppressed.
+    pub fn is_suppressing(&self) -> bool {
+        self.paused || self.concealed || self.prompt
+    }
+
+    /// Update the state from an output event's data.
+    pub fn observe_output(&mut self, data: &str) {
+        let mut rest = data;
+        while let Some(esc) = rest.find('\x1b') {
+            self.push_text(&rest[..esc]);
+            rest = &rest[esc..];
+            rest = if let Some(after) = rest.strip_prefix(CAPTURE_OSC_PREFIX) {
+                if after.starts_with("off") {
+                    self.paused = true;
+                } else if after.starts_with("on") {
+                    self.paused = false;
+                }
+                skip_osc(after)
+            } else if let Some(after) = rest.strip_prefix("\x1b]") {
+                skip_osc(after)
+            } else if let Some(after) = rest.strip_prefix("\x1b[") {
+                self.apply_csi(after)
+            } else {
+                &rest[1..]
+            };
+        }
+        self.push_text(rest);
+
+        if password_prompt().is_match(&self.line) {
+            self.prompt = true;
+        }
+    }
+
+    /// Decide whether an input event's data should be recorded.
+    ///
+    /// Keystrokes typed at a password prompt are dropped up to and
+    /// including the Enter (or Ctrl-C/Ctrl-D) that ends the prompt.
+    pub fn should_record_input(&mut self, data: &str) -> bool {
+        if self.prompt {
+            if data.contains(['\r', '\n', '\x03', '\x04']) {
+                self.prompt = false;
+                self.line.clear();
+            }
+            return false;
+        }
+        !(self.paused || self.concealed)
+    }
+
+    /// Track the visible text after the last line break.
+    fn push_text(&mut self, text: &str) {
+        let text = match text.rfind(['\n', '\r']) {
+            Some(pos) => {
+                self.line.clear();
+                &text[pos + 1..]
+            }
+            None => text,
+        };
+        self.line.push_str(text);
+        let excess = self.line.chars().count().saturating_sub(MAX_LINE_TAIL);
+        if excess > 0 {
+            self.line = self.line.chars().skip(excess).collect();
+        }
+    }
+
+    /// Apply a CSI sequence (after `ESC[`), returning the text after it.
+    fn apply_csi<'a>(&mut self, after: &'a str) -> &'a str {
+        let Some(end) = after.find(|c: char| ('\x40'..='\x7e').contains(&c)) else {
+            return "";
+        };
+        if after[end..].starts_with('m') {
+            self.apply_sgr(&after[..end]);
+        }
+        &after[end + 1..]
+    }
+
+    /// Track SGR 8/28 and resets in an SGR parameter string.
+    ///
+    /// Parameters are read like [`crate::terminal`] does: a colon group
+    /// (`38:2::r:g:b`) is one parameter, and the `5;n` / `2;r;g;b`
+    /// sub-parameters after a semicolon-form `38`/`48`/`58` are skipped, so
+    /// a color index of 8 or a channel of 0 is not taken for SGR 8 or a
+    /// reset.
+    fn apply_sgr(&mut self, params: &str) {
+        let groups: Vec<&str> = params.split(';').collect();
+        let mut i = 0;
+
+        while i < groups.len() {
+            let group = groups[i];
+            i += 1;
+            let first = group.split(':').next().unwrap_or("");
+            let param = if first.is_empty() {
+                Some(0)
+            } else {
+                first.parse::<u16>().ok()
+            };
+
+            match param {
+                Some(8) => self.concealed = true,
+                Some(0) | Some(28) => self.concealed = false,
+                Some(38) | Some(48) | Some(58) if !group.contains(':') => {
+                    let used = match groups.get(i).copied() {
+                        Some("5") => 2,
+                        Some("2") => 4,
+                        Some(_) => 1,
+                        None => 0,
+                    };
+                    i = (i + used).min(groups.len());
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Skip an OSC body up to and including its BEL or ST terminator.
+fn skip_osc(after: &str) -> &str {
+    match (after.find('\x07'), after.find("\x1b\\")) {
+        (Some(bel), Some(st)) if st < bel => &after[st + 2..],
+        (Some(bel), _) => &after[bel + 1..],
+        (None, Some(st)) => &after[st + 2..],
+        (None, None) => "",
+    }
+}
+
+/// A transform that drops input events typed into sensitive windows.
+#[derive(Debug, Clone, Default)]
+pub struct SensitiveInputFilter {
+    state: InputEchoState,
+    dropped: usize,
+}
+
+impl SensitiveInputFilter {
+    /// Create a filter with capture enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of input events dropped so far.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+impl Transform for SensitiveInputFilter {
+    fn transform(&mut self, events: &mut Vec<Event>) {
+        let mut carry_time = 0.0f64;
+        let mut kept = Vec::with_capacity(events.len());
+
+        for mut event in events.drain(..) {
+            match event.event_type {
+                EventType::Output => self.state.observe_output(&event.data),
+                EventType::Input if !self.state.should_record_input(&event.data) => {
+                    carry_time += event.time;
+                    self.dropped += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            event.time += carry_time;
+            carry_time = 0.0;
+            kept.push(event);
+        }
+        // Trailing dropped events: keep the total duration
+        if carry_time > 0.0 {
+            if let Some(last) = kept.last_mut() {
+                last.time += carry_time;
+            }
+        }
+        *events = kept;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(data: &str) -> Event {
+        Event::new(0.1, EventType::Input, data)
+    }
+
+    #[test]
+    fn records_input_by_default() {
+        let mut state = InputEchoState::new();
+        state.observe_output("$ ");
+        assert!(state.should_record_input("ls"));
+        assert!(!state.is_suppressing());
+    }
+
+    #[test]
+    fn password_prompt_suppresses_until_enter() {
+        let mut state = InputEchoState::new();
+        state.observe_output("\x1b[1mEnter passphrase for key '/home/u/.ssh/id_ed25519':\x1b[0m ");
+        assert!(state.is_suppressing());
+        assert!(!state.should_record_input("s3"));
+        assert!(!state.should_record_input("cret\r"));
+        assert!(!state.is_suppressing());
+        assert!(state.should_record_input("ls"));
+    }
+
+    #[test]
+    fn password_prompt_split_across_output_events() {
+        let mut state = InputEchoState::new();
+        state.observe_output("building...\r\nPass");
+        assert!(state.should_record_input("x"));
+        state.observe_output("word: ");
+        assert!(!state.should_record_input("x"));
+    }
+
+    #[test]
+    fn ctrl_c_ends_password_prompt() {
+        let mut state = InputEchoState::new();
+        state.observe_output("Password: ");
+        assert!(!state.should_record_input("\x03"));
+        assert!(state.should_record_input("y"));
+    }
+
+    #[test]
+    fn prompt_text_in_earlier_lines_is_ignored() {
+        let mut state = InputEchoState::new();
+        state.observe_output("Password: changed successfully\r\n$ ");
+        assert!(state.should_record_input("ls"));
+        state.observe_output("password reset link: https://example.com\r\n");
+        assert!(state.should_record_input("ls"));
+    }
+
+    #[test]
+    fn concealed_text_suppresses_until_reveal() {
+        let mut state = InputEchoState::new();
+        state.observe_output("token \x1b[8m");
+        assert!(!state.should_record_input("abc"));
+        state.observe_output("\x1b[28m");
+        assert!(state.should_record_input("abc"));
+
+        state.observe_output("\x1b[1;8m");
+        assert!(!state.should_record_input("abc"));
+        state.observe_output("\x1b[m");
+        assert!(state.should_record_input("abc"));
+    }
+
+    #[test]
+    fn indexed_color_8_does_not_conceal() {
+        let mut state = InputEchoState::new();
+        state.observe_output("\x1b[38;5;8mgrey\x1b[48;5;8m");
+        assert!(state.should_record_input("abc"));
+        state.observe_output("\x1b[38:5:8m");
+        assert!(state.should_record_input("abc"));
+    }
+
+    #[test]
+    fn zero_color_channel_is_not_a_reset() {
+        let mut state = InputEchoState::new();
+        state.observe_output("\x1b[8m");
+        state.observe_output("\x1b[38;2;0;0;0m");
+        assert!(!state.should_record_input("hunter2"));
+        state.observe_output("\x1b[48:2::0:0:0m\x1b[1;38;2;0;0;0;4m");
+        assert!(!state.should_record_input("hunter2"));
+        state.observe_output("\x1b[38;2;0;0;0;0m");
+        assert!(state.should_record_input("ls"));
+    }
+
+    #[test]
+    fn explicit_escapes_pause_and_resume() {
+        let mut state = InputEchoState::new();
+        state.observe_output(&format!("before{}after", INPUT_CAPTURE_OFF));
+        assert!(!state.should_record_input("a"));
+        // Enter does not end an explicit pause
+        assert!(!state.should_record_input("\r"));
+        state.observe_output(INPUT_CAPTURE_ON);
+        assert!(state.should_record_input("a"));
+    }
+
+    #[test]
+    fn explicit_escape_with_st_terminator_and_last_one_wins() {
+        let mut state = InputEchoState::new();
+        state.observe_output("\x1b]9999;agr:input-off\x1b\\x\x1b]9999;agr:input-on\x07");
+        assert!(state.should_record_input("a"));
+        state.observe_output("\x1b]9999;agr:input-off\x1b\\");
+        assert!(!state.should_record_input("a"));
+    }
+
+    #[test]
+    fn escapes_do_not_count_as_prompt_text() {
+        let mut state = InputEchoState::new();
+        state.observe_output("\x1b]0;password: title\x07$ ");
+        assert!(state.should_record_input("ls"));
+    }
+
+    #[test]
+    fn transform_drops_sensitive_input_and_keeps_timing() {
+        let mut events = vec![
+            Event::output(0.5, "Password: "),
+            input("pw"),
+            input("\r"),
+            Event::output(0.3, "\r\nok\r\n"),
+            Event::marker(0.2, "done"),
+            input("q"),
+        ];
+        let total: f64 = events.iter().map(|e| e.time).sum();
+
+        let mut filter = SensitiveInputFilter::new();
+        filter.transform(&mut events);
+
+        assert_eq!(filter.dropped(), 2);
+        let kinds: Vec<EventType> = events.iter().map(|e| e.event_type).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventType::Output,
+                EventType::Output,
+                EventType::Marker,
+                EventType::Input
+            ]
+        );
+        assert!((events[1].time - 0.5).abs() < 1e-9);
+        let after: f64 = events.iter().map(|e| e.time).sum();
+        assert!((after - total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn transform_carries_trailing_dropped_time() {
+        let mut events = vec![Event::output(0.5, "PIN: "), input("1234")];
+        SensitiveInputFilter::new().transform(&mut events);
+        assert_eq!(events.len(), 1);
+        assert!((events[0].time - 0.6).abs() < 1e-9);
+    }
+}
@@ -5,16 +5,61 @@
 use std::path::Path;
 
 use anyhow::{bail, Result};
+use serde::Serialize;
 
 use super::types::{AsciicastFile, Event, EventType};
 
 /// Information about a marker
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MarkerInfo {
     pub timestamp: f64,
     pub label: String,
 }
 
+/// How `agr marker list --format` shows marker times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MarkerTimeFormat {
+    /// Minutes and seconds, e.g. 02:05 (h:mm:ss past an hour)
+    Clock,
+    /// Seconds with one decimal, e.g. 125.0
+    Seconds,
+    /// Percentage of the recording duration, e.g. 41%
+    Percent,
+}
+
+impl MarkerInfo {
+    /// The marker time in `format`.
+    ///
+    /// `duration` is the length of the whole recording and is only used by
+    /// [`MarkerTimeFormat::Percent`]; an empty recording shows `0%`.
+    pub fn format_time(&self, format: MarkerTimeFormat, duration: f64) -> String {
+        match format {
+            MarkerTimeFormat::Clock => {
+                let secs = self.timestamp.max(0.0) as u64;
+                if secs >= 3600 {
+                    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+                } else {
+                    format!("{:02}:{:02}", secs / 60, secs % 60)
+                }
+            }
+            MarkerTimeFormat::Seconds => format!("{:.1}", self.timestamp),
+            MarkerTimeFormat::Percent => {
+                let percent = if duration > 0.0 {
+                    (self.timestamp / duration * 100.0).clamp(0.0, 100.0)
+                } else {
+                    0.0
+                };
+                format!("{}%", percent.floor() as u64)
+            }
+        }
+    }
+
+    /// The marker as `[time] label` with the time in `format`.
+    pub fn display_with(&self, format: MarkerTimeFormat, duration: f64) -> String {
+        format!("[{}] {}", self.format_time(format, duration), self.label)
+    }
+}
+
 impl std::fmt::Display for MarkerInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:.1}s: {}", self.timestamp, self.label)
@@ -39,6 +84,8 @@ impl MarkerManager {
 
         let mut cast = AsciicastFile::parse(path)?;
         Self::add_marker_to_cast(&mut cast, timestamp, label)?;
+        // A marker past the end extends the recording
+        cast.refresh_duration();
         cast.write(path)?;
 
         Ok(())
@@ -52,34 +99,10 @@ impl MarkerManager {
         let marker = Event::marker(relative_time, label);
         cast.events.insert(index, marker);
 
-        // Adjust the next event's time if there is one
-        if index + 1 < cast.events.len() {
-            let cumulative_times = {
-                let mut times = Vec::with_capacity(cast.events.len());
-                let mut cumulative = 0.0;
-                for event in &cast.events {
-                    cumulative += event.time;
-                    times.push(cumulative);
-                }
-                times
-            };
-
-            // The next event's cumulative time needs to remain the same
-            // So we need to adjust its relative time
-            if let Some(next_event) = cast.events.get_mut(index + 1) {
-                let original_cumulative = cumulative_times.get(index + 1).copied().unwrap_or(0.0);
-                let new_cumulative_for_marker = timestamp;
-                let adjustment = original_cumulative - new_cumulative_for_marker - next_event.time;
-                if adjustment > 0.0 {
-                    next_event.time += adjustment;
-                } else {
-                    // If the marker is at exactly the same time or after, set next event time to 0
-                    let marker_cumulative = cumulative_times.get(index).copied().unwrap_or(0.0);
-                    if marker_cumulative >= original_cumulative {
-                        next_event.time = 0.0;
-                    }
-                }
-            }
+        // The next event keeps its cumulative time, so the marker's share
+        // of the gap comes off its relative time
+        if let Some(next_event) = cast.events.get_mut(index + 1) {
+            next_event.time = (next_event.time - relative_time).max(0.0);
         }
 
         Ok(())
@@ -125,12 +148,23 @@ impl MarkerManager {
     ///
     /// Returns the number of markers removed.
     pub fn clear_markers_from_cast(cast: &mut AsciicastFile) -> usize {
+        Self::remove_markers_from_cast(cast, |_| true)
+    }
+
+    /// Remove the markers whose label matches `remove` from a cast in memory.
+    ///
+    /// Other markers stay put; timing is preserved as in
+    /// [`Self::clear_markers_from_cast`]. Returns the number removed.
+    pub fn remove_markers_from_cast(
+        cast: &mut AsciicastFile,
+        remove: impl Fn(&str) -> bool,
+    ) -> usize {
         let mut removed = 0usize;
         let mut carry_time = 0.0f64;
         let mut output = Vec::with_capacity(cast.events.len());
 
         for mut event in cast.events.drain(..) {
-            if event.event_type == EventType::Marker {
+            if event.event_type == EventType::Marker && remove(&event.data) {
                 carry_time += event.time;
                 removed += 1;
             } else {
@@ -145,7 +179,7 @@ impl MarkerManager {
                 last.time += carry_time;
             }
         }
-        cast.events = output;
+        cast.events = output.into();
         removed
     }
 
@@ -237,6 +271,69 @@ mod tests {
         assert_eq!(format!("{}", info), "1.5s: test marker");
     }
 
+    fn marker_at(timestamp: f64) -> MarkerInfo {
+        MarkerInfo {
+            timestamp,
+            label: "Build done".to_string(),
+        }
+    }
+
+    #[test]
+    fn clock_format_shows_minutes_and_seconds() {
+        let marker = marker_at(125.0);
+        assert_eq!(marker.format_time(MarkerTimeFormat::Clock, 300.0), "02:05");
+        assert_eq!(
+            marker_at(0.4).format_time(MarkerTimeFormat::Clock, 300.0),
+            "00:00"
+        );
+        assert_eq!(
+            marker_at(3725.9).format_time(MarkerTimeFormat::Clock, 4000.0),
+            "1:02:05"
+        );
+    }
+
+    #[test]
+    fn seconds_format_shows_one_decimal() {
+        assert_eq!(
+            marker_at(125.0).format_time(MarkerTimeFormat::Seconds, 300.0),
+            "125.0"
+        );
+        assert_eq!(
+            marker_at(1.26).format_time(MarkerTimeFormat::Seconds, 300.0),
+            "1.3"
+        );
+    }
+
+    #[test]
+    fn percent_format_is_share_of_duration() {
+        assert_eq!(
+            marker_at(125.0).format_time(MarkerTimeFormat::Percent, 300.0),
+            "41%"
+        );
+        assert_eq!(
+            marker_at(300.0).format_time(MarkerTimeFormat::Percent, 300.0),
+            "100%"
+        );
+        assert_eq!(
+            marker_at(5.0).format_time(MarkerTimeFormat::Percent, 0.0),
+            "0%"
+        );
+    }
+
+    #[test]
+    fn display_with_prefixes_the_formatted_time() {
+        assert_eq!(
+            marker_at(125.0).display_with(MarkerTimeFormat::Clock, 300.0),
+            "[02:05] Build done"
+        );
+    }
+
+    #[test]
+    fn marker_info_serializes_timestamp_and_label() {
+        let json = serde_json::to_string(&marker_at(125.0)).unwrap();
+        assert_eq!(json, r#"{"timestamp":125.0,"label":"Build done"}"#);
+    }
+
     #[test]
     fn add_marker_rejects_negative_timestamp() {
         let mut cast = create_test_cast();
@@ -270,6 +367,24 @@ mod tests {
         assert_eq!(cast.events.len(), 3);
     }
 
+    #[test]
+    fn remove_markers_keeps_unmatched_markers_and_timing() {
+        let mut cast = create_test_cast();
+        MarkerManager::add_marker_to_cast(&mut cast, 0.15, "auto: first").unwrap();
+        MarkerManager::add_marker_to_cast(&mut cast, 0.2, "mine").unwrap();
+        MarkerManager::add_marker_to_cast(&mut cast, 0.5, "auto: second").unwrap();
+
+        let removed =
+            MarkerManager::remove_markers_from_cast(&mut cast, |label| label.starts_with("auto: "));
+
+        assert_eq!(removed, 2);
+        let markers = MarkerManager::list_markers_from_cast(&cast).unwrap();
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].label, "mine");
+        assert!((markers[0].timestamp - 0.2).abs() < 1e-9);
+        assert!((cast.duration() - 0.6).abs() < 1e-9);
+    }
+
     #[test]
     fn clear_markers_on_empty_returns_zero() {
         let mut cast = create_test_cast();
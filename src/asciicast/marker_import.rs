@@ -0,0 +1,404 @@
+//! Bulk marker import from JSON or CSV files.
+//!
+//! Markers generated by external tools are read as `timestamp`, `label`
+//! and optional `category` entries and injected with [`MarkerManager`].
+//! A category is written as a `[CATEGORY]` label prefix, the same form
+//! the analyzer uses.
+//!
+//! # Formats
+//!
+//! JSON is an array of objects, optionally wrapped in `{"markers": [...]}`:
+//!
+//! ```text
+//! [{"timestamp": 12.5, "label": "Build failed", "category": "failure"}]
+//! ```
+//!
+//! CSV has one marker per row. A header row naming the columns
+//! (`timestamp`, `label`, `category`) is optional; without one the columns
+//! are taken in that order. Fields may be quoted with `"`.
+//!
+//! ```text
+//! timestamp,label,category
+//! 12.5,"Build failed, retrying",failure
+//! ```
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use super::marker::{MarkerInfo, MarkerManager};
+use super::types::AsciicastFile;
+
+/// Input format of a marker import file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ImportFormat {
+    /// JSON array of marker objects
+    #[default]
+    Json,
+    /// Comma-separated rows
+    Csv,
+}
+
+impl ImportFormat {
+    /// Pick the format from a path's extension, if it names one.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(ImportFormat::Json),
+            "csv" => Some(ImportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// A marker read from an import file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ImportedMarker {
+    /// Seconds from the start of the recording
+    #[serde(alias = "time")]
+    pub timestamp: f64,
+    /// Marker description
+    pub label: String,
+    /// Optional category, written as a `[CATEGORY]` label prefix
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+impl ImportedMarker {
+    /// The label as written to the recording.
+    pub fn marker_text(&self) -> String {
+        match self.category.as_deref().map(str::trim) {
+            Some(category) if !category.is_empty() => {
+                format!("[{}] {}", category.to_uppercase(), self.label)
+            }
+            _ => self.label.clone(),
+        }
+    }
+}
+
+/// Outcome of importing markers into a recording.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    /// Markers added to the recording
+    pub added: usize,
+    /// Markers skipped because the recording already had them
+    pub duplicates: usize,
+    /// Markers skipped because they lie outside the recording
+    pub out_of_range: Vec<ImportedMarker>,
+}
+
+/// Parse markers from file content in the given format.
+pub fn parse_markers(content: &str, format: ImportFormat) -> Result<Vec<ImportedMarker>> {
+    match format {
+        ImportFormat::Json => parse_markers_json(content),
+        ImportFormat::Csv => parse_markers_csv(content),
+    }
+}
+
+/// Parse a JSON marker array (bare or wrapped in `{"markers": [...]}`).
+pub fn parse_markers_json(content: &str) -> Result<Vec<ImportedMarker>> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Document {
+        List(Vec<ImportedMarker>),
+        Wrapped { markers: Vec<ImportedMarker> },
+    }
+
+    let document: Document = serde_json::from_str(content)
+        .context("Invalid marker JSON: expected an array of {\"timestamp\", \"label\"} objects")?;
+    let markers = match document {
+        Document::List(markers) | Document::Wrapped { markers } => markers,
+    };
+    markers.iter().try_for_each(check_marker)?;
+    Ok(markers)
+}
+
+/// Parse CSV marker rows, with or without a header row.
+pub fn parse_markers_csv(content: &str) -> Result<Vec<ImportedMarker>> {
+    let mut rows = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| split_csv_line(line).map(|fields| (i + 1, fields)))
+        .peekable();
+
+    // Column order: timestamp, label, category (or as named by a header)
+    let mut columns = [Some(0), Some(1), Some(2)];
+    if let Some(Ok((_, first))) = rows.peek() {
+        let is_header = first
+            .first()
+            .is_some_and(|f| f.trim().parse::<f64>().is_err());
+        if is_header {
+            let find = |names: &[&str]| {
+                first
+                    .iter()
+                    .position(|f| names.contains(&f.trim().to_ascii_lowercase().as_str()))
+            };
+            columns = [
+                find(&["timestamp", "time"]),
+                find(&["label"]),
+                find(&["category"]),
+            ];
+            if columns[0].is_none() || columns[1].is_none() {
+                bail!("CSV header must name 'timestamp' and 'label' columns");
+            }
+            rows.next();
+        }
+    }
+
+    let mut markers = Vec::new();
+    for row in rows {
+        let (line, fields) = row?;
+        let field = |col: Option<usize>| col.and_then(|c| fields.get(c)).map(|f| f.trim());
+        let timestamp = field(columns[0])
+            .unwrap_or_default()
+            .parse::<f64>()
+            .with_context(|| format!("CSV line {}: timestamp must be a number", line))?;
+        let Some(label) = field(columns[1]) else {
+            bail!("CSV line {}: missing label", line);
+        };
+        let marker = ImportedMarker {
+            timestamp,
+            label: label.to_string(),
+            category: field(columns[2])
+                .filter(|c| !c.is_empty())
+                .map(str::to_string),
+        };
+        check_marker(&marker).with_context(|| format!("CSV line {}", line))?;
+        markers.push(marker);
+    }
+    Ok(markers)
+}
+
+/// Reject markers that can never be written.
+fn check_marker(marker: &ImportedMarker) -> Result<()> {
+    if !marker.timestamp.is_finite() || marker.timestamp < 0.0 {
+        bail!(
+            "invalid timestamp {} for '{}'",
+            marker.timestamp,
+            marker.label
+        );
+    }
+    if marker.label.trim().is_empty() {
+        bail!("marker at {}s has an empty label", marker.timestamp);
+    }
+    Ok(())
+}
+
+/// Split one CSV line into fields, honoring `"` quoting and `""` escapes.
+fn split_csv_line(line: &str) -> Result<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        bail!("unterminated quoted field in CSV line: {}", line);
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+/// Whether two timestamps are the same marker time (to the millisecond).
+fn same_time(a: f64, b: f64) -> bool {
+    (a * 1000.0).round() == (b * 1000.0).round()
+}
+
+impl MarkerManager {
+    /// Import markers into an asciicast file.
+    ///
+    /// See [`import_markers_to_cast`](Self::import_markers_to_cast). The file
+    /// is only rewritten when at least one marker was added.
+    pub fn import_markers<P: AsRef<Path>>(
+        path: P,
+        markers: &[ImportedMarker],
+    ) -> Result<ImportReport> {
+        let path = path.as_ref();
+        let mut cast = AsciicastFile::parse(path)?;
+        let report = Self::import_markers_to_cast(&mut cast, markers);
+        if report.added > 0 {
+            cast.refresh_duration();
+            cast.write(path)?;
+        }
+        Ok(report)
+    }
+
+    /// Import markers into an asciicast file in memory.
+    ///
+    /// Markers are inserted in time order. A marker with the same time and
+    /// label as one already in the recording (or earlier in `markers`) is
+    /// skipped, as is any marker past the end of the recording.
+    pub fn import_markers_to_cast(
+        cast: &mut AsciicastFile,
+        markers: &[ImportedMarker],
+    ) -> ImportReport {
+        let duration = cast.duration();
+        let mut existing: Vec<MarkerInfo> = Self::list_markers_from_cast(cast).unwrap_or_default();
+        let mut sorted: Vec<&ImportedMarker> = markers.iter().collect();
+        sorted.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+        let mut report = ImportReport::default();
+        for marker in sorted {
+            if marker.timestamp > duration + 1e-6 {
+                report.out_of_range.push(marker.clone());
+                continue;
+            }
+            let text = marker.marker_text();
+            if existing
+                .iter()
+                .any(|m| m.label == text && same_time(m.timestamp, marker.timestamp))
+            {
+                report.duplicates += 1;
+                continue;
+            }
+            if Self::add_marker_to_cast(cast, marker.timestamp, &text).is_ok() {
+                report.added += 1;
+                existing.push(MarkerInfo {
+                    timestamp: marker.timestamp,
+                    label: text,
+                });
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(timestamp: f64, label: &str, category: Option<&str>) -> ImportedMarker {
+        ImportedMarker {
+            timestamp,
+            label: label.to_string(),
+            category: category.map(str::to_string),
+        }
+    }
+
+    fn cast() -> AsciicastFile {
+        AsciicastFile::parse_str(concat!(
+            "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n",
+            "[1.0,\"o\",\"a\"]\n",
+            "[1.0,\"m\",\"existing\"]\n",
+            "[8.0,\"o\",\"b\"]\n",
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_json_array_and_wrapped_form() {
+        let expected = vec![
+            marker(1.5, "Build", None),
+            marker(3.0, "Tests failed", Some("failure")),
+        ];
+        let array = r#"[{"timestamp": 1.5, "label": "Build"},
+            {"time": 3, "label": "Tests failed", "category": "failure"}]"#;
+        assert_eq!(parse_markers_json(array).unwrap(), expected);
+
+        let wrapped = format!("{{\"markers\": {}}}", array);
+        assert_eq!(parse_markers_json(&wrapped).unwrap(), expected);
+    }
+
+    #[test]
+    fn json_rejects_bad_markers() {
+        assert!(parse_markers_json(r#"[{"label": "no time"}]"#).is_err());
+        assert!(parse_markers_json(r#"[{"timestamp": -1, "label": "x"}]"#).is_err());
+        assert!(parse_markers_json(r#"[{"timestamp": 1, "label": " "}]"#).is_err());
+    }
+
+    #[test]
+    fn parses_csv_without_header() {
+        let csv = "1.5,Build\n\n3,\"Tests failed, retrying\",failure\n";
+        assert_eq!(
+            parse_markers_csv(csv).unwrap(),
+            vec![
+                marker(1.5, "Build", None),
+                marker(3.0, "Tests failed, retrying", Some("failure")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_csv_with_header_in_any_column_order() {
+        let csv = "category,label,timestamp\nplan,\"Say \"\"hi\"\"\",2\n,Done,4\n";
+        assert_eq!(
+            parse_markers_csv(csv).unwrap(),
+            vec![
+                marker(2.0, "Say \"hi\"", Some("plan")),
+                marker(4.0, "Done", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_errors_name_the_line() {
+        let err = parse_markers_csv("timestamp,label\n1,ok\nsoon,bad\n").unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{}", err);
+        assert!(parse_markers_csv("1,\"open\n").is_err());
+        assert!(parse_markers_csv("time,text\n1,x\n").is_err());
+    }
+
+    #[test]
+    fn category_becomes_label_prefix() {
+        assert_eq!(
+            marker(1.0, "Tests pass", Some("success")).marker_text(),
+            "[SUCCESS] Tests pass"
+        );
+        assert_eq!(marker(1.0, "Plain", Some(" ")).marker_text(), "Plain");
+    }
+
+    #[test]
+    fn import_inserts_in_time_order() {
+        let mut cast = cast();
+        let report = MarkerManager::import_markers_to_cast(
+            &mut cast,
+            &[marker(9.0, "late", None), marker(0.5, "early", None)],
+        );
+        assert_eq!(report.added, 2);
+
+        let markers = MarkerManager::list_markers_from_cast(&cast).unwrap();
+        let labels: Vec<&str> = markers.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels, vec!["early", "existing", "late"]);
+        assert!((cast.duration() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn import_skips_exact_duplicates() {
+        let mut cast = cast();
+        let report = MarkerManager::import_markers_to_cast(
+            &mut cast,
+            &[
+                marker(2.0, "existing", None),
+                marker(4.0, "new", None),
+                marker(4.0, "new", None),
+                marker(4.0, "new", Some("impl")),
+            ],
+        );
+        assert_eq!(report.added, 2);
+        assert_eq!(report.duplicates, 2);
+        assert_eq!(MarkerManager::count_markers_from_cast(&cast), 3);
+    }
+
+    #[test]
+    fn import_reports_out_of_range_markers() {
+        let mut cast = cast();
+        let report = MarkerManager::import_markers_to_cast(
+            &mut cast,
+            &[marker(10.0, "at end", None), marker(12.5, "past end", None)],
+        );
+        assert_eq!(report.added, 1);
+        assert_eq!(report.out_of_range, vec![marker(12.5, "past end", None)]);
+    }
+}
@@ -10,25 +10,67 @@
 //! - [`types`] - Core type definitions (Header, Event, AsciicastFile)
 //! - [`reader`] - Parsing asciicast files from various sources
 //! - [`writer`] - Writing asciicast files to various destinations
+//! - [`export`] - Flattening markers for plain asciicast export
 //! - [`marker`] - Adding and listing markers in recordings
+//! - [`marker_import`] - Importing markers from JSON/CSV files
+//! - [`input_echo`] - Dropping keystrokes typed at password prompts
+//! - [`redact`] - Secret redaction transform
+//! - [`remote`] - Downloading recordings from `http(s)://` URLs
+//! - [`snapshot`] - Rendering the screen at a point in time
+//! - [`source`] - Reading a recording from a file, stdin or a URL
+//! - [`subtitles`] - Exporting markers as WebVTT/SRT subtitles
 //! - [`transform`] - Event transformation pipeline utilities
 //! - [`transform_ops`] - High-level file transform operations (backup, restore)
+//! - [`validate`] - Recording health checks for `agr validate`
 
+mod export;
+mod input_echo;
 pub mod integrity;
 pub mod marker;
+pub mod marker_import;
 mod reader;
+mod redact;
+pub mod remote;
 mod silence_removal;
+mod snapshot;
+mod source;
+mod subtitles;
 mod transform;
 pub mod transform_ops;
 mod types;
+pub mod validate;
 mod writer;
 
+// Re-export export types
+pub use export::{marker_comment, ExportFormat, FlattenMarkers, MarkerFlattening};
+
 // Re-export marker types
-pub use marker::{MarkerInfo, MarkerManager};
+pub use marker::{MarkerInfo, MarkerManager, MarkerTimeFormat};
+
+// Re-export marker import types
+pub use marker_import::{ImportFormat, ImportReport, ImportedMarker};
+
+// Re-export input suppression types
+pub use input_echo::{InputEchoState, SensitiveInputFilter, INPUT_CAPTURE_OFF, INPUT_CAPTURE_ON};
+
+// Re-export tolerant parse report
+pub use reader::ParseReport;
+
+// Re-export redaction types
+pub use redact::{SecretRedaction, BUILTIN_PATTERNS, REDACTED};
 
 // Re-export silence removal types
 pub use silence_removal::{SilenceRemoval, DEFAULT_SILENCE_THRESHOLD};
 
+// Re-export snapshot types
+pub use snapshot::{styled_lines_to_ansi, SnapshotAt, SnapshotFormat};
+
+// Re-export recording source types
+pub use source::{CastSource, STDIN_ARG};
+
+// Re-export subtitle export types
+pub use subtitles::{markers_to_subtitles, SubtitleFormat, LAST_CUE_SECS};
+
 // Re-export transform types
 pub use transform::{Transform, TransformChain};
 
@@ -38,5 +80,8 @@ pub use transform_ops::{apply_transforms, TransformResult};
 // Re-export integrity types
 pub use integrity::{check_file_integrity, diagnose, repair, DiagnoseResult, LineDiagnostic};
 
+// Re-export validation types
+pub use validate::{validate_file, validate_str, Problem, Severity, ValidationReport};
+
 // Re-export core types
-pub use types::{AsciicastFile, EnvInfo, Event, EventType, Header, TermInfo};
+pub use types::{AsciicastFile, EnvInfo, Event, EventList, EventType, Header, TermInfo};
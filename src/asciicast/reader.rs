@@ -20,6 +20,10 @@
 //! - JSON parsing errors include the line number
 //! - Version mismatches report the found version
 //!
+//! The tolerant variants ([`AsciicastFile::parse_tolerant`] and friends)
+//! skip malformed event lines instead of failing, and report them in a
+//! [`ParseReport`]. A missing or invalid header is still an error.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -36,13 +40,55 @@
 //! ```
 
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 
 use anyhow::{bail, Context, Result};
 
 use super::types::{AsciicastFile, Event, EventType, Header};
 
+/// Event lines skipped by a tolerant parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseReport {
+    /// 1-based line numbers of the skipped lines, in file order.
+    pub skipped_lines: Vec<usize>,
+}
+
+impl ParseReport {
+    /// Number of skipped lines.
+    pub fn skipped(&self) -> usize {
+        self.skipped_lines.len()
+    }
+
+    /// Whether every event line parsed.
+    pub fn is_clean(&self) -> bool {
+        self.skipped_lines.is_empty()
+    }
+
+    /// A one-line warning describing the skipped lines, if any.
+    pub fn warning(&self) -> Option<String> {
+        const MAX_LISTED: usize = 5;
+
+        if self.is_clean() {
+            return None;
+        }
+        let mut listed: Vec<String> = self
+            .skipped_lines
+            .iter()
+            .take(MAX_LISTED)
+            .map(|n| n.to_string())
+            .collect();
+        if self.skipped() > MAX_LISTED {
+            listed.push("...".to_string());
+        }
+        Some(format!(
+            "Skipped {} malformed event line(s) (line {}). Run 'agr repair' to remove them.",
+            self.skipped(),
+            listed.join(", ")
+        ))
+    }
+}
+
 impl Event {
     /// Parse an event from a JSON line.
     ///
@@ -119,6 +165,45 @@ impl AsciicastFile {
     /// - The version is not 3
     /// - Any event line fails to parse
     pub fn parse_reader<R: BufRead>(reader: R) -> Result<Self> {
+        Self::parse_lines(reader, true).map(|(file, _)| file)
+    }
+
+    /// Parse an asciicast v3 file, skipping malformed event lines.
+    ///
+    /// Opens the file and delegates to
+    /// [`parse_reader_tolerant`](Self::parse_reader_tolerant).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or the header is invalid.
+    pub fn parse_tolerant<P: AsRef<Path>>(path: P) -> Result<(Self, ParseReport)> {
+        let path = path.as_ref();
+        let file =
+            fs::File::open(path).with_context(|| format!("Failed to open file: {:?}", path))?;
+        Self::parse_reader_tolerant(BufReader::new(file))
+    }
+
+    /// Parse from a buffered reader, skipping malformed event lines.
+    ///
+    /// Event lines that are not valid JSON events (or not valid UTF-8) are
+    /// left out of the result and their line numbers recorded in the
+    /// returned [`ParseReport`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is empty, the header is invalid or not
+    /// version 3, or reading fails for a reason other than bad line content.
+    pub fn parse_reader_tolerant<R: BufRead>(reader: R) -> Result<(Self, ParseReport)> {
+        Self::parse_lines(reader, false)
+    }
+
+    /// String variant of [`parse_reader_tolerant`](Self::parse_reader_tolerant).
+    pub fn parse_str_tolerant(content: &str) -> Result<(Self, ParseReport)> {
+        Self::parse_reader_tolerant(BufReader::new(content.as_bytes()))
+    }
+
+    /// Shared parser; `strict` fails on the first malformed event line.
+    fn parse_lines<R: BufRead>(reader: R, strict: bool) -> Result<(Self, ParseReport)> {
         let mut lines = reader.lines();
 
         // First line is the header
@@ -139,20 +224,100 @@ impl AsciicastFile {
 
         // Remaining lines are events
         let mut events = Vec::new();
+        let mut report = ParseReport::default();
         for (line_num, line_result) in lines.enumerate() {
-            let line =
-                line_result.with_context(|| format!("Failed to read line {}", line_num + 2))?;
+            let line_number = line_num + 2;
+            let line = match line_result {
+                Ok(line) => line,
+                // Invalid UTF-8: the line is consumed, so reading can go on
+                Err(e) if !strict && e.kind() == io::ErrorKind::InvalidData => {
+                    report.skipped_lines.push(line_number);
+                    continue;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to read line {}", line_number))
+                }
+            };
 
             if line.trim().is_empty() {
                 continue;
             }
 
-            let event = Event::from_json(&line)
-                .with_context(|| format!("Failed to parse event on line {}", line_num + 2))?;
-            events.push(event);
+            match Event::from_json(&line) {
+                Ok(event) => events.push(event),
+                Err(_) if !strict => report.skipped_lines.push(line_number),
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("Failed to parse event on line {}", line_number))
+                }
+            }
+        }
+
+        Ok((
+            AsciicastFile {
+                header,
+                events: events.into(),
+            },
+            report,
+        ))
+    }
+
+    /// Read only the header and total duration of a file.
+    ///
+    /// Uses the header `duration` when present. Otherwise streams the event
+    /// lines and sums their relative timestamps without decoding event data,
+    /// which keeps this cheap for large recordings. Lines whose timestamp
+    /// cannot be read are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or the header is invalid.
+    pub fn read_header_and_duration<P: AsRef<Path>>(path: P) -> Result<(Header, f64)> {
+        let path = path.as_ref();
+        let file =
+            fs::File::open(path).with_context(|| format!("Failed to open file: {:?}", path))?;
+        Self::read_header_and_duration_from(BufReader::new(file))
+    }
+
+    /// Read only the header line of an asciicast file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or the header is invalid.
+    pub fn read_header<P: AsRef<Path>>(path: P) -> Result<Header> {
+        let path = path.as_ref();
+        let file =
+            fs::File::open(path).with_context(|| format!("Failed to open file: {:?}", path))?;
+        let header_line = BufReader::new(file)
+            .lines()
+            .next()
+            .context("File is empty")?
+            .context("Failed to read header line")?;
+        serde_json::from_str(&header_line).context("Failed to parse header")
+    }
+
+    /// Reader-based variant of [`read_header_and_duration`](Self::read_header_and_duration).
+    pub fn read_header_and_duration_from<R: BufRead>(reader: R) -> Result<(Header, f64)> {
+        let mut lines = reader.lines();
+        let header_line = lines
+            .next()
+            .context("File is empty")?
+            .context("Failed to read header line")?;
+        let header: Header =
+            serde_json::from_str(&header_line).context("Failed to parse header")?;
+
+        if let Some(duration) = header.duration {
+            return Ok((header, duration));
         }
 
-        Ok(AsciicastFile { header, events })
+        let mut duration = 0.0;
+        for line in lines {
+            let line = line.context("Failed to read event line")?;
+            if let Some(time) = leading_event_time(&line) {
+                duration += time;
+            }
+        }
+        Ok((header, duration))
     }
 
     /// Parse an asciicast v3 file from a string.
@@ -168,6 +333,13 @@ impl AsciicastFile {
     }
 }
 
+/// Extract the relative timestamp from an event line (`[0.5, "o", ...]`).
+fn leading_event_time(line: &str) -> Option<f64> {
+    let rest = line.trim_start().strip_prefix('[')?;
+    let end = rest.find(',')?;
+    rest[..end].trim().parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,4 +413,97 @@ mod tests {
         let result = AsciicastFile::parse_str(&content);
         assert!(result.is_err());
     }
+
+    const ONE_GARBAGE_LINE: &str =
+        "{\"version\":3}\n[0.1, \"o\", \"hello\"]\n{\"garbage\n[0.2, \"o\", \" world\"]\n";
+
+    #[test]
+    fn parse_strict_fails_on_garbage_line() {
+        let err = AsciicastFile::parse_str(ONE_GARBAGE_LINE).unwrap_err();
+        assert!(format!("{:#}", err).contains("line 3"));
+    }
+
+    #[test]
+    fn parse_tolerant_skips_garbage_line() {
+        let (file, report) = AsciicastFile::parse_str_tolerant(ONE_GARBAGE_LINE).unwrap();
+        let data: Vec<&str> = file.events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, vec!["hello", " world"]);
+        assert_eq!(report.skipped(), 1);
+        assert_eq!(report.skipped_lines, vec![3]);
+        assert!(report.warning().unwrap().contains("line 3"));
+    }
+
+    #[test]
+    fn parse_tolerant_clean_file_has_empty_report() {
+        let content = "{\"version\":3}\n[0.1, \"o\", \"hello\"]\n\n";
+        let (file, report) = AsciicastFile::parse_str_tolerant(content).unwrap();
+        assert_eq!(file.events.len(), 1);
+        assert!(report.is_clean());
+        assert_eq!(report.warning(), None);
+    }
+
+    #[test]
+    fn parse_tolerant_skips_invalid_utf8_and_null_bytes() {
+        let mut content = b"{\"version\":3}\n[0.1, \"o\", \"a\"]\n".to_vec();
+        content.extend_from_slice(&[0xff, 0xfe, b'\n']);
+        content.extend_from_slice(&[0u8; 16]);
+        content.extend_from_slice(b"\n[0.2, \"o\", \"b\"]\n");
+
+        let (file, report) = AsciicastFile::parse_reader_tolerant(&content[..]).unwrap();
+        assert_eq!(file.events.len(), 2);
+        assert_eq!(report.skipped_lines, vec![3, 4]);
+    }
+
+    #[test]
+    fn parse_tolerant_still_fails_on_bad_header() {
+        assert!(AsciicastFile::parse_str_tolerant("not a header\n[0.1, \"o\", \"x\"]\n").is_err());
+        assert!(AsciicastFile::parse_str_tolerant("{\"version\":2}\n").is_err());
+        assert!(AsciicastFile::parse_str_tolerant("").is_err());
+    }
+
+    #[test]
+    fn parse_report_warning_truncates_long_lists() {
+        let report = ParseReport {
+            skipped_lines: (2..10).collect(),
+        };
+        assert_eq!(
+            report.warning().unwrap(),
+            "Skipped 8 malformed event line(s) (line 2, 3, 4, 5, 6, ...). Run 'agr repair' to remove them."
+        );
+    }
+
+    #[test]
+    fn read_header_and_duration_prefers_header_duration() {
+        let content = "{\"version\":3,\"duration\":42.5}\n[0.1, \"o\", \"x\"]\n";
+        let (header, duration) =
+            AsciicastFile::read_header_and_duration_from(content.as_bytes()).unwrap();
+        assert_eq!(header.version, 3);
+        assert_eq!(duration, 42.5);
+    }
+
+    #[test]
+    fn read_header_and_duration_sums_event_times() {
+        let content = "{\"version\":3}\n[0.5, \"o\", \"a, b\"]\n\n[1.25,\"m\",\"\"]\ngarbage\n";
+        let (_, duration) =
+            AsciicastFile::read_header_and_duration_from(content.as_bytes()).unwrap();
+        assert!((duration - 1.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn read_header_and_duration_rejects_bad_header() {
+        assert!(AsciicastFile::read_header_and_duration_from("nope\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn read_header_reads_title_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("a.cast");
+        fs::write(
+            &path,
+            "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24},\"title\":\"claude chat\"}\nnot an event\n",
+        )
+        .unwrap();
+        let header = AsciicastFile::read_header(&path).unwrap();
+        assert_eq!(header.title.as_deref(), Some("claude chat"));
+    }
 }
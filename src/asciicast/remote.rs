@@ -0,0 +1,410 @@
+//! Reading recordings from `http(s)://` URLs.
+//!
+//! `agr play https://...` and `agr analyze https://...` download the cast
+//! before processing it. The download goes through the [`HttpFetcher`]
+//! trait; [`CurlFetcher`] runs the `curl` CLI, tests use a stub. A download
+//! is capped at [`MAX_DOWNLOAD_BYTES`] and refused when the server says it
+//! is something other than a recording (an HTML page, an image, ...).
+//!
+//! An asciinema.org recording page (`https://asciinema.org/a/<id>`) is
+//! rewritten to its `.cast` download URL.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use humansize::{format_size, BINARY};
+
+/// Largest recording that is downloaded (64 MiB).
+pub const MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Content types a recording may be served with.
+///
+/// Servers commonly label `.cast` files as JSON, plain text or raw bytes.
+const CAST_CONTENT_TYPES: &[&str] = &[
+    "application/x-asciicast",
+    "application/json",
+    "application/x-ndjson",
+    "application/octet-stream",
+    "text/plain",
+];
+
+/// A downloaded response body and its `Content-Type`, if the server sent one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Download {
+    pub body: Vec<u8>,
+    pub content_type: Option<String>,
+}
+
+/// Fetches a URL over HTTP(S).
+pub trait HttpFetcher {
+    /// Download `url`, reading at most `max_bytes` of the body.
+    ///
+    /// A body larger than `max_bytes` may be cut off or rejected; callers
+    /// treat a body longer than `max_bytes` as too large.
+    fn fetch(&self, url: &str, max_bytes: u64) -> Result<Download>;
+}
+
+/// [`HttpFetcher`] that runs the `curl` CLI.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurlFetcher;
+
+/// curl exit code for `--max-filesize` being exceeded.
+const CURL_FILESIZE_EXCEEDED: i32 = 63;
+
+impl HttpFetcher for CurlFetcher {
+    fn fetch(&self, url: &str, max_bytes: u64) -> Result<Download> {
+        // The content type follows the body on its own line
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--location", "--fail"])
+            .arg("--max-filesize")
+            .arg(max_bytes.to_string())
+            .args(["--write-out", "\n%{content_type}"])
+            .arg(url)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run curl (it is needed to download recordings)")?;
+
+        // Content types are short; anything past this is an oversized body
+        let limit = max_bytes + 1 + 256;
+        let mut output = Vec::new();
+        child
+            .stdout
+            .take()
+            .context("Failed to read curl output")?
+            .take(limit)
+            .read_to_end(&mut output)
+            .context("Failed to read curl output")?;
+        if output.len() as u64 >= limit {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!(too_large(url, max_bytes));
+        }
+
+        let result = child.wait_with_output().context("Failed to run curl")?;
+        if !result.status.success() {
+            if result.status.code() == Some(CURL_FILESIZE_EXCEEDED) {
+                bail!(too_large(url, max_bytes));
+            }
+            bail!(
+                "Failed to download {}: {}",
+                url,
+                String::from_utf8_lossy(&result.stderr).trim()
+            );
+        }
+
+        let split = output.iter().rposition(|&b| b == b'\n').unwrap_or(0);
+        let content_type = String::from_utf8_lossy(&output[split..]).trim().to_string();
+        output.truncate(split);
+        Ok(Download {
+            body: output,
+            content_type: (!content_type.is_empty()).then_some(content_type),
+        })
+    }
+}
+
+fn too_large(url: &str, max_bytes: u64) -> String {
+    format!(
+        "Recording at {} is larger than the {} download limit",
+        url,
+        format_size(max_bytes, BINARY)
+    )
+}
+
+/// Whether a command-line argument is an `http://` or `https://` URL.
+pub fn is_url(arg: &str) -> bool {
+    let lower = arg.get(..8).unwrap_or(arg).to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// The URL to download for `url`.
+///
+/// `https://asciinema.org/a/<id>` (a player page) becomes
+/// `https://asciinema.org/a/<id>.cast`; other URLs are unchanged.
+pub fn cast_url(url: &str) -> String {
+    let (base, query) = match url.find(['?', '#']) {
+        Some(i) => url.split_at(i),
+        None => (url, ""),
+    };
+    let rest = base
+        .strip_prefix("https://")
+        .or_else(|| base.strip_prefix("http://"))
+        .unwrap_or(base);
+    let mut parts = rest.trim_end_matches('/').split('/');
+    let is_page = matches!(
+        (parts.next(), parts.next(), parts.next(), parts.next()),
+        (Some("asciinema.org" | "www.asciinema.org"), Some("a"), Some(id), None)
+            if !id.is_empty() && !id.contains('.')
+    );
+    if is_page {
+        format!("{}.cast{}", base.trim_end_matches('/'), query)
+    } else {
+        url.to_string()
+    }
+}
+
+/// File name for a downloaded recording, e.g. `12345.cast`.
+pub fn file_name(url: &str) -> String {
+    let url = cast_url(url);
+    let base = url.split(['?', '#']).next().unwrap_or_default();
+    let path = base
+        .split_once("://")
+        .map_or(base, |(_, rest)| rest)
+        .split_once('/')
+        .map_or("", |(_, path)| path);
+    let last = path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let stem = last.strip_suffix(".cast").unwrap_or(last);
+    let stem: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "download.cast".to_string()
+    } else {
+        format!("{}.cast", stem)
+    }
+}
+
+/// Whether a `Content-Type` header value can be a recording.
+///
+/// A missing content type is accepted; parameters such as `charset` are
+/// ignored.
+pub fn is_cast_content_type(content_type: Option<&str>) -> bool {
+    let Some(content_type) = content_type else {
+        return true;
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    CAST_CONTENT_TYPES.contains(&mime.as_str())
+}
+
+/// Download the recording at `url`, checking its size and content type.
+pub fn download(fetcher: &dyn HttpFetcher, url: &str) -> Result<Vec<u8>> {
+    let url = cast_url(url);
+    let download = fetcher.fetch(&url, MAX_DOWNLOAD_BYTES)?;
+    if download.body.len() as u64 > MAX_DOWNLOAD_BYTES {
+        bail!(too_large(&url, MAX_DOWNLOAD_BYTES));
+    }
+    if !is_cast_content_type(download.content_type.as_deref()) {
+        bail!(
+            "{} is not an asciicast recording (Content-Type: {})\nHint: Use the URL of the .cast file itself, not a web page.",
+            url,
+            download.content_type.unwrap_or_default()
+        );
+    }
+    Ok(download.body)
+}
+
+/// Download the recording at `url` into `dir`, returning the file path.
+///
+/// An existing file of the same name is an error, never overwritten.
+pub fn download_to(fetcher: &dyn HttpFetcher, url: &str, dir: &Path) -> Result<PathBuf> {
+    let body = download(fetcher, url)?;
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    let path = dir.join(file_name(url));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(&body))
+        .with_context(|| format!("Failed to write downloaded recording: {}", path.display()))?;
+    Ok(path)
+}
+
+/// Download the recording at `url` into a new private temporary directory.
+///
+/// The directory (`agr-download-*`, readable only by the user on Unix) is
+/// unique to this call and is not removed, so the caller can tell the user
+/// where the copy was kept.
+pub fn download_to_temp_dir(fetcher: &dyn HttpFetcher, url: &str) -> Result<PathBuf> {
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("agr-download-");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(std::fs::Permissions::from_mode(0o700));
+    }
+    let dir = builder
+        .tempdir()
+        .context("Failed to create a temporary directory for the download")?;
+    let path = download_to(fetcher, url, dir.path())?;
+    let _ = dir.keep();
+    Ok(path)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Fetcher that returns a canned response and records the URL asked for.
+    pub(crate) struct StubFetcher {
+        pub response: Download,
+        pub requested: RefCell<Vec<String>>,
+    }
+
+    impl StubFetcher {
+        pub fn new(body: &str, content_type: Option<&str>) -> Self {
+            Self {
+                response: Download {
+                    body: body.as_bytes().to_vec(),
+                    content_type: content_type.map(String::from),
+                },
+                requested: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl HttpFetcher for StubFetcher {
+        fn fetch(&self, url: &str, _max_bytes: u64) -> Result<Download> {
+            self.requested.borrow_mut().push(url.to_string());
+            Ok(self.response.clone())
+        }
+    }
+
+    #[test]
+    fn urls_are_detected_by_scheme() {
+        assert!(is_url("https://asciinema.org/a/12345"));
+        assert!(is_url("http://example.com/demo.cast"));
+        assert!(is_url("HTTPS://EXAMPLE.COM/demo.cast"));
+        assert!(!is_url("claude/session.cast"));
+        assert!(!is_url("/tmp/https://x.cast"));
+        assert!(!is_url("ftp://example.com/demo.cast"));
+        assert!(!is_url("-"));
+        assert!(!is_url("http:"));
+    }
+
+    #[test]
+    fn asciinema_pages_map_to_cast_downloads() {
+        assert_eq!(
+            cast_url("https://asciinema.org/a/12345"),
+            "https://asciinema.org/a/12345.cast"
+        );
+        assert_eq!(
+            cast_url("https://asciinema.org/a/abc/?t=5"),
+            "https://asciinema.org/a/abc.cast?t=5"
+        );
+        assert_eq!(
+            cast_url("https://asciinema.org/a/12345.cast"),
+            "https://asciinema.org/a/12345.cast"
+        );
+        assert_eq!(
+            cast_url("https://example.com/a/12345"),
+            "https://example.com/a/12345"
+        );
+    }
+
+    #[test]
+    fn file_names_come_from_the_url_path() {
+        assert_eq!(file_name("https://asciinema.org/a/12345"), "12345.cast");
+        assert_eq!(
+            file_name("https://example.com/x/demo.cast?dl=1"),
+            "demo.cast"
+        );
+        assert_eq!(file_name("https://example.com/my%20demo"), "my_20demo.cast");
+        assert_eq!(file_name("https://example.com/"), "download.cast");
+    }
+
+    #[test]
+    fn content_types_other_than_recordings_are_rejected() {
+        assert!(is_cast_content_type(None));
+        assert!(is_cast_content_type(Some("application/x-asciicast")));
+        assert!(is_cast_content_type(Some("text/plain; charset=utf-8")));
+        assert!(is_cast_content_type(Some("Application/JSON")));
+        assert!(!is_cast_content_type(Some("text/html; charset=utf-8")));
+        assert!(!is_cast_content_type(Some("image/png")));
+    }
+
+    #[test]
+    fn download_fetches_the_cast_url() {
+        let fetcher = StubFetcher::new("{\"version\":3}\n", Some("application/x-asciicast"));
+        let body = download(&fetcher, "https://asciinema.org/a/42").unwrap();
+
+        assert_eq!(body, b"{\"version\":3}\n");
+        assert_eq!(
+            fetcher.requested.borrow().as_slice(),
+            ["https://asciinema.org/a/42.cast"]
+        );
+    }
+
+    #[test]
+    fn download_rejects_html_with_a_clear_error() {
+        let fetcher = StubFetcher::new("<!doctype html>", Some("text/html; charset=utf-8"));
+        let err = download(&fetcher, "https://example.com/page").unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("is not an asciicast recording"));
+        assert!(message.contains("text/html"));
+    }
+
+    #[test]
+    fn download_enforces_the_size_cap() {
+        let oversized = "x".repeat(MAX_DOWNLOAD_BYTES as usize + 1);
+        let fetcher = StubFetcher::new(&oversized, None);
+        let err = download(&fetcher, "https://example.com/big.cast").unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("larger than the 64 MiB download limit"));
+    }
+
+    #[test]
+    fn download_to_writes_the_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let fetcher = StubFetcher::new("{\"version\":3}\n", None);
+        let path = download_to(&fetcher, "https://asciinema.org/a/7", dir.path()).unwrap();
+
+        assert_eq!(path, dir.path().join("7.cast"));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "{\"version\":3}\n");
+    }
+
+    #[test]
+    fn download_to_never_overwrites_an_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("demo.cast"), "mine").unwrap();
+        let fetcher = StubFetcher::new("{\"version\":3}\n", None);
+
+        assert!(download_to(&fetcher, "https://example.com/demo.cast", dir.path()).is_err());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("demo.cast")).unwrap(),
+            "mine"
+        );
+    }
+
+    #[test]
+    fn downloads_to_temp_dirs_do_not_collide() {
+        let fetcher = StubFetcher::new("{\"version\":3}\n", None);
+        let first = download_to_temp_dir(&fetcher, "https://a.example/demo.cast").unwrap();
+        let second = download_to_temp_dir(&fetcher, "https://b.example/demo.cast").unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(first.file_name(), second.file_name());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let dir = first.parent().unwrap();
+            let mode = std::fs::metadata(dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o077, 0);
+        }
+        for path in [first, second] {
+            std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,400 @@
+//! Render the terminal screen of a recording at a point in time.
+//!
+//! Replays output and resize events into a [`TerminalBuffer`] up to the
+//! requested timestamp, then renders the screen as plain text or as text
+//! with ANSI color codes and any captured inline images. A transcript
+//! replays the whole recording and keeps the lines that scrolled off the
+//! top of the screen as well.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+
+use super::types::{AsciicastFile, EventType};
+use crate::terminal::{Cell, CellStyle, InlineImage, StyledLine, TerminalBuffer};
+
+/// A point in a recording, either relative to its length or absolute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnapshotAt {
+    /// Percentage of the total duration (0-100)
+    Percent(f64),
+    /// Seconds from the start of the recording
+    Seconds(f64),
+}
+
+impl SnapshotAt {
+    /// Resolve to an absolute timestamp, clamped to the recording duration.
+    pub fn resolve(&self, duration: f64) -> f64 {
+        let time = match *self {
+            SnapshotAt::Percent(pct) => duration * pct / 100.0,
+            SnapshotAt::Seconds(secs) => secs,
+        };
+        time.clamp(0.0, duration.max(0.0))
+    }
+}
+
+impl FromStr for SnapshotAt {
+    type Err = anyhow::Error;
+
+    /// Parse `50%` as a percentage and `12.5` or `12.5s` as seconds.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(pct) = s.strip_suffix('%') {
+            let pct: f64 = pct
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid percentage: {}", s))?;
+            if !(0.0..=100.0).contains(&pct) {
+                bail!("Percentage must be between 0% and 100%, got {}", s);
+            }
+            return Ok(SnapshotAt::Percent(pct));
+        }
+
+        let secs = s.strip_suffix('s').unwrap_or(s);
+        let secs: f64 = secs
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid time '{}' (use e.g. 50% or 12.5)", s))?;
+        if !secs.is_finite() || secs < 0.0 {
+            bail!("Time must be a non-negative number of seconds, got {}", s);
+        }
+        Ok(SnapshotAt::Seconds(secs))
+    }
+}
+
+impl fmt::Display for SnapshotAt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotAt::Percent(pct) => write!(f, "{}%", pct),
+            SnapshotAt::Seconds(secs) => write!(f, "{}s", secs),
+        }
+    }
+}
+
+/// Output format for a rendered snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SnapshotFormat {
+    /// Plain text without colors
+    #[default]
+    Text,
+    /// Text with ANSI color and attribute codes
+    Ansi,
+}
+
+impl AsciicastFile {
+    /// Replay the recording into a terminal buffer up to `timestamp`.
+    ///
+    /// Output and resize events at or before the timestamp are applied,
+    /// starting from the header's terminal size.
+    pub fn terminal_at(&self, timestamp: f64) -> TerminalBuffer {
+        let (cols, rows) = self.terminal_size();
+        let mut buffer = TerminalBuffer::new(cols as usize, rows as usize);
+        let mut cumulative = 0.0;
+
+        for event in &self.events {
+            cumulative += event.time;
+            if cumulative > timestamp {
+                break;
+            }
+            match event.event_type {
+                EventType::Output => buffer.process(&event.data, None),
+                EventType::Resize => {
+                    if let Some((cols, rows)) = event.parse_resize() {
+                        buffer.resize(cols as usize, rows as usize);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        buffer
+    }
+
+    /// Render the screen at `timestamp` in the given format.
+    ///
+    /// The ANSI format also re-emits captured inline images (Sixel, iTerm2)
+    /// at the position they were received.
+    pub fn snapshot_at(&self, timestamp: f64, format: SnapshotFormat) -> String {
+        let buffer = self.terminal_at(timestamp);
+        match format {
+            SnapshotFormat::Text => buffer.to_string(),
+            SnapshotFormat::Ansi => styled_lines_to_ansi(&buffer.styled_lines(), buffer.images()),
+        }
+    }
+}
+
+impl AsciicastFile {
+    /// Plain text of everything the recording printed.
+    ///
+    /// Replays all events, collecting each line as it scrolls off the top
+    /// of the screen, then appends the final screen. Lines are trimmed of
+    /// trailing whitespace and trailing empty lines are dropped. Content
+    /// erased in place (e.g. by a full-screen clear) is not recovered.
+    pub fn transcript(&self) -> String {
+        let (cols, rows) = self.terminal_size();
+        let mut buffer = TerminalBuffer::new(cols as usize, rows as usize);
+        let mut lines: Vec<String> = Vec::new();
+
+        for event in &self.events {
+            match event.event_type {
+                EventType::Output => {
+                    let mut on_scroll = |row: Vec<Cell>| lines.push(row_text(&row));
+                    buffer.process(&event.data, Some(&mut on_scroll));
+                }
+                EventType::Resize => {
+                    if let Some((cols, rows)) = event.parse_resize() {
+                        buffer.resize(cols as usize, rows as usize);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        lines.extend(buffer.to_string().lines().map(str::to_string));
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+}
+
+fn row_text(row: &[Cell]) -> String {
+    row.iter()
+        .map(|cell| cell.char)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// Render styled lines as text with ANSI escape codes.
+///
+/// Codes are only emitted when the style changes, and every line that
+/// leaves a non-default style active ends with a reset. Inline images are
+/// written verbatim at their anchor cell. Trailing empty lines are dropped,
+/// matching the plain text rendering.
+pub fn styled_lines_to_ansi(lines: &[StyledLine], images: &[InlineImage]) -> String {
+    let mut rendered: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .map(|(row, line)| {
+            let row_images: Vec<&InlineImage> = images.iter().filter(|i| i.row == row).collect();
+            styled_line_to_ansi(line, &row_images)
+        })
+        .collect();
+    while rendered.last().map(|s| s.is_empty()).unwrap_or(false) {
+        rendered.pop();
+    }
+    rendered.join("\n")
+}
+
+fn styled_line_to_ansi(line: &StyledLine, images: &[&InlineImage]) -> String {
+    let mut out = String::new();
+    let mut current = CellStyle::default();
+
+    for (col, cell) in line.cells.iter().enumerate() {
+        for image in images.iter().filter(|i| i.col == col) {
+            if current != CellStyle::default() {
+                out.push_str("\x1b[0m");
+                current = CellStyle::default();
+            }
+            out.push_str(&image.sequence);
+        }
+        if cell.style != current {
+            if current != CellStyle::default() {
+                out.push_str("\x1b[0m");
+            }
+            cell.style.to_ansi_fg(&mut out);
+            cell.style.to_ansi_bg(&mut out);
+            cell.style.to_ansi_attrs(&mut out);
+            current = cell.style;
+        }
+        out.push(cell.char);
+    }
+
+    if current != CellStyle::default() {
+        out.push_str("\x1b[0m");
+    }
+    for image in images.iter().filter(|i| i.col >= line.cells.len()) {
+        out.push_str(&image.sequence);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asciicast::Event;
+    use crate::terminal::{Cell, Color};
+
+    fn file_with(events: Vec<Event>) -> AsciicastFile {
+        let mut file =
+            AsciicastFile::parse_str(r#"{"version":3,"term":{"cols":80,"rows":24}}"#).unwrap();
+        file.events = events.into();
+        file
+    }
+
+    #[test]
+    fn parse_percentage() {
+        assert_eq!(
+            "50%".parse::<SnapshotAt>().unwrap(),
+            SnapshotAt::Percent(50.0)
+        );
+        assert_eq!(
+            "0%".parse::<SnapshotAt>().unwrap(),
+            SnapshotAt::Percent(0.0)
+        );
+        assert_eq!(
+            "100%".parse::<SnapshotAt>().unwrap(),
+            SnapshotAt::Percent(100.0)
+        );
+        assert_eq!(
+            "12.5%".parse::<SnapshotAt>().unwrap(),
+            SnapshotAt::Percent(12.5)
+        );
+    }
+
+    #[test]
+    fn parse_seconds() {
+        assert_eq!(
+            "12".parse::<SnapshotAt>().unwrap(),
+            SnapshotAt::Seconds(12.0)
+        );
+        assert_eq!(
+            "1.5".parse::<SnapshotAt>().unwrap(),
+            SnapshotAt::Seconds(1.5)
+        );
+        assert_eq!(
+            "3s".parse::<SnapshotAt>().unwrap(),
+            SnapshotAt::Seconds(3.0)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        assert!("".parse::<SnapshotAt>().is_err());
+        assert!("abc".parse::<SnapshotAt>().is_err());
+        assert!("150%".parse::<SnapshotAt>().is_err());
+        assert!("-5%".parse::<SnapshotAt>().is_err());
+        assert!("-1".parse::<SnapshotAt>().is_err());
+        assert!("inf".parse::<SnapshotAt>().is_err());
+    }
+
+    #[test]
+    fn resolve_percentage_and_seconds() {
+        assert_eq!(SnapshotAt::Percent(50.0).resolve(10.0), 5.0);
+        assert_eq!(SnapshotAt::Percent(100.0).resolve(10.0), 10.0);
+        assert_eq!(SnapshotAt::Seconds(3.0).resolve(10.0), 3.0);
+    }
+
+    #[test]
+    fn resolve_clamps_seconds_past_end() {
+        assert_eq!(SnapshotAt::Seconds(99.0).resolve(10.0), 10.0);
+        assert_eq!(SnapshotAt::Seconds(1.0).resolve(0.0), 0.0);
+    }
+
+    #[test]
+    fn terminal_at_stops_at_timestamp() {
+        let file = file_with(vec![
+            Event::output(0.5, "first\r\n"),
+            Event::output(1.0, "second\r\n"),
+        ]);
+        assert_eq!(file.snapshot_at(0.0, SnapshotFormat::Text), "");
+        assert_eq!(file.snapshot_at(0.5, SnapshotFormat::Text), "first");
+        assert_eq!(file.snapshot_at(1.5, SnapshotFormat::Text), "first\nsecond");
+    }
+
+    #[test]
+    fn transcript_keeps_lines_scrolled_off_screen() {
+        let mut file =
+            AsciicastFile::parse_str(r#"{"version":3,"term":{"cols":20,"rows":3}}"#).unwrap();
+        file.events = (1..=5)
+            .map(|i| Event::output(0.1, format!("\x1b[1mline {}\x1b[0m   \r\n", i)))
+            .collect::<Vec<_>>()
+            .into();
+
+        assert_eq!(
+            file.snapshot_at(10.0, SnapshotFormat::Text),
+            "line 4\nline 5"
+        );
+        assert_eq!(file.transcript(), "line 1\nline 2\nline 3\nline 4\nline 5");
+    }
+
+    #[test]
+    fn transcript_of_empty_recording_is_empty() {
+        assert_eq!(file_with(Vec::new()).transcript(), "");
+    }
+
+    #[test]
+    fn terminal_at_applies_resize_events() {
+        let file = file_with(vec![
+            Event::new(0.1, EventType::Resize, "20x5"),
+            Event::output(0.1, "x"),
+        ]);
+        let buffer = file.terminal_at(1.0);
+        assert_eq!((buffer.width(), buffer.height()), (20, 5));
+    }
+
+    #[test]
+    fn ansi_snapshot_includes_colors() {
+        let file = file_with(vec![Event::output(0.1, "\x1b[31mred\x1b[0m plain")]);
+        assert_eq!(
+            file.snapshot_at(1.0, SnapshotFormat::Ansi),
+            "\x1b[31mred\x1b[0m plain"
+        );
+        assert_eq!(file.snapshot_at(1.0, SnapshotFormat::Text), "red plain");
+    }
+
+    #[test]
+    fn ansi_line_resets_when_style_changes() {
+        let red = CellStyle {
+            fg: Color::Red,
+            ..Default::default()
+        };
+        let bold = CellStyle {
+            bold: true,
+            ..Default::default()
+        };
+        let line = StyledLine {
+            cells: vec![
+                Cell {
+                    char: 'a',
+                    style: red,
+                },
+                Cell {
+                    char: 'b',
+                    style: bold,
+                },
+            ],
+        };
+        assert_eq!(
+            styled_lines_to_ansi(&[line], &[]),
+            "\x1b[31ma\x1b[0m\x1b[1mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn ansi_drops_trailing_empty_lines() {
+        let lines = vec![
+            StyledLine {
+                cells: vec![Cell {
+                    char: 'x',
+                    style: CellStyle::default(),
+                }],
+            },
+            StyledLine { cells: vec![] },
+        ];
+        assert_eq!(styled_lines_to_ansi(&lines, &[]), "x");
+    }
+
+    #[test]
+    fn ansi_snapshot_reemits_sixel_images() {
+        let sixel = "\x1bPq#0;2;0;0;0#0~~\x1b\\";
+        let file = file_with(vec![Event::output(0.1, format!("ab{}cd", sixel))]);
+        assert_eq!(
+            file.snapshot_at(1.0, SnapshotFormat::Ansi),
+            format!("ab{}cd", sixel)
+        );
+        assert_eq!(file.snapshot_at(1.0, SnapshotFormat::Text), "abcd");
+    }
+}
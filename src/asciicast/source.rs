@@ -0,0 +1,168 @@
+//! Where a recording is read from: a file, standard input or a URL.
+//!
+//! `agr play -` reads the whole of stdin into memory, so seeking works the
+//! same as for a file. A URL is downloaded into memory the same way (see
+//! [`super::remote`]).
+
+use std::io::{self, BufRead, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::reader::ParseReport;
+use super::remote::{self, CurlFetcher, HttpFetcher};
+use super::types::AsciicastFile;
+
+/// Command-line argument that means "read from stdin".
+pub const STDIN_ARG: &str = "-";
+
+/// A recording to read: a file on disk, standard input or a URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CastSource {
+    /// A `.cast` file
+    File(PathBuf),
+    /// Standard input, read to the end
+    Stdin,
+    /// An `http(s)://` URL, downloaded before parsing
+    Url(String),
+}
+
+impl CastSource {
+    /// Whether a command-line argument names stdin (`-`).
+    pub fn is_stdin_arg(arg: &str) -> bool {
+        arg == STDIN_ARG
+    }
+
+    /// The file path, or `None` for stdin.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            CastSource::File(path) => Some(path),
+            CastSource::Stdin | CastSource::Url(_) => None,
+        }
+    }
+
+    /// A short name for display, e.g. in the player's status bar.
+    pub fn name(&self) -> String {
+        match self {
+            CastSource::File(path) => path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            CastSource::Stdin => "stdin".to_string(),
+            CastSource::Url(url) => remote::file_name(url),
+        }
+    }
+
+    /// Parse the recording.
+    ///
+    /// With `strict`, any malformed event line is an error; otherwise such
+    /// lines are skipped and listed in the report.
+    pub fn parse(&self, strict: bool) -> Result<(AsciicastFile, ParseReport)> {
+        self.parse_with(&CurlFetcher, strict)
+    }
+
+    /// Parse the recording, downloading a URL source with `fetcher`.
+    pub fn parse_with(
+        &self,
+        fetcher: &dyn HttpFetcher,
+        strict: bool,
+    ) -> Result<(AsciicastFile, ParseReport)> {
+        match self {
+            CastSource::File(path) if strict => {
+                AsciicastFile::parse(path).map(|cast| (cast, ParseReport::default()))
+            }
+            CastSource::File(path) => AsciicastFile::parse_tolerant(path),
+            CastSource::Stdin => {
+                let mut content = Vec::new();
+                io::stdin()
+                    .lock()
+                    .read_to_end(&mut content)
+                    .context("Failed to read recording from stdin")?;
+                parse_buffered(Cursor::new(content), strict)
+            }
+            CastSource::Url(url) => {
+                let content = remote::download(fetcher, url)?;
+                parse_buffered(Cursor::new(content), strict)
+                    .with_context(|| format!("Failed to parse recording from {}", url))
+            }
+        }
+    }
+}
+
+/// Parse a recording held in memory (or any other reader).
+fn parse_buffered<R: BufRead>(reader: R, strict: bool) -> Result<(AsciicastFile, ParseReport)> {
+    if strict {
+        AsciicastFile::parse_reader(reader).map(|cast| (cast, ParseReport::default()))
+    } else {
+        AsciicastFile::parse_reader_tolerant(reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asciicast::remote::tests::StubFetcher;
+
+    const SAMPLE: &str = "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n\
+        [0.5,\"o\",\"hello\"]\n\
+        not an event\n\
+        [1.0,\"m\",\"done\"]\n";
+
+    #[test]
+    fn in_memory_parse_matches_file_parse() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        std::fs::write(&path, SAMPLE).unwrap();
+
+        let (from_file, file_report) = CastSource::File(path).parse(false).unwrap();
+        let (from_memory, memory_report) =
+            parse_buffered(Cursor::new(SAMPLE.as_bytes().to_vec()), false).unwrap();
+
+        assert_eq!(
+            from_memory.to_string().unwrap(),
+            from_file.to_string().unwrap()
+        );
+        assert_eq!(memory_report, file_report);
+        assert_eq!(memory_report.skipped_lines, vec![3]);
+    }
+
+    #[test]
+    fn strict_in_memory_parse_rejects_corrupt_lines() {
+        assert!(parse_buffered(Cursor::new(SAMPLE.as_bytes().to_vec()), true).is_err());
+    }
+
+    #[test]
+    fn dash_means_stdin() {
+        assert!(CastSource::is_stdin_arg("-"));
+        assert!(!CastSource::is_stdin_arg("session.cast"));
+        assert_eq!(CastSource::Stdin.name(), "stdin");
+        assert_eq!(CastSource::Stdin.path(), None);
+        let file = CastSource::File(PathBuf::from("/tmp/claude/a.cast"));
+        assert_eq!(file.name(), "a.cast");
+    }
+
+    #[test]
+    fn url_source_is_fetched_then_parsed() {
+        let fetcher = StubFetcher::new(SAMPLE, Some("application/x-asciicast"));
+        let source = CastSource::Url("https://asciinema.org/a/42".to_string());
+
+        let (cast, report) = source.parse_with(&fetcher, false).unwrap();
+
+        assert_eq!(cast.events.len(), 2);
+        assert_eq!(cast.terminal_size(), (80, 24));
+        assert_eq!(report.skipped_lines, vec![3]);
+        assert_eq!(source.name(), "42.cast");
+        assert_eq!(source.path(), None);
+        assert!(source.parse_with(&fetcher, true).is_err());
+    }
+
+    #[test]
+    fn url_source_rejects_web_pages() {
+        let fetcher = StubFetcher::new("<html></html>", Some("text/html"));
+        let source = CastSource::Url("https://example.com/demo".to_string());
+
+        let err = source.parse_with(&fetcher, false).unwrap_err();
+        assert!(err.to_string().contains("not an asciicast recording"));
+    }
+}
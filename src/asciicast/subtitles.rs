@@ -0,0 +1,193 @@
+//! Subtitle export of recording markers.
+//!
+//! Converts markers into WebVTT or SubRip (SRT) cues so a recording turned
+//! into a video can show the marker labels as captions. Each cue starts at
+//! its marker's cumulative time and lasts until the next marker; the last
+//! cue lasts [`LAST_CUE_SECS`].
+//!
+//! # Example
+//!
+//! ```
+//! use agr::asciicast::{markers_to_subtitles, SubtitleFormat};
+//! use agr::MarkerInfo;
+//!
+//! let markers = vec![MarkerInfo {
+//!     timestamp: 1.5,
+//!     label: "Build started".to_string(),
+//! }];
+//!
+//! let vtt = markers_to_subtitles(&markers, SubtitleFormat::Vtt);
+//! assert_eq!(vtt, "WEBVTT\n\n00:00:01.500 --> 00:00:04.500\nBuild started\n");
+//! ```
+
+use std::path::Path;
+
+use super::MarkerInfo;
+
+/// Duration of the last cue, which has no following marker to end it.
+pub const LAST_CUE_SECS: f64 = 3.0;
+
+/// Subtitle file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SubtitleFormat {
+    /// WebVTT (.vtt)
+    #[default]
+    Vtt,
+    /// SubRip (.srt)
+    Srt,
+}
+
+impl SubtitleFormat {
+    /// Pick the format from an output path's extension, if it names one.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "vtt" => Some(SubtitleFormat::Vtt),
+            "srt" => Some(SubtitleFormat::Srt),
+            _ => None,
+        }
+    }
+}
+
+/// Render markers as a subtitle file.
+///
+/// Markers are sorted by time first, so the result is valid even if the
+/// input is not in order. No markers yields a file without cues.
+pub fn markers_to_subtitles(markers: &[MarkerInfo], format: SubtitleFormat) -> String {
+    let mut sorted: Vec<&MarkerInfo> = markers.iter().collect();
+    sorted.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+    let mut out = String::new();
+    if format == SubtitleFormat::Vtt {
+        out.push_str("WEBVTT\n");
+    }
+
+    for (i, marker) in sorted.iter().enumerate() {
+        let start = marker.timestamp.max(0.0);
+        let end = sorted
+            .get(i + 1)
+            .map_or(start + LAST_CUE_SECS, |next| next.timestamp.max(start));
+
+        if i > 0 || format == SubtitleFormat::Vtt {
+            out.push('\n');
+        }
+        if format == SubtitleFormat::Srt {
+            out.push_str(&format!("{}\n", i + 1));
+        }
+        out.push_str(&format!(
+            "{} --> {}\n{}\n",
+            cue_time(start, format),
+            cue_time(end, format),
+            cue_text(&marker.label, format)
+        ));
+    }
+    out
+}
+
+/// Format seconds as `HH:MM:SS.mmm` (VTT) or `HH:MM:SS,mmm` (SRT).
+fn cue_time(secs: f64, format: SubtitleFormat) -> String {
+    let total_ms = (secs * 1000.0).round() as u64;
+    let (hours, rest) = (total_ms / 3_600_000, total_ms % 3_600_000);
+    let (minutes, rest) = (rest / 60_000, rest % 60_000);
+    let (seconds, millis) = (rest / 1000, rest % 1000);
+    let separator = match format {
+        SubtitleFormat::Vtt => '.',
+        SubtitleFormat::Srt => ',',
+    };
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        hours, minutes, seconds, separator, millis
+    )
+}
+
+/// Make a label safe to use as the text of one cue.
+///
+/// Line breaks and control characters become spaces, because a blank line
+/// would end the cue. WebVTT markup characters are escaped; SRT has no
+/// escapes, so `-->`, `<` and `>` are replaced by look-alikes to keep
+/// players from reading them as timings or tags.
+fn cue_text(label: &str, format: SubtitleFormat) -> String {
+    let flat: String = label
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let flat = flat.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match format {
+        SubtitleFormat::Vtt => flat
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+        SubtitleFormat::Srt => flat.replace("-->", "→").replace('<', "‹").replace('>', "›"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(timestamp: f64, label: &str) -> MarkerInfo {
+        MarkerInfo {
+            timestamp,
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn cue_time_formats_hours_and_millis() {
+        assert_eq!(cue_time(3723.4567, SubtitleFormat::Vtt), "01:02:03.457");
+        assert_eq!(cue_time(0.0, SubtitleFormat::Srt), "00:00:00,000");
+    }
+
+    #[test]
+    fn vtt_escapes_markup() {
+        assert_eq!(
+            cue_text("a <b> & c --> d", SubtitleFormat::Vtt),
+            "a &lt;b&gt; &amp; c --&gt; d"
+        );
+    }
+
+    #[test]
+    fn srt_replaces_tags_and_arrows() {
+        assert_eq!(
+            cue_text("<i>x</i> --> y", SubtitleFormat::Srt),
+            "‹i›x‹/i› → y"
+        );
+    }
+
+    #[test]
+    fn labels_are_flattened_to_one_line() {
+        assert_eq!(
+            cue_text("first\n\nsecond\tthird\r\n", SubtitleFormat::Vtt),
+            "first second third"
+        );
+    }
+
+    #[test]
+    fn markers_are_sorted_before_export() {
+        let srt = markers_to_subtitles(&[marker(5.0, "b"), marker(1.0, "a")], SubtitleFormat::Srt);
+        assert_eq!(
+            srt,
+            "1\n00:00:01,000 --> 00:00:05,000\na\n\n2\n00:00:05,000 --> 00:00:08,000\nb\n"
+        );
+    }
+
+    #[test]
+    fn no_markers_gives_empty_file() {
+        assert_eq!(markers_to_subtitles(&[], SubtitleFormat::Vtt), "WEBVTT\n");
+        assert_eq!(markers_to_subtitles(&[], SubtitleFormat::Srt), "");
+    }
+
+    #[test]
+    fn format_from_path_uses_extension() {
+        assert_eq!(
+            SubtitleFormat::from_path(Path::new("subs.SRT")),
+            Some(SubtitleFormat::Srt)
+        );
+        assert_eq!(
+            SubtitleFormat::from_path(Path::new("out/subs.vtt")),
+            Some(SubtitleFormat::Vtt)
+        );
+        assert_eq!(SubtitleFormat::from_path(Path::new("subs.txt")), None);
+    }
+}
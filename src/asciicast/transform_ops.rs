@@ -89,6 +89,7 @@ pub fn apply_transforms(path: &Path) -> Result<TransformResult> {
     // Apply silence removal transform
     let mut transform = SilenceRemoval::new(threshold);
     transform.transform(&mut cast.events);
+    cast.refresh_duration();
 
     let new_duration = cast.duration();
 
@@ -130,7 +131,7 @@ mod tests {
             env: None,
             idle_time_limit: None,
         });
-        file.events = events;
+        file.events = events.into();
         file.write(&path).unwrap();
         path
     }
@@ -154,7 +155,7 @@ mod tests {
             env: None,
             idle_time_limit: Some(idle_time_limit),
         });
-        file.events = events;
+        file.events = events.into();
         file.write(&path).unwrap();
         path
     }
@@ -276,6 +277,24 @@ mod tests {
         assert!((result.time_saved() - 8.0).abs() < 0.001);
     }
 
+    #[test]
+    fn apply_transforms_updates_stored_header_duration() {
+        let dir = TempDir::new().unwrap();
+        let path = create_test_cast_file(
+            &dir,
+            "test.cast",
+            vec![Event::output(0.1, "hello"), Event::output(10.0, "end")],
+        );
+        let mut cast = AsciicastFile::parse(&path).unwrap();
+        cast.stamp_duration();
+        cast.write(&path).unwrap();
+
+        apply_transforms(&path).unwrap();
+
+        let header = AsciicastFile::read_header(&path).unwrap();
+        assert_eq!(header.duration, Some(2.1));
+    }
+
     #[test]
     fn apply_transforms_uses_header_idle_time_limit() {
         let dir = TempDir::new().unwrap();
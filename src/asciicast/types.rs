@@ -23,6 +23,9 @@
 //! [0.1,"m","marker label"]
 //! ```
 
+use std::ops::{Deref, DerefMut};
+use std::sync::OnceLock;
+
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -272,7 +275,119 @@ pub struct AsciicastFile {
     pub header: Header,
 
     /// All events in the recording, in chronological order.
-    pub events: Vec<Event>,
+    pub events: EventList,
+}
+
+// ============================================================================
+// Event List
+// ============================================================================
+
+/// The events of a recording, with their cumulative times cached.
+///
+/// Derefs to `Vec<Event>`, so it is used like one. The absolute time of
+/// each event is computed on first use and kept until the events are next
+/// accessed mutably, which drops the cache.
+#[derive(Debug, Clone, Default)]
+pub struct EventList {
+    events: Vec<Event>,
+    cumulative: OnceLock<Vec<f64>>,
+}
+
+impl EventList {
+    /// Create an empty event list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Absolute timestamps of all events (running total of relative times).
+    ///
+    /// Negative relative times count as zero, so the times never decrease
+    /// and can be binary searched.
+    pub fn cumulative_times(&self) -> &[f64] {
+        self.cumulative.get_or_init(|| {
+            let mut times = Vec::with_capacity(self.events.len());
+            let mut cumulative = 0.0;
+            for event in &self.events {
+                cumulative += event.time.max(0.0);
+                times.push(cumulative);
+            }
+            times
+        })
+    }
+
+    /// Whether the cumulative times are currently cached.
+    pub fn is_cached(&self) -> bool {
+        self.cumulative.get().is_some()
+    }
+
+    /// Take the events out of the list.
+    pub fn into_vec(self) -> Vec<Event> {
+        self.events
+    }
+}
+
+impl Deref for EventList {
+    type Target = Vec<Event>;
+
+    fn deref(&self) -> &Vec<Event> {
+        &self.events
+    }
+}
+
+impl DerefMut for EventList {
+    /// Mutable access invalidates the cached cumulative times.
+    fn deref_mut(&mut self) -> &mut Vec<Event> {
+        self.cumulative.take();
+        &mut self.events
+    }
+}
+
+impl From<Vec<Event>> for EventList {
+    fn from(events: Vec<Event>) -> Self {
+        Self {
+            events,
+            cumulative: OnceLock::new(),
+        }
+    }
+}
+
+impl From<EventList> for Vec<Event> {
+    fn from(list: EventList) -> Self {
+        list.events
+    }
+}
+
+impl FromIterator<Event> for EventList {
+    fn from_iter<I: IntoIterator<Item = Event>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
+impl IntoIterator for EventList {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a EventList {
+    type Item = &'a Event;
+    type IntoIter = std::slice::Iter<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut EventList {
+    type Item = &'a mut Event;
+    type IntoIter = std::slice::IterMut<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
 }
 
 impl AsciicastFile {
@@ -280,7 +395,7 @@ impl AsciicastFile {
     pub fn new(header: Header) -> Self {
         Self {
             header,
-            events: Vec::new(),
+            events: EventList::new(),
         }
     }
 
@@ -297,29 +412,21 @@ impl AsciicastFile {
     /// Calculate cumulative (absolute) timestamps for all events.
     ///
     /// Since events store relative times, this computes the running total
-    /// to get the absolute time of each event from recording start.
-    pub fn cumulative_times(&self) -> Vec<f64> {
-        let mut times = Vec::with_capacity(self.events.len());
-        let mut cumulative = 0.0;
-        for event in &self.events {
-            cumulative += event.time;
-            times.push(cumulative);
-        }
-        times
+    /// to get the absolute time of each event from recording start. A
+    /// negative relative time (invalid, see [`crate::asciicast::validate`])
+    /// counts as zero. The result is cached until the events are modified.
+    pub fn cumulative_times(&self) -> &[f64] {
+        self.events.cumulative_times()
     }
 
     /// Find the insertion index for an event at the given absolute timestamp.
     ///
     /// Returns the index where a new event should be inserted to maintain
-    /// chronological order.
+    /// chronological order: after every event at or before `timestamp`.
+    /// Binary searches the cached cumulative times, which never decrease.
     pub fn find_insertion_index(&self, timestamp: f64) -> usize {
-        let cumulative_times = self.cumulative_times();
-        for (i, &time) in cumulative_times.iter().enumerate() {
-            if time > timestamp {
-                return i;
-            }
-        }
-        self.events.len()
+        self.cumulative_times()
+            .partition_point(|&time| time <= timestamp)
     }
 
     /// Calculate the relative time for insertion at a given index.
@@ -349,17 +456,32 @@ impl AsciicastFile {
         self.cumulative_times().last().copied().unwrap_or(0.0)
     }
 
+    /// Store the computed [`duration`](Self::duration) in the header.
+    ///
+    /// The value is rounded to microseconds to drop summation noise.
+    pub fn stamp_duration(&mut self) {
+        self.header.duration = Some((self.duration() * 1_000_000.0).round() / 1_000_000.0);
+    }
+
+    /// Recompute the header duration after events changed.
+    ///
+    /// Does nothing when the header has no duration, so files that never
+    /// stored one are not changed.
+    pub fn refresh_duration(&mut self) {
+        if self.header.duration.is_some() {
+            self.stamp_duration();
+        }
+    }
+
     /// Get concatenated output text up to a specific timestamp.
     ///
     /// Returns all output data combined up to (and including) the given
     /// absolute timestamp. Useful for getting terminal state at a point in time.
     pub fn output_at(&self, timestamp: f64) -> String {
         let mut output = String::new();
-        let mut cumulative = 0.0;
 
-        for event in &self.events {
-            cumulative += event.time;
-            if cumulative > timestamp {
+        for (event, &time) in self.events.iter().zip(self.cumulative_times()) {
+            if time > timestamp {
                 break;
             }
             if event.is_output() {
@@ -454,12 +576,78 @@ mod tests {
         assert!((times[3] - 0.7).abs() < 0.001);
     }
 
+    #[test]
+    fn cumulative_times_clamp_negative_intervals() {
+        let mut file = create_test_file();
+        file.events[1].time = -0.5;
+        let times = file.cumulative_times();
+        assert!((times[1] - 0.1).abs() < 0.001);
+        assert!(times.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(file.find_insertion_index(0.1), 2);
+    }
+
+    #[test]
+    fn cumulative_times_are_cached_until_mutation() {
+        let mut file = create_test_file();
+        assert!(!file.events.is_cached());
+        let first = file.cumulative_times().as_ptr();
+        assert!(file.events.is_cached());
+        assert_eq!(file.cumulative_times().as_ptr(), first);
+
+        file.events[0].time = 1.0;
+        assert!(!file.events.is_cached());
+        assert!((file.duration() - 1.6).abs() < 0.001);
+    }
+
+    #[test]
+    fn adding_marker_invalidates_cumulative_times() {
+        let mut file = create_test_file();
+        assert!((file.duration() - 0.7).abs() < 0.001);
+
+        crate::asciicast::MarkerManager::add_marker_to_cast(&mut file, 0.35, "mid").unwrap();
+
+        let times = file.cumulative_times();
+        assert_eq!(times.len(), 5);
+        assert!((times[2] - 0.35).abs() < 0.001);
+        assert!((file.duration() - 0.7).abs() < 0.001);
+        assert_eq!(file.find_insertion_index(0.35), 3);
+        assert_eq!(file.output_at(0.35), "hello world");
+    }
+
+    #[test]
+    fn appending_events_extends_duration() {
+        let mut file = create_test_file();
+        assert!((file.duration() - 0.7).abs() < 0.001);
+        file.events.push(Event::output(0.5, "more"));
+        assert!((file.duration() - 1.2).abs() < 0.001);
+        file.events = vec![Event::output(2.0, "only")].into();
+        assert!((file.duration() - 2.0).abs() < 0.001);
+    }
+
     #[test]
     fn duration_returns_total_time() {
         let file = create_test_file();
         assert!((file.duration() - 0.7).abs() < 0.001);
     }
 
+    #[test]
+    fn stamp_duration_rounds_to_microseconds() {
+        let mut file = create_test_file();
+        file.stamp_duration();
+        assert_eq!(file.header.duration, Some(0.7));
+    }
+
+    #[test]
+    fn refresh_duration_only_updates_stored_duration() {
+        let mut file = create_test_file();
+        file.refresh_duration();
+        assert_eq!(file.header.duration, None);
+
+        file.header.duration = Some(99.0);
+        file.refresh_duration();
+        assert_eq!(file.header.duration, Some(0.7));
+    }
+
     #[test]
     fn output_at_returns_output_up_to_timestamp() {
         let file = create_test_file();
@@ -485,6 +673,37 @@ mod tests {
         assert_eq!(file.find_insertion_index(1.0), 4);
     }
 
+    #[test]
+    fn find_insertion_index_matches_linear_scan() {
+        let mut file = create_test_file();
+        // Zero gaps put several events at the same timestamp
+        for i in 0..200 {
+            let gap = [0.0, 0.05, 0.0, 0.3, 1.7][i % 5];
+            file.events.push(Event::output(gap, "x"));
+        }
+        let linear = |file: &AsciicastFile, timestamp: f64| {
+            let times = file.cumulative_times();
+            (0..times.len())
+                .find(|&i| times[i] > timestamp)
+                .unwrap_or(times.len())
+        };
+
+        let duration = file.duration();
+        let mut timestamp = -0.5;
+        while timestamp < duration + 1.0 {
+            assert_eq!(
+                file.find_insertion_index(timestamp),
+                linear(&file, timestamp),
+                "timestamp {}",
+                timestamp
+            );
+            timestamp += 0.037;
+        }
+        for &time in file.cumulative_times() {
+            assert_eq!(file.find_insertion_index(time), linear(&file, time));
+        }
+    }
+
     #[test]
     fn marker_count_returns_correct_count() {
         let file = create_test_file();
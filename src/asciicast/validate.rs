@@ -0,0 +1,548 @@
+//! Recording health checks for `agr validate`.
+//!
+//! [`validate_str`] runs every check over the raw file content and collects
+//! the problems in a [`ValidationReport`]. Errors make the recording
+//! unplayable (or lose part of it); warnings point at content that plays
+//! but may render wrongly.
+//!
+//! Each check is a separate function so it can be tested on its own:
+//!
+//! - [`check_header`] - header parses, is v3 and has a terminal size
+//! - [`check_event_lines`] - every event line parses; detects a truncated last line
+//! - [`check_times`] - event times never go backwards
+//! - [`check_markers`] - markers are in order, labelled and inside the recording
+//! - [`check_escape_sequences`] - no unterminated strings or out-of-range colors
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::types::{Event, Header};
+
+/// How serious a problem is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Plays, but may render wrongly
+    Warning,
+    /// Cannot be played as recorded
+    Error,
+}
+
+/// One problem found in a recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub severity: Severity,
+    /// 1-based line in the file, if the problem belongs to one line
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    fn error(line: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            line,
+            message: message.into(),
+        }
+    }
+
+    fn warning(line: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+/// Everything [`validate_str`] found.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    /// Event lines that parsed
+    pub event_count: usize,
+    pub problems: Vec<Problem>,
+}
+
+impl ValidationReport {
+    /// Whether no check found an error.
+    pub fn is_playable(&self) -> bool {
+        self.count(Severity::Error) == 0
+    }
+
+    /// Number of problems at the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.problems
+            .iter()
+            .filter(|p| p.severity == severity)
+            .count()
+    }
+}
+
+/// An event together with its 1-based line number.
+pub type NumberedEvent = (usize, Event);
+
+/// Check the header line.
+///
+/// Returns the parsed header, if any, so later checks can use it.
+pub fn check_header(line: Option<&str>) -> (Option<Header>, Vec<Problem>) {
+    let Some(line) = line.filter(|l| !l.trim().is_empty()) else {
+        return (
+            None,
+            vec![Problem::error(Some(1), "header line is missing")],
+        );
+    };
+    let header: Header = match serde_json::from_str(line) {
+        Ok(header) => header,
+        Err(e) => {
+            return (
+                None,
+                vec![Problem::error(Some(1), format!("header is invalid: {}", e))],
+            )
+        }
+    };
+
+    let mut problems = Vec::new();
+    if header.version != 3 {
+        problems.push(Problem::error(
+            Some(1),
+            format!(
+                "header version is {}, only asciicast v3 is supported",
+                header.version
+            ),
+        ));
+    }
+    let term = header.term.as_ref();
+    let cols = term.and_then(|t| t.cols);
+    let rows = term.and_then(|t| t.rows);
+    match (cols, rows) {
+        (Some(0), _) | (_, Some(0)) => problems.push(Problem::error(
+            Some(1),
+            "header terminal size is zero (term.cols/term.rows)",
+        )),
+        (Some(_), Some(_)) => {}
+        _ => problems.push(Problem::warning(
+            Some(1),
+            "header has no term.cols/term.rows; players assume 80x24",
+        )),
+    }
+    (Some(header), problems)
+}
+
+/// Parse the event lines (everything after the header).
+///
+/// `lines` are `(line number, text)` pairs. An unparseable line is an
+/// error; when it is the last line and the file does not end in a newline
+/// it is reported as a truncated final line, the usual result of a
+/// recorder that was killed mid-write.
+pub fn check_event_lines<'a>(
+    lines: impl IntoIterator<Item = (usize, &'a str)>,
+    ends_with_newline: bool,
+) -> (Vec<NumberedEvent>, Vec<Problem>) {
+    let lines: Vec<(usize, &str)> = lines
+        .into_iter()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .collect();
+    let last_line = lines.last().map(|(n, _)| *n);
+
+    let mut events = Vec::with_capacity(lines.len());
+    let mut problems = Vec::new();
+    for (number, line) in lines {
+        match Event::from_json(line) {
+            Ok(event) => events.push((number, event)),
+            Err(_) if Some(number) == last_line && !ends_with_newline => {
+                problems.push(Problem::error(
+                    Some(number),
+                    "final line is truncated (the recording was cut off mid-write)",
+                ));
+            }
+            Err(e) => problems.push(Problem::error(
+                Some(number),
+                format!("event is invalid: {:#}", e),
+            )),
+        }
+    }
+    (events, problems)
+}
+
+/// Check that event times never go backwards.
+///
+/// v3 times are intervals since the previous event, so a negative time
+/// means the clock went back.
+pub fn check_times(events: &[NumberedEvent]) -> Vec<Problem> {
+    events
+        .iter()
+        .filter(|(_, e)| e.time < 0.0)
+        .map(|(line, e)| {
+            Problem::error(
+                Some(*line),
+                format!("time goes backwards (interval {:.3}s)", e.time),
+            )
+        })
+        .collect()
+}
+
+/// Check marker order, labels and position.
+///
+/// `duration` is the header duration, if recorded.
+pub fn check_markers(events: &[NumberedEvent], duration: Option<f64>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut time = 0.0;
+    let mut previous_marker: Option<f64> = None;
+
+    for (line, event) in events {
+        time += event.time;
+        if !event.is_marker() {
+            continue;
+        }
+        if event.data.trim().is_empty() {
+            problems.push(Problem::warning(Some(*line), "marker has an empty label"));
+        }
+        if let Some(previous) = previous_marker.filter(|&p| time < p) {
+            problems.push(Problem::warning(
+                Some(*line),
+                format!(
+                    "marker '{}' at {:.1}s is before the previous marker at {:.1}s",
+                    event.data, time, previous
+                ),
+            ));
+        }
+        if let Some(duration) = duration.filter(|&d| time > d + 1e-6) {
+            problems.push(Problem::warning(
+                Some(*line),
+                format!(
+                    "marker '{}' at {:.1}s is past the header duration {:.1}s",
+                    event.data, time, duration
+                ),
+            ));
+        }
+        previous_marker = Some(time);
+    }
+    problems
+}
+
+/// Escape sequence parser state for [`check_escape_sequences`].
+enum EscapeState {
+    Ground,
+    Escape,
+    Csi(String),
+    /// OSC/DCS/APC/PM/SOS string started on a line; `true` after an ESC
+    Str(&'static str, usize, bool),
+}
+
+/// Check output for escape sequences that corrupt the terminal buffer.
+///
+/// Sequences may span events, so output is scanned as one stream. An
+/// OSC/DCS-style string that is never terminated swallows all output after
+/// it, and SGR colors outside 0-255 are rendered differently by every
+/// player.
+pub fn check_escape_sequences(events: &[NumberedEvent]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut state = EscapeState::Ground;
+
+    for (line, event) in events.iter().filter(|(_, e)| e.is_output()) {
+        for c in event.data.chars() {
+            state = match state {
+                EscapeState::Ground if c == '\x1b' => EscapeState::Escape,
+                EscapeState::Ground => EscapeState::Ground,
+                EscapeState::Escape => match c {
+                    '[' => EscapeState::Csi(String::new()),
+                    ']' => EscapeState::Str("OSC", *line, false),
+                    'P' => EscapeState::Str("DCS", *line, false),
+                    '_' => EscapeState::Str("APC", *line, false),
+                    '^' => EscapeState::Str("PM", *line, false),
+                    'X' => EscapeState::Str("SOS", *line, false),
+                    '\x1b' => EscapeState::Escape,
+                    _ => EscapeState::Ground,
+                },
+                EscapeState::Csi(mut params) => match c {
+                    '\x20'..='\x3f' => {
+                        params.push(c);
+                        EscapeState::Csi(params)
+                    }
+                    '\x40'..='\x7e' => {
+                        if c == 'm' {
+                            problems.extend(
+                                sgr_color_problems(&params)
+                                    .into_iter()
+                                    .map(|m| Problem::warning(Some(*line), m)),
+                            );
+                        }
+                        EscapeState::Ground
+                    }
+                    '\x1b' => EscapeState::Escape,
+                    _ => EscapeState::Ground,
+                },
+                EscapeState::Str(kind, start, after_esc) => match c {
+                    '\x07' if kind == "OSC" => EscapeState::Ground,
+                    '\\' if after_esc => EscapeState::Ground,
+                    '\x1b' => EscapeState::Str(kind, start, true),
+                    _ => EscapeState::Str(kind, start, false),
+                },
+            };
+        }
+    }
+
+    if let EscapeState::Str(kind, start, _) = state {
+        problems.push(Problem::warning(
+            Some(start),
+            format!(
+                "{} sequence is never terminated; all output after it is swallowed",
+                kind
+            ),
+        ));
+    }
+    problems
+}
+
+/// Out-of-range colors in the parameters of one SGR sequence.
+fn sgr_color_problems(params: &str) -> Vec<String> {
+    let values: Vec<u32> = params
+        .split([';', ':'])
+        .map(|p| p.parse::<u32>().unwrap_or(0))
+        .collect();
+    let mut problems = Vec::new();
+    let mut i = 0;
+    while i < values.len() {
+        match values[i] {
+            38 | 48 | 58 => match values.get(i + 1) {
+                Some(5) => {
+                    if let Some(&index) = values.get(i + 2).filter(|&&v| v > 255) {
+                        problems.push(format!("SGR color index {} is out of range (0-255)", index));
+                    }
+                    i += 3;
+                }
+                Some(2) => {
+                    let rgb = values.iter().skip(i + 2).take(3);
+                    if let Some(component) = rgb.copied().find(|&v| v > 255) {
+                        problems.push(format!(
+                            "SGR RGB component {} is out of range (0-255)",
+                            component
+                        ));
+                    }
+                    i += 5;
+                }
+                _ => i += 1,
+            },
+            _ => i += 1,
+        }
+    }
+    problems
+}
+
+/// Run every check over the content of a cast file.
+pub fn validate_str(content: &str) -> ValidationReport {
+    let mut lines = content.lines().enumerate().map(|(i, l)| (i + 1, l));
+    let (header, mut problems) = check_header(lines.next().map(|(_, l)| l));
+    let Some(header) = header else {
+        return ValidationReport {
+            event_count: 0,
+            problems,
+        };
+    };
+
+    let (events, line_problems) = check_event_lines(lines, content.ends_with('\n'));
+    problems.extend(line_problems);
+    problems.extend(check_times(&events));
+    problems.extend(check_markers(&events, header.duration));
+    problems.extend(check_escape_sequences(&events));
+    problems.sort_by_key(|p| p.line);
+
+    ValidationReport {
+        event_count: events.len(),
+        problems,
+    }
+}
+
+/// Run every check over a cast file.
+///
+/// Invalid UTF-8 is decoded lossily, so it shows up as a bad event line
+/// rather than a read error.
+pub fn validate_file<P: AsRef<Path>>(path: P) -> Result<ValidationReport> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).with_context(|| format!("Failed to read file: {:?}", path))?;
+    Ok(validate_str(&String::from_utf8_lossy(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = r#"{"version":3,"term":{"cols":80,"rows":24}}"#;
+
+    fn numbered(events: Vec<Event>) -> Vec<NumberedEvent> {
+        events
+            .into_iter()
+            .enumerate()
+            .map(|(i, e)| (i + 2, e))
+            .collect()
+    }
+
+    fn messages(problems: &[Problem]) -> Vec<(Option<usize>, &str)> {
+        problems
+            .iter()
+            .map(|p| (p.line, p.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn clean_recording_has_no_problems() {
+        let content = format!(
+            "{}\n{}\n{}\n{}\n",
+            HEADER,
+            r#"[0.5,"o","\u001b[1;38;5;208mhello\u001b[0m\r\n"]"#,
+            r#"[0.1,"m","Greeting"]"#,
+            r#"[0.2,"o","\u001b]0;title\u0007done\r\n"]"#
+        );
+        let report = validate_str(&content);
+
+        assert_eq!(report.problems, vec![]);
+        assert_eq!(report.event_count, 3);
+        assert!(report.is_playable());
+    }
+
+    #[test]
+    fn header_problems() {
+        let (_, problems) = check_header(None);
+        assert_eq!(problems[0].severity, Severity::Error);
+
+        let (header, problems) = check_header(Some("not json"));
+        assert!(header.is_none());
+        assert!(problems[0].message.starts_with("header is invalid"));
+
+        let (_, problems) = check_header(Some(r#"{"version":2,"width":80,"height":24}"#));
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].severity, Severity::Error);
+        assert!(problems[0].message.contains("version is 2"));
+        assert_eq!(problems[1].severity, Severity::Warning);
+        assert!(problems[1].message.contains("term.cols/term.rows"));
+
+        let (_, problems) = check_header(Some(r#"{"version":3,"term":{"cols":0,"rows":24}}"#));
+        assert_eq!(problems[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn unparseable_event_line_is_an_error() {
+        let lines = [(2, r#"[0.1,"o","ok"]"#), (3, r#"[0.1,"o""#), (4, "")];
+        let (events, problems) = check_event_lines(lines, true);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(3));
+        assert!(problems[0].message.starts_with("event is invalid"));
+    }
+
+    #[test]
+    fn cut_off_last_line_is_reported_as_truncated() {
+        let content = format!(
+            "{}\n{}\n{}",
+            HEADER, r#"[0.1,"o","ok"]"#, r#"[0.2,"o","hal"#
+        );
+        let report = validate_str(&content);
+
+        assert!(!report.is_playable());
+        assert_eq!(
+            messages(&report.problems),
+            vec![(
+                Some(3),
+                "final line is truncated (the recording was cut off mid-write)"
+            )]
+        );
+    }
+
+    #[test]
+    fn negative_interval_is_an_error() {
+        let events = numbered(vec![
+            Event::output(1.0, "a"),
+            Event::output(-0.5, "b"),
+            Event::output(0.0, "c"),
+        ]);
+        let problems = check_times(&events);
+
+        assert_eq!(
+            messages(&problems),
+            vec![(Some(3), "time goes backwards (interval -0.500s)")]
+        );
+        assert_eq!(problems[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn markers_out_of_order_unlabelled_or_past_the_end() {
+        let events = numbered(vec![
+            Event::marker(5.0, "Second"),
+            Event::marker(-2.0, "First"),
+            Event::marker(1.0, " "),
+            Event::marker(10.0, "Late"),
+        ]);
+        let problems = check_markers(&events, Some(10.0));
+
+        assert_eq!(
+            messages(&problems),
+            vec![
+                (
+                    Some(3),
+                    "marker 'First' at 3.0s is before the previous marker at 5.0s"
+                ),
+                (Some(4), "marker has an empty label"),
+                (
+                    Some(5),
+                    "marker 'Late' at 14.0s is past the header duration 10.0s"
+                ),
+            ]
+        );
+        assert!(problems.iter().all(|p| p.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn unterminated_osc_swallows_the_rest() {
+        let events = numbered(vec![
+            Event::output(0.1, "$ ls\r\n"),
+            Event::output(0.1, "\x1b]8;;https://example.com"),
+            Event::output(0.1, "file.txt\r\n"),
+        ]);
+        let problems = check_escape_sequences(&events);
+
+        assert_eq!(
+            messages(&problems),
+            vec![(
+                Some(3),
+                "OSC sequence is never terminated; all output after it is swallowed"
+            )]
+        );
+    }
+
+    #[test]
+    fn terminated_strings_spanning_events_are_fine() {
+        let events = numbered(vec![
+            Event::output(0.1, "\x1b]0;ti"),
+            Event::output(0.1, "tle\x1b"),
+            Event::output(0.1, "\\\x1bPq#0\x1b\\ok"),
+        ]);
+        assert_eq!(check_escape_sequences(&events), vec![]);
+    }
+
+    #[test]
+    fn out_of_range_colors_are_warnings() {
+        let events = numbered(vec![
+            Event::output(0.1, "\x1b[38;5;300mA"),
+            Event::output(0.1, "\x1b[1;48;2;10;999;0mB"),
+            Event::output(0.1, "\x1b[38:5:255mC\x1b[0m"),
+        ]);
+        let problems = check_escape_sequences(&events);
+
+        assert_eq!(
+            messages(&problems),
+            vec![
+                (Some(2), "SGR color index 300 is out of range (0-255)"),
+                (Some(3), "SGR RGB component 999 is out of range (0-255)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_header_stops_further_checks() {
+        let report = validate_str("");
+        assert_eq!(report.problems.len(), 1);
+        assert!(!report.is_playable());
+    }
+}
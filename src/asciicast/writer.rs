@@ -99,6 +99,9 @@ impl AsciicastFile {
     /// Write the asciicast file to any writer.
     ///
     /// Writes the header as the first line, followed by each event on its own line.
+    /// The header is flushed before any event, so a reader on the other end of
+    /// a pipe can start as soon as it arrives. Produces the same bytes as
+    /// [`write`](Self::write).
     ///
     /// # Errors
     ///
@@ -108,6 +111,7 @@ impl AsciicastFile {
         let header_json =
             serde_json::to_string(&self.header).context("Failed to serialize header")?;
         writeln!(writer, "{}", header_json)?;
+        writer.flush()?;
 
         // Write events
         for event in &self.events {
@@ -195,4 +199,75 @@ mod tests {
         let lines: Vec<&str> = output.lines().collect();
         assert_eq!(lines.len(), 2); // header + 1 event
     }
+
+    #[test]
+    fn write_to_buffer_matches_file_bytes() {
+        let mut file = AsciicastFile::new(Header {
+            version: 3,
+            width: None,
+            height: None,
+            term: None,
+            timestamp: Some(1700000000),
+            duration: Some(1.5),
+            title: Some("claude".to_string()),
+            command: None,
+            env: None,
+            idle_time_limit: None,
+        });
+        file.events.push(Event::output(0.5, "héllo \u{1b}[1m"));
+        file.events.push(Event::marker(1.0, "[PLAN] start"));
+        file.events.push(Event::new(0.0, EventType::Exit, "0"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        file.write(&path).unwrap();
+
+        let mut buffer = Vec::new();
+        file.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer, fs::read(&path).unwrap());
+    }
+
+    /// Records what each flush has seen so far.
+    struct FlushLog {
+        written: Vec<u8>,
+        flushed_at: Vec<usize>,
+    }
+
+    impl Write for FlushLog {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushed_at.push(self.written.len());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_to_flushes_header_before_events() {
+        let mut file = AsciicastFile::new(Header {
+            version: 3,
+            width: None,
+            height: None,
+            term: None,
+            timestamp: None,
+            duration: None,
+            title: None,
+            command: None,
+            env: None,
+            idle_time_limit: None,
+        });
+        file.events.push(Event::output(0.1, "hello"));
+
+        let mut sink = FlushLog {
+            written: Vec::new(),
+            flushed_at: Vec::new(),
+        };
+        file.write_to(&mut sink).unwrap();
+
+        let header_len = sink.written.iter().position(|&b| b == b'\n').unwrap() + 1;
+        assert_eq!(sink.flushed_at.first(), Some(&header_len));
+    }
 }
@@ -7,6 +7,14 @@ use clap::builder::styling::{AnsiColor, Effects, Styles};
 use clap::{Parser, Subcommand};
 use clap_complete::Shell as CompletionShell;
 
+use crate::analyzer::ExtractionLevel;
+use crate::asciicast::{
+    ExportFormat, ImportFormat, MarkerTimeFormat, SnapshotAt, SnapshotFormat, SubtitleFormat,
+};
+use crate::clipboard::CopyFormat;
+use crate::shell::AgentCompletion;
+use crate::theme::ColorChoice;
+
 /// Build clap styles using our theme colors.
 ///
 /// Maps theme colors to clap's styling system for consistent CLI appearance.
@@ -47,6 +55,44 @@ For more information, see: https://github.com/thiscantbeserious/agent-session-re
 )]
 #[command(version)]
 pub struct Cli {
+    /// Config profile to apply over the base config
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Apply the [profiles.<NAME>] config overrides (or set AGR_PROFILE)"
+    )]
+    pub profile: Option<String>,
+
+    /// Config file to use instead of ~/.config/agr/config.toml
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Use this config file instead of ~/.config/agr/config.toml (or set AGR_CONFIG)"
+    )]
+    pub config: Option<String>,
+
+    /// When to use colors in output
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = ColorChoice::Auto,
+        help = "When to use colors: auto (TTY and no NO_COLOR), always, never"
+    )]
+    pub color: ColorChoice,
+
+    /// Hide the recording banners and logo
+    #[arg(
+        long = "no-banner",
+        visible_alias = "quiet",
+        global = true,
+        help = "Hide the recording banners and logo (or set AGR_NO_BANNER)"
+    )]
+    pub no_banner: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -65,7 +111,29 @@ EXAMPLES:
     agr record codex                     Record an OpenAI Codex session
     agr record claude --name my-session  Record with a specific filename
     agr record claude -- --help          Pass --help flag to claude
-    agr record gemini -- chat        Start gemini in chat mode")]
+    agr record gemini -- chat        Start gemini in chat mode
+    agr record claude --output - | gzip > session.cast.gz
+                                         Pipe the recording to gzip
+    agr record claude --status           Show elapsed time and size
+    agr record claude --cols 120 --rows 40
+                                         Record at a fixed terminal size
+
+With --output, the recording is streamed to stdout for '-' while the
+session runs, or written to the given file, instead of the storage
+directory. An existing file is only replaced once the session ends, so a
+failed start or an aborted session leaves it untouched. The session is
+still shown on the terminal, there is no rename prompt, and pre_hook,
+post_hook and auto-analyze are not run.
+
+With --status, the elapsed time and recording size are shown in the
+terminal window title and updated every second, so the recorded session
+is not disturbed. When stderr is not a terminal, the status is written to
+it line by line instead.
+
+With --cols/--rows (or [recording] cols/rows in the config), the session
+runs in a terminal of that size instead of inheriting the current one, so
+recordings come out the same size. A dimension that is not given is taken
+from the current terminal.")]
     Record {
         /// Agent name (e.g., claude, codex, gemini)
         #[arg(help = "Agent name (e.g., claude, codex, gemini)")]
@@ -73,6 +141,34 @@ EXAMPLES:
         /// Optional session name (skips rename prompt)
         #[arg(long, short, help = "Session name (skips rename prompt)")]
         name: Option<String>,
+        /// Write the recording here instead of storage ('-' streams to stdout)
+        #[arg(
+            long,
+            short,
+            value_name = "PATH",
+            conflicts_with = "name",
+            help = "Write the recording to PATH instead of storage ('-' streams to stdout)"
+        )]
+        output: Option<String>,
+        /// Show elapsed time and file size while recording
+        #[arg(long, help = "Show elapsed time and recording size while recording")]
+        status: bool,
+        /// Terminal width to record with
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(u16).range(1..),
+            help = "Record with a terminal N columns wide (overrides [recording] cols)"
+        )]
+        cols: Option<u16>,
+        /// Terminal height to record with
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(u16).range(1..),
+            help = "Record with a terminal N rows high (overrides [recording] rows)"
+        )]
+        rows: Option<u16>,
         /// Arguments to pass to the agent command
         #[arg(last = true, help = "Arguments to pass to the agent (after --)")]
         args: Vec<String>,
@@ -81,17 +177,71 @@ EXAMPLES:
     /// Show storage statistics
     #[command(long_about = "Display storage statistics for recorded sessions.
 
-Shows total size, disk usage percentage, session count by agent,
-and age of the oldest recording.
+Shows total size, disk usage percentage, session count and size by
+agent, and age of the oldest recording.
 
-EXAMPLE:
+Each run also records the day's total size in a small history file in
+the storage directory. With --trend, the growth over the last N days
+(default 30) is shown as a sparkline.
+
+With --json, prints the statistics as a JSON object instead. Fields:
+total_size (bytes), session_count, sessions_by_agent, sizes_by_agent
+(bytes), disk_percentage and oldest_session (same shape as
+'agr list --json' entries, or null).
+
+EXAMPLES:
     agr status
+    agr status --trend 14
+    agr status --json
 
 OUTPUT:
     Agent Sessions: 1.2 GB (0.5% of disk)
        Sessions: 23 total (claude: 15, codex: 8)
+       Size: claude: 900 MiB, codex: 300 MiB
        Oldest: 2025-01-01 (20 days ago)")]
-    Status,
+    Status {
+        /// Print machine-readable JSON
+        #[arg(long, help = "Print statistics as JSON")]
+        json: bool,
+        /// Show total-size growth over the last N days
+        #[arg(
+            long,
+            value_name = "DAYS",
+            num_args = 0..=1,
+            default_missing_value = "30",
+            value_parser = clap::value_parser!(u32).range(1..),
+            conflicts_with = "json",
+            help = "Show size growth over the last DAYS days [default: 30]"
+        )]
+        trend: Option<u32>,
+    },
+
+    /// Show recording analytics
+    #[command(long_about = "Display aggregate analytics for recorded sessions.
+
+Shows total recorded hours, average session length, the busiest day
+of the week, and per-agent session counts and durations. Durations
+come from each recording's header when available.
+
+Durations are cached in ~/.cache/agr/index.json (see 'agr list');
+--refresh re-reads every recording.
+
+EXAMPLES:
+    agr stats
+    agr stats --refresh
+
+OUTPUT:
+    Recording Stats: 23 sessions, 14.2 hours recorded
+       Average session: 37m 2s
+       Busiest day: Tuesday (7 sessions)
+       By agent:
+          claude: 15 sessions, 9h 48m
+          codex: 8 sessions, 4h 24m")]
+    Stats {
+        /// Rebuild the storage index
+        #[arg(long, help = "Re-read every recording instead of using the index")]
+        refresh: bool,
+    },
 
     /// Interactive cleanup of old sessions
     #[command(
@@ -106,6 +256,19 @@ EXAMPLES:
     agr cleanup --agent claude           Only show Claude sessions
     agr cleanup --older-than 60          Only show sessions older than 60 days
     agr cleanup --agent codex --older-than 30
+    agr cleanup --free 2GB               Delete the oldest sessions until 2 GB is freed
+    agr cleanup --since 2025-01-01 --until 2025-01-31
+    agr cleanup --permanent              Delete for good instead of using the trash
+
+With --free, the oldest matching sessions are selected until their total
+size reaches the target (e.g. 500MB, 2GB, 1.5GiB) and you are asked to
+confirm the selection.
+
+Pinned sessions (see 'agr pin') are listed separately and never deleted.
+
+Deleted sessions are moved to the trash (~/.local/share/agr/trash) so
+they can be restored with 'agr trash restore'. Set [storage] use_trash =
+false or pass --permanent to remove them outright.
 
 INTERACTIVE OPTIONS:
     [number]    Delete the N oldest sessions
@@ -120,6 +283,152 @@ INTERACTIVE OPTIONS:
         /// Only show sessions older than N days
         #[arg(long, help = "Only show sessions older than N days")]
         older_than: Option<u32>,
+        /// Delete the oldest sessions until this much space is freed
+        #[arg(
+            long,
+            value_name = "SIZE",
+            value_parser = crate::storage::parse_size,
+            help = "Delete the oldest sessions until SIZE is freed (e.g. 500MB, 2GB, 1.5GiB)"
+        )]
+        free: Option<u64>,
+        /// Only include sessions recorded on or after this date
+        #[arg(
+            long,
+            value_name = "DATE",
+            value_parser = crate::storage::parse_date_bound,
+            help = "Only sessions recorded on or after DATE (2025-01-01, 2025-01-01T09:30, or 7d, 12h, 2w ago)"
+        )]
+        since: Option<crate::storage::DateBound>,
+        /// Only include sessions recorded on or before this date
+        #[arg(
+            long,
+            value_name = "DATE",
+            value_parser = crate::storage::parse_date_bound,
+            help = "Only sessions recorded on or before DATE (a bare date includes the whole day)"
+        )]
+        until: Option<crate::storage::DateBound>,
+        /// Delete permanently instead of moving to the trash
+        #[arg(
+            long,
+            help = "Delete permanently instead of moving sessions to the trash"
+        )]
+        permanent: bool,
+    },
+
+    /// Restore or empty deleted recordings
+    #[command(
+        subcommand,
+        long_about = "Manage recordings deleted by 'agr cleanup'.
+
+Deleted sessions are moved to ~/.local/share/agr/trash together with
+their sidecars (backup, bookmarks, tags, pin, notes). A manifest records
+where each one came from, so it can be put back.
+
+EXAMPLES:
+    agr trash list                   Show trashed recordings
+    agr trash restore session.cast   Move a recording back
+    agr trash empty                  Delete everything in the trash for good"
+    )]
+    Trash(TrashCommands),
+
+    /// Protect a recording from cleanup
+    #[command(long_about = "Pin a recording so that 'agr cleanup' never deletes it.
+
+Pinned recordings are listed separately by cleanup and are excluded
+from every selection ('all', 'old', a count, or --free). The pin is
+stored as an empty '<file>.cast.pinned' file next to the recording.
+
+EXAMPLES:
+    agr pin claude/reference.cast
+    agr unpin claude/reference.cast")]
+    Pin {
+        /// Path to the .cast file to pin
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+    },
+
+    /// Allow cleanup to delete a pinned recording again
+    #[command(
+        long_about = "Remove the pin from a recording so 'agr cleanup' may delete it.
+
+EXAMPLE:
+    agr unpin claude/reference.cast"
+    )]
+    Unpin {
+        /// Path to the .cast file to unpin
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+    },
+
+    /// Attach freeform notes to a recording
+    #[command(
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true,
+        long_about = "Append a note to a recording, or show its notes.
+
+Notes are kept in a Markdown file next to the recording
+('<file>.cast.md'), each under a heading with the time it was added.
+The notes file follows the recording when it is renamed.
+
+EXAMPLES:
+    agr note claude/session.cast \"Remember to check the retry logic\"
+    agr note show claude/session.cast"
+    )]
+    Note {
+        #[command(subcommand)]
+        command: Option<NoteCommands>,
+        /// Path to the .cast file
+        #[arg(required = true, help = "Path to the .cast recording file")]
+        file: Option<String>,
+        /// Text of the note
+        #[arg(required = true, help = "Note text to append")]
+        text: Option<String>,
+    },
+
+    /// Find and delete duplicate recordings
+    #[command(
+        long_about = "Find recordings with identical content and delete the extra copies.
+
+Recordings are compared by their events, so copies that differ only in
+the header timestamp (e.g. a session recorded twice by the shell
+integration) are treated as duplicates. Recordings without any events are
+never treated as duplicates. The newest copy in each group is kept; you
+are asked to confirm before anything is deleted.
+
+EXAMPLES:
+    agr dedupe              Review duplicates and confirm deletion
+    agr dedupe --dry-run    Only report duplicate groups"
+    )]
+    Dedupe {
+        /// Only report duplicate groups without deleting anything
+        #[arg(long, help = "Report duplicate groups without deleting")]
+        dry_run: bool,
+    },
+
+    /// Store missing durations in recording headers
+    #[command(
+        long_about = "Compute the duration of recordings whose header has none and store it.
+
+New recordings get their duration written when recording finishes. Older
+files without one make 'agr list --json' and 'agr stats' re-scan every
+event; backfilling stores the duration once. Files that already have a
+duration are left untouched, so running this again is safe.
+
+EXAMPLES:
+    agr backfill-duration claude/session.cast   Backfill one recording
+    agr backfill-duration --all                 Backfill every recording"
+    )]
+    BackfillDuration {
+        /// Path to the .cast file
+        #[arg(
+            required_unless_present = "all",
+            conflicts_with = "all",
+            help = "Path to the .cast recording file"
+        )]
+        file: Option<String>,
+        /// Backfill every recording in storage
+        #[arg(long, help = "Backfill every recording in storage")]
+        all: bool,
     },
 
     /// List recorded sessions
@@ -130,16 +439,56 @@ INTERACTIVE OPTIONS:
 Shows sessions sorted by date (newest first) with agent name,
 age, file size, and filename.
 
+With --json, prints a JSON array (newest first) instead of the table or
+interactive explorer. Each entry has: filename, agent, path, title,
+modified (RFC 3339), age_days, size (bytes) and duration (seconds).
+
 EXAMPLES:
     agr list                List all sessions
     agr ls                  Same as 'agr list' (alias)
     agr list claude         List only Claude sessions
-    agr list codex          List only Codex sessions"
+    agr list codex          List only Codex sessions
+    agr list --json         Machine-readable output for scripts
+    agr list --refresh      Re-read every recording
+    agr list --since 7d     Sessions from the last 7 days
+    agr list --since 2025-01-01 --until 2025-01-31
+
+--since and --until filter by the time recording started (from the
+header, or the file's modification time if the header has none).
+
+Durations and titles are cached in ~/.cache/agr/index.json, so only
+recordings that changed since the last listing are read again."
     )]
     List {
         /// Filter by agent name
         #[arg(help = "Filter sessions by agent name")]
         agent: Option<String>,
+
+        /// Print machine-readable JSON
+        #[arg(long, help = "Print sessions as a JSON array")]
+        json: bool,
+
+        /// Rebuild the storage index
+        #[arg(long, help = "Re-read every recording instead of using the index")]
+        refresh: bool,
+
+        /// Only include sessions recorded on or after this date
+        #[arg(
+            long,
+            value_name = "DATE",
+            value_parser = crate::storage::parse_date_bound,
+            help = "Only sessions recorded on or after DATE (2025-01-01, 2025-01-01T09:30, or 7d, 12h, 2w ago)"
+        )]
+        since: Option<crate::storage::DateBound>,
+
+        /// Only include sessions recorded on or before this date
+        #[arg(
+            long,
+            value_name = "DATE",
+            value_parser = crate::storage::parse_date_bound,
+            help = "Only sessions recorded on or before DATE (a bare date includes the whole day)"
+        )]
+        until: Option<crate::storage::DateBound>,
     },
 
     /// Analyze a recording with AI
@@ -153,7 +502,9 @@ For large files, analysis is parallelized across multiple chunks, with
 automatic retry and rate limit handling.
 
 The default agent is configured in ~/.config/agr/config.toml under
-[analysis].agent. Use --agent to override for a single run.
+[analysis].agent. Use --agent to override for a single run. Without
+either, the agent that produced the recording is used when it can be
+told from the storage directory (claude/...) or the header command.
 
 EXAMPLES:
     agr analyze session.cast                     Analyze with default agent
@@ -161,14 +512,30 @@ EXAMPLES:
     agr analyze session.cast --workers 4         Use 4 parallel workers
     agr analyze session.cast --no-parallel       Sequential mode
     agr analyze session.cast --timeout 180       3 minute timeout per chunk
+    agr analyze session.cast --from 600 --to 900 Only analyze minutes 10-15
+    agr analyze session.cast --level aggressive  Condense noisy output harder
+    agr analyze session.cast --review            Approve each marker before writing
+
+Press Ctrl-C to cancel: no new chunks are sent, running agents are
+stopped and nothing is written unless --write-partial is given. Press
+Ctrl-C again to quit immediately.
+
+If some chunks fail, the markers of the other chunks are still written and
+the failed time ranges are listed. Use --fail-on-error to write nothing
+instead.
+
+An http(s):// URL (e.g. an asciinema.org link) is downloaded to a new
+private temporary directory first, and the markers are written to that
+copy. The path of the annotated copy is printed when analysis finishes.
+Downloads need curl and are limited to 64 MiB.
 
 SUPPORTED AGENTS:
     claude      Claude Code CLI (default)
     codex       OpenAI Codex CLI
     gemini      Google Gemini CLI")]
     Analyze {
-        /// Path to the .cast file to analyze
-        #[arg(help = "Path to the .cast recording file")]
+        /// Path to the .cast file to analyze (or a URL)
+        #[arg(help = "Path to the .cast recording file, or an http(s) URL to download")]
         file: String,
         /// Override the configured analysis agent
         #[arg(long, short, help = "Agent to use: claude, codex, gemini")]
@@ -202,11 +569,74 @@ SUPPORTED AGENTS:
         /// Skip JSON schema enforcement for faster analysis (less reliable)
         #[arg(long, help = "Skip JSON schema enforcement (faster but less reliable)")]
         fast: bool,
+        /// Only analyze events from this many seconds into the recording
+        #[arg(
+            long,
+            value_name = "SECS",
+            help = "Start of the time window to analyze (seconds)"
+        )]
+        from: Option<f64>,
+        /// Only analyze events up to this many seconds into the recording
+        #[arg(
+            long,
+            value_name = "SECS",
+            help = "End of the time window to analyze (seconds)"
+        )]
+        to: Option<f64>,
+        /// Extraction preset (default: [analysis.extraction].level or normal)
+        #[arg(long, value_enum, help = "Extraction aggressiveness preset")]
+        level: Option<ExtractionLevel>,
+        /// Accept, edit or skip each proposed marker before it is written
+        #[arg(long, help = "Review each marker before writing it")]
+        review: bool,
+        /// On Ctrl-C, write the markers of the chunks that finished
+        #[arg(long, help = "Keep markers from finished chunks when cancelled")]
+        write_partial: bool,
+        /// Write nothing if any chunk fails
+        #[arg(long, help = "Write no markers if any chunk fails")]
+        fail_on_error: bool,
         /// Wait for keypress before exiting (used by TUI)
         #[arg(long, hide = true)]
         wait: bool,
     },
 
+    /// Summarize a recording in a short paragraph
+    #[command(
+        long_about = "Summarize a recording in 3-5 sentences using an AI agent.
+
+Extracts the meaningful content of the recording (like 'agr analyze') and
+asks the agent for a short prose summary of what happened. Long recordings
+are summarized chunk by chunk and the chunk summaries are then combined.
+
+The summary is printed to stdout. Use --title to store it as the
+recording's title, or --save to write it to a <file>.cast.summary file
+next to the recording. The summary file is kept with the recording when
+it is renamed or moved to the trash.
+
+EXAMPLES:
+    agr summarize session.cast                   Print a summary
+    agr summarize session.cast --agent codex     Use Codex instead
+    agr summarize session.cast --title           Store as recording title
+    agr summarize session.cast --save            Write session.cast.summary"
+    )]
+    Summarize {
+        /// Path to the .cast file to summarize
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// Override the configured analysis agent
+        #[arg(long, short, help = "Agent to use: claude, codex, gemini")]
+        agent: Option<String>,
+        /// Timeout per agent call in seconds (default: 120)
+        #[arg(long, short, help = "Timeout per agent call in seconds")]
+        timeout: Option<u64>,
+        /// Store the summary as the recording's header title
+        #[arg(long, help = "Store the summary in the header title")]
+        title: bool,
+        /// Write the summary to a .summary file next to the recording
+        #[arg(long, help = "Write the summary to a .summary sidecar file")]
+        save: bool,
+    },
+
     /// Play a recording with the native player
     #[command(long_about = "Play an asciicast recording using the native player.
 
@@ -218,18 +648,65 @@ EXAMPLES:
     agr play session.cast                 Play by filename (fuzzy match)
     agr play claude/session.cast          Play using short format
     agr play /path/to/session.cast        Play by absolute path
+    agr play session.cast --loop          Loop until you quit (demo mode)
+    agr play session.cast --loop-count 3  Play three times, then exit
+    agr play session.cast --speed 2       Start at 2x speed
+    agr play session.cast --strict        Refuse to play files with corrupt lines
+    agr play session.cast --record-to review.cast
+                                          Save what you watched as a new cast
+    cat session.cast | agr play -         Play a recording piped to stdin
+    agr play https://asciinema.org/a/123  Download and play a shared recording
+
+With --record-to, every frame drawn into the viewport is written to a new
+cast file when playback ends, timed as it appeared on screen: seeks,
+pauses and speed changes are replayed exactly. Use it to turn a review of
+a recording into a walkthrough. The progress and status bars are not
+recorded.
 
 PLAYER CONTROLS:
     q, Esc      Quit
     Space       Pause/resume
     +/-         Adjust playback speed
+    1, 2, 3     Speed presets (1x, 2x, 4x)
     <, > or ,, .  Seek backward/forward 5s
+    ,, . (paused) Step backward/forward one event
+    g           Go to a time (mm:ss or seconds)
     m           Jump to next marker
+    l           List markers; Enter seeks to the selected one
+    b, n/N      Add bookmark / next, previous bookmark
     ?           Show help overlay")]
     Play {
-        /// Path to the .cast file to play
-        #[arg(help = "Path to the .cast recording file")]
+        /// Path to the .cast file to play ('-' for stdin, or a URL)
+        #[arg(help = "Path to the .cast recording file ('-' reads stdin, or an http(s) URL)")]
         file: String,
+        /// Restart from the beginning when the recording ends
+        #[arg(long = "loop", help = "Loop playback until you quit")]
+        loop_playback: bool,
+        /// Stop after this many iterations (implies --loop)
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Loop N times, then exit"
+        )]
+        loop_count: Option<u32>,
+        /// Initial playback speed (overrides [player] default_speed)
+        #[arg(
+            long,
+            value_name = "X",
+            help = "Start playback at this speed (0.1 to 16)"
+        )]
+        speed: Option<f64>,
+        /// Fail on malformed event lines instead of skipping them
+        #[arg(long, help = "Fail on corrupt event lines instead of skipping them")]
+        strict: bool,
+        /// Record the playback session to a new cast file
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Record what you watch (viewport only) to a new .cast file"
+        )]
+        record_to: Option<String>,
     },
 
     /// Copy a recording to the clipboard
@@ -239,23 +716,103 @@ On macOS, the file is copied as a file reference, allowing direct paste
 into Slack, email, or other applications as an attachment. On Linux,
 the file content is copied as text (file copy not supported).
 
+With --as html, a self-contained HTML snippet is copied instead: the cast
+is embedded (gzipped, base64) along with a minimal inline player, so it can
+be pasted into a web page or HTML document and played without asciinema.
+With --as text, the recording is replayed and the plain text it printed
+(including lines that scrolled off screen) is copied, e.g. for an issue.
+
 Recordings can be specified by absolute path, short format (agent/file.cast),
 or just filename (fuzzy matches across all agents).
 
 EXAMPLES:
     agr copy session.cast                 Copy by filename (fuzzy match)
     agr copy claude/session.cast          Copy using short format
-    agr copy /path/to/session.cast        Copy by absolute path")]
+    agr copy /path/to/session.cast        Copy by absolute path
+    agr copy session.cast --as html       Copy as a playable HTML snippet
+    agr copy session.cast --as text       Copy a plain-text transcript")]
     Copy {
         /// Path to the .cast file to copy
         #[arg(help = "Path to the .cast recording file")]
         file: String,
+        /// What to put on the clipboard
+        #[arg(
+            long = "as",
+            value_enum,
+            default_value_t = CopyFormat::File,
+            help = "Copy the file itself, a playable HTML snippet or a text transcript"
+        )]
+        format: CopyFormat,
+    },
+
+    /// Rename a recording
+    #[command(long_about = "Rename a recording within its agent directory.
+
+Companion files (backups, bookmarks, tags) are renamed along with the
+recording. The new name must be a plain filename; '.cast' is appended
+if missing. Fails if a recording with the new name already exists.
+
+EXAMPLES:
+    agr rename 20250119-100000.cast auth-refactor
+    agr rename claude/session.cast fix-login-bug.cast")]
+    Rename {
+        /// Path to the .cast file to rename
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// New filename for the recording
+        #[arg(help = "New filename (without directory; .cast is optional)")]
+        new_name: String,
+    },
+
+    /// Render a frame of a recording as text
+    #[command(
+        long_about = "Render the terminal screen of a recording at a point in time.
+
+Replays the recording up to the given time and prints the screen, which
+is handy for thumbnails, previews or pasting into bug reports. The time
+is either a percentage of the recording length (50%) or seconds from
+the start (12.5). Use --format ansi to keep colors, text attributes and
+inline images (Sixel, iTerm2). Malformed event lines are skipped with a
+warning unless --strict is given.
+
+EXAMPLES:
+    agr snapshot session.cast                   Final screen (100%)
+    agr snapshot session.cast --at 50%          Screen halfway through
+    agr snapshot session.cast --at 12.5         Screen after 12.5 seconds
+    agr snapshot session.cast --at 50% --out snap.txt
+    agr snapshot session.cast --format ansi     Keep colors"
+    )]
+    Snapshot {
+        /// Path to the .cast file to render
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// Point in the recording to render
+        #[arg(
+            long,
+            default_value = "100%",
+            help = "Time to render: percentage (50%) or seconds (12.5)"
+        )]
+        at: SnapshotAt,
+        /// Write the snapshot to a file instead of stdout
+        #[arg(
+            long,
+            short,
+            value_name = "FILE",
+            help = "Write to file instead of stdout"
+        )]
+        out: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = SnapshotFormat::Text, help = "Output format")]
+        format: SnapshotFormat,
+        /// Fail on malformed event lines instead of skipping them
+        #[arg(long, help = "Fail on corrupt event lines instead of skipping them")]
+        strict: bool,
     },
 
     /// Manage markers in cast files
     #[command(
         subcommand,
-        long_about = "Add and list markers in asciicast recording files.
+        long_about = "Add, list, import and export markers in asciicast recording files.
 
 Markers are annotations at specific timestamps in a recording,
 useful for highlighting key moments like errors, decisions, or
@@ -264,10 +821,28 @@ milestones. Markers use the native asciicast v3 marker format.
 EXAMPLES:
     agr marker add session.cast 45.2 \"Build failed\"
     agr marker add session.cast 120.5 \"Deployment complete\"
-    agr marker list session.cast"
+    agr marker list session.cast
+    agr marker import session.cast markers.json
+    agr marker export session.cast --out subs.vtt"
     )]
     Marker(MarkerCommands),
 
+    /// View and edit recording metadata
+    #[command(
+        subcommand,
+        long_about = "Show and edit the header metadata of an asciicast recording.
+
+Only the header line is rewritten; all events are preserved exactly.
+Editable keys: title, command, idle_time_limit. Setting an empty value
+removes the key from the header.
+
+EXAMPLES:
+    agr meta show session.cast
+    agr meta set session.cast title \"Fixing the login bug\"
+    agr meta set session.cast idle_time_limit 2.5"
+    )]
+    Meta(MetaCommands),
+
     /// Manage configured agents
     #[command(
         subcommand,
@@ -299,6 +874,27 @@ EXAMPLES:
     )]
     Config(ConfigCommands),
 
+    /// Diagnose the recording environment
+    #[command(
+        long_about = "Check that everything needed to record sessions is in place.
+
+Prints a checklist with a pass/warn/fail result and a hint for each
+problem:
+  - The config file parses and is valid
+  - asciinema is on PATH
+  - Each enabled agent is on PATH
+  - The storage directory exists and is writable
+  - Shell integration is installed (once)
+  - A clipboard tool for 'agr copy' is installed
+
+Exits with status 1 if any check fails; warnings do not affect the
+exit status.
+
+EXAMPLE:
+    agr doctor"
+    )]
+    Doctor,
+
     /// Manage shell integration
     #[command(
         subcommand,
@@ -306,23 +902,33 @@ EXAMPLES:
 
 Shell integration adds wrapper functions to your shell that automatically
 record sessions when you run configured agents. It modifies your .zshrc
-or .bashrc with a clearly marked section.
+or .bashrc with a clearly marked section (on Windows, the PowerShell
+profile at $PROFILE). If fish is installed, completions are also written
+to ~/.config/fish/completions/agr.fish.
 
 EXAMPLES:
     agr shell status         Check if shell integration is installed
     agr shell install        Install shell integration
     agr shell uninstall      Remove shell integration
+    agr shell doctor         Find and repair duplicate or stale sections
 
 After installing, restart your shell or run: source ~/.zshrc"
     )]
     Shell(ShellCommands),
 
     /// Optimize asciicast recordings (removes silence)
-    #[command(long_about = "Optimize asciicast recording files by removing silence.
+    #[command(
+        visible_alias = "transform",
+        long_about = "Optimize asciicast recording files by removing silence.
 
 Optimization modifies the timing of recordings by capping long pauses
 at a configurable threshold.
 
+With --preview, runs the analysis extraction pipeline instead and prints
+exactly the text 'agr analyze' would send, followed by extraction
+statistics. No agent is called and the file is not modified. Add --json
+to print the statistics as JSON.
+
 THRESHOLD RESOLUTION:
     1. CLI argument (explicit user intent)
     2. Header's idle_time_limit (recording author's intent)
@@ -336,7 +942,17 @@ EXAMPLES:
         Use explicit 1.5s threshold (note: requires = for value)
 
     agr optimize --remove-silence --output fast.cast session.cast
-        Write to separate file, preserving original")]
+        Write to separate file, preserving original
+
+    agr transform --preview session.cast
+        Show the extracted analysis content and statistics
+
+    agr transform --preview --json session.cast
+        Print extraction statistics as JSON
+
+    agr transform --preview --level light session.cast
+        Preview a lighter extraction that keeps more content"
+    )]
     Optimize {
         /// Remove silence by capping intervals at threshold
         #[arg(
@@ -353,11 +969,115 @@ EXAMPLES:
         #[arg(long, short, value_name = "FILE", help = "Output file path")]
         output: Option<String>,
 
+        /// Preview the analysis extraction without calling an agent
+        #[arg(
+            long,
+            conflicts_with_all = ["remove_silence", "output"],
+            help = "Print the content 'agr analyze' would send"
+        )]
+        preview: bool,
+
+        /// Extraction preset for the preview
+        #[arg(
+            long,
+            value_enum,
+            requires = "preview",
+            help = "Extraction aggressiveness preset"
+        )]
+        level: Option<ExtractionLevel>,
+
+        /// Print preview statistics as JSON
+        #[arg(
+            long,
+            requires = "preview",
+            help = "Print extraction statistics as JSON"
+        )]
+        json: bool,
+
         /// Path to the .cast file to optimize
         #[arg(help = "Path to the .cast recording file")]
         file: String,
     },
 
+    /// Strip secrets from a recording
+    #[command(long_about = "Replace secrets echoed to the terminal with ████.
+
+Scans output, input and marker events, plus the recording's title and
+command, for common secret patterns and rewrites the recording. Timing,
+marker positions and all other events are preserved.
+
+BUILT-IN PATTERNS:
+    AWS access keys (AKIA..., ASIA...)
+    GitHub tokens (ghp_, gho_, ghu_, ghs_, ghr_, github_pat_)
+    JWTs (eyJ....eyJ....)
+    OpenAI-style keys (sk-...)
+
+Add your own regular expressions under [redact] patterns in the config.
+Without --out the file is modified in place, and its .bak backup is
+redacted too (or removed if it cannot be parsed).
+
+EXAMPLES:
+    agr redact session.cast --out clean.cast   Write a redacted copy
+    agr redact claude/session.cast             Redact in place")]
+    Redact {
+        /// Path to the .cast file to redact
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// Write the redacted recording to a separate file
+        #[arg(long, short, value_name = "FILE", help = "Output file path")]
+        out: Option<String>,
+    },
+
+    /// Export a recording for other asciicast tools
+    #[command(long_about = "Export a recording as a plain asciicast v3 file.
+
+Some players and converters do not understand marker events. Use
+--flatten-markers to turn each marker into a dimmed '# marker: label'
+output line, or --no-markers to drop markers entirely. The timing of
+all other events is unchanged. The original recording is not modified.
+
+EXAMPLES:
+    agr export session.cast out.cast --flatten-markers
+    agr export claude/session.cast out.cast --no-markers")]
+    Export {
+        /// Path to the .cast file to export
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// Path of the exported file
+        #[arg(help = "Output file path")]
+        out: String,
+        /// Export format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Cast, help = "Export format")]
+        format: ExportFormat,
+        /// Rewrite markers as dimmed output lines
+        #[arg(
+            long,
+            conflicts_with = "no_markers",
+            help = "Rewrite markers as '# marker: label' output lines"
+        )]
+        flatten_markers: bool,
+        /// Drop markers from the export
+        #[arg(long, help = "Remove markers from the export")]
+        no_markers: bool,
+    },
+
+    /// Check a recording for problems before sharing it
+    #[command(long_about = "Check a recording for problems before sharing it.
+
+Reports invalid or missing header fields, event lines that do not parse,
+a final line cut off mid-write, times that go backwards, markers out of
+order or past the end, unterminated escape sequences and out-of-range
+colors. Exits with status 1 if the recording is not playable.
+
+EXAMPLES:
+    agr validate session.cast
+    agr validate claude/session.cast")]
+    Validate {
+        /// Path to the .cast file to check
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+    },
+
     /// Generate shell completions (internal use)
     #[command(hide = true)]
     Completions {
@@ -377,6 +1097,16 @@ EXAMPLES:
         #[arg(long)]
         files: bool,
 
+        /// List agent names for completion (default: enabled agents)
+        #[arg(
+            long,
+            value_enum,
+            num_args = 0..=1,
+            default_missing_value = "enabled",
+            require_equals = true
+        )]
+        agents: Option<AgentCompletion>,
+
         /// Limit number of files returned
         #[arg(long, default_value = "10")]
         limit: usize,
@@ -412,18 +1142,176 @@ EXAMPLE:
     #[command(
         long_about = "List all markers in a cast file with their timestamps and labels.
 
-EXAMPLE:
+Use --format to show times as mm:ss (clock), raw seconds or a percentage
+of the recording duration, and --json to print the markers as a JSON
+array of {\"timestamp\", \"label\"} objects for scripting.
+
+EXAMPLES:
     agr marker list ~/recorded_agent_sessions/claude/session.cast
+    agr marker list session.cast --format clock
+    agr marker list session.cast --json | jq '.[].label'
 
 OUTPUT:
     Markers:
-      [45.2s] Build error
-      [120.5s] Deployment complete"
+      45.2s: Build error
+      120.5s: Deployment complete"
     )]
     List {
         /// Path to the .cast file
         #[arg(help = "Path to the .cast recording file")]
         file: String,
+        /// How marker times are shown
+        #[arg(long, value_enum, help = "Time format [default: 45.2s]")]
+        format: Option<MarkerTimeFormat>,
+        /// Print machine-readable JSON
+        #[arg(
+            long,
+            conflicts_with = "format",
+            help = "Print markers as a JSON array"
+        )]
+        json: bool,
+    },
+    /// Export markers as WebVTT or SRT subtitles
+    #[command(
+        long_about = "Export the markers of a cast file as WebVTT or SRT subtitles.
+
+Each marker becomes one cue that starts at the marker and lasts until
+the next marker; the last cue lasts 3 seconds. Use the subtitles as
+captions when converting a recording to video. The format defaults to
+the --out extension (.vtt or .srt), else WebVTT.
+
+EXAMPLES:
+    agr marker export session.cast --out subs.vtt
+    agr marker export session.cast --format srt > subs.srt"
+    )]
+    Export {
+        /// Path to the .cast file
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// Subtitle format
+        #[arg(
+            long,
+            value_enum,
+            help = "Subtitle format [default: from --out, else vtt]"
+        )]
+        format: Option<SubtitleFormat>,
+        /// Write the subtitles to a file instead of stdout
+        #[arg(
+            long,
+            short,
+            value_name = "FILE",
+            help = "Write to file instead of stdout"
+        )]
+        out: Option<String>,
+    },
+    /// Import markers from a JSON or CSV file
+    #[command(long_about = "Import markers into a cast file from a JSON or CSV file.
+
+JSON input is an array of {\"timestamp\", \"label\", \"category\"} objects;
+CSV input has timestamp,label[,category] rows with an optional header.
+A category is written as a [CATEGORY] label prefix. Markers are inserted
+in time order; exact duplicates (same time and label) are skipped, and
+markers past the end of the recording are skipped with a warning. The
+format defaults to the markers file extension, else JSON.
+
+EXAMPLES:
+    agr marker import session.cast markers.json
+    agr marker import session.cast markers.txt --format csv")]
+    Import {
+        /// Path to the .cast file
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// Path to the markers file
+        #[arg(help = "JSON or CSV file with the markers to import")]
+        markers: String,
+        /// Markers file format
+        #[arg(
+            long,
+            value_enum,
+            help = "Markers file format [default: from extension, else json]"
+        )]
+        format: Option<ImportFormat>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MetaCommands {
+    /// Set a header metadata field
+    #[command(long_about = "Set a header metadata field in a cast file.
+
+Supported keys are title, command and idle_time_limit (seconds, must be
+greater than 0). An empty value removes the field from the header.
+
+EXAMPLES:
+    agr meta set claude/session.cast title \"Refactor auth module\"
+    agr meta set claude/session.cast idle_time_limit 2
+    agr meta set claude/session.cast command \"\"")]
+    Set {
+        /// Path to the .cast file
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+        /// Metadata key to set
+        #[arg(help = "Metadata key: title, command or idle_time_limit")]
+        key: String,
+        /// New value for the key
+        #[arg(help = "New value (empty to remove the field)")]
+        value: String,
+    },
+    /// Show the header metadata of a cast file
+    #[command(long_about = "Show the header metadata of a cast file.
+
+EXAMPLE:
+    agr meta show claude/session.cast")]
+    Show {
+        /// Path to the .cast file
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrashCommands {
+    /// List recordings in the trash
+    #[command(
+        long_about = "List trashed recordings with their size, deletion time and
+original location, oldest first.
+
+EXAMPLE:
+    agr trash list"
+    )]
+    List,
+    /// Move a trashed recording back
+    #[command(
+        long_about = "Move a recording out of the trash to where it was deleted from.
+
+NAME is the name shown by 'agr trash list' or the original file name;
+when several recordings match, the most recently deleted one wins.
+'.cast' may be left off. Restoring fails if a file with that name exists
+again.
+
+EXAMPLE:
+    agr trash restore session.cast"
+    )]
+    Restore {
+        /// Name of the trashed recording
+        #[arg(help = "Recording name from 'agr trash list'")]
+        name: String,
+    },
+    /// Permanently delete everything in the trash
+    Empty,
+}
+
+#[derive(Subcommand)]
+pub enum NoteCommands {
+    /// Show the notes of a recording
+    #[command(long_about = "Print the notes attached to a recording.
+
+EXAMPLE:
+    agr note show claude/session.cast")]
+    Show {
+        /// Path to the .cast file
+        #[arg(help = "Path to the .cast recording file")]
+        file: String,
     },
 }
 
@@ -528,9 +1416,26 @@ pub enum ConfigCommands {
 Shows all settings including storage paths, agent list, shell options,
 and recording preferences.
 
-EXAMPLE:
-    agr config show")]
-    Show,
+With --diff, only the values that differ from the defaults are shown,
+each with its default. With --annotated, the full config is shown with
+'# (default)' after every value that is still at its default.
+
+EXAMPLES:
+    agr config show
+    agr config show --diff
+    agr config show --annotated")]
+    Show {
+        /// Only show values that differ from the defaults
+        #[arg(long, help = "Only show values that differ from the defaults")]
+        diff: bool,
+        /// Mark values that are still at their default
+        #[arg(
+            long,
+            conflicts_with = "diff",
+            help = "Mark values that are still at their default"
+        )]
+        annotated: bool,
+    },
     /// Open configuration file in your default editor
     #[command(long_about = "Open the configuration file in your default editor.
 
@@ -541,6 +1446,20 @@ EXAMPLE:
     agr config edit
     EDITOR=nano agr config edit")]
     Edit,
+    /// Check the configuration against this machine
+    #[command(
+        long_about = "Validate the configuration against the local environment.
+
+Checks that enabled agents are installed, the storage directory is
+writable, and an analysis agent can be resolved. Missing agents are
+reported as warnings; an unwritable storage directory or a missing
+analysis agent that is explicitly configured (or needed for
+auto_analyze) is an error and makes the command exit with status 1.
+
+EXAMPLE:
+    agr config check"
+    )]
+    Check,
     /// Add missing fields to config file
     #[command(long_about = "Add missing fields to your config file.
 
@@ -563,8 +1482,8 @@ EXAMPLES:
     /// Reset configuration to defaults
     #[command(long_about = "Reset configuration to default values.
 
-Backs up the current config file (if it exists) to config.toml.bak,
-then writes a fresh default configuration.
+Backs up the current config file (if it exists) to config.toml.bak.N
+(the first free number), then writes a fresh default configuration.
 
 EXAMPLES:
     agr config reset               Interactive mode (asks confirmation)
@@ -611,4 +1530,24 @@ the shell script. Restart your shell after uninstalling.
 EXAMPLE:
     agr shell uninstall")]
     Uninstall,
+    /// Check shell integration for problems and offer to repair them
+    #[command(
+        long_about = "Check shell integration for problems and offer to repair them.
+
+Looks for:
+  - RC files containing the AGR section more than once
+  - Old-style sections that source a script file that no longer exists
+  - Completion files left behind after the integration was removed
+
+Each problem is listed, then you are asked whether to repair them.
+
+EXAMPLES:
+    agr shell doctor               Report problems and prompt to repair
+    agr shell doctor --yes         Repair without confirmation"
+    )]
+    Doctor {
+        /// Repair problems without confirmation prompt
+        #[arg(long, short = 'y', help = "Skip confirmation prompt")]
+        yes: bool,
+    },
 }
@@ -1,6 +1,7 @@
 //! Copy orchestrator for clipboard operations.
 
 use super::error::{ClipboardError, MAX_CONTENT_SIZE};
+use super::html::cast_html;
 use super::result::CopyResult;
 use super::tool::{CopyTool, CopyToolError};
 use super::tools::platform_tools;
@@ -46,7 +47,6 @@ impl Copy {
         }
 
         // Try file copy with tools that support it
-        let mut last_error: Option<String> = None;
         for tool in &self.tools {
             if tool.is_available() && tool.can_copy_files() {
                 match tool.try_copy_file(path) {
@@ -62,7 +62,6 @@ impl Copy {
                             tool.name(),
                             msg
                         );
-                        last_error = Some(msg);
                         continue;
                     }
                 }
@@ -70,21 +69,39 @@ impl Copy {
         }
 
         // Check file size before content fallback to prevent memory exhaustion
-        let metadata = std::fs::metadata(path)?;
-        if metadata.len() > MAX_CONTENT_SIZE {
-            return Err(ClipboardError::FileTooLarge {
-                size_mb: metadata.len() as f64 / (1024.0 * 1024.0),
-                max_mb: MAX_CONTENT_SIZE / (1024 * 1024),
-            });
-        }
+        check_content_size(path)?;
 
         // Fall back to content copy
         let content = std::fs::read_to_string(path)?;
+        self.text(&content)
+    }
+
+    /// Copy a file as a self-contained HTML player snippet.
+    ///
+    /// The snippet embeds the gzipped cast (see [`cast_html`]), so the same
+    /// size limit as the content fallback applies to the cast file.
+    pub fn html(&self, path: &Path, title: &str) -> Result<CopyResult, ClipboardError> {
+        if !path.exists() {
+            return Err(ClipboardError::FileNotFound {
+                path: path.to_path_buf(),
+            });
+        }
+        check_content_size(path)?;
+        let content = std::fs::read_to_string(path)?;
+        self.text(&cast_html(&content, title))
+    }
+
+    /// Copy text to the clipboard with the first tool that accepts it.
+    ///
+    /// Text over the content size limit is refused with `FileTooLarge`.
+    pub fn text(&self, content: &str) -> Result<CopyResult, ClipboardError> {
         let size = content.len();
+        check_size(size as u64)?;
+        let mut last_error: Option<String> = None;
 
         for tool in &self.tools {
             if tool.is_available() {
-                match tool.try_copy_text(&content) {
+                match tool.try_copy_text(content) {
                     Ok(()) => {
                         return Ok(CopyResult::content_copied(tool.method(), size));
                     }
@@ -112,6 +129,21 @@ impl Copy {
     }
 }
 
+/// Refuse files too large to hold in memory for a content copy.
+fn check_content_size(path: &Path) -> Result<(), ClipboardError> {
+    check_size(std::fs::metadata(path)?.len())
+}
+
+fn check_size(size: u64) -> Result<(), ClipboardError> {
+    if size > MAX_CONTENT_SIZE {
+        return Err(ClipboardError::FileTooLarge {
+            size_mb: size as f64 / (1024.0 * 1024.0),
+            max_mb: MAX_CONTENT_SIZE / (1024 * 1024),
+        });
+    }
+    Ok(())
+}
+
 impl Default for Copy {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,498 @@
+//! Self-contained HTML player snippets for recordings.
+//!
+//! [`cast_html`] wraps a cast in an HTML fragment that plays it back without
+//! any external assets: the cast is gzipped and base64-encoded into an inert
+//! `<script>` element, and a small inline script decodes it with the
+//! browser's `DecompressionStream` and replays the output events into a
+//! `<pre>`. Escape sequences are stripped rather than rendered, so the
+//! snippet shows the text of the session, not its colors.
+//!
+//! The compressor is a plain LZ77 pass emitting fixed-Huffman deflate
+//! blocks. It is not as tight as zlib, but terminal output is repetitive
+//! enough that it still shrinks a cast several times over. The tests check
+//! the gzip and base64 output against the system `gzip` and `base64` tools,
+//! not only against the decoders written for them.
+
+/// MIME type of the element holding the encoded cast.
+pub const CAST_DATA_TYPE: &str = "application/x-asciicast+gzip";
+
+/// Inline player: decodes the cast data and replays `o` events.
+///
+/// Idle gaps are capped at the header's `idle_time_limit` (2s if unset).
+const PLAYER_SCRIPT: &str = r#"(function(){
+var root=document.currentScript.parentNode,screen=root.querySelector("pre"),button=root.querySelector("button");
+var data=root.querySelector("script[type='application/x-asciicast+gzip']").textContent.trim();
+function load(){var bytes=Uint8Array.from(atob(data),function(c){return c.charCodeAt(0);});
+return new Response(new Blob([bytes]).stream().pipeThrough(new DecompressionStream("gzip"))).text();}
+function frames(text){var lines=text.split("\n"),header=JSON.parse(lines[0]),limit=header.idle_time_limit||2,last=0,t=0,out=[];
+for(var i=1;i<lines.length;i++){var e;try{e=JSON.parse(lines[i]);}catch(_){continue;}
+if(!Array.isArray(e))continue;var time=header.version>=3?last+e[0]:e[0];t+=Math.min(Math.max(time-last,0),limit);last=time;
+if(e[1]==="o")out.push([t,e[2]]);}return out;}
+function render(text){screen.textContent+=text.replace(/\x1b\][^\x07\x1b]*(\x07|\x1b\\)/g,"").replace(/\x1b\[[0-9;?]*[ -\/]*[@-~]/g,"").replace(/\x1b[()][0-9A-Z]|\x1b[=>78]/g,"").replace(/\r\n/g,"\n").replace(/\r/g,"");}
+button.onclick=function(){button.disabled=true;screen.textContent="";load().then(function(text){var f=frames(text),i=0,start=performance.now();
+(function tick(){var now=(performance.now()-start)/1000;while(i<f.length&&f[i][0]<=now)render(f[i++][1]);screen.scrollTop=screen.scrollHeight;
+if(i<f.length)setTimeout(tick,Math.min(100,(f[i][0]-now)*1000));else button.disabled=false;})();});};
+})();"#;
+
+/// Build an HTML fragment that embeds `cast` and plays it inline.
+///
+/// `title` is shown above the player and escaped for HTML.
+pub fn cast_html(cast: &str, title: &str) -> String {
+    let data = base64_encode(&gzip(cast.as_bytes()));
+    format!(
+        concat!(
+            "<div class=\"agr-player\" style=\"font-family:sans-serif\">\n",
+            "<div><strong>{title}</strong> <button type=\"button\">Play</button></div>\n",
+            "<pre style=\"background:#1e1e1e;color:#d4d4d4;padding:8px;max-height:32em;",
+            "overflow:auto;font:13px/1.25 monospace;white-space:pre\"></pre>\n",
+            "<script type=\"{data_type}\">{data}</script>\n",
+            "<script>{script}</script>\n",
+            "</div>\n"
+        ),
+        title = escape_html(title),
+        data_type = CAST_DATA_TYPE,
+        data = data,
+        script = PLAYER_SCRIPT,
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard base64 with padding.
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// CRC-32 (IEEE), as used in the gzip trailer.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Wrap `data` in a gzip member with a single fixed-Huffman deflate block.
+fn gzip(data: &[u8]) -> Vec<u8> {
+    // Magic, deflate, no flags, no mtime, no extra flags, unknown OS
+    let mut out = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+    out.extend(deflate(data));
+    out.extend(crc32(data).to_le_bytes());
+    out.extend((data.len() as u32).to_le_bytes());
+    out
+}
+
+const WINDOW_SIZE: usize = 32 * 1024;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+/// Candidates examined per position; bounds the cost on repetitive input.
+const MAX_CHAIN: usize = 32;
+const HASH_BITS: u32 = 15;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// Deflate `data` (RFC 1951) as one final block with the fixed Huffman codes.
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    bits.write(1, 1); // BFINAL
+    bits.write(1, 2); // BTYPE = fixed Huffman
+
+    let mut head = vec![usize::MAX; 1 << HASH_BITS];
+    let mut prev = vec![usize::MAX; WINDOW_SIZE];
+    let insert = |i: usize, head: &mut [usize], prev: &mut [usize]| {
+        if i + MIN_MATCH <= data.len() {
+            let h = hash(data, i);
+            prev[i % WINDOW_SIZE] = head[h];
+            head[h] = i;
+        }
+    };
+
+    let mut i = 0;
+    while i < data.len() {
+        let (mut best_len, mut best_dist) = (0, 0);
+        if i + MIN_MATCH <= data.len() {
+            let max_len = MAX_MATCH.min(data.len() - i);
+            let mut candidate = head[hash(data, i)];
+            let mut depth = 0;
+            while candidate != usize::MAX && i - candidate <= WINDOW_SIZE && depth < MAX_CHAIN {
+                let len = data[candidate..]
+                    .iter()
+                    .zip(&data[i..i + max_len])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if len > best_len {
+                    best_len = len;
+                    best_dist = i - candidate;
+                    if len == max_len {
+                        break;
+                    }
+                }
+                let older = prev[candidate % WINDOW_SIZE];
+                // An older slot may have been reused by a newer position
+                if older >= candidate {
+                    break;
+                }
+                candidate = older;
+                depth += 1;
+            }
+        }
+
+        if best_len >= MIN_MATCH {
+            bits.write_match(best_len, best_dist);
+            for j in i..i + best_len {
+                insert(j, &mut head, &mut prev);
+            }
+            i += best_len;
+        } else {
+            bits.write_symbol(u16::from(data[i]));
+            insert(i, &mut head, &mut prev);
+            i += 1;
+        }
+    }
+    bits.write_symbol(256); // end of block
+    bits.finish()
+}
+
+/// Hash of the three bytes starting at `i`.
+fn hash(data: &[u8], i: usize) -> usize {
+    let key = u32::from(data[i]) | u32::from(data[i + 1]) << 8 | u32::from(data[i + 2]) << 16;
+    (key.wrapping_mul(0x9E37_79B1) >> (32 - HASH_BITS)) as usize
+}
+
+/// LSB-first bit packer for deflate output.
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: u32) {
+        self.acc |= u64::from(value) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Huffman codes are packed starting from their most significant bit.
+    fn write_code(&mut self, code: u32, len: u32) {
+        self.write(code.reverse_bits() >> (32 - len), len);
+    }
+
+    /// Write a literal/length symbol with the fixed code table.
+    fn write_symbol(&mut self, symbol: u16) {
+        let symbol = u32::from(symbol);
+        match symbol {
+            0..=143 => self.write_code(0x30 + symbol, 8),
+            144..=255 => self.write_code(0x190 + symbol - 144, 9),
+            256..=279 => self.write_code(symbol - 256, 7),
+            _ => self.write_code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn write_match(&mut self, len: usize, dist: usize) {
+        let code = LENGTH_BASE
+            .iter()
+            .rposition(|&base| base as usize <= len)
+            .unwrap();
+        self.write_symbol(257 + code as u16);
+        self.write(
+            (len - LENGTH_BASE[code] as usize) as u32,
+            u32::from(LENGTH_EXTRA[code]),
+        );
+
+        let code = DISTANCE_BASE
+            .iter()
+            .rposition(|&base| base as usize <= dist)
+            .unwrap();
+        self.write_code(code as u32, 5);
+        self.write(
+            (dist - DISTANCE_BASE[code] as usize) as u32,
+            u32::from(DISTANCE_EXTRA[code]),
+        );
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base64_decode(text: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut acc = 0u32;
+        let mut bits = 0;
+        for c in text.bytes().filter(|&c| c != b'=') {
+            let value = BASE64_ALPHABET.iter().position(|&a| a == c).unwrap() as u32;
+            acc = (acc << 6) | value;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                out.push((acc >> bits) as u8);
+            }
+        }
+        out
+    }
+
+    struct BitReader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bit(&mut self) -> u32 {
+            let bit = (self.data[self.pos / 8] >> (self.pos % 8)) & 1;
+            self.pos += 1;
+            u32::from(bit)
+        }
+
+        fn bits(&mut self, count: u8) -> usize {
+            (0..count).fold(0, |acc, i| acc | (self.bit() as usize) << i)
+        }
+
+        fn code(&mut self, len: u32) -> u32 {
+            (0..len).fold(0, |acc, _| acc << 1 | self.bit())
+        }
+
+        fn symbol(&mut self) -> u32 {
+            let mut code = self.code(7);
+            if code <= 0x17 {
+                return 256 + code;
+            }
+            code = code << 1 | self.bit();
+            match code {
+                0x30..=0xBF => code - 0x30,
+                0xC0..=0xC7 => 280 + code - 0xC0,
+                _ => 144 + (code << 1 | self.bit()) - 0x190,
+            }
+        }
+    }
+
+    /// Minimal inflater for the single fixed-Huffman block we emit.
+    fn gunzip(data: &[u8]) -> Vec<u8> {
+        assert_eq!(&data[..3], &[0x1f, 0x8b, 8]);
+        let mut reader = BitReader {
+            data: &data[10..],
+            pos: 0,
+        };
+        assert_eq!(reader.bits(1), 1, "final block");
+        assert_eq!(reader.bits(2), 1, "fixed Huffman block");
+        let mut out = Vec::new();
+        loop {
+            let symbol = reader.symbol();
+            match symbol {
+                0..=255 => out.push(symbol as u8),
+                256 => break,
+                _ => {
+                    let code = (symbol - 257) as usize;
+                    let len = LENGTH_BASE[code] as usize + reader.bits(LENGTH_EXTRA[code]);
+                    let code = reader.code(5) as usize;
+                    let dist = DISTANCE_BASE[code] as usize + reader.bits(DISTANCE_EXTRA[code]);
+                    for _ in 0..len {
+                        out.push(out[out.len() - dist]);
+                    }
+                }
+            }
+        }
+        let trailer = &data[data.len() - 8..];
+        assert_eq!(trailer[..4], crc32(&out).to_le_bytes());
+        assert_eq!(trailer[4..], (out.len() as u32).to_le_bytes());
+        out
+    }
+
+    /// Run `input` through an installed tool, e.g. `gzip -dc`.
+    ///
+    /// Checks our encoders against real implementations rather than only the
+    /// decoders above. `None` if the tool is not installed.
+    fn pipe_through(program: &str, args: &[&str], input: &[u8]) -> Option<Vec<u8>> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .ok()?;
+        let mut stdin = child.stdin.take().unwrap();
+        let input = input.to_vec();
+        // Written from a thread so a full stdout pipe cannot deadlock
+        let writer = std::thread::spawn(move || stdin.write_all(&input));
+        let output = child.wait_with_output().unwrap();
+        writer.join().unwrap().unwrap();
+        assert!(
+            output.status.success(),
+            "{} rejected the input: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Some(output.stdout)
+    }
+
+    fn embedded_data(html: &str) -> &str {
+        let open = format!("<script type=\"{}\">", CAST_DATA_TYPE);
+        let start = html.find(&open).unwrap() + open.len();
+        let end = start + html[start..].find("</script>").unwrap();
+        &html[start..end]
+    }
+
+    const CAST: &str = "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n\
+        [0.5,\"o\",\"$ cargo test\\r\\n\"]\n\
+        [1.0,\"o\",\"\\u001b[32mok\\u001b[0m\\r\\n\"]\n\
+        [0.1,\"m\",\"[SUCCESS] Tests pass\"]\n";
+
+    #[test]
+    fn base64_matches_known_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_decode("Zm9vYmE="), b"fooba");
+    }
+
+    #[test]
+    fn crc32_matches_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn gzip_round_trips() {
+        let repetitive = "[0.1,\"o\",\"progress 42%\\r\"]\n".repeat(2000);
+        let binary: Vec<u8> = (0..70_000u32).map(|i| (i * 7919 % 251) as u8).collect();
+        for data in [
+            b"".as_slice(),
+            b"a",
+            b"aaaaaaaaaaaaaaaaaaaa",
+            CAST.as_bytes(),
+        ] {
+            assert_eq!(gunzip(&gzip(data)), data);
+        }
+        assert_eq!(gunzip(&gzip(&binary)), binary);
+
+        let compressed = gzip(repetitive.as_bytes());
+        assert_eq!(gunzip(&compressed), repetitive.as_bytes());
+        assert!(compressed.len() * 10 < repetitive.len());
+    }
+
+    #[test]
+    fn gzip_output_is_accepted_by_system_gzip() {
+        let repetitive = "[0.1,\"o\",\"progress 42%\r\"]\n".repeat(2000);
+        let binary: Vec<u8> = (0..70_000u32).map(|i| (i * 7919 % 251) as u8).collect();
+        for data in [
+            b"".as_slice(),
+            b"a",
+            b"aaaaaaaaaaaaaaaaaaaa",
+            CAST.as_bytes(),
+            repetitive.as_bytes(),
+            &binary,
+        ] {
+            let Some(decoded) = pipe_through("gzip", &["-dc"], &gzip(data)) else {
+                eprintln!("gzip is not installed; skipping");
+                return;
+            };
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn html_embeds_cast_that_round_trips() {
+        let html = cast_html(CAST, "session");
+        let data = embedded_data(&html);
+        assert!(data
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || b"+/=".contains(&c)));
+        assert_eq!(gunzip(&base64_decode(data)), CAST.as_bytes());
+    }
+
+    #[test]
+    fn html_embedded_cast_decodes_with_system_tools() {
+        let html = cast_html(CAST, "session");
+        let data = embedded_data(&html);
+        let Some(compressed) = pipe_through("base64", &["-d"], data.as_bytes()) else {
+            eprintln!("base64 is not installed; skipping");
+            return;
+        };
+        assert_eq!(compressed, base64_decode(data));
+        let Some(decoded) = pipe_through("gzip", &["-dc"], &compressed) else {
+            eprintln!("gzip is not installed; skipping");
+            return;
+        };
+        assert_eq!(decoded, CAST.as_bytes());
+    }
+
+    #[test]
+    fn html_fragment_is_well_formed() {
+        let html = cast_html(CAST, "fix <login> & \"auth\"");
+        assert!(html.starts_with("<div class=\"agr-player\""));
+        assert!(html.trim_end().ends_with("</div>"));
+        assert_eq!(html.matches("<script").count(), 2);
+        assert_eq!(html.matches("</script>").count(), 2);
+        assert_eq!(html.matches("<pre").count(), 1);
+        assert!(html.contains("<strong>fix &lt;login&gt; &amp; &quot;auth&quot;</strong>"));
+        // The player must not end its own script element early
+        assert!(!PLAYER_SCRIPT.contains("</"));
+    }
+}
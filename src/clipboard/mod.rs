@@ -17,6 +17,7 @@
 
 pub mod copy;
 mod error;
+pub mod html;
 mod result;
 pub mod tool;
 pub mod tools;
@@ -26,6 +27,34 @@ pub use result::{CopyMethod, CopyResult};
 
 use copy::Copy;
 use std::path::Path;
+use tool::CopyTool;
+use tools::platform_tools;
+
+/// What `agr copy` puts on the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum CopyFormat {
+    /// The recording itself (as a file reference where supported)
+    #[default]
+    File,
+    /// A self-contained HTML snippet that plays the recording
+    Html,
+    /// A plain-text transcript of what the recording printed
+    Text,
+}
+
+/// Name of the clipboard tool `agr copy` would use (e.g. `pbcopy`,
+/// `xclip`, `wl-copy`), or `None` if no supported tool is installed.
+pub fn available_tool() -> Option<&'static str> {
+    select_tool(&platform_tools())
+}
+
+/// The first available tool of `tools`, which are in priority order.
+pub fn select_tool(tools: &[Box<dyn CopyTool>]) -> Option<&'static str> {
+    tools
+        .iter()
+        .find(|tool| tool.is_available())
+        .map(|tool| tool.name())
+}
 
 /// Copy a file to the system clipboard.
 ///
@@ -39,3 +68,22 @@ use std::path::Path;
 pub fn copy_file_to_clipboard(path: &Path) -> Result<CopyResult, ClipboardError> {
     Copy::new().file(path)
 }
+
+/// Copy a recording to the clipboard as an HTML player snippet.
+///
+/// `title` is shown above the player.
+///
+/// # Errors
+/// Same as [`copy_file_to_clipboard`]; the size limit applies to the cast file.
+pub fn copy_html_to_clipboard(path: &Path, title: &str) -> Result<CopyResult, ClipboardError> {
+    Copy::new().html(path, title)
+}
+
+/// Copy text (e.g. a recording transcript) to the clipboard.
+///
+/// # Errors
+/// - `ClipboardError::NoToolAvailable` - no clipboard tool found
+/// - `ClipboardError::FileTooLarge` - text exceeds the content size limit
+pub fn copy_text_to_clipboard(text: &str) -> Result<CopyResult, ClipboardError> {
+    Copy::new().text(text)
+}
@@ -2,24 +2,30 @@
 //!
 //! Uses the AnalyzerService facade to orchestrate analysis:
 //! 1. Parse cast file
-//! 2. Check for existing markers (offer to remove)
+//! 2. Check for existing markers (a previous analysis is replaced)
 //! 3. Extract content (strip ANSI, dedupe progress)
 //! 4. Chunk content based on agent token limits
 //! 5. Execute parallel analysis
 //! 6. Aggregate and deduplicate markers
 //! 7. Write markers to file
-//! 8. Optionally curate markers (reduce to 8-12 most significant)
-//! 9. Suggest better filename via LLM based on analysis
+//! 8. Optionally review markers before they are written (`--review`)
+//! 9. Optionally curate markers (reduce to 8-12 most significant)
+//! 10. Suggest better filename via LLM based on analysis
 
 use std::io::{self, BufRead, Write};
+use std::path::Path;
 use std::time::Duration;
 
 use anyhow::Result;
 
-use agr::analyzer::{AgentType, AnalyzeOptions, AnalyzerService};
-use agr::{Config, MarkerManager};
+use agr::analyzer::{
+    AgentType, AnalyzeOptions, AnalyzerService, CancelToken, ExtractionConfig, ExtractionLevel,
+    MarkerReview, MarkerWriter, PromptTemplate, ReviewDecision, TimeRange, ValidatedMarker,
+};
+use agr::Config;
 
 use agr::asciicast::integrity::check_file_integrity;
+use agr::asciicast::{remote, AsciicastFile};
 use agr::files::resolve::resolve_file_path;
 
 /// Threshold for offering marker curation.
@@ -28,7 +34,9 @@ const CURATION_THRESHOLD: usize = 12;
 /// Analyze a recording file using an AI agent.
 ///
 /// Reads the cast file, extracts meaningful content, and uses AI to identify
-/// key engineering moments. Markers are added directly to the file.
+/// key engineering moments. Markers are added directly to the file. An
+/// `http(s)://` URL is first downloaded to a new private temporary directory,
+/// the markers are written to that copy, and its path is printed at the end.
 #[cfg(not(tarpaulin_include))]
 #[allow(clippy::too_many_arguments)]
 pub fn handle(
@@ -41,19 +49,26 @@ pub fn handle(
     debug: bool,
     output: Option<String>,
     fast: bool,
+    from: Option<f64>,
+    to: Option<f64>,
+    level: Option<ExtractionLevel>,
+    review: bool,
+    write_partial: bool,
+    fail_on_error: bool,
     wait: bool,
 ) -> Result<()> {
+    let time_range = parse_time_range(from, to)?;
     let config = Config::load()?;
 
-    // Resolve agent: CLI override > config > default
-    let resolved_agent = match agent_override {
-        Some(name) => name.to_string(),
-        None => config.resolve_analysis_agent(),
-    };
-    let agent = parse_agent_type(&resolved_agent)?;
-
     // Resolve file path (supports short format like "claude/session.cast")
-    let filepath = resolve_file_path(file, &config)?;
+    let downloaded = remote::is_url(file);
+    let filepath = if downloaded {
+        let path = remote::download_to_temp_dir(&remote::CurlFetcher, file)?;
+        println!("Downloaded {} to {}", file, path.display());
+        path
+    } else {
+        resolve_file_path(file, &config)?
+    };
     if !filepath.exists() {
         anyhow::bail!(
             "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
@@ -61,6 +76,23 @@ pub fn handle(
         );
     }
 
+    // Resolve agent: CLI override > config > inferred from recording > default
+    let resolved_agent = match (agent_override, &config.analysis.agent) {
+        (Some(name), _) => name.to_string(),
+        (None, Some(_)) => config.resolve_analysis_agent(),
+        (None, None) => {
+            let header = AsciicastFile::read_header(&filepath).ok();
+            match AnalyzerService::infer_agent(&filepath, header.as_ref()) {
+                Some(inferred) => {
+                    println!("Inferred agent from recording: {}", inferred.command_name());
+                    inferred.command_name().to_string()
+                }
+                None => config.resolve_analysis_agent(),
+            }
+        }
+    };
+    let agent = parse_agent_type(&resolved_agent)?;
+
     // Check file has .cast extension
     if filepath.extension().and_then(|e| e.to_str()) != Some("cast") {
         eprintln!("Warning: File does not have .cast extension");
@@ -72,11 +104,8 @@ pub fn handle(
     // Refuse to analyze a file being actively recorded
     agr::files::lock::check_not_locked(&filepath)?;
 
-    // Look up per-agent config
-    let agent_config = config.analysis_agent_config(&resolved_agent);
-
     // Build options with three-tier cascade: CLI > config > defaults
-    let mut options = AnalyzeOptions::with_agent(agent);
+    let mut options = agent_options(&config, &resolved_agent, agent, timeout, level);
 
     // Workers: CLI > config > auto-scale (None)
     if let Some(w) = workers {
@@ -85,13 +114,6 @@ pub fn handle(
         options = options.workers(w);
     }
 
-    // Timeout: CLI > config > default
-    if let Some(t) = timeout {
-        options = options.timeout(t);
-    } else if let Some(t) = config.analysis.timeout {
-        options = options.timeout(t);
-    }
-
     if no_parallel {
         options = options.sequential();
     }
@@ -102,95 +124,93 @@ pub fn handle(
         options = options.output(out);
     }
 
+    if let Some(range) = time_range {
+        options = options.time_range(range);
+    }
+    if review {
+        options = options.defer_write();
+    }
+    if let Some(path) = config.analysis.prompt_template_path() {
+        options = options.prompt_template(PromptTemplate::load(&path)?);
+    }
+
     // Fast: CLI true wins, else config, else false
     if fast || config.analysis.fast.unwrap_or(false) {
         options = options.fast(true);
     }
 
-    // Pass per-task extra_args and token_budget_override from per-agent config
-    if let Some(ac) = agent_config {
-        let analyze_args = ac.effective_analyze_args();
-        if !analyze_args.is_empty() {
-            options = options.extra_args(analyze_args.to_vec());
-        }
-        let curate_args = ac.effective_curate_args();
-        if !curate_args.is_empty() {
-            options = options.curate_extra_args(curate_args.to_vec());
-        }
-        let rename_args = ac.effective_rename_args();
-        if !rename_args.is_empty() {
-            options = options.rename_extra_args(rename_args.to_vec());
-        }
-        if let Some(budget) = ac.token_budget {
-            options = options.token_budget_override(budget);
-        }
-    }
+    // Ctrl-C stops dispatching chunks and kills running agent CLIs
+    let cancel = CancelToken::process();
+    cancel.register_signal_handlers();
+    options = options
+        .cancel(cancel.clone())
+        .write_partial(write_partial)
+        .fail_on_error(fail_on_error);
 
     // Create service
-    let service = AnalyzerService::new(options);
-    let agent_name = &resolved_agent;
+    let mut service = AnalyzerService::new(options);
+    fall_back_to_installed_agent(&mut service, &resolved_agent)?;
 
-    // Check agent is available
-    if !service.is_agent_available() {
-        anyhow::bail!(
-            "Analysis agent '{}' is not installed. Install it or use --agent to specify another.\n\
-             Supported agents: claude, codex, gemini",
-            agent_name
-        );
-    }
+    // Run analysis
+    println!("Analyzing {} with {}...", file, service.agent());
+    let result = service.analyze(&filepath)?;
 
-    // Check for existing markers and offer to remove them
-    let existing_count = MarkerManager::count_markers(&filepath)?;
-    if existing_count > 0 {
-        print!(
-            "File contains {} existing marker(s). Remove them before analysis? [y/N]: ",
-            existing_count
+    // Only reached when cancelled with --write-partial
+    if cancel.is_cancelled() {
+        if review {
+            MarkerWriter::write_markers(&filepath, &result.markers)?;
+        }
+        println!(
+            "Analysis cancelled. Kept {} marker(s) from {} finished chunk(s).",
+            result.markers.len(),
+            result.usage_summary.successful_chunks
         );
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().lock().read_line(&mut input)?;
-
-        if input.trim().eq_ignore_ascii_case("y") || input.trim().eq_ignore_ascii_case("yes") {
-            let removed = MarkerManager::clear_markers(&filepath)?;
-            println!("Removed {} marker(s).", removed);
+        if downloaded {
+            print_kept_copy(file, &filepath);
         }
+        return Ok(());
     }
 
-    // Run analysis
-    println!("Analyzing {} with {}...", file, agent);
-    let result = service.analyze(&filepath)?;
-
     // Report results
     if result.is_partial() {
         eprintln!(
-            "Warning: Analysis partially complete. {} of {} chunks succeeded.",
-            result.usage_summary.successful_chunks, result.usage_summary.chunks_processed
+            "Warning: {} of {} chunks failed. Markers from the other chunks were kept (use --fail-on-error to write none).",
+            result.usage_summary.failed_chunks, result.usage_summary.chunks_processed
         );
     }
 
+    let markers = if review {
+        let approved = review_markers(result.markers.clone())?;
+        MarkerWriter::write_markers(&filepath, &approved)?;
+        println!(
+            "\nApproved {} of {} marker(s).",
+            approved.len(),
+            result.markers.len()
+        );
+        approved
+    } else {
+        result.markers.clone()
+    };
+
     // Print markers verbosely
-    println!("\nMarkers found ({}):", result.markers.len());
-    for marker in &result.markers {
+    println!("\nMarkers found ({}):", markers.len());
+    for marker in &markers {
         print_marker(marker.timestamp, &marker.label);
     }
 
     // Handle curation if we have many markers
     // Curate: CLI true wins, else config, else false
     let effective_curate = curate || config.analysis.curate.unwrap_or(false);
-    let final_marker_count = if result.markers.len() > CURATION_THRESHOLD {
+    let final_marker_count = if markers.len() > CURATION_THRESHOLD {
         let should_curate = if effective_curate {
             // Auto-curate with --curate flag
-            println!(
-                "\nAuto-curating {} markers to 8-12...",
-                result.markers.len()
-            );
+            println!("\nAuto-curating {} markers to 8-12...", markers.len());
             true
         } else {
             // Prompt user
             print!(
                 "\nFound {} markers. Curate to 8-12 most significant? [y/N]: ",
-                result.markers.len()
+                markers.len()
             );
             io::stdout().flush()?;
 
@@ -201,13 +221,10 @@ pub fn handle(
 
         if should_curate {
             let timeout_duration = Duration::from_secs(timeout.unwrap_or(120));
-            match service.curate_markers(&result.markers, result.total_duration, timeout_duration) {
+            match service.curate_markers(&markers, result.total_duration, timeout_duration) {
                 Ok(curated) => {
                     // Write curated markers to file (replacing the ones from analyze)
-                    MarkerManager::clear_markers(&filepath)?;
-                    for marker in &curated {
-                        MarkerManager::add_marker(&filepath, marker.timestamp, &marker.label)?;
-                    }
+                    MarkerWriter::write_markers(&filepath, &curated)?;
 
                     println!("\nCurated markers ({}):", curated.len());
                     for marker in &curated {
@@ -217,14 +234,14 @@ pub fn handle(
                 }
                 Err(e) => {
                     eprintln!("Warning: Curation failed ({}), keeping all markers.", e);
-                    result.markers.len()
+                    markers.len()
                 }
             }
         } else {
-            result.markers.len()
+            markers.len()
         }
     } else {
-        result.markers.len()
+        markers.len()
     };
 
     println!(
@@ -233,7 +250,8 @@ pub fn handle(
     );
 
     // Suggest a descriptive filename via LLM
-    if !result.markers.is_empty() {
+    let mut final_path = filepath.clone();
+    if !markers.is_empty() {
         let current_filename = filepath
             .file_stem()
             .and_then(|s| s.to_str())
@@ -242,7 +260,7 @@ pub fn handle(
         let timeout_duration = Duration::from_secs(timeout.unwrap_or(120));
 
         match service.suggest_rename(
-            &result.markers,
+            &markers,
             result.total_duration,
             timeout_duration,
             &current_filename,
@@ -262,6 +280,7 @@ pub fn handle(
                     {
                         std::fs::rename(&filepath, &new_path)?;
                         println!("Renamed to: {}", new_path.display());
+                        final_path = new_path;
                     }
                 }
             }
@@ -271,6 +290,10 @@ pub fn handle(
         }
     }
 
+    if downloaded {
+        print_kept_copy(file, &final_path);
+    }
+
     if wait {
         print!("\nPress Enter to continue...");
         io::stdout().flush()?;
@@ -281,6 +304,63 @@ pub fn handle(
     Ok(())
 }
 
+/// Tell the user where the annotated copy of a downloaded recording is.
+fn print_kept_copy(url: &str, path: &Path) {
+    println!(
+        "\nThe annotated copy of {} is kept at {}",
+        url,
+        path.display()
+    );
+}
+
+/// Ask for a decision on each proposed marker and return the approved ones.
+///
+/// Input ending early (e.g. Ctrl-D) skips the markers not yet reviewed.
+#[cfg(not(tarpaulin_include))]
+fn review_markers(markers: Vec<ValidatedMarker>) -> Result<Vec<ValidatedMarker>> {
+    let mut review = MarkerReview::new(markers);
+    while let Some(marker) = review.current() {
+        let (position, total) = review.position();
+        println!();
+        print!("[{}/{}]", position, total);
+        print_marker(marker.timestamp, &marker.label);
+        print!("Accept [a], edit [e], skip [s], accept all [all]: ");
+        io::stdout().flush()?;
+
+        let Some(input) = read_reply()? else {
+            review.skip_rest();
+            break;
+        };
+        let decision = if ReviewDecision::is_edit_request(&input) {
+            print!("New description: ");
+            io::stdout().flush()?;
+            match read_reply()? {
+                Some(description) => ReviewDecision::Edit(description),
+                None => {
+                    review.skip_rest();
+                    break;
+                }
+            }
+        } else if let Some(decision) = ReviewDecision::parse(&input) {
+            decision
+        } else {
+            println!("Please answer a, e, s or all.");
+            continue;
+        };
+        review.decide(decision);
+    }
+    Ok(review.into_approved())
+}
+
+/// Read one line from stdin, or `None` at end of input.
+fn read_reply() -> Result<Option<String>> {
+    let mut input = String::new();
+    if io::stdin().lock().read_line(&mut input)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(input.trim().to_string()))
+}
+
 /// Print a marker with formatted timestamp.
 fn print_marker(timestamp: f64, label: &str) {
     let minutes = (timestamp / 60.0).floor() as u32;
@@ -289,7 +369,55 @@ fn print_marker(timestamp: f64, label: &str) {
 }
 
 /// Parse agent name string to AgentType enum.
-fn parse_agent_type(name: &str) -> Result<AgentType> {
+/// Analyzer options shared by `agr analyze` and `agr summarize`.
+///
+/// Applies the timeout (CLI > per-agent config > config > default), idle
+/// gap, extraction level and the per-agent extra args, model and token
+/// budget.
+pub(crate) fn agent_options(
+    config: &Config,
+    resolved_agent: &str,
+    agent: AgentType,
+    timeout: Option<u64>,
+    level: Option<ExtractionLevel>,
+) -> AnalyzeOptions {
+    let mut options = AnalyzeOptions::with_agent(agent);
+    if let Some(t) = timeout.or(config.analysis_timeout(resolved_agent)) {
+        options = options.timeout(t);
+    }
+    if let Some(secs) = config.analysis.idle_gap_secs {
+        options = options.idle_gap(secs);
+    }
+    options = options.extraction(ExtractionConfig::resolve(
+        level,
+        &config.analysis.extraction,
+    ));
+
+    // Pass per-task extra_args and token_budget_override from per-agent config
+    if let Some(ac) = config.analysis_agent_config(resolved_agent) {
+        let analyze_args = ac.effective_analyze_args();
+        if !analyze_args.is_empty() {
+            options = options.extra_args(analyze_args.to_vec());
+        }
+        let curate_args = ac.effective_curate_args();
+        if !curate_args.is_empty() {
+            options = options.curate_extra_args(curate_args.to_vec());
+        }
+        let rename_args = ac.effective_rename_args();
+        if !rename_args.is_empty() {
+            options = options.rename_extra_args(rename_args.to_vec());
+        }
+        if let Some(model) = &ac.model {
+            options = options.model(model.clone());
+        }
+        if let Some(budget) = ac.token_budget {
+            options = options.token_budget_override(budget);
+        }
+    }
+    options
+}
+
+pub(crate) fn parse_agent_type(name: &str) -> Result<AgentType> {
     match name.to_lowercase().as_str() {
         "claude" => Ok(AgentType::Claude),
         "codex" => Ok(AgentType::Codex),
@@ -301,6 +429,47 @@ fn parse_agent_type(name: &str) -> Result<AgentType> {
     }
 }
 
+/// Switch to another installed agent if `requested` is missing, saying so.
+pub(crate) fn fall_back_to_installed_agent(
+    service: &mut AnalyzerService,
+    requested: &str,
+) -> Result<()> {
+    match service.fall_back_if_unavailable() {
+        Ok(None) => Ok(()),
+        Ok(Some(agent)) => {
+            eprintln!(
+                "Analysis agent '{}' is not installed, falling back to {}.",
+                requested,
+                agent.command_name()
+            );
+            Ok(())
+        }
+        Err(_) => anyhow::bail!(
+            "No analysis agent is installed ('{}' was requested).\n\
+             Supported agents: claude, codex, gemini",
+            requested
+        ),
+    }
+}
+
+/// Build the analysis time window from `--from` / `--to`.
+///
+/// Returns `None` when neither flag is given (analyze the whole recording).
+fn parse_time_range(from: Option<f64>, to: Option<f64>) -> Result<Option<TimeRange>> {
+    if from.is_none() && to.is_none() {
+        return Ok(None);
+    }
+    let start = from.unwrap_or(0.0);
+    let end = to.unwrap_or(f64::INFINITY);
+    if !start.is_finite() || start < 0.0 || end.is_nan() || end < 0.0 {
+        anyhow::bail!("--from and --to must be non-negative numbers of seconds");
+    }
+    if start >= end {
+        anyhow::bail!("--from ({}) must be earlier than --to ({})", start, end);
+    }
+    Ok(Some(TimeRange::new(start, end)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,4 +494,26 @@ mod tests {
     fn parse_agent_type_unknown() {
         assert!(parse_agent_type("unknown").is_err());
     }
+
+    #[test]
+    fn parse_time_range_none_without_flags() {
+        assert!(parse_time_range(None, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_time_range_open_ended() {
+        let range = parse_time_range(Some(600.0), None).unwrap().unwrap();
+        assert_eq!(range.start, 600.0);
+        assert!(range.end.is_infinite());
+
+        let range = parse_time_range(None, Some(900.0)).unwrap().unwrap();
+        assert_eq!(range, TimeRange::new(0.0, 900.0));
+    }
+
+    #[test]
+    fn parse_time_range_rejects_invalid_windows() {
+        assert!(parse_time_range(Some(900.0), Some(600.0)).is_err());
+        assert!(parse_time_range(Some(600.0), Some(600.0)).is_err());
+        assert!(parse_time_range(Some(-1.0), None).is_err());
+    }
 }
@@ -0,0 +1,160 @@
+//! Backfill-duration command handler
+//!
+//! Stores the computed recording duration in the header of files that do
+//! not have one yet, so listing and stats no longer re-scan their events.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use agr::asciicast::integrity::check_file_integrity;
+use agr::files::lock;
+use agr::files::resolve::resolve_file_path;
+use agr::recording::stamp_duration_in_file;
+use agr::theme::current_theme;
+use agr::{AsciicastFile, Config, StorageManager};
+
+/// Store missing header durations for one file or, with `all`, every recording.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(file: Option<&str>, all: bool) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+
+    if !all {
+        let file = file.unwrap_or_default();
+        let filepath = resolve_file_path(file, &config)?;
+        if !filepath.exists() {
+            anyhow::bail!(
+                "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+                file
+            );
+        }
+
+        check_file_integrity(&filepath)?;
+        lock::check_not_locked(&filepath)?;
+
+        let message = match backfill_file(&filepath)? {
+            Backfill::Stamped(duration) => format!("Stored duration: {:.1}s", duration),
+            Backfill::AlreadySet(duration) => {
+                format!("Duration already stored: {:.1}s", duration)
+            }
+        };
+        println!("{}", theme.primary_text(&message));
+        return Ok(());
+    }
+
+    let storage = StorageManager::new(config);
+    let sessions = storage.list_sessions(None)?;
+    let (mut stamped, mut present, mut failed) = (0, 0, 0);
+
+    for session in &sessions {
+        let name = format!("{}/{}", session.agent, session.filename);
+        if lock::read_lock(&session.path).is_some() {
+            println!(
+                "{}",
+                theme.secondary_text(&format!("  skipped {} (recording in progress)", name))
+            );
+            continue;
+        }
+        match backfill_file(&session.path) {
+            Ok(Backfill::Stamped(duration)) => {
+                stamped += 1;
+                println!(
+                    "{}",
+                    theme.primary_text(&format!("  {} ({:.1}s)", name, duration))
+                );
+            }
+            Ok(Backfill::AlreadySet(_)) => present += 1,
+            Err(e) => {
+                failed += 1;
+                eprintln!("{}", theme.error_text(&format!("  {}: {:#}", name, e)));
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        theme.success_text(&format!(
+            "Backfilled {} recording(s); {} already had a duration, {} failed.",
+            stamped, present, failed
+        ))
+    );
+    if failed > 0 {
+        anyhow::bail!("{} recording(s) could not be backfilled", failed);
+    }
+    Ok(())
+}
+
+/// Outcome of backfilling one file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Backfill {
+    /// The header had no duration; the computed one was written
+    Stamped(f64),
+    /// The header already had this duration; the file was not touched
+    AlreadySet(f64),
+}
+
+/// Store the duration in a file's header unless it already has one.
+pub(crate) fn backfill_file(path: &Path) -> Result<Backfill> {
+    if let Some(duration) = AsciicastFile::read_header(path)?.duration {
+        return Ok(Backfill::AlreadySet(duration));
+    }
+    stamp_duration_in_file(path)?;
+    let duration = AsciicastFile::read_header(path)?.duration.unwrap_or(0.0);
+    Ok(Backfill::Stamped(duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAST: &str = concat!(
+        "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24},\"title\":\"demo\"}\n",
+        "[0.5,\"o\",\"hello\"]\n",
+        "[1.25,\"m\",\"step\"]\n",
+        "[0.25,\"o\",\"world\"]\n",
+    );
+
+    #[test]
+    fn backfill_stamps_missing_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        std::fs::write(&path, CAST).unwrap();
+
+        assert_eq!(backfill_file(&path).unwrap(), Backfill::Stamped(2.0));
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content.lines().next().unwrap(),
+            "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24},\"duration\":2.0,\"title\":\"demo\"}"
+        );
+        assert_eq!(
+            content.lines().skip(1).collect::<Vec<_>>(),
+            CAST.lines().skip(1).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn backfill_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        std::fs::write(&path, CAST).unwrap();
+
+        backfill_file(&path).unwrap();
+        let first = std::fs::read_to_string(&path).unwrap();
+
+        assert_eq!(backfill_file(&path).unwrap(), Backfill::AlreadySet(2.0));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), first);
+    }
+
+    #[test]
+    fn backfill_keeps_existing_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        let content = CAST.replacen("\"version\":3,", "\"version\":3,\"duration\":9.5,", 1);
+        std::fs::write(&path, &content).unwrap();
+
+        assert_eq!(backfill_file(&path).unwrap(), Backfill::AlreadySet(9.5));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+    }
+}
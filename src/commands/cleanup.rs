@@ -5,7 +5,7 @@ use std::io::IsTerminal;
 use anyhow::Result;
 use std::io::{self, BufRead, Write};
 
-use agr::storage::{SessionInfo, StorageStats};
+use agr::storage::{select_to_free, DateRange, SessionInfo, StorageStats};
 use agr::theme::current_theme;
 use agr::tui::app::TuiApp;
 use agr::tui::widgets::FileItem;
@@ -18,24 +18,56 @@ use super::truncate_string;
 ///
 /// When stdout is a TTY, shows an interactive file explorer with multi-select.
 /// When piped, shows a text-based prompt interface (fallback).
-/// Supports filtering by agent and age threshold.
+/// Supports filtering by agent, age threshold and recording date. With
+/// `free`, the oldest sessions totalling at least that many bytes are
+/// selected for deletion. Deleted sessions go to the trash unless
+/// `permanent` is set or `[storage] use_trash` is off.
 #[cfg(not(tarpaulin_include))]
-pub fn handle(agent_filter: Option<&str>, older_than: Option<u32>) -> Result<()> {
-    let config = Config::load()?;
+pub fn handle(
+    agent_filter: Option<&str>,
+    older_than: Option<u32>,
+    free: Option<u64>,
+    range: DateRange,
+    permanent: bool,
+) -> Result<()> {
+    let mut config = Config::load()?;
+    if permanent {
+        config.storage.use_trash = false;
+    }
     let age_threshold = config.storage.age_threshold_days;
     let storage = StorageManager::new(config.clone());
 
-    // Get sessions, optionally filtered by agent
-    let mut sessions = storage.list_sessions(agent_filter)?;
+    // Get sessions, optionally filtered by agent; the date range needs the
+    // header start times, which the index provides
+    let mut sessions = if range.is_unbounded() {
+        storage.list_sessions(agent_filter)?
+    } else {
+        storage
+            .list_sessions_indexed(agent_filter, false)?
+            .into_iter()
+            .filter(|(session, entry)| range.matches(session, entry.timestamp))
+            .map(|(session, _)| session)
+            .collect()
+    };
 
     // Apply older_than filter if specified
     if let Some(days) = older_than {
         sessions.retain(|s| s.age_days > days as i64);
     }
 
+    // Pinned sessions are never offered for deletion
+    let (pinned, sessions): (Vec<SessionInfo>, Vec<SessionInfo>) =
+        sessions.into_iter().partition(|s| s.pinned);
+    print_pinned(&pinned);
+
     if sessions.is_empty() {
         let theme = current_theme();
-        if agent_filter.is_some() || older_than.is_some() {
+        if !pinned.is_empty() {
+            println!(
+                "{}",
+                theme.primary_text("All matching sessions are pinned. Nothing to clean up.")
+            );
+        } else if agent_filter.is_some() || older_than.is_some() || !range.is_unbounded() {
             println!(
                 "{}",
                 theme.primary_text("No sessions match the specified filters.")
@@ -46,11 +78,26 @@ pub fn handle(agent_filter: Option<&str>, older_than: Option<u32>) -> Result<()>
         return Ok(());
     }
 
+    if let Some(target) = free {
+        let stats = storage.get_stats()?;
+        print_header(&stats, agent_filter, older_than, &range)?;
+        let to_delete = select_to_free(&sessions, target);
+        print_free_selection(&to_delete, target);
+        return confirm_and_delete(&to_delete, &storage);
+    }
+
     // Check if we're in a TTY - if so, use interactive TUI
     if std::io::stdout().is_terminal() {
         handle_tui(sessions, agent_filter, config)
     } else {
-        handle_text(sessions, agent_filter, older_than, age_threshold, storage)
+        handle_text(
+            sessions,
+            agent_filter,
+            older_than,
+            &range,
+            age_threshold,
+            storage,
+        )
     }
 }
 
@@ -78,6 +125,7 @@ fn handle_text(
     sessions: Vec<SessionInfo>,
     agent_filter: Option<&str>,
     older_than: Option<u32>,
+    range: &DateRange,
     age_threshold: u32,
     storage: StorageManager,
 ) -> Result<()> {
@@ -90,7 +138,7 @@ fn handle_text(
         .count();
 
     // Print header with breakdown by agent
-    print_header(&stats, agent_filter, older_than)?;
+    print_header(&stats, agent_filter, older_than, range)?;
 
     // Build session summary message
     print_session_summary(sessions.len(), old_count, age_threshold);
@@ -107,6 +155,7 @@ pub(crate) fn print_header(
     stats: &StorageStats,
     agent_filter: Option<&str>,
     older_than: Option<u32>,
+    range: &DateRange,
 ) -> Result<()> {
     let theme = current_theme();
     println!("{}", theme.primary_text("=== Agent Session Cleanup ==="));
@@ -150,13 +199,41 @@ pub(crate) fn print_header(
             theme.primary_text(&format!("Filtered by age: > {} days", days))
         );
     }
-    if agent_filter.is_some() || older_than.is_some() {
+    if !range.is_unbounded() {
+        println!(
+            "{}",
+            theme.primary_text(&format!("Filtered by date: {}", range.describe()))
+        );
+    }
+    if agent_filter.is_some() || older_than.is_some() || !range.is_unbounded() {
         println!();
     }
 
     Ok(())
 }
 
+/// List pinned sessions, which cleanup always keeps.
+pub(crate) fn print_pinned(pinned: &[SessionInfo]) {
+    if pinned.is_empty() {
+        return;
+    }
+    let theme = current_theme();
+    println!(
+        "{}",
+        theme.secondary_text(&format!(
+            "Keeping {} pinned session(s) (run 'agr unpin' to allow deletion):",
+            pinned.len()
+        ))
+    );
+    for session in pinned {
+        println!(
+            "{}",
+            theme.secondary_text(&format!("  - {}/{}", session.agent, session.filename))
+        );
+    }
+    println!();
+}
+
 /// Print the session summary message.
 pub(crate) fn print_session_summary(total: usize, old_count: usize, age_threshold: u32) {
     let theme = current_theme();
@@ -260,26 +337,28 @@ pub(crate) fn parse_deletion_input(
     age_threshold: u32,
 ) -> Result<Vec<SessionInfo>> {
     let theme = current_theme();
+    // Pinned sessions are excluded from every selection
+    let deletable = sessions.iter().filter(|s| !s.pinned);
     if input == "0" || input.is_empty() {
         println!("{}", theme.primary_text("No sessions deleted."));
         return Ok(vec![]);
     } else if input == "all" {
-        return Ok(sessions.to_vec());
+        return Ok(deletable.cloned().collect());
     } else if input == "old" && old_count > 0 {
-        return Ok(sessions
-            .iter()
+        return Ok(deletable
             .filter(|s| s.age_days > age_threshold as i64)
             .cloned()
             .collect());
     } else if let Ok(count) = input.parse::<usize>() {
-        if count > sessions.len() {
+        let available = deletable.clone().count();
+        if count > available {
             println!(
                 "{}",
-                theme.primary_text(&format!("Invalid number. Maximum is {}.", sessions.len()))
+                theme.primary_text(&format!("Invalid number. Maximum is {}.", available))
             );
             return Ok(vec![]);
         }
-        return Ok(sessions.iter().take(count).cloned().collect());
+        return Ok(deletable.take(count).cloned().collect());
     }
 
     println!(
@@ -289,8 +368,37 @@ pub(crate) fn parse_deletion_input(
     Ok(vec![])
 }
 
+/// Explain how the sessions for a `--free` target were chosen.
+pub(crate) fn print_free_selection(to_delete: &[SessionInfo], target: u64) {
+    let theme = current_theme();
+    let total: u64 = to_delete.iter().map(|s| s.size).sum();
+    let target_human = humansize::format_size(target, humansize::BINARY);
+    if total < target {
+        println!(
+            "{}",
+            theme.error_text(&format!(
+                "All matching sessions total {}, less than the {} requested.",
+                humansize::format_size(total, humansize::BINARY),
+                target_human
+            ))
+        );
+    } else {
+        println!(
+            "{}",
+            theme.primary_text(&format!(
+                "Selected the {} oldest sessions to free at least {}.",
+                to_delete.len(),
+                target_human
+            ))
+        );
+    }
+}
+
 /// Confirm deletion with user and execute.
-fn confirm_and_delete(to_delete: &[SessionInfo], storage: &StorageManager) -> Result<()> {
+pub(crate) fn confirm_and_delete(
+    to_delete: &[SessionInfo],
+    storage: &StorageManager,
+) -> Result<()> {
     let theme = current_theme();
     // Calculate total size to be freed
     let total_size: u64 = to_delete.iter().map(|s| s.size).sum();
@@ -335,6 +443,15 @@ fn confirm_and_delete(to_delete: &[SessionInfo], storage: &StorageManager) -> Re
                 new_stats.size_human()
             ))
         );
+        if storage.trash().is_some() {
+            println!(
+                "{}",
+                theme.primary_text(
+                    "Moved to the trash: 'agr trash restore <name>' brings a session back, \
+                     'agr trash empty' frees the disk space."
+                )
+            );
+        }
     } else {
         println!("{}", theme.primary_text("Cancelled."));
     }
@@ -360,6 +477,8 @@ mod tests {
             age_days,
             age_hours: age_days * 24,
             age_minutes: age_days * 24 * 60,
+            pinned: false,
+            has_notes: false,
         }
     }
 
@@ -369,6 +488,7 @@ mod tests {
             total_size: 1024 * session_count as u64,
             session_count,
             sessions_by_agent: by_agent,
+            sizes_by_agent: HashMap::new(),
             oldest_session: None,
             disk_percentage: 0.5,
         }
@@ -456,33 +576,98 @@ mod tests {
         assert_eq!(result[0].filename, "first.cast");
     }
 
+    fn pinned_session(agent: &str, filename: &str, age_days: i64, size: u64) -> SessionInfo {
+        SessionInfo {
+            pinned: true,
+            ..mock_session(agent, filename, age_days, size)
+        }
+    }
+
+    fn mixed_sessions() -> Vec<SessionInfo> {
+        vec![
+            pinned_session("claude", "reference.cast", 40, 5000),
+            mock_session("claude", "old.cast", 30, 100),
+            mock_session("claude", "new.cast", 1, 100),
+        ]
+    }
+
+    fn names(sessions: &[SessionInfo]) -> Vec<&str> {
+        sessions.iter().map(|s| s.filename.as_str()).collect()
+    }
+
+    #[test]
+    fn parse_deletion_input_all_skips_pinned() {
+        let result = parse_deletion_input("all", &mixed_sessions(), 2, 14).unwrap();
+        assert_eq!(names(&result), vec!["old.cast", "new.cast"]);
+    }
+
+    #[test]
+    fn parse_deletion_input_old_skips_pinned() {
+        let result = parse_deletion_input("old", &mixed_sessions(), 2, 14).unwrap();
+        assert_eq!(names(&result), vec!["old.cast"]);
+    }
+
+    #[test]
+    fn parse_deletion_input_number_skips_pinned() {
+        let sessions = mixed_sessions();
+        let result = parse_deletion_input("1", &sessions, 0, 14).unwrap();
+        assert_eq!(names(&result), vec!["old.cast"]);
+        let result = parse_deletion_input("2", &sessions, 0, 14).unwrap();
+        assert_eq!(names(&result), vec!["old.cast", "new.cast"]);
+        // Only unpinned sessions count towards the maximum
+        assert!(parse_deletion_input("3", &sessions, 0, 14)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn select_to_free_skips_pinned() {
+        let result = select_to_free(&mixed_sessions(), 5000);
+        assert_eq!(names(&result), vec!["old.cast", "new.cast"]);
+    }
+
+    #[test]
+    fn print_pinned_does_not_panic() {
+        print_pinned(&[]);
+        print_pinned(&mixed_sessions()[..1]);
+    }
+
+    // Tests for print_free_selection
+
+    #[test]
+    fn print_free_selection_met_and_unmet_targets_do_not_panic() {
+        let sessions = vec![mock_session("claude", "s1.cast", 20, 2048)];
+        print_free_selection(&sessions, 1024);
+        print_free_selection(&sessions, 1 << 30);
+    }
+
     // Tests for print_header (these are output tests, verifying no panics)
 
     #[test]
     fn print_header_no_filters_does_not_panic() {
         let stats = mock_stats(3, HashMap::new());
-        let result = print_header(&stats, None, None);
+        let result = print_header(&stats, None, None, &DateRange::default());
         assert!(result.is_ok());
     }
 
     #[test]
     fn print_header_with_agent_filter_does_not_panic() {
         let stats = mock_stats(3, HashMap::new());
-        let result = print_header(&stats, Some("claude"), None);
+        let result = print_header(&stats, Some("claude"), None, &DateRange::default());
         assert!(result.is_ok());
     }
 
     #[test]
     fn print_header_with_age_filter_does_not_panic() {
         let stats = mock_stats(3, HashMap::new());
-        let result = print_header(&stats, None, Some(14));
+        let result = print_header(&stats, None, Some(14), &DateRange::default());
         assert!(result.is_ok());
     }
 
     #[test]
     fn print_header_with_both_filters_does_not_panic() {
         let stats = mock_stats(3, HashMap::new());
-        let result = print_header(&stats, Some("claude"), Some(14));
+        let result = print_header(&stats, Some("claude"), Some(14), &DateRange::default());
         assert!(result.is_ok());
     }
 
@@ -492,7 +677,7 @@ mod tests {
         by_agent.insert("claude".to_string(), 2);
         by_agent.insert("codex".to_string(), 1);
         let stats = mock_stats(3, by_agent);
-        let result = print_header(&stats, None, None);
+        let result = print_header(&stats, None, None, &DateRange::default());
         assert!(result.is_ok());
     }
 
@@ -5,6 +5,7 @@ use clap::CommandFactory;
 use clap_complete::{generate, Shell as CompletionShell};
 use std::io;
 
+use agr::shell::AgentCompletion;
 use agr::{shell, Config, StorageManager};
 
 /// Handle completions command.
@@ -16,6 +17,7 @@ pub fn handle<C: CommandFactory>(
     shell_init: Option<CompletionShell>,
     debug: bool,
     files: bool,
+    agents: Option<AgentCompletion>,
     limit: usize,
     prefix: &str,
 ) -> Result<()> {
@@ -24,7 +26,13 @@ pub fn handle<C: CommandFactory>(
         let output = match shell {
             CompletionShell::Zsh => shell::generate_zsh_init(debug),
             CompletionShell::Bash => shell::generate_bash_init(debug),
-            _ => return Err(anyhow!("Only zsh and bash are supported for --shell-init")),
+            CompletionShell::Fish => shell::generate_fish_init(debug),
+            CompletionShell::PowerShell => shell::generate_powershell_init(),
+            _ => {
+                return Err(anyhow!(
+                "Only zsh, bash, fish and powershell are supported for --shell-init (use --shell for others)"
+            ))
+            }
         };
         println!("{}", output);
         return Ok(());
@@ -35,15 +43,25 @@ pub fn handle<C: CommandFactory>(
         return list_cast_files(prefix, limit);
     }
 
+    // Handle --agents (dynamic agent listing)
+    if let Some(kind) = agents {
+        let config = Config::load()?;
+        for agent in shell::agent_completions(&config, kind, prefix) {
+            println!("{}", agent);
+        }
+        return Ok(());
+    }
+
     // Handle --shell (clap native completions)
     if let Some(shell) = shell_arg {
         return generate_completions::<C>(shell);
     }
 
     // No arguments - show usage
-    eprintln!("Usage: agr completions --shell <bash|zsh|fish|powershell>");
-    eprintln!("       agr completions --shell-init <bash|zsh>");
+    eprintln!("Usage: agr completions --shell <bash|zsh|fish|elvish|powershell>");
+    eprintln!("       agr completions --shell-init <bash|zsh|fish|powershell>");
     eprintln!("       agr completions --files [prefix]");
+    eprintln!("       agr completions --agents[=enabled|wrapped|no-wrap|analysis] [prefix]");
     std::process::exit(1);
 }
 
@@ -3,24 +3,78 @@
 use anyhow::{Context, Result};
 use std::fs;
 use std::io::{self, BufRead, Write};
-use std::path::Path;
 
+use agr::config::check::CheckLevel;
+use agr::config::diff::{diff_from_default, format_diff, mark_defaults};
 use agr::config::migrate_config;
-use agr::theme::ansi;
+use agr::files::atomic::write_atomic;
 use agr::theme::current_theme;
+use agr::theme::{ansi, ansi_code};
 use agr::Config;
 
 /// Show current configuration as TOML with inline documentation comments.
+///
+/// With `diff`, only values that differ from the defaults are shown; with
+/// `annotated`, values still at their default are marked `# (default)`.
 #[cfg(not(tarpaulin_include))]
-pub fn handle_show() -> Result<()> {
+pub fn handle_show(diff: bool, annotated: bool) -> Result<()> {
     let config = Config::load()?;
+    let theme = current_theme();
+
+    if diff {
+        let diffs = diff_from_default(&config)?;
+        if diffs.is_empty() {
+            println!(
+                "{}",
+                theme.primary_text("All settings are at their defaults.")
+            );
+        } else {
+            print!("{}", theme.primary_text(&format_diff(&diffs)));
+        }
+        return Ok(());
+    }
+
     let toml_str = toml::to_string_pretty(&config)?;
     // Insert commented-out templates for optional fields (e.g. # workers = auto)
     // before annotation so they also get documentation comments
     let with_templates = agr::config::docs::insert_optional_field_templates(&toml_str);
-    let annotated = agr::config::docs::annotate_config(&with_templates);
+    let mut annotated_str = agr::config::docs::annotate_config(&with_templates);
+    if annotated {
+        annotated_str = mark_defaults(&annotated_str, &diff_from_default(&config)?);
+    }
+    println!("{}", theme.primary_text(&annotated_str));
+    Ok(())
+}
+
+/// Check the configuration against this machine and print a report.
+///
+/// Exits with status 1 if any check fails hard.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_check() -> Result<()> {
+    let config = Config::load()?;
+    let report = config.check();
     let theme = current_theme();
-    println!("{}", theme.primary_text(&annotated));
+
+    for item in &report.items {
+        let line = match item.level {
+            CheckLevel::Ok => theme.success_text(&format!("  ok    {}", item.message)),
+            CheckLevel::Warning => theme.primary_text(&format!("  warn  {}", item.message)),
+            CheckLevel::Error => theme.error_text(&format!("  error {}", item.message)),
+        };
+        println!("{}", line);
+    }
+
+    let errors = report.count(CheckLevel::Error);
+    let warnings = report.count(CheckLevel::Warning);
+    println!();
+    println!(
+        "{}",
+        theme.primary_text(&format!("{} errors, {} warnings", errors, warnings))
+    );
+
+    if report.has_errors() {
+        std::process::exit(1);
+    }
     Ok(())
 }
 
@@ -107,7 +161,7 @@ pub fn handle_migrate(auto_confirm: bool) -> Result<()> {
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent)?;
         }
-        atomic_write(&config_path, &result.content)?;
+        write_atomic(&config_path, result.content.as_bytes())?;
         println!(
             "{}",
             theme.success_text("Config file created successfully.")
@@ -137,7 +191,12 @@ pub fn handle_migrate(auto_confirm: bool) -> Result<()> {
             ))
         );
         for field in &result.removed_fields {
-            println!("{}  - {}{}", ansi::RED, field, ansi::RESET);
+            println!(
+                "{}  - {}{}",
+                ansi_code(ansi::RED),
+                field,
+                ansi_code(ansi::RESET)
+            );
         }
     }
 
@@ -176,7 +235,7 @@ pub fn handle_migrate(auto_confirm: bool) -> Result<()> {
     }
 
     // Write the updated config atomically
-    atomic_write(&config_path, &result.content)?;
+    write_atomic(&config_path, result.content.as_bytes())?;
     println!("{}", theme.success_text("Config updated successfully."));
 
     Ok(())
@@ -210,13 +269,14 @@ pub fn handle_reset(auto_confirm: bool) -> Result<()> {
         return Ok(());
     }
 
-    // Back up existing config (use numbered suffix to avoid overwriting previous backups)
+    // Back up existing config (use numbered suffix to avoid overwriting previous
+    // backups; the plain config.toml.bak is the rolling backup kept by saves)
     if config_path.exists() {
-        let mut backup_path = config_path.with_extension("toml.bak");
         let mut counter = 1u32;
+        let mut backup_path = config_path.with_extension(format!("toml.bak.{}", counter));
         while backup_path.exists() {
-            backup_path = config_path.with_extension(format!("toml.bak.{}", counter));
             counter += 1;
+            backup_path = config_path.with_extension(format!("toml.bak.{}", counter));
         }
         fs::copy(&config_path, &backup_path)
             .with_context(|| format!("Failed to back up config to {}", backup_path.display()))?;
@@ -230,7 +290,7 @@ pub fn handle_reset(auto_confirm: bool) -> Result<()> {
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent)?;
     }
-    atomic_write(&config_path, &result.content)?;
+    write_atomic(&config_path, result.content.as_bytes())?;
     println!("{}", theme.success_text("Config reset to defaults."));
 
     Ok(())
@@ -282,11 +342,22 @@ fn print_diff_preview(new_content: &str, added_fields: &[String], is_new_file: b
             if is_new_file || is_added {
                 // Print pending section header if we have one
                 if let Some(header) = pending_section_header.take() {
-                    println!("{}+{} {}{}", ansi::GREEN, ansi::RESET, ansi::GREEN, header);
+                    println!(
+                        "{}+{} {}{}",
+                        ansi_code(ansi::GREEN),
+                        ansi_code(ansi::RESET),
+                        ansi_code(ansi::GREEN),
+                        header
+                    );
                 }
 
                 // Print added line with green + prefix
-                println!("{}+ {}{}", ansi::GREEN, line, ansi::RESET);
+                println!(
+                    "{}+ {}{}",
+                    ansi_code(ansi::GREEN),
+                    line,
+                    ansi_code(ansi::RESET)
+                );
             } else if section_has_additions {
                 // Show context lines in the section (without + prefix)
                 // Only show the section header once we know there are additions
@@ -298,9 +369,20 @@ fn print_diff_preview(new_content: &str, added_fields: &[String], is_new_file: b
         } else if is_new_file && !trimmed.is_empty() {
             // For new files, show comments too
             if let Some(header) = pending_section_header.take() {
-                println!("{}+{} {}{}", ansi::GREEN, ansi::RESET, ansi::GREEN, header);
+                println!(
+                    "{}+{} {}{}",
+                    ansi_code(ansi::GREEN),
+                    ansi_code(ansi::RESET),
+                    ansi_code(ansi::GREEN),
+                    header
+                );
             }
-            println!("{}+ {}{}", ansi::GREEN, line, ansi::RESET);
+            println!(
+                "{}+ {}{}",
+                ansi_code(ansi::GREEN),
+                line,
+                ansi_code(ansi::RESET)
+            );
         }
     }
 }
@@ -370,33 +452,3 @@ fn should_proceed(message: &str, auto_confirm: bool) -> Result<bool> {
     let response = input.trim().to_lowercase();
     Ok(response == "y" || response == "yes")
 }
-
-/// Write content to a file atomically.
-///
-/// Writes to a temporary file first, then renames to the target path.
-/// This prevents corruption if the write is interrupted.
-fn atomic_write(path: &Path, content: &str) -> Result<()> {
-    // Create temp file in the same directory (ensures same filesystem for rename)
-    let parent = path
-        .parent()
-        .context("Config path has no parent directory")?;
-    let temp_path = parent.join(format!(
-        ".{}.tmp",
-        path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("config")
-    ));
-
-    // Write to temp file
-    fs::write(&temp_path, content)
-        .with_context(|| format!("Failed to write temp file: {:?}", temp_path))?;
-
-    // Rename temp to target (atomic on most filesystems)
-    fs::rename(&temp_path, path).with_context(|| {
-        // Clean up temp file on rename failure
-        let _ = fs::remove_file(&temp_path);
-        format!("Failed to rename {:?} to {:?}", temp_path, path)
-    })?;
-
-    Ok(())
-}
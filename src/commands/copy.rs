@@ -2,15 +2,20 @@
 
 use anyhow::Result;
 
-use agr::{clipboard::copy_file_to_clipboard, Config};
+use agr::clipboard::{
+    copy_file_to_clipboard, copy_html_to_clipboard, copy_text_to_clipboard, CopyFormat,
+};
+use agr::{AsciicastFile, Config};
 
 use agr::files::resolve::resolve_file_path;
 
 /// Copy a recording file to the system clipboard.
 ///
 /// On macOS, copies as a file reference for paste-as-attachment.
-/// On Linux, falls back to copying file content as text.
-pub fn handle(file: &str) -> Result<()> {
+/// On Linux, falls back to copying file content as text. With
+/// [`CopyFormat::Html`], copies a playable HTML snippet instead; with
+/// [`CopyFormat::Text`], the plain text the recording printed.
+pub fn handle(file: &str, format: CopyFormat) -> Result<()> {
     let config = Config::load()?;
 
     // Resolve file path (supports short format like "claude/session.cast")
@@ -27,16 +32,32 @@ pub fn handle(file: &str) -> Result<()> {
         eprintln!("Warning: File does not have .cast extension");
     }
 
-    // Copy to clipboard
-    let result = copy_file_to_clipboard(&filepath)?;
-
     // Extract filename and strip .cast extension for the message
     let filename = filepath
         .file_stem()
         .and_then(|s| s.to_str())
         .unwrap_or("recording");
 
-    println!("{}", result.message(filename));
+    match format {
+        CopyFormat::File => {
+            let result = copy_file_to_clipboard(&filepath)?;
+            println!("{}", result.message(filename));
+        }
+        CopyFormat::Html => {
+            copy_html_to_clipboard(&filepath, filename)?;
+            println!("Copied {}.cast to clipboard as an HTML player", filename);
+        }
+        CopyFormat::Text => {
+            let cast = AsciicastFile::parse(&filepath)?;
+            let transcript = cast.transcript();
+            copy_text_to_clipboard(&transcript)?;
+            println!(
+                "Copied transcript of {}.cast to clipboard ({} lines)",
+                filename,
+                transcript.lines().count()
+            );
+        }
+    }
     Ok(())
 }
 
@@ -0,0 +1,131 @@
+//! Dedupe command handler
+
+use anyhow::Result;
+
+use agr::storage::SessionInfo;
+use agr::theme::current_theme;
+use agr::{Config, StorageManager};
+
+use super::cleanup::confirm_and_delete;
+
+/// Sessions to delete from duplicate groups: everything except the newest copy.
+pub(crate) fn redundant_copies(groups: &[Vec<SessionInfo>]) -> Vec<SessionInfo> {
+    groups
+        .iter()
+        .flat_map(|group| group.iter().skip(1).cloned())
+        .collect()
+}
+
+/// Print each duplicate group, marking the copy that will be kept.
+fn print_groups(groups: &[Vec<SessionInfo>]) {
+    let theme = current_theme();
+    for (i, group) in groups.iter().enumerate() {
+        println!(
+            "{}",
+            theme.primary_text(&format!(
+                "Group {} ({} identical recordings):",
+                i + 1,
+                group.len()
+            ))
+        );
+        for (j, session) in group.iter().enumerate() {
+            let marker = if j == 0 { "keep  " } else { "delete" };
+            println!(
+                "{}",
+                theme.primary_text(&format!(
+                    "  [{}] {}/{} ({}, {})",
+                    marker,
+                    session.agent,
+                    session.filename,
+                    session.modified.format("%Y-%m-%d %H:%M"),
+                    session.size_human()
+                ))
+            );
+        }
+    }
+    println!();
+}
+
+/// Find recordings with identical content and offer to delete the extra copies.
+///
+/// Recordings are compared by a hash of their events (the header, and so its
+/// timestamp, is ignored). The newest copy in each group is kept.
+/// With `dry_run`, only the duplicate groups are reported.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(dry_run: bool) -> Result<()> {
+    let config = Config::load()?;
+    let storage = StorageManager::new(config);
+    let theme = current_theme();
+
+    let groups = storage.find_duplicates(None)?;
+    if groups.is_empty() {
+        println!("{}", theme.primary_text("No duplicate recordings found."));
+        return Ok(());
+    }
+
+    print_groups(&groups);
+
+    let to_delete = redundant_copies(&groups);
+    if dry_run {
+        println!(
+            "{}",
+            theme.primary_text(&format!(
+                "Dry run: {} duplicate recordings in {} groups would be deleted.",
+                to_delete.len(),
+                groups.len()
+            ))
+        );
+        return Ok(());
+    }
+
+    confirm_and_delete(&to_delete, &storage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+    use std::path::PathBuf;
+
+    fn mock_session(filename: &str) -> SessionInfo {
+        SessionInfo {
+            path: PathBuf::from(format!("/mock/claude/{}", filename)),
+            agent: "claude".to_string(),
+            filename: filename.to_string(),
+            size: 100,
+            modified: Local::now(),
+            age_days: 0,
+            age_hours: 0,
+            age_minutes: 0,
+            pinned: false,
+            has_notes: false,
+        }
+    }
+
+    #[test]
+    fn redundant_copies_skips_newest_in_each_group() {
+        let groups = vec![
+            vec![mock_session("a-new.cast"), mock_session("a-old.cast")],
+            vec![
+                mock_session("b-new.cast"),
+                mock_session("b-mid.cast"),
+                mock_session("b-old.cast"),
+            ],
+        ];
+        let names: Vec<_> = redundant_copies(&groups)
+            .into_iter()
+            .map(|s| s.filename)
+            .collect();
+        assert_eq!(names, vec!["a-old.cast", "b-mid.cast", "b-old.cast"]);
+    }
+
+    #[test]
+    fn redundant_copies_empty_for_no_groups() {
+        assert!(redundant_copies(&[]).is_empty());
+    }
+
+    #[test]
+    fn print_groups_does_not_panic() {
+        print_groups(&[vec![mock_session("a.cast"), mock_session("b.cast")]]);
+    }
+}
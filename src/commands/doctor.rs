@@ -0,0 +1,390 @@
+//! Doctor command handler
+//!
+//! Runs end-to-end environment diagnostics: the recorder binary, the
+//! configured agents, the storage directory, shell integration, the
+//! clipboard tool and the config file itself. Each check is a plain function over stubbable
+//! probes so it can be tested without touching the real environment.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use agr::analyzer::backend::command_exists;
+use agr::config::check::CheckLevel;
+use agr::theme::current_theme;
+use agr::Config;
+
+/// Outcome of a single doctor check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CheckResult {
+    pub level: CheckLevel,
+    pub message: String,
+    /// What to do about a warning or error
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            level: CheckLevel::Ok,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warning(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            level: CheckLevel::Warning,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn error(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            level: CheckLevel::Error,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Environment lookups used by the checks, replaceable in tests.
+pub(crate) struct Probes<'a> {
+    /// Whether a command is on PATH
+    pub command_exists: &'a dyn Fn(&str) -> bool,
+    /// RC file with the shell integration and its block count
+    pub installed_rc: Option<(PathBuf, usize)>,
+    /// Clipboard tool `agr copy` would use
+    pub clipboard_tool: Option<&'static str>,
+}
+
+/// Run environment diagnostics and print a checklist.
+///
+/// Exits with status 1 if any hard check fails.
+#[cfg(not(tarpaulin_include))]
+pub fn handle() -> Result<()> {
+    let config = Config::load();
+    let probes = Probes {
+        command_exists: &command_exists,
+        installed_rc: agr::shell::find_installed_rc(),
+        clipboard_tool: agr::clipboard::available_tool(),
+    };
+    let results = run_checks(&config, &probes);
+    let theme = current_theme();
+
+    for result in &results {
+        let line = match result.level {
+            CheckLevel::Ok => theme.success_text(&format!("  [ok]   {}", result.message)),
+            CheckLevel::Warning => theme.primary_text(&format!("  [warn] {}", result.message)),
+            CheckLevel::Error => theme.error_text(&format!("  [fail] {}", result.message)),
+        };
+        println!("{}", line);
+        if let Some(hint) = &result.hint {
+            println!(
+                "{}",
+                theme.secondary_text(&format!("         hint: {}", hint))
+            );
+        }
+    }
+
+    println!();
+    println!("{}", theme.primary_text(&summary(&results)));
+
+    if has_failures(&results) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Run all checks in checklist order.
+///
+/// An invalid config is reported and the remaining checks use defaults.
+pub(crate) fn run_checks(config: &Result<Config>, probes: &Probes) -> Vec<CheckResult> {
+    let mut results = vec![check_config(config)];
+    let default_config;
+    let config = match config {
+        Ok(config) => config,
+        Err(_) => {
+            default_config = Config::default();
+            &default_config
+        }
+    };
+
+    results.push(check_asciinema(probes.command_exists));
+    results.extend(check_agents(&config.agents.enabled, probes.command_exists));
+    results.push(check_storage(&config.storage_directory()));
+    results.push(check_shell_integration(probes.installed_rc.as_ref()));
+    results.push(check_clipboard(probes.clipboard_tool));
+    results
+}
+
+/// Whether any check failed hard.
+pub(crate) fn has_failures(results: &[CheckResult]) -> bool {
+    results.iter().any(|r| r.level == CheckLevel::Error)
+}
+
+/// One-line tally of the results.
+pub(crate) fn summary(results: &[CheckResult]) -> String {
+    let count = |level| results.iter().filter(|r| r.level == level).count();
+    format!(
+        "{} passed, {} warnings, {} failed",
+        count(CheckLevel::Ok),
+        count(CheckLevel::Warning),
+        count(CheckLevel::Error)
+    )
+}
+
+/// The config file parses and validates.
+pub(crate) fn check_config(config: &Result<Config>) -> CheckResult {
+    match config {
+        Ok(_) => CheckResult::ok("Config is valid"),
+        Err(e) => CheckResult::error(
+            format!("Config is invalid: {:#}", e),
+            "Fix it with 'agr config edit', or start over with 'agr config reset'",
+        ),
+    }
+}
+
+/// asciinema, which does the actual recording, is on PATH.
+pub(crate) fn check_asciinema(command_exists: &dyn Fn(&str) -> bool) -> CheckResult {
+    if command_exists("asciinema") {
+        CheckResult::ok("asciinema found on PATH")
+    } else {
+        CheckResult::error(
+            "asciinema not found on PATH",
+            "Install asciinema 3.x: https://docs.asciinema.org/getting-started/",
+        )
+    }
+}
+
+/// Each enabled agent is installed.
+pub(crate) fn check_agents(
+    enabled: &[String],
+    command_exists: &dyn Fn(&str) -> bool,
+) -> Vec<CheckResult> {
+    if enabled.is_empty() {
+        return vec![CheckResult::warning(
+            "No agents enabled",
+            "Enable one with 'agr agents add <name>'",
+        )];
+    }
+    enabled
+        .iter()
+        .map(|agent| {
+            if command_exists(agent) {
+                CheckResult::ok(format!("Agent '{}' found on PATH", agent))
+            } else {
+                CheckResult::warning(
+                    format!("Agent '{}' not found on PATH", agent),
+                    format!(
+                        "Install it, or stop recording it with 'agr agents remove {}'",
+                        agent
+                    ),
+                )
+            }
+        })
+        .collect()
+}
+
+/// The storage directory exists and accepts new files.
+///
+/// A missing directory is only a warning: it is created on the first
+/// recording. Unlike `agr config check`, nothing is created here.
+pub(crate) fn check_storage(dir: &Path) -> CheckResult {
+    if !dir.exists() {
+        return CheckResult::warning(
+            format!("Storage directory does not exist: {}", dir.display()),
+            "It is created on the first recording; set storage.directory to change it",
+        );
+    }
+    if !dir.is_dir() {
+        return CheckResult::error(
+            format!("Storage path is not a directory: {}", dir.display()),
+            "Set storage.directory in 'agr config edit' to a directory",
+        );
+    }
+
+    let probe = dir.join(format!(".agr-doctor-{}", std::process::id()));
+    match fs::write(&probe, b"").and_then(|_| fs::remove_file(&probe)) {
+        Ok(()) => CheckResult::ok(format!("Storage directory writable: {}", dir.display())),
+        Err(e) => CheckResult::error(
+            format!("Storage directory not writable: {} ({})", dir.display(), e),
+            "Fix its permissions or set storage.directory in 'agr config edit'",
+        ),
+    }
+}
+
+/// Shell integration is installed exactly once.
+pub(crate) fn check_shell_integration(installed_rc: Option<&(PathBuf, usize)>) -> CheckResult {
+    match installed_rc {
+        None => CheckResult::warning(
+            "Shell integration not installed (agents are not recorded automatically)",
+            "Run 'agr shell install'",
+        ),
+        Some((rc, count)) if *count > 1 => CheckResult::warning(
+            format!(
+                "Shell integration installed {} times in {}",
+                count,
+                rc.display()
+            ),
+            "Run 'agr shell doctor' to repair",
+        ),
+        Some((rc, _)) => {
+            CheckResult::ok(format!("Shell integration installed in {}", rc.display()))
+        }
+    }
+}
+
+/// A clipboard tool is installed for `agr copy`.
+///
+/// Only a warning: recording and playback work without one.
+pub(crate) fn check_clipboard(tool: Option<&str>) -> CheckResult {
+    match tool {
+        Some(tool) => CheckResult::ok(format!("Clipboard tool: {}", tool)),
+        None => CheckResult::warning(
+            "No clipboard tool found ('agr copy' will not work)",
+            "On Linux, install xclip, xsel or wl-copy",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn installed(names: &'static [&'static str]) -> impl Fn(&str) -> bool {
+        move |cmd| names.contains(&cmd)
+    }
+
+    fn config_with_storage(dir: &Path) -> Config {
+        let mut config = Config::default();
+        config.storage.directory = dir.display().to_string();
+        config.agents.enabled = vec!["claude".to_string(), "codex".to_string()];
+        config
+    }
+
+    #[test]
+    fn asciinema_missing_is_a_failure() {
+        assert_eq!(
+            check_asciinema(&installed(&["asciinema"])).level,
+            CheckLevel::Ok
+        );
+        let missing = check_asciinema(&installed(&[]));
+        assert_eq!(missing.level, CheckLevel::Error);
+        assert!(missing.hint.unwrap().contains("asciinema"));
+    }
+
+    #[test]
+    fn agents_are_checked_individually() {
+        let agents = vec!["claude".to_string(), "codex".to_string()];
+        let results = check_agents(&agents, &installed(&["claude"]));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].level, CheckLevel::Ok);
+        assert_eq!(results[1].level, CheckLevel::Warning);
+        assert!(results[1].message.contains("'codex'"));
+        assert!(results[1]
+            .hint
+            .as_ref()
+            .unwrap()
+            .contains("agr agents remove codex"));
+
+        let none = check_agents(&[], &installed(&[]));
+        assert_eq!(none.len(), 1);
+        assert_eq!(none[0].level, CheckLevel::Warning);
+    }
+
+    #[test]
+    fn storage_writable_missing_and_not_a_directory() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(check_storage(temp.path()).level, CheckLevel::Ok);
+
+        let missing = temp.path().join("missing");
+        assert_eq!(check_storage(&missing).level, CheckLevel::Warning);
+        assert!(!missing.exists(), "doctor must not create the directory");
+
+        let file = temp.path().join("file");
+        fs::write(&file, "").unwrap();
+        assert_eq!(check_storage(&file).level, CheckLevel::Error);
+    }
+
+    #[test]
+    fn shell_integration_states() {
+        assert_eq!(check_shell_integration(None).level, CheckLevel::Warning);
+
+        let once = (PathBuf::from("/home/u/.zshrc"), 1);
+        assert_eq!(check_shell_integration(Some(&once)).level, CheckLevel::Ok);
+
+        let twice = (PathBuf::from("/home/u/.zshrc"), 2);
+        let result = check_shell_integration(Some(&twice));
+        assert_eq!(result.level, CheckLevel::Warning);
+        assert!(result.hint.unwrap().contains("agr shell doctor"));
+    }
+
+    #[test]
+    fn clipboard_tool_is_reported_or_warned() {
+        let found = check_clipboard(Some("wl-copy"));
+        assert_eq!(found.level, CheckLevel::Ok);
+        assert!(found.message.contains("wl-copy"));
+
+        let missing = check_clipboard(None);
+        assert_eq!(missing.level, CheckLevel::Warning);
+        assert!(missing.hint.unwrap().contains("xclip"));
+    }
+
+    #[test]
+    fn invalid_config_is_a_failure_with_cause() {
+        let config = Config::from_toml("[player]\ndefault_speed = -1.0\n", None);
+        let result = check_config(&config);
+        assert_eq!(result.level, CheckLevel::Error);
+        assert!(result.message.contains("Invalid config"));
+        assert_eq!(check_config(&Ok(Config::default())).level, CheckLevel::Ok);
+    }
+
+    #[test]
+    fn run_checks_aggregates_in_checklist_order() {
+        let temp = TempDir::new().unwrap();
+        let config = Ok(config_with_storage(temp.path()));
+        let probes = Probes {
+            command_exists: &installed(&["asciinema", "claude"]),
+            installed_rc: Some((PathBuf::from("/home/u/.zshrc"), 1)),
+            clipboard_tool: Some("xclip"),
+        };
+
+        let results = run_checks(&config, &probes);
+        let levels: Vec<CheckLevel> = results.iter().map(|r| r.level).collect();
+        assert_eq!(
+            levels,
+            vec![
+                CheckLevel::Ok,      // config
+                CheckLevel::Ok,      // asciinema
+                CheckLevel::Ok,      // claude
+                CheckLevel::Warning, // codex
+                CheckLevel::Ok,      // storage
+                CheckLevel::Ok,      // shell integration
+                CheckLevel::Ok,      // clipboard
+            ]
+        );
+        assert!(!has_failures(&results));
+        assert_eq!(summary(&results), "6 passed, 1 warnings, 0 failed");
+    }
+
+    #[test]
+    fn run_checks_fails_on_hard_errors_and_continues_after_bad_config() {
+        let config = Config::from_toml("[player]\ndefault_speed = -1.0\n", None);
+        let probes = Probes {
+            command_exists: &installed(&[]),
+            installed_rc: None,
+            clipboard_tool: None,
+        };
+
+        let results = run_checks(&config, &probes);
+        assert!(has_failures(&results));
+        assert_eq!(results[0].level, CheckLevel::Error);
+        assert_eq!(results[1].level, CheckLevel::Error); // asciinema
+        assert_eq!(results.last().unwrap().level, CheckLevel::Warning);
+        assert!(summary(&results).ends_with("2 failed"));
+    }
+}
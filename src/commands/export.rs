@@ -0,0 +1,70 @@
+//! Export command handler
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use agr::asciicast::integrity::check_file_integrity;
+use agr::asciicast::{AsciicastFile, ExportFormat, FlattenMarkers, MarkerFlattening, Transform};
+use agr::files::resolve::resolve_file_path;
+use agr::theme::current_theme;
+use agr::Config;
+
+/// Export a recording as plain asciicast v3, optionally flattening markers.
+///
+/// The source recording is left untouched; the result is written to `out`.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(
+    file: &str,
+    out: &str,
+    format: ExportFormat,
+    flatten_markers: bool,
+    no_markers: bool,
+) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+    let output_path = Path::new(out);
+    if output_path.exists() && output_path.canonicalize()? == filepath.canonicalize()? {
+        bail!("Output file is the recording itself: {}", out);
+    }
+
+    check_file_integrity(&filepath)?;
+
+    let mut cast = AsciicastFile::parse(&filepath)
+        .with_context(|| format!("Failed to parse asciicast file: {}", filepath.display()))?;
+
+    let mode = if no_markers {
+        MarkerFlattening::Strip
+    } else if flatten_markers {
+        MarkerFlattening::Comment
+    } else {
+        MarkerFlattening::Keep
+    };
+    let mut flatten = FlattenMarkers::new(mode);
+    flatten.transform(&mut cast.events);
+
+    match format {
+        ExportFormat::Cast => cast
+            .write(output_path)
+            .with_context(|| format!("Failed to write output file: {}", out))?,
+    }
+
+    let detail = match mode {
+        MarkerFlattening::Keep => String::new(),
+        MarkerFlattening::Comment => format!(", {} marker(s) flattened", flatten.flattened()),
+        MarkerFlattening::Strip => format!(", {} marker(s) removed", flatten.flattened()),
+    };
+    println!(
+        "{}",
+        theme.primary_text(&format!("Exported to {}{}", out, detail))
+    );
+    Ok(())
+}
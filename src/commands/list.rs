@@ -4,6 +4,8 @@ use std::io::IsTerminal;
 
 use anyhow::Result;
 
+use agr::files::index::IndexEntry;
+use agr::storage::{DateRange, SessionInfo, SessionJson};
 use agr::theme::current_theme;
 use agr::tui::app::TuiApp;
 use agr::tui::widgets::FileItem;
@@ -16,15 +18,28 @@ use super::truncate_string;
 ///
 /// When stdout is a TTY, shows an interactive file explorer.
 /// When piped, shows a simple text table (fallback).
+/// With `json`, prints a [`SessionJson`] array regardless of the terminal.
+/// Durations and titles come from the storage index; `refresh` rebuilds it.
+/// Sessions that did not start within `range` are left out.
 #[cfg(not(tarpaulin_include))]
-pub fn handle(agent: Option<&str>) -> Result<()> {
+pub fn handle(agent: Option<&str>, json: bool, refresh: bool, range: DateRange) -> Result<()> {
     let config = Config::load()?;
     let storage = StorageManager::new(config.clone());
-    let sessions = storage.list_sessions(agent)?;
+    let mut sessions = storage.list_sessions_indexed(agent, refresh)?;
+    sessions.retain(|(session, entry)| range.matches(session, entry.timestamp));
+
+    if json {
+        return handle_json(&sessions);
+    }
 
     if sessions.is_empty() {
         let theme = current_theme();
-        if let Some(agent_name) = agent {
+        if !range.is_unbounded() {
+            println!(
+                "{}",
+                theme.primary_text(&format!("No sessions found {}.", range_phrase(&range)))
+            );
+        } else if let Some(agent_name) = agent {
             println!(
                 "{}",
                 theme.primary_text(&format!("No sessions found for agent '{}'.", agent_name))
@@ -39,18 +54,41 @@ pub fn handle(agent: Option<&str>) -> Result<()> {
     if std::io::stdout().is_terminal() {
         handle_tui(sessions, agent, config)
     } else {
-        handle_text(sessions, agent, &storage)
+        let sessions = sessions.into_iter().map(|(session, _)| session).collect();
+        handle_text(sessions, agent, &range, &storage)
+    }
+}
+
+/// The range as a phrase, e.g. `from 2025-01-01 to 2025-01-31`.
+fn range_phrase(range: &DateRange) -> String {
+    match (range.since, range.until) {
+        (Some(_), Some(_)) => format!("from {}", range.describe()),
+        _ => range.describe(),
     }
 }
 
+/// Handle list command with JSON output, newest first.
+fn handle_json(sessions: &[(SessionInfo, IndexEntry)]) -> Result<()> {
+    let entries: Vec<SessionJson> = sessions
+        .iter()
+        .rev()
+        .map(|(session, entry)| SessionJson::from_indexed(session, entry))
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&entries)?);
+    Ok(())
+}
+
 /// Handle list command with interactive TUI.
 fn handle_tui(
-    sessions: Vec<agr::storage::SessionInfo>,
+    sessions: Vec<(SessionInfo, IndexEntry)>,
     agent: Option<&str>,
     config: Config,
 ) -> Result<()> {
     // Convert sessions to FileItems
-    let items: Vec<FileItem> = sessions.into_iter().map(FileItem::from).collect();
+    let items: Vec<FileItem> = sessions
+        .into_iter()
+        .map(|(session, entry)| FileItem::with_title(session, entry.title))
+        .collect();
 
     // Create and run the list app
     let mut app = ListApp::new(items, config)?;
@@ -65,8 +103,9 @@ fn handle_tui(
 
 /// Handle list command with text output (piped mode fallback).
 fn handle_text(
-    mut sessions: Vec<agr::storage::SessionInfo>,
+    mut sessions: Vec<SessionInfo>,
     agent: Option<&str>,
+    range: &DateRange,
     storage: &StorageManager,
 ) -> Result<()> {
     let theme = current_theme();
@@ -75,14 +114,21 @@ fn handle_text(
     sessions.reverse();
 
     // Print summary header
-    if let Some(agent_name) = &agent {
+    let mut filters = Vec::new();
+    if let Some(agent_name) = agent {
+        filters.push(format!("agent: {}", agent_name));
+    }
+    if !range.is_unbounded() {
+        filters.push(format!("date: {}", range.describe()));
+    }
+    if !filters.is_empty() {
         // Just show count for filtered view
         println!(
             "{}",
             theme.primary_text(&format!(
-                "Sessions: {} (filtered by agent: {})",
+                "Sessions: {} (filtered by {})",
                 sessions.len(),
-                agent_name
+                filters.join(", ")
             ))
         );
     } else {
@@ -115,7 +161,7 @@ fn handle_text(
     // Print table header
     println!(
         "{}",
-        theme.primary_text("  #  |  Age  | DateTime         | Agent       | Size       | Filename")
+        theme.primary_text("  #  |  Age  | DateTime         | Agent       | Size       | Name")
     );
     println!(
         "{}",
@@ -135,7 +181,7 @@ fn handle_text(
                 session.modified.format("%Y-%m-%d %H:%M"),
                 truncate_string(&session.agent, 11),
                 session.size_human(),
-                session.filename
+                session.display_name()
             ))
         );
     }
@@ -1,11 +1,15 @@
 //! Marker subcommands handler
 
-use anyhow::Result;
+use std::path::Path;
+
+use anyhow::{Context, Result};
 
 use agr::theme::current_theme;
-use agr::{Config, MarkerManager};
+use agr::{AsciicastFile, Config, MarkerManager};
 
 use agr::asciicast::integrity::check_file_integrity;
+use agr::asciicast::marker_import::parse_markers;
+use agr::asciicast::{markers_to_subtitles, ImportFormat, MarkerTimeFormat, SubtitleFormat};
 use agr::files::resolve::resolve_file_path;
 
 /// Add a marker to a cast file at a specific timestamp.
@@ -31,14 +35,23 @@ pub fn handle_add(file: &str, time: f64, label: &str) -> Result<()> {
 }
 
 /// List all markers in a cast file with their timestamps and labels.
+///
+/// Times are shown in `format` when given, else as `45.2s`. With `json`,
+/// prints the markers as a JSON array (empty when there are none).
 #[cfg(not(tarpaulin_include))]
-pub fn handle_list(file: &str) -> Result<()> {
+pub fn handle_list(file: &str, format: Option<MarkerTimeFormat>, json: bool) -> Result<()> {
     let config = Config::load()?;
     let theme = current_theme();
     // Resolve file path (supports short format like "claude/session.cast")
     let filepath = resolve_file_path(file, &config)?;
     check_file_integrity(&filepath)?;
-    let markers = MarkerManager::list_markers(&filepath)?;
+    let cast = AsciicastFile::parse(&filepath)?;
+    let markers = MarkerManager::list_markers_from_cast(&cast)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&markers)?);
+        return Ok(());
+    }
 
     if markers.is_empty() {
         println!("{}", theme.primary_text("No markers found in file."));
@@ -46,9 +59,93 @@ pub fn handle_list(file: &str) -> Result<()> {
     }
 
     println!("{}", theme.primary_text("Markers:"));
+    let duration = cast.duration();
     for marker in markers {
-        println!("{}", theme.primary_text(&format!("  {}", marker)));
+        let line = match format {
+            Some(format) => marker.display_with(format, duration),
+            None => marker.to_string(),
+        };
+        println!("{}", theme.primary_text(&format!("  {}", line)));
     }
 
     Ok(())
 }
+
+/// Export the markers of a cast file as WebVTT or SRT subtitles.
+///
+/// Writes to `out`, or stdout when no output file is given. Without an
+/// explicit `format`, the `out` extension decides, falling back to WebVTT.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_export(file: &str, format: Option<SubtitleFormat>, out: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+    // Resolve file path (supports short format like "claude/session.cast")
+    let filepath = resolve_file_path(file, &config)?;
+    check_file_integrity(&filepath)?;
+    let markers = MarkerManager::list_markers(&filepath)?;
+
+    let format = format
+        .or_else(|| out.and_then(|out| SubtitleFormat::from_path(Path::new(out))))
+        .unwrap_or_default();
+    let subtitles = markers_to_subtitles(&markers, format);
+
+    match out {
+        Some(out) => {
+            std::fs::write(out, &subtitles)
+                .with_context(|| format!("Failed to write subtitles to {}", out))?;
+            println!(
+                "{}",
+                theme.primary_text(&format!("Exported {} marker(s) to {}", markers.len(), out))
+            );
+        }
+        None => print!("{}", subtitles),
+    }
+    Ok(())
+}
+
+/// Import markers into a cast file from a JSON or CSV file.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_import(file: &str, markers: &str, format: Option<ImportFormat>) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+    // Resolve file path (supports short format like "claude/session.cast")
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+    check_file_integrity(&filepath)?;
+
+    // Refuse to add markers to a file being actively recorded
+    agr::files::lock::check_not_locked(&filepath)?;
+
+    let content = std::fs::read_to_string(markers)
+        .with_context(|| format!("Failed to read markers file: {}", markers))?;
+    let format = format
+        .or_else(|| ImportFormat::from_path(Path::new(markers)))
+        .unwrap_or_default();
+    let imported = parse_markers(&content, format)
+        .with_context(|| format!("Failed to parse markers file: {}", markers))?;
+
+    let report = MarkerManager::import_markers(&filepath, &imported)?;
+    for marker in &report.out_of_range {
+        eprintln!(
+            "{} {}",
+            theme.error_text("Warning:"),
+            theme.secondary_text(&format!(
+                "skipped marker at {:.1}s past the end of the recording: \"{}\"",
+                marker.timestamp, marker.label
+            ))
+        );
+    }
+    println!(
+        "{}",
+        theme.primary_text(&format!(
+            "Imported {} marker(s) ({} duplicate(s) skipped)",
+            report.added, report.duplicates
+        ))
+    );
+    Ok(())
+}
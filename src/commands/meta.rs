@@ -0,0 +1,293 @@
+//! Meta subcommands handler
+//!
+//! Shows and edits the header metadata of a recording. Edits go through
+//! the asciicast writer, so only the header line changes and every event
+//! is written back unchanged.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{Local, TimeZone};
+
+use agr::asciicast::integrity::check_file_integrity;
+use agr::asciicast::{AsciicastFile, Header};
+use agr::files::resolve::resolve_file_path;
+use agr::theme::current_theme;
+use agr::Config;
+
+/// Header keys that `agr meta set` can edit.
+pub const EDITABLE_KEYS: &[&str] = &["title", "command", "idle_time_limit"];
+
+/// Set a header metadata field in a cast file.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_set(file: &str, key: &str, value: &str) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+
+    check_file_integrity(&filepath)?;
+    agr::files::lock::check_not_locked(&filepath)?;
+
+    set_in_file(&filepath, key, value)?;
+    let message = if value.is_empty() {
+        format!("Removed {}", key)
+    } else {
+        format!("Set {} = {}", key, value)
+    };
+    println!("{}", theme.primary_text(&message));
+    Ok(())
+}
+
+/// Show the header metadata of a cast file.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_show(file: &str) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+
+    check_file_integrity(&filepath)?;
+    let cast = AsciicastFile::parse(&filepath)
+        .with_context(|| format!("Failed to parse asciicast file: {}", filepath.display()))?;
+
+    for (key, value) in header_fields(&cast.header) {
+        println!(
+            "{} {}",
+            theme.secondary_text(&format!("{:<16}", format!("{}:", key))),
+            theme.primary_text(&value)
+        );
+    }
+    Ok(())
+}
+
+/// Parse the file, set one header field, and write it back.
+fn set_in_file(path: &Path, key: &str, value: &str) -> Result<()> {
+    let mut cast = AsciicastFile::parse(path)
+        .with_context(|| format!("Failed to parse asciicast file: {}", path.display()))?;
+    set_header_field(&mut cast.header, key, value)?;
+    cast.write(path)
+        .with_context(|| format!("Failed to write file: {}", path.display()))
+}
+
+/// Set an editable header field from its string value.
+///
+/// An empty value removes the field. `idle_time_limit` must be a number
+/// of seconds greater than 0.
+pub(crate) fn set_header_field(header: &mut Header, key: &str, value: &str) -> Result<()> {
+    let text = (!value.is_empty()).then(|| value.to_string());
+    match key {
+        "title" => header.title = text,
+        "command" => header.command = text,
+        "idle_time_limit" => {
+            header.idle_time_limit = match text {
+                Some(text) => Some(parse_idle_time_limit(&text)?),
+                None => None,
+            }
+        }
+        _ => anyhow::bail!(
+            "Unknown metadata key: '{}'\nSupported keys: {}",
+            key,
+            EDITABLE_KEYS.join(", ")
+        ),
+    }
+    Ok(())
+}
+
+fn parse_idle_time_limit(value: &str) -> Result<f64> {
+    let secs: f64 = value
+        .trim()
+        .parse()
+        .with_context(|| format!("idle_time_limit must be a number, got '{}'", value))?;
+    if !secs.is_finite() || secs <= 0.0 {
+        anyhow::bail!("idle_time_limit must be greater than 0, got '{}'", value);
+    }
+    Ok(secs)
+}
+
+/// The header's metadata as display `(key, value)` pairs, in header order.
+///
+/// Fields that are not set are omitted.
+fn header_fields(header: &Header) -> Vec<(&'static str, String)> {
+    let mut fields = vec![("version", header.version.to_string())];
+    let term = header.term.as_ref();
+    let cols = term.and_then(|t| t.cols).or(header.width);
+    let rows = term.and_then(|t| t.rows).or(header.height);
+    if let (Some(cols), Some(rows)) = (cols, rows) {
+        fields.push(("size", format!("{}x{}", cols, rows)));
+    }
+    if let Some(term_type) = term.and_then(|t| t.term_type.as_ref()) {
+        fields.push(("term", term_type.clone()));
+    }
+    if let Some(ts) = header.timestamp {
+        let value = match Local.timestamp_opt(ts, 0).single() {
+            Some(started) => format!("{} ({})", ts, started.format("%Y-%m-%d %H:%M:%S")),
+            None => ts.to_string(),
+        };
+        fields.push(("timestamp", value));
+    }
+    if let Some(duration) = header.duration {
+        fields.push(("duration", format!("{:.1}s", duration)));
+    }
+    if let Some(title) = &header.title {
+        fields.push(("title", title.clone()));
+    }
+    if let Some(command) = &header.command {
+        fields.push(("command", command.clone()));
+    }
+    if let Some(shell) = header.env.as_ref().and_then(|e| e.shell.as_ref()) {
+        fields.push(("env.SHELL", shell.clone()));
+    }
+    if let Some(limit) = header.idle_time_limit {
+        fields.push(("idle_time_limit", format!("{}s", limit)));
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAST: &str = concat!(
+        "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24,\"type\":\"xterm-256color\"},",
+        "\"timestamp\":1700000000,\"command\":\"claude\",",
+        "\"env\":{\"SHELL\":\"/bin/zsh\"},\"idle_time_limit\":5.0}\n",
+        "[0.5,\"o\",\"hello \\u001b[32mworld\\u001b[0m\\r\\n\"]\n",
+        "[0.25,\"i\",\"ls\\r\"]\n",
+        "[1.0,\"m\",\"checkpoint\"]\n",
+        "[0.125,\"r\",\"100x30\"]\n",
+    );
+
+    fn write_cast(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        let path = dir.path().join("session.cast");
+        std::fs::write(&path, CAST).unwrap();
+        path
+    }
+
+    fn event_lines(content: &str) -> Vec<&str> {
+        content.lines().skip(1).collect()
+    }
+
+    fn header() -> Header {
+        AsciicastFile::parse_str(CAST).unwrap().header
+    }
+
+    #[test]
+    fn sets_title() {
+        let mut header = header();
+        set_header_field(&mut header, "title", "My demo").unwrap();
+        assert_eq!(header.title.as_deref(), Some("My demo"));
+    }
+
+    #[test]
+    fn sets_command() {
+        let mut header = header();
+        set_header_field(&mut header, "command", "codex --full-auto").unwrap();
+        assert_eq!(header.command.as_deref(), Some("codex --full-auto"));
+    }
+
+    #[test]
+    fn sets_idle_time_limit() {
+        let mut header = header();
+        set_header_field(&mut header, "idle_time_limit", "2.5").unwrap();
+        assert_eq!(header.idle_time_limit, Some(2.5));
+    }
+
+    #[test]
+    fn empty_value_removes_field() {
+        let mut header = header();
+        set_header_field(&mut header, "command", "").unwrap();
+        set_header_field(&mut header, "idle_time_limit", "").unwrap();
+        assert_eq!(header.command, None);
+        assert_eq!(header.idle_time_limit, None);
+    }
+
+    #[test]
+    fn rejects_invalid_idle_time_limit() {
+        let mut header = header();
+        for value in ["fast", "0", "-1", "NaN", "inf"] {
+            let err = set_header_field(&mut header, "idle_time_limit", value).unwrap_err();
+            assert!(err.to_string().contains("idle_time_limit"), "{}", value);
+        }
+        assert_eq!(header.idle_time_limit, Some(5.0));
+    }
+
+    #[test]
+    fn rejects_unknown_key() {
+        let mut header = header();
+        let err = set_header_field(&mut header, "duration", "10").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Unknown metadata key: 'duration'"));
+        assert!(message.contains("title, command, idle_time_limit"));
+    }
+
+    #[test]
+    fn set_in_file_preserves_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_cast(&dir);
+
+        set_in_file(&path, "title", "My demo").unwrap();
+        set_in_file(&path, "idle_time_limit", "2").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(event_lines(&content), event_lines(CAST));
+        let cast = AsciicastFile::parse(&path).unwrap();
+        assert_eq!(cast.header.title.as_deref(), Some("My demo"));
+        assert_eq!(cast.header.idle_time_limit, Some(2.0));
+    }
+
+    #[test]
+    fn set_in_file_keeps_other_header_fields_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_cast(&dir);
+
+        set_in_file(&path, "title", "My demo").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let header_line = content.lines().next().unwrap();
+        assert_eq!(
+            header_line,
+            concat!(
+                "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24,\"type\":\"xterm-256color\"},",
+                "\"timestamp\":1700000000,\"title\":\"My demo\",\"command\":\"claude\",",
+                "\"env\":{\"SHELL\":\"/bin/zsh\"},\"idle_time_limit\":5.0}"
+            )
+        );
+    }
+
+    #[test]
+    fn set_in_file_leaves_file_untouched_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_cast(&dir);
+
+        assert!(set_in_file(&path, "theme", "dark").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), CAST);
+    }
+
+    #[test]
+    fn header_fields_lists_set_fields_in_order() {
+        let keys: Vec<&str> = header_fields(&header()).iter().map(|(k, _)| *k).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "version",
+                "size",
+                "term",
+                "timestamp",
+                "command",
+                "env.SHELL",
+                "idle_time_limit"
+            ]
+        );
+    }
+}
@@ -5,17 +5,31 @@
 
 pub mod agents;
 pub mod analyze;
+pub mod backfill_duration;
 pub mod cleanup;
 pub mod completions;
 pub mod config;
 pub mod copy;
+pub mod dedupe;
+pub mod doctor;
+pub mod export;
 pub mod list;
 pub mod marker;
+pub mod meta;
+pub mod note;
+pub mod pin;
 pub mod play;
 pub mod record;
+pub mod redact;
+pub mod rename;
 pub mod shell;
+pub mod snapshot;
+pub mod stats;
 pub mod status;
+pub mod summarize;
 pub mod transform;
+pub mod trash;
+pub mod validate;
 
 /// Truncate a string to a maximum length, adding ellipsis if needed.
 pub fn truncate_string(s: &str, max_len: usize) -> String {
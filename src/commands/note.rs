@@ -0,0 +1,56 @@
+//! Note command handlers
+//!
+//! Notes live in a Markdown sidecar next to the recording (see
+//! [`agr::files::notes`]); adding one appends to it, showing prints it.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::Local;
+
+use agr::files::notes::{append_note, notes_path_for, read_notes};
+use agr::files::resolve::resolve_file_path;
+use agr::theme::current_theme;
+use agr::Config;
+
+/// Append a note to a recording.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_add(file: &str, text: &str) -> Result<()> {
+    let filepath = resolve_existing(file)?;
+    append_note(&filepath, text, Local::now())?;
+    println!(
+        "{}",
+        current_theme().primary_text(&format!(
+            "Note added: {}",
+            notes_path_for(&filepath).display()
+        ))
+    );
+    Ok(())
+}
+
+/// Print the notes of a recording.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_show(file: &str) -> Result<()> {
+    let filepath = resolve_existing(file)?;
+    match read_notes(&filepath)? {
+        Some(notes) => print!("{}", notes),
+        None => println!(
+            "{}",
+            current_theme().secondary_text(&format!("No notes for {}", file))
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(not(tarpaulin_include))]
+fn resolve_existing(file: &str) -> Result<PathBuf> {
+    let config = Config::load()?;
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+    Ok(filepath)
+}
@@ -0,0 +1,52 @@
+//! Pin and unpin command handlers
+//!
+//! A pinned recording has an empty `.pinned` sidecar next to it and is
+//! never selected for deletion by `agr cleanup`.
+
+use anyhow::Result;
+
+use agr::files::resolve::resolve_file_path;
+use agr::storage::set_pinned;
+use agr::theme::current_theme;
+use agr::Config;
+
+/// Pin a recording so cleanup never deletes it.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_pin(file: &str) -> Result<()> {
+    set(file, true)
+}
+
+/// Unpin a recording so cleanup may delete it again.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_unpin(file: &str) -> Result<()> {
+    set(file, false)
+}
+
+#[cfg(not(tarpaulin_include))]
+fn set(file: &str, pinned: bool) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+
+    // Resolve file path (supports short format like "claude/session.cast")
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+
+    let changed = set_pinned(&filepath, pinned)?;
+    let name = filepath
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(file);
+    let message = match (pinned, changed) {
+        (true, true) => format!("Pinned: {}", name),
+        (true, false) => format!("Already pinned: {}", name),
+        (false, true) => format!("Unpinned: {}", name),
+        (false, false) => format!("Not pinned: {}", name),
+    };
+    println!("{}", theme.primary_text(&message));
+    Ok(())
+}
@@ -1,8 +1,12 @@
 //! Play command handler
 
+use std::path::PathBuf;
+
 use anyhow::Result;
 
-use agr::{play_session, Config};
+use agr::asciicast::{remote, CastSource};
+use agr::player::{play_source_native, LoopMode, PlayOptions};
+use agr::Config;
 
 use agr::asciicast::integrity::check_file_integrity;
 use agr::files::resolve::resolve_file_path;
@@ -11,12 +15,60 @@ use agr::files::resolve::resolve_file_path;
 ///
 /// Resolves the file path and invokes the native player for playback.
 /// Supports absolute paths, short format (agent/file.cast), and fuzzy matching.
+/// A `file` of `-` reads the recording from stdin, and an `http(s)://`
+/// URL is downloaded before playback.
+/// `loop_playback` / `loop_count` restart the recording when it ends, and
+/// `speed` overrides the configured `[player] default_speed`, and
+/// `record_to` saves the rendered viewport of the session as a new cast.
+///
+/// Malformed event lines are skipped with a warning. With `strict`, a file
+/// with corrupt lines goes through the interactive repair prompt and is
+/// not played unless repaired.
 #[cfg(not(tarpaulin_include))]
-pub fn handle(file: &str) -> Result<()> {
+pub fn handle(
+    file: &str,
+    loop_playback: bool,
+    loop_count: Option<u32>,
+    speed: Option<f64>,
+    strict: bool,
+    record_to: Option<&str>,
+) -> Result<()> {
     let config = Config::load()?;
 
+    let source = if CastSource::is_stdin_arg(file) {
+        CastSource::Stdin
+    } else if remote::is_url(file) {
+        CastSource::Url(file.to_string())
+    } else {
+        CastSource::File(resolve_cast_file(file, &config, strict)?)
+    };
+
+    // Play the session using the native player
+    let mut options = PlayOptions::from_config(&config)?;
+    options.loop_mode = LoopMode::from_flags(loop_playback, loop_count);
+    if let Some(speed) = speed {
+        options.speed = speed;
+    }
+    options.strict = strict;
+    options.record_to = record_to.map(PathBuf::from);
+    if let (Some(record_to), Some(path)) = (&options.record_to, source.path()) {
+        if record_to.canonicalize().ok().as_deref() == Some(path.canonicalize()?.as_path()) {
+            anyhow::bail!("--record-to must not be the recording being played");
+        }
+    }
+    let result = play_source_native(&source, &options)?;
+    println!("{}", result.message());
+    if let Some(record_to) = &options.record_to {
+        println!("Playback recorded to {}", record_to.display());
+    }
+    Ok(())
+}
+
+/// Resolve and check the recording file to play.
+#[cfg(not(tarpaulin_include))]
+fn resolve_cast_file(file: &str, config: &Config, strict: bool) -> Result<PathBuf> {
     // Resolve file path (supports short format like "claude/session.cast")
-    let filepath = resolve_file_path(file, &config)?;
+    let filepath = resolve_file_path(file, config)?;
     if !filepath.exists() {
         anyhow::bail!(
             "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
@@ -29,11 +81,9 @@ pub fn handle(file: &str) -> Result<()> {
         eprintln!("Warning: File does not have .cast extension");
     }
 
-    // Check for file corruption before playing
-    check_file_integrity(&filepath)?;
-
-    // Play the session using the native player
-    let result = play_session(&filepath)?;
-    println!("{}", result.message());
-    Ok(())
+    // In strict mode, check for file corruption before playing
+    if strict {
+        check_file_integrity(&filepath)?;
+    }
+    Ok(filepath)
 }
@@ -8,16 +8,60 @@ use agr::{Config, Recorder};
 ///
 /// Creates a new recording in ~/recorded_agent_sessions/<agent>/<timestamp>.cast.
 /// Warns if the agent is not in the configured list.
+/// With `output`, the recording is streamed to stdout (`-`) or written to
+/// that file, replacing it only once the session ended, instead of storage. With `status`, elapsed time and size are shown
+/// while recording. `cols`/`rows` fix the terminal size.
+/// Returns the agent's exit code so `agr` can exit with it.
 #[cfg(not(tarpaulin_include))]
-pub fn handle(agent: &str, name: Option<&str>, args: &[String]) -> Result<()> {
+pub fn handle(
+    agent: &str,
+    name: Option<&str>,
+    output: Option<&str>,
+    status: bool,
+    cols: Option<u16>,
+    rows: Option<u16>,
+    args: &[String],
+) -> Result<i32> {
     let config = Config::load()?;
 
+    // Diagnostics are best-effort; a bad log path must not block recording
+    if let Err(e) = agr::utils::log_file::init(config.recording.log_file.as_deref()) {
+        eprintln!("Warning: {:#}", e);
+    }
+
     if !config.is_agent_enabled(agent) {
         eprintln!("Warning: Agent '{}' is not in the configured list.", agent);
         eprintln!("Add it with: agr agents add {}", agent);
         eprintln!();
     }
 
-    let mut recorder = Recorder::new(config);
-    recorder.record(agent, name, args)
+    let mut recorder = Recorder::new(config)
+        .with_status(status)
+        .with_window_size(cols, rows);
+    match output {
+        Some(path) if is_stdout(path) => {
+            let stdout = std::io::stdout();
+            recorder.record_to(agent, args, &mut stdout.lock())
+        }
+        Some(path) => recorder.record_to_file(agent, args, std::path::Path::new(path)),
+        None => recorder.record(agent, name, args),
+    }
+}
+
+/// Whether an `--output` path means stdout.
+fn is_stdout(path: &str) -> bool {
+    path == "-" || path == "/dev/stdout"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dash_and_dev_stdout_mean_stdout() {
+        assert!(is_stdout("-"));
+        assert!(is_stdout("/dev/stdout"));
+        assert!(!is_stdout("session.cast"));
+        assert!(!is_stdout("./-"));
+    }
 }
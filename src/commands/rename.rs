@@ -0,0 +1,38 @@
+//! Rename command handler
+
+use anyhow::Result;
+
+use agr::files::resolve::resolve_file_path;
+use agr::{Config, StorageManager};
+
+/// Rename a recording within its agent directory.
+///
+/// Resolves the file path (absolute, short format, or fuzzy filename),
+/// then renames the recording and any companion sidecar files.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(file: &str, new_name: &str) -> Result<()> {
+    let config = Config::load()?;
+
+    // Resolve file path (supports short format like "claude/session.cast")
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+
+    let storage = StorageManager::new(config);
+    let new_path = storage.rename_session(&filepath, new_name)?;
+
+    let old_name = filepath
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(file);
+    let new_name = new_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(new_name);
+    println!("Renamed: {} -> {}", old_name, new_name);
+    Ok(())
+}
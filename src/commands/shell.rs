@@ -1,5 +1,8 @@
 //! Shell subcommands handler
 
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
 use anyhow::Result;
 
 use agr::theme::current_theme;
@@ -37,19 +40,30 @@ pub fn handle_install() -> Result<()> {
     let rc_file = agr::shell::detect_shell_rc()
         .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
 
-    // Install shell integration to RC file (script is embedded directly)
+    // Install shell integration to RC file (script is embedded directly);
+    // an existing section is updated in place
+    let existing = agr::shell::is_installed_in(&rc_file).unwrap_or(0) > 0;
     agr::shell::install(&rc_file)
         .map_err(|e| anyhow::anyhow!("Failed to install shell integration: {}", e))?;
     println!(
         "{}",
         theme.primary_text(&format!(
-            "Installed shell integration: {}",
+            "{} shell integration: {}",
+            if existing { "Updated" } else { "Installed" },
             rc_file.display()
         ))
     );
 
     // Install completions
-    install_completions()?;
+    if let Some(fish_path) = install_completions()? {
+        println!(
+            "{}",
+            theme.primary_text(&format!(
+                "Installed fish completions: {}",
+                fish_path.display()
+            ))
+        );
+    }
 
     println!();
     println!(
@@ -59,7 +73,13 @@ pub fn handle_install() -> Result<()> {
     println!(
         "{}",
         theme.primary_text(&format!(
-            "Restart your shell or run: source {}",
+            "Restart your shell or run: {} {}",
+            if agr::shell::install::detect_shell_from_rc(&rc_file) == agr::shell::Shell::PowerShell
+            {
+                "."
+            } else {
+                "source"
+            },
             rc_file.display()
         ))
     );
@@ -67,15 +87,18 @@ pub fn handle_install() -> Result<()> {
     Ok(())
 }
 
-/// Clean up old static completion files from previous installations.
+/// Clean up old static completion files and install fish completions.
 ///
-/// Completions are now embedded in the RC file section (generated dynamically),
-/// so old static completion files are no longer needed.
-pub(crate) fn install_completions() -> Result<()> {
+/// Bash and zsh completions are embedded in the RC file section (generated
+/// dynamically), so old static completion files are no longer needed. Fish
+/// autoloads completions from its own directory; returns the fish file
+/// path if one was written.
+pub(crate) fn install_completions() -> Result<Option<PathBuf>> {
     // Clean up old completion files - completions are now embedded in RC file
     agr::shell::cleanup_old_completions()
         .map_err(|e| anyhow::anyhow!("Failed to clean up old completions: {}", e))?;
-    Ok(())
+    agr::shell::install_fish_completions()
+        .map_err(|e| anyhow::anyhow!("Failed to install fish completions: {}", e))
 }
 
 /// Remove shell integration from .zshrc/.bashrc.
@@ -84,7 +107,7 @@ pub fn handle_uninstall() -> Result<()> {
     let theme = current_theme();
     // Find where shell integration is installed
     let rc_file = match agr::shell::find_installed_rc() {
-        Some(rc) => rc,
+        Some((rc, _)) => rc,
         None => {
             println!(
                 "{}",
@@ -129,7 +152,15 @@ pub fn handle_uninstall() -> Result<()> {
             }
         }
 
-        remove_completions()?;
+        if let Some(fish_path) = remove_completions()? {
+            println!(
+                "{}",
+                theme.primary_text(&format!(
+                    "Removed fish completions: {}",
+                    fish_path.display()
+                ))
+            );
+        }
 
         println!();
         println!(
@@ -153,16 +184,63 @@ pub fn handle_uninstall() -> Result<()> {
     Ok(())
 }
 
-/// Remove any leftover static completion files.
+/// Check shell integration for problems and offer to repair them.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_doctor(yes: bool) -> Result<()> {
+    let theme = current_theme();
+    let issues = agr::shell::diagnose()
+        .map_err(|e| anyhow::anyhow!("Failed to check shell integration: {}", e))?;
+
+    if issues.is_empty() {
+        println!(
+            "{}",
+            theme.primary_text("No shell integration problems found.")
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        theme.primary_text(&format!("Found {} problem(s):", issues.len()))
+    );
+    for issue in &issues {
+        println!("  - {} ({})", issue, issue.repair_hint());
+    }
+    println!();
+
+    if !yes {
+        print!("{}", theme.primary_text("Repair these problems? [y/N]: "));
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().lock().read_line(&mut input)?;
+        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("{}", theme.primary_text("No changes made."));
+            return Ok(());
+        }
+    }
+
+    for issue in &issues {
+        issue
+            .repair()
+            .map_err(|e| anyhow::anyhow!("Failed to repair '{}': {}", issue, e))?;
+    }
+    println!(
+        "{}",
+        theme.primary_text(&format!("Repaired {} problem(s).", issues.len()))
+    );
+    Ok(())
+}
+
+/// Remove any leftover static completion files and the fish completions.
 ///
-/// Completions are now embedded in the RC file section (generated dynamically),
-/// so old static completion files should be removed during uninstall.
-pub(crate) fn remove_completions() -> Result<()> {
-    // Clean up old completion files - this is the same as install
-    // since completions are now embedded in RC file
+/// Bash and zsh completions are embedded in the RC file section (generated
+/// dynamically), so old static completion files should be removed during
+/// uninstall. Returns the fish file path if one was removed.
+pub(crate) fn remove_completions() -> Result<Option<PathBuf>> {
     agr::shell::cleanup_old_completions()
         .map_err(|e| anyhow::anyhow!("Failed to clean up old completions: {}", e))?;
-    Ok(())
+    agr::shell::remove_fish_completions()
+        .map_err(|e| anyhow::anyhow!("Failed to remove fish completions: {}", e))
 }
 
 #[cfg(test)]
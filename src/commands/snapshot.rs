@@ -0,0 +1,55 @@
+//! Snapshot command handler
+
+use std::fs;
+
+use anyhow::{Context, Result};
+
+use agr::asciicast::{SnapshotAt, SnapshotFormat};
+use agr::files::resolve::resolve_file_path;
+use agr::{AsciicastFile, Config};
+
+/// Render the screen of a recording at a point in time.
+///
+/// Resolves the file path (absolute, short format, or fuzzy filename),
+/// replays the recording up to the requested time and writes the screen
+/// to stdout or to `out`. Malformed event lines are skipped with a warning
+/// on stderr unless `strict` is set.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(
+    file: &str,
+    at: SnapshotAt,
+    out: Option<&str>,
+    format: SnapshotFormat,
+    strict: bool,
+) -> Result<()> {
+    let config = Config::load()?;
+
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+
+    let cast = if strict {
+        AsciicastFile::parse(&filepath)?
+    } else {
+        let (cast, report) = AsciicastFile::parse_tolerant(&filepath)?;
+        if let Some(warning) = report.warning() {
+            eprintln!("Warning: {}", warning);
+        }
+        cast
+    };
+    let snapshot = cast.snapshot_at(at.resolve(cast.duration()), format);
+
+    match out {
+        Some(out) => {
+            fs::write(out, format!("{}\n", snapshot))
+                .with_context(|| format!("Failed to write snapshot to {}", out))?;
+            println!("Snapshot at {} written to {}", at, out);
+        }
+        None => println!("{}", snapshot),
+    }
+    Ok(())
+}
@@ -0,0 +1,341 @@
+//! Stats command handler
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Local, TimeZone, Weekday};
+
+use agr::storage::SessionInfo;
+use agr::theme::current_theme;
+use agr::{Config, StorageManager};
+
+/// Timing data for a single session, used for aggregation.
+#[derive(Debug, Clone)]
+pub struct SessionSample {
+    pub agent: String,
+    /// Recording length in seconds
+    pub duration_secs: f64,
+    /// When the recording started (header timestamp, or file mtime)
+    pub started: DateTime<Local>,
+}
+
+impl SessionSample {
+    /// Build a sample from a session and its header data.
+    ///
+    /// Falls back to the file's modification time if the header has no timestamp.
+    pub fn new(info: &SessionInfo, duration_secs: f64, timestamp: Option<i64>) -> Self {
+        let started = timestamp
+            .and_then(|ts| Local.timestamp_opt(ts, 0).single())
+            .unwrap_or(info.modified);
+        Self {
+            agent: info.agent.clone(),
+            duration_secs: duration_secs.max(0.0),
+            started,
+        }
+    }
+}
+
+/// Per-agent totals.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentUsage {
+    pub sessions: usize,
+    pub total_secs: f64,
+}
+
+/// Aggregate analytics over a set of sessions.
+#[derive(Debug, Clone, Default)]
+pub struct UsageStats {
+    pub session_count: usize,
+    pub total_secs: f64,
+    /// Busiest weekday and the number of sessions started on it
+    pub busiest_day: Option<(Weekday, usize)>,
+    pub by_agent: BTreeMap<String, AgentUsage>,
+    /// Sessions that could not be read (excluded from totals)
+    pub unreadable: usize,
+}
+
+impl UsageStats {
+    /// Aggregate session samples.
+    pub fn from_samples(samples: &[SessionSample]) -> Self {
+        let mut stats = Self {
+            session_count: samples.len(),
+            ..Self::default()
+        };
+
+        let mut per_day = [0usize; 7];
+        for sample in samples {
+            stats.total_secs += sample.duration_secs;
+            per_day[sample.started.weekday().num_days_from_monday() as usize] += 1;
+
+            let agent = stats.by_agent.entry(sample.agent.clone()).or_default();
+            agent.sessions += 1;
+            agent.total_secs += sample.duration_secs;
+        }
+
+        // Ties resolve to the earliest day of the week
+        const WEEK: [Weekday; 7] = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        for (day, &count) in WEEK.iter().zip(per_day.iter()) {
+            if count > stats.busiest_day.map_or(0, |(_, best)| best) {
+                stats.busiest_day = Some((*day, count));
+            }
+        }
+
+        stats
+    }
+
+    /// Average session length in seconds (0 if there are no sessions).
+    pub fn average_secs(&self) -> f64 {
+        if self.session_count == 0 {
+            0.0
+        } else {
+            self.total_secs / self.session_count as f64
+        }
+    }
+
+    /// Total recorded time in hours.
+    pub fn total_hours(&self) -> f64 {
+        self.total_secs / 3600.0
+    }
+
+    /// Format a summary for display
+    pub fn summary(&self) -> String {
+        if self.session_count == 0 {
+            return "No sessions recorded yet.".to_string();
+        }
+
+        let mut summary = format!(
+            "Recording Stats: {} session{}, {:.1} hours recorded\n   Average session: {}",
+            self.session_count,
+            if self.session_count == 1 { "" } else { "s" },
+            self.total_hours(),
+            format_duration(self.average_secs())
+        );
+
+        if let Some((day, count)) = self.busiest_day {
+            summary.push_str(&format!(
+                "\n   Busiest day: {} ({} session{})",
+                weekday_name(day),
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+
+        summary.push_str("\n   By agent:");
+        for (agent, usage) in &self.by_agent {
+            summary.push_str(&format!(
+                "\n      {}: {} session{}, {}",
+                agent,
+                usage.sessions,
+                if usage.sessions == 1 { "" } else { "s" },
+                format_duration(usage.total_secs)
+            ));
+        }
+
+        if self.unreadable > 0 {
+            summary.push_str(&format!(
+                "\n   Skipped: {} unreadable recording{}",
+                self.unreadable,
+                if self.unreadable == 1 { "" } else { "s" }
+            ));
+        }
+
+        summary
+    }
+}
+
+/// Format seconds as "1h 5m", "12m 30s", or "45s".
+fn format_duration(seconds: f64) -> String {
+    let total_secs = seconds.round() as u64;
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let secs = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Full English name of a weekday.
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Display aggregate analytics for recorded sessions.
+///
+/// Shows total recorded hours, average session length, busiest day of
+/// the week, and per-agent session counts and durations. Durations come
+/// from the storage index; `refresh` rebuilds it.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(refresh: bool) -> Result<()> {
+    let config = Config::load()?;
+    let storage = StorageManager::new(config);
+    let sessions = storage.list_sessions_indexed(None, refresh)?;
+
+    let mut samples = Vec::with_capacity(sessions.len());
+    let mut unreadable = 0;
+    for (info, entry) in &sessions {
+        match entry.duration {
+            Some(duration) => samples.push(SessionSample::new(info, duration, entry.timestamp)),
+            None => unreadable += 1,
+        }
+    }
+
+    let mut stats = UsageStats::from_samples(&samples);
+    stats.unreadable = unreadable;
+
+    let theme = current_theme();
+    println!("{}", theme.primary_text(&stats.summary()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn session(agent: &str, modified: DateTime<Local>) -> SessionInfo {
+        SessionInfo {
+            path: PathBuf::from(format!("/tmp/{}/s.cast", agent)),
+            agent: agent.to_string(),
+            filename: "s.cast".to_string(),
+            size: 100,
+            modified,
+            age_days: 0,
+            age_hours: 0,
+            age_minutes: 0,
+            pinned: false,
+            has_notes: false,
+        }
+    }
+
+    /// 2026-01-05 is a Monday.
+    fn day(offset: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 1, 5 + offset, 12, 0, 0)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn empty_samples_produce_zero_stats() {
+        let stats = UsageStats::from_samples(&[]);
+        assert_eq!(stats.session_count, 0);
+        assert_eq!(stats.average_secs(), 0.0);
+        assert!(stats.busiest_day.is_none());
+        assert_eq!(stats.summary(), "No sessions recorded yet.");
+    }
+
+    #[test]
+    fn totals_and_average() {
+        let samples = vec![
+            SessionSample::new(&session("claude", day(0)), 1800.0, None),
+            SessionSample::new(&session("claude", day(1)), 3600.0, None),
+            SessionSample::new(&session("codex", day(1)), 5400.0, None),
+        ];
+        let stats = UsageStats::from_samples(&samples);
+
+        assert_eq!(stats.session_count, 3);
+        assert_eq!(stats.total_secs, 10800.0);
+        assert_eq!(stats.total_hours(), 3.0);
+        assert_eq!(stats.average_secs(), 3600.0);
+    }
+
+    #[test]
+    fn per_agent_counts_and_durations() {
+        let samples = vec![
+            SessionSample::new(&session("claude", day(0)), 60.0, None),
+            SessionSample::new(&session("claude", day(0)), 40.0, None),
+            SessionSample::new(&session("gemini", day(2)), 10.0, None),
+        ];
+        let stats = UsageStats::from_samples(&samples);
+
+        assert_eq!(
+            stats.by_agent["claude"],
+            AgentUsage {
+                sessions: 2,
+                total_secs: 100.0
+            }
+        );
+        assert_eq!(
+            stats.by_agent["gemini"],
+            AgentUsage {
+                sessions: 1,
+                total_secs: 10.0
+            }
+        );
+    }
+
+    #[test]
+    fn busiest_day_counts_sessions_per_weekday() {
+        let samples = vec![
+            SessionSample::new(&session("claude", day(0)), 1.0, None),
+            SessionSample::new(&session("claude", day(2)), 1.0, None),
+            SessionSample::new(&session("claude", day(2)), 1.0, None),
+            SessionSample::new(&session("claude", day(7)), 1.0, None), // next Monday
+        ];
+        let stats = UsageStats::from_samples(&samples);
+        // Monday and Wednesday tie at 2; the earlier weekday wins
+        assert_eq!(stats.busiest_day, Some((Weekday::Mon, 2)));
+    }
+
+    #[test]
+    fn header_timestamp_takes_precedence_over_mtime() {
+        let info = session("claude", day(0)); // Monday mtime
+        let ts = day(3).timestamp(); // Thursday start
+        let sample = SessionSample::new(&info, 5.0, Some(ts));
+        assert_eq!(sample.started.weekday(), Weekday::Thu);
+    }
+
+    #[test]
+    fn negative_duration_is_clamped() {
+        let sample = SessionSample::new(&session("claude", day(0)), -5.0, None);
+        assert_eq!(sample.duration_secs, 0.0);
+    }
+
+    #[test]
+    fn summary_lists_agents_and_busiest_day() {
+        let samples = vec![
+            SessionSample::new(&session("codex", day(1)), 90.0, None),
+            SessionSample::new(&session("claude", day(1)), 3900.0, None),
+        ];
+        let mut stats = UsageStats::from_samples(&samples);
+        stats.unreadable = 1;
+        let summary = stats.summary();
+
+        assert!(summary.contains("2 sessions, 1.1 hours recorded"));
+        assert!(summary.contains("Average session: 33m 15s"));
+        assert!(summary.contains("Busiest day: Tuesday (2 sessions)"));
+        assert!(summary.contains("claude: 1 session, 1h 5m"));
+        assert!(summary.contains("codex: 1 session, 1m 30s"));
+        assert!(summary.contains("Skipped: 1 unreadable recording"));
+    }
+
+    #[test]
+    fn summary_pluralizes_a_single_session() {
+        let samples = vec![SessionSample::new(&session("claude", day(1)), 60.0, None)];
+        let summary = UsageStats::from_samples(&samples).summary();
+
+        assert!(summary.contains("Recording Stats: 1 session, 0.0 hours recorded"));
+        assert!(summary.contains("Busiest day: Tuesday (1 session)"));
+    }
+}
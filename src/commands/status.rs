@@ -1,20 +1,113 @@
 //! Status command handler
 
 use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use humansize::{format_size, BINARY};
 
+use agr::storage::{sparkline, StatsJson};
 use agr::theme::current_theme;
 use agr::{Config, StorageManager};
 
 /// Display storage statistics for recorded sessions.
 ///
-/// Shows total size, disk usage percentage, session count by agent,
-/// and age of the oldest recording.
+/// Shows total size, disk usage percentage, session count and size by
+/// agent, and age of the oldest recording. With `json`, prints
+/// [`StatsJson`] instead. Every run records today's total size in the
+/// size history; with `trend`, the growth over the last that many days is
+/// shown as a sparkline.
 #[cfg(not(tarpaulin_include))]
-pub fn handle() -> Result<()> {
+pub fn handle(json: bool, trend: Option<u32>) -> Result<()> {
     let config = Config::load()?;
     let storage = StorageManager::new(config);
     let stats = storage.get_stats()?;
+    let today = Local::now().date_naive();
+    // The history is best-effort; an unwritable file must not break status
+    let history = storage
+        .record_size_history(stats.total_size, today)
+        .unwrap_or_default();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&StatsJson::from(&stats))?
+        );
+        return Ok(());
+    }
     let theme = current_theme();
     println!("{}", theme.primary_text(&stats.summary()));
+    if let Some(days) = trend {
+        println!(
+            "{}",
+            theme.primary_text(&format_trend(&history, today, days))
+        );
+    }
     Ok(())
 }
+
+/// Describe the size history of the last `days` days (including today).
+fn format_trend(history: &[(NaiveDate, u64)], today: NaiveDate, days: u32) -> String {
+    let since = today - chrono::Duration::days(i64::from(days.max(1)) - 1);
+    let sizes: Vec<u64> = history
+        .iter()
+        .filter(|(date, _)| *date >= since && *date <= today)
+        .map(|(_, size)| *size)
+        .collect();
+
+    let (Some(&first), Some(&last)) = (sizes.first(), sizes.last()) else {
+        return format!("   Trend (last {} days): no data yet", days);
+    };
+    if sizes.len() < 2 {
+        return format!(
+            "   Trend (last {} days): one data point so far (run 'agr status' on other days to build a history)",
+            days
+        );
+    }
+    let change = if last >= first {
+        format!("+{}", format_size(last - first, BINARY))
+    } else {
+        format!("-{}", format_size(first - last, BINARY))
+    };
+    format!(
+        "   Trend (last {} days): {} {} -> {} ({})",
+        days,
+        sparkline(&sizes),
+        format_size(first, BINARY),
+        format_size(last, BINARY),
+        change
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn trend_shows_growth_within_window() {
+        let history = vec![
+            (date(1), 9999),
+            (date(8), 1024),
+            (date(9), 2048),
+            (date(10), 4096),
+        ];
+        assert_eq!(
+            format_trend(&history, date(10), 3),
+            "   Trend (last 3 days): ▁▃█ 1 KiB -> 4 KiB (+3 KiB)"
+        );
+    }
+
+    #[test]
+    fn trend_shows_shrinking_storage() {
+        let history = vec![(date(9), 4096), (date(10), 1024)];
+        assert!(format_trend(&history, date(10), 30).ends_with("(-3 KiB)"));
+    }
+
+    #[test]
+    fn trend_needs_two_points() {
+        assert!(format_trend(&[], date(10), 7).contains("no data yet"));
+        assert!(format_trend(&[(date(10), 1)], date(10), 7).contains("one data point"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Summarize command handler
+//!
+//! Uses the AnalyzerService to produce a short prose summary of a
+//! recording, then prints it and optionally stores it in the header title
+//! or a `<file>.cast.summary` sidecar file.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use agr::analyzer::AnalyzerService;
+use agr::asciicast::integrity::check_file_integrity;
+use agr::asciicast::AsciicastFile;
+use agr::files::resolve::resolve_file_path;
+use agr::theme::current_theme;
+use agr::Config;
+
+use agr::storage::{sidecar_path, SUMMARY_SUFFIX};
+
+use super::analyze::{agent_options, fall_back_to_installed_agent, parse_agent_type};
+
+/// Summarize a recording file using an AI agent.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(
+    file: &str,
+    agent_override: Option<&str>,
+    timeout: Option<u64>,
+    title: bool,
+    save: bool,
+) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+
+    // Resolve agent: CLI override > config > default
+    let resolved_agent = match agent_override {
+        Some(name) => name.to_string(),
+        None => config.resolve_analysis_agent(),
+    };
+    let agent = parse_agent_type(&resolved_agent)?;
+
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        anyhow::bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+
+    check_file_integrity(&filepath)?;
+    agr::files::lock::check_not_locked(&filepath)?;
+
+    // Build options: CLI > config > defaults
+    let options = agent_options(&config, &resolved_agent, agent, timeout, None);
+    let mut service = AnalyzerService::new(options);
+    fall_back_to_installed_agent(&mut service, &resolved_agent)?;
+
+    eprintln!("Summarizing with {}...", service.agent().command_name());
+    let summary = service.summarize(&filepath)?;
+    println!("{}", summary);
+
+    if title {
+        write_title(&filepath, &summary)?;
+        eprintln!(
+            "{}",
+            theme.primary_text("Summary stored as recording title")
+        );
+    }
+    if save {
+        let sidecar = summary_path(&filepath);
+        std::fs::write(&sidecar, format!("{}\n", summary))
+            .with_context(|| format!("Failed to write summary file: {}", sidecar.display()))?;
+        eprintln!(
+            "{}",
+            theme.primary_text(&format!("Summary written to: {}", sidecar.display()))
+        );
+    }
+
+    Ok(())
+}
+
+/// Store the summary, joined onto one line, as the header title.
+fn write_title(path: &Path, summary: &str) -> Result<()> {
+    let mut cast = AsciicastFile::parse(path)
+        .with_context(|| format!("Failed to parse asciicast file: {}", path.display()))?;
+    cast.header.title = Some(summary.split_whitespace().collect::<Vec<_>>().join(" "));
+    cast.write(path)
+        .with_context(|| format!("Failed to write file: {}", path.display()))
+}
+
+/// Sidecar path for a recording's summary (`session.cast.summary`).
+fn summary_path(path: &Path) -> PathBuf {
+    sidecar_path(path, SUMMARY_SUFFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_path_is_a_cast_sidecar() {
+        assert_eq!(
+            summary_path(Path::new("/rec/claude/session.cast")),
+            PathBuf::from("/rec/claude/session.cast.summary")
+        );
+    }
+
+    #[test]
+    fn write_title_joins_summary_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        std::fs::write(
+            &path,
+            "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n[0.5,\"o\",\"hi\"]\n",
+        )
+        .unwrap();
+
+        write_title(&path, "First sentence.\nSecond  sentence.").unwrap();
+
+        let cast = AsciicastFile::parse(&path).unwrap();
+        assert_eq!(
+            cast.header.title.as_deref(),
+            Some("First sentence. Second sentence.")
+        );
+        assert_eq!(cast.events.len(), 1);
+    }
+}
@@ -1,12 +1,16 @@
 //! Transform command handler for asciicast file transformations.
 //!
 //! Provides CLI support for applying transforms to asciicast recordings,
-//! such as silence removal.
+//! such as silence removal, and for previewing the analysis extraction.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
+use serde::Serialize;
 
+use agr::analyzer::{
+    AnalysisContent, ContentExtractor, ExtractionConfig, ExtractionLevel, ExtractionStats,
+};
 use agr::asciicast::{AsciicastFile, SilenceRemoval, Transform, DEFAULT_SILENCE_THRESHOLD};
 use agr::theme::current_theme;
 use agr::Config;
@@ -49,6 +53,109 @@ pub fn validate_threshold(threshold: f64) -> Result<()> {
     Ok(())
 }
 
+/// Machine-readable extraction preview for `agr transform --preview --json`.
+#[derive(Debug, Serialize)]
+pub struct PreviewJson<'a> {
+    pub total_duration: f64,
+    pub segments: usize,
+    /// Estimated tokens the analysis prompt content will use
+    pub total_tokens: usize,
+    pub events_dropped: usize,
+    pub stats: &'a ExtractionStats,
+}
+
+impl<'a> From<&'a AnalysisContent> for PreviewJson<'a> {
+    fn from(content: &'a AnalysisContent) -> Self {
+        Self {
+            total_duration: content.total_duration,
+            segments: content.segments.len(),
+            total_tokens: content.total_tokens,
+            events_dropped: content.stats.events_dropped(),
+            stats: &content.stats,
+        }
+    }
+}
+
+/// Extraction config matching what `agr analyze` uses for this config.
+pub fn preview_extraction_config(
+    config: &Config,
+    level: Option<ExtractionLevel>,
+) -> ExtractionConfig {
+    let extraction = ExtractionConfig::resolve(level, &config.analysis.extraction);
+    match config.analysis.idle_gap_secs {
+        Some(secs) => extraction.with_idle_gap(secs),
+        None => extraction,
+    }
+}
+
+/// Run the extraction pipeline on a recording without modifying it.
+pub fn extract_preview(path: &Path, extraction: ExtractionConfig) -> Result<AnalysisContent> {
+    let mut cast = AsciicastFile::parse(path)
+        .with_context(|| format!("Failed to parse asciicast file: {}", path.display()))?;
+    let (cols, rows) = cast.terminal_size();
+    Ok(ContentExtractor::new(extraction).extract(&mut cast.events, cols as usize, rows as usize))
+}
+
+/// Render the extraction preview: each segment's text, then the statistics.
+pub fn format_preview(content: &AnalysisContent) -> String {
+    let mut out = String::new();
+    for segment in &content.segments {
+        out.push_str(&format!(
+            "── [{:.1}s - {:.1}s] ~{} tokens ──\n",
+            segment.start_time, segment.end_time, segment.estimated_tokens
+        ));
+        out.push_str(segment.content.trim_end());
+        out.push_str("\n\n");
+    }
+
+    let stats = &content.stats;
+    out.push_str("Extraction Stats:\n");
+    out.push_str(&format!(
+        "  Bytes:            {} → {} ({:.1}% removed)\n",
+        stats.original_bytes,
+        stats.extracted_bytes,
+        stats.reduction_percentage() * 100.0
+    ));
+    out.push_str(&format!(
+        "  Events:           {} → {} ({} dropped)\n",
+        stats.events_processed,
+        stats.events_retained,
+        stats.events_dropped()
+    ));
+    out.push_str(&format!("  Segments:         {}\n", content.segments.len()));
+    out.push_str(&format!("  Estimated tokens: {}\n", content.total_tokens));
+    out
+}
+
+/// Handle the transform command with `--preview`.
+///
+/// Prints the content `agr analyze` would send to the agent plus the
+/// extraction statistics, without calling any backend.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_preview(file: &str, level: Option<ExtractionLevel>, json: bool) -> Result<()> {
+    let config = Config::load()?;
+
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+    check_file_integrity(&filepath)?;
+
+    let content = extract_preview(&filepath, preview_extraction_config(&config, level))?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&PreviewJson::from(&content))?
+        );
+    } else {
+        print!("{}", format_preview(&content));
+    }
+    Ok(())
+}
+
 /// Handle the transform command with silence removal.
 ///
 /// Applies silence removal transform to the specified file, either modifying
@@ -115,6 +222,7 @@ pub fn handle_remove_silence(
     // Apply the transform
     let mut transform = SilenceRemoval::new(effective_threshold);
     transform.transform(&mut cast.events);
+    cast.refresh_duration();
 
     // Calculate new duration
     let new_duration = cast.duration();
@@ -237,6 +345,81 @@ mod tests {
         assert!(err_msg.contains("finite") || err_msg.contains("Infinity"));
     }
 
+    // ========================================================================
+    // Extraction Preview Tests
+    // ========================================================================
+
+    fn write_cast(dir: &tempfile::TempDir, body: &str) -> PathBuf {
+        let path = dir.path().join("session.cast");
+        std::fs::write(
+            &path,
+            format!(
+                "{{\"version\":3,\"term\":{{\"cols\":80,\"rows\":24}}}}\n{}",
+                body
+            ),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn extract_preview_does_not_modify_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_cast(
+            &dir,
+            "[0.5,\"o\",\"$ cargo build\\r\\n\"]\n[0.5,\"o\",\"Finished\\r\\n\"]\n",
+        );
+        let before = std::fs::read_to_string(&path).unwrap();
+
+        let content = extract_preview(&path, ExtractionConfig::default()).unwrap();
+
+        assert!(content.text().contains("cargo build"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn format_preview_shows_segments_and_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_cast(&dir, "[0.5,\"o\",\"$ ls\\r\\nsrc\\r\\n\"]\n");
+        let content = extract_preview(&path, ExtractionConfig::default()).unwrap();
+
+        let text = format_preview(&content);
+
+        assert!(text.contains("$ ls"));
+        assert!(text.contains("Extraction Stats:"));
+        assert!(text.contains(&format!("Estimated tokens: {}", content.total_tokens)));
+    }
+
+    #[test]
+    fn preview_json_reports_dropped_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_cast(&dir, "[0.5,\"o\",\"hello\\r\\n\"]\n[0.1,\"o\",\"\"]\n");
+        let content = extract_preview(&path, ExtractionConfig::default()).unwrap();
+
+        let json = serde_json::to_value(PreviewJson::from(&content)).unwrap();
+
+        assert_eq!(json["stats"]["events_processed"], 2);
+        assert_eq!(json["events_dropped"], content.stats.events_dropped());
+        assert_eq!(json["total_tokens"], content.total_tokens);
+    }
+
+    #[test]
+    fn preview_extraction_config_applies_analysis_settings() {
+        let mut config = Config::default();
+        config.analysis.idle_gap_secs = Some(0);
+        config.analysis.extraction.window_size = Some(7);
+
+        let extraction = preview_extraction_config(&config, None);
+
+        assert_eq!(extraction.idle_gap_threshold, None);
+        assert_eq!(extraction.event_window_size, 7);
+        assert!(extraction.global_dedupe);
+
+        let light = preview_extraction_config(&config, Some(ExtractionLevel::Light));
+        assert!(!light.global_dedupe);
+        assert_eq!(light.event_window_size, 7);
+    }
+
     #[test]
     fn validate_threshold_rejects_negative_infinity() {
         let result = validate_threshold(f64::NEG_INFINITY);
@@ -0,0 +1,113 @@
+//! Trash subcommands handler
+
+use anyhow::Result;
+use chrono::{Local, TimeZone};
+use humansize::{format_size, BINARY};
+
+use agr::files::trash::{Trash, TrashEntry};
+use agr::theme::current_theme;
+
+/// List recordings in the trash, oldest first.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_list() -> Result<()> {
+    let theme = current_theme();
+    let trash = Trash::open_default()?;
+    let entries = trash.entries()?;
+
+    if entries.is_empty() {
+        println!("{}", theme.primary_text("The trash is empty."));
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        theme.primary_text(&format!("Trash ({}):", trash.dir().display()))
+    );
+    for entry in &entries {
+        println!(
+            "{}",
+            theme.primary_text(&format!("  {}", format_entry(entry)))
+        );
+    }
+    let total: u64 = entries.iter().map(|e| e.size).sum();
+    println!(
+        "{}",
+        theme.primary_text(&format!(
+            "{} recording(s), {}",
+            entries.len(),
+            format_size(total, BINARY)
+        ))
+    );
+    Ok(())
+}
+
+/// Move a trashed recording back to where it was deleted from.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_restore(name: &str) -> Result<()> {
+    let theme = current_theme();
+    let path = Trash::open_default()?.restore(name)?;
+    println!(
+        "{}",
+        theme.primary_text(&format!("Restored: {}", path.display()))
+    );
+    Ok(())
+}
+
+/// Permanently delete everything in the trash.
+#[cfg(not(tarpaulin_include))]
+pub fn handle_empty() -> Result<()> {
+    let theme = current_theme();
+    let (count, freed) = Trash::open_default()?.empty()?;
+    let message = if count == 0 {
+        "The trash is empty.".to_string()
+    } else {
+        format!(
+            "Deleted {} recording(s) from the trash (freed {}).",
+            count,
+            format_size(freed, BINARY)
+        )
+    };
+    println!("{}", theme.primary_text(&message));
+    Ok(())
+}
+
+/// One listing line: trash name, size, deletion time and original path.
+fn format_entry(entry: &TrashEntry) -> String {
+    let deleted = Local
+        .timestamp_opt(entry.deleted_at, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    format!(
+        "{}  {}  deleted {}  from {}",
+        entry.name,
+        format_size(entry.size, BINARY),
+        deleted,
+        entry.original_path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn format_entry_shows_name_size_and_origin() {
+        let deleted_at = Local
+            .with_ymd_and_hms(2025, 1, 19, 10, 30, 0)
+            .unwrap()
+            .timestamp();
+        let entry = TrashEntry {
+            name: "session-2.cast".to_string(),
+            original_path: PathBuf::from("/rec/claude/session.cast"),
+            deleted_at,
+            size: 2048,
+            sidecars: vec![],
+        };
+        assert_eq!(
+            format_entry(&entry),
+            "session-2.cast  2 KiB  deleted 2025-01-19 10:30  from /rec/claude/session.cast"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Validate command handler
+
+use anyhow::{bail, Result};
+
+use agr::asciicast::{validate_file, Problem, Severity, ValidationReport};
+use agr::files::resolve::resolve_file_path;
+use agr::theme::current_theme;
+use agr::Config;
+
+/// Report line for one problem, e.g. `[fail] line 3: time goes backwards`.
+fn problem_line(problem: &Problem) -> String {
+    let tag = match problem.severity {
+        Severity::Error => "[fail]",
+        Severity::Warning => "[warn]",
+    };
+    match problem.line {
+        Some(line) => format!("  {} line {}: {}", tag, line, problem.message),
+        None => format!("  {} {}", tag, problem.message),
+    }
+}
+
+/// One-line verdict for a report.
+fn summary(report: &ValidationReport) -> String {
+    let errors = report.count(Severity::Error);
+    let warnings = report.count(Severity::Warning);
+    if report.problems.is_empty() {
+        format!("No problems found ({} events)", report.event_count)
+    } else if report.is_playable() {
+        format!("Playable, {} warning(s)", warnings)
+    } else {
+        format!("Not playable: {} error(s), {} warning(s)", errors, warnings)
+    }
+}
+
+/// Check a recording and print every problem found.
+///
+/// Exits with status 1 if the recording is not playable.
+#[cfg(not(tarpaulin_include))]
+pub fn handle(file: &str) -> Result<()> {
+    let config = Config::load()?;
+    let theme = current_theme();
+
+    let filepath = resolve_file_path(file, &config)?;
+    if !filepath.exists() {
+        bail!(
+            "File not found: {}\nHint: Use format 'agent/file.cast'. Run 'agr list' to see available sessions.",
+            file
+        );
+    }
+
+    let report = validate_file(&filepath)?;
+    for problem in &report.problems {
+        let line = problem_line(problem);
+        let line = match problem.severity {
+            Severity::Error => theme.error_text(&line),
+            Severity::Warning => theme.primary_text(&line),
+        };
+        println!("{}", line);
+    }
+    if !report.problems.is_empty() {
+        println!();
+    }
+
+    let verdict = summary(&report);
+    if report.is_playable() {
+        println!("{}", theme.success_text(&verdict));
+        Ok(())
+    } else {
+        println!("{}", theme.error_text(&verdict));
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agr::asciicast::validate_str;
+
+    #[test]
+    fn problem_lines_show_severity_and_line() {
+        let problem = Problem {
+            severity: Severity::Error,
+            line: Some(3),
+            message: "time goes backwards (interval -0.500s)".to_string(),
+        };
+        assert_eq!(
+            problem_line(&problem),
+            "  [fail] line 3: time goes backwards (interval -0.500s)"
+        );
+    }
+
+    #[test]
+    fn summary_reflects_playability() {
+        let header = r#"{"version":3,"term":{"cols":80,"rows":24}}"#;
+        let clean = validate_str(&format!("{}\n[0.1,\"o\",\"hi\"]\n", header));
+        assert_eq!(summary(&clean), "No problems found (1 events)");
+
+        let warned = validate_str(&format!("{}\n[0.1,\"m\",\"\"]\n", header));
+        assert_eq!(summary(&warned), "Playable, 1 warning(s)");
+
+        let broken = validate_str(&format!("{}\n[-1,\"o\",\"hi\"]\n", header));
+        assert_eq!(summary(&broken), "Not playable: 1 error(s), 0 warning(s)");
+    }
+}
@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Analysis configuration for the `analyze` command.
 ///
@@ -27,6 +28,15 @@ pub struct AnalysisConfig {
     /// Auto-curate markers when count exceeds threshold
     #[serde(default = "default_analysis_curate")]
     pub curate: Option<bool>,
+    /// Collapse pauses longer than this many seconds (0 = keep all idle time)
+    #[serde(default = "default_analysis_idle_gap_secs")]
+    pub idle_gap_secs: Option<u64>,
+    /// File with a custom analysis prompt (`{content}`/`{duration}` placeholders)
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    /// Extraction tuning from `[analysis.extraction]`
+    #[serde(default)]
+    pub extraction: AnalysisExtractionConfig,
 }
 
 pub fn default_analysis_agent() -> Option<String> {
@@ -45,6 +55,10 @@ pub fn default_analysis_curate() -> Option<bool> {
     Some(true)
 }
 
+pub fn default_analysis_idle_gap_secs() -> Option<u64> {
+    Some(60)
+}
+
 impl Default for AnalysisConfig {
     fn default() -> Self {
         Self {
@@ -53,6 +67,9 @@ impl Default for AnalysisConfig {
             timeout: default_analysis_timeout(),
             fast: default_analysis_fast(),
             curate: default_analysis_curate(),
+            idle_gap_secs: default_analysis_idle_gap_secs(),
+            prompt_template: None,
+            extraction: AnalysisExtractionConfig::default(),
         }
     }
 }
@@ -89,15 +106,36 @@ impl AnalysisConfig {
                 return Err(format!("analysis.workers {} exceeds maximum (32)", w));
             }
         }
-        Ok(())
+        if self
+            .prompt_template
+            .as_deref()
+            .is_some_and(|p| p.trim().is_empty())
+        {
+            return Err("analysis.prompt_template must not be empty".to_string());
+        }
+        self.extraction.validate()
+    }
+
+    /// Path of the custom prompt template, with `~/` expanded.
+    pub fn prompt_template_path(&self) -> Option<PathBuf> {
+        let raw = self.prompt_template.as_deref()?.trim();
+        if let Some(stripped) = raw.strip_prefix("~/") {
+            if let Some(home) = dirs::home_dir() {
+                return Some(home.join(stripped));
+            }
+        }
+        Some(PathBuf::from(raw))
     }
 
     /// Validate per-agent configs (called from Config level where agents are accessible).
     pub fn validate_agent_configs(
         &self,
-        agent_configs: &HashMap<String, AgentAnalysisConfig>,
+        agent_configs: &HashMap<String, &AgentAnalysisConfig>,
     ) -> Result<(), String> {
-        for (name, agent_config) in agent_configs {
+        let mut names: Vec<&String> = agent_configs.keys().collect();
+        names.sort();
+        for name in names {
+            let agent_config = agent_configs[name];
             if let Some(budget) = agent_config.token_budget {
                 if budget < 1000 {
                     return Err(format!(
@@ -106,6 +144,90 @@ impl AnalysisConfig {
                     ));
                 }
             }
+            if let Some(0) = agent_config.timeout {
+                return Err(format!("agents.{}.timeout must be > 0", name));
+            }
+            if let Some(t) = agent_config.timeout {
+                if t > 3600 {
+                    return Err(format!(
+                        "agents.{}.timeout {} exceeds maximum (3600s)",
+                        name, t
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extraction tuning for the `[analysis.extraction]` sub-section.
+///
+/// Unset fields keep the built-in extraction defaults. Smaller windows and
+/// higher thresholds keep more content; larger windows and lower thresholds
+/// collapse more.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct AnalysisExtractionConfig {
+    /// Extraction preset ("light", "normal", "aggressive")
+    #[serde(default)]
+    pub level: Option<String>,
+    /// Number of recent lines/events checked for repeated status lines and redraws
+    #[serde(default)]
+    pub window_size: Option<usize>,
+    /// Similarity (0.0 to 1.0) at which consecutive lines are collapsed
+    #[serde(default)]
+    pub similarity_threshold: Option<f64>,
+    /// Maximum size of an output block before it is truncated (bytes)
+    #[serde(default)]
+    pub max_block_size: Option<usize>,
+    /// Fraction (0.0 to 1.0) of non-printable characters that marks output as binary
+    #[serde(default)]
+    pub binary_threshold: Option<f64>,
+}
+
+impl AnalysisExtractionConfig {
+    /// Validate configuration values.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(ref level) = self.level {
+            let valid = ["light", "normal", "aggressive"];
+            if !valid.contains(&level.as_str()) {
+                return Err(format!(
+                    "analysis.extraction.level '{}' is unknown. Valid: {}",
+                    level,
+                    valid.join(", ")
+                ));
+            }
+        }
+        if let Some(w) = self.window_size {
+            if !(1..=10_000).contains(&w) {
+                return Err(format!(
+                    "analysis.extraction.window_size {} must be between 1 and 10000",
+                    w
+                ));
+            }
+        }
+        if let Some(t) = self.similarity_threshold {
+            if !(0.0..=1.0).contains(&t) {
+                return Err(format!(
+                    "analysis.extraction.similarity_threshold {} must be between 0.0 and 1.0",
+                    t
+                ));
+            }
+        }
+        if let Some(size) = self.max_block_size {
+            if size < 256 {
+                return Err(format!(
+                    "analysis.extraction.max_block_size {} is below minimum (256)",
+                    size
+                ));
+            }
+        }
+        if let Some(t) = self.binary_threshold {
+            if !(0.0..=1.0).contains(&t) {
+                return Err(format!(
+                    "analysis.extraction.binary_threshold {} must be between 0.0 and 1.0",
+                    t
+                ));
+            }
         }
         Ok(())
     }
@@ -113,18 +235,27 @@ impl AnalysisConfig {
 
 /// Per-agent analysis configuration.
 ///
-/// Allows customizing extra CLI arguments and token budgets for individual agents.
-/// Each task type (analyze, curate, rename) can override the global `extra_args`.
+/// Allows customizing the model, extra CLI arguments and token budgets for
+/// individual agents. Each task type (analyze, curate, rename) can override
+/// the global `extra_args`.
+///
+/// `model` is passed with the agent's own flag (`--model` for Claude and
+/// Codex, `-m` for Gemini). If a task's effective extra args already select a
+/// model (`--model`, `--model=...` or `-m`), those win and `model` is not
+/// passed for that task.
 ///
 /// ```toml
 /// [agents.codex]
-/// extra_args = ["--model", "gpt-5.2-codex"]                # default for all tasks
-/// analyze_extra_args = ["--model", "gpt-5.2-codex"]        # override for analysis
-/// curate_extra_args = ["--model", "gpt-5.1-codex-mini"]    # override for curation
-/// rename_extra_args = ["--model", "gpt-5.1-codex-mini"]    # override for rename
+/// model = "gpt-5.2-codex"                                  # passed as --model
+/// extra_args = []                                          # default for all tasks
+/// curate_extra_args = ["--model", "gpt-5.1-codex-mini"]    # curation: wins over model
+/// rename_extra_args = ["--model", "gpt-5.1-codex-mini"]    # rename: wins over model
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AgentAnalysisConfig {
+    /// Model to use for all tasks (translated to the agent's model flag)
+    #[serde(default)]
+    pub model: Option<String>,
     /// Default extra CLI arguments for all tasks
     #[serde(default)]
     pub extra_args: Vec<String>,
@@ -140,6 +271,9 @@ pub struct AgentAnalysisConfig {
     /// Override the token budget for this agent
     #[serde(default)]
     pub token_budget: Option<usize>,
+    /// Timeout per chunk in seconds (overrides `[analysis].timeout`)
+    #[serde(default)]
+    pub timeout: Option<u64>,
 }
 
 impl AgentAnalysisConfig {
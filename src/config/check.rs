@@ -0,0 +1,240 @@
+//! Config validation against the local environment
+//!
+//! Unlike `validate()` on the individual sections (which only checks that
+//! values are well-formed), these checks look at the machine: whether the
+//! configured agents are installed and the storage directory is usable.
+
+use std::fs;
+use std::path::Path;
+
+use super::Config;
+
+/// Severity of a single check result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckLevel {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// One line of a config check report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckItem {
+    pub level: CheckLevel,
+    pub message: String,
+}
+
+/// Result of running all config checks.
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    pub items: Vec<CheckItem>,
+}
+
+impl CheckReport {
+    fn push(&mut self, level: CheckLevel, message: impl Into<String>) {
+        self.items.push(CheckItem {
+            level,
+            message: message.into(),
+        });
+    }
+
+    /// Whether any check failed hard.
+    pub fn has_errors(&self) -> bool {
+        self.count(CheckLevel::Error) > 0
+    }
+
+    /// Number of items at the given level.
+    pub fn count(&self, level: CheckLevel) -> usize {
+        self.items.iter().filter(|i| i.level == level).count()
+    }
+}
+
+/// Enabled agents whose command is not found, in config order.
+pub fn missing_agents(enabled: &[String], command_exists: impl Fn(&str) -> bool) -> Vec<String> {
+    enabled
+        .iter()
+        .filter(|agent| !command_exists(agent))
+        .cloned()
+        .collect()
+}
+
+/// Run all checks for `config`.
+///
+/// `command_exists` decides whether an agent binary is installed.
+/// Missing enabled agents are warnings; an unusable storage directory or an
+/// analysis agent that is required but not installed are errors.
+pub fn check_config(config: &Config, command_exists: impl Fn(&str) -> bool) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    // Agent availability
+    let missing = missing_agents(&config.agents.enabled, &command_exists);
+    if config.agents.enabled.is_empty() {
+        report.push(CheckLevel::Warning, "No agents enabled");
+    } else if missing.is_empty() {
+        report.push(
+            CheckLevel::Ok,
+            format!("Agents installed: {}", config.agents.enabled.join(", ")),
+        );
+    } else {
+        report.push(
+            CheckLevel::Warning,
+            format!("Agents not found on PATH: {}", missing.join(", ")),
+        );
+    }
+
+    // Storage directory
+    let storage_dir = config.storage_directory();
+    match check_writable(&storage_dir) {
+        Ok(()) => report.push(
+            CheckLevel::Ok,
+            format!("Storage directory writable: {}", storage_dir.display()),
+        ),
+        Err(e) => report.push(
+            CheckLevel::Error,
+            format!(
+                "Storage directory not writable: {} ({})",
+                storage_dir.display(),
+                e
+            ),
+        ),
+    }
+
+    // Analysis agent: required if set explicitly or used for auto-analysis
+    let analysis_agent = config.resolve_analysis_agent_with(&command_exists);
+    if command_exists(&analysis_agent) {
+        report.push(
+            CheckLevel::Ok,
+            format!("Analysis agent available: {}", analysis_agent),
+        );
+    } else {
+        let required = config.analysis.agent.is_some() || config.recording.auto_analyze;
+        report.push(
+            if required {
+                CheckLevel::Error
+            } else {
+                CheckLevel::Warning
+            },
+            format!("Analysis agent not found: {}", analysis_agent),
+        );
+    }
+
+    report
+}
+
+/// Check that `dir` exists (or can be created) and accepts new files.
+fn check_writable(dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let probe = dir.join(format!(".agr-write-check-{}", std::process::id()));
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn agents(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn config_in(dir: &Path, enabled: &[&str]) -> Config {
+        let mut config = Config::default();
+        config.storage.directory = dir.to_string_lossy().to_string();
+        config.agents.enabled = agents(enabled);
+        config
+    }
+
+    #[test]
+    fn missing_agents_lists_uninstalled_in_order() {
+        let installed = |cmd: &str| cmd == "codex";
+        let missing = missing_agents(&agents(&["claude", "codex", "gemini"]), installed);
+        assert_eq!(missing, vec!["claude", "gemini"]);
+    }
+
+    #[test]
+    fn missing_agents_empty_when_all_installed() {
+        assert!(missing_agents(&agents(&["claude", "codex"]), |_| true).is_empty());
+    }
+
+    #[test]
+    fn check_all_ok() {
+        let dir = TempDir::new().unwrap();
+        let report = check_config(&config_in(dir.path(), &["claude"]), |_| true);
+        assert!(!report.has_errors());
+        assert_eq!(report.count(CheckLevel::Warning), 0);
+        assert_eq!(report.count(CheckLevel::Ok), 3);
+    }
+
+    #[test]
+    fn missing_agents_are_warnings_not_errors() {
+        let dir = TempDir::new().unwrap();
+        let config = config_in(dir.path(), &["claude", "gemini"]);
+        let report = check_config(&config, |cmd| cmd == "claude");
+
+        assert!(!report.has_errors());
+        assert!(report
+            .items
+            .iter()
+            .any(|i| i.level == CheckLevel::Warning && i.message.contains("gemini")));
+    }
+
+    #[test]
+    fn analysis_agent_falls_back_to_installed_agent() {
+        let dir = TempDir::new().unwrap();
+        let config = config_in(dir.path(), &["claude", "codex"]);
+        let report = check_config(&config, |cmd| cmd == "codex");
+        assert!(report
+            .items
+            .iter()
+            .any(|i| i.level == CheckLevel::Ok && i.message == "Analysis agent available: codex"));
+    }
+
+    #[test]
+    fn explicit_missing_analysis_agent_is_error() {
+        let dir = TempDir::new().unwrap();
+        let mut config = config_in(dir.path(), &["claude"]);
+        config.analysis.agent = Some("gemini".to_string());
+        let report = check_config(&config, |cmd| cmd == "claude");
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn auto_analyze_without_any_agent_is_error() {
+        let dir = TempDir::new().unwrap();
+        let mut config = config_in(dir.path(), &["claude"]);
+        config.recording.auto_analyze = true;
+        let report = check_config(&config, |_| false);
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn no_analysis_agent_without_auto_analyze_is_warning() {
+        let dir = TempDir::new().unwrap();
+        let report = check_config(&config_in(dir.path(), &["claude"]), |_| false);
+        assert!(!report.has_errors());
+        assert_eq!(report.count(CheckLevel::Warning), 2);
+    }
+
+    #[test]
+    fn unwritable_storage_is_error() {
+        let dir = TempDir::new().unwrap();
+        // A regular file where the storage directory should be
+        let blocker = dir.path().join("not-a-dir");
+        fs::write(&blocker, "").unwrap();
+        let report = check_config(&config_in(&blocker, &["claude"]), |_| true);
+        assert!(report.has_errors());
+        assert!(report.items.iter().any(|i| i.level == CheckLevel::Error
+            && i.message.starts_with("Storage directory not writable")));
+    }
+
+    #[test]
+    fn no_enabled_agents_is_warning() {
+        let dir = TempDir::new().unwrap();
+        let report = check_config(&config_in(dir.path(), &[]), |_| true);
+        assert!(report
+            .items
+            .iter()
+            .any(|i| i.message == "No agents enabled"));
+    }
+}
@@ -0,0 +1,197 @@
+//! Structural comparison of a config against the defaults.
+//!
+//! Used by `agr config show --diff` to print only customized values and by
+//! `agr config show --annotated` to mark the values left at their default.
+//! Both configs are serialized to TOML values and compared leaf by leaf;
+//! arrays are compared as a whole.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use toml::Value;
+
+use super::types::Config;
+
+/// A config value that differs from its default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Dotted table path (`""` for top-level keys), e.g. `player.keys`
+    pub section: String,
+    /// Field name within the section
+    pub key: String,
+    /// The configured value, `None` if unset
+    pub value: Option<Value>,
+    /// The default value, `None` if unset by default
+    pub default: Option<Value>,
+}
+
+impl FieldDiff {
+    /// The full dotted path of the field, e.g. `storage.directory`.
+    pub fn path(&self) -> String {
+        if self.section.is_empty() {
+            self.key.clone()
+        } else {
+            format!("{}.{}", self.section, self.key)
+        }
+    }
+}
+
+/// List the fields of `config` that differ from [`Config::default`].
+///
+/// Fields are sorted by section and key, with fields only present in the
+/// default (unset by the config) listed after the ones the config sets.
+pub fn diff_from_default(config: &Config) -> Result<Vec<FieldDiff>> {
+    let actual = Value::try_from(config).context("Failed to serialize config")?;
+    let default = Value::try_from(Config::default()).context("Failed to serialize config")?;
+
+    let mut actual_leaves = Vec::new();
+    flatten("", &actual, &mut actual_leaves);
+    let mut default_leaves = Vec::new();
+    flatten("", &default, &mut default_leaves);
+
+    let find = |leaves: &[(String, String, Value)], section: &str, key: &str| {
+        leaves
+            .iter()
+            .find(|(s, k, _)| s == section && k == key)
+            .map(|(_, _, v)| v.clone())
+    };
+
+    let mut diffs = Vec::new();
+    for (section, key, value) in &actual_leaves {
+        let default = find(&default_leaves, section, key);
+        if default.as_ref() != Some(value) {
+            diffs.push(FieldDiff {
+                section: section.clone(),
+                key: key.clone(),
+                value: Some(value.clone()),
+                default,
+            });
+        }
+    }
+    for (section, key, value) in &default_leaves {
+        if find(&actual_leaves, section, key).is_none() {
+            diffs.push(FieldDiff {
+                section: section.clone(),
+                key: key.clone(),
+                value: None,
+                default: Some(value.clone()),
+            });
+        }
+    }
+    Ok(diffs)
+}
+
+/// Collect `(section, key, value)` for every non-table value.
+fn flatten(section: &str, value: &Value, out: &mut Vec<(String, String, Value)>) {
+    let Value::Table(table) = value else {
+        return;
+    };
+    for (key, value) in table {
+        if value.is_table() {
+            let child = if section.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", section, key)
+            };
+            flatten(&child, value, out);
+        } else {
+            out.push((section.to_string(), key.clone(), value.clone()));
+        }
+    }
+}
+
+/// Render diffs as TOML grouped by section, each with its default noted.
+///
+/// Returns an empty string when nothing differs.
+pub fn format_diff(diffs: &[FieldDiff]) -> String {
+    let mut sections: Vec<&str> = Vec::new();
+    for diff in diffs {
+        if !sections.contains(&diff.section.as_str()) {
+            sections.push(&diff.section);
+        }
+    }
+
+    let mut out = String::new();
+    for section in sections {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        if !section.is_empty() {
+            out.push_str(&format!("[{}]\n", section));
+        }
+        for diff in diffs.iter().filter(|d| d.section == section) {
+            let default = diff
+                .default
+                .as_ref()
+                .map_or_else(|| "unset".to_string(), inline);
+            match &diff.value {
+                Some(value) => out.push_str(&format!(
+                    "{} = {}  # default: {}\n",
+                    diff.key,
+                    inline(value),
+                    default
+                )),
+                None => out.push_str(&format!(
+                    "# {} is unset  # default: {}\n",
+                    diff.key, default
+                )),
+            }
+        }
+    }
+    out
+}
+
+/// Append `# (default)` to the lines of serialized config TOML whose value
+/// is not in `diffs`.
+///
+/// Comment lines and section headers are left alone, so this can run on
+/// the output of [`annotate_config`](super::docs::annotate_config).
+pub fn mark_defaults(toml_str: &str, diffs: &[FieldDiff]) -> String {
+    let customized: BTreeSet<(&str, &str)> = diffs
+        .iter()
+        .map(|d| (d.section.as_str(), d.key.as_str()))
+        .collect();
+
+    let mut result = String::new();
+    let mut section = String::new();
+    let mut in_array = false;
+
+    for line in toml_str.lines() {
+        let trimmed = line.trim();
+        result.push_str(line);
+
+        if in_array {
+            // Continuation of a multi-line array
+            in_array = !trimmed.starts_with(']');
+        } else if trimmed.starts_with('[') {
+            section = trimmed
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string();
+        } else if let Some((key, value)) = trimmed.split_once('=') {
+            if !trimmed.starts_with('#') {
+                let value = value.trim();
+                in_array = value.starts_with('[') && !value.ends_with(']');
+                if !customized.contains(&(section.as_str(), key.trim())) {
+                    result.push_str("  # (default)");
+                }
+            }
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Render a value on one line, as it would appear after `key = `.
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Array(items) => format!(
+            "[{}]",
+            items.iter().map(inline).collect::<Vec<_>>().join(", ")
+        ),
+        other => other.to_string(),
+    }
+}
@@ -56,6 +56,11 @@ pub const CONFIG_SECTIONS: &[SectionDoc] = &[
                 description: "Age threshold in days for cleanup suggestions",
                 default_display: "30",
             },
+            FieldDoc {
+                name: "use_trash",
+                description: "Move deleted sessions to ~/.local/share/agr/trash instead of removing them",
+                default_display: "true",
+            },
         ],
     },
     SectionDoc {
@@ -67,9 +72,14 @@ pub const CONFIG_SECTIONS: &[SectionDoc] = &[
                 description: "Automatically run AI analysis after recording ends",
                 default_display: "false",
             },
+            FieldDoc {
+                name: "auto_analyze_background",
+                description: "Run the auto-analysis in a detached process so the prompt returns immediately",
+                default_display: "false",
+            },
             FieldDoc {
                 name: "filename_template",
-                description: "Filename template using {directory}, {date}, {time} tags",
+                description: "Filename template using {directory}, {project}, {agent}, {date}, {time}, {pid}, {counter} tags",
                 default_display: "{directory}_{date}_{time}",
             },
             FieldDoc {
@@ -77,8 +87,80 @@ pub const CONFIG_SECTIONS: &[SectionDoc] = &[
                 description: "Maximum characters for directory component in filename",
                 default_display: "14",
             },
+            FieldDoc {
+                name: "log_file",
+                description: "Write recording diagnostics to this file (AGR_LOG overrides)",
+                default_display: "off",
+            },
+            FieldDoc {
+                name: "max_duration_secs",
+                description: "Auto-stop the recording after this many seconds",
+                default_display: "off",
+            },
+            FieldDoc {
+                name: "max_size_mb",
+                description: "Auto-stop the recording once the file exceeds this size in MB",
+                default_display: "off",
+            },
+            FieldDoc {
+                name: "capture_input",
+                description: "Record keystrokes as input events",
+                default_display: "false",
+            },
+            FieldDoc {
+                name: "no_input_echo",
+                description: "Drop captured keystrokes typed at password prompts or hidden input when recording ends",
+                default_display: "true",
+            },
+            FieldDoc {
+                name: "pre_hook",
+                description: "Shell command run before recording starts (AGR_CAST_PATH is set)",
+                default_display: "off",
+            },
+            FieldDoc {
+                name: "post_hook",
+                description: "Shell command run after recording ends, e.g. agr analyze \"$AGR_CAST_PATH\"",
+                default_display: "off",
+            },
+            FieldDoc {
+                name: "cols",
+                description: "Record with this terminal width instead of the current one",
+                default_display: "inherit",
+            },
+            FieldDoc {
+                name: "rows",
+                description: "Record with this terminal height instead of the current one",
+                default_display: "inherit",
+            },
         ],
     },
+    SectionDoc {
+        name: "player",
+        description: "Native player settings",
+        fields: &[FieldDoc {
+            name: "default_speed",
+            description: "Initial playback speed multiplier (0.1 to 16.0)",
+            default_display: "1.0",
+        }],
+    },
+    SectionDoc {
+        name: "tui",
+        description: "Interactive list and cleanup view settings",
+        fields: &[FieldDoc {
+            name: "preview_cache_size",
+            description: "Number of session previews kept in memory (minimum 5)",
+            default_display: "20",
+        }],
+    },
+    SectionDoc {
+        name: "theme",
+        description: "Color theme for the TUI, player and CLI output",
+        fields: &[FieldDoc {
+            name: "name",
+            description: "Built-in theme: claude_code, classic or ocean",
+            default_display: "claude_code",
+        }],
+    },
     SectionDoc {
         name: "analysis",
         description: "AI analysis settings",
@@ -108,6 +190,16 @@ pub const CONFIG_SECTIONS: &[SectionDoc] = &[
                 description: "Auto-curate markers when count exceeds threshold",
                 default_display: "true",
             },
+            FieldDoc {
+                name: "idle_gap_secs",
+                description: "Collapse pauses longer than this many seconds (0 = off)",
+                default_display: "60",
+            },
+            FieldDoc {
+                name: "prompt_template",
+                description: "File with a custom analysis prompt ({content} and {duration} required)",
+                default_display: "built-in",
+            },
         ],
     },
     SectionDoc {
@@ -126,10 +218,25 @@ pub const CONFIG_SECTIONS: &[SectionDoc] = &[
             },
         ],
     },
+    SectionDoc {
+        name: "redact",
+        description: "Secret redaction settings for agr redact",
+        fields: &[FieldDoc {
+            name: "patterns",
+            description: "Extra regex patterns to redact (built-ins always apply)",
+            default_display: "[]",
+        }],
+    },
 ];
 
 /// Per-agent sub-section fields (applies to [agents.claude], [agents.codex], etc.)
 pub const AGENT_FIELDS: &[FieldDoc] = &[
+    FieldDoc {
+        name: "model",
+        description:
+            "Model for all tasks (--model, or -m for Gemini); a model flag in extra_args wins",
+        default_display: "agent default",
+    },
     FieldDoc {
         name: "extra_args",
         description: "Default extra CLI arguments for all tasks",
@@ -155,6 +262,198 @@ pub const AGENT_FIELDS: &[FieldDoc] = &[
         description: "Override the token budget for this agent",
         default_display: "auto",
     },
+    FieldDoc {
+        name: "timeout",
+        description: "Timeout per chunk in seconds (overrides [analysis] timeout)",
+        default_display: "global",
+    },
+];
+
+/// Extraction tuning fields for the [analysis.extraction] sub-section
+pub const ANALYSIS_EXTRACTION_FIELDS: &[FieldDoc] = &[
+    FieldDoc {
+        name: "level",
+        description: "Extraction preset: light, normal, aggressive (--level overrides)",
+        default_display: r#""normal""#,
+    },
+    FieldDoc {
+        name: "window_size",
+        description: "Lines/events checked for repeated status lines and redraws (1-10000)",
+        default_display: "50",
+    },
+    FieldDoc {
+        name: "similarity_threshold",
+        description: "Similarity (0.0-1.0) at which consecutive lines are collapsed",
+        default_display: "0.8",
+    },
+    FieldDoc {
+        name: "max_block_size",
+        description: "Output block size in bytes before truncation (min 256)",
+        default_display: "8192",
+    },
+    FieldDoc {
+        name: "binary_threshold",
+        description: "Share (0.0-1.0) of non-printable characters that marks output as binary",
+        default_display: "0.3",
+    },
+];
+
+/// Color override fields for the [theme.custom] sub-section
+pub const THEME_CUSTOM_FIELDS: &[FieldDoc] = &[
+    FieldDoc {
+        name: "text_primary",
+        description: "Primary text color",
+        default_display: "preset",
+    },
+    FieldDoc {
+        name: "text_secondary",
+        description: "Secondary/dimmed text color",
+        default_display: "preset",
+    },
+    FieldDoc {
+        name: "accent",
+        description: "Accent color for highlights, keys and the logo",
+        default_display: "preset",
+    },
+    FieldDoc {
+        name: "error",
+        description: "Error color",
+        default_display: "preset",
+    },
+    FieldDoc {
+        name: "success",
+        description: "Success color",
+        default_display: "preset",
+    },
+    FieldDoc {
+        name: "marker",
+        description: "Marker color in the player progress bar",
+        default_display: "preset",
+    },
+    FieldDoc {
+        name: "background",
+        description: "Background color",
+        default_display: "preset",
+    },
+];
+
+/// Key binding fields for the [player.keys] sub-section (action name -> key string)
+pub const PLAYER_KEY_FIELDS: &[FieldDoc] = &[
+    FieldDoc {
+        name: "quit",
+        description: "Quit the player",
+        default_display: r#""q""#,
+    },
+    FieldDoc {
+        name: "pause",
+        description: "Pause / resume playback",
+        default_display: r#""space""#,
+    },
+    FieldDoc {
+        name: "help",
+        description: "Show the help overlay",
+        default_display: r#""?""#,
+    },
+    FieldDoc {
+        name: "viewport_mode",
+        description: "Toggle viewport mode",
+        default_display: r#""v""#,
+    },
+    FieldDoc {
+        name: "free_mode",
+        description: "Toggle free mode (line-by-line navigation)",
+        default_display: r#""f""#,
+    },
+    FieldDoc {
+        name: "speed_up",
+        description: "Increase playback speed",
+        default_display: r#""+""#,
+    },
+    FieldDoc {
+        name: "speed_down",
+        description: "Decrease playback speed",
+        default_display: r#""-""#,
+    },
+    FieldDoc {
+        name: "speed_1x",
+        description: "Set playback speed to 1x",
+        default_display: r#""1""#,
+    },
+    FieldDoc {
+        name: "speed_2x",
+        description: "Set playback speed to 2x",
+        default_display: r#""2""#,
+    },
+    FieldDoc {
+        name: "speed_4x",
+        description: "Set playback speed to 4x",
+        default_display: r#""3""#,
+    },
+    FieldDoc {
+        name: "resize",
+        description: "Resize terminal to recording size",
+        default_display: r#""r""#,
+    },
+    FieldDoc {
+        name: "next_marker",
+        description: "Jump to next marker",
+        default_display: r#""m""#,
+    },
+    FieldDoc {
+        name: "marker_list",
+        description: "Toggle the marker list panel",
+        default_display: r#""l""#,
+    },
+    FieldDoc {
+        name: "add_bookmark",
+        description: "Bookmark the current position",
+        default_display: r#""b""#,
+    },
+    FieldDoc {
+        name: "next_bookmark",
+        description: "Jump to next bookmark",
+        default_display: r#""n""#,
+    },
+    FieldDoc {
+        name: "prev_bookmark",
+        description: "Jump to previous bookmark",
+        default_display: r#""N""#,
+    },
+    FieldDoc {
+        name: "step_backward",
+        description: "Step back one event (while paused)",
+        default_display: r#"",""#,
+    },
+    FieldDoc {
+        name: "step_forward",
+        description: "Step forward one event (while paused)",
+        default_display: r#"".""#,
+    },
+    FieldDoc {
+        name: "seek_backward",
+        description: "Seek backward 5s",
+        default_display: r#""<""#,
+    },
+    FieldDoc {
+        name: "seek_forward",
+        description: "Seek forward 5s",
+        default_display: r#"">""#,
+    },
+    FieldDoc {
+        name: "seek_start",
+        description: "Go to start of recording",
+        default_display: r#""home""#,
+    },
+    FieldDoc {
+        name: "seek_end",
+        description: "Go to end of recording",
+        default_display: r#""end""#,
+    },
+    FieldDoc {
+        name: "goto_time",
+        description: "Prompt for a time (mm:ss or seconds) and jump there",
+        default_display: r#""g""#,
+    },
 ];
 
 /// Insert commented-out template lines for optional fields that are absent.
@@ -195,6 +494,12 @@ pub fn insert_optional_field_templates(toml_str: &str) -> String {
     let section_fields: Vec<(&str, &[FieldDoc])> = CONFIG_SECTIONS
         .iter()
         .map(|s| (s.name, s.fields))
+        .chain(std::iter::once(("player.keys", PLAYER_KEY_FIELDS)))
+        .chain(std::iter::once(("theme.custom", THEME_CUSTOM_FIELDS)))
+        .chain(std::iter::once((
+            "analysis.extraction",
+            ANALYSIS_EXTRACTION_FIELDS,
+        )))
         .chain(std::iter::once(("agents.claude", AGENT_FIELDS)))
         .chain(std::iter::once(("agents.codex", AGENT_FIELDS)))
         .chain(std::iter::once(("agents.gemini", AGENT_FIELDS)))
@@ -267,6 +572,15 @@ pub fn annotate_config(toml_str: &str) -> String {
             lookup.insert((section.name, field.name), field.description);
         }
     }
+    for field in PLAYER_KEY_FIELDS {
+        lookup.insert(("player.keys", field.name), field.description);
+    }
+    for field in THEME_CUSTOM_FIELDS {
+        lookup.insert(("theme.custom", field.name), field.description);
+    }
+    for field in ANALYSIS_EXTRACTION_FIELDS {
+        lookup.insert(("analysis.extraction", field.name), field.description);
+    }
     // Per-agent fields apply to agents.claude, agents.codex, agents.gemini
     for field in AGENT_FIELDS {
         lookup.insert(("agents.claude", field.name), field.description);
@@ -342,6 +656,45 @@ pub fn generate_config_markdown() -> String {
         md.push('\n');
     }
 
+    // Player key bindings
+    md.push_str("### [player.keys]\n\n");
+    md.push_str("Remap player keys. Values are key strings such as `\"x\"`, `\"space\"`, `\"ctrl+n\"` or `\"shift+left\"`. Unmapped actions keep their default keys.\n\n");
+    md.push_str("| Action | Default | Description |\n");
+    md.push_str("|--------|---------|-------------|\n");
+    for field in PLAYER_KEY_FIELDS {
+        md.push_str(&format!(
+            "| `{}` | `{}` | {} |\n",
+            field.name, field.default_display, field.description
+        ));
+    }
+    md.push('\n');
+
+    // Custom theme colors
+    md.push_str("### [theme.custom]\n\n");
+    md.push_str("Override individual colors of the `[theme]` preset. Values are color names (`\"cyan\"`, `\"light-green\"`), 256-color indexes (`\"208\"`) or hex values (`\"#00ff88\"`).\n\n");
+    md.push_str("| Option | Default | Description |\n");
+    md.push_str("|--------|---------|-------------|\n");
+    for field in THEME_CUSTOM_FIELDS {
+        md.push_str(&format!(
+            "| `{}` | `{}` | {} |\n",
+            field.name, field.default_display, field.description
+        ));
+    }
+    md.push('\n');
+
+    // Extraction tuning
+    md.push_str("### [analysis.extraction]\n\n");
+    md.push_str("Tune how aggressively recordings are condensed before analysis. Unset options keep the built-in defaults.\n\n");
+    md.push_str("| Option | Default | Description |\n");
+    md.push_str("|--------|---------|-------------|\n");
+    for field in ANALYSIS_EXTRACTION_FIELDS {
+        md.push_str(&format!(
+            "| `{}` | `{}` | {} |\n",
+            field.name, field.default_display, field.description
+        ));
+    }
+    md.push('\n');
+
     // Per-agent config
     md.push_str("### [agents.\\<name\\>]\n\n");
     md.push_str("Per-agent analysis configuration. Applies to `[agents.claude]`, `[agents.codex]`, `[agents.gemini]`.\n\n");
@@ -355,6 +708,16 @@ pub fn generate_config_markdown() -> String {
     }
     md.push('\n');
 
+    // Profiles
+    md.push_str("## Profiles\n\n");
+    md.push_str("Named profiles override parts of the config, e.g. for different machines. Select one with `--profile <name>` or the `AGR_PROFILE` environment variable; its values are merged over the base config and everything else is left unchanged.\n\n");
+    md.push_str("```toml\n");
+    md.push_str("[storage]\n");
+    md.push_str("directory = \"~/recorded_agent_sessions\"\n\n");
+    md.push_str("[profiles.server.storage]\n");
+    md.push_str("directory = \"/srv/recordings\"\n");
+    md.push_str("```\n\n");
+
     // Filename templates (static reference content)
     md.push_str("## Filename Templates\n\n");
     md.push_str("Customize how recording filenames are generated using template tags.\n\n");
@@ -362,13 +725,19 @@ pub fn generate_config_markdown() -> String {
     md.push_str("| Tag | Description | Example Output |\n");
     md.push_str("|-----|-------------|----------------|\n");
     md.push_str("| `{directory}` | Current working directory name | `my-project` |\n");
+    md.push_str("| `{project}` | Git repository root name (else directory) | `agr` |\n");
+    md.push_str("| `{agent}` | Agent being recorded | `claude` |\n");
     md.push_str("| `{date}` | Date in YYMMDD format | `260129` |\n");
     md.push_str(
         "| `{date:FORMAT}` | Date with custom strftime | `{date:%Y-%m-%d}` → `2026-01-29` |\n",
     );
     md.push_str("| `{time}` | Time in HHMM format | `1430` |\n");
     md.push_str("| `{time:FORMAT}` | Time with custom strftime | `{time:%H:%M}` → `14:30` |\n");
+    md.push_str("| `{pid}` | Process ID of the recorder | `48213` |\n");
+    md.push_str("| `{counter}` | Recordings in the agent folder plus one | `12` |\n");
     md.push('\n');
+    md.push_str("Unknown tags are rejected when the config is loaded. If a name is\n");
+    md.push_str("already taken, `-1`, `-2`, ... is appended instead of overwriting.\n\n");
 
     md.push_str("### Example Templates\n\n");
     md.push_str("```toml\n");
@@ -378,7 +747,9 @@ pub fn generate_config_markdown() -> String {
     md.push_str("# ISO date: project_2026-01-29.cast\n");
     md.push_str("filename_template = \"{directory}_{date:%Y-%m-%d}\"\n\n");
     md.push_str("# Simple timestamp: 260129-143022.cast\n");
-    md.push_str("filename_template = \"{date:%y%m%d}-{time:%H%M%S}\"\n");
+    md.push_str("filename_template = \"{date:%y%m%d}-{time:%H%M%S}\"\n\n");
+    md.push_str("# Numbered per agent: agr-claude-12.cast\n");
+    md.push_str("filename_template = \"{project}-{agent}-{counter}\"\n");
     md.push_str("```\n\n");
 
     md.push_str("### Sanitization\n\n");
@@ -462,6 +833,59 @@ mod tests {
             }
         }
         assert!(md.contains("[agents.\\<name\\>]"));
+        assert!(md.contains("### [player.keys]"));
+        assert!(md.contains("### [theme.custom]"));
+        assert!(md.contains("### [analysis.extraction]"));
+    }
+
+    #[test]
+    fn analysis_extraction_field_defaults_match_extractor() {
+        let defaults = crate::analyzer::ExtractionConfig::default();
+        let display = |name: &str| {
+            ANALYSIS_EXTRACTION_FIELDS
+                .iter()
+                .find(|f| f.name == name)
+                .unwrap()
+                .default_display
+        };
+        assert_eq!(
+            display("window_size"),
+            defaults.event_window_size.to_string()
+        );
+        assert_eq!(
+            display("similarity_threshold"),
+            defaults.similarity_threshold.to_string()
+        );
+        assert_eq!(
+            display("max_block_size"),
+            defaults.max_block_size.to_string()
+        );
+        assert_eq!(
+            display("binary_threshold"),
+            defaults.binary_threshold.to_string()
+        );
+    }
+
+    #[test]
+    fn player_key_fields_cover_all_actions() {
+        let documented: Vec<&str> = PLAYER_KEY_FIELDS.iter().map(|f| f.name).collect();
+        let actions: Vec<&str> = crate::player::Action::ALL
+            .iter()
+            .map(|a| a.name())
+            .collect();
+        assert_eq!(documented, actions);
+    }
+
+    #[test]
+    fn player_key_field_defaults_parse() {
+        let keys = PLAYER_KEY_FIELDS
+            .iter()
+            .map(|f| {
+                let key = f.default_display.trim_matches('"');
+                (f.name.to_string(), key.to_string())
+            })
+            .collect();
+        assert!(crate::player::KeyMap::from_config(&keys).is_ok());
     }
 
     #[test]
@@ -469,7 +893,17 @@ mod tests {
         let names: Vec<&str> = CONFIG_SECTIONS.iter().map(|s| s.name).collect();
         assert_eq!(
             names,
-            vec!["shell", "storage", "recording", "analysis", "agents"]
+            vec![
+                "shell",
+                "storage",
+                "recording",
+                "player",
+                "tui",
+                "theme",
+                "analysis",
+                "agents",
+                "redact"
+            ]
         );
     }
 }
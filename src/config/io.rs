@@ -2,54 +2,321 @@
 
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::files::atomic::write_atomic;
+
+use super::profile;
 use super::types::Config;
 
-/// Get the config file path (~/.config/agr/config.toml)
+/// Environment variable naming an alternate config file (set by `--config`).
+pub const CONFIG_ENV: &str = "AGR_CONFIG";
+
+/// Get the config file path: `AGR_CONFIG` if set, else ~/.config/agr/config.toml
 pub fn config_path() -> Result<PathBuf> {
+    if let Some(path) = config_override() {
+        return Ok(path);
+    }
     let config_dir = config_dir()?;
     Ok(config_dir.join("config.toml"))
 }
 
+/// The config file named by `AGR_CONFIG`, if set and non-empty (`~/` expanded).
+fn config_override() -> Option<PathBuf> {
+    let raw = std::env::var(CONFIG_ENV).ok()?;
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if let Some(stripped) = raw.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return Some(home.join(stripped));
+        }
+    }
+    Some(PathBuf::from(raw))
+}
+
 /// Get the config directory path (~/.config/agr)
 pub fn config_dir() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Could not determine home directory")?;
     Ok(home.join(".config").join("agr"))
 }
 
-/// Load configuration from file, or return defaults if not found
+/// Load configuration from file, or return defaults if not found.
+///
+/// The profile named by `AGR_PROFILE` (or `--profile`) is merged over the
+/// base values.
 pub fn load() -> Result<Config> {
-    let config_path = config_path()?;
+    load_with_profile(profile::active_profile().as_deref())
+}
 
-    if config_path.exists() {
-        let contents = fs::read_to_string(&config_path)
-            .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
-        let config: Config = toml::from_str(&contents)
-            .with_context(|| format!("Failed to parse config file: {:?}", config_path))?;
-        config
-            .analysis
-            .validate()
-            .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
-        Ok(config)
-    } else {
-        Ok(Config::default())
+/// Load configuration without applying any profile.
+///
+/// Use this when the config will be modified and saved, so that profile
+/// overrides are not written back into the base sections. See
+/// [`load_base_from`].
+pub fn load_base() -> Result<Config> {
+    load_base_from(&config_path()?)
+}
+
+/// Load the config at `path` for modifying it, without profile or backup.
+///
+/// Unlike [`load_from`], an invalid file is an error rather than a fallback
+/// to the backup: saving the backup would silently undo the user's edits.
+/// A missing file yields the defaults.
+pub fn load_base_from(path: &Path) -> Result<Config> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    read_and_parse(path, None).map_err(|e| {
+        anyhow::anyhow!(
+            "{:#}\nHint: Fix the config file (see 'agr config check') before changing it.",
+            e
+        )
+    })
+}
+
+/// Get the path of the last known good config (`config.toml.bak`).
+///
+/// [`save_to`] copies the current file here before replacing it, as long as
+/// the current file is valid.
+pub fn backup_path(config_path: &Path) -> PathBuf {
+    config_path.with_extension("toml.bak")
+}
+
+fn load_with_profile(profile_name: Option<&str>) -> Result<Config> {
+    load_from(&config_path()?, profile_name)
+}
+
+/// Load the config at `path`, falling back to its backup if it is invalid.
+///
+/// Only for reading: a config that will be saved must come from
+/// [`load_base_from`]. A missing file yields the defaults, unless a profile
+/// is requested.
+pub fn load_from(path: &Path, profile_name: Option<&str>) -> Result<Config> {
+    if !path.exists() {
+        return match profile_name {
+            Some(name) => anyhow::bail!("Unknown config profile: '{}' (no config file)", name),
+            None => Ok(Config::default()),
+        };
+    }
+
+    let err = match read_and_parse(path, profile_name) {
+        Ok(config) => return Ok(config),
+        Err(err) => err,
+    };
+    let backup = backup_path(path);
+    if backup.exists() {
+        if let Ok(config) = read_and_parse(&backup, profile_name) {
+            eprintln!(
+                "Warning: {:#}\nUsing the last known good config from {:?}. Fix the config file (see 'agr config check') to clear this warning.",
+                err, backup
+            );
+            return Ok(config);
+        }
     }
+    Err(err)
 }
 
-/// Save configuration to file
-pub fn save(config: &Config) -> Result<()> {
-    let config_path = config_path()?;
+fn read_and_parse(path: &Path, profile_name: Option<&str>) -> Result<Config> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {:?}", path))?;
+    parse(&contents, profile_name)
+        .with_context(|| format!("Failed to parse config file: {:?}", path))
+}
 
+/// Parse config TOML, merge the named profile over it, and validate.
+pub fn parse(contents: &str, profile_name: Option<&str>) -> Result<Config> {
+    let mut table: toml::Table = toml::from_str(contents)?;
+    if let Some(name) = profile_name {
+        profile::apply_profile(&mut table, name)?;
+    }
+    let config: Config = toml::Value::Table(table).try_into()?;
+    config
+        .analysis
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
+    config
+        .analysis
+        .validate_agent_configs(&config.agents.agent_configs_map())
+        .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
+    config
+        .recording
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
+    config
+        .player
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
+    config
+        .theme
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
+    config
+        .redact
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
+    Ok(config)
+}
+
+/// Save the config to `path` atomically, backing up the previous file.
+///
+/// The directory is created if needed. The previous file is copied to [`backup_path`] only if it is valid, so a
+/// corrupted file never replaces the last good backup. The new contents are
+/// written to a temp file in the same directory and renamed over `path`, so
+/// an interrupted save leaves either the old or the new file, never a mix.
+pub fn save_to(path: &Path, config: &Config) -> Result<()> {
     // Ensure config directory exists
-    if let Some(parent) = config_path.parent() {
+    if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create config directory: {:?}", parent))?;
     }
 
     let contents = toml::to_string_pretty(config).context("Failed to serialize config")?;
-    fs::write(&config_path, contents)
-        .with_context(|| format!("Failed to write config file: {:?}", config_path))?;
 
-    Ok(())
+    if read_and_parse(path, None).is_ok() {
+        let backup = backup_path(path);
+        fs::copy(path, &backup)
+            .with_context(|| format!("Failed to back up config to {:?}", backup))?;
+    }
+
+    write_atomic(path, contents.as_bytes())
+        .with_context(|| format!("Failed to write config file: {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_agents(agents: &[&str]) -> Config {
+        let mut config = Config::default();
+        config.agents.enabled = agents.iter().map(|a| a.to_string()).collect();
+        config
+    }
+
+    #[test]
+    fn save_replaces_file_without_leaving_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agr").join("config.toml");
+
+        save_to(&path, &config_with_agents(&["claude"])).unwrap();
+        save_to(&path, &config_with_agents(&["codex"])).unwrap();
+
+        let loaded = load_from(&path, None).unwrap();
+        assert_eq!(loaded.agents.enabled, vec!["codex"]);
+        let mut names: Vec<String> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["config.toml", "config.toml.bak"]);
+    }
+
+    #[test]
+    fn save_backs_up_previous_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        save_to(&path, &config_with_agents(&["claude"])).unwrap();
+        assert!(!backup_path(&path).exists());
+        save_to(&path, &config_with_agents(&["codex"])).unwrap();
+
+        let backup = read_and_parse(&backup_path(&path), None).unwrap();
+        assert_eq!(backup.agents.enabled, vec!["claude"]);
+    }
+
+    #[test]
+    fn truncated_config_recovers_from_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        save_to(&path, &config_with_agents(&["claude"])).unwrap();
+        save_to(&path, &config_with_agents(&["claude", "codex"])).unwrap();
+
+        // Simulate a write cut off half way
+        let contents = fs::read_to_string(&path).unwrap();
+        let cut = contents.find("directory = \"").unwrap() + 14;
+        fs::write(&path, &contents[..cut]).unwrap();
+        assert!(read_and_parse(&path, None).is_err());
+
+        let loaded = load_from(&path, None).unwrap();
+        assert_eq!(loaded.agents.enabled, vec!["claude"]);
+    }
+
+    #[test]
+    fn corrupted_config_does_not_replace_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        save_to(&path, &config_with_agents(&["claude"])).unwrap();
+        save_to(&path, &config_with_agents(&["codex"])).unwrap();
+        fs::write(&path, "[agents\n").unwrap();
+
+        save_to(&path, &config_with_agents(&["gemini"])).unwrap();
+
+        let backup = read_and_parse(&backup_path(&path), None).unwrap();
+        assert_eq!(backup.agents.enabled, vec!["claude"]);
+        assert_eq!(
+            load_from(&path, None).unwrap().agents.enabled,
+            vec!["gemini"]
+        );
+    }
+
+    #[test]
+    fn invalid_config_without_backup_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[agents\n").unwrap();
+
+        let err = load_from(&path, None).unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to parse config file"));
+    }
+
+    #[test]
+    fn load_base_does_not_fall_back_to_the_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        save_to(&path, &config_with_agents(&["claude"])).unwrap();
+        save_to(&path, &config_with_agents(&["claude", "codex"])).unwrap();
+        // A typo in a hand-edited config
+        fs::write(&path, "[agents]\nenabled = [\"claude\", \"codex\"\n").unwrap();
+
+        let err = load_base_from(&path).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(
+            message.contains("Failed to parse config file"),
+            "{}",
+            message
+        );
+        assert!(message.contains("agr config check"), "{}", message);
+        // Read-only loads still use the last known good config
+        assert_eq!(
+            load_from(&path, None).unwrap().agents.enabled,
+            vec!["claude"]
+        );
+    }
+
+    #[test]
+    fn load_base_of_missing_config_is_the_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = load_base_from(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(config.agents.enabled, Config::default().agents.enabled);
+    }
+
+    #[test]
+    fn invalid_backup_is_not_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[agents\n").unwrap();
+        fs::write(backup_path(&path), "also broken =").unwrap();
+
+        assert!(load_from(&path, None).is_err());
+    }
+
+    #[test]
+    fn missing_config_loads_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let config = load_from(&path, None).unwrap();
+        assert_eq!(config.agents.enabled, Config::default().agents.enabled);
+        assert!(load_from(&path, Some("work")).is_err());
+    }
 }
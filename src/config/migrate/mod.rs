@@ -97,6 +97,18 @@ pub fn migrate_config(existing_content: &str) -> Result<MigrateResult> {
         .analysis
         .validate()
         .map_err(|e| anyhow::anyhow!("Migrated config has invalid values: {}", e))?;
+    migrated_config
+        .player
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Migrated config has invalid values: {}", e))?;
+    migrated_config
+        .theme
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Migrated config has invalid values: {}", e))?;
+    migrated_config
+        .redact
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Migrated config has invalid values: {}", e))?;
 
     // Sort sections first, then insert commented-out templates for optional
     // fields (Option<None> defaults). Templates must be inserted AFTER sorting
@@ -122,8 +134,12 @@ const SECTION_ORDER: &[&str] = &[
     "shell",
     "storage",
     "recording",
+    "player",
+    "tui",
+    "theme",
     "analysis",
     "agents",
+    "redact",
 ];
 
 /// Reorder top-level sections to match `SECTION_ORDER`.
@@ -365,12 +381,15 @@ agent = "claude"
     fn empty_input_returns_full_default_config() {
         let result = migrate_config("").unwrap();
 
-        assert_eq!(result.sections_added.len(), 5);
+        assert_eq!(result.sections_added.len(), 9);
         assert!(result.sections_added.contains(&"storage".to_string()));
         assert!(result.sections_added.contains(&"agents".to_string()));
         assert!(result.sections_added.contains(&"shell".to_string()));
         assert!(result.sections_added.contains(&"recording".to_string()));
+        assert!(result.sections_added.contains(&"player".to_string()));
+        assert!(result.sections_added.contains(&"tui".to_string()));
         assert!(result.sections_added.contains(&"analysis".to_string()));
+        assert!(result.sections_added.contains(&"redact".to_string()));
 
         let parsed: Config = toml::from_str(&result.content).unwrap();
         assert_eq!(parsed.storage.directory, "~/recorded_agent_sessions");
@@ -387,10 +406,11 @@ directory = "~/my-recordings"
 
         let result = migrate_config(input).unwrap();
 
-        assert_eq!(result.sections_added.len(), 4);
+        assert_eq!(result.sections_added.len(), 8);
         assert!(result.sections_added.contains(&"agents".to_string()));
         assert!(result.sections_added.contains(&"shell".to_string()));
         assert!(result.sections_added.contains(&"recording".to_string()));
+        assert!(result.sections_added.contains(&"player".to_string()));
         assert!(result.sections_added.contains(&"analysis".to_string()));
         assert!(!result.sections_added.contains(&"storage".to_string()));
 
@@ -425,8 +445,12 @@ auto_analyze = true
 
         let result = migrate_config(input).unwrap();
 
-        assert_eq!(result.sections_added.len(), 1);
+        assert_eq!(result.sections_added.len(), 5);
+        assert!(result.sections_added.contains(&"player".to_string()));
+        assert!(result.sections_added.contains(&"tui".to_string()));
+        assert!(result.sections_added.contains(&"theme".to_string()));
         assert!(result.sections_added.contains(&"analysis".to_string()));
+        assert!(result.sections_added.contains(&"redact".to_string()));
 
         assert!(result
             .added_fields
@@ -495,6 +519,31 @@ foo = "bar"
         assert!(result.content.contains("foo"));
     }
 
+    #[test]
+    fn profiles_are_preserved() {
+        let input = r#"
+[storage]
+directory = "~/recordings"
+
+[profiles.server.storage]
+directory = "/srv/recordings"
+
+[profiles.server.recording]
+auto_analyze = true
+"#;
+
+        let result = migrate_config(input).unwrap();
+
+        assert!(result.content.contains("[profiles.server.storage]"));
+        assert!(result.content.contains("directory = \"/srv/recordings\""));
+        assert!(result.content.contains("[profiles.server.recording]"));
+        assert!(!result.sections_added.contains(&"profiles".to_string()));
+
+        let config = Config::from_toml(&result.content, Some("server")).unwrap();
+        assert_eq!(config.storage.directory, "/srv/recordings");
+        assert!(config.recording.auto_analyze);
+    }
+
     #[test]
     fn nested_dotted_tables_are_preserved() {
         let input = r#"
@@ -617,7 +666,7 @@ default_agent = "claude"
     #[test]
     fn whitespace_only_input_treated_as_empty() {
         let result = migrate_config("   \n\n   ").unwrap();
-        assert_eq!(result.sections_added.len(), 5);
+        assert_eq!(result.sections_added.len(), 9);
     }
 
     #[test]
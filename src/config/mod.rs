@@ -1,22 +1,34 @@
 //! Configuration management for ASR
 
 pub mod analysis;
+pub mod check;
+pub mod diff;
 pub mod docs;
 mod io;
 mod migrate;
+pub mod player;
+pub mod profile;
+pub mod redact;
+pub mod theme;
+pub mod tui;
 mod types;
 
 pub use analysis::*;
+pub use io::CONFIG_ENV;
 pub use migrate::*;
+pub use player::*;
+pub use redact::*;
+pub use theme::*;
+pub use tui::*;
 pub use types::*;
 
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::analyzer::backend::command_exists;
 
 impl Config {
-    /// Get the config file path (~/.config/agr/config.toml)
+    /// Get the config file path (`AGR_CONFIG` / `--config`, else ~/.config/agr/config.toml)
     pub fn config_path() -> Result<PathBuf> {
         io::config_path()
     }
@@ -26,14 +38,46 @@ impl Config {
         io::config_dir()
     }
 
-    /// Load configuration from file, or return defaults if not found
+    /// Load configuration from file, or return defaults if not found.
+    ///
+    /// Applies the active profile (`AGR_PROFILE` / `--profile`) over the base.
     pub fn load() -> Result<Self> {
         io::load()
     }
 
-    /// Save configuration to file
+    /// Load configuration without applying a profile (for load-modify-save).
+    pub fn load_base() -> Result<Self> {
+        io::load_base()
+    }
+
+    /// Load configuration from an explicit file, applying the active profile.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        io::load_from(path, profile::active_profile().as_deref())
+    }
+
+    /// Parse config TOML, optionally merging a named profile over it.
+    pub fn from_toml(contents: &str, profile: Option<&str>) -> Result<Self> {
+        io::parse(contents, profile)
+    }
+
+    /// Save configuration to file.
+    ///
+    /// Warns (without failing) about enabled agents that are not installed.
     pub fn save(&self) -> Result<()> {
-        io::save(self)
+        self.save_to(&Self::config_path()?)
+    }
+
+    /// Save configuration to an explicit file (see [`Config::save`]).
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        io::save_to(path, self)?;
+        let missing = self.missing_agents();
+        if !missing.is_empty() {
+            eprintln!(
+                "Warning: enabled agents not found on PATH: {}",
+                missing.join(", ")
+            );
+        }
+        Ok(())
     }
 
     /// Expand ~ in storage directory path
@@ -100,6 +144,11 @@ impl Config {
     /// 2. Auto-detect first available agent binary on PATH
     /// 3. Fall back to "claude"
     pub fn resolve_analysis_agent(&self) -> String {
+        self.resolve_analysis_agent_with(command_exists)
+    }
+
+    /// [`Self::resolve_analysis_agent`] with a custom binary lookup.
+    pub fn resolve_analysis_agent_with(&self, command_exists: impl Fn(&str) -> bool) -> String {
         // 1. Prefer explicit [analysis].agent
         if let Some(ref agent) = self.analysis.agent {
             return agent.clone();
@@ -120,10 +169,28 @@ impl Config {
         "claude".to_string()
     }
 
+    /// Enabled agents whose command is not installed.
+    pub fn missing_agents(&self) -> Vec<String> {
+        check::missing_agents(&self.agents.enabled, command_exists)
+    }
+
+    /// Check the config against this machine (agents, storage, analysis).
+    pub fn check(&self) -> check::CheckReport {
+        check::check_config(self, command_exists)
+    }
+
     /// Look up per-agent analysis configuration.
     ///
     /// Returns `None` if the agent name is not recognized.
     pub fn analysis_agent_config(&self, agent_name: &str) -> Option<&AgentAnalysisConfig> {
         self.agents.agent_config(agent_name)
     }
+
+    /// Timeout per chunk for an agent: `[agents.<name>].timeout`, else
+    /// `[analysis].timeout`.
+    pub fn analysis_timeout(&self, agent_name: &str) -> Option<u64> {
+        self.analysis_agent_config(agent_name)
+            .and_then(|agent| agent.timeout)
+            .or(self.analysis.timeout)
+    }
 }
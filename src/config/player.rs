@@ -0,0 +1,112 @@
+//! Player configuration types for `agr play`.
+//!
+//! Key strings are parsed by the player's key map; this module only holds
+//! the raw config values and validates them on load.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::player::{KeyMap, PlaybackState};
+
+/// Native player configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerConfig {
+    /// Initial playback speed multiplier
+    #[serde(default = "default_speed")]
+    pub default_speed: f64,
+    /// Key binding overrides: action name -> key string (e.g. `quit = "ctrl+q"`)
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+pub fn default_speed() -> f64 {
+    1.0
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        Self {
+            default_speed: default_speed(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl PlayerConfig {
+    /// Validate configuration values.
+    ///
+    /// Returns an error for an out-of-range speed, unknown action names,
+    /// or unparseable key strings.
+    pub fn validate(&self) -> Result<(), String> {
+        let (min, max) = (PlaybackState::MIN_SPEED, PlaybackState::MAX_SPEED);
+        if !(min..=max).contains(&self.default_speed) {
+            return Err(format!(
+                "player.default_speed {} must be between {} and {}",
+                self.default_speed, min, max
+            ));
+        }
+        self.key_map().map(|_| ())
+    }
+
+    /// Build the player key map from `[player.keys]`.
+    pub fn key_map(&self) -> Result<KeyMap, String> {
+        KeyMap::from_config(&self.keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_has_no_key_overrides() {
+        let config = PlayerConfig::default();
+        assert!(config.keys.is_empty());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn default_speed_is_1x() {
+        assert_eq!(PlayerConfig::default().default_speed, 1.0);
+        let config: PlayerConfig = toml::from_str("").unwrap();
+        assert_eq!(config.default_speed, 1.0);
+    }
+
+    #[test]
+    fn parses_default_speed() {
+        let config: PlayerConfig = toml::from_str("default_speed = 2.0\n").unwrap();
+        assert_eq!(config.default_speed, 2.0);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn out_of_range_default_speed_fails_validation() {
+        for speed in ["0.05", "16.5", "nan"] {
+            let config: PlayerConfig =
+                toml::from_str(&format!("default_speed = {}\n", speed)).unwrap();
+            let err = config.validate().unwrap_err();
+            assert!(err.contains("player.default_speed"), "{}", speed);
+        }
+    }
+
+    #[test]
+    fn parses_keys_table() {
+        let config: PlayerConfig =
+            toml::from_str("[keys]\nquit = \"ctrl+q\"\nnext_marker = \"j\"\n").unwrap();
+        assert_eq!(config.keys.get("quit").map(String::as_str), Some("ctrl+q"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_key_fails_validation() {
+        let config: PlayerConfig = toml::from_str("[keys]\npause = \"ctrl+bogus\"\n").unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("player.keys.pause"));
+    }
+
+    #[test]
+    fn unknown_action_fails_validation() {
+        let config: PlayerConfig = toml::from_str("[keys]\nteleport = \"t\"\n").unwrap();
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! Named config profiles
+//!
+//! A profile is a `[profiles.<name>]` table whose keys mirror the top-level
+//! config. When a profile is active its values are merged over the base
+//! config, so only the settings that differ need to be listed:
+//!
+//! ```toml
+//! [storage]
+//! directory = "~/recorded_agent_sessions"
+//!
+//! [profiles.server.storage]
+//! directory = "/srv/recordings"
+//! ```
+
+use anyhow::{Context, Result};
+use toml::{Table, Value};
+
+/// Environment variable that selects the active profile.
+pub const PROFILE_ENV: &str = "AGR_PROFILE";
+
+/// Top-level key holding the profile tables.
+const PROFILES_KEY: &str = "profiles";
+
+/// Name of the active profile from `AGR_PROFILE`, if set and non-empty.
+pub fn active_profile() -> Option<String> {
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Merge the named profile over the base config table.
+///
+/// Nested tables are merged key by key; any other value replaces the base
+/// value. The `profiles` table itself is left untouched so the config still
+/// round-trips on save. Fails if no profile with that name is defined.
+pub fn apply_profile(config: &mut Table, name: &str) -> Result<()> {
+    let overrides = config
+        .get(PROFILES_KEY)
+        .and_then(Value::as_table)
+        .and_then(|profiles| profiles.get(name))
+        .with_context(|| format!("Unknown config profile: '{}'", name))?
+        .as_table()
+        .with_context(|| format!("Config profile '{}' must be a table", name))?
+        .clone();
+
+    for (key, value) in overrides {
+        if key == PROFILES_KEY {
+            continue;
+        }
+        merge_value(config, key, value);
+    }
+
+    Ok(())
+}
+
+/// Recursively merge `value` into `table[key]`.
+fn merge_value(table: &mut Table, key: String, value: Value) {
+    match (table.get_mut(&key), value) {
+        (Some(Value::Table(base)), Value::Table(overrides)) => {
+            for (k, v) in overrides {
+                merge_value(base, k, v);
+            }
+        }
+        (_, value) => {
+            table.insert(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    const BASE: &str = r#"
+[storage]
+directory = "~/laptop-recordings"
+size_threshold_gb = 7.5
+age_threshold_days = 45
+
+[recording]
+auto_analyze = true
+
+[agents]
+enabled = ["claude", "codex"]
+
+[profiles.server.storage]
+directory = "/srv/recordings"
+
+[profiles.quiet.recording]
+auto_analyze = false
+"#;
+
+    fn load_with(name: Option<&str>) -> Result<Config> {
+        Config::from_toml(BASE, name)
+    }
+
+    #[test]
+    fn active_profile_overrides_storage_directory() {
+        let config = load_with(Some("server")).unwrap();
+        assert_eq!(config.storage.directory, "/srv/recordings");
+    }
+
+    #[test]
+    fn active_profile_keeps_unrelated_base_fields() {
+        let config = load_with(Some("server")).unwrap();
+        // Same section, not overridden
+        assert_eq!(config.storage.size_threshold_gb, 7.5);
+        assert_eq!(config.storage.age_threshold_days, 45);
+        // Other sections
+        assert!(config.recording.auto_analyze);
+        assert_eq!(config.agents.enabled, vec!["claude", "codex"]);
+    }
+
+    #[test]
+    fn no_profile_uses_base_values() {
+        let config = load_with(None).unwrap();
+        assert_eq!(config.storage.directory, "~/laptop-recordings");
+        assert!(config.recording.auto_analyze);
+    }
+
+    #[test]
+    fn profiles_are_independent() {
+        let config = load_with(Some("quiet")).unwrap();
+        assert!(!config.recording.auto_analyze);
+        assert_eq!(config.storage.directory, "~/laptop-recordings");
+    }
+
+    #[test]
+    fn profiles_are_kept_after_merge() {
+        let config = load_with(Some("server")).unwrap();
+        assert!(config.profiles.contains_key("server"));
+        assert!(config.profiles.contains_key("quiet"));
+    }
+
+    #[test]
+    fn unknown_profile_errors() {
+        let err = load_with(Some("missing")).unwrap_err();
+        assert!(format!("{:#}", err).contains("Unknown config profile: 'missing'"));
+    }
+
+    #[test]
+    fn non_table_profile_errors() {
+        let mut table: Table = toml::from_str("profiles = { broken = 1 }").unwrap();
+        assert!(apply_profile(&mut table, "broken").is_err());
+    }
+
+    #[test]
+    fn scalar_override_replaces_table_value() {
+        let mut table: Table = toml::from_str("[a]\nx = 1\n[profiles.p]\na = 5\n").unwrap();
+        apply_profile(&mut table, "p").unwrap();
+        assert_eq!(table["a"].as_integer(), Some(5));
+    }
+}
@@ -0,0 +1,56 @@
+//! Redaction configuration for `agr redact`.
+//!
+//! The built-in secret patterns live in `asciicast::redact`; this section
+//! only adds user patterns and checks that they compile.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Secret redaction configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RedactConfig {
+    /// Extra regular expressions to redact, on top of the built-in patterns
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl RedactConfig {
+    /// Validate configuration values.
+    ///
+    /// Returns an error naming the first pattern that is not a valid regex.
+    pub fn validate(&self) -> Result<(), String> {
+        for pattern in &self.patterns {
+            Regex::new(pattern)
+                .map_err(|e| format!("redact.patterns: invalid regex '{}': {}", pattern, e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_has_no_extra_patterns() {
+        let config: RedactConfig = toml::from_str("").unwrap();
+        assert!(config.patterns.is_empty());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn parses_patterns() {
+        let config: RedactConfig =
+            toml::from_str("patterns = ['internal-[0-9]{6}', 'xoxb-\\S+']\n").unwrap();
+        assert_eq!(config.patterns.len(), 2);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn invalid_pattern_fails_validation() {
+        let config: RedactConfig = toml::from_str("patterns = ['(unclosed']\n").unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("redact.patterns"));
+        assert!(err.contains("(unclosed"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Theme selection (`[theme]`) and custom colors (`[theme.custom]`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::theme::{parse_color, Theme};
+
+/// Color theme configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    /// Built-in preset name (see [`Theme::NAMES`])
+    #[serde(default = "default_theme_name")]
+    pub name: String,
+    /// Per-color overrides applied on top of the preset
+    #[serde(default)]
+    pub custom: CustomThemeConfig,
+}
+
+pub fn default_theme_name() -> String {
+    "claude_code".to_string()
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: default_theme_name(),
+            custom: CustomThemeConfig::default(),
+        }
+    }
+}
+
+/// Colors for the `[theme.custom]` sub-section.
+///
+/// Each value is a color name, 256-color index or `#rrggbb` hex value (see
+/// [`parse_color`]). Unset colors come from the preset named by `name`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomThemeConfig {
+    #[serde(default)]
+    pub text_primary: Option<String>,
+    #[serde(default)]
+    pub text_secondary: Option<String>,
+    #[serde(default)]
+    pub accent: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub success: Option<String>,
+    #[serde(default)]
+    pub marker: Option<String>,
+    #[serde(default)]
+    pub background: Option<String>,
+}
+
+impl CustomThemeConfig {
+    /// Field names paired with their values, in declaration order.
+    fn colors(&self) -> [(&'static str, &Option<String>); 7] {
+        [
+            ("text_primary", &self.text_primary),
+            ("text_secondary", &self.text_secondary),
+            ("accent", &self.accent),
+            ("error", &self.error),
+            ("success", &self.success),
+            ("marker", &self.marker),
+            ("background", &self.background),
+        ]
+    }
+
+    /// Apply the set colors to `theme`. Unparseable values are skipped.
+    pub fn apply(&self, theme: &mut Theme) {
+        let parsed = |value: &Option<String>| value.as_deref().and_then(parse_color);
+        let slots = [
+            (&mut theme.text_primary, parsed(&self.text_primary)),
+            (&mut theme.text_secondary, parsed(&self.text_secondary)),
+            (&mut theme.accent, parsed(&self.accent)),
+            (&mut theme.error, parsed(&self.error)),
+            (&mut theme.success, parsed(&self.success)),
+            (&mut theme.marker, parsed(&self.marker)),
+            (&mut theme.background, parsed(&self.background)),
+        ];
+        for (slot, color) in slots {
+            if let Some(color) = color {
+                *slot = color;
+            }
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// Validate configuration values.
+    ///
+    /// Returns an error if `name` is not a built-in preset or a custom
+    /// color does not parse.
+    pub fn validate(&self) -> Result<(), String> {
+        if Theme::by_name(&self.name).is_none() {
+            return Err(format!(
+                "theme.name: unknown theme '{}' (expected one of: {})",
+                self.name,
+                Theme::NAMES.join(", ")
+            ));
+        }
+        for (field, value) in self.custom.colors() {
+            if let Some(value) = value {
+                if parse_color(value).is_none() {
+                    return Err(format!(
+                        "theme.custom.{}: invalid color '{}' (use a name like \"cyan\" or hex like \"#00ff88\")",
+                        field, value
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The selected preset (the default theme if the name is unknown) with
+    /// the custom colors applied.
+    pub fn theme(&self) -> Theme {
+        let mut theme = Theme::by_name(&self.name).unwrap_or_default();
+        self.custom.apply(&mut theme);
+        theme
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn default_theme_is_claude_code() {
+        let config: ThemeConfig = toml::from_str("").unwrap();
+        assert_eq!(config.name, "claude_code");
+        assert!(config.validate().is_ok());
+        assert_eq!(config.theme().accent, Theme::claude_code().accent);
+    }
+
+    #[test]
+    fn parses_preset_name() {
+        let config: ThemeConfig = toml::from_str("name = \"ocean\"\n").unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.theme().text_primary, Color::Cyan);
+    }
+
+    #[test]
+    fn unknown_name_fails_validation() {
+        let config: ThemeConfig = toml::from_str("name = \"solarized\"\n").unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("theme.name"));
+        assert!(err.contains("classic"));
+        assert_eq!(config.theme().accent, Theme::default().accent);
+    }
+
+    #[test]
+    fn custom_colors_override_preset_and_inherit_the_rest() {
+        let config: ThemeConfig = toml::from_str(
+            "name = \"classic\"\n[custom]\naccent = \"#00ff88\"\nmarker = \"light-blue\"\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        let theme = config.theme();
+        let classic = Theme::classic();
+        assert_eq!(theme.accent, Color::Rgb(0, 255, 136));
+        assert_eq!(theme.marker, Color::LightBlue);
+        assert_eq!(theme.text_primary, classic.text_primary);
+        assert_eq!(theme.text_secondary, classic.text_secondary);
+        assert_eq!(theme.success, classic.success);
+        assert_eq!(theme.error, classic.error);
+    }
+
+    #[test]
+    fn invalid_custom_color_fails_validation() {
+        let config: ThemeConfig = toml::from_str("[custom]\nsuccess = \"#12345\"\n").unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("theme.custom.success"));
+        assert!(err.contains("#12345"));
+        // The preset color is kept
+        assert_eq!(config.theme().success, Theme::default().success);
+    }
+}
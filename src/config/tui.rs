@@ -0,0 +1,64 @@
+//! TUI configuration for the interactive `agr ls` / `agr cleanup` views.
+
+use serde::{Deserialize, Serialize};
+
+/// Smallest preview cache size used, whatever the config says.
+///
+/// The list prefetches the previews around the selection, so a cache
+/// smaller than that window would evict entries it is about to show.
+pub const MIN_PREVIEW_CACHE_SIZE: usize = 5;
+
+/// Interactive TUI configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TuiConfig {
+    /// Number of session previews kept in memory
+    #[serde(default = "default_preview_cache_size")]
+    pub preview_cache_size: usize,
+}
+
+pub fn default_preview_cache_size() -> usize {
+    20
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            preview_cache_size: default_preview_cache_size(),
+        }
+    }
+}
+
+impl TuiConfig {
+    /// Preview cache capacity, raised to [`MIN_PREVIEW_CACHE_SIZE`] if lower.
+    pub fn preview_cache_capacity(&self) -> usize {
+        self.preview_cache_size.max(MIN_PREVIEW_CACHE_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_preview_cache_size_is_20() {
+        let config: TuiConfig = toml::from_str("").unwrap();
+        assert_eq!(config.preview_cache_size, 20);
+        assert_eq!(config.preview_cache_capacity(), 20);
+    }
+
+    #[test]
+    fn parses_preview_cache_size() {
+        let config: TuiConfig = toml::from_str("preview_cache_size = 64\n").unwrap();
+        assert_eq!(config.preview_cache_capacity(), 64);
+    }
+
+    #[test]
+    fn small_preview_cache_size_is_raised_to_minimum() {
+        for size in [0, 1, MIN_PREVIEW_CACHE_SIZE - 1] {
+            let config = TuiConfig {
+                preview_cache_size: size,
+            };
+            assert_eq!(config.preview_cache_capacity(), MIN_PREVIEW_CACHE_SIZE);
+        }
+    }
+}
@@ -1,10 +1,14 @@
 //! Configuration type definitions and defaults
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use super::analysis::{AgentAnalysisConfig, AnalysisConfig};
+use super::player::PlayerConfig;
+use super::redact::RedactConfig;
+use super::theme::ThemeConfig;
+use super::tui::TuiConfig;
 use crate::config::migrate::CURRENT_VERSION;
 
 /// Main configuration structure
@@ -20,9 +24,20 @@ pub struct Config {
     #[serde(default)]
     pub recording: RecordingConfig,
     #[serde(default)]
+    pub player: PlayerConfig,
+    #[serde(default)]
+    pub tui: TuiConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
     pub analysis: AnalysisConfig,
     #[serde(default)]
     pub agents: AgentsConfig,
+    #[serde(default)]
+    pub redact: RedactConfig,
+    /// Named `[profiles.<name>]` override tables (see `config::profile`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, toml::Table>,
 }
 
 fn default_config_version() -> u32 {
@@ -36,8 +51,13 @@ impl Default for Config {
             shell: ShellConfig::default(),
             storage: StorageConfig::default(),
             recording: RecordingConfig::default(),
+            player: PlayerConfig::default(),
+            tui: TuiConfig::default(),
+            theme: ThemeConfig::default(),
             analysis: AnalysisConfig::default(),
             agents: AgentsConfig::default(),
+            redact: RedactConfig::default(),
+            profiles: BTreeMap::new(),
         }
     }
 }
@@ -72,12 +92,43 @@ pub struct RecordingConfig {
     /// Whether to automatically analyze the recording after session ends
     #[serde(default)]
     pub auto_analyze: bool,
-    /// Filename template using tags like {directory}, {date}, {time}
+    /// Run the auto-analysis as a detached `agr analyze` process instead of waiting for it
+    #[serde(default)]
+    pub auto_analyze_background: bool,
+    /// Filename template using tags like {directory}, {date}, {time}, {agent}, {counter}
     #[serde(default = "default_filename_template")]
     pub filename_template: String,
     /// Maximum length for directory component in filename
     #[serde(default = "default_directory_max_length")]
     pub directory_max_length: usize,
+    /// Diagnostic log file for recording sessions (None = logging off)
+    #[serde(default)]
+    pub log_file: Option<String>,
+    /// Stop the recording after this many seconds (None = no limit)
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>,
+    /// Stop the recording once the cast file exceeds this many MB (None = no limit)
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+    /// Record keystrokes as input events (asciinema --capture-input)
+    #[serde(default)]
+    pub capture_input: bool,
+    /// Drop captured keystrokes typed at password prompts or into hidden text
+    /// from the finished recording (the live cast holds them until it ends)
+    #[serde(default = "default_no_input_echo")]
+    pub no_input_echo: bool,
+    /// Shell command run before the recording starts
+    #[serde(default)]
+    pub pre_hook: Option<String>,
+    /// Shell command run after the recording ends (cast path in `AGR_CAST_PATH`)
+    #[serde(default)]
+    pub post_hook: Option<String>,
+    /// Fixed terminal width for recordings (None = inherit the terminal's)
+    #[serde(default)]
+    pub cols: Option<u16>,
+    /// Fixed terminal height for recordings (None = inherit the terminal's)
+    #[serde(default)]
+    pub rows: Option<u16>,
 }
 
 pub fn default_filename_template() -> String {
@@ -88,16 +139,51 @@ pub fn default_directory_max_length() -> usize {
     14
 }
 
+pub fn default_no_input_echo() -> bool {
+    true
+}
+
 impl Default for RecordingConfig {
     fn default() -> Self {
         Self {
             auto_analyze: false,
+            auto_analyze_background: false,
             filename_template: default_filename_template(),
             directory_max_length: default_directory_max_length(),
+            log_file: None,
+            max_duration_secs: None,
+            max_size_mb: None,
+            capture_input: false,
+            no_input_echo: default_no_input_echo(),
+            pre_hook: None,
+            post_hook: None,
+            cols: None,
+            rows: None,
         }
     }
 }
 
+impl RecordingConfig {
+    /// Validate configuration values.
+    ///
+    /// Returns an error if `filename_template` does not parse, e.g. because
+    /// it uses an unknown tag, or if `cols`/`rows` is 0.
+    pub fn validate(&self) -> Result<(), String> {
+        crate::files::filename::Template::parse(&self.filename_template).map_err(|e| {
+            format!(
+                "recording.filename_template: {} (in '{}')",
+                e, self.filename_template
+            )
+        })?;
+        for (name, value) in [("cols", self.cols), ("rows", self.rows)] {
+            if value == Some(0) {
+                return Err(format!("recording.{} must be greater than 0", name));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Storage configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
@@ -107,6 +193,9 @@ pub struct StorageConfig {
     pub size_threshold_gb: f64,
     #[serde(default = "default_age_threshold")]
     pub age_threshold_days: u32,
+    /// Move deleted sessions to the trash instead of removing them
+    #[serde(default = "default_use_trash")]
+    pub use_trash: bool,
 }
 
 pub fn default_directory() -> String {
@@ -121,12 +210,17 @@ pub fn default_age_threshold() -> u32 {
     30
 }
 
+pub fn default_use_trash() -> bool {
+    true
+}
+
 impl Default for StorageConfig {
     fn default() -> Self {
         Self {
             directory: default_directory(),
             size_threshold_gb: default_size_threshold(),
             age_threshold_days: default_age_threshold(),
+            use_trash: default_use_trash(),
         }
     }
 }
@@ -0,0 +1,76 @@
+//! Atomic file replacement.
+//!
+//! Contents are written to a hidden temp file next to the target
+//! (`.<name>.tmp`, so it is on the same filesystem), flushed to disk and
+//! renamed over the target. An interrupted write leaves either the old or
+//! the new file, never a mix.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Temp file used while replacing `path` (`dir/.name.tmp`).
+pub fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.tmp", file_name))
+}
+
+/// Replace the file at `path` with `contents` atomically.
+///
+/// The temp file is removed again if the write or rename fails.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let temp_path = temp_path(path);
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.with_context(|| format!("Failed to write {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_path_is_hidden_next_to_the_target() {
+        assert_eq!(
+            temp_path(Path::new("/cfg/agr/config.toml")),
+            PathBuf::from("/cfg/agr/.config.toml.tmp")
+        );
+    }
+
+    #[test]
+    fn write_atomic_replaces_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.json");
+        fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, b"new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[test]
+    fn failed_write_removes_the_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        // Renaming a file over a non-empty directory fails
+        let path = dir.path().join("target");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("keep"), "x").unwrap();
+
+        assert!(write_atomic(&path, b"new").is_err());
+        assert!(!temp_path(&path).exists());
+        assert!(path.join("keep").exists());
+    }
+}
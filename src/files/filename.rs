@@ -1,10 +1,15 @@
 //! Filename generation and sanitization for recordings.
 //!
 //! Provides configurable filename templates with tags like `{directory}`, `{date}`, `{time}`,
-//! and comprehensive sanitization to ensure filesystem-safe names.
+//! `{agent}`, `{project}`, `{pid}` and `{counter}`, comprehensive sanitization to ensure
+//! filesystem-safe names, and collision handling so an existing recording is never reused.
+
+use std::path::Path;
 
 use deunicode::deunicode;
 
+use super::lock::lock_path_for;
+
 /// Minimum allowed value for directory_max_length.
 const MIN_DIRECTORY_MAX_LENGTH: usize = 1;
 
@@ -133,15 +138,26 @@ pub fn validate_length(filename: &str) -> Result<(), FilenameError> {
 
 /// Generates a filename from a template and directory name.
 ///
+/// Shorthand for [`generate_with`] using [`RenderContext::new`].
+#[allow(dead_code)]
+pub fn generate(directory: &str, template: &str, config: &Config) -> Result<String, GenerateError> {
+    generate_with(&RenderContext::new(directory), template, config)
+}
+
+/// Generates a filename from a template and the values for its tags.
+///
 /// This is the main entry point for filename generation. It:
 /// 1. Parses the template
-/// 2. Renders it with the directory and current datetime
+/// 2. Renders it with the context and current datetime
 /// 3. Adds `.cast` extension
 /// 4. Validates the final length
-#[allow(dead_code)]
-pub fn generate(directory: &str, template: &str, config: &Config) -> Result<String, GenerateError> {
+pub fn generate_with(
+    context: &RenderContext,
+    template: &str,
+    config: &Config,
+) -> Result<String, GenerateError> {
     let parsed = Template::parse(template)?;
-    let rendered = parsed.render(directory, config);
+    let rendered = parsed.render_with(context, config);
 
     // Add .cast extension if not present
     let filename = if rendered.ends_with(".cast") {
@@ -156,6 +172,57 @@ pub fn generate(directory: &str, template: &str, config: &Config) -> Result<Stri
     Ok(filename)
 }
 
+/// Returns `filename`, or the first free `-N` variant of it, within `dir`.
+///
+/// A name is taken when the recording or its lock file exists, so a second
+/// session started before the first one has written anything still gets
+/// its own file. Suffixes count up from `-1` before the `.cast` extension:
+/// `session.cast`, `session-1.cast`, `session-2.cast`, ...
+pub fn unique_filename(dir: &Path, filename: &str) -> String {
+    let is_taken = |name: &str| {
+        let path = dir.join(name);
+        path.exists() || lock_path_for(&path).exists()
+    };
+    if !is_taken(filename) {
+        return filename.to_string();
+    }
+
+    let stem = filename.strip_suffix(".cast").unwrap_or(filename);
+    (1..)
+        .map(|n| format!("{}-{}.cast", stem, n))
+        .find(|name| !is_taken(name))
+        .expect("an unused suffix exists")
+}
+
+/// Returns the `{counter}` value for a new recording in `dir`.
+///
+/// This is one more than the number of recordings already in the directory,
+/// so the first recording is `1`. Deleted recordings can make the counter
+/// repeat; [`unique_filename`] resolves the resulting collision.
+pub fn next_counter(dir: &Path) -> usize {
+    let existing = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "cast"))
+                .count()
+        })
+        .unwrap_or(0);
+    existing + 1
+}
+
+/// Returns the project name for a working directory.
+///
+/// This is the name of the closest ancestor (including `cwd` itself) that
+/// contains a `.git` entry, or the name of `cwd` outside a repository.
+pub fn project_name(cwd: &Path) -> Option<String> {
+    let root = cwd
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(cwd);
+    root.file_name().map(|n| n.to_string_lossy().to_string())
+}
+
 /// Errors that can occur during filename generation.
 #[derive(Debug)]
 pub enum GenerateError {
@@ -492,6 +559,44 @@ pub enum Segment {
     Date(String),
     /// Time tag with format string.
     Time(String),
+    /// Agent name tag.
+    Agent,
+    /// Project (repository root) name tag.
+    Project,
+    /// Process ID tag.
+    Pid,
+    /// Per-agent recording counter tag.
+    Counter,
+}
+
+/// Values substituted for the non-datetime template tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderContext {
+    /// Working directory name for `{directory}`.
+    pub directory: String,
+    /// Agent name for `{agent}`.
+    pub agent: String,
+    /// Project name for `{project}`.
+    pub project: String,
+    /// Process ID for `{pid}`.
+    pub pid: u32,
+    /// Counter value for `{counter}`.
+    pub counter: usize,
+}
+
+impl RenderContext {
+    /// Creates a context for `directory`, which also serves as the project
+    /// name. The agent is empty, the pid is the current process and the
+    /// counter is `1`.
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: directory.to_string(),
+            agent: String::new(),
+            project: directory.to_string(),
+            pid: std::process::id(),
+            counter: 1,
+        }
+    }
 }
 
 /// Default date format for {date} tag.
@@ -579,6 +684,14 @@ impl Template {
 
     /// Renders the template with the given directory name and config.
     pub fn render(&self, directory: &str, config: &Config) -> String {
+        self.render_with(&RenderContext::new(directory), config)
+    }
+
+    /// Renders the template with the given tag values and config.
+    ///
+    /// `{directory}` and `{project}` are sanitized and truncated to
+    /// `directory_max_length`; `{agent}` is sanitized only.
+    pub fn render_with(&self, context: &RenderContext, config: &Config) -> String {
         use chrono::Local;
 
         let now = Local::now();
@@ -588,9 +701,15 @@ impl Template {
             match segment {
                 Segment::Literal(s) => result.push_str(s),
                 Segment::Directory => {
-                    let sanitized = sanitize_directory(directory, config);
+                    let sanitized = sanitize_directory(&context.directory, config);
                     result.push_str(&sanitized);
                 }
+                Segment::Agent => result.push_str(&sanitize(&context.agent, config)),
+                Segment::Project => {
+                    result.push_str(&sanitize_directory(&context.project, config));
+                }
+                Segment::Pid => result.push_str(&context.pid.to_string()),
+                Segment::Counter => result.push_str(&context.counter.to_string()),
                 Segment::Date(fmt) => {
                     let formatted = now.format(fmt).to_string();
                     result.push_str(&formatted);
@@ -617,15 +736,22 @@ fn parse_tag(content: &str) -> Result<Segment, TemplateError> {
         None => (content, None),
     };
 
-    match tag_name {
-        "directory" => {
-            if format.is_some() {
-                return Err(TemplateError::InvalidFormat(
-                    "directory tag does not accept format".to_string(),
-                ));
-            }
-            Ok(Segment::Directory)
+    let plain = |segment: Segment| {
+        if format.is_some() {
+            return Err(TemplateError::InvalidFormat(format!(
+                "{} tag does not accept format",
+                tag_name
+            )));
         }
+        Ok(segment)
+    };
+
+    match tag_name {
+        "directory" => plain(Segment::Directory),
+        "agent" => plain(Segment::Agent),
+        "project" => plain(Segment::Project),
+        "pid" => plain(Segment::Pid),
+        "counter" => plain(Segment::Counter),
         "date" => {
             let fmt = format.unwrap_or(DEFAULT_DATE_FORMAT);
             if fmt.is_empty() {
@@ -0,0 +1,302 @@
+//! On-disk index of recording details (`~/.cache/agr/index.json`).
+//!
+//! Listing needs each recording's duration and title, which means reading
+//! the file. The index remembers them per path together with the file's
+//! modification time and size, so repeated listings only re-read files that
+//! changed. Entries for deleted files are pruned on refresh.
+//!
+//! The index is a cache: a missing, corrupt or outdated file is treated as
+//! empty, and failing to save it never fails the command.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::asciicast::AsciicastFile;
+use crate::files::atomic::write_atomic;
+use crate::storage::{display_title, SessionInfo};
+
+/// Format version of the index file; other versions are discarded.
+const INDEX_VERSION: u32 = 1;
+
+/// Default index location: `~/.cache/agr/index.json`.
+pub fn default_index_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    Ok(home.join(".cache").join("agr").join("index.json"))
+}
+
+/// Cached details of one recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// File modification time when the details were read
+    pub modified: SystemTime,
+    /// File size in bytes when the details were read
+    pub size: u64,
+    pub agent: String,
+    /// Recording length in seconds, or `None` if the file could not be read
+    pub duration: Option<f64>,
+    /// Display title (see [`display_title`])
+    pub title: Option<String>,
+    /// Header start time (Unix seconds)
+    pub timestamp: Option<i64>,
+}
+
+impl IndexEntry {
+    /// Read the details of a session from its file.
+    pub fn read(session: &SessionInfo) -> Self {
+        let header = AsciicastFile::read_header_and_duration(&session.path).ok();
+        let (duration, title, timestamp) = match header {
+            Some((header, duration)) => (
+                Some(duration).filter(|d| d.is_finite()),
+                display_title(header.title, &session.agent),
+                header.timestamp,
+            ),
+            None => (None, None, None),
+        };
+        Self {
+            modified: session.modified.into(),
+            size: session.size,
+            agent: session.agent.clone(),
+            duration,
+            title,
+            timestamp,
+        }
+    }
+
+    /// Whether this entry still describes the file behind `session`.
+    pub fn is_current(&self, session: &SessionInfo) -> bool {
+        self.modified == SystemTime::from(session.modified)
+            && self.size == session.size
+            && self.agent == session.agent
+    }
+}
+
+/// Recording details keyed by path.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SessionIndex {
+    version: u32,
+    entries: BTreeMap<String, IndexEntry>,
+}
+
+impl SessionIndex {
+    /// Load the index at `path`, or an empty index if it is missing,
+    /// unreadable or from another format version.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Self>(&contents).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Save the index to `path` atomically, creating the directory if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create cache directory: {:?}", parent))?;
+        }
+        let index = Self {
+            version: INDEX_VERSION,
+            entries: self.entries.clone(),
+        };
+        let json = serde_json::to_string(&index).context("Failed to serialize index")?;
+        write_atomic(path, json.as_bytes())
+            .with_context(|| format!("Failed to write index: {:?}", path))
+    }
+
+    /// Number of indexed recordings.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the index has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The cached details for `path`, current or not.
+    pub fn get(&self, path: &Path) -> Option<&IndexEntry> {
+        self.entries.get(&key(path))
+    }
+
+    /// Drop all entries, or only those of `agent`.
+    pub fn clear(&mut self, agent: Option<&str>) {
+        match agent {
+            Some(agent) => self.entries.retain(|_, entry| entry.agent != agent),
+            None => self.entries.clear(),
+        }
+    }
+
+    /// Bring the index up to date with a fresh listing.
+    ///
+    /// Sessions without a current entry are re-read (in parallel). Entries
+    /// for files missing from `sessions` are pruned, but only for `agent`
+    /// when the listing was filtered to one agent. Returns the number of
+    /// files that were read.
+    pub fn refresh(&mut self, sessions: &[SessionInfo], agent: Option<&str>) -> usize {
+        let stale: Vec<&SessionInfo> = sessions
+            .iter()
+            .filter(|session| {
+                self.get(&session.path)
+                    .map_or(true, |entry| !entry.is_current(session))
+            })
+            .collect();
+        let read: Vec<(String, IndexEntry)> = stale
+            .par_iter()
+            .map(|session| (key(&session.path), IndexEntry::read(session)))
+            .collect();
+        let count = read.len();
+        self.entries.extend(read);
+
+        let listed: std::collections::HashSet<String> =
+            sessions.iter().map(|session| key(&session.path)).collect();
+        self.entries.retain(|path, entry| {
+            listed.contains(path) || agent.is_some_and(|agent| entry.agent != agent)
+        });
+        count
+    }
+}
+
+fn key(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, Local};
+
+    fn write_session(dir: &Path, agent: &str, name: &str, title: &str) -> SessionInfo {
+        let agent_dir = dir.join(agent);
+        fs::create_dir_all(&agent_dir).unwrap();
+        let path = agent_dir.join(name);
+        fs::write(
+            &path,
+            format!(
+                "{{\"version\":3,\"term\":{{\"cols\":80,\"rows\":24}},\"title\":\"{}\"}}\n[1.5,\"o\",\"hi\"]\n",
+                title
+            ),
+        )
+        .unwrap();
+        session_for(&path, agent)
+    }
+
+    fn session_for(path: &Path, agent: &str) -> SessionInfo {
+        let metadata = fs::metadata(path).unwrap();
+        let modified: DateTime<Local> = metadata.modified().unwrap().into();
+        SessionInfo {
+            path: path.to_path_buf(),
+            agent: agent.to_string(),
+            filename: path.file_name().unwrap().to_string_lossy().to_string(),
+            size: metadata.len(),
+            modified,
+            age_days: 0,
+            age_hours: 0,
+            age_minutes: 0,
+            pinned: false,
+            has_notes: false,
+        }
+    }
+
+    #[test]
+    fn refresh_reads_new_sessions_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let sessions = vec![
+            write_session(dir.path(), "claude", "a.cast", "Fix login"),
+            write_session(dir.path(), "codex", "b.cast", "codex session"),
+        ];
+        let mut index = SessionIndex::default();
+
+        assert_eq!(index.refresh(&sessions, None), 2);
+        assert_eq!(index.refresh(&sessions, None), 0);
+
+        let entry = index.get(&sessions[0].path).unwrap();
+        assert_eq!(entry.duration, Some(1.5));
+        assert_eq!(entry.title.as_deref(), Some("Fix login"));
+        assert_eq!(index.get(&sessions[1].path).unwrap().title, None);
+    }
+
+    #[test]
+    fn stale_entry_is_refreshed_when_mtime_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut session = write_session(dir.path(), "claude", "a.cast", "Before");
+        let mut index = SessionIndex::default();
+        index.refresh(std::slice::from_ref(&session), None);
+
+        // Same size, new contents and a different mtime
+        write_session(dir.path(), "claude", "a.cast", "After!");
+        session.modified += Duration::seconds(5);
+        assert!(!index.get(&session.path).unwrap().is_current(&session));
+
+        assert_eq!(index.refresh(std::slice::from_ref(&session), None), 1);
+        let entry = index.get(&session.path).unwrap();
+        assert_eq!(entry.title.as_deref(), Some("After!"));
+        assert!(entry.is_current(&session));
+    }
+
+    #[test]
+    fn deleted_sessions_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = write_session(dir.path(), "claude", "a.cast", "A");
+        let b = write_session(dir.path(), "claude", "b.cast", "B");
+        let c = write_session(dir.path(), "codex", "c.cast", "C");
+        let mut index = SessionIndex::default();
+        index.refresh(&[a.clone(), b.clone(), c.clone()], None);
+
+        fs::remove_file(&b.path).unwrap();
+        // A listing filtered to claude must not prune codex entries
+        index.refresh(std::slice::from_ref(&a), Some("claude"));
+        assert!(index.get(&b.path).is_none());
+        assert!(index.get(&c.path).is_some());
+
+        index.refresh(std::slice::from_ref(&a), None);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn clear_drops_only_the_given_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = write_session(dir.path(), "claude", "a.cast", "A");
+        let c = write_session(dir.path(), "codex", "c.cast", "C");
+        let mut index = SessionIndex::default();
+        index.refresh(&[a.clone(), c.clone()], None);
+
+        index.clear(Some("claude"));
+        assert!(index.get(&a.path).is_none());
+        assert!(index.get(&c.path).is_some());
+        index.clear(None);
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let session = write_session(dir.path(), "claude", "a.cast", "Title");
+        let mut index = SessionIndex::default();
+        index.refresh(std::slice::from_ref(&session), None);
+
+        let path = dir.path().join("cache").join("index.json");
+        index.save(&path).unwrap();
+        let loaded = SessionIndex::load(&path);
+        assert_eq!(loaded.get(&session.path), index.get(&session.path));
+        assert!(loaded.get(&session.path).unwrap().is_current(&session));
+    }
+
+    #[test]
+    fn corrupt_or_outdated_index_loads_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.json");
+        assert!(SessionIndex::load(&path).is_empty());
+
+        fs::write(&path, "{not json").unwrap();
+        assert!(SessionIndex::load(&path).is_empty());
+
+        fs::write(&path, "{\"version\":999,\"entries\":{}}").unwrap();
+        assert!(SessionIndex::load(&path).is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 //! File-related utilities for agent session recordings.
 
+pub mod atomic;
 pub mod backup;
 pub mod filename;
+pub mod index;
 pub mod lock;
+pub mod notes;
 pub mod resolve;
+pub mod trash;
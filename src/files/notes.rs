@@ -0,0 +1,126 @@
+//! Freeform notes kept in a Markdown sidecar next to a recording.
+//!
+//! `agr note` appends each note to `<file>.cast.md` under a heading with the
+//! time it was written, so the sidecar stays readable in any editor and
+//! notes can be added by hand as well.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+
+/// Suffix of the notes sidecar (`<file>.cast.md`).
+pub const NOTES_SUFFIX: &str = ".md";
+
+/// Get the notes sidecar path for a recording.
+pub fn notes_path_for(path: &Path) -> PathBuf {
+    let mut notes = path.as_os_str().to_owned();
+    notes.push(NOTES_SUFFIX);
+    PathBuf::from(notes)
+}
+
+/// Whether a recording has a non-empty notes sidecar.
+pub fn has_notes(path: &Path) -> bool {
+    fs::metadata(notes_path_for(path)).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Format one note as it is appended to the sidecar.
+///
+/// Notes after the first are separated from the previous one by a blank line.
+pub fn format_note(text: &str, written: DateTime<Local>, first: bool) -> String {
+    let separator = if first { "" } else { "\n" };
+    format!(
+        "{}## {}\n\n{}\n",
+        separator,
+        written.format("%Y-%m-%d %H:%M:%S"),
+        text.trim()
+    )
+}
+
+/// Append a note to a recording's sidecar, creating it if needed.
+pub fn append_note(path: &Path, text: &str, written: DateTime<Local>) -> Result<()> {
+    if text.trim().is_empty() {
+        bail!("Note text is empty");
+    }
+    let notes = notes_path_for(path);
+    let first = !has_notes(path);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&notes)
+        .with_context(|| format!("Failed to open notes: {:?}", notes))?;
+    file.write_all(format_note(text, written, first).as_bytes())
+        .with_context(|| format!("Failed to write notes: {:?}", notes))
+}
+
+/// Read a recording's notes, or `None` if it has none.
+pub fn read_notes(path: &Path) -> Result<Option<String>> {
+    if !has_notes(path) {
+        return Ok(None);
+    }
+    let notes = notes_path_for(path);
+    fs::read_to_string(&notes)
+        .map(Some)
+        .with_context(|| format!("Failed to read notes: {:?}", notes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2025, 3, 14, hour, minute, 5)
+            .unwrap()
+    }
+
+    #[test]
+    fn notes_path_appends_md_to_the_cast_path() {
+        assert_eq!(
+            notes_path_for(Path::new("/rec/claude/session.cast")),
+            PathBuf::from("/rec/claude/session.cast.md")
+        );
+    }
+
+    #[test]
+    fn format_note_puts_time_heading_above_trimmed_text() {
+        assert_eq!(
+            format_note("  Check the retry logic \n", at(9, 30), true),
+            "## 2025-03-14 09:30:05\n\nCheck the retry logic\n"
+        );
+        assert!(format_note("Later", at(10, 0), false).starts_with("\n## "));
+    }
+
+    #[test]
+    fn append_note_creates_then_extends_the_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let cast = dir.path().join("session.cast");
+        fs::write(&cast, "").unwrap();
+        assert!(!has_notes(&cast));
+        assert_eq!(read_notes(&cast).unwrap(), None);
+
+        append_note(&cast, "Check the retry logic", at(9, 30)).unwrap();
+        append_note(&cast, "Flaky test at 2:10", at(9, 45)).unwrap();
+
+        assert!(has_notes(&cast));
+        assert_eq!(
+            read_notes(&cast).unwrap().unwrap(),
+            "## 2025-03-14 09:30:05\n\nCheck the retry logic\n\
+             \n## 2025-03-14 09:45:05\n\nFlaky test at 2:10\n"
+        );
+    }
+
+    #[test]
+    fn empty_note_is_rejected_and_empty_sidecar_is_no_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cast = dir.path().join("session.cast");
+        assert!(append_note(&cast, "   ", at(9, 30)).is_err());
+        assert!(!notes_path_for(&cast).exists());
+
+        fs::write(notes_path_for(&cast), "").unwrap();
+        assert!(!has_notes(&cast));
+    }
+}
@@ -0,0 +1,464 @@
+//! Trash for deleted recordings (`~/.local/share/agr/trash/`).
+//!
+//! With `[storage] use_trash` (the default), deleting a session moves the
+//! recording and its sidecars here instead of unlinking them. A manifest
+//! (`manifest.json`) remembers where each file came from, so
+//! `agr trash restore` can put it back; `agr trash empty` deletes the
+//! trashed files for good.
+//!
+//! A recording keeps its file name in the trash unless another trashed
+//! file already has it, in which case a number is added (`session-2.cast`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::files::atomic::write_atomic;
+use crate::storage::{sidecar_path, SIDECAR_SUFFIXES};
+
+/// Format version of the manifest; other versions are refused.
+const MANIFEST_VERSION: u32 = 1;
+
+/// Name of the manifest file inside the trash directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Default trash location: `~/.local/share/agr/trash`.
+pub fn default_trash_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    Ok(home.join(".local").join("share").join("agr").join("trash"))
+}
+
+/// A recording in the trash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// File name inside the trash directory
+    pub name: String,
+    /// Where the recording was before it was trashed
+    pub original_path: PathBuf,
+    /// When the recording was trashed (Unix seconds)
+    pub deleted_at: i64,
+    /// Size of the recording in bytes
+    pub size: u64,
+    /// Sidecar suffixes trashed along with the recording (e.g. `.bak`)
+    #[serde(default)]
+    pub sidecars: Vec<String>,
+}
+
+impl TrashEntry {
+    /// The original file name, e.g. `session.cast`.
+    pub fn original_name(&self) -> String {
+        self.original_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+/// On-disk manifest format.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    #[serde(default)]
+    entries: Vec<TrashEntry>,
+}
+
+/// The trash directory and its manifest.
+#[derive(Debug, Clone)]
+pub struct Trash {
+    dir: PathBuf,
+}
+
+impl Trash {
+    /// Use `dir` as the trash directory. It is created on first use.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The trash at [`default_trash_dir`].
+    pub fn open_default() -> Result<Self> {
+        default_trash_dir().map(Self::new)
+    }
+
+    /// The trash directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Trashed recordings, oldest first. An empty or missing trash has none.
+    pub fn entries(&self) -> Result<Vec<TrashEntry>> {
+        let path = self.dir.join(MANIFEST_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read trash manifest: {:?}", path))
+            }
+        };
+        let manifest: Manifest = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse trash manifest: {:?}", path))?;
+        if manifest.version != MANIFEST_VERSION {
+            bail!(
+                "Unsupported trash manifest version {} in {:?}",
+                manifest.version,
+                path
+            );
+        }
+        Ok(manifest.entries)
+    }
+
+    /// Move a recording and its sidecars into the trash.
+    pub fn move_to_trash(&self, path: &Path) -> Result<TrashEntry> {
+        let size = fs::metadata(path)
+            .with_context(|| format!("Failed to read: {:?}", path))?
+            .len();
+        let mut entries = self.entries()?;
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create trash directory: {:?}", self.dir))?;
+
+        let name = self.unique_name(path, &entries);
+        let trashed = self.dir.join(&name);
+        move_file(path, &trashed)?;
+
+        let mut sidecars = Vec::new();
+        for suffix in SIDECAR_SUFFIXES {
+            let from = sidecar_path(path, suffix);
+            if from.exists() && move_file(&from, &sidecar_path(&trashed, suffix)).is_ok() {
+                sidecars.push(suffix.to_string());
+            }
+        }
+
+        let entry = TrashEntry {
+            name,
+            original_path: path.to_path_buf(),
+            deleted_at: chrono::Utc::now().timestamp(),
+            size,
+            sidecars,
+        };
+        entries.push(entry.clone());
+        if let Err(e) = self.save(&entries) {
+            // Without a manifest entry the file could not be restored
+            let _ = self.move_back(&entry);
+            return Err(e);
+        }
+        Ok(entry)
+    }
+
+    /// Move a trashed recording back to where it was, returning that path.
+    ///
+    /// `name` is the name in the trash or the original file name; when
+    /// several entries match, the most recently trashed one wins. `.cast` may
+    /// be left off. Restoring fails if a file with the original name exists
+    /// again.
+    pub fn restore(&self, name: &str) -> Result<PathBuf> {
+        let mut entries = self.entries()?;
+        let index = find_entry(&entries, name)
+            .with_context(|| format!("No recording named '{}' in the trash", name))?;
+        let entry = entries[index].clone();
+
+        let mut targets = vec![entry.original_path.clone()];
+        targets.extend(
+            entry
+                .sidecars
+                .iter()
+                .map(|suffix| sidecar_path(&entry.original_path, suffix)),
+        );
+        if let Some(existing) = targets.iter().find(|path| path.exists()) {
+            bail!(
+                "Cannot restore '{}': {} already exists",
+                entry.name,
+                existing.display()
+            );
+        }
+        if let Some(parent) = entry.original_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+
+        self.move_back(&entry)?;
+        entries.remove(index);
+        self.save(&entries)?;
+        Ok(entry.original_path)
+    }
+
+    /// Permanently delete everything in the trash.
+    ///
+    /// Returns the number of recordings and the bytes freed.
+    pub fn empty(&self) -> Result<(usize, u64)> {
+        let entries = self.entries()?;
+        let mut freed = 0;
+        for entry in &entries {
+            let trashed = self.dir.join(&entry.name);
+            remove_if_exists(&trashed)?;
+            for suffix in &entry.sidecars {
+                remove_if_exists(&sidecar_path(&trashed, suffix))?;
+            }
+            freed += entry.size;
+        }
+        if !entries.is_empty() {
+            self.save(&[])?;
+        }
+        Ok((entries.len(), freed))
+    }
+
+    /// A file name for `path` not used by another trashed recording.
+    fn unique_name(&self, path: &Path, entries: &[TrashEntry]) -> String {
+        let file_name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let taken =
+            |name: &str| entries.iter().any(|e| e.name == name) || self.dir.join(name).exists();
+        if !taken(&file_name) {
+            return file_name;
+        }
+        let (stem, ext) = match file_name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() => (stem.to_string(), format!(".{}", ext)),
+            _ => (file_name.clone(), String::new()),
+        };
+        (2..)
+            .map(|n| format!("{}-{}{}", stem, n, ext))
+            .find(|name| !taken(name))
+            .expect("an unused name exists")
+    }
+
+    /// Move a trashed recording and its sidecars to the original location.
+    fn move_back(&self, entry: &TrashEntry) -> Result<()> {
+        let trashed = self.dir.join(&entry.name);
+        move_file(&trashed, &entry.original_path)?;
+        for suffix in &entry.sidecars {
+            let _ = move_file(
+                &sidecar_path(&trashed, suffix),
+                &sidecar_path(&entry.original_path, suffix),
+            );
+        }
+        Ok(())
+    }
+
+    fn save(&self, entries: &[TrashEntry]) -> Result<()> {
+        let path = self.dir.join(MANIFEST_FILE);
+        let manifest = Manifest {
+            version: MANIFEST_VERSION,
+            entries: entries.to_vec(),
+        };
+        let json = serde_json::to_string_pretty(&manifest)
+            .context("Failed to serialize trash manifest")?;
+        write_atomic(&path, json.as_bytes())
+            .with_context(|| format!("Failed to write trash manifest: {:?}", path))
+    }
+}
+
+/// Index of the entry `name` refers to (see [`Trash::restore`]).
+fn find_entry(entries: &[TrashEntry], name: &str) -> Option<usize> {
+    let with_ext = if name.ends_with(".cast") {
+        name.to_string()
+    } else {
+        format!("{}.cast", name)
+    };
+    let matches = |candidate: &str| candidate == name || candidate == with_ext;
+    // Entries are in the order they were trashed
+    entries
+        .iter()
+        .rposition(|e| matches(&e.name) || matches(&e.original_name()))
+}
+
+/// Rename a file, copying it when the rename fails (e.g. across filesystems).
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to).with_context(|| format!("Failed to move {:?} to {:?}", from, to))?;
+    fs::remove_file(from).with_context(|| format!("Failed to remove {:?}", from))
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to delete: {:?}", path))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::PIN_SUFFIX;
+    use tempfile::TempDir;
+
+    /// A recordings directory and a trash directory in one temp dir.
+    fn setup() -> (TempDir, PathBuf, Trash) {
+        let dir = TempDir::new().unwrap();
+        let agent_dir = dir.path().join("sessions").join("claude");
+        fs::create_dir_all(&agent_dir).unwrap();
+        let trash = Trash::new(dir.path().join("trash"));
+        (dir, agent_dir, trash)
+    }
+
+    fn write(path: &Path, content: &str) {
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn move_to_trash_moves_recording_and_sidecars() {
+        let (_dir, agent_dir, trash) = setup();
+        let session = agent_dir.join("session.cast");
+        write(&session, "recording");
+        write(&sidecar_path(&session, ".bak"), "backup");
+        write(&sidecar_path(&session, PIN_SUFFIX), "");
+
+        let entry = trash.move_to_trash(&session).unwrap();
+
+        assert!(!session.exists());
+        assert!(!sidecar_path(&session, ".bak").exists());
+        assert_eq!(entry.name, "session.cast");
+        let trashed = trash.dir().join("session.cast");
+        assert_eq!(fs::read_to_string(&trashed).unwrap(), "recording");
+        assert_eq!(
+            fs::read_to_string(sidecar_path(&trashed, ".bak")).unwrap(),
+            "backup"
+        );
+        assert_eq!(
+            entry.sidecars,
+            vec![".bak".to_string(), PIN_SUFFIX.to_string()]
+        );
+    }
+
+    #[test]
+    fn manifest_records_the_original_location() {
+        let (_dir, agent_dir, trash) = setup();
+        let session = agent_dir.join("session.cast");
+        write(&session, "0123456789");
+
+        trash.move_to_trash(&session).unwrap();
+
+        let entries = trash.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original_path, session);
+        assert_eq!(entries[0].original_name(), "session.cast");
+        assert_eq!(entries[0].size, 10);
+        assert!(entries[0].deleted_at > 0);
+        assert!(trash.dir().join(MANIFEST_FILE).exists());
+    }
+
+    #[test]
+    fn same_name_from_another_agent_gets_a_numbered_name() {
+        let (dir, agent_dir, trash) = setup();
+        let codex_dir = dir.path().join("sessions").join("codex");
+        fs::create_dir_all(&codex_dir).unwrap();
+        write(&agent_dir.join("session.cast"), "claude");
+        write(&codex_dir.join("session.cast"), "codex");
+
+        trash
+            .move_to_trash(&agent_dir.join("session.cast"))
+            .unwrap();
+        let second = trash
+            .move_to_trash(&codex_dir.join("session.cast"))
+            .unwrap();
+
+        assert_eq!(second.name, "session-2.cast");
+        assert_eq!(
+            fs::read_to_string(trash.dir().join("session-2.cast")).unwrap(),
+            "codex"
+        );
+    }
+
+    #[test]
+    fn restore_round_trip() {
+        let (_dir, agent_dir, trash) = setup();
+        let session = agent_dir.join("session.cast");
+        write(&session, "recording");
+        write(&sidecar_path(&session, ".bookmarks"), "[]");
+        trash.move_to_trash(&session).unwrap();
+        fs::remove_dir_all(&agent_dir).unwrap();
+
+        let restored = trash.restore("session").unwrap();
+
+        assert_eq!(restored, session);
+        assert_eq!(fs::read_to_string(&session).unwrap(), "recording");
+        assert_eq!(
+            fs::read_to_string(sidecar_path(&session, ".bookmarks")).unwrap(),
+            "[]"
+        );
+        assert!(!trash.dir().join("session.cast").exists());
+        assert!(trash.entries().unwrap().is_empty());
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite() {
+        let (_dir, agent_dir, trash) = setup();
+        let session = agent_dir.join("session.cast");
+        write(&session, "old");
+        trash.move_to_trash(&session).unwrap();
+        write(&session, "new");
+
+        let err = trash.restore("session.cast").unwrap_err();
+
+        assert!(err.to_string().contains("already exists"));
+        assert_eq!(fs::read_to_string(&session).unwrap(), "new");
+        assert_eq!(trash.entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn restore_by_original_name_picks_the_latest() {
+        let (dir, agent_dir, trash) = setup();
+        let codex_dir = dir.path().join("sessions").join("codex");
+        fs::create_dir_all(&codex_dir).unwrap();
+        write(&agent_dir.join("a.cast"), "claude");
+        write(&codex_dir.join("a.cast"), "codex");
+        trash.move_to_trash(&agent_dir.join("a.cast")).unwrap();
+        trash.move_to_trash(&codex_dir.join("a.cast")).unwrap();
+
+        // Both copies were named "a"; the codex one was trashed last
+        assert_eq!(trash.restore("a").unwrap(), codex_dir.join("a.cast"));
+        assert_eq!(trash.restore("a").unwrap(), agent_dir.join("a.cast"));
+        assert!(trash.restore("a").is_err());
+    }
+
+    #[test]
+    fn restore_by_trash_name_picks_that_copy() {
+        let (dir, agent_dir, trash) = setup();
+        let codex_dir = dir.path().join("sessions").join("codex");
+        fs::create_dir_all(&codex_dir).unwrap();
+        write(&agent_dir.join("a.cast"), "claude");
+        write(&codex_dir.join("a.cast"), "codex");
+        trash.move_to_trash(&agent_dir.join("a.cast")).unwrap();
+        trash.move_to_trash(&codex_dir.join("a.cast")).unwrap();
+
+        // "a-2" is the trash name of the codex copy
+        assert_eq!(trash.restore("a-2").unwrap(), codex_dir.join("a.cast"));
+        assert_eq!(trash.restore("a").unwrap(), agent_dir.join("a.cast"));
+    }
+
+    #[test]
+    fn empty_deletes_everything() {
+        let (_dir, agent_dir, trash) = setup();
+        for name in ["a.cast", "b.cast"] {
+            let session = agent_dir.join(name);
+            write(&session, "12345");
+            write(&sidecar_path(&session, ".tags"), "x");
+            trash.move_to_trash(&session).unwrap();
+        }
+
+        assert_eq!(trash.empty().unwrap(), (2, 10));
+
+        assert!(trash.entries().unwrap().is_empty());
+        let left: Vec<_> = fs::read_dir(trash.dir())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(left, vec![std::ffi::OsString::from(MANIFEST_FILE)]);
+        assert_eq!(trash.empty().unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn missing_trash_is_empty() {
+        let trash = Trash::new(TempDir::new().unwrap().path().join("none"));
+        assert!(trash.entries().unwrap().is_empty());
+        assert_eq!(trash.empty().unwrap(), (0, 0));
+        assert!(trash.restore("x").is_err());
+    }
+}
@@ -11,9 +11,10 @@ use terminal_size::{terminal_size, Width};
 mod commands;
 
 use agr::cli::{
-    build_cli_styles, AgentCommands, Cli, Commands, ConfigCommands, MarkerCommands, NoWrapCommands,
-    ShellCommands,
+    build_cli_styles, AgentCommands, Cli, Commands, ConfigCommands, MarkerCommands, MetaCommands,
+    NoWrapCommands, NoteCommands, ShellCommands, TrashCommands,
 };
+use agr::storage::DateRange;
 use agr::theme;
 use agr::tui;
 
@@ -241,18 +242,71 @@ fn print_themed_help(err: &clap::Error) {
     print!("{}", colored);
 }
 
+/// Find the `--color` choice before clap parses the arguments.
+///
+/// Help and usage errors are rendered during parsing, so the choice has to be
+/// known up front. Unknown values fall back to `auto`; clap reports them.
+fn color_choice_from_args<I, S>(args: I) -> theme::ColorChoice
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    use clap::ValueEnum;
+
+    let mut choice = theme::ColorChoice::Auto;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let arg = arg.as_ref().to_string_lossy().into_owned();
+        let value = if arg == "--" {
+            break;
+        } else if arg == "--color" {
+            args.next()
+                .map(|v| v.as_ref().to_string_lossy().into_owned())
+        } else {
+            arg.strip_prefix("--color=").map(str::to_string)
+        };
+        if let Some(parsed) = value.and_then(|v| theme::ColorChoice::from_str(&v, true).ok()) {
+            choice = parsed;
+        }
+    }
+    choice
+}
+
+/// Whether `--no-banner` / `--quiet` is given, found before clap parses
+/// the arguments (the help logo is built before parsing).
+fn no_banner_from_args<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    args.into_iter()
+        .map(|arg| arg.as_ref().to_string_lossy().into_owned())
+        .take_while(|arg| arg != "--")
+        .any(|arg| arg == "--no-banner" || arg == "--quiet")
+}
+
 #[cfg(not(tarpaulin_include))]
 fn main() -> Result<()> {
+    theme::set_color_choice(color_choice_from_args(std::env::args_os().skip(1)));
+    theme::set_banners_enabled(!theme::no_banner_requested(no_banner_from_args(
+        std::env::args_os().skip(1),
+    )));
+
     // Check for interactive TUI help
     if should_show_tui_help() {
         return show_tui_help();
     }
 
     // Build command with styles, logo, and custom version
-    let cmd = Cli::command()
+    let mut cmd = Cli::command()
         .styles(build_cli_styles())
-        .before_help(build_logo())
         .version(build_version());
+    if theme::banners_enabled() {
+        cmd = cmd.before_help(build_logo());
+    }
+    if !theme::colors_enabled() {
+        cmd = cmd.color(clap::ColorChoice::Never);
+    }
 
     // Try to parse, handling help requests with themed output
     let matches = match cmd.try_get_matches() {
@@ -284,15 +338,93 @@ fn main() -> Result<()> {
 
     let cli = Cli::from_arg_matches(&matches).unwrap();
 
+    // Config::load reads the active profile from the environment; this also
+    // carries it over to agr processes spawned from this one.
+    if let Some(profile) = &cli.profile {
+        std::env::set_var(agr::config::profile::PROFILE_ENV, profile);
+    }
+    // The same goes for an alternate config file
+    if let Some(config) = &cli.config {
+        std::env::set_var(agr::config::CONFIG_ENV, config);
+    }
+    // The theme may already have been read (for help) from the default config
+    if cli.profile.is_some() || cli.config.is_some() {
+        theme::reload_current_theme();
+    }
+
     match cli.command {
-        Commands::Record { agent, name, args } => {
-            commands::record::handle(&agent, name.as_deref(), &args)
+        Commands::Record {
+            agent,
+            name,
+            output,
+            status,
+            cols,
+            rows,
+            args,
+        } => {
+            // Exit with the agent's code so scripts can check `$?`
+            let code = commands::record::handle(
+                &agent,
+                name.as_deref(),
+                output.as_deref(),
+                status,
+                cols,
+                rows,
+                &args,
+            )?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+            Ok(())
         }
-        Commands::Status => commands::status::handle(),
-        Commands::Cleanup { agent, older_than } => {
-            commands::cleanup::handle(agent.as_deref(), older_than)
+        Commands::Status { json, trend } => commands::status::handle(json, trend),
+        Commands::Stats { refresh } => commands::stats::handle(refresh),
+        Commands::Doctor => commands::doctor::handle(),
+        Commands::Cleanup {
+            agent,
+            older_than,
+            free,
+            since,
+            until,
+            permanent,
+        } => commands::cleanup::handle(
+            agent.as_deref(),
+            older_than,
+            free,
+            DateRange::new(since, until),
+            permanent,
+        ),
+        Commands::Trash(cmd) => match cmd {
+            TrashCommands::List => commands::trash::handle_list(),
+            TrashCommands::Restore { name } => commands::trash::handle_restore(&name),
+            TrashCommands::Empty => commands::trash::handle_empty(),
+        },
+        Commands::Pin { file } => commands::pin::handle_pin(&file),
+        Commands::Unpin { file } => commands::pin::handle_unpin(&file),
+        Commands::Note {
+            command: Some(NoteCommands::Show { file }),
+            ..
+        } => commands::note::handle_show(&file),
+        Commands::Note { file, text, .. } => commands::note::handle_add(
+            file.as_deref().unwrap_or_default(),
+            text.as_deref().unwrap_or_default(),
+        ),
+        Commands::Dedupe { dry_run } => commands::dedupe::handle(dry_run),
+        Commands::BackfillDuration { file, all } => {
+            commands::backfill_duration::handle(file.as_deref(), all)
         }
-        Commands::List { agent } => commands::list::handle(agent.as_deref()),
+        Commands::List {
+            agent,
+            json,
+            refresh,
+            since,
+            until,
+        } => commands::list::handle(
+            agent.as_deref(),
+            json,
+            refresh,
+            DateRange::new(since, until),
+        ),
         Commands::Analyze {
             file,
             agent,
@@ -303,6 +435,12 @@ fn main() -> Result<()> {
             debug,
             output,
             fast,
+            from,
+            to,
+            level,
+            review,
+            write_partial,
+            fail_on_error,
             wait,
         } => commands::analyze::handle(
             &file,
@@ -314,15 +452,66 @@ fn main() -> Result<()> {
             debug,
             output,
             fast,
+            from,
+            to,
+            level,
+            review,
+            write_partial,
+            fail_on_error,
             wait,
         ),
-        Commands::Play { file } => commands::play::handle(&file),
-        Commands::Copy { file } => commands::copy::handle(&file),
+        Commands::Summarize {
+            file,
+            agent,
+            timeout,
+            title,
+            save,
+        } => commands::summarize::handle(&file, agent.as_deref(), timeout, title, save),
+        Commands::Play {
+            file,
+            loop_playback,
+            loop_count,
+            speed,
+            strict,
+            record_to,
+        } => commands::play::handle(
+            &file,
+            loop_playback,
+            loop_count,
+            speed,
+            strict,
+            record_to.as_deref(),
+        ),
+        Commands::Copy { file, format } => commands::copy::handle(&file, format),
+        Commands::Rename { file, new_name } => commands::rename::handle(&file, &new_name),
+        Commands::Snapshot {
+            file,
+            at,
+            out,
+            format,
+            strict,
+        } => commands::snapshot::handle(&file, at, out.as_deref(), format, strict),
         Commands::Marker(cmd) => match cmd {
             MarkerCommands::Add { file, time, label } => {
                 commands::marker::handle_add(&file, time, &label)
             }
-            MarkerCommands::List { file } => commands::marker::handle_list(&file),
+            MarkerCommands::List { file, format, json } => {
+                commands::marker::handle_list(&file, format, json)
+            }
+            MarkerCommands::Export { file, format, out } => {
+                commands::marker::handle_export(&file, format, out.as_deref())
+            }
+            MarkerCommands::Import {
+                file,
+                markers,
+                format,
+            } => commands::marker::handle_import(&file, &markers, format),
+        },
+        Commands::Meta(cmd) => match cmd {
+            MetaCommands::Set { file, key, value } => {
+                commands::meta::handle_set(&file, &key, &value)
+            }
+            MetaCommands::Show { file } => commands::meta::handle_show(&file),
         },
         Commands::Agents(cmd) => match cmd {
             AgentCommands::List => commands::agents::handle_list(),
@@ -336,8 +525,11 @@ fn main() -> Result<()> {
             },
         },
         Commands::Config(cmd) => match cmd {
-            ConfigCommands::Show => commands::config::handle_show(),
+            ConfigCommands::Show { diff, annotated } => {
+                commands::config::handle_show(diff, annotated)
+            }
             ConfigCommands::Edit => commands::config::handle_edit(),
+            ConfigCommands::Check => commands::config::handle_check(),
             ConfigCommands::Migrate { yes } => commands::config::handle_migrate(yes),
             ConfigCommands::Reset { yes } => commands::config::handle_reset(yes),
         },
@@ -345,11 +537,20 @@ fn main() -> Result<()> {
             ShellCommands::Status => commands::shell::handle_status(),
             ShellCommands::Install => commands::shell::handle_install(),
             ShellCommands::Uninstall => commands::shell::handle_uninstall(),
+            ShellCommands::Doctor { yes } => commands::shell::handle_doctor(yes),
         },
+        Commands::Optimize {
+            preview: true,
+            level,
+            json,
+            file,
+            ..
+        } => commands::transform::handle_preview(&file, level, json),
         Commands::Optimize {
             remove_silence,
             output,
             file,
+            ..
         } => {
             // Parse the threshold from the optional string value
             let threshold = match remove_silence {
@@ -369,20 +570,34 @@ fn main() -> Result<()> {
 
             commands::transform::handle_remove_silence(&file, threshold, output.as_deref())
         }
+        Commands::Redact { file, out } => commands::redact::handle(&file, out.as_deref()),
+        Commands::Export {
+            file,
+            out,
+            format,
+            flatten_markers,
+            no_markers,
+        } => commands::export::handle(&file, &out, format, flatten_markers, no_markers),
+        Commands::Validate { file } => commands::validate::handle(&file),
         Commands::Completions {
             shell,
             shell_init,
             debug,
             files,
+            agents,
             limit,
             prefix,
-        } => commands::completions::handle::<Cli>(shell, shell_init, debug, files, limit, &prefix),
+        } => commands::completions::handle::<Cli>(
+            shell, shell_init, debug, files, agents, limit, &prefix,
+        ),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use agr::asciicast::{SnapshotAt, SnapshotFormat};
+    use agr::clipboard::CopyFormat;
     use clap::{CommandFactory, Parser};
     use clap_complete::Shell as CompletionShell;
 
@@ -390,7 +605,9 @@ mod tests {
     fn cli_cleanup_parses_with_no_args() {
         let cli = Cli::try_parse_from(["agr", "cleanup"]).unwrap();
         match cli.command {
-            Commands::Cleanup { agent, older_than } => {
+            Commands::Cleanup {
+                agent, older_than, ..
+            } => {
                 assert!(agent.is_none());
                 assert!(older_than.is_none());
             }
@@ -398,11 +615,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_cleanup_parses_permanent_flag() {
+        let cli = Cli::try_parse_from(["agr", "cleanup", "--permanent"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Cleanup {
+                permanent: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn cli_trash_subcommands_parse() {
+        let cli = Cli::try_parse_from(["agr", "trash", "list"]).unwrap();
+        assert!(matches!(cli.command, Commands::Trash(TrashCommands::List)));
+
+        let cli = Cli::try_parse_from(["agr", "trash", "restore", "session.cast"]).unwrap();
+        match cli.command {
+            Commands::Trash(TrashCommands::Restore { name }) => assert_eq!(name, "session.cast"),
+            _ => panic!("Expected Trash Restore command"),
+        }
+
+        let cli = Cli::try_parse_from(["agr", "trash", "empty"]).unwrap();
+        assert!(matches!(cli.command, Commands::Trash(TrashCommands::Empty)));
+        assert!(Cli::try_parse_from(["agr", "trash", "restore"]).is_err());
+    }
+
+    #[test]
+    fn cli_pin_and_unpin_parse() {
+        let cli = Cli::try_parse_from(["agr", "pin", "claude/ref.cast"]).unwrap();
+        match cli.command {
+            Commands::Pin { file } => assert_eq!(file, "claude/ref.cast"),
+            _ => panic!("Expected Pin command"),
+        }
+        let cli = Cli::try_parse_from(["agr", "unpin", "claude/ref.cast"]).unwrap();
+        match cli.command {
+            Commands::Unpin { file } => assert_eq!(file, "claude/ref.cast"),
+            _ => panic!("Expected Unpin command"),
+        }
+    }
+
+    #[test]
+    fn cli_note_add_and_show_parse() {
+        let cli = Cli::try_parse_from(["agr", "note", "claude/a.cast", "Check retries"]).unwrap();
+        match cli.command {
+            Commands::Note {
+                command: None,
+                file,
+                text,
+            } => {
+                assert_eq!(file.as_deref(), Some("claude/a.cast"));
+                assert_eq!(text.as_deref(), Some("Check retries"));
+            }
+            _ => panic!("Expected Note command"),
+        }
+        let cli = Cli::try_parse_from(["agr", "note", "show", "claude/a.cast"]).unwrap();
+        match cli.command {
+            Commands::Note {
+                command: Some(NoteCommands::Show { file }),
+                ..
+            } => assert_eq!(file, "claude/a.cast"),
+            _ => panic!("Expected Note Show command"),
+        }
+        assert!(Cli::try_parse_from(["agr", "note", "claude/a.cast"]).is_err());
+    }
+
+    #[test]
+    fn cli_cleanup_parses_free_size() {
+        let cli = Cli::try_parse_from(["agr", "cleanup", "--free", "1.5GiB"]).unwrap();
+        match cli.command {
+            Commands::Cleanup { free, .. } => assert_eq!(free, Some(1_610_612_736)),
+            _ => panic!("Expected Cleanup command"),
+        }
+        assert!(Cli::try_parse_from(["agr", "cleanup", "--free", "lots"]).is_err());
+    }
+
+    #[test]
+    fn cli_list_and_cleanup_parse_date_range() {
+        use agr::storage::DateBound;
+        use chrono::NaiveDate;
+
+        let cli = Cli::try_parse_from([
+            "agr",
+            "list",
+            "--since",
+            "2025-01-01",
+            "--until",
+            "2025-01-31",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List { since, until, .. } => {
+                let day = |d| Some(DateBound::Day(NaiveDate::from_ymd_opt(2025, 1, d).unwrap()));
+                assert_eq!(since, day(1));
+                assert_eq!(until, day(31));
+            }
+            _ => panic!("Expected List command"),
+        }
+        let cli = Cli::try_parse_from(["agr", "cleanup", "--since", "7d"]).unwrap();
+        match cli.command {
+            Commands::Cleanup { since, until, .. } => {
+                assert!(matches!(since, Some(DateBound::Instant(_))));
+                assert!(until.is_none());
+            }
+            _ => panic!("Expected Cleanup command"),
+        }
+        assert!(Cli::try_parse_from(["agr", "list", "--since", "last week"]).is_err());
+    }
+
     #[test]
     fn cli_cleanup_parses_with_agent_flag() {
         let cli = Cli::try_parse_from(["agr", "cleanup", "--agent", "claude"]).unwrap();
         match cli.command {
-            Commands::Cleanup { agent, older_than } => {
+            Commands::Cleanup {
+                agent, older_than, ..
+            } => {
                 assert_eq!(agent, Some("claude".to_string()));
                 assert!(older_than.is_none());
             }
@@ -414,7 +743,9 @@ mod tests {
     fn cli_cleanup_parses_with_older_than_flag() {
         let cli = Cli::try_parse_from(["agr", "cleanup", "--older-than", "30"]).unwrap();
         match cli.command {
-            Commands::Cleanup { agent, older_than } => {
+            Commands::Cleanup {
+                agent, older_than, ..
+            } => {
                 assert!(agent.is_none());
                 assert_eq!(older_than, Some(30));
             }
@@ -427,7 +758,9 @@ mod tests {
         let cli = Cli::try_parse_from(["agr", "cleanup", "--agent", "codex", "--older-than", "60"])
             .unwrap();
         match cli.command {
-            Commands::Cleanup { agent, older_than } => {
+            Commands::Cleanup {
+                agent, older_than, ..
+            } => {
                 assert_eq!(agent, Some("codex".to_string()));
                 assert_eq!(older_than, Some(60));
             }
@@ -435,6 +768,12 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_doctor_parses() {
+        let cli = Cli::try_parse_from(["agr", "doctor"]).unwrap();
+        assert!(matches!(cli.command, Commands::Doctor));
+    }
+
     #[test]
     fn cli_shell_status_parses() {
         let cli = Cli::try_parse_from(["agr", "shell", "status"]).unwrap();
@@ -462,6 +801,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_shell_doctor_parses() {
+        let cli = Cli::try_parse_from(["agr", "shell", "doctor"]).unwrap();
+        match cli.command {
+            Commands::Shell(ShellCommands::Doctor { yes }) => assert!(!yes),
+            _ => panic!("Expected Shell Doctor command"),
+        }
+
+        let cli = Cli::try_parse_from(["agr", "shell", "doctor", "-y"]).unwrap();
+        match cli.command {
+            Commands::Shell(ShellCommands::Doctor { yes }) => assert!(yes),
+            _ => panic!("Expected Shell Doctor command"),
+        }
+    }
+
     #[test]
     fn cli_analyze_parses_with_file_only() {
         let cli = Cli::try_parse_from(["agr", "analyze", "session.cast"]).unwrap();
@@ -476,9 +830,21 @@ mod tests {
                 debug: _,
                 output: _,
                 fast,
+                from,
+                to,
+                level,
+                review,
+                write_partial,
+                fail_on_error,
                 wait,
             } => {
                 assert_eq!(file, "session.cast");
+                assert!(from.is_none());
+                assert!(to.is_none());
+                assert!(level.is_none());
+                assert!(!review);
+                assert!(!write_partial);
+                assert!(!fail_on_error);
                 assert!(agent.is_none());
                 assert!(workers.is_none());
                 assert!(timeout.is_none());
@@ -491,6 +857,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_analyze_parses_review_flag() {
+        let cli = Cli::try_parse_from(["agr", "analyze", "session.cast", "--review"]).unwrap();
+        match cli.command {
+            Commands::Analyze { review, .. } => assert!(review),
+            _ => panic!("Expected Analyze command"),
+        }
+    }
+
+    #[test]
+    fn cli_analyze_parses_write_partial_flag() {
+        let cli =
+            Cli::try_parse_from(["agr", "analyze", "session.cast", "--write-partial"]).unwrap();
+        match cli.command {
+            Commands::Analyze { write_partial, .. } => assert!(write_partial),
+            _ => panic!("Expected Analyze command"),
+        }
+    }
+
+    #[test]
+    fn cli_analyze_parses_fail_on_error_flag() {
+        let cli =
+            Cli::try_parse_from(["agr", "analyze", "session.cast", "--fail-on-error"]).unwrap();
+        match cli.command {
+            Commands::Analyze { fail_on_error, .. } => assert!(fail_on_error),
+            _ => panic!("Expected Analyze command"),
+        }
+    }
+
     #[test]
     fn cli_analyze_parses_with_agent_flag() {
         let cli =
@@ -597,6 +992,12 @@ mod tests {
                 debug,
                 output,
                 fast,
+                from,
+                to,
+                level,
+                review: _,
+                write_partial: _,
+                fail_on_error: _,
                 wait,
             } => {
                 assert_eq!(file, "session.cast");
@@ -608,12 +1009,134 @@ mod tests {
                 assert!(debug);
                 assert_eq!(output, Some("debug.txt".to_string()));
                 assert!(!fast);
+                assert!(from.is_none());
+                assert!(to.is_none());
+                assert!(level.is_none());
                 assert!(!wait);
             }
             _ => panic!("Expected Analyze command"),
         }
     }
 
+    #[test]
+    fn cli_analyze_parses_level() {
+        let cli = Cli::try_parse_from(["agr", "analyze", "session.cast", "--level", "aggressive"])
+            .unwrap();
+        match cli.command {
+            Commands::Analyze { level, .. } => {
+                assert_eq!(level, Some(agr::analyzer::ExtractionLevel::Aggressive));
+            }
+            _ => panic!("Expected Analyze command"),
+        }
+        assert!(Cli::try_parse_from(["agr", "analyze", "s.cast", "--level", "extreme"]).is_err());
+    }
+
+    #[test]
+    fn cli_analyze_parses_time_window() {
+        let cli = Cli::try_parse_from([
+            "agr",
+            "analyze",
+            "session.cast",
+            "--from",
+            "600",
+            "--to",
+            "900.5",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Analyze { from, to, .. } => {
+                assert_eq!(from, Some(600.0));
+                assert_eq!(to, Some(900.5));
+            }
+            _ => panic!("Expected Analyze command"),
+        }
+    }
+
+    #[test]
+    fn cli_transform_preview_parses() {
+        let cli = Cli::try_parse_from(["agr", "transform", "--preview", "--json", "session.cast"])
+            .unwrap();
+        match cli.command {
+            Commands::Optimize {
+                preview,
+                json,
+                file,
+                ..
+            } => {
+                assert!(preview);
+                assert!(json);
+                assert_eq!(file, "session.cast");
+            }
+            _ => panic!("Expected Optimize command"),
+        }
+    }
+
+    #[test]
+    fn cli_transform_json_requires_preview() {
+        assert!(Cli::try_parse_from(["agr", "optimize", "--json", "session.cast"]).is_err());
+        assert!(Cli::try_parse_from([
+            "agr",
+            "optimize",
+            "--preview",
+            "--remove-silence",
+            "session.cast"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn cli_summarize_parses_with_file_only() {
+        let cli = Cli::try_parse_from(["agr", "summarize", "session.cast"]).unwrap();
+        match cli.command {
+            Commands::Summarize {
+                file,
+                agent,
+                timeout,
+                title,
+                save,
+            } => {
+                assert_eq!(file, "session.cast");
+                assert!(agent.is_none());
+                assert!(timeout.is_none());
+                assert!(!title);
+                assert!(!save);
+            }
+            _ => panic!("Expected Summarize command"),
+        }
+    }
+
+    #[test]
+    fn cli_summarize_parses_all_flags() {
+        let cli = Cli::try_parse_from([
+            "agr",
+            "summarize",
+            "claude/session.cast",
+            "-a",
+            "gemini",
+            "-t",
+            "60",
+            "--title",
+            "--save",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Summarize {
+                file,
+                agent,
+                timeout,
+                title,
+                save,
+            } => {
+                assert_eq!(file, "claude/session.cast");
+                assert_eq!(agent, Some("gemini".to_string()));
+                assert_eq!(timeout, Some(60));
+                assert!(title);
+                assert!(save);
+            }
+            _ => panic!("Expected Summarize command"),
+        }
+    }
+
     #[test]
     fn cli_completions_parses_with_shell_flag() {
         let cli = Cli::try_parse_from(["agr", "completions", "--shell", "bash"]).unwrap();
@@ -677,6 +1200,7 @@ mod tests {
                 shell_init,
                 debug,
                 files,
+                agents,
                 limit,
                 prefix,
             } => {
@@ -684,6 +1208,7 @@ mod tests {
                 assert_eq!(shell_init, Some(CompletionShell::Zsh));
                 assert!(!debug);
                 assert!(!files);
+                assert!(agents.is_none());
                 assert_eq!(limit, 10);
                 assert_eq!(prefix, "");
             }
@@ -702,6 +1227,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_completions_parses_agents_flag() {
+        use agr::shell::AgentCompletion;
+
+        let cli = Cli::try_parse_from(["agr", "completions", "--agents", "cl"]).unwrap();
+        match cli.command {
+            Commands::Completions { agents, prefix, .. } => {
+                assert_eq!(agents, Some(AgentCompletion::Enabled));
+                assert_eq!(prefix, "cl");
+            }
+            _ => panic!("Expected Completions command"),
+        }
+
+        let cli = Cli::try_parse_from(["agr", "completions", "--agents=no-wrap"]).unwrap();
+        match cli.command {
+            Commands::Completions { agents, .. } => {
+                assert_eq!(agents, Some(AgentCompletion::NoWrap));
+            }
+            _ => panic!("Expected Completions command"),
+        }
+    }
+
     #[test]
     fn cli_completions_parses_with_limit() {
         let cli = Cli::try_parse_from(["agr", "completions", "--files", "--limit", "20"]).unwrap();
@@ -746,7 +1293,7 @@ mod tests {
         // Test that 'ls' is accepted as an alias for 'list'
         let cli = Cli::try_parse_from(["agr", "ls"]).unwrap();
         match cli.command {
-            Commands::List { agent } => {
+            Commands::List { agent, .. } => {
                 assert!(agent.is_none());
             }
             _ => panic!("Expected List command from 'ls' alias"),
@@ -757,7 +1304,7 @@ mod tests {
     fn cli_ls_alias_accepts_agent_argument() {
         let cli = Cli::try_parse_from(["agr", "ls", "claude"]).unwrap();
         match cli.command {
-            Commands::List { agent } => {
+            Commands::List { agent, .. } => {
                 assert_eq!(agent, Some("claude".to_string()));
             }
             _ => panic!("Expected List command from 'ls' alias with agent"),
@@ -799,65 +1346,234 @@ mod tests {
     fn cli_marker_list_parses() {
         let cli = Cli::try_parse_from(["agr", "marker", "list", "test.cast"]).unwrap();
         match cli.command {
-            Commands::Marker(MarkerCommands::List { file }) => {
+            Commands::Marker(MarkerCommands::List { file, format, json }) => {
                 assert_eq!(file, "test.cast");
+                assert_eq!(format, None);
+                assert!(!json);
             }
             _ => panic!("Expected Marker List command"),
         }
     }
 
     #[test]
-    fn cli_record_parses_with_agent_only() {
-        let cli = Cli::try_parse_from(["agr", "record", "claude"]).unwrap();
+    fn cli_marker_list_parses_format_and_json() {
+        let cli =
+            Cli::try_parse_from(["agr", "marker", "list", "test.cast", "--format", "percent"])
+                .unwrap();
         match cli.command {
-            Commands::Record { agent, name, args } => {
-                assert_eq!(agent, "claude");
-                assert!(name.is_none());
-                assert!(args.is_empty());
+            Commands::Marker(MarkerCommands::List { format, json, .. }) => {
+                assert_eq!(format, Some(agr::asciicast::MarkerTimeFormat::Percent));
+                assert!(!json);
             }
-            _ => panic!("Expected Record command"),
+            _ => panic!("Expected Marker List command"),
         }
+
+        let cli = Cli::try_parse_from(["agr", "marker", "list", "test.cast", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Marker(MarkerCommands::List { json: true, .. })
+        ));
+
+        let result = Cli::try_parse_from([
+            "agr",
+            "marker",
+            "list",
+            "test.cast",
+            "--json",
+            "--format",
+            "clock",
+        ]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn cli_record_parses_with_name() {
-        let cli = Cli::try_parse_from(["agr", "record", "claude", "--name", "my-session"]).unwrap();
+    fn cli_marker_export_parses() {
+        let cli = Cli::try_parse_from([
+            "agr",
+            "marker",
+            "export",
+            "test.cast",
+            "--format",
+            "srt",
+            "--out",
+            "subs.srt",
+        ])
+        .unwrap();
         match cli.command {
-            Commands::Record { agent, name, args } => {
-                assert_eq!(agent, "claude");
-                assert_eq!(name, Some("my-session".to_string()));
-                assert!(args.is_empty());
+            Commands::Marker(MarkerCommands::Export { file, format, out }) => {
+                assert_eq!(file, "test.cast");
+                assert_eq!(format, Some(agr::asciicast::SubtitleFormat::Srt));
+                assert_eq!(out.as_deref(), Some("subs.srt"));
             }
-            _ => panic!("Expected Record command"),
+            _ => panic!("Expected Marker Export command"),
         }
     }
 
     #[test]
-    fn cli_record_parses_with_passthrough_args() {
-        let cli =
-            Cli::try_parse_from(["agr", "record", "claude", "--", "--help", "some-arg"]).unwrap();
+    fn cli_marker_import_parses() {
+        let cli = Cli::try_parse_from([
+            "agr",
+            "marker",
+            "import",
+            "test.cast",
+            "markers.txt",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
         match cli.command {
-            Commands::Record { agent, name, args } => {
-                assert_eq!(agent, "claude");
-                assert!(name.is_none());
-                assert_eq!(args, vec!["--help", "some-arg"]);
+            Commands::Marker(MarkerCommands::Import {
+                file,
+                markers,
+                format,
+            }) => {
+                assert_eq!(file, "test.cast");
+                assert_eq!(markers, "markers.txt");
+                assert_eq!(format, Some(agr::asciicast::ImportFormat::Csv));
             }
-            _ => panic!("Expected Record command"),
+            _ => panic!("Expected Marker Import command"),
         }
     }
 
     #[test]
-    fn cli_agents_list_parses() {
-        let cli = Cli::try_parse_from(["agr", "agents", "list"]).unwrap();
+    fn cli_meta_set_parses() {
+        let cli =
+            Cli::try_parse_from(["agr", "meta", "set", "test.cast", "title", "My demo"]).unwrap();
         match cli.command {
-            Commands::Agents(AgentCommands::List) => {}
-            _ => panic!("Expected Agents List command"),
+            Commands::Meta(MetaCommands::Set { file, key, value }) => {
+                assert_eq!(file, "test.cast");
+                assert_eq!(key, "title");
+                assert_eq!(value, "My demo");
+            }
+            _ => panic!("Expected Meta Set command"),
         }
     }
 
     #[test]
-    fn cli_agents_add_parses() {
-        let cli = Cli::try_parse_from(["agr", "agents", "add", "my-agent"]).unwrap();
+    fn cli_meta_show_parses() {
+        let cli = Cli::try_parse_from(["agr", "meta", "show", "test.cast"]).unwrap();
+        match cli.command {
+            Commands::Meta(MetaCommands::Show { file }) => {
+                assert_eq!(file, "test.cast");
+            }
+            _ => panic!("Expected Meta Show command"),
+        }
+    }
+
+    #[test]
+    fn cli_record_parses_with_agent_only() {
+        let cli = Cli::try_parse_from(["agr", "record", "claude"]).unwrap();
+        match cli.command {
+            Commands::Record {
+                agent, name, args, ..
+            } => {
+                assert_eq!(agent, "claude");
+                assert!(name.is_none());
+                assert!(args.is_empty());
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn cli_record_parses_status_flag() {
+        let cli = Cli::try_parse_from(["agr", "record", "claude", "--status"]).unwrap();
+        match cli.command {
+            Commands::Record { status, .. } => assert!(status),
+            _ => panic!("Expected Record command"),
+        }
+        let cli = Cli::try_parse_from(["agr", "record", "claude"]).unwrap();
+        match cli.command {
+            Commands::Record { status, .. } => assert!(!status),
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn cli_record_parses_window_size() {
+        let cli = Cli::try_parse_from(["agr", "record", "claude", "--cols", "120", "--rows", "40"])
+            .unwrap();
+        match cli.command {
+            Commands::Record { cols, rows, .. } => {
+                assert_eq!(cols, Some(120));
+                assert_eq!(rows, Some(40));
+            }
+            _ => panic!("Expected Record command"),
+        }
+        let cli = Cli::try_parse_from(["agr", "record", "claude"]).unwrap();
+        match cli.command {
+            Commands::Record { cols, rows, .. } => {
+                assert!(cols.is_none());
+                assert!(rows.is_none());
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn cli_record_rejects_zero_window_size() {
+        assert!(Cli::try_parse_from(["agr", "record", "claude", "--cols", "0"]).is_err());
+        assert!(Cli::try_parse_from(["agr", "record", "claude", "--rows", "0"]).is_err());
+    }
+
+    #[test]
+    fn cli_record_parses_with_name() {
+        let cli = Cli::try_parse_from(["agr", "record", "claude", "--name", "my-session"]).unwrap();
+        match cli.command {
+            Commands::Record {
+                agent, name, args, ..
+            } => {
+                assert_eq!(agent, "claude");
+                assert_eq!(name, Some("my-session".to_string()));
+                assert!(args.is_empty());
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn cli_record_parses_output() {
+        let cli = Cli::try_parse_from(["agr", "record", "claude", "--output", "-"]).unwrap();
+        match cli.command {
+            Commands::Record { agent, output, .. } => {
+                assert_eq!(agent, "claude");
+                assert_eq!(output.as_deref(), Some("-"));
+            }
+            _ => panic!("Expected Record command"),
+        }
+        assert!(
+            Cli::try_parse_from(["agr", "record", "claude", "-o", "-", "--name", "x"]).is_err()
+        );
+    }
+
+    #[test]
+    fn cli_record_parses_with_passthrough_args() {
+        let cli =
+            Cli::try_parse_from(["agr", "record", "claude", "--", "--help", "some-arg"]).unwrap();
+        match cli.command {
+            Commands::Record {
+                agent, name, args, ..
+            } => {
+                assert_eq!(agent, "claude");
+                assert!(name.is_none());
+                assert_eq!(args, vec!["--help", "some-arg"]);
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn cli_agents_list_parses() {
+        let cli = Cli::try_parse_from(["agr", "agents", "list"]).unwrap();
+        match cli.command {
+            Commands::Agents(AgentCommands::List) => {}
+            _ => panic!("Expected Agents List command"),
+        }
+    }
+
+    #[test]
+    fn cli_agents_add_parses() {
+        let cli = Cli::try_parse_from(["agr", "agents", "add", "my-agent"]).unwrap();
         match cli.command {
             Commands::Agents(AgentCommands::Add { name }) => {
                 assert_eq!(name, "my-agent");
@@ -881,11 +1597,35 @@ mod tests {
     fn cli_config_show_parses() {
         let cli = Cli::try_parse_from(["agr", "config", "show"]).unwrap();
         match cli.command {
-            Commands::Config(ConfigCommands::Show) => {}
+            Commands::Config(ConfigCommands::Show { diff, annotated }) => {
+                assert!(!diff);
+                assert!(!annotated);
+            }
             _ => panic!("Expected Config Show command"),
         }
     }
 
+    #[test]
+    fn cli_config_show_diff_and_annotated_parse() {
+        let cli = Cli::try_parse_from(["agr", "config", "show", "--diff"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config(ConfigCommands::Show {
+                diff: true,
+                annotated: false
+            })
+        ));
+        let cli = Cli::try_parse_from(["agr", "config", "show", "--annotated"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config(ConfigCommands::Show {
+                diff: false,
+                annotated: true
+            })
+        ));
+        assert!(Cli::try_parse_from(["agr", "config", "show", "--diff", "--annotated"]).is_err());
+    }
+
     #[test]
     fn cli_config_edit_parses() {
         let cli = Cli::try_parse_from(["agr", "config", "edit"]).unwrap();
@@ -950,42 +1690,439 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_rename_parses() {
+        let cli = Cli::try_parse_from(["agr", "rename", "claude/a.cast", "b"]).unwrap();
+        match cli.command {
+            Commands::Rename { file, new_name } => {
+                assert_eq!(file, "claude/a.cast");
+                assert_eq!(new_name, "b");
+            }
+            _ => panic!("Expected Rename command"),
+        }
+    }
+
+    #[test]
+    fn cli_snapshot_defaults_to_end_as_text() {
+        let cli = Cli::try_parse_from(["agr", "snapshot", "a.cast"]).unwrap();
+        match cli.command {
+            Commands::Snapshot {
+                file,
+                at,
+                out,
+                format,
+                strict,
+            } => {
+                assert_eq!(file, "a.cast");
+                assert_eq!(at, SnapshotAt::Percent(100.0));
+                assert_eq!(out, None);
+                assert_eq!(format, SnapshotFormat::Text);
+                assert!(!strict);
+            }
+            _ => panic!("Expected Snapshot command"),
+        }
+    }
+
+    #[test]
+    fn cli_snapshot_parses_options() {
+        let cli = Cli::try_parse_from([
+            "agr", "snapshot", "a.cast", "--at", "12.5", "--out", "snap.txt", "--format", "ansi",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Snapshot {
+                at, out, format, ..
+            } => {
+                assert_eq!(at, SnapshotAt::Seconds(12.5));
+                assert_eq!(out.as_deref(), Some("snap.txt"));
+                assert_eq!(format, SnapshotFormat::Ansi);
+            }
+            _ => panic!("Expected Snapshot command"),
+        }
+    }
+
+    #[test]
+    fn cli_snapshot_rejects_invalid_time() {
+        assert!(Cli::try_parse_from(["agr", "snapshot", "a.cast", "--at", "150%"]).is_err());
+        assert!(Cli::try_parse_from(["agr", "snapshot", "a.cast", "--at", "soon"]).is_err());
+    }
+
+    #[test]
+    fn cli_redact_parses() {
+        let cli = Cli::try_parse_from(["agr", "redact", "a.cast", "--out", "clean.cast"]).unwrap();
+        match cli.command {
+            Commands::Redact { file, out } => {
+                assert_eq!(file, "a.cast");
+                assert_eq!(out.as_deref(), Some("clean.cast"));
+            }
+            _ => panic!("Expected Redact command"),
+        }
+    }
+
+    #[test]
+    fn cli_export_parses_marker_flags() {
+        let cli = Cli::try_parse_from([
+            "agr",
+            "export",
+            "a.cast",
+            "out.cast",
+            "--format",
+            "cast",
+            "--flatten-markers",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Export {
+                file,
+                out,
+                format,
+                flatten_markers,
+                no_markers,
+            } => {
+                assert_eq!(file, "a.cast");
+                assert_eq!(out, "out.cast");
+                assert_eq!(format, agr::asciicast::ExportFormat::Cast);
+                assert!(flatten_markers);
+                assert!(!no_markers);
+            }
+            _ => panic!("Expected Export command"),
+        }
+        assert!(Cli::try_parse_from([
+            "agr",
+            "export",
+            "a.cast",
+            "out.cast",
+            "--flatten-markers",
+            "--no-markers",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn cli_validate_parses_file() {
+        let cli = Cli::try_parse_from(["agr", "validate", "a.cast"]).unwrap();
+        match cli.command {
+            Commands::Validate { file } => assert_eq!(file, "a.cast"),
+            _ => panic!("Expected Validate command"),
+        }
+        assert!(Cli::try_parse_from(["agr", "validate"]).is_err());
+    }
+
+    #[test]
+    fn cli_rename_requires_new_name() {
+        assert!(Cli::try_parse_from(["agr", "rename", "a.cast"]).is_err());
+    }
+
+    #[test]
+    fn cli_stats_parses() {
+        let cli = Cli::try_parse_from(["agr", "stats"]).unwrap();
+        match cli.command {
+            Commands::Stats { refresh } => assert!(!refresh),
+            _ => panic!("Expected Stats command"),
+        }
+    }
+
+    #[test]
+    fn cli_refresh_parses_for_list_and_stats() {
+        let cli = Cli::try_parse_from(["agr", "stats", "--refresh"]).unwrap();
+        assert!(matches!(cli.command, Commands::Stats { refresh: true }));
+        let cli = Cli::try_parse_from(["agr", "list", "--refresh"]).unwrap();
+        assert!(matches!(cli.command, Commands::List { refresh: true, .. }));
+    }
+
+    #[test]
+    fn cli_global_profile_parses_after_subcommand() {
+        let cli = Cli::try_parse_from(["agr", "list", "--profile", "server"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("server"));
+        assert!(matches!(cli.command, Commands::List { .. }));
+    }
+
+    #[test]
+    fn cli_global_config_parses_before_and_after_subcommand() {
+        let cli = Cli::try_parse_from(["agr", "--config", "/tmp/alt.toml", "status"]).unwrap();
+        assert_eq!(cli.config.as_deref(), Some("/tmp/alt.toml"));
+        let cli = Cli::try_parse_from(["agr", "list", "--config", "alt.toml"]).unwrap();
+        assert_eq!(cli.config.as_deref(), Some("alt.toml"));
+        let cli = Cli::try_parse_from(["agr", "list"]).unwrap();
+        assert!(cli.config.is_none());
+    }
+
+    #[test]
+    fn cli_profile_defaults_to_none() {
+        let cli = Cli::try_parse_from(["agr", "status"]).unwrap();
+        assert!(cli.profile.is_none());
+    }
+
+    #[test]
+    fn cli_list_json_parses() {
+        let cli = Cli::try_parse_from(["agr", "list", "claude", "--json"]).unwrap();
+        match cli.command {
+            Commands::List {
+                agent,
+                json,
+                refresh,
+                ..
+            } => {
+                assert_eq!(agent.as_deref(), Some("claude"));
+                assert!(json);
+                assert!(!refresh);
+            }
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn cli_status_json_parses() {
+        let cli = Cli::try_parse_from(["agr", "status", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Status {
+                json: true,
+                trend: None
+            }
+        ));
+    }
+
+    #[test]
+    fn cli_color_defaults_to_auto() {
+        let cli = Cli::try_parse_from(["agr", "status"]).unwrap();
+        assert_eq!(cli.color, theme::ColorChoice::Auto);
+    }
+
+    #[test]
+    fn cli_global_color_parses_after_subcommand() {
+        let cli = Cli::try_parse_from(["agr", "list", "--color", "never"]).unwrap();
+        assert_eq!(cli.color, theme::ColorChoice::Never);
+    }
+
+    #[test]
+    fn cli_color_rejects_unknown_value() {
+        assert!(Cli::try_parse_from(["agr", "status", "--color=sometimes"]).is_err());
+    }
+
+    #[test]
+    fn color_choice_from_args_finds_both_forms() {
+        assert_eq!(
+            color_choice_from_args(["list", "--color", "never"]),
+            theme::ColorChoice::Never
+        );
+        assert_eq!(
+            color_choice_from_args(["--color=always", "status"]),
+            theme::ColorChoice::Always
+        );
+        assert_eq!(color_choice_from_args(["status"]), theme::ColorChoice::Auto);
+    }
+
+    #[test]
+    fn no_banner_from_args_finds_flag_and_alias() {
+        assert!(no_banner_from_args(["record", "claude", "--no-banner"]));
+        assert!(no_banner_from_args(["--quiet", "list"]));
+        assert!(!no_banner_from_args(["list"]));
+        assert!(!no_banner_from_args(["record", "claude", "--", "--quiet"]));
+    }
+
+    #[test]
+    fn cli_no_banner_parses_globally() {
+        let cli = Cli::try_parse_from(["agr", "record", "claude", "--no-banner"]).unwrap();
+        assert!(cli.no_banner);
+        let cli = Cli::try_parse_from(["agr", "--quiet", "list"]).unwrap();
+        assert!(cli.no_banner);
+        assert!(!Cli::try_parse_from(["agr", "list"]).unwrap().no_banner);
+    }
+
+    #[test]
+    fn color_choice_from_args_stops_at_agent_args() {
+        assert_eq!(
+            color_choice_from_args(["record", "claude", "--", "--color", "never"]),
+            theme::ColorChoice::Auto
+        );
+    }
+
+    #[test]
+    fn cli_config_check_parses() {
+        let cli = Cli::try_parse_from(["agr", "config", "check"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config(ConfigCommands::Check)
+        ));
+    }
+
+    #[test]
+    fn cli_dedupe_parses() {
+        let cli = Cli::try_parse_from(["agr", "dedupe"]).unwrap();
+        match cli.command {
+            Commands::Dedupe { dry_run } => assert!(!dry_run),
+            _ => panic!("Expected Dedupe command"),
+        }
+    }
+
+    #[test]
+    fn cli_dedupe_dry_run_parses() {
+        let cli = Cli::try_parse_from(["agr", "dedupe", "--dry-run"]).unwrap();
+        match cli.command {
+            Commands::Dedupe { dry_run } => assert!(dry_run),
+            _ => panic!("Expected Dedupe command"),
+        }
+    }
+
+    #[test]
+    fn cli_backfill_duration_parses_file() {
+        let cli = Cli::try_parse_from(["agr", "backfill-duration", "claude/a.cast"]).unwrap();
+        match cli.command {
+            Commands::BackfillDuration { file, all } => {
+                assert_eq!(file.as_deref(), Some("claude/a.cast"));
+                assert!(!all);
+            }
+            _ => panic!("Expected BackfillDuration command"),
+        }
+    }
+
+    #[test]
+    fn cli_backfill_duration_parses_all() {
+        let cli = Cli::try_parse_from(["agr", "backfill-duration", "--all"]).unwrap();
+        match cli.command {
+            Commands::BackfillDuration { file, all } => {
+                assert_eq!(file, None);
+                assert!(all);
+            }
+            _ => panic!("Expected BackfillDuration command"),
+        }
+    }
+
+    #[test]
+    fn cli_backfill_duration_requires_file_or_all() {
+        assert!(Cli::try_parse_from(["agr", "backfill-duration"]).is_err());
+        assert!(Cli::try_parse_from(["agr", "backfill-duration", "a.cast", "--all"]).is_err());
+    }
+
     #[test]
     fn cli_status_parses() {
         let cli = Cli::try_parse_from(["agr", "status"]).unwrap();
         match cli.command {
-            Commands::Status => {}
+            Commands::Status { json, trend } => {
+                assert!(!json);
+                assert_eq!(trend, None);
+            }
             _ => panic!("Expected Status command"),
         }
     }
 
+    #[test]
+    fn cli_status_trend_parses() {
+        let trend = |args: &[&str]| match Cli::try_parse_from(args).unwrap().command {
+            Commands::Status { trend, .. } => trend,
+            _ => panic!("Expected Status command"),
+        };
+        assert_eq!(trend(&["agr", "status", "--trend"]), Some(30));
+        assert_eq!(trend(&["agr", "status", "--trend", "7"]), Some(7));
+        assert!(Cli::try_parse_from(["agr", "status", "--trend", "0"]).is_err());
+        assert!(Cli::try_parse_from(["agr", "status", "--trend", "--json"]).is_err());
+    }
+
     #[test]
     fn cli_play_parses_with_file() {
         let cli = Cli::try_parse_from(["agr", "play", "session.cast"]).unwrap();
         match cli.command {
-            Commands::Play { file } => {
+            Commands::Play { file, .. } => {
                 assert_eq!(file, "session.cast");
             }
             _ => panic!("Expected Play command"),
         }
     }
 
+    #[test]
+    fn cli_play_parses_stdin_dash() {
+        let cli = Cli::try_parse_from(["agr", "play", "-"]).unwrap();
+        match cli.command {
+            Commands::Play { file, .. } => assert_eq!(file, "-"),
+            _ => panic!("Expected Play command"),
+        }
+    }
+
     #[test]
     fn cli_play_parses_with_path() {
         let cli = Cli::try_parse_from(["agr", "play", "/path/to/session.cast"]).unwrap();
         match cli.command {
-            Commands::Play { file } => {
+            Commands::Play { file, .. } => {
                 assert_eq!(file, "/path/to/session.cast");
             }
             _ => panic!("Expected Play command"),
         }
     }
 
+    #[test]
+    fn cli_play_parses_record_to() {
+        let cli =
+            Cli::try_parse_from(["agr", "play", "s.cast", "--record-to", "review.cast"]).unwrap();
+        match cli.command {
+            Commands::Play { record_to, .. } => {
+                assert_eq!(record_to.as_deref(), Some("review.cast"));
+            }
+            _ => panic!("Expected Play command"),
+        }
+    }
+
+    #[test]
+    fn cli_play_parses_loop_flags() {
+        let cli =
+            Cli::try_parse_from(["agr", "play", "s.cast", "--loop", "--loop-count", "3"]).unwrap();
+        match cli.command {
+            Commands::Play {
+                loop_playback,
+                loop_count,
+                ..
+            } => {
+                assert!(loop_playback);
+                assert_eq!(loop_count, Some(3));
+            }
+            _ => panic!("Expected Play command"),
+        }
+    }
+
+    #[test]
+    fn cli_play_parses_speed() {
+        let cli = Cli::try_parse_from(["agr", "play", "s.cast", "--speed", "2.5"]).unwrap();
+        match cli.command {
+            Commands::Play { speed, .. } => assert_eq!(speed, Some(2.5)),
+            _ => panic!("Expected Play command"),
+        }
+    }
+
+    #[test]
+    fn cli_play_parses_strict() {
+        let cli = Cli::try_parse_from(["agr", "play", "s.cast", "--strict"]).unwrap();
+        match cli.command {
+            Commands::Play { strict, .. } => assert!(strict),
+            _ => panic!("Expected Play command"),
+        }
+    }
+
+    #[test]
+    fn cli_play_rejects_zero_loop_count() {
+        assert!(Cli::try_parse_from(["agr", "play", "s.cast", "--loop-count", "0"]).is_err());
+    }
+
+    #[test]
+    fn cli_play_loop_defaults_off() {
+        let cli = Cli::try_parse_from(["agr", "play", "s.cast"]).unwrap();
+        match cli.command {
+            Commands::Play {
+                loop_playback,
+                loop_count,
+                ..
+            } => {
+                assert!(!loop_playback);
+                assert_eq!(loop_count, None);
+            }
+            _ => panic!("Expected Play command"),
+        }
+    }
+
     #[test]
     fn cli_play_parses_with_short_format() {
         let cli = Cli::try_parse_from(["agr", "play", "claude/session.cast"]).unwrap();
         match cli.command {
-            Commands::Play { file } => {
+            Commands::Play { file, .. } => {
                 assert_eq!(file, "claude/session.cast");
             }
             _ => panic!("Expected Play command"),
@@ -996,7 +2133,7 @@ mod tests {
     fn cli_copy_parses_with_file() {
         let cli = Cli::try_parse_from(["agr", "copy", "session.cast"]).unwrap();
         match cli.command {
-            Commands::Copy { file } => {
+            Commands::Copy { file, .. } => {
                 assert_eq!(file, "session.cast");
             }
             _ => panic!("Expected Copy command"),
@@ -1007,7 +2144,7 @@ mod tests {
     fn cli_copy_parses_with_path() {
         let cli = Cli::try_parse_from(["agr", "copy", "/path/to/session.cast"]).unwrap();
         match cli.command {
-            Commands::Copy { file } => {
+            Commands::Copy { file, .. } => {
                 assert_eq!(file, "/path/to/session.cast");
             }
             _ => panic!("Expected Copy command"),
@@ -1018,13 +2155,37 @@ mod tests {
     fn cli_copy_parses_with_short_format() {
         let cli = Cli::try_parse_from(["agr", "copy", "claude/session.cast"]).unwrap();
         match cli.command {
-            Commands::Copy { file } => {
+            Commands::Copy { file, .. } => {
                 assert_eq!(file, "claude/session.cast");
             }
             _ => panic!("Expected Copy command"),
         }
     }
 
+    #[test]
+    fn cli_copy_parses_as_format() {
+        let cli = Cli::try_parse_from(["agr", "copy", "session.cast"]).unwrap();
+        match cli.command {
+            Commands::Copy { format, .. } => assert_eq!(format, CopyFormat::File),
+            _ => panic!("Expected Copy command"),
+        }
+
+        let cli = Cli::try_parse_from(["agr", "copy", "session.cast", "--as", "html"]).unwrap();
+        match cli.command {
+            Commands::Copy { file, format } => {
+                assert_eq!(file, "session.cast");
+                assert_eq!(format, CopyFormat::Html);
+            }
+            _ => panic!("Expected Copy command"),
+        }
+
+        let cli = Cli::try_parse_from(["agr", "copy", "session.cast", "--as", "text"]).unwrap();
+        match cli.command {
+            Commands::Copy { format, .. } => assert_eq!(format, CopyFormat::Text),
+            _ => panic!("Expected Copy command"),
+        }
+    }
+
     #[test]
     fn cli_copy_requires_file_argument() {
         // `agr copy` without file should fail
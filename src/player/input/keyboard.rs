@@ -7,8 +7,12 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::io::{self, Write};
 use std::time::{Duration, Instant};
 
+use super::keymap::Action;
 use crate::asciicast::AsciicastFile;
-use crate::player::playback::{find_event_index_at_time, seek_to_time};
+use crate::player::playback::{
+    find_event_index_at_time, seek_to_event_index, seek_to_time, step_event_position,
+};
+use crate::player::render::marker_seek_time;
 use crate::player::state::{InputResult, MarkerPosition, PlaybackState};
 use crate::terminal::TerminalBuffer;
 
@@ -34,10 +38,37 @@ pub fn handle_key_event(
         return InputResult::Continue;
     }
 
+    // Ctrl+C always quits, regardless of key bindings
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        return InputResult::Quit;
+    }
+
+    // While the marker list is open, keys move its selection
+    if state.marker_list.is_some() {
+        handle_marker_list_key(key, state, buffer, cast, markers, rec_cols, rec_rows);
+        return InputResult::Continue;
+    }
+
+    // While the go-to-time prompt is open, keys edit its text
+    if state.time_prompt.is_some() {
+        handle_time_prompt_key(key, state, buffer, cast, total_duration, rec_cols, rec_rows);
+        return InputResult::Continue;
+    }
+
+    if let Some(action) = state.keymap.action_for(&key, state.paused) {
+        return handle_action(
+            action,
+            state,
+            buffer,
+            cast,
+            markers,
+            total_duration,
+            rec_cols,
+            rec_rows,
+        );
+    }
+
     match key.code {
-        // === Quit ===
-        KeyCode::Char('q') => InputResult::Quit,
-        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => InputResult::Quit,
         KeyCode::Esc => {
             if state.exit_mode_or_quit() {
                 InputResult::Continue
@@ -46,64 +77,6 @@ pub fn handle_key_event(
             }
         }
 
-        // === Mode toggles ===
-        KeyCode::Char('?') => {
-            state.toggle_help();
-            InputResult::Continue
-        }
-        KeyCode::Char('v') => {
-            state.toggle_viewport_mode();
-            InputResult::Continue
-        }
-        KeyCode::Char('f') => {
-            state.toggle_free_mode(buffer.cursor_row());
-            InputResult::Continue
-        }
-
-        // === Playback controls ===
-        KeyCode::Char(' ') => {
-            state.toggle_pause();
-            InputResult::Continue
-        }
-        KeyCode::Char('+') | KeyCode::Char('=') => {
-            state.speed_up();
-            InputResult::Continue
-        }
-        KeyCode::Char('-') | KeyCode::Char('_') => {
-            state.speed_down();
-            InputResult::Continue
-        }
-
-        // === Resize terminal ===
-        KeyCode::Char('r') => {
-            handle_resize_to_recording(state, buffer);
-            InputResult::Continue
-        }
-
-        // === Marker navigation ===
-        KeyCode::Char('m') => {
-            handle_jump_to_marker(state, buffer, cast, markers, rec_cols, rec_rows);
-            InputResult::Continue
-        }
-
-        // === Seeking ===
-        KeyCode::Char('<') | KeyCode::Char(',') => {
-            handle_seek_backward(state, buffer, cast, 5.0, rec_cols, rec_rows);
-            InputResult::Continue
-        }
-        KeyCode::Char('>') | KeyCode::Char('.') => {
-            handle_seek_forward(state, buffer, cast, 5.0, total_duration, rec_cols, rec_rows);
-            InputResult::Continue
-        }
-        KeyCode::Home => {
-            handle_seek_to_start(state, buffer, cast, rec_cols, rec_rows);
-            InputResult::Continue
-        }
-        KeyCode::End => {
-            handle_seek_to_end(state, buffer, cast, total_duration, rec_cols, rec_rows);
-            InputResult::Continue
-        }
-
         // === Arrow keys (context-dependent) ===
         KeyCode::Left => {
             handle_left_key(
@@ -142,6 +115,173 @@ pub fn handle_key_event(
     }
 }
 
+/// Perform a (remappable) player action.
+#[allow(clippy::too_many_arguments)]
+fn handle_action(
+    action: Action,
+    state: &mut PlaybackState,
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    markers: &[MarkerPosition],
+    total_duration: f64,
+    rec_cols: u32,
+    rec_rows: u32,
+) -> InputResult {
+    match action {
+        // === Quit ===
+        Action::Quit => return InputResult::Quit,
+
+        // === Mode toggles ===
+        Action::Help => state.toggle_help(),
+        Action::ViewportMode => state.toggle_viewport_mode(),
+        Action::FreeMode => state.toggle_free_mode(buffer.cursor_row()),
+
+        // === Playback controls ===
+        Action::Pause => state.toggle_pause(),
+        Action::SpeedUp => state.speed_up(),
+        Action::SpeedDown => state.speed_down(),
+        Action::SpeedPreset1x => state.set_speed(1.0),
+        Action::SpeedPreset2x => state.set_speed(2.0),
+        Action::SpeedPreset4x => state.set_speed(4.0),
+
+        // === Resize terminal ===
+        Action::Resize => handle_resize_to_recording(state, buffer),
+
+        // === Marker navigation ===
+        Action::NextMarker => {
+            handle_jump_to_marker(state, buffer, cast, markers, rec_cols, rec_rows)
+        }
+        Action::MarkerList => handle_open_marker_list(state, markers),
+
+        // === Bookmarks ===
+        Action::AddBookmark => handle_add_bookmark(state),
+        Action::NextBookmark => {
+            handle_jump_to_bookmark(state, buffer, cast, true, rec_cols, rec_rows)
+        }
+        Action::PrevBookmark => {
+            handle_jump_to_bookmark(state, buffer, cast, false, rec_cols, rec_rows)
+        }
+
+        // === Frame stepping (paused only) ===
+        Action::StepBackward => handle_step_event(state, buffer, cast, false, rec_cols, rec_rows),
+        Action::StepForward => handle_step_event(state, buffer, cast, true, rec_cols, rec_rows),
+
+        // === Seeking ===
+        Action::SeekBackward => handle_seek_backward(state, buffer, cast, 5.0, rec_cols, rec_rows),
+        Action::SeekForward => {
+            handle_seek_forward(state, buffer, cast, 5.0, total_duration, rec_cols, rec_rows)
+        }
+        Action::SeekStart => handle_seek_to_start(state, buffer, cast, rec_cols, rec_rows),
+        Action::SeekEnd => {
+            handle_seek_to_end(state, buffer, cast, total_duration, rec_cols, rec_rows)
+        }
+        Action::GoToTime => {
+            state.time_prompt = Some(String::new());
+            state.needs_render = true;
+        }
+    }
+    InputResult::Continue
+}
+
+/// Longest text the go-to-time prompt accepts.
+const TIME_PROMPT_MAX_LEN: usize = 12;
+
+/// Parse a time typed into the go-to-time prompt, in seconds.
+///
+/// Accepts plain seconds (`90`, `12.5`), `mm:ss` and `h:mm:ss`; the
+/// seconds may have a fraction (`1:02.5`). Minutes and seconds after the
+/// first field must be below 60.
+pub fn parse_time_input(input: &str) -> Option<f64> {
+    let input = input.trim();
+    let fields: Vec<&str> = input.split(':').collect();
+    if fields.len() > 3 || fields.iter().any(|f| f.is_empty()) {
+        return None;
+    }
+    let (seconds, whole) = fields.split_last()?;
+    if !seconds.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    let mut total: f64 = seconds.parse().ok().filter(|s: &f64| s.is_finite())?;
+    if !whole.is_empty() && total >= 60.0 {
+        return None;
+    }
+    for (i, field) in whole.iter().rev().enumerate() {
+        let value: u64 = field.parse().ok()?;
+        // Minutes are bounded when hours come before them
+        if i == 0 && whole.len() == 2 && value >= 60 {
+            return None;
+        }
+        total += value as f64 * 60f64.powi(i as i32 + 1);
+    }
+    Some(total)
+}
+
+/// Edit the go-to-time prompt, or jump and close it on Enter.
+///
+/// Esc closes the prompt. Enter with text that does not parse keeps it
+/// open so the time can be corrected.
+fn handle_time_prompt_key(
+    key: KeyEvent,
+    state: &mut PlaybackState,
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    total_duration: f64,
+    rec_cols: u32,
+    rec_rows: u32,
+) {
+    let Some(input) = state.time_prompt.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Char(c)
+            if (c.is_ascii_digit() || c == ':' || c == '.')
+                && input.len() < TIME_PROMPT_MAX_LEN =>
+        {
+            input.push(c);
+        }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Esc => state.time_prompt = None,
+        KeyCode::Enter => {
+            if let Some(time) = parse_time_input(input) {
+                state.time_prompt = None;
+                handle_seek_to(
+                    state,
+                    buffer,
+                    cast,
+                    time,
+                    total_duration,
+                    rec_cols,
+                    rec_rows,
+                );
+            }
+        }
+        _ => {}
+    }
+    state.needs_render = true;
+}
+
+/// Seek to an absolute time (clamped to the recording), keeping the play state.
+fn handle_seek_to(
+    state: &mut PlaybackState,
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    time: f64,
+    total_duration: f64,
+    rec_cols: u32,
+    rec_rows: u32,
+) {
+    let time = time.clamp(0.0, total_duration.max(0.0));
+    seek_to_time(buffer, cast, time, rec_cols, rec_rows);
+    state.set_current_time(time, total_duration);
+    state.set_time_offset(state.current_time());
+    state.start_time = Instant::now();
+    let (idx, cumulative) = find_event_index_at_time(cast, state.current_time());
+    state.set_event_position(idx, cumulative, cast.events.len());
+    state.needs_render = true;
+}
+
 /// Handle resize terminal to match current buffer size.
 fn handle_resize_to_recording(state: &mut PlaybackState, buffer: &TerminalBuffer) {
     // NOTE: This uses xterm escape sequence which only works on
@@ -196,15 +336,136 @@ fn handle_jump_to_marker(
     rec_rows: u32,
 ) {
     if let Some(next) = markers.iter().find(|m| m.time > state.current_time() + 0.1) {
-        seek_to_time(buffer, cast, next.time, rec_cols, rec_rows);
-        state.set_current_time(next.time, f64::MAX);
-        state.set_time_offset(state.current_time());
-        state.start_time = Instant::now();
-        let (idx, cumulative) = find_event_index_at_time(cast, state.current_time());
-        state.set_event_position(idx, cumulative, cast.events.len());
-        state.paused = true;
-        state.needs_render = true;
+        jump_to_time_paused(state, buffer, cast, next.time, rec_cols, rec_rows);
+    }
+}
+
+/// Open the marker list with the next marker (or the last one) selected.
+fn handle_open_marker_list(state: &mut PlaybackState, markers: &[MarkerPosition]) {
+    let current = state.current_time();
+    let selected = markers
+        .iter()
+        .position(|m| m.time > current + 0.1)
+        .unwrap_or(markers.len().saturating_sub(1));
+    state.marker_list = Some(selected);
+    state.needs_render = true;
+}
+
+/// Move the marker list selection, or seek to the selected marker on Enter.
+///
+/// Esc or the marker list key closes the list without seeking.
+fn handle_marker_list_key(
+    key: KeyEvent,
+    state: &mut PlaybackState,
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    markers: &[MarkerPosition],
+    rec_cols: u32,
+    rec_rows: u32,
+) {
+    let Some(selected) = state.marker_list else {
+        return;
+    };
+    let last = markers.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.marker_list = Some(selected.saturating_sub(1)),
+        KeyCode::Down | KeyCode::Char('j') => state.marker_list = Some((selected + 1).min(last)),
+        KeyCode::Home => state.marker_list = Some(0),
+        KeyCode::End => state.marker_list = Some(last),
+        KeyCode::Enter => {
+            state.marker_list = None;
+            if let Some(time) = marker_seek_time(markers, selected) {
+                jump_to_time_paused(state, buffer, cast, time, rec_cols, rec_rows);
+            }
+        }
+        KeyCode::Esc => state.marker_list = None,
+        _ => {
+            if state.keymap.action_for(&key, state.paused) == Some(Action::MarkerList) {
+                state.marker_list = None;
+            }
+        }
     }
+    state.needs_render = true;
+}
+
+/// Handle stepping exactly one event forward or backward (while paused).
+///
+/// Forward applies the next event to the buffer incrementally; backward
+/// rebuilds the buffer up to the previous event index.
+fn handle_step_event(
+    state: &mut PlaybackState,
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    forward: bool,
+    rec_cols: u32,
+    rec_rows: u32,
+) {
+    let Some((idx, cumulative)) = step_event_position(cast, state.event_idx(), forward) else {
+        return;
+    };
+
+    if forward {
+        let event = &cast.events[state.event_idx()];
+        if event.is_output() {
+            buffer.process(&event.data, None);
+        } else if let Some((cols, rows)) = event.parse_resize() {
+            buffer.resize(cols as usize, rows as usize);
+        }
+    } else {
+        seek_to_event_index(buffer, cast, idx, rec_cols, rec_rows);
+    }
+
+    state.set_event_position(idx, cumulative, cast.events.len());
+    state.set_current_time(cumulative, f64::MAX);
+    state.set_time_offset(state.current_time());
+    state.start_time = Instant::now();
+    state.needs_render = true;
+}
+
+/// Handle adding a bookmark at the current playback time.
+fn handle_add_bookmark(state: &mut PlaybackState) {
+    let time = state.current_time();
+    state.bookmarks.insert(time);
+    state.needs_render = true;
+}
+
+/// Handle jump to next (`forward`) or previous bookmark, wrapping around.
+fn handle_jump_to_bookmark(
+    state: &mut PlaybackState,
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    forward: bool,
+    rec_cols: u32,
+    rec_rows: u32,
+) {
+    let current = state.current_time();
+    let target = if forward {
+        state.bookmarks.next_after(current)
+    } else {
+        state.bookmarks.previous_before(current)
+    };
+    if let Some(time) = target {
+        jump_to_time_paused(state, buffer, cast, time, rec_cols, rec_rows);
+    }
+}
+
+/// Seek to an absolute time and pause there (used by marker/bookmark jumps).
+fn jump_to_time_paused(
+    state: &mut PlaybackState,
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    time: f64,
+    rec_cols: u32,
+    rec_rows: u32,
+) {
+    seek_to_time(buffer, cast, time, rec_cols, rec_rows);
+    state.set_current_time(time, f64::MAX);
+    state.set_time_offset(state.current_time());
+    state.start_time = Instant::now();
+    let (idx, cumulative) = find_event_index_at_time(cast, state.current_time());
+    state.set_event_position(idx, cumulative, cast.events.len());
+    state.paused = true;
+    state.needs_render = true;
 }
 
 /// Handle seeking backward by a given amount.
@@ -458,6 +719,173 @@ mod tests {
         KeyEvent::new(code, mods)
     }
 
+    // === Marker list ===
+
+    fn press_with_markers(
+        state: &mut PlaybackState,
+        buffer: &mut TerminalBuffer,
+        markers: &[MarkerPosition],
+        code: KeyCode,
+    ) {
+        let cast = create_test_cast();
+        handle_key_event(
+            create_key_event(code),
+            state,
+            buffer,
+            &cast,
+            markers,
+            0.6,
+            80,
+            24,
+        );
+    }
+
+    fn test_markers() -> Vec<MarkerPosition> {
+        [(0.2, "Start"), (0.35, "Middle"), (0.5, "End")]
+            .into_iter()
+            .map(|(time, label)| MarkerPosition {
+                time,
+                label: label.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn marker_list_enter_seeks_to_selected_marker() {
+        let mut state = create_test_state();
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let markers = test_markers();
+
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Char('l'));
+        assert_eq!(state.marker_list, Some(0));
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Down);
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Down);
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Down);
+        assert_eq!(state.marker_list, Some(2));
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Up);
+
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Enter);
+        assert!(state.marker_list.is_none());
+        assert!((state.current_time() - 0.35).abs() < 1e-9);
+        assert!(state.paused);
+    }
+
+    #[test]
+    fn marker_list_opens_at_next_marker_and_closes_without_seeking() {
+        let mut state = create_test_state();
+        state.set_current_time(0.2, 0.6);
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let markers = test_markers();
+
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Char('l'));
+        assert_eq!(state.marker_list, Some(1));
+        // Other player keys do nothing while the list is open
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Char('q'));
+        assert!(state.marker_list.is_some());
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Char('l'));
+        assert!(state.marker_list.is_none());
+
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Char('l'));
+        press_with_markers(&mut state, &mut buffer, &markers, KeyCode::Esc);
+        assert!(state.marker_list.is_none());
+        assert!((state.current_time() - 0.2).abs() < 1e-9);
+    }
+
+    // === Go-to-time prompt ===
+
+    #[test]
+    fn parse_time_input_accepts_seconds_and_clock_forms() {
+        assert_eq!(parse_time_input("90"), Some(90.0));
+        assert_eq!(parse_time_input(" 12.5 "), Some(12.5));
+        assert_eq!(parse_time_input("1:30"), Some(90.0));
+        assert_eq!(parse_time_input("01:02.5"), Some(62.5));
+        assert_eq!(parse_time_input("75:00"), Some(4500.0));
+        assert_eq!(parse_time_input("1:02:03"), Some(3723.0));
+        assert_eq!(parse_time_input("0:00"), Some(0.0));
+    }
+
+    #[test]
+    fn parse_time_input_rejects_invalid_input() {
+        for input in [
+            "", ":", "1:", ":30", "1::30", "1:75", "1:60:00", "1:2:3:4", "abc", "1e3", "-5",
+            "1.2.3", ".", "1.5:00",
+        ] {
+            assert_eq!(
+                parse_time_input(input),
+                None,
+                "{:?} should be rejected",
+                input
+            );
+        }
+    }
+
+    fn press(state: &mut PlaybackState, buffer: &mut TerminalBuffer, code: KeyCode) {
+        let cast = create_test_cast();
+        handle_key_event(
+            create_key_event(code),
+            state,
+            buffer,
+            &cast,
+            &[],
+            0.6,
+            80,
+            24,
+        );
+    }
+
+    #[test]
+    fn goto_prompt_jumps_to_entered_time() {
+        let mut state = create_test_state();
+        let mut buffer = TerminalBuffer::new(80, 24);
+
+        press(&mut state, &mut buffer, KeyCode::Char('g'));
+        assert_eq!(state.time_prompt.as_deref(), Some(""));
+        // Letters are ignored; backspace removes the last character
+        for c in "0:0x.39".chars() {
+            press(&mut state, &mut buffer, KeyCode::Char(c));
+        }
+        press(&mut state, &mut buffer, KeyCode::Backspace);
+        press(&mut state, &mut buffer, KeyCode::Char('5'));
+        assert_eq!(state.time_prompt.as_deref(), Some("0:0.35"));
+
+        press(&mut state, &mut buffer, KeyCode::Enter);
+        assert!(state.time_prompt.is_none());
+        assert!((state.current_time() - 0.35).abs() < 1e-9);
+        assert_eq!(state.event_idx(), 2);
+        assert!(buffer.to_string().contains("hello world"));
+    }
+
+    #[test]
+    fn goto_prompt_keeps_invalid_input_and_cancels_on_esc() {
+        let mut state = create_test_state();
+        let mut buffer = TerminalBuffer::new(80, 24);
+
+        press(&mut state, &mut buffer, KeyCode::Char('g'));
+        for c in "1:75".chars() {
+            press(&mut state, &mut buffer, KeyCode::Char(c));
+        }
+        press(&mut state, &mut buffer, KeyCode::Enter);
+        assert_eq!(state.time_prompt.as_deref(), Some("1:75"));
+
+        // Esc only closes the prompt; it does not quit the player
+        press(&mut state, &mut buffer, KeyCode::Esc);
+        assert!(state.time_prompt.is_none());
+        assert_eq!(state.current_time(), 0.0);
+    }
+
+    #[test]
+    fn goto_prompt_clamps_to_recording_length() {
+        let mut state = create_test_state();
+        let mut buffer = TerminalBuffer::new(80, 24);
+
+        press(&mut state, &mut buffer, KeyCode::Char('g'));
+        for c in "10:00".chars() {
+            press(&mut state, &mut buffer, KeyCode::Char(c));
+        }
+        press(&mut state, &mut buffer, KeyCode::Enter);
+        assert!((state.current_time() - 0.6).abs() < 1e-9);
+    }
+
     // === handle_key_event dispatch tests ===
 
     #[test]
@@ -1317,6 +1745,235 @@ mod tests {
         assert_eq!(state.current_time(), 15.0); // Unchanged
     }
 
+    // === Key binding tests ===
+
+    #[test]
+    fn handle_key_event_uses_remapped_keys() {
+        let mut state = create_test_state();
+        let keys = std::collections::BTreeMap::from([("quit".to_string(), "x".to_string())]);
+        state.keymap = crate::player::KeyMap::from_config(&keys).unwrap();
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+        let markers = vec![];
+
+        let result = handle_key_event(
+            create_key_event(KeyCode::Char('x')),
+            &mut state,
+            &mut buffer,
+            &cast,
+            &markers,
+            10.0,
+            80,
+            24,
+        );
+        assert_eq!(result, InputResult::Quit);
+
+        let result = handle_key_event(
+            create_key_event(KeyCode::Char('q')),
+            &mut state,
+            &mut buffer,
+            &cast,
+            &markers,
+            10.0,
+            80,
+            24,
+        );
+        assert_eq!(result, InputResult::Continue);
+    }
+
+    #[test]
+    fn handle_key_event_ctrl_c_quits_even_when_remapped() {
+        let mut state = create_test_state();
+        let keys = std::collections::BTreeMap::from([("pause".to_string(), "ctrl+c".to_string())]);
+        state.keymap = crate::player::KeyMap::from_config(&keys).unwrap();
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+        let markers = vec![];
+
+        let result = handle_key_event(
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            &mut state,
+            &mut buffer,
+            &cast,
+            &markers,
+            10.0,
+            80,
+            24,
+        );
+        assert_eq!(result, InputResult::Quit);
+    }
+
+    // === Speed preset tests ===
+
+    #[test]
+    fn handle_key_event_number_keys_set_speed_presets() {
+        let mut state = create_test_state();
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+        let markers = vec![];
+
+        for (ch, expected) in [('3', 4.0), ('2', 2.0), ('1', 1.0)] {
+            let _ = handle_key_event(
+                create_key_event(KeyCode::Char(ch)),
+                &mut state,
+                &mut buffer,
+                &cast,
+                &markers,
+                10.0,
+                80,
+                24,
+            );
+            assert_eq!(state.speed, expected);
+        }
+    }
+
+    // === Frame step tests ===
+
+    #[test]
+    fn handle_key_event_period_steps_one_event_when_paused() {
+        let mut state = create_test_state();
+        state.paused = true;
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+        let markers = vec![];
+
+        let _ = handle_key_event(
+            create_key_event(KeyCode::Char('.')),
+            &mut state,
+            &mut buffer,
+            &cast,
+            &markers,
+            10.0,
+            80,
+            24,
+        );
+
+        assert_eq!(state.event_idx(), 1);
+        assert_eq!(state.cumulative_time(), cast.events[0].time);
+        assert_eq!(state.current_time(), cast.events[0].time);
+    }
+
+    #[test]
+    fn handle_key_event_period_seeks_when_playing() {
+        let mut state = create_test_state();
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+        let markers = vec![];
+
+        let _ = handle_key_event(
+            create_key_event(KeyCode::Char('.')),
+            &mut state,
+            &mut buffer,
+            &cast,
+            &markers,
+            10.0,
+            80,
+            24,
+        );
+
+        assert_eq!(state.current_time(), 5.0);
+    }
+
+    #[test]
+    fn handle_step_event_backward_at_first_event_does_nothing() {
+        let mut state = create_test_state();
+        state.paused = true;
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+
+        handle_step_event(&mut state, &mut buffer, &cast, false, 80, 24);
+
+        assert_eq!(state.event_idx(), 0);
+        assert_eq!(state.current_time(), 0.0);
+    }
+
+    #[test]
+    fn handle_step_event_forward_at_last_event_does_nothing() {
+        let mut state = create_test_state();
+        state.paused = true;
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+        let len = cast.events.len();
+        state.set_event_position(len, 10.0, len);
+
+        handle_step_event(&mut state, &mut buffer, &cast, true, 80, 24);
+
+        assert_eq!(state.event_idx(), len);
+    }
+
+    #[test]
+    fn handle_step_event_back_then_forward_restores_position() {
+        let mut state = create_test_state();
+        state.paused = true;
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+
+        handle_step_event(&mut state, &mut buffer, &cast, true, 80, 24);
+        handle_step_event(&mut state, &mut buffer, &cast, true, 80, 24);
+        let (idx, cumulative) = (state.event_idx(), state.cumulative_time());
+
+        handle_step_event(&mut state, &mut buffer, &cast, false, 80, 24);
+        assert_eq!(state.event_idx(), idx - 1);
+
+        handle_step_event(&mut state, &mut buffer, &cast, true, 80, 24);
+        assert_eq!(state.event_idx(), idx);
+        assert_eq!(state.cumulative_time(), cumulative);
+    }
+
+    // === Bookmark tests ===
+
+    #[test]
+    fn handle_key_event_b_adds_bookmark() {
+        let mut state = create_test_state();
+        state.set_current_time(4.0, 10.0);
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+        let markers = vec![];
+
+        let _ = handle_key_event(
+            create_key_event(KeyCode::Char('b')),
+            &mut state,
+            &mut buffer,
+            &cast,
+            &markers,
+            10.0,
+            80,
+            24,
+        );
+
+        assert_eq!(state.bookmarks.times(), &[4.0]);
+        assert!(state.bookmarks.is_dirty());
+    }
+
+    #[test]
+    fn handle_jump_to_bookmark_forward_and_back() {
+        let mut state = create_test_state();
+        state.bookmarks = crate::player::playback::Bookmarks::from_times([2.0, 6.0]);
+        state.set_current_time(3.0, 10.0);
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+
+        handle_jump_to_bookmark(&mut state, &mut buffer, &cast, true, 80, 24);
+        assert_eq!(state.current_time(), 6.0);
+        assert!(state.paused);
+
+        handle_jump_to_bookmark(&mut state, &mut buffer, &cast, false, 80, 24);
+        assert_eq!(state.current_time(), 2.0);
+    }
+
+    #[test]
+    fn handle_jump_to_bookmark_without_bookmarks_does_nothing() {
+        let mut state = create_test_state();
+        state.set_current_time(3.0, 10.0);
+        let mut buffer = TerminalBuffer::new(80, 24);
+        let cast = create_test_cast();
+
+        handle_jump_to_bookmark(&mut state, &mut buffer, &cast, true, 80, 24);
+
+        assert_eq!(state.current_time(), 3.0);
+        assert!(!state.paused);
+    }
+
     // === Seek to start/end tests ===
 
     #[test]
@@ -0,0 +1,498 @@
+//! Configurable key bindings for the native player.
+//!
+//! Each player [`Action`] has built-in default keys. The `[player.keys]`
+//! config section maps action names to key strings (e.g. `quit = "ctrl+q"`)
+//! which replace the defaults for that action. Keys claimed by a user
+//! binding no longer trigger the action they were bound to by default.
+
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A remappable player action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Pause,
+    Help,
+    ViewportMode,
+    FreeMode,
+    SpeedUp,
+    SpeedDown,
+    SpeedPreset1x,
+    SpeedPreset2x,
+    SpeedPreset4x,
+    Resize,
+    NextMarker,
+    MarkerList,
+    AddBookmark,
+    NextBookmark,
+    PrevBookmark,
+    StepBackward,
+    StepForward,
+    SeekBackward,
+    SeekForward,
+    SeekStart,
+    SeekEnd,
+    GoToTime,
+}
+
+impl Action {
+    /// All actions in lookup order.
+    ///
+    /// Step actions come before seek actions so that, while paused,
+    /// their shared default keys (`,` and `.`) step instead of seeking.
+    pub const ALL: &'static [Action] = &[
+        Action::Quit,
+        Action::Pause,
+        Action::Help,
+        Action::ViewportMode,
+        Action::FreeMode,
+        Action::SpeedUp,
+        Action::SpeedDown,
+        Action::SpeedPreset1x,
+        Action::SpeedPreset2x,
+        Action::SpeedPreset4x,
+        Action::Resize,
+        Action::NextMarker,
+        Action::MarkerList,
+        Action::AddBookmark,
+        Action::NextBookmark,
+        Action::PrevBookmark,
+        Action::StepBackward,
+        Action::StepForward,
+        Action::SeekBackward,
+        Action::SeekForward,
+        Action::SeekStart,
+        Action::SeekEnd,
+        Action::GoToTime,
+    ];
+
+    /// Config name of this action (key in `[player.keys]`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Pause => "pause",
+            Action::Help => "help",
+            Action::ViewportMode => "viewport_mode",
+            Action::FreeMode => "free_mode",
+            Action::SpeedUp => "speed_up",
+            Action::SpeedDown => "speed_down",
+            Action::SpeedPreset1x => "speed_1x",
+            Action::SpeedPreset2x => "speed_2x",
+            Action::SpeedPreset4x => "speed_4x",
+            Action::Resize => "resize",
+            Action::NextMarker => "next_marker",
+            Action::MarkerList => "marker_list",
+            Action::AddBookmark => "add_bookmark",
+            Action::NextBookmark => "next_bookmark",
+            Action::PrevBookmark => "prev_bookmark",
+            Action::StepBackward => "step_backward",
+            Action::StepForward => "step_forward",
+            Action::SeekBackward => "seek_backward",
+            Action::SeekForward => "seek_forward",
+            Action::SeekStart => "seek_start",
+            Action::SeekEnd => "seek_end",
+            Action::GoToTime => "goto_time",
+        }
+    }
+
+    /// Look up an action by its config name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|a| a.name() == name)
+    }
+
+    /// Built-in keys for this action.
+    pub fn default_keys(self) -> &'static [KeyBinding] {
+        macro_rules! c {
+            ($ch:literal) => {
+                KeyBinding {
+                    code: KeyCode::Char($ch),
+                    modifiers: KeyModifiers::NONE,
+                }
+            };
+        }
+        const HOME: KeyBinding = KeyBinding::new(KeyCode::Home, KeyModifiers::NONE);
+        const END: KeyBinding = KeyBinding::new(KeyCode::End, KeyModifiers::NONE);
+        match self {
+            Action::Quit => &[c!('q')],
+            Action::Pause => &[c!(' ')],
+            Action::Help => &[c!('?')],
+            Action::ViewportMode => &[c!('v')],
+            Action::FreeMode => &[c!('f')],
+            Action::SpeedUp => &[c!('+'), c!('=')],
+            Action::SpeedDown => &[c!('-'), c!('_')],
+            Action::SpeedPreset1x => &[c!('1')],
+            Action::SpeedPreset2x => &[c!('2')],
+            Action::SpeedPreset4x => &[c!('3')],
+            Action::Resize => &[c!('r')],
+            Action::NextMarker => &[c!('m')],
+            Action::MarkerList => &[c!('l')],
+            Action::AddBookmark => &[c!('b')],
+            Action::NextBookmark => &[c!('n')],
+            Action::PrevBookmark => &[c!('N')],
+            Action::StepBackward => &[c!(',')],
+            Action::StepForward => &[c!('.')],
+            Action::SeekBackward => &[c!('<'), c!(',')],
+            Action::SeekForward => &[c!('>'), c!('.')],
+            Action::SeekStart => &[HOME],
+            Action::SeekEnd => &[END],
+            Action::GoToTime => &[c!('g')],
+        }
+    }
+
+    /// Whether this action only applies while playback is paused.
+    fn requires_pause(self) -> bool {
+        matches!(self, Action::StepBackward | Action::StepForward)
+    }
+}
+
+/// A single key with modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Create a key binding.
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Check whether a key event triggers this binding.
+    ///
+    /// Shift is ignored for character keys since it is already reflected
+    /// in the character itself (`N` vs `n`).
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let mask = match self.code {
+            KeyCode::Char(_) => KeyModifiers::CONTROL | KeyModifiers::ALT,
+            _ => KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        };
+        self.code == key.code && (key.modifiers & mask) == self.modifiers
+    }
+}
+
+/// Parse a key string like `"q"`, `"space"`, `"ctrl+n"` or `"shift+left"`.
+///
+/// Modifiers (`ctrl`, `alt`, `shift`) are joined to the key with `+`.
+/// A literal plus is written as `"+"` (or `"ctrl++"` with modifiers).
+pub fn parse_key(input: &str) -> Result<KeyBinding, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("key string is empty".to_string());
+    }
+
+    let (mod_part, key_part) = if input == "+" {
+        ("", "+")
+    } else if let Some(prefix) = input.strip_suffix("++") {
+        (prefix, "+")
+    } else {
+        match input.rsplit_once('+') {
+            Some((mods, key)) => (mods, key),
+            None => ("", input),
+        }
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    if !mod_part.is_empty() {
+        for m in mod_part.split('+') {
+            modifiers |= match m.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}'", other)),
+            };
+        }
+    }
+
+    let code = parse_key_code(key_part.trim())?;
+
+    // Normalize character keys: shift is expressed through the character
+    if let KeyCode::Char(ch) = code {
+        let ch = if modifiers.contains(KeyModifiers::SHIFT) {
+            ch.to_ascii_uppercase()
+        } else {
+            ch
+        };
+        modifiers.remove(KeyModifiers::SHIFT);
+        return Ok(KeyBinding::new(KeyCode::Char(ch), modifiers));
+    }
+
+    Ok(KeyBinding::new(code, modifiers))
+}
+
+/// Parse the key name part of a key string (without modifiers).
+fn parse_key_code(name: &str) -> Result<KeyCode, String> {
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(ch));
+    }
+
+    let lower = name.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "space" => KeyCode::Char(' '),
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => {
+            let n = lower
+                .strip_prefix('f')
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| (1..=12).contains(n));
+            match n {
+                Some(n) => KeyCode::F(n),
+                None if name.is_empty() => return Err("missing key after modifier".to_string()),
+                None => return Err(format!("unknown key '{}'", name)),
+            }
+        }
+    };
+    Ok(code)
+}
+
+/// Resolved key bindings for the player.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    /// User bindings from config, which take precedence over defaults
+    overrides: Vec<(Action, KeyBinding)>,
+}
+
+impl KeyMap {
+    /// Build a key map from the `[player.keys]` config table.
+    ///
+    /// Returns an error for unknown action names or invalid key strings.
+    pub fn from_config(keys: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut overrides = Vec::with_capacity(keys.len());
+        for (name, key) in keys {
+            let action = Action::from_name(name).ok_or_else(|| {
+                let valid: Vec<&str> = Action::ALL.iter().map(|a| a.name()).collect();
+                format!(
+                    "player.keys: unknown action '{}'. Valid: {}",
+                    name,
+                    valid.join(", ")
+                )
+            })?;
+            let binding = parse_key(key)
+                .map_err(|e| format!("player.keys.{}: invalid key '{}': {}", name, key, e))?;
+            overrides.push((action, binding));
+        }
+        Ok(Self { overrides })
+    }
+
+    /// Find the action triggered by a key event.
+    ///
+    /// User bindings are consulted first. Default keys apply only to actions
+    /// without a user binding, and only if no user binding claims the key.
+    /// Pause-only actions (frame stepping) are skipped while playing.
+    pub fn action_for(&self, key: &KeyEvent, paused: bool) -> Option<Action> {
+        let available = |action: Action| paused || !action.requires_pause();
+
+        if let Some((action, _)) = self
+            .overrides
+            .iter()
+            .find(|(action, binding)| available(*action) && binding.matches(key))
+        {
+            return Some(*action);
+        }
+
+        if self
+            .overrides
+            .iter()
+            .any(|(_, binding)| binding.matches(key))
+        {
+            return None;
+        }
+
+        Action::ALL.iter().copied().find(|&action| {
+            available(action)
+                && !self.overrides.iter().any(|(a, _)| *a == action)
+                && action.default_keys().iter().any(|b| b.matches(key))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn keymap(pairs: &[(&str, &str)]) -> KeyMap {
+        let keys = pairs
+            .iter()
+            .map(|(a, k)| (a.to_string(), k.to_string()))
+            .collect();
+        KeyMap::from_config(&keys).unwrap()
+    }
+
+    #[test]
+    fn parse_single_char() {
+        assert_eq!(
+            parse_key("q").unwrap(),
+            KeyBinding::new(KeyCode::Char('q'), KeyModifiers::NONE)
+        );
+    }
+
+    #[test]
+    fn parse_ctrl_modifier() {
+        assert_eq!(
+            parse_key("ctrl+n").unwrap(),
+            KeyBinding::new(KeyCode::Char('n'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn parse_multiple_modifiers_case_insensitive() {
+        assert_eq!(
+            parse_key("Ctrl+Alt+x").unwrap(),
+            KeyBinding::new(
+                KeyCode::Char('x'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            )
+        );
+    }
+
+    #[test]
+    fn parse_shift_letter_becomes_uppercase() {
+        assert_eq!(
+            parse_key("shift+n").unwrap(),
+            KeyBinding::new(KeyCode::Char('N'), KeyModifiers::NONE)
+        );
+    }
+
+    #[test]
+    fn parse_named_keys() {
+        assert_eq!(parse_key("space").unwrap().code, KeyCode::Char(' '));
+        assert_eq!(parse_key("Home").unwrap().code, KeyCode::Home);
+        assert_eq!(parse_key("pgdn").unwrap().code, KeyCode::PageDown);
+        assert_eq!(parse_key("f5").unwrap().code, KeyCode::F(5));
+        assert_eq!(
+            parse_key("shift+left").unwrap(),
+            KeyBinding::new(KeyCode::Left, KeyModifiers::SHIFT)
+        );
+    }
+
+    #[test]
+    fn parse_literal_plus() {
+        assert_eq!(parse_key("+").unwrap().code, KeyCode::Char('+'));
+        assert_eq!(
+            parse_key("ctrl++").unwrap(),
+            KeyBinding::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_keys() {
+        assert!(parse_key("").is_err());
+        assert!(parse_key("ctrl+").is_err());
+        assert!(parse_key("hyper+q").is_err());
+        assert!(parse_key("nope").is_err());
+        assert!(parse_key("f13").is_err());
+    }
+
+    #[test]
+    fn from_config_rejects_unknown_action() {
+        let keys = BTreeMap::from([("jump".to_string(), "j".to_string())]);
+        let err = KeyMap::from_config(&keys).unwrap_err();
+        assert!(err.contains("unknown action 'jump'"));
+    }
+
+    #[test]
+    fn from_config_rejects_invalid_key() {
+        let keys = BTreeMap::from([("quit".to_string(), "ctrl+nope".to_string())]);
+        let err = KeyMap::from_config(&keys).unwrap_err();
+        assert!(err.contains("player.keys.quit"));
+    }
+
+    #[test]
+    fn action_names_roundtrip() {
+        for action in Action::ALL {
+            assert_eq!(Action::from_name(action.name()), Some(*action));
+        }
+    }
+
+    #[test]
+    fn default_keymap_uses_builtin_keys() {
+        let map = KeyMap::default();
+        assert_eq!(
+            map.action_for(&key(KeyCode::Char('q')), false),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            map.action_for(&key(KeyCode::Char('=')), false),
+            Some(Action::SpeedUp)
+        );
+        assert_eq!(map.action_for(&key(KeyCode::Char('z')), false), None);
+    }
+
+    #[test]
+    fn default_step_keys_apply_only_when_paused() {
+        let map = KeyMap::default();
+        let period = key(KeyCode::Char('.'));
+        assert_eq!(map.action_for(&period, false), Some(Action::SeekForward));
+        assert_eq!(map.action_for(&period, true), Some(Action::StepForward));
+    }
+
+    #[test]
+    fn user_binding_replaces_default_for_action() {
+        let map = keymap(&[("quit", "x")]);
+        assert_eq!(
+            map.action_for(&key(KeyCode::Char('x')), false),
+            Some(Action::Quit)
+        );
+        assert_eq!(map.action_for(&key(KeyCode::Char('q')), false), None);
+    }
+
+    #[test]
+    fn user_binding_takes_precedence_over_other_defaults() {
+        // 'm' is next_marker by default; rebinding it to pause wins
+        let map = keymap(&[("pause", "m")]);
+        assert_eq!(
+            map.action_for(&key(KeyCode::Char('m')), false),
+            Some(Action::Pause)
+        );
+        // next_marker no longer has a usable key, space is unclaimed
+        assert_eq!(map.action_for(&key(KeyCode::Char(' ')), false), None);
+    }
+
+    #[test]
+    fn unmapped_actions_keep_defaults() {
+        let map = keymap(&[("quit", "ctrl+q")]);
+        assert_eq!(
+            map.action_for(&key(KeyCode::Char(' ')), false),
+            Some(Action::Pause)
+        );
+    }
+
+    #[test]
+    fn modifier_bindings_require_modifier() {
+        let map = keymap(&[("next_marker", "ctrl+n")]);
+        let ctrl_n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(map.action_for(&ctrl_n, false), Some(Action::NextMarker));
+        // Plain 'n' is still next_bookmark
+        assert_eq!(
+            map.action_for(&key(KeyCode::Char('n')), false),
+            Some(Action::NextBookmark)
+        );
+    }
+
+    #[test]
+    fn shifted_char_event_matches_uppercase_binding() {
+        let map = KeyMap::default();
+        let shift_n = KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert_eq!(map.action_for(&shift_n, false), Some(Action::PrevBookmark));
+    }
+}
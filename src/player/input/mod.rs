@@ -4,9 +4,11 @@
 //! them to the appropriate handlers and returning control flow signals.
 
 mod keyboard;
+mod keymap;
 mod mouse;
 
 pub use keyboard::handle_key_event;
+pub use keymap::{Action, KeyMap};
 pub use mouse::handle_mouse_event;
 
 use crate::player::state::InputResult;
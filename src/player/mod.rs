@@ -8,8 +8,9 @@
 //!
 //! The player is organized into submodules:
 //! - `state`: PlaybackState struct and shared types (MarkerPosition, InputResult)
-//! - `input/`: Keyboard and mouse input handling
+//! - `input/`: Keyboard and mouse input handling, configurable key bindings
 //! - `playback/`: Seeking, marker collection, and time management
+//! - `record`: Recording the rendered viewport with `agr play --record-to`
 //! - `render/`: UI rendering (viewport, progress bar, status bar, help, scroll indicators)
 //!
 //! # Usage
@@ -29,8 +30,14 @@
 pub(crate) mod input;
 mod native;
 pub(crate) mod playback;
+mod record;
 pub mod render;
 pub mod state;
 
-pub use native::{play_session, play_session_native, PlaybackResult};
+pub use input::{Action, KeyMap};
+
+pub use native::{
+    play_session, play_session_native, play_source_native, LoopMode, PlayOptions, PlaybackResult,
+};
+pub use record::{FrameClock, SessionRecorder};
 pub use state::{InputResult, MarkerPosition, PlaybackState};
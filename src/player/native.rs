@@ -8,7 +8,7 @@
 //! - Help overlay
 
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::Result;
@@ -19,14 +19,18 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
 
-use crate::asciicast::AsciicastFile;
+use crate::asciicast::{AsciicastFile, CastSource};
+use crate::config::Config;
 use crate::terminal::TerminalBuffer;
+use crate::theme::PlayerColors;
 
-use super::input::handle_event;
-use super::playback::collect_markers;
+use super::input::{handle_event, KeyMap};
+use super::playback::{collect_markers, Bookmarks};
+use super::record::SessionRecorder;
 use super::render::{
-    render_help, render_progress_bar, render_scroll_indicator, render_separator_line,
-    render_single_line, render_status_bar, render_viewport,
+    render_dirty_rows, render_help, render_marker_list, render_progress_bar,
+    render_scroll_indicator, render_separator_line, render_single_line, render_status_bar,
+    render_time_prompt, render_viewport,
 };
 use super::state::{InputResult, PlaybackState};
 
@@ -52,9 +56,95 @@ impl PlaybackResult {
     }
 }
 
+/// How many times the recording should be played before playback ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopMode {
+    /// Play once and stop (default)
+    #[default]
+    Once,
+    /// Restart from the beginning until the user quits
+    Forever,
+    /// Play this many more iterations (including the current one)
+    Times(u32),
+}
+
+impl LoopMode {
+    /// Build a loop mode from the `--loop` / `--loop-count` CLI flags.
+    ///
+    /// `--loop-count` implies looping; a count of 1 plays once. The CLI
+    /// rejects a count of 0.
+    pub fn from_flags(loop_forever: bool, loop_count: Option<u32>) -> Self {
+        match loop_count {
+            Some(n) if n > 1 => LoopMode::Times(n),
+            Some(_) => LoopMode::Once,
+            None if loop_forever => LoopMode::Forever,
+            None => LoopMode::Once,
+        }
+    }
+
+    /// Advance past a finished iteration.
+    ///
+    /// Returns the mode for the next iteration, or `None` if playback is done.
+    pub fn next_iteration(self) -> Option<Self> {
+        match self {
+            LoopMode::Once => None,
+            LoopMode::Forever => Some(LoopMode::Forever),
+            LoopMode::Times(n) if n <= 1 => None,
+            LoopMode::Times(2) => Some(LoopMode::Once),
+            LoopMode::Times(n) => Some(LoopMode::Times(n - 1)),
+        }
+    }
+}
+
+/// Options controlling a playback session.
+#[derive(Debug, Clone)]
+pub struct PlayOptions {
+    /// Initial playback speed multiplier (clamped to the player's bounds)
+    pub speed: f64,
+    /// Whether (and how often) to restart when the recording ends
+    pub loop_mode: LoopMode,
+    /// Key bindings (defaults plus `[player.keys]` overrides)
+    pub keymap: KeyMap,
+    /// Fail on malformed event lines instead of skipping them
+    pub strict: bool,
+    /// Progress and status bar colors (from `[theme]`)
+    pub colors: PlayerColors,
+    /// Record the rendered viewport of this session to a new cast file
+    pub record_to: Option<PathBuf>,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            loop_mode: LoopMode::default(),
+            keymap: KeyMap::default(),
+            strict: false,
+            colors: PlayerColors::default(),
+            record_to: None,
+        }
+    }
+}
+
+impl PlayOptions {
+    /// Build options from the `[player]` and `[theme]` config sections.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let keymap = config
+            .player
+            .key_map()
+            .map_err(|e| anyhow::anyhow!("Invalid config: {}", e))?;
+        Ok(Self {
+            speed: config.player.default_speed,
+            keymap,
+            colors: PlayerColors::from_theme(&config.theme.theme()),
+            ..Self::default()
+        })
+    }
+}
+
 /// Play a session using the native renderer (default).
 pub fn play_session(path: &Path) -> Result<PlaybackResult> {
-    play_session_native(path)
+    play_session_native(path, &PlayOptions::default())
 }
 
 /// Play a session using the native renderer.
@@ -63,24 +153,39 @@ pub fn play_session(path: &Path) -> Result<PlaybackResult> {
 /// playback at any terminal size. The virtual terminal matches the original
 /// recording dimensions, and a viewport shows the visible portion.
 ///
+/// Malformed event lines are skipped with a warning on stderr unless
+/// `options.strict` is set, in which case they fail the load.
+///
 /// Controls:
 /// - q/Esc: Quit
 /// - Space: Pause/resume
 /// - Arrow keys: Seek (or scroll in viewport mode)
-/// - +/-: Adjust speed
-/// - m: Jump to next marker
+/// - +/-: Adjust speed, 1/2/3: Speed presets (1x/2x/4x)
+/// - m: Jump to next marker, l: Marker list
+/// - b: Add bookmark, n/N: Next/previous bookmark
 /// - </> or ,/.: Seek backward/forward 5s
+/// - ,/. while paused: Step backward/forward one event
 /// - Home/End: Go to start/end
+/// - g: Go to a typed time (mm:ss or seconds)
 /// - v: Toggle viewport mode
 /// - r: Resize terminal to recording size
 /// - ?: Show help
-pub fn play_session_native(path: &Path) -> Result<PlaybackResult> {
-    let cast = AsciicastFile::parse(path)?;
-    let name = path
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
+pub fn play_session_native(path: &Path, options: &PlayOptions) -> Result<PlaybackResult> {
+    play_source_native(&CastSource::File(path.to_path_buf()), options)
+}
+
+/// Play a recording from a file or stdin using the native renderer.
+///
+/// Same as [`play_session_native`]. A recording from stdin is read fully
+/// before playback starts; bookmarks are only kept for files. With
+/// `options.record_to`, the rendered viewport is saved there as a new cast
+/// when playback ends.
+pub fn play_source_native(source: &CastSource, options: &PlayOptions) -> Result<PlaybackResult> {
+    let (cast, report) = source.parse(options.strict)?;
+    if let Some(warning) = report.warning() {
+        eprintln!("Warning: {}", warning);
+    }
+    let name = source.name();
 
     // Get recording dimensions and duration
     let (rec_cols, rec_rows) = cast.terminal_size();
@@ -97,6 +202,16 @@ pub fn play_session_native(path: &Path) -> Result<PlaybackResult> {
 
     // Initialize playback state
     let mut state = PlaybackState::new(term_cols, term_rows);
+    if let Some(path) = source.path() {
+        state.bookmarks = Bookmarks::load(path).unwrap_or_default();
+    }
+    state.keymap = options.keymap.clone();
+    state.colors = options.colors.clone();
+    state.speed = PlaybackState::clamp_speed(options.speed);
+    let mut recorder = options
+        .record_to
+        .as_ref()
+        .map(|_| SessionRecorder::new(state.view_cols, state.view_rows));
 
     // Setup terminal
     let mut stdout = io::stdout();
@@ -113,15 +228,62 @@ pub fn play_session_native(path: &Path) -> Result<PlaybackResult> {
         rec_cols,
         rec_rows,
         &name,
+        options.loop_mode,
+        recorder.as_mut(),
     );
 
     // Cleanup
     execute!(stdout, Show, DisableMouseCapture, LeaveAlternateScreen)?;
     crossterm::terminal::disable_raw_mode()?;
 
+    // Persist bookmarks added during this session
+    if let Some(path) = source.path().filter(|_| state.bookmarks.is_dirty()) {
+        if let Err(e) = state.bookmarks.save(path) {
+            eprintln!("Warning: failed to save bookmarks: {}", e);
+        }
+    }
+
+    if let (Some(recorder), Some(path)) = (recorder, &options.record_to) {
+        recorder.save(path)?;
+    }
+
     result
 }
 
+/// The viewport geometry a frame was drawn with.
+///
+/// While it stays the same between frames, only rows the terminal buffer
+/// marked dirty need to be redrawn. A buffer resize changes it too, since
+/// rows that no longer exist are not flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ViewportFrame {
+    row_offset: usize,
+    col_offset: usize,
+    view_rows: usize,
+    view_cols: usize,
+    term_cols: u16,
+    term_rows: u16,
+    buffer_cols: usize,
+    buffer_rows: usize,
+    highlight_line: Option<usize>,
+}
+
+impl ViewportFrame {
+    fn of(state: &PlaybackState, buffer: &TerminalBuffer) -> Self {
+        Self {
+            row_offset: state.view_row_offset(),
+            col_offset: state.view_col_offset(),
+            view_rows: state.view_rows,
+            view_cols: state.view_cols,
+            term_cols: state.term_cols,
+            term_rows: state.term_rows,
+            buffer_cols: buffer.width(),
+            buffer_rows: buffer.height(),
+            highlight_line: state.free_mode.then(|| state.free_line()),
+        }
+    }
+}
+
 /// Main playback loop
 #[allow(clippy::too_many_arguments)]
 fn run_main_loop(
@@ -134,7 +296,11 @@ fn run_main_loop(
     rec_cols: u32,
     rec_rows: u32,
     name: &str,
+    mut loop_mode: LoopMode,
+    mut recorder: Option<&mut SessionRecorder>,
 ) -> Result<PlaybackResult> {
+    // Geometry of the last full viewport render; `None` forces a full redraw
+    let mut last_frame: Option<ViewportFrame> = None;
     loop {
         // Handle all pending input events before rendering
         // First poll waits up to 16ms, then drain any queued events with zero timeout
@@ -203,6 +369,7 @@ fn run_main_loop(
 
         if state.show_help {
             render_help(stdout, state.term_cols, state.term_rows)?;
+            last_frame = None;
         } else {
             // Begin synchronized update to prevent flicker
             write!(stdout, "\x1b[?2026h")?;
@@ -210,8 +377,9 @@ fn run_main_loop(
             // Partial update: only re-render changed highlight lines in free mode
             // Skip all UI chrome (progress bar, status bar, etc.) for partial updates
             if state.free_line_only && state.free_mode {
+                let mut viewport = Vec::new();
                 render_single_line(
-                    stdout,
+                    &mut viewport,
                     buffer,
                     state.prev_free_line,
                     state.view_row_offset(),
@@ -220,7 +388,7 @@ fn run_main_loop(
                     false, // not highlighted
                 )?;
                 render_single_line(
-                    stdout,
+                    &mut viewport,
                     buffer,
                     state.free_line(),
                     state.view_row_offset(),
@@ -228,25 +396,46 @@ fn run_main_loop(
                     state.view_cols,
                     true, // highlighted
                 )?;
+                draw_viewport(stdout, recorder.as_deref_mut(), &viewport)?;
                 state.free_line_only = false;
+                if let Some(frame) = last_frame.as_mut() {
+                    frame.highlight_line = Some(state.free_line());
+                }
                 // End synchronized update and skip UI chrome
                 write!(stdout, "\x1b[?2026l")?;
                 stdout.flush()?;
+                if let Some(recorder) = recorder.as_deref_mut() {
+                    recorder.end_frame(state.view_cols, state.view_rows);
+                }
                 continue; // Skip the sleep at end of loop for faster response
             } else {
-                render_viewport(
-                    stdout,
-                    buffer,
-                    state.view_row_offset(),
-                    state.view_col_offset(),
-                    state.view_rows,
-                    state.view_cols,
-                    if state.free_mode {
-                        Some(state.free_line())
-                    } else {
-                        None
-                    },
-                )?;
+                // Redraw only changed rows unless the viewport moved or resized
+                let frame = ViewportFrame::of(state, buffer);
+                let mut viewport = Vec::new();
+                if last_frame == Some(frame) {
+                    render_dirty_rows(
+                        &mut viewport,
+                        buffer,
+                        frame.row_offset,
+                        frame.col_offset,
+                        frame.view_rows,
+                        frame.view_cols,
+                        frame.highlight_line,
+                    )?;
+                } else {
+                    render_viewport(
+                        &mut viewport,
+                        buffer,
+                        frame.row_offset,
+                        frame.col_offset,
+                        frame.view_rows,
+                        frame.view_cols,
+                        frame.highlight_line,
+                    )?;
+                    last_frame = Some(frame);
+                }
+                draw_viewport(stdout, recorder.as_deref_mut(), &viewport)?;
+                buffer.clear_dirty();
 
                 // Show scroll indicator if viewport can scroll
                 render_scroll_indicator(
@@ -260,7 +449,12 @@ fn run_main_loop(
                     buffer.width(),
                 )?;
 
-                render_separator_line(stdout, state.term_cols, state.term_rows.saturating_sub(3))?;
+                render_separator_line(
+                    stdout,
+                    state.term_cols,
+                    state.term_rows.saturating_sub(3),
+                    &state.colors,
+                )?;
 
                 render_progress_bar(
                     stdout,
@@ -269,24 +463,49 @@ fn run_main_loop(
                     state.current_time(),
                     total_duration,
                     markers,
+                    &state.colors,
                 )?;
 
-                render_status_bar(
-                    stdout,
-                    state.term_cols,
-                    state.term_rows.saturating_sub(1),
-                    state.paused,
-                    state.speed,
-                    buffer.width() as u32,
-                    buffer.height() as u32,
-                    state.view_cols,
-                    state.view_rows,
-                    state.view_col_offset(),
-                    state.view_row_offset(),
-                    markers.len(),
-                    state.viewport_mode,
-                    state.free_mode,
-                )?;
+                if let Some(input) = &state.time_prompt {
+                    render_time_prompt(
+                        stdout,
+                        state.term_cols,
+                        state.term_rows.saturating_sub(1),
+                        input,
+                        &state.colors,
+                    )?;
+                } else {
+                    render_status_bar(
+                        stdout,
+                        state.term_cols,
+                        state.term_rows.saturating_sub(1),
+                        state.paused,
+                        state.speed,
+                        buffer.width() as u32,
+                        buffer.height() as u32,
+                        state.view_cols,
+                        state.view_rows,
+                        state.view_col_offset(),
+                        state.view_row_offset(),
+                        markers.len(),
+                        state.viewport_mode,
+                        state.free_mode,
+                        &state.colors,
+                    )?;
+                }
+
+                // The panel covers part of the viewport, so redraw it in full next time
+                if let Some(selected) = state.marker_list {
+                    render_marker_list(
+                        stdout,
+                        state.term_cols,
+                        state.view_rows,
+                        markers,
+                        selected,
+                        &state.colors,
+                    )?;
+                    last_frame = None;
+                }
 
                 // End synchronized update
                 write!(stdout, "\x1b[?2026l")?;
@@ -294,16 +513,56 @@ fn run_main_loop(
         }
 
         stdout.flush()?;
+        if let Some(recorder) = recorder.as_deref_mut() {
+            recorder.end_frame(state.view_cols, state.view_rows);
+        }
 
         if state.event_idx() >= cast.events.len() && !state.paused {
             std::thread::sleep(Duration::from_millis(500));
-            return Ok(PlaybackResult::Success(name.to_string()));
+            match loop_mode.next_iteration() {
+                Some(next) => {
+                    loop_mode = next;
+                    restart_playback(buffer, state, cast, rec_cols, rec_rows);
+                }
+                None => return Ok(PlaybackResult::Success(name.to_string())),
+            }
         }
 
         std::thread::sleep(Duration::from_millis(8));
     }
 }
 
+/// Write rendered viewport output, copying it to the session recorder if any.
+fn draw_viewport(
+    stdout: &mut io::Stdout,
+    recorder: Option<&mut SessionRecorder>,
+    viewport: &[u8],
+) -> Result<()> {
+    stdout.write_all(viewport)?;
+    if let Some(recorder) = recorder {
+        recorder.capture(viewport);
+    }
+    Ok(())
+}
+
+/// Reset buffer and playback position to the start for the next loop iteration.
+fn restart_playback(
+    buffer: &mut TerminalBuffer,
+    state: &mut PlaybackState,
+    cast: &AsciicastFile,
+    rec_cols: u32,
+    rec_rows: u32,
+) {
+    *buffer = TerminalBuffer::new(rec_cols as usize, rec_rows as usize);
+    state.set_current_time(0.0, f64::MAX);
+    state.set_time_offset(0.0);
+    state.start_time = std::time::Instant::now();
+    state.set_event_position(0, 0.0, cast.events.len());
+    state.set_view_row_offset(0, 0);
+    state.set_view_col_offset(0, 0);
+    state.needs_render = true;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,4 +598,78 @@ mod tests {
         let debug_str = format!("{:?}", result);
         assert!(debug_str.contains("Interrupted"));
     }
+
+    #[test]
+    fn loop_mode_once_stops_after_first_iteration() {
+        assert_eq!(LoopMode::Once.next_iteration(), None);
+    }
+
+    #[test]
+    fn loop_mode_forever_never_stops() {
+        assert_eq!(LoopMode::Forever.next_iteration(), Some(LoopMode::Forever));
+    }
+
+    #[test]
+    fn loop_mode_times_counts_down_to_once() {
+        let mut mode = LoopMode::Times(3);
+        let mut iterations = 1;
+        while let Some(next) = mode.next_iteration() {
+            mode = next;
+            iterations += 1;
+        }
+        assert_eq!(iterations, 3);
+        assert_eq!(
+            LoopMode::Times(3).next_iteration(),
+            Some(LoopMode::Times(2))
+        );
+        assert_eq!(LoopMode::Times(2).next_iteration(), Some(LoopMode::Once));
+    }
+
+    #[test]
+    fn loop_mode_times_zero_or_one_stops() {
+        assert_eq!(LoopMode::Times(1).next_iteration(), None);
+        assert_eq!(LoopMode::Times(0).next_iteration(), None);
+    }
+
+    #[test]
+    fn loop_mode_from_flags() {
+        assert_eq!(LoopMode::from_flags(false, None), LoopMode::Once);
+        assert_eq!(LoopMode::from_flags(true, None), LoopMode::Forever);
+        assert_eq!(LoopMode::from_flags(false, Some(3)), LoopMode::Times(3));
+        assert_eq!(LoopMode::from_flags(true, Some(2)), LoopMode::Times(2));
+        assert_eq!(LoopMode::from_flags(true, Some(1)), LoopMode::Once);
+    }
+
+    #[test]
+    fn play_options_default_plays_once() {
+        assert_eq!(PlayOptions::default().loop_mode, LoopMode::Once);
+    }
+
+    #[test]
+    fn play_options_from_config_uses_default_speed() {
+        let mut config = Config::default();
+        config.player.default_speed = 2.0;
+        assert_eq!(PlayOptions::from_config(&config).unwrap().speed, 2.0);
+    }
+
+    #[test]
+    fn play_options_from_config_rejects_invalid_keys() {
+        let mut config = Config::default();
+        config
+            .player
+            .keys
+            .insert("quit".to_string(), "ctrl+".to_string());
+        assert!(PlayOptions::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn play_options_from_config_uses_theme_colors() {
+        let mut config = Config::default();
+        config.theme.name = "ocean".to_string();
+        let options = PlayOptions::from_config(&config).unwrap();
+        assert_eq!(
+            options.colors,
+            PlayerColors::from_theme(&crate::theme::Theme::ocean())
+        );
+    }
 }
@@ -0,0 +1,243 @@
+//! Bookmark tracking and persistence for the native player.
+//!
+//! Bookmarks are user-placed positions in a recording, distinct from the
+//! markers stored inside the cast file. They are kept in a plain-text
+//! `<file>.bookmarks` sidecar (one timestamp in seconds per line) so they
+//! survive between playback sessions without modifying the recording.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Minimum distance (in seconds) from the current time for a bookmark
+/// to count as "next" or "previous". Matches marker navigation tolerance.
+const NAVIGATION_EPSILON: f64 = 0.1;
+
+/// Get the bookmarks sidecar path for a given cast file.
+///
+/// The sidecar path is the original path with `.bookmarks` appended.
+pub fn bookmarks_path_for(path: &Path) -> PathBuf {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".bookmarks");
+    PathBuf::from(sidecar)
+}
+
+/// Sorted list of bookmark times for a recording.
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    /// Bookmark times in seconds, sorted ascending
+    times: Vec<f64>,
+    /// True when bookmarks changed since load (needs saving)
+    dirty: bool,
+}
+
+impl Bookmarks {
+    /// Create a bookmark list from arbitrary times (sorted and deduplicated).
+    pub fn from_times(times: impl IntoIterator<Item = f64>) -> Self {
+        let mut bookmarks = Self::default();
+        for time in times {
+            bookmarks.insert(time);
+        }
+        bookmarks.dirty = false;
+        bookmarks
+    }
+
+    /// Load bookmarks from the sidecar next to `cast_path`.
+    ///
+    /// Returns an empty list if no sidecar exists.
+    pub fn load(cast_path: &Path) -> Result<Self> {
+        let sidecar = bookmarks_path_for(cast_path);
+        if !sidecar.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&sidecar)
+            .with_context(|| format!("Failed to read bookmarks: {}", sidecar.display()))?;
+        Ok(Self::parse(&content))
+    }
+
+    /// Save bookmarks to the sidecar next to `cast_path`.
+    pub fn save(&self, cast_path: &Path) -> Result<()> {
+        let sidecar = bookmarks_path_for(cast_path);
+        fs::write(&sidecar, self.serialize())
+            .with_context(|| format!("Failed to write bookmarks: {}", sidecar.display()))
+    }
+
+    /// Parse sidecar content. Blank and unparseable lines are skipped.
+    pub fn parse(content: &str) -> Self {
+        Self::from_times(
+            content
+                .lines()
+                .filter_map(|line| line.trim().parse::<f64>().ok())
+                .filter(|t| t.is_finite() && *t >= 0.0),
+        )
+    }
+
+    /// Serialize bookmarks to sidecar content (one time per line).
+    pub fn serialize(&self) -> String {
+        self.times.iter().map(|t| format!("{:.3}\n", t)).collect()
+    }
+
+    /// Add a bookmark at `time`. Returns false if one already exists there.
+    pub fn insert(&mut self, time: f64) -> bool {
+        let time = time.max(0.0);
+        if self
+            .times
+            .iter()
+            .any(|t| (t - time).abs() < NAVIGATION_EPSILON)
+        {
+            return false;
+        }
+        let idx = self.times.partition_point(|t| *t < time);
+        self.times.insert(idx, time);
+        self.dirty = true;
+        true
+    }
+
+    /// Find the next bookmark after `current`, wrapping to the first.
+    pub fn next_after(&self, current: f64) -> Option<f64> {
+        self.times
+            .iter()
+            .copied()
+            .find(|t| *t > current + NAVIGATION_EPSILON)
+            .or_else(|| self.times.first().copied())
+    }
+
+    /// Find the previous bookmark before `current`, wrapping to the last.
+    pub fn previous_before(&self, current: f64) -> Option<f64> {
+        self.times
+            .iter()
+            .rev()
+            .copied()
+            .find(|t| *t < current - NAVIGATION_EPSILON)
+            .or_else(|| self.times.last().copied())
+    }
+
+    /// Get all bookmark times (sorted ascending).
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Number of bookmarks.
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Check if there are no bookmarks.
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Check if bookmarks changed since they were loaded.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn bookmarks_path_appends_suffix() {
+        let path = bookmarks_path_for(Path::new("/tmp/session.cast"));
+        assert_eq!(path, PathBuf::from("/tmp/session.cast.bookmarks"));
+    }
+
+    #[test]
+    fn insert_keeps_times_sorted() {
+        let mut bookmarks = Bookmarks::default();
+        assert!(bookmarks.insert(10.0));
+        assert!(bookmarks.insert(2.0));
+        assert!(bookmarks.insert(5.0));
+        assert_eq!(bookmarks.times(), &[2.0, 5.0, 10.0]);
+        assert!(bookmarks.is_dirty());
+    }
+
+    #[test]
+    fn insert_rejects_duplicate_position() {
+        let mut bookmarks = Bookmarks::default();
+        assert!(bookmarks.insert(5.0));
+        assert!(!bookmarks.insert(5.05));
+        assert_eq!(bookmarks.len(), 1);
+    }
+
+    #[test]
+    fn next_after_finds_following_bookmark() {
+        let bookmarks = Bookmarks::from_times([2.0, 5.0, 10.0]);
+        assert_eq!(bookmarks.next_after(3.0), Some(5.0));
+        assert_eq!(bookmarks.next_after(5.0), Some(10.0)); // skips current
+    }
+
+    #[test]
+    fn next_after_wraps_to_first() {
+        let bookmarks = Bookmarks::from_times([2.0, 5.0, 10.0]);
+        assert_eq!(bookmarks.next_after(10.0), Some(2.0));
+        assert_eq!(bookmarks.next_after(50.0), Some(2.0));
+    }
+
+    #[test]
+    fn previous_before_finds_preceding_bookmark() {
+        let bookmarks = Bookmarks::from_times([2.0, 5.0, 10.0]);
+        assert_eq!(bookmarks.previous_before(7.0), Some(5.0));
+        assert_eq!(bookmarks.previous_before(5.0), Some(2.0)); // skips current
+    }
+
+    #[test]
+    fn previous_before_wraps_to_last() {
+        let bookmarks = Bookmarks::from_times([2.0, 5.0, 10.0]);
+        assert_eq!(bookmarks.previous_before(2.0), Some(10.0));
+        assert_eq!(bookmarks.previous_before(0.0), Some(10.0));
+    }
+
+    #[test]
+    fn navigation_on_empty_returns_none() {
+        let bookmarks = Bookmarks::default();
+        assert_eq!(bookmarks.next_after(1.0), None);
+        assert_eq!(bookmarks.previous_before(1.0), None);
+    }
+
+    #[test]
+    fn single_bookmark_wraps_to_itself() {
+        let bookmarks = Bookmarks::from_times([4.0]);
+        assert_eq!(bookmarks.next_after(4.0), Some(4.0));
+        assert_eq!(bookmarks.previous_before(4.0), Some(4.0));
+    }
+
+    #[test]
+    fn serialize_writes_one_time_per_line() {
+        let bookmarks = Bookmarks::from_times([12.5, 1.25]);
+        assert_eq!(bookmarks.serialize(), "1.250\n12.500\n");
+    }
+
+    #[test]
+    fn parse_skips_invalid_lines() {
+        let bookmarks = Bookmarks::parse("3.0\n\nnot-a-number\n-1.0\n1.5\n");
+        assert_eq!(bookmarks.times(), &[1.5, 3.0]);
+        assert!(!bookmarks.is_dirty());
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let cast_path = dir.path().join("session.cast");
+
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.insert(42.125);
+        bookmarks.insert(7.0);
+        bookmarks.save(&cast_path).unwrap();
+
+        assert!(bookmarks_path_for(&cast_path).exists());
+        let loaded = Bookmarks::load(&cast_path).unwrap();
+        assert_eq!(loaded.times(), &[7.0, 42.125]);
+        assert!(!loaded.is_dirty());
+    }
+
+    #[test]
+    fn load_without_sidecar_is_empty() {
+        let dir = TempDir::new().unwrap();
+        let loaded = Bookmarks::load(&dir.path().join("missing.cast")).unwrap();
+        assert!(loaded.is_empty());
+    }
+}
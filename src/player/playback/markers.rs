@@ -57,7 +57,7 @@ mod tests {
     fn empty_cast_returns_no_markers() {
         let cast = AsciicastFile {
             header: make_header(),
-            events: vec![],
+            events: vec![].into(),
         };
         let markers = collect_markers(&cast);
         assert!(markers.is_empty());
@@ -78,7 +78,8 @@ mod tests {
                     event_type: EventType::Output,
                     data: "world".to_string(),
                 },
-            ],
+            ]
+            .into(),
         };
         let markers = collect_markers(&cast);
         assert!(markers.is_empty());
@@ -109,7 +110,8 @@ mod tests {
                     event_type: EventType::Marker,
                     data: "marker2".to_string(),
                 },
-            ],
+            ]
+            .into(),
         };
         let markers = collect_markers(&cast);
         assert_eq!(markers.len(), 2);
@@ -134,7 +136,8 @@ mod tests {
                     event_type: EventType::Output,
                     data: "output".to_string(),
                 },
-            ],
+            ]
+            .into(),
         };
         let markers = collect_markers(&cast);
         assert_eq!(markers.len(), 1);
@@ -1,9 +1,13 @@
 //! Playback logic for the native player.
 //!
-//! This module handles seeking, marker collection, and playback time management.
+//! This module handles seeking, marker collection, bookmarks, and playback time management.
 
+mod bookmarks;
 mod markers;
 mod seeking;
 
+pub use bookmarks::Bookmarks;
 pub use markers::collect_markers;
-pub use seeking::{find_event_index_at_time, seek_to_time};
+pub use seeking::{
+    find_event_index_at_time, seek_to_event_index, seek_to_time, step_event_position,
+};
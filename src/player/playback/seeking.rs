@@ -16,16 +16,15 @@ use crate::terminal::TerminalBuffer;
 ///
 /// # Returns
 /// A tuple of (event_index, cumulative_time_before_that_event)
+///
+/// Uses a binary search over the recording's cached cumulative times.
 pub fn find_event_index_at_time(cast: &AsciicastFile, target_time: f64) -> (usize, f64) {
-    let mut cumulative = 0.0f64;
-    for (i, event) in cast.events.iter().enumerate() {
-        let next_cumulative = cumulative + event.time;
-        if next_cumulative > target_time {
-            return (i, cumulative);
-        }
-        cumulative = next_cumulative;
-    }
-    (cast.events.len(), cumulative)
+    // Cumulative times never decrease (negative intervals count as zero), so
+    // binary search for the first event past the target
+    let times = cast.cumulative_times();
+    let idx = times.partition_point(|&time| time <= target_time);
+    let cumulative = if idx == 0 { 0.0 } else { times[idx - 1] };
+    (idx, cumulative)
 }
 
 /// Seek to a specific time by re-rendering the buffer from scratch.
@@ -61,6 +60,56 @@ pub fn seek_to_time(
     }
 }
 
+/// Compute the event position one event after (`forward`) or before the current one.
+///
+/// `event_idx` is the index of the next event to be processed, so the buffer
+/// reflects events `0..event_idx`. Stepping forward moves past one more event;
+/// stepping backward un-applies the most recently processed event.
+///
+/// # Returns
+/// `Some((new_event_index, cumulative_time_before_that_event))`, or `None`
+/// if already at the first (backward) or past the last (forward) event.
+pub fn step_event_position(
+    cast: &AsciicastFile,
+    event_idx: usize,
+    forward: bool,
+) -> Option<(usize, f64)> {
+    let new_idx = if forward {
+        if event_idx >= cast.events.len() {
+            return None;
+        }
+        event_idx + 1
+    } else {
+        event_idx.checked_sub(1)?.min(cast.events.len())
+    };
+    let cumulative = match new_idx {
+        0 => 0.0,
+        idx => cast.cumulative_times()[idx - 1],
+    };
+    Some((new_idx, cumulative))
+}
+
+/// Rebuild the buffer from scratch with exactly the first `event_idx` events applied.
+///
+/// Like [`seek_to_time`], but addresses a position by event index so that
+/// events sharing a timestamp can be stepped through individually.
+pub fn seek_to_event_index(
+    buffer: &mut TerminalBuffer,
+    cast: &AsciicastFile,
+    event_idx: usize,
+    cols: u32,
+    rows: u32,
+) {
+    *buffer = TerminalBuffer::new(cols as usize, rows as usize);
+    for event in cast.events.iter().take(event_idx) {
+        if event.is_output() {
+            buffer.process(&event.data, None);
+        } else if let Some((new_cols, new_rows)) = event.parse_resize() {
+            buffer.resize(new_cols as usize, new_rows as usize);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,7 +141,7 @@ mod tests {
             .collect();
         AsciicastFile {
             header: make_header(),
-            events,
+            events: events.into(),
         }
     }
 
@@ -152,7 +201,8 @@ mod tests {
                 time: 1.0,
                 event_type: EventType::Output,
                 data: "hello".to_string(),
-            }],
+            }]
+            .into(),
         };
         let mut buffer = TerminalBuffer::new(80, 24);
         buffer.process("some content", None);
@@ -172,7 +222,8 @@ mod tests {
                 time: 1.0,
                 event_type: EventType::Output,
                 data: "hello".to_string(),
-            }],
+            }]
+            .into(),
         };
         let mut buffer = TerminalBuffer::new(80, 24);
 
@@ -199,7 +250,8 @@ mod tests {
                     event_type: EventType::Output,
                     data: "text".to_string(),
                 },
-            ],
+            ]
+            .into(),
         };
         let mut buffer = TerminalBuffer::new(80, 24);
 
@@ -210,4 +262,156 @@ mod tests {
         let content: String = row.iter().take(4).map(|c| c.char).collect();
         assert_eq!(content, "text");
     }
+
+    #[test]
+    fn step_forward_from_first_event() {
+        let cast = make_cast(&[1.0, 2.0, 3.0]);
+        assert_eq!(step_event_position(&cast, 0, true), Some((1, 1.0)));
+    }
+
+    #[test]
+    fn step_forward_onto_last_event() {
+        let cast = make_cast(&[1.0, 2.0, 3.0]);
+        assert_eq!(step_event_position(&cast, 2, true), Some((3, 6.0)));
+    }
+
+    #[test]
+    fn step_forward_past_last_event_is_none() {
+        let cast = make_cast(&[1.0, 2.0, 3.0]);
+        assert_eq!(step_event_position(&cast, 3, true), None);
+    }
+
+    #[test]
+    fn step_backward_from_end() {
+        let cast = make_cast(&[1.0, 2.0, 3.0]);
+        assert_eq!(step_event_position(&cast, 3, false), Some((2, 3.0)));
+    }
+
+    #[test]
+    fn step_backward_to_first_event() {
+        let cast = make_cast(&[1.0, 2.0, 3.0]);
+        assert_eq!(step_event_position(&cast, 1, false), Some((0, 0.0)));
+    }
+
+    #[test]
+    fn step_backward_at_start_is_none() {
+        let cast = make_cast(&[1.0, 2.0, 3.0]);
+        assert_eq!(step_event_position(&cast, 0, false), None);
+    }
+
+    #[test]
+    fn step_uses_cached_cumulative_times() {
+        let cast = make_cast(&[1.0, 2.0, 3.0]);
+        assert!(!cast.events.is_cached());
+        assert_eq!(step_event_position(&cast, 1, true), Some((2, 3.0)));
+        assert!(cast.events.is_cached());
+    }
+
+    #[test]
+    fn step_on_empty_cast_is_none() {
+        let cast = make_cast(&[]);
+        assert_eq!(step_event_position(&cast, 0, true), None);
+        assert_eq!(step_event_position(&cast, 0, false), None);
+    }
+
+    #[test]
+    fn seek_to_event_index_applies_exact_event_count() {
+        let cast = AsciicastFile {
+            header: make_header(),
+            events: vec![
+                Event::output(0.5, "ab"),
+                Event::output(0.0, "cd"), // same timestamp as previous event
+                Event::output(0.5, "ef"),
+            ]
+            .into(),
+        };
+        let mut buffer = TerminalBuffer::new(80, 24);
+
+        seek_to_event_index(&mut buffer, &cast, 1, 80, 24);
+        let content: String = buffer
+            .row(0)
+            .unwrap()
+            .iter()
+            .take(6)
+            .map(|c| c.char)
+            .collect();
+        assert_eq!(content, "ab    ");
+
+        seek_to_event_index(&mut buffer, &cast, 2, 80, 24);
+        let content: String = buffer
+            .row(0)
+            .unwrap()
+            .iter()
+            .take(6)
+            .map(|c| c.char)
+            .collect();
+        assert_eq!(content, "abcd  ");
+    }
+
+    /// The original linear scan, kept as a reference for the binary search.
+    fn find_event_index_linear(cast: &AsciicastFile, target_time: f64) -> (usize, f64) {
+        let mut cumulative = 0.0f64;
+        for (i, event) in cast.events.iter().enumerate() {
+            let next_cumulative = cumulative + event.time;
+            if next_cumulative > target_time {
+                return (i, cumulative);
+            }
+            cumulative = next_cumulative;
+        }
+        (cast.events.len(), cumulative)
+    }
+
+    /// Deterministic pseudo-random numbers in `0.0..1.0` (64-bit LCG).
+    fn pseudo_random(seed: u64) -> impl FnMut() -> f64 {
+        let mut state = seed;
+        move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
+    #[test]
+    fn binary_search_matches_linear_scan() {
+        let mut next = pseudo_random(42);
+        // Mix of regular gaps, zero gaps (same timestamp) and long pauses
+        let times: Vec<f64> = (0..2_000)
+            .map(|i| match i % 7 {
+                0 => 0.0,
+                3 => next() * 10.0,
+                _ => next() * 0.2,
+            })
+            .collect();
+        let cast = make_cast(&times);
+        let duration = cast.duration();
+
+        for _ in 0..5_000 {
+            let target = next() * (duration + 2.0) - 1.0;
+            assert_eq!(
+                find_event_index_at_time(&cast, target),
+                find_event_index_linear(&cast, target),
+                "target {}",
+                target
+            );
+        }
+        // Exact event timestamps are the boundary cases
+        for &time in cast.cumulative_times() {
+            assert_eq!(
+                find_event_index_at_time(&cast, time),
+                find_event_index_linear(&cast, time)
+            );
+        }
+    }
+
+    #[test]
+    fn negative_interval_counts_as_zero_when_seeking() {
+        // Invalid negative intervals are clamped, so times never go backwards
+        let cast = make_cast(&[1.0, -0.5, 1.0]);
+        assert_eq!(find_event_index_at_time(&cast, 0.7), (0, 0.0));
+        assert_eq!(find_event_index_at_time(&cast, 1.0), (2, 1.0));
+        assert_eq!(find_event_index_at_time(&cast, 1.9), (2, 1.0));
+        assert_eq!(find_event_index_at_time(&cast, 2.0), (3, 2.0));
+        assert_eq!(step_event_position(&cast, 1, true), Some((2, 1.0)));
+    }
 }
@@ -0,0 +1,219 @@
+//! Recording a playback session with `agr play --record-to`.
+//!
+//! The [`SessionRecorder`] receives a copy of everything the player draws
+//! into the viewport. Each rendered frame becomes one output event, timed
+//! by the wall-clock gap since the previous frame, so seeks, pauses and
+//! speed changes show up in the new cast exactly as they did on screen.
+//! The player chrome (progress bar, status bar, overlays) is not part of
+//! the recording.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use crate::asciicast::{AsciicastFile, Event, EventType, Header, TermInfo};
+
+/// Turns frame times into relative asciicast event times.
+#[derive(Debug, Clone, Default)]
+pub struct FrameClock {
+    last: Duration,
+}
+
+impl FrameClock {
+    /// Create a clock for a session that starts now.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Relative event time of a frame drawn `at` after the session started.
+    ///
+    /// A frame time earlier than the previous one counts as no delay, so
+    /// the resulting event times never go backwards. Times are rounded to
+    /// microseconds.
+    pub fn frame_delta(&mut self, at: Duration) -> f64 {
+        let delta = at.saturating_sub(self.last);
+        self.last = self.last.max(at);
+        (delta.as_secs_f64() * 1_000_000.0).round() / 1_000_000.0
+    }
+}
+
+/// Collects the rendered viewport frames of a playback session.
+#[derive(Debug)]
+pub struct SessionRecorder {
+    started: Instant,
+    clock: FrameClock,
+    size: (usize, usize),
+    frame: Vec<u8>,
+    cast: AsciicastFile,
+}
+
+impl SessionRecorder {
+    /// Start recording a viewport of `cols` x `rows`.
+    pub fn new(cols: usize, rows: usize) -> Self {
+        let cast = AsciicastFile::new(Header {
+            version: 3,
+            width: None,
+            height: None,
+            term: Some(TermInfo {
+                cols: Some(cols as u32),
+                rows: Some(rows as u32),
+                term_type: None,
+            }),
+            timestamp: Some(chrono::Utc::now().timestamp()),
+            duration: None,
+            title: None,
+            command: None,
+            env: None,
+            idle_time_limit: None,
+        });
+        Self {
+            started: Instant::now(),
+            clock: FrameClock::new(),
+            size: (cols, rows),
+            frame: Vec::new(),
+            cast,
+        }
+    }
+
+    /// Add viewport output to the frame being drawn.
+    pub fn capture(&mut self, output: &[u8]) {
+        self.frame.extend_from_slice(output);
+    }
+
+    /// Finish the current frame, drawn with a viewport of `cols` x `rows`.
+    pub fn end_frame(&mut self, cols: usize, rows: usize) {
+        self.end_frame_at(self.started.elapsed(), cols, rows);
+    }
+
+    /// Finish the current frame as if drawn `at` after the session started.
+    ///
+    /// Frames without viewport output add no event. A changed viewport
+    /// size is recorded as a resize event before the frame.
+    pub fn end_frame_at(&mut self, at: Duration, cols: usize, rows: usize) {
+        if self.frame.is_empty() {
+            return;
+        }
+        let mut time = self.clock.frame_delta(at);
+        if (cols, rows) != self.size {
+            self.size = (cols, rows);
+            self.cast.events.push(Event::new(
+                time,
+                EventType::Resize,
+                format!("{}x{}", cols, rows),
+            ));
+            time = 0.0;
+        }
+        let data = String::from_utf8_lossy(&self.frame).into_owned();
+        self.cast.events.push(Event::output(time, data));
+        self.frame.clear();
+    }
+
+    /// The recording so far.
+    pub fn cast(&self) -> &AsciicastFile {
+        &self.cast
+    }
+
+    /// Write the recording to `path`.
+    pub fn save(mut self, path: &Path) -> Result<()> {
+        self.cast.stamp_duration();
+        self.cast
+            .write(path)
+            .with_context(|| format!("Failed to write playback recording: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn frame_delta_is_the_gap_since_the_previous_frame() {
+        let mut clock = FrameClock::new();
+        assert_eq!(clock.frame_delta(ms(16)), 0.016);
+        assert_eq!(clock.frame_delta(ms(32)), 0.016);
+        // A two second pause between frames
+        assert_eq!(clock.frame_delta(ms(2032)), 2.0);
+    }
+
+    #[test]
+    fn frame_delta_never_goes_backwards() {
+        let mut clock = FrameClock::new();
+        assert_eq!(clock.frame_delta(ms(500)), 0.5);
+        assert_eq!(clock.frame_delta(ms(400)), 0.0);
+        // Measured from the latest frame, not the out-of-order one
+        assert_eq!(clock.frame_delta(ms(600)), 0.1);
+    }
+
+    #[test]
+    fn frame_delta_rounds_to_microseconds() {
+        let mut clock = FrameClock::new();
+        assert_eq!(clock.frame_delta(Duration::from_nanos(1_234_567)), 0.001235);
+    }
+
+    #[test]
+    fn frames_become_output_events_with_monotonic_times() {
+        let mut recorder = SessionRecorder::new(80, 24);
+        let frame_times = [ms(10), ms(26), ms(26), ms(1500), ms(1516)];
+        for (i, at) in frame_times.iter().enumerate() {
+            recorder.capture(format!("frame {}", i).as_bytes());
+            recorder.end_frame_at(*at, 80, 24);
+        }
+
+        let cast = recorder.cast();
+        assert_eq!(cast.events.len(), 5);
+        assert!(cast.events.iter().all(|e| e.is_output() && e.time >= 0.0));
+        assert_eq!(cast.events[3].data, "frame 3");
+        let absolute = cast.cumulative_times();
+        assert!(absolute.windows(2).all(|w| w[0] <= w[1]));
+        assert!((cast.duration() - 1.516).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_frames_add_no_event_and_keep_their_time() {
+        let mut recorder = SessionRecorder::new(80, 24);
+        recorder.capture(b"first");
+        recorder.end_frame_at(ms(100), 80, 24);
+        recorder.end_frame_at(ms(200), 80, 24);
+        recorder.capture(b"second");
+        recorder.end_frame_at(ms(300), 80, 24);
+
+        let times: Vec<f64> = recorder.cast().events.iter().map(|e| e.time).collect();
+        assert_eq!(times, vec![0.1, 0.2]);
+    }
+
+    #[test]
+    fn viewport_resize_is_recorded_before_the_frame() {
+        let mut recorder = SessionRecorder::new(80, 24);
+        recorder.capture(b"small");
+        recorder.end_frame_at(ms(100), 80, 24);
+        recorder.capture(b"large");
+        recorder.end_frame_at(ms(250), 120, 40);
+
+        let events = &recorder.cast().events;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1].parse_resize(), Some((120, 40)));
+        assert_eq!(events[1].time, 0.15);
+        assert_eq!(events[2].data, "large");
+        assert_eq!(events[2].time, 0.0);
+    }
+
+    #[test]
+    fn save_writes_a_playable_cast() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("review.cast");
+        let mut recorder = SessionRecorder::new(100, 30);
+        recorder.capture(b"\x1b[1;1Hhello");
+        recorder.end_frame_at(ms(40), 100, 30);
+        recorder.save(&path).unwrap();
+
+        let cast = AsciicastFile::parse(&path).unwrap();
+        assert_eq!(cast.terminal_size(), (100, 30));
+        assert_eq!(cast.events.len(), 1);
+        assert_eq!(cast.header.duration, Some(0.04));
+    }
+}
@@ -23,11 +23,19 @@ pub const HELP_LINES: &[&str] = &[
     "  ║    Space      Pause / Resume              ║",
     "  ║    <-/->      Seek +/-5s                  ║",
     "  ║    Shift+<-/->  Seek +/-5%                ║",
+    "  ║    ,/.        Step one event (paused)     ║",
     "  ║    +/-        Speed up / down             ║",
+    "  ║    1/2/3      Speed 1x / 2x / 4x          ║",
     "  ║    Home/End   Go to start / end           ║",
+    "  ║    g          Go to time (mm:ss / secs)   ║",
     "  ║                                           ║",
     "  ║  Markers                                  ║",
     "  ║    m          Jump to next marker         ║",
+    "  ║    l          List markers (Enter seeks)  ║",
+    "  ║                                           ║",
+    "  ║  Bookmarks                                ║",
+    "  ║    b          Add bookmark                ║",
+    "  ║    n/N        Next / previous bookmark    ║",
     "  ║                                           ║",
     "  ║  Free Mode (line-by-line navigation)      ║",
     "  ║    f          Toggle free mode            ║",
@@ -0,0 +1,229 @@
+//! Marker list overlay for the native player.
+//!
+//! Draws a panel at the right edge of the viewport listing every marker
+//! with its time and label. The selected marker is highlighted and the
+//! list scrolls to keep it visible.
+
+use std::io::{self, Write};
+
+use anyhow::Result;
+use unicode_width::UnicodeWidthChar;
+
+use super::progress::format_duration;
+use crate::player::state::MarkerPosition;
+use crate::theme::{ansi_code, PlayerColors};
+
+/// Widest the panel gets, borders included.
+pub const MARKER_PANEL_MAX_WIDTH: usize = 60;
+
+/// Truncate `text` to at most `width` columns, ending in `…` when cut.
+fn fit_width(text: &str, width: usize) -> String {
+    let total: usize = text.chars().map(|c| c.width().unwrap_or(0)).sum();
+    if total <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if width > 0 {
+        out.truncate(out.trim_end().len());
+        out.push('…');
+    }
+    out
+}
+
+/// First marker shown so that `selected` is visible in `rows` lines.
+fn scroll_start(count: usize, selected: usize, rows: usize) -> usize {
+    if rows == 0 || count <= rows {
+        0
+    } else {
+        selected.saturating_sub(rows - 1).min(count - rows)
+    }
+}
+
+/// Format the visible marker rows, each `width` columns or narrower.
+///
+/// Rows read `> mm:ss  label` for the selected marker and `  mm:ss  label`
+/// otherwise. At most `rows` markers are returned, scrolled so that the
+/// selected one is included.
+pub fn marker_list_lines(
+    markers: &[MarkerPosition],
+    selected: usize,
+    width: usize,
+    rows: usize,
+) -> Vec<String> {
+    if markers.is_empty() {
+        return vec![fit_width("  No markers in this recording", width)];
+    }
+    let start = scroll_start(markers.len(), selected, rows);
+    markers
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(rows)
+        .map(|(i, marker)| {
+            let pointer = if i == selected { '>' } else { ' ' };
+            let line = format!(
+                "{} {}  {}",
+                pointer,
+                format_duration(marker.time),
+                marker.label
+            );
+            fit_width(&line, width)
+        })
+        .collect()
+}
+
+/// Seek time of the marker at `selected`, if there is one.
+pub fn marker_seek_time(markers: &[MarkerPosition], selected: usize) -> Option<f64> {
+    markers.get(selected).map(|marker| marker.time)
+}
+
+/// Render the marker list panel over the right side of the viewport.
+///
+/// # Arguments
+/// * `stdout` - The stdout handle to write to
+/// * `term_cols` - Terminal width
+/// * `view_rows` - Number of viewport rows the panel may cover
+/// * `markers` - All markers of the recording
+/// * `selected` - Index of the highlighted marker
+/// * `colors` - Theme colors for the border, times and selection
+pub fn render_marker_list(
+    stdout: &mut io::Stdout,
+    term_cols: u16,
+    view_rows: usize,
+    markers: &[MarkerPosition],
+    selected: usize,
+    colors: &PlayerColors,
+) -> Result<()> {
+    let width = (term_cols as usize).min(MARKER_PANEL_MAX_WIDTH);
+    if width < 4 || view_rows < 3 {
+        return Ok(());
+    }
+    let inner = width - 2;
+    let list_rows = view_rows - 2;
+    let col = term_cols as usize - width + 1;
+    let reset = ansi_code("\x1b[0m");
+
+    let title = format!(" Markers ({}) ", markers.len());
+    let title = fit_width(&title, inner);
+    let title_len: usize = title.chars().map(|c| c.width().unwrap_or(0)).sum();
+
+    let mut output = String::with_capacity(width * (view_rows + 2) * 2);
+    output.push_str(&format!("\x1b[1;{}H{}┌{}", col, colors.dim, colors.key));
+    output.push_str(&title);
+    output.push_str(&colors.dim);
+    output.push_str(&"─".repeat(inner - title_len));
+    output.push('┐');
+
+    let lines = marker_list_lines(markers, selected, inner, list_rows);
+    let start = scroll_start(markers.len(), selected, list_rows);
+    for row in 0..list_rows {
+        output.push_str(&format!("\x1b[{};{}H{}│", row + 2, col, colors.dim));
+        let line = lines.get(row).map(String::as_str).unwrap_or("");
+        let is_selected = !markers.is_empty() && start + row == selected;
+        output.push_str(if is_selected {
+            &colors.marker
+        } else {
+            &colors.text
+        });
+        output.push_str(line);
+        let used: usize = line.chars().map(|c| c.width().unwrap_or(0)).sum();
+        output.push_str(&" ".repeat(inner.saturating_sub(used)));
+        output.push_str(&colors.dim);
+        output.push('│');
+    }
+
+    let hint = fit_width(" enter:seek esc:close ", inner);
+    let hint_len: usize = hint.chars().map(|c| c.width().unwrap_or(0)).sum();
+    output.push_str(&format!("\x1b[{};{}H{}└", view_rows, col, colors.dim));
+    output.push_str(&hint);
+    output.push_str(&"─".repeat(inner - hint_len));
+    output.push('┘');
+    output.push_str(reset);
+
+    write!(stdout, "{}", output)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn markers(count: usize) -> Vec<MarkerPosition> {
+        (0..count)
+            .map(|i| MarkerPosition {
+                time: i as f64 * 65.0,
+                label: format!("Marker {}", i),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lines_show_time_label_and_selection() {
+        let lines = marker_list_lines(&markers(3), 1, 40, 10);
+        assert_eq!(
+            lines,
+            vec![
+                "  00:00  Marker 0",
+                "> 01:05  Marker 1",
+                "  02:10  Marker 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn long_labels_are_cut_to_the_panel_width() {
+        let markers = vec![MarkerPosition {
+            time: 5.0,
+            label: "[FAILURE] Tests fail after the parser rewrite".to_string(),
+        }];
+        let lines = marker_list_lines(&markers, 0, 20, 5);
+        assert_eq!(lines, vec!["> 00:05  [FAILURE]…"]);
+        assert!(lines[0].chars().count() <= 20);
+    }
+
+    #[test]
+    fn list_scrolls_to_keep_selection_visible() {
+        let markers = markers(10);
+        let first = |selected| marker_list_lines(&markers, selected, 40, 4)[0].clone();
+        assert_eq!(first(2), "  00:00  Marker 0");
+        assert_eq!(first(5), "  02:10  Marker 2");
+        let last = marker_list_lines(&markers, 9, 40, 4);
+        assert_eq!(last.len(), 4);
+        assert_eq!(last[3], "> 09:45  Marker 9");
+    }
+
+    #[test]
+    fn empty_marker_list_has_a_placeholder() {
+        assert_eq!(
+            marker_list_lines(&[], 0, 40, 5),
+            vec!["  No markers in this recording"]
+        );
+    }
+
+    #[test]
+    fn selected_index_maps_to_marker_time() {
+        let markers = markers(3);
+        assert_eq!(marker_seek_time(&markers, 0), Some(0.0));
+        assert_eq!(marker_seek_time(&markers, 2), Some(130.0));
+        assert_eq!(marker_seek_time(&markers, 3), None);
+        assert_eq!(marker_seek_time(&[], 0), None);
+    }
+
+    #[test]
+    fn render_marker_list_does_not_panic() {
+        let mut stdout = io::stdout();
+        let colors = PlayerColors::default();
+        assert!(render_marker_list(&mut stdout, 80, 20, &markers(30), 12, &colors).is_ok());
+        assert!(render_marker_list(&mut stdout, 3, 20, &markers(3), 0, &colors).is_ok());
+        assert!(render_marker_list(&mut stdout, 80, 2, &[], 0, &colors).is_ok());
+    }
+}
@@ -7,6 +7,7 @@ use std::io::{self, Write};
 use anyhow::Result;
 
 use crate::player::state::MarkerPosition;
+use crate::theme::{ansi_code, PlayerColors};
 
 /// Format a duration in seconds to MM:SS format.
 ///
@@ -79,6 +80,7 @@ pub fn build_progress_bar_chars(
 /// * `current_time` - Current playback time
 /// * `total_duration` - Total duration of the recording
 /// * `markers` - Slice of marker positions
+/// * `colors` - Theme colors for the bar, markers and time
 pub fn render_progress_bar(
     stdout: &mut io::Stdout,
     width: u16,
@@ -86,6 +88,7 @@ pub fn render_progress_bar(
     current_time: f64,
     total_duration: f64,
     markers: &[MarkerPosition],
+    colors: &PlayerColors,
 ) -> Result<()> {
     // Compute time display first to determine its actual length
     let current_str = format_duration(current_time);
@@ -99,38 +102,32 @@ pub fn render_progress_bar(
     // Build output string
     let mut output = String::with_capacity(width as usize * 4);
     output.push_str(&format!("\x1b[{};1H", row + 1)); // Move cursor
-    output.push_str("\x1b[48;5;236m "); // Dark gray background + padding
+    output.push_str(ansi_code("\x1b[48;5;236m")); // Dark gray background
+    output.push(' '); // Padding
 
-    // ANSI color codes
-    const GREEN: &str = "\x1b[32m";
-    const YELLOW: &str = "\x1b[33m";
-    const WHITE: &str = "\x1b[97m";
-    const DARK_GREY: &str = "\x1b[90m";
-    const GREY: &str = "\x1b[37m";
-
-    output.push_str(GREEN);
+    output.push_str(&colors.progress);
     for (i, &c) in bar.iter().enumerate() {
         if i < filled {
             if c == '◆' {
-                output.push_str(YELLOW);
+                output.push_str(&colors.marker);
                 output.push(c);
-                output.push_str(GREEN);
+                output.push_str(&colors.progress);
             } else {
                 output.push('━');
             }
         } else if i == filled {
-            output.push_str(WHITE);
+            output.push_str(&colors.text);
             output.push(c);
         } else if c == '◆' {
-            output.push_str(YELLOW);
+            output.push_str(&colors.marker);
             output.push(c);
         } else {
-            output.push_str(DARK_GREY);
+            output.push_str(&colors.dim);
             output.push(c);
         }
     }
 
-    output.push_str(GREY);
+    output.push_str(&colors.text);
     output.push_str(&time_display);
 
     // Fill remaining width
@@ -140,7 +137,7 @@ pub fn render_progress_bar(
         output.push(' ');
     }
 
-    output.push_str("\x1b[0m"); // Reset
+    output.push_str(ansi_code("\x1b[0m")); // Reset
     write!(stdout, "{}", output)?;
 
     Ok(())
@@ -6,6 +6,8 @@ use std::io::{self, Write};
 
 use anyhow::Result;
 
+use crate::theme::{ansi_code, PlayerColors};
+
 /// Count digits in a number (for width calculation).
 ///
 /// # Arguments
@@ -28,14 +30,20 @@ pub fn count_digits(n: usize) -> usize {
 /// * `stdout` - The stdout handle to write to
 /// * `width` - Terminal width
 /// * `row` - Row to render at (0-indexed)
-pub fn render_separator_line(stdout: &mut io::Stdout, width: u16, row: u16) -> Result<()> {
+/// * `colors` - Theme colors (the line uses the dim color)
+pub fn render_separator_line(
+    stdout: &mut io::Stdout,
+    width: u16,
+    row: u16,
+    colors: &PlayerColors,
+) -> Result<()> {
     // Build line as string to minimize syscalls
     let mut output = String::with_capacity(width as usize + 20);
-    output.push_str(&format!("\x1b[{};1H\x1b[90m", row + 1)); // Move + dark gray
+    output.push_str(&format!("\x1b[{};1H{}", row + 1, colors.dim)); // Move + dim
     for _ in 0..width {
         output.push('─');
     }
-    output.push_str("\x1b[0m"); // Reset
+    output.push_str(ansi_code("\x1b[0m")); // Reset
     write!(stdout, "{}", output)?;
     Ok(())
 }
@@ -57,6 +65,7 @@ pub fn render_separator_line(stdout: &mut io::Stdout, width: u16, row: u16) -> R
 /// * `marker_count` - Number of markers in the recording
 /// * `viewport_mode` - Whether viewport mode is active
 /// * `free_mode` - Whether free mode is active
+/// * `colors` - Theme colors for labels, values and key hints
 #[allow(clippy::too_many_arguments)]
 pub fn render_status_bar(
     stdout: &mut io::Stdout,
@@ -73,22 +82,16 @@ pub fn render_status_bar(
     marker_count: usize,
     viewport_mode: bool,
     free_mode: bool,
+    colors: &PlayerColors,
 ) -> Result<()> {
-    // ANSI color codes
-    const WHITE: &str = "\x1b[97m";
-    const MAGENTA: &str = "\x1b[35m";
-    const GREEN: &str = "\x1b[32m";
-    const DARK_GREY: &str = "\x1b[90m";
-    const YELLOW: &str = "\x1b[33m";
-    const CYAN: &str = "\x1b[36m";
-    const RESET: &str = "\x1b[0m";
+    let reset = ansi_code("\x1b[0m");
 
     let mut output = String::with_capacity(256);
     let mut visible_len: usize = 0; // Track visible width manually
 
     output.push_str(&format!("\x1b[{};1H", row + 1));
 
-    output.push_str(WHITE);
+    output.push_str(&colors.text);
     output.push(' ');
     visible_len += 1;
 
@@ -98,83 +101,83 @@ pub fn render_status_bar(
     visible_len += 4; // icon (2) + 2 spaces
 
     if viewport_mode {
-        output.push_str(MAGENTA);
+        output.push_str(&colors.key);
         output.push_str("[V] ");
         visible_len += 4;
     }
 
     if free_mode {
-        output.push_str(GREEN);
+        output.push_str(&colors.free_mode);
         output.push_str("[F] ");
         visible_len += 4;
     }
 
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str("spd:");
     visible_len += 4;
-    output.push_str(WHITE);
+    output.push_str(&colors.text);
     let speed_str = format!("{:.1}x ", speed);
     visible_len += speed_str.len();
     output.push_str(&speed_str);
 
     if marker_count > 0 {
-        output.push_str(YELLOW);
+        output.push_str(&colors.marker);
         let marker_str = format!("◆{} ", marker_count);
         visible_len += 1 + count_digits(marker_count) + 1; // ◆ + digits + space
         output.push_str(&marker_str);
     }
 
     if rec_cols as usize > view_cols || rec_rows as usize > view_rows {
-        output.push_str(DARK_GREY);
+        output.push_str(&colors.dim);
         let offset_str = format!("[{},{}] ", col_offset, row_offset);
         visible_len += offset_str.len();
         output.push_str(&offset_str);
     }
 
     let play_action = if paused { ":play " } else { ":pause " };
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str("│ ");
     visible_len += 2;
-    output.push_str(CYAN);
+    output.push_str(&colors.key);
     output.push_str("space");
     visible_len += 5;
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str(play_action);
     visible_len += play_action.len();
-    output.push_str(CYAN);
+    output.push_str(&colors.key);
     output.push('m');
     visible_len += 1;
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str(":mrk ");
     visible_len += 5;
-    output.push_str(CYAN);
+    output.push_str(&colors.key);
     output.push('f');
     visible_len += 1;
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str(":fre ");
     visible_len += 5;
-    output.push_str(CYAN);
+    output.push_str(&colors.key);
     output.push('v');
     visible_len += 1;
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str(":vpt ");
     visible_len += 5;
-    output.push_str(CYAN);
+    output.push_str(&colors.key);
     output.push('r');
     visible_len += 1;
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str(":rsz ");
     visible_len += 5;
-    output.push_str(CYAN);
+    output.push_str(&colors.key);
     output.push('?');
     visible_len += 1;
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str(":hlp ");
     visible_len += 5;
-    output.push_str(CYAN);
+    output.push_str(&colors.key);
     output.push('q');
     visible_len += 1;
-    output.push_str(DARK_GREY);
+    output.push_str(&colors.dim);
     output.push_str(":quit");
     visible_len += 5;
 
@@ -184,12 +187,53 @@ pub fn render_status_bar(
         output.push(' ');
     }
 
-    output.push_str(RESET);
+    output.push_str(reset);
     write!(stdout, "{}", output)?;
 
     Ok(())
 }
 
+/// Render the go-to-time prompt in place of the status bar.
+///
+/// # Arguments
+/// * `stdout` - The stdout handle to write to
+/// * `width` - Terminal width
+/// * `row` - Row to render at (0-indexed)
+/// * `input` - Text typed so far
+/// * `colors` - Theme colors for the label, input and hints
+pub fn render_time_prompt(
+    stdout: &mut io::Stdout,
+    width: u16,
+    row: u16,
+    input: &str,
+    colors: &PlayerColors,
+) -> Result<()> {
+    const LABEL: &str = " Go to (mm:ss or seconds): ";
+    const HINT: &str = "  enter:jump esc:cancel";
+
+    let mut output = String::with_capacity(128);
+    output.push_str(&format!("\x1b[{};1H", row + 1));
+    output.push_str(&colors.dim);
+    output.push_str(LABEL);
+    output.push_str(&colors.text);
+    output.push_str(input);
+    output.push_str(&colors.key);
+    output.push('_');
+    output.push_str(&colors.dim);
+    output.push_str(HINT);
+    let visible_len = LABEL.len() + input.len() + 1 + HINT.len();
+
+    // Pad to full width to overwrite the status bar
+    let padding = (width as usize).saturating_sub(visible_len);
+    for _ in 0..padding {
+        output.push(' ');
+    }
+
+    output.push_str(ansi_code("\x1b[0m"));
+    write!(stdout, "{}", output)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,16 +272,24 @@ mod tests {
     fn render_separator_line_does_not_panic() {
         // Just verify it doesn't panic with various inputs
         let mut stdout = io::stdout();
-        assert!(render_separator_line(&mut stdout, 80, 0).is_ok());
-        assert!(render_separator_line(&mut stdout, 80, 10).is_ok());
-        assert!(render_separator_line(&mut stdout, 120, 5).is_ok());
+        assert!(render_separator_line(&mut stdout, 80, 0, &PlayerColors::default()).is_ok());
+        assert!(render_separator_line(&mut stdout, 80, 10, &PlayerColors::default()).is_ok());
+        assert!(render_separator_line(&mut stdout, 120, 5, &PlayerColors::default()).is_ok());
     }
 
     #[test]
     fn render_separator_line_handles_small_width() {
         let mut stdout = io::stdout();
-        assert!(render_separator_line(&mut stdout, 10, 0).is_ok());
-        assert!(render_separator_line(&mut stdout, 1, 0).is_ok());
+        assert!(render_separator_line(&mut stdout, 10, 0, &PlayerColors::default()).is_ok());
+        assert!(render_separator_line(&mut stdout, 1, 0, &PlayerColors::default()).is_ok());
+    }
+
+    #[test]
+    fn render_time_prompt_does_not_panic() {
+        let mut stdout = io::stdout();
+        let colors = PlayerColors::default();
+        assert!(render_time_prompt(&mut stdout, 80, 25, "", &colors).is_ok());
+        assert!(render_time_prompt(&mut stdout, 10, 0, "12:34", &colors).is_ok());
     }
 
     #[test]
@@ -258,6 +310,7 @@ mod tests {
             0,     // marker_count
             false, // viewport_mode
             false, // free_mode
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -280,6 +333,7 @@ mod tests {
             0,
             false,
             false,
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -302,6 +356,7 @@ mod tests {
             0,
             true, // viewport_mode
             false,
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -324,6 +379,7 @@ mod tests {
             0,
             false,
             true, // free_mode
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -346,6 +402,7 @@ mod tests {
             5, // marker_count
             false,
             false,
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -369,6 +426,7 @@ mod tests {
             0,
             false,
             false,
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -391,6 +449,7 @@ mod tests {
             0,
             false,
             false,
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -413,6 +472,7 @@ mod tests {
             0,
             false,
             false,
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -435,6 +495,7 @@ mod tests {
             0,
             false,
             false,
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -458,6 +519,7 @@ mod tests {
             7,     // marker_count
             true,  // viewport_mode
             false, // free_mode (can't be both)
+            &PlayerColors::default(),
         );
         assert!(result.is_ok());
     }
@@ -2,11 +2,10 @@
 //!
 //! Renders the terminal buffer content within the visible viewport area.
 
-use std::io::{self, Write};
+use std::io::Write;
 
 use anyhow::Result;
 
-use crate::player::render::ansi::{style_to_ansi_attrs, style_to_ansi_bg, style_to_ansi_fg};
 use crate::terminal::{CellStyle, TerminalBuffer};
 
 /// Render a viewport of the terminal buffer to stdout.
@@ -22,8 +21,8 @@ use crate::terminal::{CellStyle, TerminalBuffer};
 /// * `view_cols` - Number of visible columns
 /// * `highlight_line` - Optional line to highlight (for free mode)
 #[allow(clippy::too_many_arguments)]
-pub fn render_viewport(
-    stdout: &mut io::Stdout,
+pub fn render_viewport<W: Write>(
+    stdout: &mut W,
     buffer: &TerminalBuffer,
     row_offset: usize,
     col_offset: usize,
@@ -36,81 +35,66 @@ pub fn render_viewport(
 
     for view_row in 0..view_rows {
         let buf_row = view_row + row_offset;
-        let is_highlighted = highlight_line == Some(buf_row);
-
-        // Move cursor to start of line (no clear - we'll overwrite)
-        output.push_str(&format!("\x1b[{};1H", view_row + 1));
-
-        // Set highlight style if needed
-        if is_highlighted {
-            output.push_str("\x1b[97;42m"); // White text on green background
-        }
-
-        let mut chars_written = 0;
-
-        if let Some(row) = buffer.row(buf_row) {
-            let mut current_style = CellStyle::default();
-            let mut in_highlight_style = is_highlighted;
-
-            for view_col in 0..view_cols {
-                let buf_col = view_col + col_offset;
-
-                if buf_col < row.len() {
-                    let cell = &row[buf_col];
-
-                    if !is_highlighted && cell.style != current_style {
-                        // Apply style using ANSI codes directly
-                        output.push_str("\x1b[0m"); // Reset
-                        style_to_ansi_fg(&cell.style, &mut output);
-                        style_to_ansi_bg(&cell.style, &mut output);
-                        style_to_ansi_attrs(&cell.style, &mut output);
-                        current_style = cell.style;
-                        in_highlight_style = false;
-                    } else if is_highlighted && !in_highlight_style {
-                        output.push_str("\x1b[97;42m");
-                        in_highlight_style = true;
-                    }
-
-                    output.push(cell.char);
-                    chars_written += 1;
-                } else {
-                    // Past end of row content - fill with spaces
-                    if !is_highlighted && current_style != CellStyle::default() {
-                        output.push_str("\x1b[0m");
-                        current_style = CellStyle::default();
-                    }
-                    output.push(' ');
-                    chars_written += 1;
-                }
-            }
-
-            // Reset at end of line
-            if current_style != CellStyle::default() || is_highlighted {
-                output.push_str("\x1b[0m");
-            }
-        } else {
-            // Empty row - fill with spaces
-            if is_highlighted {
-                for _ in 0..view_cols {
-                    output.push(' ');
-                }
-                output.push_str("\x1b[0m");
-            } else {
-                for _ in 0..view_cols {
-                    output.push(' ');
-                }
-            }
-            chars_written = view_cols;
-        }
-
-        // Ensure we've written the full width (clear any trailing content)
-        let _ = chars_written; // Already writing full width above
+        push_line(
+            &mut output,
+            buffer,
+            buf_row,
+            view_row,
+            col_offset,
+            view_cols,
+            highlight_line == Some(buf_row),
+        );
     }
 
     write!(stdout, "{}", output)?;
     Ok(())
 }
 
+/// Render only the viewport rows that changed since the last frame.
+///
+/// Generalizes [`render_single_line`] to every row the buffer flagged as
+/// dirty (see [`TerminalBuffer::is_row_dirty`]), so a frame that touched
+/// one line rewrites one line instead of the whole screen. The caller is
+/// responsible for a full [`render_viewport`] whenever the viewport itself
+/// moves or resizes, and for clearing the dirty flags after each frame.
+///
+/// Returns the number of rows written.
+#[allow(clippy::too_many_arguments)]
+pub fn render_dirty_rows<W: Write>(
+    stdout: &mut W,
+    buffer: &TerminalBuffer,
+    row_offset: usize,
+    col_offset: usize,
+    view_rows: usize,
+    view_cols: usize,
+    highlight_line: Option<usize>,
+) -> Result<usize> {
+    let mut output = String::new();
+    let mut rows = 0;
+
+    for view_row in 0..view_rows {
+        let buf_row = view_row + row_offset;
+        if !buffer.is_row_dirty(buf_row) {
+            continue;
+        }
+        push_line(
+            &mut output,
+            buffer,
+            buf_row,
+            view_row,
+            col_offset,
+            view_cols,
+            highlight_line == Some(buf_row),
+        );
+        rows += 1;
+    }
+
+    if rows > 0 {
+        write!(stdout, "{}", output)?;
+    }
+    Ok(rows)
+}
+
 /// Render a single line of the viewport (for partial updates in free mode).
 ///
 /// This is an optimization to avoid re-rendering the entire viewport when
@@ -125,8 +109,8 @@ pub fn render_viewport(
 /// * `view_cols` - Number of visible columns
 /// * `is_highlighted` - Whether this line should be highlighted
 #[allow(clippy::too_many_arguments)]
-pub fn render_single_line(
-    stdout: &mut io::Stdout,
+pub fn render_single_line<W: Write>(
+    stdout: &mut W,
     buffer: &TerminalBuffer,
     buf_row: usize,
     view_row_offset: usize,
@@ -141,12 +125,37 @@ pub fn render_single_line(
     let screen_row = buf_row - view_row_offset;
 
     let mut output = String::with_capacity(view_cols * 2);
+    push_line(
+        &mut output,
+        buffer,
+        buf_row,
+        screen_row,
+        col_offset,
+        view_cols,
+        is_highlighted,
+    );
+
+    write!(stdout, "{}", output)?;
+    Ok(())
+}
 
-    // Move cursor to start of line
+/// Append one buffer row, drawn at `screen_row`, to `output`.
+///
+/// Always writes the full `view_cols` width so no stale content remains.
+fn push_line(
+    output: &mut String,
+    buffer: &TerminalBuffer,
+    buf_row: usize,
+    screen_row: usize,
+    col_offset: usize,
+    view_cols: usize,
+    is_highlighted: bool,
+) {
+    // Move cursor to start of line (no clear - we'll overwrite)
     output.push_str(&format!("\x1b[{};1H", screen_row + 1));
 
     if is_highlighted {
-        output.push_str("\x1b[97;42m"); // White on green
+        output.push_str("\x1b[97;42m"); // White text on green background
     }
 
     if let Some(row) = buffer.row(buf_row) {
@@ -159,15 +168,17 @@ pub fn render_single_line(
                 let cell = &row[buf_col];
 
                 if !is_highlighted && cell.style != current_style {
-                    output.push_str("\x1b[0m");
-                    style_to_ansi_fg(&cell.style, &mut output);
-                    style_to_ansi_bg(&cell.style, &mut output);
-                    style_to_ansi_attrs(&cell.style, &mut output);
+                    // Apply style using ANSI codes directly
+                    output.push_str("\x1b[0m"); // Reset
+                    cell.style.to_ansi_fg(output);
+                    cell.style.to_ansi_bg(output);
+                    cell.style.to_ansi_attrs(output);
                     current_style = cell.style;
                 }
 
                 output.push(cell.char);
             } else {
+                // Past end of row content - fill with spaces
                 if !is_highlighted && current_style != CellStyle::default() {
                     output.push_str("\x1b[0m");
                     current_style = CellStyle::default();
@@ -176,11 +187,12 @@ pub fn render_single_line(
             }
         }
 
+        // Reset at end of line
         if current_style != CellStyle::default() || is_highlighted {
             output.push_str("\x1b[0m");
         }
     } else {
-        // Empty row
+        // Empty row - fill with spaces
         for _ in 0..view_cols {
             output.push(' ');
         }
@@ -188,15 +200,13 @@ pub fn render_single_line(
             output.push_str("\x1b[0m");
         }
     }
-
-    write!(stdout, "{}", output)?;
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::terminal::TerminalBuffer;
+    use std::io;
 
     fn create_buffer_with_content(width: usize, height: usize, content: &str) -> TerminalBuffer {
         let mut buffer = TerminalBuffer::new(width, height);
@@ -434,4 +444,84 @@ mod tests {
         let result = render_single_line(&mut stdout, &buffer, 15, 0, 0, 80, false);
         assert!(result.is_ok());
     }
+
+    // === render_dirty_rows tests ===
+
+    /// Count the rows a render wrote (one cursor move per row).
+    fn rows_written(output: &[u8]) -> usize {
+        String::from_utf8_lossy(output).matches(";1H").count()
+    }
+
+    #[test]
+    fn render_dirty_rows_draws_everything_on_first_frame() {
+        let buffer = create_buffer_with_content(80, 24, "Hello");
+        let mut out = Vec::new();
+        let rows = render_dirty_rows(&mut out, &buffer, 0, 0, 24, 80, None).unwrap();
+        assert_eq!(rows, 24);
+        assert_eq!(rows_written(&out), 24);
+    }
+
+    #[test]
+    fn render_dirty_rows_only_writes_changed_rows() {
+        let mut buffer = create_buffer_with_content(80, 24, "Line 1\r\nLine 2\r\nLine 3");
+        buffer.clear_dirty();
+
+        // A localized update: rewrite the second line in place
+        buffer.process("\x1b[2;1HChanged", None);
+
+        let mut full = Vec::new();
+        render_viewport(&mut full, &buffer, 0, 0, 24, 80, None).unwrap();
+        let mut diff = Vec::new();
+        let rows = render_dirty_rows(&mut diff, &buffer, 0, 0, 24, 80, None).unwrap();
+
+        assert_eq!(rows_written(&full), 24);
+        assert_eq!(rows, 1);
+        assert_eq!(rows_written(&diff), 1);
+        let diff = String::from_utf8(diff).unwrap();
+        assert!(diff.starts_with("\x1b[2;1HChanged"));
+    }
+
+    #[test]
+    fn render_dirty_rows_matches_full_render_for_changed_row() {
+        let mut buffer = create_buffer_with_content(40, 5, "\x1b[31mRed\x1b[0m");
+        buffer.clear_dirty();
+        buffer.process("\x1b[3;1H\x1b[1mBold\x1b[0m", None);
+
+        let mut full = Vec::new();
+        render_single_line(&mut full, &buffer, 2, 0, 0, 40, false).unwrap();
+        let mut diff = Vec::new();
+        render_dirty_rows(&mut diff, &buffer, 0, 0, 5, 40, None).unwrap();
+        assert_eq!(diff, full);
+    }
+
+    #[test]
+    fn render_dirty_rows_writes_nothing_when_clean() {
+        let mut buffer = create_buffer_with_content(80, 24, "Hello");
+        buffer.clear_dirty();
+        let mut out = Vec::new();
+        assert_eq!(
+            render_dirty_rows(&mut out, &buffer, 0, 0, 24, 80, None).unwrap(),
+            0
+        );
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn render_dirty_rows_maps_rows_through_offset() {
+        let mut buffer = TerminalBuffer::new(80, 30);
+        buffer.clear_dirty();
+        buffer.process("\x1b[11;1Hx", None);
+
+        let mut out = Vec::new();
+        let rows = render_dirty_rows(&mut out, &buffer, 5, 0, 20, 80, None).unwrap();
+        assert_eq!(rows, 1);
+        assert!(String::from_utf8(out).unwrap().starts_with("\x1b[6;1Hx"));
+
+        // Scrolled past the changed row: nothing to draw
+        let mut out = Vec::new();
+        assert_eq!(
+            render_dirty_rows(&mut out, &buffer, 12, 0, 18, 80, None).unwrap(),
+            0
+        );
+    }
 }
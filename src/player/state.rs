@@ -5,6 +5,10 @@
 
 use std::time::Instant;
 
+use super::input::KeyMap;
+use super::playback::Bookmarks;
+use crate::theme::PlayerColors;
+
 /// Result of processing an input event.
 ///
 /// This enum is returned by input handlers to signal control flow
@@ -65,6 +69,10 @@ pub struct PlaybackState {
     pub viewport_mode: bool,
     /// Whether free mode is active (line-by-line navigation)
     pub free_mode: bool,
+    /// Text typed into the go-to-time prompt, `None` when it is closed
+    pub time_prompt: Option<String>,
+    /// Selected row of the marker list panel, `None` when it is closed
+    pub marker_list: Option<usize>,
 
     // === Free mode state (guarded) ===
     /// Current highlighted line in free mode (private, use getter/setter)
@@ -92,9 +100,19 @@ pub struct PlaybackState {
     /// Number of visible content columns
     pub view_cols: usize,
 
+    // === Bookmarks ===
+    /// User-placed bookmarks (persisted to a sidecar on quit)
+    pub bookmarks: Bookmarks,
+
+    // === Input ===
+    /// Key bindings (defaults plus `[player.keys]` overrides)
+    pub keymap: KeyMap,
+
     // === Rendering flags ===
     /// True when screen needs to be redrawn
     pub needs_render: bool,
+    /// Chrome colors from the `[theme]` config
+    pub colors: PlayerColors,
 }
 
 impl PlaybackState {
@@ -124,6 +142,8 @@ impl PlaybackState {
             show_help: false,
             viewport_mode: false,
             free_mode: false,
+            time_prompt: None,
+            marker_list: None,
 
             // Free mode state
             free_line: 0,
@@ -138,8 +158,13 @@ impl PlaybackState {
             view_row_offset: 0,
             view_col_offset: 0,
 
+            // Bookmarks
+            bookmarks: Bookmarks::default(),
+            keymap: KeyMap::default(),
+
             // Rendering flags
             needs_render: true,
+            colors: PlayerColors::default(),
         }
     }
 
@@ -186,6 +211,29 @@ impl PlaybackState {
         self.needs_render = true;
     }
 
+    /// Slowest allowed playback speed.
+    pub const MIN_SPEED: f64 = 0.1;
+    /// Fastest allowed playback speed.
+    pub const MAX_SPEED: f64 = 16.0;
+
+    /// Clamp a speed multiplier to `MIN_SPEED..=MAX_SPEED` (non-finite -> 1.0).
+    pub fn clamp_speed(speed: f64) -> f64 {
+        if speed.is_finite() {
+            speed.clamp(Self::MIN_SPEED, Self::MAX_SPEED)
+        } else {
+            1.0
+        }
+    }
+
+    /// Set playback speed (clamped), preserving the current playback position.
+    pub fn set_speed(&mut self, speed: f64) {
+        // Rebase timing so the position doesn't jump when the rate changes
+        self.set_time_offset(self.current_time());
+        self.start_time = Instant::now();
+        self.speed = Self::clamp_speed(speed);
+        self.needs_render = true;
+    }
+
     /// Fixed speed steps for clean playback speed values.
     /// Using fixed steps prevents floating point drift when adjusting speed up and down.
     const SPEED_STEPS: &'static [f64] = &[0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
@@ -436,6 +484,25 @@ mod tests {
         assert_eq!(state.time_offset(), 10.0); // Preserved current time
     }
 
+    #[test]
+    fn clamp_speed_bounds() {
+        assert_eq!(PlaybackState::clamp_speed(2.0), 2.0);
+        assert_eq!(PlaybackState::clamp_speed(0.01), PlaybackState::MIN_SPEED);
+        assert_eq!(PlaybackState::clamp_speed(100.0), PlaybackState::MAX_SPEED);
+        assert_eq!(PlaybackState::clamp_speed(-3.0), PlaybackState::MIN_SPEED);
+        assert_eq!(PlaybackState::clamp_speed(f64::NAN), 1.0);
+        assert_eq!(PlaybackState::clamp_speed(f64::INFINITY), 1.0);
+    }
+
+    #[test]
+    fn set_speed_clamps_and_preserves_position() {
+        let mut state = PlaybackState::new(80, 27);
+        state.set_current_time(12.0, 100.0);
+        state.set_speed(32.0);
+        assert_eq!(state.speed, 16.0);
+        assert_eq!(state.time_offset(), 12.0);
+    }
+
     #[test]
     fn speed_up_uses_fixed_steps() {
         let mut state = PlaybackState::new(80, 27);
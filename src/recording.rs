@@ -10,20 +10,147 @@ use std::env;
 use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 use crate::analyzer::{AgentType, AnalyzeOptions, AnalyzerService};
-use crate::config::Config;
-use crate::files::{backup, filename, lock};
+use crate::asciicast::{
+    integrity, AsciicastFile, Event, EventType, Header, MarkerManager, SensitiveInputFilter,
+    TermInfo, Transform,
+};
+use crate::config::{Config, RecordingConfig};
+use crate::files::{atomic, backup, filename, lock};
 use crate::storage::StorageManager;
 use crate::theme;
 use crate::utils::process_guard::ProcessGuard;
 
+pub mod status;
+pub mod stream;
+
+use status::StatusMonitor;
+use stream::CastStream;
+
+/// Marker label written when a recording limit stops the session.
+pub const AUTO_STOP_MARKER: &str = "[auto-stop] limit reached";
+
+/// Environment variable holding the cast path for `pre_hook`/`post_hook`.
+pub const CAST_PATH_ENV: &str = "AGR_CAST_PATH";
+
+/// Exit code of `agr record` when asciinema fails without recording anything.
+///
+/// Chosen outside the range agents normally use, like `env` and `git bisect`
+/// do for failures of the wrapper rather than the wrapped command.
+pub const RECORDER_FAILURE_EXIT_CODE: i32 = 125;
+
+/// Map how a recording ended to the exit code `agr record` should use.
+///
+/// - `recorded`: the agent's code from the recording's exit event, if any
+/// - `code`/`signal`: how asciinema exited, which reflects the agent
+/// - `wrote_recording`: whether asciinema produced a recording at all
+///
+/// A recorded exit event wins. Without one, asciinema's own code is used,
+/// and a signal maps to the shell convention of 128 + signal. If asciinema
+/// left no recording, it failed before the agent ran.
+pub fn session_exit_code(
+    recorded: Option<i32>,
+    code: Option<i32>,
+    signal: Option<i32>,
+    wrote_recording: bool,
+) -> i32 {
+    if let Some(code) = recorded {
+        return code;
+    }
+    if !wrote_recording {
+        return RECORDER_FAILURE_EXIT_CODE;
+    }
+    match (code, signal) {
+        (Some(code), _) => code,
+        (None, Some(signal)) => 128 + signal,
+        (None, None) => RECORDER_FAILURE_EXIT_CODE,
+    }
+}
+
+/// The agent's exit code from the recording's last exit event, if any.
+fn recorded_exit_code(cast: &AsciicastFile) -> Option<i32> {
+    cast.events
+        .iter()
+        .rev()
+        .find(|e| e.event_type == EventType::Exit)
+        .and_then(|e| e.data.trim().parse().ok())
+}
+
+/// The signal that terminated a process, if any.
+fn exit_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
+
+/// Which recording limit was reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitReached {
+    Duration,
+    Size,
+}
+
+/// Auto-stop limits from `[recording] max_duration_secs` and `max_size_mb`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecordingLimits {
+    pub max_duration: Option<Duration>,
+    pub max_size_bytes: Option<u64>,
+}
+
+impl RecordingLimits {
+    /// Build limits from the recording config. Zero values mean no limit.
+    pub fn from_config(config: &RecordingConfig) -> Self {
+        Self {
+            max_duration: config
+                .max_duration_secs
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            max_size_bytes: config
+                .max_size_mb
+                .filter(|&mb| mb > 0)
+                .map(|mb| mb.saturating_mul(1024 * 1024)),
+        }
+    }
+
+    /// Whether no limit is configured.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_duration.is_none() && self.max_size_bytes.is_none()
+    }
+
+    /// Check elapsed time and file size against the limits.
+    ///
+    /// Returns the limit that was reached, if any. A limit is reached once
+    /// the value is at or above it; duration is checked first.
+    pub fn check(&self, elapsed: Duration, size_bytes: u64) -> Option<LimitReached> {
+        if self.max_duration.is_some_and(|max| elapsed >= max) {
+            return Some(LimitReached::Duration);
+        }
+        if self.max_size_bytes.is_some_and(|max| size_bytes >= max) {
+            return Some(LimitReached::Size);
+        }
+        None
+    }
+}
+
 /// Session recorder that wraps asciinema
 pub struct Recorder {
     #[allow(dead_code)]
     config: Config,
     storage: StorageManager,
     guard: ProcessGuard,
+    /// Show the live duration/size indicator while recording
+    show_status: bool,
+    /// Terminal size from `--cols`/`--rows`, overriding the config
+    window_size: (Option<u16>, Option<u16>),
 }
 
 impl Recorder {
@@ -34,26 +161,64 @@ impl Recorder {
             config,
             storage,
             guard: ProcessGuard::new(),
+            show_status: false,
+            window_size: (None, None),
         }
     }
 
-    /// Generate a filename using the configured template.
+    /// Show elapsed time and file size while recording (`--status`).
+    ///
+    /// See [`status`] for where the indicator is shown.
+    pub fn with_status(mut self, enabled: bool) -> Self {
+        self.show_status = enabled;
+        self
+    }
+
+    /// Record with a fixed terminal size (`--cols`/`--rows`).
+    ///
+    /// Each given dimension overrides `[recording] cols`/`rows`; see
+    /// [`resolve_window_size`].
+    pub fn with_window_size(mut self, cols: Option<u16>, rows: Option<u16>) -> Self {
+        self.window_size = (cols, rows);
+        self
+    }
+
+    /// The fixed terminal size to record with, if any.
+    fn forced_window_size(&self) -> Option<(u16, u16)> {
+        let config = (self.config.recording.cols, self.config.recording.rows);
+        resolve_window_size(self.window_size, config, crossterm::terminal::size().ok())
+    }
+
+    /// Generate a filename for a new `agent` recording in `agent_dir`.
     ///
-    /// Uses the `filename_template` from config with tags like `{directory}`, `{date}`, `{time}`.
+    /// Uses the `filename_template` from config with tags like `{directory}`, `{date}`,
+    /// `{time}`, `{agent}`, `{project}`, `{pid}` and `{counter}`.
     /// Falls back to a timestamp-based name if template generation fails.
-    pub fn generate_filename(&self) -> String {
+    /// The name is not checked for collisions; see [`filename::unique_filename`].
+    pub fn generate_filename(&self, agent: &str, agent_dir: &Path) -> String {
         // Get current working directory name
-        let dir_name = env::current_dir()
-            .ok()
+        let cwd = env::current_dir().ok();
+        let dir_name = cwd
+            .as_deref()
             .and_then(|p| p.file_name().map(|s| s.to_string_lossy().to_string()))
             .unwrap_or_else(|| "recording".to_string());
 
+        let context = filename::RenderContext {
+            agent: agent.to_string(),
+            project: cwd
+                .as_deref()
+                .and_then(filename::project_name)
+                .unwrap_or_else(|| dir_name.clone()),
+            counter: filename::next_counter(agent_dir),
+            ..filename::RenderContext::new(&dir_name)
+        };
+
         // Build filename config from recording config (enforces minimum of 1)
         let filename_config = filename::Config::new(self.config.recording.directory_max_length);
 
         // Generate using template, fallback to simple timestamp on error
-        filename::generate(
-            &dir_name,
+        filename::generate_with(
+            &context,
             &self.config.recording.filename_template,
             &filename_config,
         )
@@ -102,70 +267,70 @@ impl Recorder {
         Ok(())
     }
 
-    /// Record an agent session
+    /// Record an agent session.
+    ///
+    /// Returns the exit code `agr record` should exit with: the agent's own
+    /// code, or [`RECORDER_FAILURE_EXIT_CODE`] if asciinema failed.
     pub fn record(
         &mut self,
         agent: &str,
         session_name: Option<&str>,
         args: &[String],
-    ) -> Result<()> {
+    ) -> Result<i32> {
         Self::check_asciinema()?;
 
         // Ensure agent directory exists
         let agent_dir = self.storage.ensure_agent_dir(agent)?;
 
-        // Generate filename - use provided name or template-based
+        // Generate filename - use provided name or template-based.
+        // Never reuse an existing recording's name; append -N instead.
         let filename = match session_name {
             Some(name) => Self::sanitize_filename(name),
-            None => self.generate_filename(),
+            None => self.generate_filename(agent, &agent_dir),
         };
+        let filename = filename::unique_filename(&agent_dir, &filename);
         let filepath = agent_dir.join(&filename);
 
+        let record_span = tracing::info_span!("record", agent, file = %filepath.display());
+        let _record = record_span.enter();
+
         // Lock the file before recording starts
         lock::create_lock(&filepath)?;
 
-        // Build the command to run
-        let command = if args.is_empty() {
-            agent.to_string()
-        } else {
-            format!("{} {}", agent, args.join(" "))
-        };
+        // Build the command to run (quoted so args with spaces survive `sh -c`)
+        let command = shell_command(agent, args);
+        let title = recording_title(agent, args);
 
         // Set up signal handlers for clean shutdown (SIGINT + SIGHUP)
         self.guard.register_signal_handlers();
 
+        self.run_hook_or_warn(
+            "pre_hook",
+            self.config.recording.pre_hook.as_deref(),
+            &filepath,
+        );
+
         theme::print_start_banner();
         theme::print_box_line(&format!("  ⏺ {}/{}", agent, filename));
         theme::print_box_bottom();
         println!();
 
-        // Spawn asciinema rec (spawn + poll so we can react to signals)
-        let mut child = match Command::new("asciinema")
-            .arg("rec")
-            .arg(&filepath)
-            .arg("--title")
-            .arg(format!("{} session", agent))
-            .arg("-c")
-            .arg(&command)
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .spawn()
-        {
-            Ok(c) => c,
-            Err(e) => {
-                lock::remove_lock(&filepath);
-                return Err(anyhow::Error::new(e).context("Failed to start asciinema"));
-            }
-        };
-
-        let status = match self.guard.wait_or_kill(&mut child) {
-            Ok(s) => s,
+        let window_size = self.forced_window_size();
+        let (status, limit_reached) = match self.run_asciinema(
+            &filepath,
+            &title,
+            &command,
+            window_size,
+            Stdio::inherit(),
+            || {},
+        ) {
+            Ok(result) => result,
             Err(e) => {
                 lock::remove_lock(&filepath);
                 return Err(e);
             }
         };
+        let exit_code = self.finish_recording(&filepath, &status, limit_reached, window_size);
 
         println!();
         theme::print_done_banner();
@@ -178,7 +343,15 @@ impl Recorder {
         lock::remove_lock(&filepath);
 
         // Handle exit and get final filepath (may have been renamed)
-        let final_filepath = if self.guard.is_interrupted() {
+        let final_filepath = if let Some(limit) = limit_reached {
+            let reason = match limit {
+                LimitReached::Duration => "time limit",
+                LimitReached::Size => "size limit",
+            };
+            theme::print_box_line(&format!("  ⏹ {} ({} reached)", filename, reason));
+            theme::print_box_bottom();
+            filepath.clone()
+        } else if self.guard.is_interrupted() {
             theme::print_box_line(&format!("  ⏹ {}", filename));
             theme::print_box_bottom();
             filepath.clone()
@@ -207,10 +380,339 @@ impl Recorder {
         // Run auto-analyze if enabled
         self.maybe_auto_analyze(&final_filepath);
 
+        self.run_hook_or_warn(
+            "post_hook",
+            self.config.recording.post_hook.as_deref(),
+            &final_filepath,
+        );
+
         // Show storage warning if threshold exceeded
         self.show_storage_warning()?;
 
-        Ok(())
+        Ok(exit_code)
+    }
+
+    /// Record an agent session and stream the cast to `sink` as it is recorded.
+    ///
+    /// Used by `agr record --output` to pipe recordings into other tools.
+    /// Nothing is kept in storage and there is no rename prompt. The session
+    /// is drawn on the controlling terminal, so `sink` may be stdout even
+    /// when stdout is a pipe. asciinema records into a private temporary
+    /// directory, and each line it writes is copied to `sink` within a poll
+    /// interval (see [`stream`]). Returns the exit code like
+    /// [`record`](Self::record); nothing is written if asciinema failed
+    /// before recording.
+    ///
+    /// Unlike [`record`](Self::record), `pre_hook`, `post_hook` and
+    /// auto-analyze are not run: there is no recording in storage for
+    /// them to work on.
+    pub fn record_to<W: Write>(
+        &mut self,
+        agent: &str,
+        args: &[String],
+        sink: &mut W,
+    ) -> Result<i32> {
+        self.stream_to(agent, args, sink)
+            .map(|(exit_code, _)| exit_code)
+    }
+
+    /// Record like [`record_to`](Self::record_to) into the file at `path`.
+    ///
+    /// The cast is streamed into a temp file next to `path` (see
+    /// [`atomic::temp_path`]), which replaces `path` only when a recording
+    /// was made and the session was not interrupted. A failed start or an
+    /// aborted session leaves an existing file at `path` untouched.
+    pub fn record_to_file(&mut self, agent: &str, args: &[String], path: &Path) -> Result<i32> {
+        let temp_path = atomic::temp_path(path);
+        let result = std::fs::File::create(&temp_path)
+            .with_context(|| format!("Failed to create output file: {}", path.display()))
+            .and_then(|mut file| {
+                let result = self.stream_to(agent, args, &mut file)?;
+                file.sync_all().context("Failed to write recording")?;
+                Ok(result)
+            });
+
+        match result {
+            Ok((exit_code, true)) if !self.guard.is_interrupted() => {
+                std::fs::rename(&temp_path, path)
+                    .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+                Ok(exit_code)
+            }
+            Ok((exit_code, started)) => {
+                let _ = std::fs::remove_file(&temp_path);
+                let reason = if started {
+                    "Session aborted"
+                } else {
+                    "Nothing recorded"
+                };
+                if path.exists() {
+                    eprintln!("  \u{26a0} {}, left {} unchanged", reason, path.display());
+                } else {
+                    eprintln!("  \u{26a0} {}, {} not written", reason, path.display());
+                }
+                Ok(exit_code)
+            }
+            Err(e) => {
+                let _ = std::fs::remove_file(&temp_path);
+                Err(e)
+            }
+        }
+    }
+
+    /// Run a recording for [`record_to`](Self::record_to), streaming it to `sink`.
+    ///
+    /// Returns the exit code and whether anything was written to `sink`.
+    fn stream_to<W: Write>(
+        &mut self,
+        agent: &str,
+        args: &[String],
+        sink: &mut W,
+    ) -> Result<(i32, bool)> {
+        Self::check_asciinema()?;
+
+        // Only readable by the user; removed with its contents when dropped
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("agr-record-");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o700));
+        }
+        let temp_dir = builder
+            .tempdir()
+            .context("Failed to create a temporary directory for the recording")?;
+        let filepath = temp_dir
+            .path()
+            .join(format!("{}.cast", Self::sanitize_filename(agent)));
+        let record_span = tracing::info_span!("record", agent, file = %filepath.display());
+        let _record = record_span.enter();
+
+        let command = shell_command(agent, args);
+        let title = recording_title(agent, args);
+        let terminal = std::fs::OpenOptions::new()
+            .write(true)
+            .open("/dev/tty")
+            .context("Recording to a pipe needs a terminal to show the session (/dev/tty)")?;
+
+        self.guard.register_signal_handlers();
+
+        let window_size = self.forced_window_size();
+        let suppress_input =
+            self.config.recording.capture_input && self.config.recording.no_input_echo;
+        let mut stream = CastStream::new(&filepath, window_size, suppress_input);
+        // A failing sink (e.g. the consumer exited) does not end the session
+        let mut stream_error = None;
+        let result = self
+            .run_asciinema(
+                &filepath,
+                &title,
+                &command,
+                window_size,
+                Stdio::from(terminal),
+                || {
+                    if stream_error.is_none() {
+                        stream_error = stream.poll(sink).err();
+                    }
+                },
+            )
+            .and_then(|(status, limit_reached)| {
+                if let Some(e) = stream_error {
+                    return Err(e);
+                }
+                let exit_code = stream.finish(
+                    sink,
+                    limit_reached.is_some(),
+                    status.code(),
+                    exit_signal(&status),
+                )?;
+                Ok((exit_code, stream.started()))
+            });
+        drop(temp_dir);
+        result
+    }
+
+    /// Run `asciinema rec` into `filepath` until the agent exits.
+    ///
+    /// Stops the recording early when a configured limit is reached.
+    /// `window_size` fixes the terminal size of the recorded session.
+    /// `stdout` is where asciinema draws the session, normally the terminal.
+    /// `on_poll` is called each time the process is polled while it runs.
+    fn run_asciinema(
+        &self,
+        filepath: &Path,
+        title: &str,
+        command: &str,
+        window_size: Option<(u16, u16)>,
+        stdout: Stdio,
+        mut on_poll: impl FnMut(),
+    ) -> Result<(std::process::ExitStatus, Option<LimitReached>)> {
+        // Spawn asciinema rec (spawn + poll so we can react to signals)
+        let spawn_span = tracing::info_span!("spawn", command = %title);
+        let spawn_guard = spawn_span.enter();
+        let mut asciinema = Command::new("asciinema");
+        asciinema
+            .arg("rec")
+            .arg(filepath)
+            .arg("--title")
+            .arg(title)
+            .arg("-c")
+            .arg(command);
+        if self.config.recording.capture_input {
+            asciinema.arg("--capture-input");
+        }
+        if let Some((cols, rows)) = window_size {
+            asciinema
+                .arg("--window-size")
+                .arg(format!("{}x{}", cols, rows));
+        }
+        let mut child = match asciinema
+            .stdin(Stdio::inherit())
+            .stdout(stdout)
+            .stderr(Stdio::inherit())
+            .spawn()
+        {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!(error = %e, "failed to start asciinema");
+                return Err(anyhow::Error::new(e).context("Failed to start asciinema"));
+            }
+        };
+        tracing::info!(pid = child.id(), "asciinema started");
+        drop(spawn_guard);
+        let mut status_monitor = self
+            .show_status
+            .then(|| StatusMonitor::start(filepath.to_path_buf()));
+
+        let wait_span = tracing::info_span!(
+            "wait",
+            exit_status = tracing::field::Empty,
+            interrupted = tracing::field::Empty,
+            bytes = tracing::field::Empty
+        );
+        let wait_guard = wait_span.enter();
+        let limits = RecordingLimits::from_config(&self.config.recording);
+        let started = Instant::now();
+        let mut limit_reached = None;
+        let wait_result = self.guard.wait_or_stop(&mut child, || {
+            on_poll();
+            if limits.is_unlimited() {
+                return false;
+            }
+            let size = std::fs::metadata(filepath).map(|m| m.len()).unwrap_or(0);
+            limit_reached = limits.check(started.elapsed(), size);
+            limit_reached.is_some()
+        });
+        if let Some(monitor) = status_monitor.as_mut() {
+            monitor.stop();
+        }
+        let status = match wait_result {
+            Ok((s, _)) => s,
+            Err(e) => {
+                tracing::error!(error = %e, "waiting for asciinema failed");
+                return Err(e);
+            }
+        };
+        wait_span.record("exit_status", tracing::field::debug(status.code()));
+        wait_span.record("interrupted", self.guard.is_interrupted());
+        if let Ok(metadata) = std::fs::metadata(filepath) {
+            wait_span.record("bytes", metadata.len());
+        }
+        drop(wait_guard);
+
+        Ok((status, limit_reached))
+    }
+
+    /// Post-process a finished recording and return the agent's exit code.
+    ///
+    /// Marks auto-stops, filters sensitive input, records the exit code and
+    /// stores the duration and any fixed terminal size. The recording is
+    /// parsed once and written back once, atomically, with all edits.
+    /// Failures to update it are reported but not fatal.
+    fn finish_recording(
+        &self,
+        filepath: &Path,
+        status: &std::process::ExitStatus,
+        limit_reached: Option<LimitReached>,
+        window_size: Option<(u16, u16)>,
+    ) -> i32 {
+        let code = status.code();
+        let signal = exit_signal(status);
+        if !filepath.exists() {
+            return session_exit_code(None, code, signal, false);
+        }
+
+        // Drop a partially written last line before parsing
+        let mut auto_stopped = false;
+        if let Some(limit) = limit_reached {
+            tracing::info!(?limit, "recording limit reached");
+            match integrity::repair(filepath) {
+                Ok(_) => auto_stopped = true,
+                Err(e) => eprintln!("  \u{26a0} Could not repair recording: {}", e),
+            }
+        }
+
+        let mut cast = match AsciicastFile::parse(filepath) {
+            Ok(cast) => cast,
+            Err(e) => {
+                eprintln!("  \u{26a0} Could not finish recording: {}", e);
+                return session_exit_code(None, code, signal, true);
+            }
+        };
+        if auto_stopped {
+            Self::mark_auto_stop(&mut cast);
+        }
+        if self.config.recording.capture_input && self.config.recording.no_input_echo {
+            Self::suppress_sensitive_input(&mut cast);
+        }
+        let exit_code = Self::record_exit_code(&mut cast, code, signal);
+        if let Some(size) = window_size {
+            set_window_size(&mut cast, size);
+        }
+        // Saves `agr list` and `agr stats` from re-scanning the events
+        cast.stamp_duration();
+
+        if let Err(e) = cast.write(filepath) {
+            eprintln!("  \u{26a0} Could not update recording: {}", e);
+        }
+        exit_code
+    }
+
+    /// Determine the agent's exit code and record it as an exit event.
+    ///
+    /// asciinema may already have written the exit event; otherwise one is
+    /// appended with the code asciinema exited with.
+    fn record_exit_code(cast: &mut AsciicastFile, code: Option<i32>, signal: Option<i32>) -> i32 {
+        if let Some(recorded) = recorded_exit_code(cast) {
+            return recorded;
+        }
+        let exit_code = session_exit_code(None, code, signal, true);
+        cast.events
+            .push(Event::new(0.0, EventType::Exit, exit_code.to_string()));
+        exit_code
+    }
+
+    /// Mark where an auto-stopped recording was cut.
+    ///
+    /// Appends the auto-stop marker at the end of the recording.
+    fn mark_auto_stop(cast: &mut AsciicastFile) {
+        let duration = cast.duration();
+        if let Err(e) = MarkerManager::add_marker_to_cast(cast, duration, AUTO_STOP_MARKER) {
+            eprintln!("  \u{26a0} Could not add auto-stop marker: {}", e);
+        }
+    }
+
+    /// Drop keystrokes typed at password prompts from the finished recording.
+    ///
+    /// Until this runs they are in the cast file asciinema is writing.
+    fn suppress_sensitive_input(cast: &mut AsciicastFile) {
+        let mut filter = SensitiveInputFilter::new();
+        filter.transform(&mut cast.events);
+        if filter.dropped() > 0 {
+            tracing::info!(
+                dropped = filter.dropped(),
+                "suppressed sensitive input events"
+            );
+        }
     }
 
     /// Prompt user to rename the session file, returns final filepath.
@@ -255,7 +757,15 @@ impl Recorder {
             let new_filename = Self::sanitize_filename(input);
             let new_filepath = actual_path.parent().unwrap().join(&new_filename);
 
+            let _rename = tracing::info_span!(
+                "rename",
+                from = %actual_path.display(),
+                to = %new_filepath.display()
+            )
+            .entered();
+
             if new_filepath.exists() {
+                tracing::warn!("target exists, kept original");
                 println!("  \u{26a0} Exists, kept original");
                 Ok(actual_path)
             } else {
@@ -350,10 +860,26 @@ impl Recorder {
         Ok(())
     }
 
+    /// Run a `[recording]` hook if configured, warning instead of failing.
+    fn run_hook_or_warn(&self, name: &str, hook: Option<&str>, cast_path: &Path) {
+        let Some(hook) = hook.filter(|h| !h.trim().is_empty()) else {
+            return;
+        };
+        if let Err(e) = run_hook(hook, cast_path) {
+            tracing::warn!(hook = name, error = %e, "hook failed");
+            eprintln!("  \u{26a0} {} failed: {:#}", name, e);
+        }
+    }
+
     /// Run auto-analysis if enabled in config
     fn maybe_auto_analyze(&self, filepath: &Path) {
-        if !self.config.recording.auto_analyze {
-            return;
+        match AutoAnalyze::from_config(&self.config.recording) {
+            AutoAnalyze::Off => return,
+            AutoAnalyze::Background => {
+                self.spawn_background_analyze(filepath);
+                return;
+            }
+            AutoAnalyze::Foreground => {}
         }
 
         let agent_name = self.config.resolve_analysis_agent();
@@ -374,23 +900,33 @@ impl Recorder {
 
         // Create analyzer service with quiet mode (auto-analyze is background operation)
         let options = AnalyzeOptions::with_agent(agent).quiet();
-        let service = AnalyzerService::new(options);
-
-        // Check if agent is installed
-        if !service.is_agent_available() {
-            println!();
-            println!(
-                "Auto-analyze skipped: '{}' not installed. Install it or set [analysis].agent in config.",
-                agent_name
-            );
-            println!(
-                "Tip: Run 'agr list' to see recordings, then use your agent's CLI to analyze."
-            );
-            return;
+        let mut service = AnalyzerService::new(options);
+
+        // Fall back to another installed agent, or skip if there is none
+        match service.fall_back_if_unavailable() {
+            Ok(None) => {}
+            Ok(Some(fallback)) => {
+                println!();
+                println!(
+                    "'{}' not installed, falling back to {}.",
+                    agent_name,
+                    fallback.command_name()
+                );
+            }
+            Err(_) => {
+                println!();
+                println!(
+                    "Auto-analyze skipped: no analysis agent installed. Install one or set [analysis].agent in config."
+                );
+                println!(
+                    "Tip: Run 'agr list' to see recordings, then use your agent's CLI to analyze."
+                );
+                return;
+            }
         }
 
         println!();
-        println!("Analyzing session with {}...", agent);
+        println!("Analyzing session with {}...", service.agent());
 
         match service.analyze(filepath) {
             Ok(result) => {
@@ -407,4 +943,637 @@ impl Recorder {
             }
         }
     }
+
+    /// Start `agr analyze` for the recording without waiting for it.
+    ///
+    /// A failure to spawn only warns: the recording itself is already saved.
+    fn spawn_background_analyze(&self, filepath: &Path) {
+        let spawned = env::current_exe()
+            .context("Could not locate the agr executable")
+            .and_then(|exe| {
+                background_analyze_command(&exe, filepath)
+                    .spawn()
+                    .context("Failed to start agr analyze")
+            });
+        match spawned {
+            Ok(child) => {
+                println!();
+                println!(
+                    "Analyzing in background (pid {}); markers are added when it finishes.",
+                    child.id()
+                );
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "background auto-analyze failed to start");
+                eprintln!("Auto-analyze failed: {:#}", e);
+                println!(
+                    "Tip: Run 'agr analyze {}' to analyze it.",
+                    filepath.display()
+                );
+            }
+        }
+    }
+}
+
+/// How a finished recording is analyzed, per `[recording]` config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoAnalyze {
+    /// No automatic analysis
+    Off,
+    /// Analyze before `agr record` returns
+    Foreground,
+    /// Hand the analysis to a detached `agr analyze` process
+    Background,
+}
+
+impl AutoAnalyze {
+    pub fn from_config(config: &RecordingConfig) -> Self {
+        match (config.auto_analyze, config.auto_analyze_background) {
+            (false, _) => Self::Off,
+            (true, false) => Self::Foreground,
+            (true, true) => Self::Background,
+        }
+    }
+}
+
+/// Build the detached `agr analyze <cast>` command used for background analysis.
+///
+/// The child gets no terminal: its standard streams are closed so the curate
+/// prompt reads end of input, and on Unix it starts its own session so the
+/// hangup sent when the shell exits does not reach it. The active profile and
+/// config file are inherited through the environment.
+pub fn background_analyze_command(exe: &Path, cast_path: &Path) -> Command {
+    let mut command = Command::new(exe);
+    command
+        .arg("analyze")
+        .arg(cast_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: setsid is async-signal-safe and touches no parent state
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    command
+}
+
+/// Parse a recording, store its computed duration in the header, and write it back.
+///
+/// Events and all other header fields are written unchanged.
+pub fn stamp_duration_in_file(path: &Path) -> Result<()> {
+    let mut cast = AsciicastFile::parse(path)
+        .with_context(|| format!("Failed to parse asciicast file: {}", path.display()))?;
+    cast.stamp_duration();
+    cast.write(path)
+        .with_context(|| format!("Failed to write file: {}", path.display()))
+}
+
+/// Terminal size assumed for a dimension that is neither fixed nor known.
+pub const FALLBACK_WINDOW_SIZE: (u16, u16) = (80, 24);
+
+/// The fixed terminal size to record with, as `(cols, rows)`.
+///
+/// Each dimension comes from the CLI (`--cols`/`--rows`), then the config
+/// (`[recording] cols`/`rows`), then the current terminal (`inherited`).
+/// Returns `None` when neither the CLI nor the config fixes a dimension,
+/// so the recording simply inherits the terminal size.
+pub fn resolve_window_size(
+    cli: (Option<u16>, Option<u16>),
+    config: (Option<u16>, Option<u16>),
+    inherited: Option<(u16, u16)>,
+) -> Option<(u16, u16)> {
+    let cols = cli.0.or(config.0);
+    let rows = cli.1.or(config.1);
+    if cols.is_none() && rows.is_none() {
+        return None;
+    }
+    let (inherited_cols, inherited_rows) = inherited.unwrap_or(FALLBACK_WINDOW_SIZE);
+    Some((
+        cols.unwrap_or(inherited_cols),
+        rows.unwrap_or(inherited_rows),
+    ))
+}
+
+/// Set a recording's header terminal size to `cols` x `rows`.
+///
+/// Deprecated `width`/`height` fields are updated too.
+pub fn set_window_size(cast: &mut AsciicastFile, size: (u16, u16)) {
+    set_header_window_size(&mut cast.header, size);
+}
+
+/// Set the terminal size in a header; see [`set_window_size`].
+fn set_header_window_size(header: &mut Header, (cols, rows): (u16, u16)) {
+    let term = header.term.get_or_insert(TermInfo {
+        cols: None,
+        rows: None,
+        term_type: None,
+    });
+    term.cols = Some(cols as u32);
+    term.rows = Some(rows as u32);
+    if header.width.is_some() || header.height.is_some() {
+        header.width = Some(cols as u32);
+        header.height = Some(rows as u32);
+    }
+}
+
+/// Build the `sh -c` command for a `pre_hook`/`post_hook`.
+///
+/// The hook gets the recording's path in [`CAST_PATH_ENV`].
+pub fn hook_command(hook: &str, cast_path: &Path) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(hook).env(CAST_PATH_ENV, cast_path);
+    command
+}
+
+/// Run a hook to completion on the current terminal.
+///
+/// Fails if the hook cannot be started or exits unsuccessfully.
+pub fn run_hook(hook: &str, cast_path: &Path) -> Result<()> {
+    let status = hook_command(hook, cast_path)
+        .stdin(Stdio::null())
+        .status()
+        .with_context(|| format!("Failed to run '{}'", hook))?;
+    if !status.success() {
+        bail!("'{}' exited with {}", hook, status);
+    }
+    Ok(())
+}
+
+/// Flag-name words that mark the flag's value as a secret.
+const SECRET_FLAG_WORDS: &[&str] = &["key", "apikey", "token", "secret", "password", "passwd"];
+
+/// Placeholder written in place of redacted values.
+const REDACTED: &str = "***";
+
+/// Whether a `--flag` (or `-flag`) names a secret, e.g. `--api-key` or `--auth-token`.
+fn is_secret_flag(flag: &str) -> bool {
+    let name = flag.trim_start_matches('-');
+    if name.len() == flag.len() {
+        return false;
+    }
+    name.to_lowercase()
+        .split(['-', '_'])
+        .any(|word| SECRET_FLAG_WORDS.contains(&word))
+}
+
+/// Replace the values of secret-looking flags with `***`.
+///
+/// Handles both `--api-key=VALUE` and `--api-key VALUE`.
+pub fn redact_secret_args(args: &[String]) -> Vec<String> {
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut redact_next = false;
+    for arg in args {
+        if redact_next {
+            redacted.push(REDACTED.to_string());
+            redact_next = false;
+        } else if let Some((flag, _)) = arg.split_once('=').filter(|(f, _)| is_secret_flag(f)) {
+            redacted.push(format!("{}={}", flag, REDACTED));
+        } else {
+            redact_next = is_secret_flag(arg);
+            redacted.push(arg.clone());
+        }
+    }
+    redacted
+}
+
+/// Quote a word for POSIX `sh` if it contains anything but safe characters.
+fn shell_quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// Build the shell command line passed to `asciinema rec -c`.
+pub fn shell_command(agent: &str, args: &[String]) -> String {
+    std::iter::once(agent)
+        .chain(args.iter().map(String::as_str))
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Build the recording title: the agent command line with secrets redacted.
+///
+/// e.g. `claude chat --model x`.
+pub fn recording_title(agent: &str, args: &[String]) -> String {
+    std::iter::once(shell_quote(agent))
+        .chain(redact_secret_args(args).iter().map(|arg| {
+            if arg == REDACTED {
+                arg.clone()
+            } else {
+                shell_quote(arg)
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(secs: Option<u64>, mb: Option<u64>) -> RecordingLimits {
+        RecordingLimits::from_config(&RecordingConfig {
+            max_duration_secs: secs,
+            max_size_mb: mb,
+            ..RecordingConfig::default()
+        })
+    }
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn exit_code_passes_through_agent_code() {
+        assert_eq!(session_exit_code(None, Some(0), None, true), 0);
+        assert_eq!(session_exit_code(None, Some(3), None, true), 3);
+    }
+
+    #[test]
+    fn exit_code_prefers_recorded_exit_event() {
+        assert_eq!(session_exit_code(Some(2), Some(0), None, true), 2);
+        assert_eq!(session_exit_code(Some(0), Some(1), None, true), 0);
+    }
+
+    #[test]
+    fn exit_code_maps_signals_to_128_plus_signal() {
+        assert_eq!(session_exit_code(None, None, Some(2), true), 130);
+        assert_eq!(session_exit_code(None, None, Some(15), true), 143);
+    }
+
+    #[test]
+    fn exit_code_for_asciinema_failure_is_distinct() {
+        assert_eq!(
+            session_exit_code(None, Some(1), None, false),
+            RECORDER_FAILURE_EXIT_CODE
+        );
+        assert_eq!(
+            session_exit_code(None, None, None, true),
+            RECORDER_FAILURE_EXIT_CODE
+        );
+        assert_ne!(RECORDER_FAILURE_EXIT_CODE, 1);
+    }
+
+    #[test]
+    fn recorded_exit_code_reads_last_exit_event() {
+        let mut cast = AsciicastFile::parse_str(
+            "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n[0.1,\"o\",\"hi\"]\n",
+        )
+        .unwrap();
+        assert_eq!(recorded_exit_code(&cast), None);
+        cast.events.push(Event::new(0.2, EventType::Exit, "7"));
+        assert_eq!(recorded_exit_code(&cast), Some(7));
+    }
+
+    #[test]
+    fn record_exit_code_appends_exit_event() {
+        let mut cast = AsciicastFile::parse_str(
+            "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n[0.5,\"o\",\"hi\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(Recorder::record_exit_code(&mut cast, Some(4), None), 4);
+        assert_eq!(cast.events.len(), 2);
+        assert_eq!(recorded_exit_code(&cast), Some(4));
+
+        // An existing exit event is kept rather than duplicated
+        assert_eq!(Recorder::record_exit_code(&mut cast, Some(0), None), 4);
+        assert_eq!(cast.events.len(), 2);
+    }
+
+    #[test]
+    fn finish_recording_without_recording_is_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let status = std::process::Command::new("true").status().unwrap();
+        let recorder = Recorder::new(Config::default());
+        assert_eq!(
+            recorder.finish_recording(&dir.path().join("missing.cast"), &status, None, None),
+            RECORDER_FAILURE_EXIT_CODE
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finish_recording_applies_all_edits_in_one_pass() {
+        use std::os::unix::process::ExitStatusExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        // Auto-stopped mid-write: the last line is cut off
+        std::fs::write(
+            &path,
+            "{\"version\":3,\"term\":{\"cols\":211,\"rows\":57}}\n[0.5,\"o\",\"hi\"]\n[1.0,\"o\",\"cut",
+        )
+        .unwrap();
+
+        // Exit status 4 is encoded as 4 << 8 in a raw wait status
+        let status = std::process::ExitStatus::from_raw(4 << 8);
+        let recorder = Recorder::new(Config::default());
+        let code = recorder.finish_recording(
+            &path,
+            &status,
+            Some(LimitReached::Duration),
+            Some((120, 40)),
+        );
+
+        assert_eq!(code, 4);
+        let cast = AsciicastFile::parse(&path).unwrap();
+        assert_eq!(cast.terminal_size(), (120, 40));
+        assert_eq!(cast.header.duration, Some(0.5));
+        assert_eq!(cast.events.len(), 3);
+        assert_eq!(cast.events[1].data, AUTO_STOP_MARKER);
+        assert_eq!(recorded_exit_code(&cast), Some(4));
+        assert!(!dir.path().join("session.cast.tmp").exists());
+    }
+
+    #[test]
+    fn limits_default_to_unlimited() {
+        let limits = limits(None, None);
+        assert!(limits.is_unlimited());
+        assert_eq!(
+            limits.check(Duration::from_secs(86_400), 100 * 1024 * MB),
+            None
+        );
+    }
+
+    #[test]
+    fn zero_limits_are_disabled() {
+        assert!(limits(Some(0), Some(0)).is_unlimited());
+    }
+
+    #[test]
+    fn duration_limit_stops_at_threshold() {
+        let limits = limits(Some(60), None);
+        assert_eq!(limits.check(Duration::from_secs(59), 0), None);
+        assert_eq!(
+            limits.check(Duration::from_secs(60), 0),
+            Some(LimitReached::Duration)
+        );
+        assert_eq!(
+            limits.check(Duration::from_secs(61), 0),
+            Some(LimitReached::Duration)
+        );
+    }
+
+    #[test]
+    fn size_limit_stops_at_threshold() {
+        let limits = limits(None, Some(10));
+        assert_eq!(limits.check(Duration::ZERO, 10 * MB - 1), None);
+        assert_eq!(
+            limits.check(Duration::ZERO, 10 * MB),
+            Some(LimitReached::Size)
+        );
+    }
+
+    #[test]
+    fn duration_is_reported_when_both_limits_are_reached() {
+        let limits = limits(Some(1), Some(1));
+        assert_eq!(
+            limits.check(Duration::from_secs(2), 2 * MB),
+            Some(LimitReached::Duration)
+        );
+        assert_eq!(
+            limits.check(Duration::ZERO, 2 * MB),
+            Some(LimitReached::Size)
+        );
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn title_is_agent_without_args() {
+        assert_eq!(recording_title("claude", &[]), "claude");
+    }
+
+    #[test]
+    fn title_includes_passthrough_args() {
+        assert_eq!(
+            recording_title("claude", &args(&["chat", "--model", "x"])),
+            "claude chat --model x"
+        );
+    }
+
+    #[test]
+    fn title_quotes_args_with_spaces() {
+        assert_eq!(
+            recording_title("codex", &args(&["fix the bug", "it's"])),
+            "codex 'fix the bug' 'it'\\''s'"
+        );
+    }
+
+    #[test]
+    fn redacts_separate_secret_values() {
+        assert_eq!(
+            redact_secret_args(&args(&["--api-key", "sk-123", "--token", "abc", "run"])),
+            args(&["--api-key", "***", "--token", "***", "run"])
+        );
+    }
+
+    #[test]
+    fn redacts_inline_secret_values() {
+        assert_eq!(
+            redact_secret_args(&args(&["--api-key=sk-123", "--auth_token=abc"])),
+            args(&["--api-key=***", "--auth_token=***"])
+        );
+    }
+
+    #[test]
+    fn redaction_matches_whole_words_only() {
+        let keep = args(&["--keyboard", "us", "--monkey", "x", "key", "--model=token"]);
+        assert_eq!(redact_secret_args(&keep), keep);
+    }
+
+    #[test]
+    fn trailing_secret_flag_is_kept() {
+        assert_eq!(
+            redact_secret_args(&args(&["--password"])),
+            args(&["--password"])
+        );
+    }
+
+    #[test]
+    fn title_redacts_secrets() {
+        assert_eq!(
+            recording_title("gemini", &args(&["--api-key", "sk-1", "-p", "hi"])),
+            "gemini --api-key *** -p hi"
+        );
+    }
+
+    #[test]
+    fn shell_command_quotes_empty_args() {
+        assert_eq!(shell_command("claude", &args(&[""])), "claude ''");
+    }
+
+    #[test]
+    fn finished_recording_carries_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        // As written by asciinema: no duration in the header
+        let events = "[0.1,\"o\",\"$ \"]\n[0.2,\"i\",\"ls\\r\"]\n[1.3,\"o\",\"a b\\r\\n\"]\n[0.4,\"x\",\"0\"]\n";
+        std::fs::write(
+            &path,
+            format!(
+                "{{\"version\":3,\"term\":{{\"cols\":80,\"rows\":24}},\"timestamp\":1700000000,\"command\":\"claude\"}}\n{}",
+                events
+            ),
+        )
+        .unwrap();
+
+        stamp_duration_in_file(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let (header_line, rest) = content.split_once('\n').unwrap();
+        assert_eq!(
+            header_line,
+            "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24},\"timestamp\":1700000000,\"duration\":2.0,\"command\":\"claude\"}"
+        );
+        assert_eq!(rest, events);
+        let (_, duration) = AsciicastFile::read_header_and_duration(&path).unwrap();
+        assert_eq!(duration, 2.0);
+    }
+
+    #[test]
+    fn window_size_prefers_cli_over_config_over_terminal() {
+        let terminal = Some((200, 50));
+        assert_eq!(
+            resolve_window_size((None, None), (None, None), terminal),
+            None
+        );
+        assert_eq!(
+            resolve_window_size((None, None), (Some(120), Some(40)), terminal),
+            Some((120, 40))
+        );
+        assert_eq!(
+            resolve_window_size((Some(100), Some(30)), (Some(120), Some(40)), terminal),
+            Some((100, 30))
+        );
+        // Mixed sources fill each dimension separately
+        assert_eq!(
+            resolve_window_size((Some(100), None), (None, Some(40)), terminal),
+            Some((100, 40))
+        );
+        assert_eq!(
+            resolve_window_size((None, None), (Some(120), None), terminal),
+            Some((120, 50))
+        );
+    }
+
+    #[test]
+    fn window_size_falls_back_when_the_terminal_size_is_unknown() {
+        assert_eq!(
+            resolve_window_size((Some(120), None), (None, None), None),
+            Some((120, FALLBACK_WINDOW_SIZE.1))
+        );
+        assert_eq!(resolve_window_size((None, None), (None, None), None), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn forced_window_size_is_stored_in_the_header() {
+        use std::os::unix::process::ExitStatusExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.cast");
+        std::fs::write(
+            &path,
+            "{\"version\":3,\"term\":{\"cols\":211,\"rows\":57,\"type\":\"xterm-256color\"},\"timestamp\":1700000000}\n[0.1,\"o\",\"$ \"]\n[0.4,\"x\",\"0\"]\n",
+        )
+        .unwrap();
+
+        let size = resolve_window_size((Some(120), Some(40)), (None, None), Some((211, 57)));
+        let status = std::process::ExitStatus::from_raw(0);
+        Recorder::new(Config::default()).finish_recording(&path, &status, None, size);
+
+        let cast = AsciicastFile::parse(&path).unwrap();
+        assert_eq!(cast.terminal_size(), (120, 40));
+        let term = cast.header.term.as_ref().unwrap();
+        assert_eq!(term.term_type.as_deref(), Some("xterm-256color"));
+        assert_eq!(cast.events.len(), 2);
+    }
+
+    #[test]
+    fn set_window_size_adds_a_missing_term() {
+        let mut cast = AsciicastFile::parse_str(
+            "{\"version\":3,\"width\":211,\"height\":57}\n[0.1,\"o\",\"$ \"]\n",
+        )
+        .unwrap();
+
+        set_window_size(&mut cast, (100, 30));
+
+        assert_eq!(cast.terminal_size(), (100, 30));
+        assert_eq!(cast.header.width, Some(100));
+        assert_eq!(cast.header.height, Some(30));
+    }
+
+    #[test]
+    fn background_analyze_command_runs_agr_analyze_on_the_cast() {
+        let exe = Path::new("/usr/local/bin/agr");
+        let cast = Path::new("/recordings/claude/session.cast");
+        let command = background_analyze_command(exe, cast);
+        assert_eq!(command.get_program(), exe.as_os_str());
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, vec!["analyze", "/recordings/claude/session.cast"]);
+    }
+
+    #[test]
+    fn auto_analyze_mode_follows_config() {
+        let mut config = RecordingConfig::default();
+        assert_eq!(AutoAnalyze::from_config(&config), AutoAnalyze::Off);
+        config.auto_analyze_background = true;
+        assert_eq!(AutoAnalyze::from_config(&config), AutoAnalyze::Off);
+        config.auto_analyze = true;
+        assert_eq!(AutoAnalyze::from_config(&config), AutoAnalyze::Background);
+        config.auto_analyze_background = false;
+        assert_eq!(AutoAnalyze::from_config(&config), AutoAnalyze::Foreground);
+    }
+
+    #[test]
+    fn hook_command_runs_through_sh_with_cast_path() {
+        let path = Path::new("/recordings/claude/session.cast");
+        let command = hook_command("tmux rename-window rec", path);
+        assert_eq!(command.get_program(), "sh");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["-c", "tmux rename-window rec"]);
+        let envs: Vec<_> = command.get_envs().collect();
+        assert_eq!(
+            envs,
+            [(std::ffi::OsStr::new(CAST_PATH_ENV), Some(path.as_os_str()))]
+        );
+    }
+
+    #[test]
+    fn post_hook_receives_cast_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let cast = dir.path().join("session one.cast");
+        let out = dir.path().join("hook.out");
+        let hook = format!("printf '%s' \"${}\" > '{}'", CAST_PATH_ENV, out.display());
+
+        run_hook(&hook, &cast).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            cast.to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn failing_hook_is_an_error() {
+        let path = Path::new("/tmp/x.cast");
+        let err = run_hook("exit 3", path).unwrap_err();
+        assert!(err.to_string().contains("exit 3"), "{}", err);
+        assert!(run_hook("true", path).is_ok());
+    }
 }
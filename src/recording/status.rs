@@ -0,0 +1,168 @@
+//! Live duration/size indicator for `agr record --status`.
+//!
+//! asciinema owns the screen while recording, so the indicator never
+//! draws on it. On a terminal it is shown in the window title (OSC 2),
+//! which asciinema does not record, and the previous title is restored on
+//! exit. When stderr is not a terminal, one plain line per update is
+//! written instead, e.g. for `agr record --status claude 2>status.log`.
+
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use humansize::{format_size, BINARY};
+
+/// How often the indicator is refreshed.
+pub const STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the monitor thread checks whether it should stop.
+const STOP_POLL: Duration = Duration::from_millis(100);
+
+/// Save the window title on terminals that keep a title stack (xterm).
+const PUSH_TITLE: &str = "\x1b[22;2t";
+/// Restore the title saved by [`PUSH_TITLE`].
+const POP_TITLE: &str = "\x1b[23;2t";
+
+/// Format elapsed time as `HH:MM:SS`.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Status text for an elapsed time and file size, e.g. `⏺ REC 00:01:05 · 1.50 MiB`.
+pub fn format_status(elapsed: Duration, size_bytes: u64) -> String {
+    format!(
+        "⏺ REC {} · {}",
+        format_elapsed(elapsed),
+        format_size(size_bytes, BINARY)
+    )
+}
+
+/// Bytes to write for one status update.
+///
+/// On a terminal the status replaces the window title; otherwise it is a
+/// plain line.
+fn status_update(status: &str, terminal: bool) -> String {
+    if terminal {
+        format!("\x1b]2;{}\x07", status)
+    } else {
+        format!("{}\n", status)
+    }
+}
+
+/// Background thread that refreshes the indicator until stopped.
+pub struct StatusMonitor {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+    terminal: bool,
+}
+
+impl StatusMonitor {
+    /// Start refreshing the indicator for a recording written to `path`.
+    pub fn start(path: PathBuf) -> Self {
+        let terminal = io::stderr().is_terminal();
+        let stop = Arc::new(AtomicBool::new(false));
+        if terminal {
+            let _ = write_stderr(PUSH_TITLE);
+        }
+
+        let thread_stop = Arc::clone(&stop);
+        let handle = std::thread::spawn(move || {
+            let started = Instant::now();
+            let mut next_update = started;
+            while !thread_stop.load(Ordering::SeqCst) {
+                if Instant::now() >= next_update {
+                    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                    let status = format_status(started.elapsed(), size);
+                    let _ = write_stderr(&status_update(&status, terminal));
+                    next_update += STATUS_INTERVAL;
+                }
+                std::thread::sleep(STOP_POLL);
+            }
+        });
+
+        Self {
+            stop,
+            handle: Some(handle),
+            terminal,
+        }
+    }
+
+    /// Stop the thread and clear the indicator.
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+            if self.terminal {
+                // Terminals without a title stack keep the empty title
+                let _ = write_stderr(&format!("{}{}", status_update("", true), POP_TITLE));
+            }
+        }
+    }
+}
+
+impl Drop for StatusMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn write_stderr(data: &str) -> io::Result<()> {
+    let mut stderr = io::stderr().lock();
+    stderr.write_all(data.as_bytes())?;
+    stderr.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_is_hours_minutes_seconds() {
+        assert_eq!(format_elapsed(Duration::ZERO), "00:00:00");
+        assert_eq!(format_elapsed(Duration::from_millis(65_900)), "00:01:05");
+        assert_eq!(
+            format_elapsed(Duration::from_secs(3 * 3600 + 7)),
+            "03:00:07"
+        );
+        assert_eq!(format_elapsed(Duration::from_secs(100 * 3600)), "100:00:00");
+    }
+
+    #[test]
+    fn status_shows_elapsed_time_and_size() {
+        assert_eq!(format_status(Duration::ZERO, 0), "⏺ REC 00:00:00 · 0 B");
+        assert_eq!(
+            format_status(Duration::from_secs(65), 1536),
+            "⏺ REC 00:01:05 · 1.50 KiB"
+        );
+        assert_eq!(
+            format_status(Duration::from_secs(3661), 5 * 1024 * 1024),
+            "⏺ REC 01:01:01 · 5 MiB"
+        );
+    }
+
+    #[test]
+    fn terminal_updates_set_the_window_title() {
+        assert_eq!(
+            status_update("⏺ REC 00:00:01 · 0 B", true),
+            "\x1b]2;⏺ REC 00:00:01 · 0 B\x07"
+        );
+        assert_eq!(
+            status_update("⏺ REC 00:00:01 · 0 B", false),
+            "⏺ REC 00:00:01 · 0 B\n"
+        );
+    }
+
+    #[test]
+    fn monitor_stops_promptly() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut monitor = StatusMonitor::start(dir.path().join("session.cast"));
+        let started = Instant::now();
+        monitor.stop();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        monitor.stop();
+    }
+}
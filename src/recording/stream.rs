@@ -0,0 +1,330 @@
+//! Live copy of a recording for `agr record --output`.
+//!
+//! asciinema appends one line per event to the cast file it records into.
+//! A [`CastStream`] follows that file while asciinema runs and writes each
+//! complete line to the sink as soon as it appears, so a consumer on the
+//! other end of a pipe sees the session as it happens. The edits
+//! [`Recorder`](super::Recorder) makes to a finished recording in storage
+//! are applied on the way:
+//!
+//! - a forced terminal size is written into the header,
+//! - captured keystrokes typed into sensitive windows are dropped (see
+//!   [`InputEchoState`]); they never reach the sink,
+//! - the auto-stop marker and the exit event are appended at the end.
+//!
+//! The header is sent before the session has a length, so a streamed
+//! recording has no `duration` in its header.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::asciicast::{Event, EventType, Header, InputEchoState};
+
+use super::{session_exit_code, set_header_window_size, AUTO_STOP_MARKER};
+
+/// Follows a cast file that is being written and copies it to a sink.
+#[derive(Debug)]
+pub struct CastStream {
+    path: PathBuf,
+    /// Opened once asciinema has created the file
+    file: Option<File>,
+    /// Bytes after the last complete line
+    partial: Vec<u8>,
+    header_sent: bool,
+    window_size: Option<(u16, u16)>,
+    /// Decides which input events are sent; `None` sends all of them
+    input_filter: Option<InputEchoState>,
+    /// Time of dropped events, added to the next event sent
+    carry_time: f64,
+    dropped: usize,
+    /// Exit code from an exit event asciinema wrote itself
+    recorded_exit: Option<i32>,
+}
+
+impl CastStream {
+    /// Follow the cast asciinema records into `path`.
+    ///
+    /// `window_size` is stored in the header; with `suppress_input`,
+    /// sensitive input events are dropped.
+    pub fn new(path: &Path, window_size: Option<(u16, u16)>, suppress_input: bool) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            file: None,
+            partial: Vec::new(),
+            header_sent: false,
+            window_size,
+            input_filter: suppress_input.then(InputEchoState::new),
+            carry_time: 0.0,
+            dropped: 0,
+            recorded_exit: None,
+        }
+    }
+
+    /// Whether anything has been written to the sink yet.
+    pub fn started(&self) -> bool {
+        self.header_sent
+    }
+
+    /// Send the complete lines appended to the file since the last call.
+    ///
+    /// Does nothing until asciinema has created the file.
+    pub fn poll<W: Write>(&mut self, sink: &mut W) -> Result<()> {
+        if self.file.is_none() {
+            match File::open(&self.path) {
+                Ok(file) => self.file = Some(file),
+                Err(_) => return Ok(()),
+            }
+        }
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+        file.read_to_end(&mut self.partial)
+            .with_context(|| format!("Failed to read recording: {}", self.path.display()))?;
+
+        let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(());
+        };
+        let complete: Vec<u8> = self.partial.drain(..=end).collect();
+        for line in complete.split(|&b| b == b'\n') {
+            self.send_line(line, sink)?;
+        }
+        sink.flush().context("Failed to write recording")
+    }
+
+    /// Send the rest of the recording and end it, returning the exit code.
+    ///
+    /// A partial last line (from a session cut by a limit) is dropped. The
+    /// auto-stop marker is added when `auto_stopped`, and an exit event
+    /// with the code asciinema exited with unless asciinema wrote one.
+    pub fn finish<W: Write>(
+        &mut self,
+        sink: &mut W,
+        auto_stopped: bool,
+        code: Option<i32>,
+        signal: Option<i32>,
+    ) -> Result<i32> {
+        self.poll(sink)?;
+        if !self.partial.is_empty() {
+            tracing::warn!(bytes = self.partial.len(), "dropped partial last line");
+        }
+        if !self.header_sent {
+            return Ok(session_exit_code(None, code, signal, false));
+        }
+        if self.dropped > 0 {
+            tracing::info!(dropped = self.dropped, "suppressed sensitive input events");
+        }
+
+        if auto_stopped {
+            self.send_event(Event::marker(0.0, AUTO_STOP_MARKER), sink)?;
+        }
+        let exit_code = match self.recorded_exit {
+            Some(recorded) => recorded,
+            None => {
+                let exit_code = session_exit_code(None, code, signal, true);
+                self.send_event(
+                    Event::new(0.0, EventType::Exit, exit_code.to_string()),
+                    sink,
+                )?;
+                exit_code
+            }
+        };
+        sink.flush().context("Failed to write recording")?;
+        Ok(exit_code)
+    }
+
+    /// Send one line of the file; unreadable event lines are skipped.
+    fn send_line<W: Write>(&mut self, line: &[u8], sink: &mut W) -> Result<()> {
+        let Ok(line) = std::str::from_utf8(line) else {
+            return Ok(());
+        };
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+
+        if !self.header_sent {
+            self.header_sent = true;
+            let header = match serde_json::from_str::<Header>(line) {
+                Ok(mut header) => {
+                    if let Some(size) = self.window_size {
+                        set_header_window_size(&mut header, size);
+                    }
+                    serde_json::to_string(&header).context("Failed to serialize header")?
+                }
+                Err(_) => line.to_string(),
+            };
+            writeln!(sink, "{}", header).context("Failed to write recording")?;
+            // The consumer can start before the first event
+            return sink.flush().context("Failed to write recording");
+        }
+
+        match Event::from_json(line) {
+            Ok(event) => self.send_event(event, sink),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Send an event unless it is sensitive input.
+    fn send_event<W: Write>(&mut self, mut event: Event, sink: &mut W) -> Result<()> {
+        match event.event_type {
+            EventType::Output => {
+                if let Some(filter) = self.input_filter.as_mut() {
+                    filter.observe_output(&event.data);
+                }
+            }
+            EventType::Input => {
+                if let Some(filter) = self.input_filter.as_mut() {
+                    if !filter.should_record_input(&event.data) {
+                        self.carry_time += event.time;
+                        self.dropped += 1;
+                        return Ok(());
+                    }
+                }
+            }
+            EventType::Exit => self.recorded_exit = event.data.trim().parse().ok(),
+            _ => {}
+        }
+        event.time += self.carry_time;
+        self.carry_time = 0.0;
+        writeln!(sink, "{}", event.to_json()).context("Failed to write recording")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+
+    const HEADER: &str = "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n";
+
+    fn append(path: &Path, text: &str) {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn lines(sink: &[u8]) -> Vec<String> {
+        String::from_utf8(sink.to_vec())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn sends_lines_as_they_are_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("live.cast");
+        let mut stream = CastStream::new(&path, None, false);
+        let mut sink = Vec::new();
+
+        // Nothing to send before asciinema creates the file
+        stream.poll(&mut sink).unwrap();
+        assert!(sink.is_empty());
+        assert!(!stream.started());
+
+        append(&path, HEADER);
+        stream.poll(&mut sink).unwrap();
+        assert_eq!(lines(&sink).len(), 1);
+        assert!(stream.started());
+
+        append(&path, "[0.5,\"o\",\"hi\"]\n[0.2,\"o\",\"th");
+        stream.poll(&mut sink).unwrap();
+        assert_eq!(lines(&sink)[1], "[0.5,\"o\",\"hi\"]");
+        assert_eq!(lines(&sink).len(), 2);
+
+        append(&path, "ere\"]\n");
+        stream.poll(&mut sink).unwrap();
+        assert_eq!(lines(&sink)[2], "[0.2,\"o\",\"there\"]");
+    }
+
+    #[test]
+    fn finish_appends_exit_event_and_drops_partial_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("live.cast");
+        append(&path, &format!("{}[0.5,\"o\",\"hi\"]\n[0.1,\"o", HEADER));
+        let mut stream = CastStream::new(&path, None, false);
+        let mut sink = Vec::new();
+
+        assert_eq!(stream.finish(&mut sink, true, Some(3), None).unwrap(), 3);
+
+        let cast =
+            crate::asciicast::AsciicastFile::parse_str(&String::from_utf8(sink).unwrap()).unwrap();
+        let kinds: Vec<EventType> = cast.events.iter().map(|e| e.event_type).collect();
+        assert_eq!(
+            kinds,
+            vec![EventType::Output, EventType::Marker, EventType::Exit]
+        );
+        assert_eq!(cast.events[1].data, AUTO_STOP_MARKER);
+        assert_eq!(cast.events[2].data, "3");
+    }
+
+    #[test]
+    fn finish_keeps_the_exit_event_asciinema_wrote() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("live.cast");
+        append(&path, &format!("{}[0.5,\"x\",\"7\"]\n", HEADER));
+        let mut stream = CastStream::new(&path, None, false);
+        let mut sink = Vec::new();
+
+        assert_eq!(stream.finish(&mut sink, false, Some(0), None).unwrap(), 7);
+        assert_eq!(lines(&sink).len(), 2);
+    }
+
+    #[test]
+    fn finish_without_recording_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut stream = CastStream::new(&dir.path().join("missing.cast"), None, false);
+        let mut sink = Vec::new();
+
+        let code = stream.finish(&mut sink, false, Some(0), None).unwrap();
+
+        assert_eq!(code, super::super::RECORDER_FAILURE_EXIT_CODE);
+        assert!(sink.is_empty());
+    }
+
+    #[test]
+    fn sensitive_input_never_reaches_the_sink() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("live.cast");
+        append(
+            &path,
+            &format!(
+                "{}[0.5,\"o\",\"Password: \"]\n[0.3,\"i\",\"hunter2\"]\n[0.2,\"i\",\"\\r\"]\n[0.1,\"o\",\"ok\"]\n",
+                HEADER
+            ),
+        );
+        let mut stream = CastStream::new(&path, None, true);
+        let mut sink = Vec::new();
+
+        stream.poll(&mut sink).unwrap();
+
+        let text = String::from_utf8(sink).unwrap();
+        assert!(!text.contains("hunter2"));
+        let cast = crate::asciicast::AsciicastFile::parse_str(&text).unwrap();
+        assert_eq!(cast.events.len(), 2);
+        // The dropped time moves to the next event
+        assert!((cast.events[1].time - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn forced_window_size_is_written_into_the_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("live.cast");
+        append(&path, "{\"version\":3}\n");
+        let mut stream = CastStream::new(&path, Some((120, 40)), false);
+        let mut sink = Vec::new();
+
+        stream.poll(&mut sink).unwrap();
+
+        let header: Header = serde_json::from_str(&lines(&sink)[0]).unwrap();
+        let term = header.term.unwrap();
+        assert_eq!((term.cols, term.rows), (Some(120), Some(40)));
+    }
+}
@@ -0,0 +1,73 @@
+# Agent Session Recorder - PowerShell Integration
+# This script provides wrapper functions for AI agents.
+# Completions are generated by clap and embedded in the profile section.
+
+# Mark that AGR shell integration is loaded
+$env:_AGR_LOADED = '1'
+
+# Template for a self-contained wrapper function; __AGENT__ is replaced
+# with the agent name. Each wrapper survives without helper functions.
+$global:_AGR_WRAPPER_TEMPLATE = @'
+$_AGR_WRAPPER = 1
+$exe = Get-Command -Name '__AGENT__' -CommandType Application -ErrorAction SilentlyContinue | Select-Object -First 1
+if (-not $exe) {
+    Write-Error "__AGENT__: command not found"
+    return
+}
+# Don't wrap if already in a recording session
+if ($env:ASCIINEMA_REC) {
+    & $exe @args
+    return
+}
+# Don't wrap if asciinema or agr aren't available
+if (-not (Get-Command -Name asciinema -CommandType Application -ErrorAction SilentlyContinue) -or
+    -not (Get-Command -Name agr -CommandType Application -ErrorAction SilentlyContinue)) {
+    & $exe @args
+    return
+}
+# Check if this agent should be wrapped
+agr agents is-wrapped '__AGENT__' 2>$null | Out-Null
+if ($LASTEXITCODE -ne 0) {
+    & $exe @args
+    return
+}
+# Record the session
+agr record '__AGENT__' -- @args
+'@
+
+# Generate wrapper functions from config
+function global:_agr_setup_wrappers {
+    $agents = @()
+
+    # Try to get agent list from agr, stripping ANSI color codes
+    if (Get-Command -Name agr -CommandType Application -ErrorAction SilentlyContinue) {
+        $esc = [char]27
+        $agents = @(agr agents list 2>$null |
+            ForEach-Object { ($_ -replace "$esc\[[0-9;]*m", '').Trim() } |
+            Where-Object { $_ -and $_ -notmatch '^(Configured|No agents)' })
+    }
+
+    # Fallback to default agents if agr not available
+    if ($agents.Count -eq 0) {
+        $agents = @('claude', 'codex', 'gemini')
+    }
+
+    foreach ($agent in $agents) {
+        # Validate agent name (alphanumeric, dash, underscore only)
+        if ($agent -notmatch '^[a-zA-Z0-9_-]+$') {
+            continue
+        }
+
+        # Skip if a self-contained AGR wrapper already exists for this agent
+        $existing = Get-Item -Path "function:$agent" -ErrorAction SilentlyContinue
+        if ($existing -and $existing.Definition -match '_AGR_WRAPPER') {
+            continue
+        }
+
+        $body = $global:_AGR_WRAPPER_TEMPLATE.Replace('__AGENT__', $agent)
+        Set-Item -Path "function:global:$agent" -Value ([scriptblock]::Create($body))
+    }
+}
+
+# Initialize wrappers
+_agr_setup_wrappers
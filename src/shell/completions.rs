@@ -6,12 +6,14 @@
 
 use std::fs;
 use std::io;
+use std::path::{Path, PathBuf};
 
 use clap::CommandFactory;
 
 use super::minify;
-use super::paths::{bash_completion_path, zsh_completion_path};
+use super::paths::{bash_completion_path, fish_completion_path, zsh_completion_path};
 use crate::cli::Cli;
+use crate::Config;
 
 // ============================================================================
 // Legacy completion file cleanup
@@ -39,6 +41,105 @@ pub fn cleanup_old_completions() -> io::Result<()> {
     Ok(())
 }
 
+// ============================================================================
+// Fish completion file
+// ============================================================================
+
+/// Install fish completions to `~/.config/fish/completions/agr.fish`
+///
+/// Fish has no RC section to embed completions in; it autoloads one file
+/// per command instead. The file is only written when fish is installed
+/// or its config directory exists. Returns the path written, if any.
+pub fn install_fish_completions() -> io::Result<Option<PathBuf>> {
+    let Some(path) = fish_completion_path() else {
+        return Ok(None);
+    };
+    let fish_dir = path.parent().and_then(Path::parent);
+    let has_fish =
+        crate::analyzer::backend::command_exists("fish") || fish_dir.is_some_and(Path::exists);
+    if !has_fish {
+        return Ok(None);
+    }
+    write_fish_completions(&path)?;
+    Ok(Some(path))
+}
+
+/// Write the generated fish completion script, creating parent directories.
+pub fn write_fish_completions(path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", generate_fish_init(false)))
+}
+
+/// Remove the fish completion file. Returns the path if one was removed.
+pub fn remove_fish_completions() -> io::Result<Option<PathBuf>> {
+    match fish_completion_path() {
+        Some(path) if path.exists() => {
+            fs::remove_file(&path)?;
+            Ok(Some(path))
+        }
+        _ => Ok(None),
+    }
+}
+
+// ============================================================================
+// Dynamic agent completion
+// ============================================================================
+
+/// Which agents `agr completions --agents` lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum AgentCompletion {
+    /// Agents in the enabled list (`agr record`, `agr agents remove`)
+    #[default]
+    Enabled,
+    /// Enabled agents that are still auto-wrapped (`agr agents no-wrap add`)
+    Wrapped,
+    /// Agents on the no-wrap list (`agr agents no-wrap remove`)
+    NoWrap,
+    /// Installed analysis agents (`agr analyze --agent`)
+    Analysis,
+}
+
+/// List agent names for completion, filtered by `prefix`
+pub fn agent_completions(config: &Config, kind: AgentCompletion, prefix: &str) -> Vec<String> {
+    agent_completions_with(
+        config,
+        kind,
+        prefix,
+        crate::analyzer::backend::command_exists,
+    )
+}
+
+/// [`agent_completions`] with a custom binary lookup.
+pub fn agent_completions_with(
+    config: &Config,
+    kind: AgentCompletion,
+    prefix: &str,
+    command_exists: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let agents: Vec<String> = match kind {
+        AgentCompletion::Enabled => config.agents.enabled.clone(),
+        AgentCompletion::Wrapped => config
+            .agents
+            .enabled
+            .iter()
+            .filter(|name| config.should_wrap_agent(name))
+            .cloned()
+            .collect(),
+        AgentCompletion::NoWrap => config.agents.no_wrap.clone(),
+        AgentCompletion::Analysis => ["claude", "codex", "gemini"]
+            .iter()
+            .filter(|name| command_exists(name))
+            .map(|name| name.to_string())
+            .collect(),
+    };
+    agents
+        .into_iter()
+        .filter(|name| name.starts_with(prefix))
+        .collect()
+}
+
 // ============================================================================
 // Dynamic shell init code generation (REQ-1)
 // ============================================================================
@@ -52,6 +153,10 @@ pub struct CommandInfo {
     pub description: String,
     /// Whether this command accepts a file argument
     pub accepts_file: bool,
+    /// Whether this command takes an agent name as a positional argument
+    pub accepts_agent: bool,
+    /// Whether this command has an `--agent` option
+    pub agent_option: bool,
     /// Subcommands (if any)
     pub subcommands: Vec<CommandInfo>,
 }
@@ -72,6 +177,8 @@ fn extract_subcommands(cmd: &clap::Command) -> Vec<CommandInfo> {
             name: sub.get_name().to_string(),
             description: sub.get_about().map(|s| s.to_string()).unwrap_or_default(),
             accepts_file: has_file_argument(sub),
+            accepts_agent: sub.get_positionals().any(|arg| arg.get_id() == "agent"),
+            agent_option: sub.get_opts().any(|arg| arg.get_id() == "agent"),
             subcommands: extract_subcommands(sub),
         })
         .collect()
@@ -82,6 +189,32 @@ fn has_file_argument(cmd: &clap::Command) -> bool {
     cmd.get_positionals().any(|arg| arg.get_id() == "file")
 }
 
+/// Space-separated command names for agent completion
+struct AgentCommandLists {
+    /// Commands taking an agent name as a positional argument
+    positional: String,
+    /// Commands whose `--agent` picks the analysis agent for a recording
+    analysis_option: String,
+    /// Commands whose `--agent` filters recordings by agent
+    filter_option: String,
+}
+
+fn agent_command_lists(commands: &[CommandInfo]) -> AgentCommandLists {
+    let names = |pred: fn(&CommandInfo) -> bool| {
+        commands
+            .iter()
+            .filter(|c| pred(c))
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    AgentCommandLists {
+        positional: names(|c| c.accepts_agent),
+        analysis_option: names(|c| c.agent_option && c.accepts_file),
+        filter_option: names(|c| c.agent_option && !c.accepts_file),
+    }
+}
+
 /// Generate zsh initialization code with embedded completions
 ///
 /// The generated code includes:
@@ -142,12 +275,20 @@ pub fn generate_zsh_init(debug: bool) -> String {
         .map(|c| c.name.as_str())
         .collect();
     let file_cmds_space = file_cmds.join(" ");
+    let AgentCommandLists {
+        positional: agent_cmds,
+        analysis_option: agent_opt_cmds,
+        filter_option: agent_filter_cmds,
+    } = agent_command_lists(&commands);
 
     let raw_output = format!(
         r#"# AGR Shell Integration - Zsh
 # Generated by: agr completions --shell-init zsh
 _agr_commands=({cmd_array})
 _agr_file_cmds="{file_cmds_space}"
+_agr_agent_cmds="{agent_cmds}"
+_agr_agent_opt_cmds="{agent_opt_cmds}"
+_agr_agent_filter_cmds="{agent_filter_cmds}"
 {subcmd_arrays}
 # Zsh-specific completion setup (skip if sourced by bash for testing)
 if [[ -n "$ZSH_VERSION" ]]; then
@@ -163,22 +304,42 @@ if [[ -n "$ZSH_VERSION" ]]; then
         (( $#files )) && _describe 'recordings' files
     }}
 
-    # Multi-layer completion: commands, subcommands, files
+    # Helper: complete with agent names (enabled, wrapped, no-wrap, analysis)
+    _agr_complete_agents() {{
+        local -a agents
+        agents=(${{(f)"$(agr completions --agents=$1 2>/dev/null)"}})
+        (( $#agents )) && _describe 'agents' agents
+    }}
+
+    # Multi-layer completion: commands, subcommands, files, agents
     _agr_complete() {{
         local cur="${{words[CURRENT]}}"
+        local prev="${{words[CURRENT-1]}}"
         local cmd="${{words[2]}}"
         local subcmd="${{words[3]}}"
 
-        if (( CURRENT == 2 )); then
+        if [[ "$prev" == "--agent" || "$prev" == "-a" ]] && [[ " $_agr_agent_opt_cmds " =~ " $cmd " ]]; then
+            _agr_complete_agents analysis
+        elif [[ "$prev" == "--agent" ]] && [[ " $_agr_agent_filter_cmds " =~ " $cmd " ]]; then
+            _agr_complete_agents enabled
+        elif (( CURRENT == 2 )); then
             _describe 'commands' _agr_commands
         elif (( CURRENT == 3 )); then
             case "$cmd" in
-{subcmd_cases}            *) [[ " $_agr_file_cmds " =~ " $cmd " ]] && _agr_complete_files "$cur" ;;
+{subcmd_cases}            *) [[ " $_agr_file_cmds " =~ " $cmd " ]] && _agr_complete_files "$cur"; [[ " $_agr_agent_cmds " =~ " $cmd " ]] && _agr_complete_agents enabled ;;
             esac
         elif (( CURRENT >= 4 )); then
-            # Position 4+: files for marker add/list, or other file-accepting contexts
+            # Position 4+: files for marker add/list, agent names for agents subcommands
             if [[ "$cmd" == "marker" ]]; then
                 _agr_complete_files "$cur"
+            elif [[ "$cmd" == "agents" ]]; then
+                if (( CURRENT == 4 )) && [[ "$subcmd" == "remove" || "$subcmd" == "is-wrapped" ]]; then
+                    _agr_complete_agents enabled
+                elif (( CURRENT == 5 )) && [[ "$subcmd" == "no-wrap" && "${{words[4]}}" == "add" ]]; then
+                    _agr_complete_agents wrapped
+                elif (( CURRENT == 5 )) && [[ "$subcmd" == "no-wrap" && "${{words[4]}}" == "remove" ]]; then
+                    _agr_complete_agents no-wrap
+                fi
             fi
         fi
     }}
@@ -220,6 +381,11 @@ pub fn generate_bash_init(debug: bool) -> String {
         .map(|c| c.name.as_str())
         .collect();
     let file_cmd_pattern = file_cmds.join(" ");
+    let AgentCommandLists {
+        positional: agent_cmds,
+        analysis_option: agent_opt_cmds,
+        filter_option: agent_filter_cmds,
+    } = agent_command_lists(&commands);
 
     // Build subcommand variables and case statements
     let mut subcmd_vars = String::new();
@@ -249,6 +415,9 @@ pub fn generate_bash_init(debug: bool) -> String {
 # Generated by: agr completions --shell-init bash
 _agr_commands="{cmd_list}"
 _agr_file_cmds="{file_cmd_pattern}"
+_agr_agent_cmds="{agent_cmds}"
+_agr_agent_opt_cmds="{agent_opt_cmds}"
+_agr_agent_filter_cmds="{agent_filter_cmds}"
 {subcmd_vars}
 # Helper: complete with cast files
 _agr_complete_files() {{
@@ -258,21 +427,42 @@ _agr_complete_files() {{
     COMPREPLY=($(compgen -W "$files" -- "$cur"))
 }}
 
+# Helper: complete with agent names (enabled, wrapped, no-wrap, analysis)
+_agr_complete_agents() {{
+    local cur="$1"
+    local agents
+    agents=$(agr completions --agents=$2 2>/dev/null)
+    COMPREPLY=($(compgen -W "$agents" -- "$cur"))
+}}
+
 _agr_complete() {{
     local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
     local cmd="${{COMP_WORDS[1]}}"
     local subcmd="${{COMP_WORDS[2]}}"
 
-    if [[ $COMP_CWORD -eq 1 ]]; then
+    if [[ "$prev" == "--agent" || "$prev" == "-a" ]] && [[ " $_agr_agent_opt_cmds " =~ " $cmd " ]]; then
+        _agr_complete_agents "$cur" analysis
+    elif [[ "$prev" == "--agent" ]] && [[ " $_agr_agent_filter_cmds " =~ " $cmd " ]]; then
+        _agr_complete_agents "$cur" enabled
+    elif [[ $COMP_CWORD -eq 1 ]]; then
         COMPREPLY=($(compgen -W "$_agr_commands" -- "$cur"))
     elif [[ $COMP_CWORD -eq 2 ]]; then
         case "$cmd" in
-{subcmd_cases}        *) [[ " $_agr_file_cmds " =~ " $cmd " ]] && _agr_complete_files "$cur" ;;
+{subcmd_cases}        *) [[ " $_agr_file_cmds " =~ " $cmd " ]] && _agr_complete_files "$cur"; [[ " $_agr_agent_cmds " =~ " $cmd " ]] && _agr_complete_agents "$cur" enabled ;;
         esac
     elif [[ $COMP_CWORD -ge 3 ]]; then
-        # Position 3+: files for marker add/list, or other file-accepting contexts
+        # Position 3+: files for marker add/list, agent names for agents subcommands
         if [[ "$cmd" == "marker" ]]; then
             _agr_complete_files "$cur"
+        elif [[ "$cmd" == "agents" ]]; then
+            if [[ $COMP_CWORD -eq 3 ]] && [[ "$subcmd" == "remove" || "$subcmd" == "is-wrapped" ]]; then
+                _agr_complete_agents "$cur" enabled
+            elif [[ $COMP_CWORD -eq 4 ]] && [[ "$subcmd" == "no-wrap" && "${{COMP_WORDS[3]}}" == "add" ]]; then
+                _agr_complete_agents "$cur" wrapped
+            elif [[ $COMP_CWORD -eq 4 ]] && [[ "$subcmd" == "no-wrap" && "${{COMP_WORDS[3]}}" == "remove" ]]; then
+                _agr_complete_agents "$cur" no-wrap
+            fi
         fi
     fi
 }}
@@ -289,6 +479,137 @@ complete -F _agr_complete agr
     }
 }
 
+/// Quote a string for fish using single quotes
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Generate fish completions
+///
+/// The generated code includes:
+/// - One `complete` rule per command and subcommand (from clap)
+/// - Dynamic file completion via `agr completions --files`, evaluated by
+///   fish each time Tab is pressed
+///
+/// Unlike bash and zsh this is written to fish's completions directory
+/// rather than an RC file. When `debug` is false, comments, blank lines
+/// and indentation are stripped.
+pub fn generate_fish_init(debug: bool) -> String {
+    let commands = extract_commands();
+
+    let cmd_names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+    let cmd_list = cmd_names.join(" ");
+
+    let mut rules = String::new();
+    for cmd in &commands {
+        rules.push_str(&format!(
+            "complete -c agr -n __fish_use_subcommand -a {} -d {}\n",
+            cmd.name,
+            fish_quote(&cmd.description)
+        ));
+    }
+
+    let mut subcmd_rules = String::new();
+    let mut nested_file_rules = String::new();
+    for cmd in &commands {
+        if cmd.subcommands.is_empty() {
+            continue;
+        }
+        let sub_names: Vec<&str> = cmd.subcommands.iter().map(|s| s.name.as_str()).collect();
+        let sub_list = sub_names.join(" ");
+        for sub in &cmd.subcommands {
+            subcmd_rules.push_str(&format!(
+                "complete -c agr -n '__fish_seen_subcommand_from {}; and not __fish_seen_subcommand_from {}' -a {} -d {}\n",
+                cmd.name,
+                sub_list,
+                sub.name,
+                fish_quote(&sub.description)
+            ));
+        }
+        let file_subs: Vec<&str> = cmd
+            .subcommands
+            .iter()
+            .filter(|s| s.accepts_file)
+            .map(|s| s.name.as_str())
+            .collect();
+        if !file_subs.is_empty() {
+            nested_file_rules.push_str(&format!(
+                "complete -c agr -n '__fish_seen_subcommand_from {}; and __fish_seen_subcommand_from {}' -a '(__agr_complete_files)'\n",
+                cmd.name,
+                file_subs.join(" ")
+            ));
+        }
+    }
+
+    let file_cmds: Vec<&str> = commands
+        .iter()
+        .filter(|c| c.accepts_file)
+        .map(|c| c.name.as_str())
+        .collect();
+    let file_cmd_list = file_cmds.join(" ");
+
+    let agent_lists = agent_command_lists(&commands);
+    let mut agent_rules = String::new();
+    if !agent_lists.positional.is_empty() {
+        agent_rules.push_str(&format!(
+            "complete -c agr -n '__fish_seen_subcommand_from {}; and not __fish_seen_subcommand_from agents marker' -a '(__agr_complete_agents enabled)'\n",
+            agent_lists.positional
+        ));
+    }
+    if !agent_lists.analysis_option.is_empty() {
+        agent_rules.push_str(&format!(
+            "complete -c agr -n '__fish_seen_subcommand_from {}' -s a -l agent -x -a '(__agr_complete_agents analysis)'\n",
+            agent_lists.analysis_option
+        ));
+    }
+    if !agent_lists.filter_option.is_empty() {
+        agent_rules.push_str(&format!(
+            "complete -c agr -n '__fish_seen_subcommand_from {}' -l agent -x -a '(__agr_complete_agents enabled)'\n",
+            agent_lists.filter_option
+        ));
+    }
+    agent_rules.push_str(
+        "complete -c agr -n '__fish_seen_subcommand_from agents; and __fish_seen_subcommand_from remove is-wrapped; and not __fish_seen_subcommand_from no-wrap' -a '(__agr_complete_agents enabled)'\n\
+         complete -c agr -n '__fish_seen_subcommand_from no-wrap; and __fish_seen_subcommand_from add' -a '(__agr_complete_agents wrapped)'\n\
+         complete -c agr -n '__fish_seen_subcommand_from no-wrap; and __fish_seen_subcommand_from remove' -a '(__agr_complete_agents no-wrap)'\n",
+    );
+
+    let raw_output = format!(
+        r#"# AGR Shell Integration - Fish
+# Generated by: agr completions --shell-init fish
+# Commands: {cmd_list}
+
+# Never fall back to completing paths in the current directory
+complete -c agr -f
+
+# Helper: complete with cast files matching the current token
+function __agr_complete_files
+    agr completions --files --limit 20 (commandline -ct) 2>/dev/null
+end
+
+# Helper: complete with agent names (enabled, wrapped, no-wrap, analysis)
+function __agr_complete_agents
+    agr completions --agents=$argv[1] (commandline -ct) 2>/dev/null
+end
+
+# Commands
+{rules}
+# Subcommands
+{subcmd_rules}
+# Files for file-accepting commands and subcommands
+complete -c agr -n '__fish_seen_subcommand_from {file_cmd_list}' -a '(__agr_complete_files)'
+{nested_file_rules}
+# Agent names
+{agent_rules}"#
+    );
+
+    if debug {
+        minify::debug(&raw_output)
+    } else {
+        minify::strip(&raw_output)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,4 +752,136 @@ mod tests {
             "Should register completion function"
         );
     }
+
+    #[test]
+    fn fish_quote_escapes_quotes_and_backslashes() {
+        assert_eq!(fish_quote("plain"), "'plain'");
+        assert_eq!(fish_quote("it's"), r"'it\'s'");
+        assert_eq!(fish_quote(r"a\b"), r"'a\\b'");
+    }
+
+    #[test]
+    fn generate_fish_init_contains_commands() {
+        let init = generate_fish_init(true);
+        assert!(init.contains("complete -c agr -n __fish_use_subcommand -a record"));
+        assert!(init.contains("-a play"));
+        assert!(init.contains("-a status"));
+    }
+
+    #[test]
+    fn generate_fish_init_completes_subcommands() {
+        let init = generate_fish_init(true);
+        assert!(
+            init.contains("__fish_seen_subcommand_from shell; and not __fish_seen_subcommand_from")
+        );
+        assert!(init.contains("-a install"));
+    }
+
+    #[test]
+    fn generate_fish_init_completes_files_dynamically() {
+        let init = generate_fish_init(true);
+        assert!(init.contains("function __agr_complete_files"));
+        assert!(init.contains("agr completions --files --limit 20 (commandline -ct)"));
+        assert!(init
+            .contains("'__fish_seen_subcommand_from marker; and __fish_seen_subcommand_from add"));
+
+        let file_rule = init
+            .lines()
+            .find(|l| l.contains("-a '(__agr_complete_files)'"))
+            .unwrap();
+        assert!(file_rule.contains(" play "));
+        assert!(!file_rule.contains(" status "));
+    }
+
+    #[test]
+    fn generate_fish_init_minified_strips_comments() {
+        let init = generate_fish_init(false);
+        assert!(!init.contains("# AGR"));
+        assert!(!init.lines().any(|l| l.trim().is_empty()));
+        assert!(!init.lines().any(|l| l.starts_with(char::is_whitespace)));
+        assert!(init.contains("function __agr_complete_files\nagr completions"));
+    }
+
+    #[test]
+    fn write_fish_completions_creates_parent_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fish/completions/agr.fish");
+
+        write_fish_completions(&path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("complete -c agr"));
+    }
+
+    fn agents_config() -> Config {
+        let mut config = Config::default();
+        config.agents.enabled = vec!["claude".into(), "codex".into(), "aider".into()];
+        config.agents.no_wrap = vec!["codex".into()];
+        config
+    }
+
+    #[test]
+    fn agent_completions_list_enabled_agents() {
+        let config = agents_config();
+        assert_eq!(
+            agent_completions_with(&config, AgentCompletion::Enabled, "", |_| false),
+            config.agents.enabled
+        );
+        assert_eq!(
+            agent_completions_with(&config, AgentCompletion::Enabled, "c", |_| false),
+            vec!["claude", "codex"]
+        );
+    }
+
+    #[test]
+    fn agent_completions_respect_no_wrap_list() {
+        let config = agents_config();
+        assert_eq!(
+            agent_completions_with(&config, AgentCompletion::Wrapped, "", |_| false),
+            vec!["claude", "aider"]
+        );
+        assert_eq!(
+            agent_completions_with(&config, AgentCompletion::NoWrap, "", |_| false),
+            vec!["codex"]
+        );
+    }
+
+    #[test]
+    fn agent_completions_analysis_lists_installed_agents() {
+        let config = agents_config();
+        assert_eq!(
+            agent_completions_with(&config, AgentCompletion::Analysis, "", |cmd| cmd != "codex"),
+            vec!["claude", "gemini"]
+        );
+    }
+
+    #[test]
+    fn extract_commands_marks_agent_arguments() {
+        let commands = extract_commands();
+        let find = |name: &str| commands.iter().find(|c| c.name == name).unwrap();
+        assert!(find("record").accepts_agent);
+        assert!(!find("play").accepts_agent);
+        assert!(find("analyze").agent_option);
+        assert!(!find("record").agent_option);
+
+        let lists = agent_command_lists(&commands);
+        assert!(lists.analysis_option.split(' ').any(|c| c == "analyze"));
+        assert!(lists.filter_option.split(' ').any(|c| c == "cleanup"));
+        assert!(!lists.analysis_option.split(' ').any(|c| c == "cleanup"));
+    }
+
+    #[test]
+    fn init_scripts_complete_agents() {
+        for init in [generate_zsh_init(true), generate_bash_init(true)] {
+            assert!(init.contains("_agr_complete_agents"));
+            assert!(init.contains("agr completions --agents=$"));
+            assert!(init.contains("_agr_agent_cmds=\"record"));
+            assert!(init.contains("wrapped"));
+            assert!(init.contains("no-wrap"));
+        }
+        let fish = generate_fish_init(true);
+        assert!(fish.contains("'__fish_seen_subcommand_from record"));
+        assert!(fish.contains("-s a -l agent -x -a '(__agr_complete_agents analysis)'"));
+        assert!(fish.contains("-l agent -x -a '(__agr_complete_agents enabled)'"));
+    }
 }
@@ -0,0 +1,141 @@
+//! Shell integration health checks
+//!
+//! This module detects broken shell integration states that install and
+//! uninstall can leave behind across versions: duplicated RC sections,
+//! old-style sections that source a script file that no longer exists,
+//! and completion files left over after the integration was removed.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::install::install;
+use super::paths::{
+    all_shell_rcs, bash_completion_path, fish_completion_path, zsh_completion_path,
+};
+use super::status::{extract_script_path, is_installed_in};
+
+/// A problem found in the shell integration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellIssue {
+    /// The integration section appears more than once in an RC file
+    DuplicateBlocks { rc_file: PathBuf, count: usize },
+    /// An old-style section sources a script file that does not exist
+    MissingScript {
+        rc_file: PathBuf,
+        script_path: PathBuf,
+    },
+    /// A completion file exists but no RC file has the integration
+    OrphanedCompletion { path: PathBuf },
+}
+
+impl ShellIssue {
+    /// Describe how [`ShellIssue::repair`] fixes this issue.
+    pub fn repair_hint(&self) -> &'static str {
+        match self {
+            ShellIssue::DuplicateBlocks { .. } | ShellIssue::MissingScript { .. } => {
+                "reinstall a single embedded section"
+            }
+            ShellIssue::OrphanedCompletion { .. } => "remove the completion file",
+        }
+    }
+
+    /// Repair the issue.
+    ///
+    /// RC file issues are fixed by reinstalling, which removes every
+    /// existing section and writes one embedded section. Orphaned
+    /// completion files are deleted.
+    pub fn repair(&self) -> io::Result<()> {
+        match self {
+            ShellIssue::DuplicateBlocks { rc_file, .. }
+            | ShellIssue::MissingScript { rc_file, .. } => install(rc_file),
+            ShellIssue::OrphanedCompletion { path } => fs::remove_file(path),
+        }
+    }
+}
+
+impl fmt::Display for ShellIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShellIssue::DuplicateBlocks { rc_file, count } => write!(
+                f,
+                "{} contains {} shell integration sections (expected 1)",
+                rc_file.display(),
+                count
+            ),
+            ShellIssue::MissingScript {
+                rc_file,
+                script_path,
+            } => write!(
+                f,
+                "{} sources a missing script: {}",
+                rc_file.display(),
+                script_path.display()
+            ),
+            ShellIssue::OrphanedCompletion { path } => write!(
+                f,
+                "Orphaned completion file (shell integration not installed): {}",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Check the user's RC files and completion files for problems.
+pub fn diagnose() -> io::Result<Vec<ShellIssue>> {
+    let completion_files: Vec<PathBuf> = [
+        fish_completion_path(),
+        bash_completion_path(),
+        zsh_completion_path(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    diagnose_in(&all_shell_rcs(), &completion_files)
+}
+
+/// Check the given RC files and completion file locations for problems.
+///
+/// Completion files that do not exist are ignored; existing ones are
+/// reported as orphaned when none of the RC files has the integration.
+pub fn diagnose_in(
+    rc_files: &[PathBuf],
+    completion_files: &[PathBuf],
+) -> io::Result<Vec<ShellIssue>> {
+    let mut issues = Vec::new();
+    let mut installed = false;
+
+    for rc_file in rc_files {
+        let count = is_installed_in(rc_file)?;
+        installed |= count > 0;
+        if count > 1 {
+            issues.push(ShellIssue::DuplicateBlocks {
+                rc_file: rc_file.clone(),
+                count,
+            });
+        }
+        if let Some(script_path) = missing_script(rc_file)? {
+            issues.push(ShellIssue::MissingScript {
+                rc_file: rc_file.clone(),
+                script_path,
+            });
+        }
+    }
+
+    if !installed {
+        issues.extend(
+            completion_files
+                .iter()
+                .filter(|path| path.exists())
+                .map(|path| ShellIssue::OrphanedCompletion { path: path.clone() }),
+        );
+    }
+
+    Ok(issues)
+}
+
+/// The script an old-style section sources, if that script is missing.
+fn missing_script(rc_file: &Path) -> io::Result<Option<PathBuf>> {
+    Ok(extract_script_path(rc_file)?.filter(|path| !path.exists()))
+}
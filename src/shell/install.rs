@@ -1,19 +1,23 @@
 //! RC file installation and uninstallation
 //!
 //! This module handles installing and uninstalling the shell integration
-//! section in RC files (.zshrc, .bashrc).
+//! section in RC files (.zshrc, .bashrc) and the PowerShell profile.
 
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::path::Path;
 
 use super::completions::{generate_bash_init, generate_zsh_init};
 use super::minify;
-use super::status::{is_installed_in, MARKER_END, MARKER_START};
+use super::powershell::generate_powershell_init;
+use super::status::{block_spans, MARKER_END, MARKER_START, PS_MARKER_END, PS_MARKER_START};
 
 /// Warning comment included in the shell integration section
 pub const MARKER_WARNING: &str = "# DO NOT EDIT - managed by 'agr shell install/uninstall'";
 
+/// Version comment included in the shell integration section
+pub const VERSION_COMMENT: &str = concat!("# agr ", env!("CARGO_PKG_VERSION"));
+
 /// The embedded shell script content (wrapper functions for agents)
 pub const SHELL_SCRIPT: &str = include_str!("assets/agr.sh");
 
@@ -22,13 +26,29 @@ pub const SHELL_SCRIPT: &str = include_str!("assets/agr.sh");
 pub enum Shell {
     Zsh,
     Bash,
+    PowerShell,
+}
+
+/// Start and end marker comments of an integration section
+pub type Markers = (&'static str, &'static str);
+
+impl Shell {
+    /// Marker comments delimiting this shell's integration section
+    pub fn markers(self) -> Markers {
+        match self {
+            Shell::Zsh | Shell::Bash => (MARKER_START, MARKER_END),
+            Shell::PowerShell => (PS_MARKER_START, PS_MARKER_END),
+        }
+    }
 }
 
 /// Detect shell type from RC file name
-fn detect_shell_from_rc(rc_file: &Path) -> Shell {
+pub fn detect_shell_from_rc(rc_file: &Path) -> Shell {
     let name = rc_file.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-    if name.contains("zsh") {
+    if name.to_ascii_lowercase().ends_with(".ps1") {
+        Shell::PowerShell
+    } else if name.contains("zsh") {
         Shell::Zsh
     } else {
         Shell::Bash
@@ -47,35 +67,42 @@ fn detect_shell_from_rc(rc_file: &Path) -> Shell {
 /// - Ghost text autosuggestions for file-accepting commands
 ///
 /// The combined script is minified to reduce the size of the RC file.
+/// PowerShell sections use `#region` markers and are not minified.
 pub fn generate_section(shell: Shell) -> String {
     // Generate shell-specific init code with embedded completions
     // Use debug=false for minified output in RC files
     let init_code = match shell {
         Shell::Zsh => generate_zsh_init(false),
         Shell::Bash => generate_bash_init(false),
+        Shell::PowerShell => {
+            let init = generate_powershell_init();
+            return format!(
+                "{PS_MARKER_START}\n{MARKER_WARNING}\n{VERSION_COMMENT}\n{init}\n{PS_MARKER_END}"
+            );
+        }
     };
 
     // Combine wrapper script with completions and minify
     let combined = format!("{}\n{}", SHELL_SCRIPT, init_code);
     let minified = minify::exec(&combined);
 
-    format!("{MARKER_START}\n{MARKER_WARNING}\n{minified}\n{MARKER_END}")
+    format!("{MARKER_START}\n{MARKER_WARNING}\n{VERSION_COMMENT}\n{minified}\n{MARKER_END}")
 }
 
 /// Install shell integration to an RC file
 ///
 /// This embeds the full shell script content directly into the RC file,
 /// including dynamically generated completions based on shell type.
-/// If there's an existing installation (old-style or new), it will be replaced.
+///
+/// If the file already has an integration section (old-style or new), the
+/// first one is replaced in place and any duplicates are removed, so
+/// re-running install after an upgrade only updates the section. Content
+/// outside the markers is preserved byte-for-byte. Otherwise the section
+/// is appended. The file is written atomically.
 pub fn install(rc_file: &Path) -> io::Result<()> {
     // Detect shell type from RC file
     let shell = detect_shell_from_rc(rc_file);
 
-    // First, remove any existing installation (handles both old and new style)
-    if is_installed_in(rc_file)? {
-        uninstall(rc_file)?;
-    }
-
     // Read existing content
     let content = if rc_file.exists() {
         fs::read_to_string(rc_file)?
@@ -86,46 +113,108 @@ pub fn install(rc_file: &Path) -> io::Result<()> {
     // Generate section with embedded script and completions
     let section = generate_section(shell);
 
-    // Append to file
-    let new_content = if content.is_empty() {
-        section
-    } else if content.ends_with('\n') {
-        format!("{content}\n{section}\n")
+    let new_content = replace_sections(&content, &section, shell.markers()).unwrap_or_else(|| {
+        // Append to file
+        if content.is_empty() {
+            section
+        } else if content.ends_with('\n') {
+            format!("{content}\n{section}\n")
+        } else {
+            format!("{content}\n\n{section}\n")
+        }
+    });
+
+    if new_content == content {
+        return Ok(());
+    }
+    write_atomically(rc_file, &new_content)
+}
+
+/// Replace the first integration section in `content` with `section`.
+///
+/// Later duplicate sections are removed together with their line ending.
+/// Returns `None` if the content has no complete section.
+fn replace_sections(content: &str, section: &str, markers: Markers) -> Option<String> {
+    let spans = block_spans(content, markers);
+    let first = spans.first()?;
+
+    let mut result = String::with_capacity(content.len() + section.len());
+    result.push_str(&content[..first.start]);
+    result.push_str(section);
+    let mut rest = first.end;
+    for span in &spans[1..] {
+        result.push_str(&content[rest..span.start]);
+        rest = span.end;
+        let after = &content[rest..];
+        rest += if after.starts_with("\r\n") {
+            2
+        } else {
+            usize::from(after.starts_with('\n'))
+        };
+    }
+    result.push_str(&content[rest..]);
+    Some(result)
+}
+
+/// Write `content` to `path` via a temp file in the same directory and a rename.
+///
+/// A symlinked RC file (e.g. managed by a dotfiles repo) is resolved so the
+/// link itself is kept, and the original file permissions are preserved.
+fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+    let target = if path.is_symlink() {
+        fs::canonicalize(path)?
     } else {
-        format!("{content}\n\n{section}\n")
+        path.to_path_buf()
     };
+    let file_name = target.file_name().and_then(|n| n.to_str()).unwrap_or("rc");
+    let temp_path = target.with_file_name(format!(".{file_name}.agr-tmp"));
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(&target) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        fs::rename(&temp_path, &target)
+    })();
 
-    fs::write(rc_file, new_content)
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
 }
 
 /// Uninstall shell integration from an RC file
+///
+/// Removes every marked section, so blocks duplicated by earlier installs
+/// are cleaned up too. A stray start or end marker without its partner is
+/// removed on its own, leaving the lines around it untouched.
 pub fn uninstall(rc_file: &Path) -> io::Result<bool> {
     if !rc_file.exists() {
         return Ok(false);
     }
 
     let content = fs::read_to_string(rc_file)?;
+    let markers = detect_shell_from_rc(rc_file).markers();
+    let (marker_start, marker_end) = markers;
 
-    if !content.contains(MARKER_START) {
+    if !content.contains(marker_start) {
         return Ok(false);
     }
 
-    // Remove the marked section
+    // Remove the marked sections and any stray markers
+    let spans = block_spans(&content, markers);
     let mut new_lines: Vec<&str> = Vec::new();
-    let mut in_section = false;
+    let mut offset = 0;
 
-    for line in content.lines() {
-        if line.contains(MARKER_START) {
-            in_section = true;
-            continue;
-        }
-        if line.contains(MARKER_END) {
-            in_section = false;
-            continue;
-        }
-        if !in_section {
+    for raw in content.split_inclusive('\n') {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let in_section = spans.iter().any(|span| span.contains(&offset));
+        if !in_section && !line.contains(marker_start) && !line.contains(marker_end) {
             new_lines.push(line);
         }
+        offset += raw.len();
     }
 
     // Remove trailing empty lines that were before the section
@@ -179,12 +268,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detect_shell_powershell() {
+        assert_eq!(
+            detect_shell_from_rc(&PathBuf::from(
+                "C:/Users/u/Documents/PowerShell/Microsoft.PowerShell_profile.ps1"
+            )),
+            Shell::PowerShell
+        );
+        assert_eq!(
+            detect_shell_from_rc(&PathBuf::from("profile.PS1")),
+            Shell::PowerShell
+        );
+    }
+
+    #[test]
+    fn generate_section_powershell_uses_region_markers() {
+        let section = generate_section(Shell::PowerShell);
+        assert!(section.starts_with(PS_MARKER_START));
+        assert!(section.ends_with(PS_MARKER_END));
+        assert!(section.contains(MARKER_WARNING));
+        assert!(section.contains(VERSION_COMMENT));
+        assert!(!section.contains(MARKER_START));
+        assert!(section.contains("_agr_setup_wrappers"));
+    }
+
     #[test]
     fn generate_section_zsh_contains_markers() {
         let section = generate_section(Shell::Zsh);
         assert!(section.contains(MARKER_START));
         assert!(section.contains(MARKER_END));
         assert!(section.contains(MARKER_WARNING));
+        assert!(section.contains(VERSION_COMMENT));
     }
 
     #[test]
@@ -195,6 +310,20 @@ mod tests {
         assert!(section.contains(MARKER_WARNING));
     }
 
+    #[test]
+    fn replace_sections_keeps_surrounding_bytes() {
+        let old = format!("{MARKER_START}\nold\n{MARKER_END}");
+        let content = format!("a\r\n\n{old}\r\nb  \n{old}\nc");
+        assert_eq!(
+            replace_sections(&content, "NEW", Shell::Bash.markers()).unwrap(),
+            "a\r\n\nNEW\r\nb  \nc"
+        );
+        assert_eq!(
+            replace_sections("a\nb\n", "NEW", Shell::Bash.markers()),
+            None
+        );
+    }
+
     #[test]
     fn generate_section_contains_completions() {
         let zsh_section = generate_section(Shell::Zsh);
@@ -214,4 +343,15 @@ mod tests {
         // Should contain wrapper setup function
         assert!(section.contains("_agr_setup_wrappers"));
     }
+
+    #[test]
+    fn generate_section_forwards_original_argv() {
+        // Wrappers pass the agent's argv through unchanged after `--` so the
+        // recorded command and title match what the user typed
+        for shell in [Shell::Zsh, Shell::Bash] {
+            let section = generate_section(shell);
+            assert!(section.contains(r#"agr record "$agent" -- "$@""#));
+            assert!(section.contains(r#"agr record \"${agent}\" -- \"\$@\""#));
+        }
+    }
 }
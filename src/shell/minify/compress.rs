@@ -2,6 +2,15 @@
 //!
 //! Removes whitespace around operators (&&, ||, redirects) while
 //! preserving spaces required for shell correctness.
+//!
+//! # Preserved constructs
+//!
+//! Inside these constructs `<`, `>`, `&` and `|` are comparison or
+//! arithmetic operators rather than redirects and pipes, so their
+//! content is copied verbatim like quoted strings:
+//! - Arithmetic commands and expansions: `(( i > 10 ))`, `$(( a >> 1 ))`
+//! - Conditional expressions: `[[ $a < $b || -z $c ]]`
+//! - Array literals: `declare -A m=( [a]=1 [b]=2 )`
 
 /// Remove unnecessary whitespace around operators.
 ///
@@ -9,10 +18,12 @@
 /// - Process substitution: `< <(cmd)` must keep space
 /// - Quoted content: never modified
 /// - File descriptor redirects: `2>&1` not `2 >&1`
+/// - Arithmetic, `[[ ]]` and array literal content: never modified
 pub fn compress_operators(input: &str) -> String {
     let mut result = String::new();
     let mut chars = input.chars().peekable();
     let mut quote_state = QuoteState::None;
+    let mut constructs = ConstructStack::default();
     let mut prev_char = ' ';
 
     while let Some(c) = chars.next() {
@@ -24,7 +35,17 @@ pub fn compress_operators(input: &str) -> String {
             continue;
         }
 
-        if c == ' ' && should_skip_space(&result, &mut chars, prev_char) {
+        if prev_char != '\\' {
+            if let Some(closing) = constructs.update(c, prev_char, &mut chars) {
+                result.push(c);
+                result.push_str(closing);
+                prev_char = closing.chars().last().unwrap_or(c);
+                continue;
+            }
+        }
+
+        if c == ' ' && !constructs.is_inside() && should_skip_space(&result, &mut chars, prev_char)
+        {
             prev_char = c;
             continue;
         }
@@ -69,6 +90,92 @@ impl QuoteState {
     }
 }
 
+// ============================================================================
+// Construct tracking
+// ============================================================================
+
+/// A construct whose content must not be compressed.
+enum Construct {
+    /// `((` ... `))`, with the depth of nested single parentheses
+    Arithmetic { depth: usize },
+    /// `[[` ... `]]`
+    Conditional,
+    /// `=(` ... `)`, with the depth of nested parentheses
+    ArrayLiteral { depth: usize },
+}
+
+/// Tracks the (possibly nested) preserved constructs we're inside.
+#[derive(Default)]
+struct ConstructStack {
+    stack: Vec<Construct>,
+}
+
+impl ConstructStack {
+    /// Update the construct stack for an unquoted, unescaped character.
+    ///
+    /// Two-character delimiters (`((`, `))`, `[[`, `]]`) are consumed from
+    /// `chars` as a whole; the consumed remainder is returned so the caller
+    /// can copy it through.
+    fn update(
+        &mut self,
+        c: char,
+        prev: char,
+        chars: &mut std::iter::Peekable<std::str::Chars>,
+    ) -> Option<&'static str> {
+        let next = chars.peek().copied();
+        match (self.stack.last_mut(), c) {
+            (Some(Construct::Conditional), ']') if next == Some(']') => {
+                chars.next();
+                self.stack.pop();
+                Some("]")
+            }
+            (Some(Construct::Conditional), _) => None,
+            (Some(Construct::Arithmetic { depth: 0 }), ')') if next == Some(')') => {
+                chars.next();
+                self.stack.pop();
+                Some(")")
+            }
+            (Some(Construct::ArrayLiteral { depth: 0 }), ')') => {
+                self.stack.pop();
+                None
+            }
+            (Some(Construct::Arithmetic { depth } | Construct::ArrayLiteral { depth }), ')') => {
+                *depth = depth.saturating_sub(1);
+                None
+            }
+            (_, '(') if next == Some('(') => {
+                chars.next();
+                self.stack.push(Construct::Arithmetic { depth: 0 });
+                Some("(")
+            }
+            (_, '(') if prev == '=' => {
+                self.stack.push(Construct::ArrayLiteral { depth: 0 });
+                None
+            }
+            (Some(Construct::Arithmetic { depth } | Construct::ArrayLiteral { depth }), '(') => {
+                *depth += 1;
+                None
+            }
+            (_, '[') if next == Some('[') && starts_word(prev) => {
+                chars.next();
+                self.stack.push(Construct::Conditional);
+                Some("[")
+            }
+            _ => None,
+        }
+    }
+
+    /// Check if currently inside any preserved construct.
+    fn is_inside(&self) -> bool {
+        !self.stack.is_empty()
+    }
+}
+
+/// Whether a character before `[[` leaves it at the start of a word.
+fn starts_word(prev: char) -> bool {
+    prev.is_whitespace() || matches!(prev, ';' | '&' | '|' | '(' | '!')
+}
+
 // ============================================================================
 // Space handling
 // ============================================================================
@@ -160,6 +267,45 @@ mod tests {
         assert_eq!(compress_operators(input), "echo 'hello   world'");
     }
 
+    #[test]
+    fn test_preserve_arithmetic_command() {
+        let input = "(( i > 10 )) && echo big";
+        assert_eq!(compress_operators(input), "(( i > 10 ))&&echo big");
+    }
+
+    #[test]
+    fn test_preserve_arithmetic_expansion() {
+        let input = "x=$(( (y >> 1) | 4 )) || echo no";
+        assert_eq!(compress_operators(input), "x=$(( (y >> 1) | 4 ))||echo no");
+    }
+
+    #[test]
+    fn test_preserve_conditional_expression() {
+        let input = "[[ \"$a\" > \"$b\" || -z $c ]] && echo yes";
+        assert_eq!(
+            compress_operators(input),
+            "[[ \"$a\" > \"$b\" || -z $c ]] &&echo yes"
+        );
+    }
+
+    #[test]
+    fn test_preserve_array_literal() {
+        let input = "declare -A m=( [x]=$(( 1 | 2 )) [y]=\"a b\" ) && echo ${m[x]}";
+        assert_eq!(
+            compress_operators(input),
+            "declare -A m=( [x]=$(( 1 | 2 )) [y]=\"a b\" )&&echo ${m[x]}"
+        );
+    }
+
+    #[test]
+    fn test_compresses_after_construct_closes() {
+        let input = "[[ -n $a ]] && cmd || (( n < 2 )) && cmd 2>&1";
+        assert_eq!(
+            compress_operators(input),
+            "[[ -n $a ]] &&cmd||(( n < 2 ))&&cmd 2>&1"
+        );
+    }
+
     #[test]
     fn test_fd_redirect_preserved() {
         let input = "cmd 2>&1";
@@ -2,6 +2,9 @@
 //!
 //! Combines lines into a single-line script where possible,
 //! respecting shell syntax rules for control structures.
+//!
+//! Lines inside a multi-line array literal (`m=(` ... `)`) are joined
+//! with spaces: a semicolon there would become an array element.
 
 use super::comments::detect_heredoc_start;
 
@@ -22,10 +25,13 @@ pub fn join_statements(lines: &[String]) -> String {
 
     let mut result = String::new();
     let mut heredoc_state = HeredocState::None;
+    let mut array_depth = 0;
 
     for (i, line) in lines.iter().enumerate() {
         let separator = if i == 0 {
             String::new()
+        } else if array_depth > 0 && !matches!(heredoc_state, HeredocState::Inside { .. }) {
+            " ".to_string()
         } else {
             compute_separator(lines, i, &mut heredoc_state)
         };
@@ -39,8 +45,11 @@ pub fn join_statements(lines: &[String]) -> String {
             result.push_str(line.trim());
         }
 
-        // Track heredoc state for future lines
+        // Track heredoc and array literal state for future lines
         update_heredoc_state(line, &mut heredoc_state);
+        if !matches!(heredoc_state, HeredocState::Inside { .. }) {
+            array_depth = update_array_depth(line, array_depth);
+        }
     }
 
     post_process(&result)
@@ -72,6 +81,42 @@ fn update_heredoc_state(line: &str, state: &mut HeredocState) {
     }
 }
 
+// ============================================================================
+// Array literal tracking
+// ============================================================================
+
+/// Update the open-parenthesis depth of a multi-line array literal.
+///
+/// Outside an array literal only a line ending in `=(` opens one. Inside,
+/// unquoted parentheses are counted until the literal is closed.
+fn update_array_depth(line: &str, depth: usize) -> usize {
+    let trimmed = line.trim();
+    if depth == 0 {
+        return usize::from(trimmed.ends_with("=("));
+    }
+
+    let mut depth = depth;
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    for c in trimmed.chars() {
+        match (quote, c) {
+            (Some(q), _) if c == q && (q == '\'' || prev != '\\') => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') if prev != '\\' => quote = Some(c),
+            (None, '(') if prev != '\\' => depth += 1,
+            (None, ')') if prev != '\\' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    depth
+}
+
 // ============================================================================
 // Separator computation
 // ============================================================================
@@ -361,6 +406,21 @@ mod tests {
         assert!(result.contains(";fi") || result.contains(" fi"));
     }
 
+    #[test]
+    fn test_multiline_array_joined_with_spaces() {
+        let lines = vec![
+            "declare -A m=(".to_string(),
+            "[a]=\"x)\"".to_string(),
+            "[b]=$(echo 2)".to_string(),
+            ")".to_string(),
+            "echo ${m[a]}".to_string(),
+        ];
+        assert_eq!(
+            join_statements(&lines),
+            "declare -A m=( [a]=\"x)\" [b]=$(echo 2) );echo ${m[a]}"
+        );
+    }
+
     #[test]
     fn test_is_case_pattern() {
         assert!(is_case_pattern("*)"));
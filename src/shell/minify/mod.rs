@@ -17,6 +17,8 @@
 //! - Word boundaries preserved: `echo $var` not `echo$var`
 //! - Quoted strings preserved verbatim
 //! - Don't join across control structure boundaries
+//! - `(( ))`, `$(( ))`, `[[ ]]` and array literal content preserved verbatim
+//! - Multi-line array literals joined with spaces, never semicolons
 
 mod comments;
 mod compress;
@@ -39,6 +41,8 @@ mod join;
 /// - Heredoc content
 /// - Word boundaries (prevents `echo$var`)
 /// - Zsh parameter expansion flags `${(f)...}`
+/// - Spacing inside arithmetic `(( ))`/`$(( ))` and conditional `[[ ]]`
+/// - Associative and indexed array literals `m=( [a]=1 [b]=2 )`
 ///
 /// # Example
 /// ```
@@ -53,6 +57,21 @@ pub fn exec(script: &str) -> String {
     compress::compress_operators(&joined)
 }
 
+/// Remove comments, blank lines and indentation without joining statements.
+///
+/// Used for fish scripts: the statement joiner and operator compression
+/// only understand POSIX-style control structures.
+///
+/// # Example
+/// ```
+/// use agr::shell::minify;
+/// let input = "# comment\nfunction f\n    echo a\nend";
+/// assert_eq!(minify::strip(input), "function f\necho a\nend");
+/// ```
+pub fn strip(script: &str) -> String {
+    comments::remove_comments_and_blanks(script).join("\n")
+}
+
 /// Return script unchanged for debugging/readability.
 ///
 /// Use this instead of [`exec`] when you need readable output.
@@ -1,12 +1,15 @@
 //! Shell integration management for AGR
 //!
 //! This module handles installing and uninstalling shell integration
-//! to .zshrc and .bashrc files using marked sections.
+//! to .zshrc and .bashrc files and the PowerShell profile using marked
+//! sections.
 
 pub mod completions;
+pub mod doctor;
 pub mod install;
 pub mod minify;
 pub mod paths;
+pub mod powershell;
 pub mod status;
 
 // minify.rs - use as minify::exec() or minify::debug()
@@ -14,21 +17,31 @@ pub mod status;
 // Re-export public items for backward compatibility
 // paths.rs
 pub use paths::{
-    all_shell_rcs, bash_completion_path, default_script_path, detect_shell_rc, zsh_completion_path,
+    all_shell_rcs, bash_completion_path, default_script_path, detect_shell_rc,
+    fish_completion_path, powershell_profile_path, powershell_profile_path_in, zsh_completion_path,
 };
 
 // status.rs
 pub use status::{
     extract_script_path, find_installed_rc, get_status, is_installed_in, ShellStatus, MARKER_END,
-    MARKER_START,
+    MARKER_START, PS_MARKER_END, PS_MARKER_START,
 };
 
+// powershell.rs
+pub use powershell::{generate_powershell_init, POWERSHELL_SCRIPT};
+
+// doctor.rs
+pub use doctor::{diagnose, diagnose_in, ShellIssue};
+
 // install.rs
 pub use install::{
-    generate_section, install, install_script, uninstall, Shell, MARKER_WARNING, SHELL_SCRIPT,
+    generate_section, install, install_script, uninstall, Markers, Shell, MARKER_WARNING,
+    SHELL_SCRIPT, VERSION_COMMENT,
 };
 
 // completions.rs
 pub use completions::{
-    cleanup_old_completions, extract_commands, generate_bash_init, generate_zsh_init, CommandInfo,
+    agent_completions, cleanup_old_completions, extract_commands, generate_bash_init,
+    generate_fish_init, generate_zsh_init, install_fish_completions, remove_fish_completions,
+    AgentCompletion, CommandInfo,
 };
@@ -2,10 +2,16 @@
 //!
 //! This module handles detecting RC files and completion directories.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get the path to the default shell RC file
+///
+/// On Windows this is the PowerShell profile.
 pub fn detect_shell_rc() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return powershell_profile_path();
+    }
+
     let home = dirs::home_dir()?;
 
     // Check zshrc first (more common on macOS)
@@ -31,7 +37,60 @@ pub fn all_shell_rcs() -> Vec<PathBuf> {
         None => return vec![],
     };
 
-    vec![home.join(".zshrc"), home.join(".bashrc")]
+    let mut rcs = vec![home.join(".zshrc"), home.join(".bashrc")];
+    rcs.extend(powershell_profile_path());
+    rcs
+}
+
+/// File name of the PowerShell profile for the current user and host
+const POWERSHELL_PROFILE: &str = "Microsoft.PowerShell_profile.ps1";
+
+/// Get the PowerShell profile path (`$PROFILE`)
+///
+/// Prefers PowerShell 7+ (`pwsh`) when installed, otherwise Windows
+/// PowerShell 5.1.
+pub fn powershell_profile_path() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    let documents = dirs::document_dir();
+    let xdg = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
+    let core = !cfg!(windows) || crate::analyzer::backend::command_exists("pwsh");
+    Some(powershell_profile_path_in(
+        &home,
+        documents.as_deref(),
+        xdg.as_deref(),
+        cfg!(windows),
+        core,
+    ))
+}
+
+/// Resolve the PowerShell profile path for the given directories.
+///
+/// On Windows the profile lives in the Documents folder: `PowerShell` for
+/// PowerShell 7+ (`core`), `WindowsPowerShell` for Windows PowerShell.
+/// Elsewhere pwsh follows XDG and uses `$XDG_CONFIG_HOME/powershell`,
+/// falling back to `~/.config` when it is unset or relative.
+pub fn powershell_profile_path_in(
+    home: &Path,
+    documents: Option<&Path>,
+    xdg_config_home: Option<&Path>,
+    windows: bool,
+    core: bool,
+) -> PathBuf {
+    if windows {
+        let documents = documents.map_or_else(|| home.join("Documents"), Path::to_path_buf);
+        let dir = if core {
+            "PowerShell"
+        } else {
+            "WindowsPowerShell"
+        };
+        return documents.join(dir).join(POWERSHELL_PROFILE);
+    }
+
+    let config_dir = match xdg_config_home {
+        Some(dir) if dir.is_absolute() => dir.to_path_buf(),
+        _ => home.join(".config"),
+    };
+    config_dir.join("powershell").join(POWERSHELL_PROFILE)
 }
 
 /// Get the default bash completion installation path
@@ -52,8 +111,93 @@ pub fn zsh_completion_path() -> Option<PathBuf> {
     Some(home.join(".zsh").join("completions").join("_agr"))
 }
 
+/// Get the fish completion installation path
+///
+/// Fish autoloads completions from `$XDG_CONFIG_HOME/fish/completions`,
+/// falling back to `~/.config` when `XDG_CONFIG_HOME` is unset.
+pub fn fish_completion_path() -> Option<PathBuf> {
+    let home = dirs::home_dir()?;
+    let xdg = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
+    Some(fish_completion_path_in(&home, xdg.as_deref()))
+}
+
+/// Resolve the fish completion path for a home and optional XDG config dir.
+///
+/// A relative `XDG_CONFIG_HOME` is invalid per the spec and is ignored.
+pub fn fish_completion_path_in(home: &Path, xdg_config_home: Option<&Path>) -> PathBuf {
+    let config_dir = match xdg_config_home {
+        Some(dir) if dir.is_absolute() => dir.to_path_buf(),
+        _ => home.join(".config"),
+    };
+    config_dir.join("fish").join("completions").join("agr.fish")
+}
+
 /// Get the default script path (in the config directory)
 pub fn default_script_path() -> Option<PathBuf> {
     let home = dirs::home_dir()?;
     Some(home.join(".config").join("agr").join("agr.sh"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fish_completion_path_defaults_to_dot_config() {
+        assert_eq!(
+            fish_completion_path_in(Path::new("/home/u"), None),
+            PathBuf::from("/home/u/.config/fish/completions/agr.fish")
+        );
+    }
+
+    #[test]
+    fn fish_completion_path_honors_xdg_config_home() {
+        assert_eq!(
+            fish_completion_path_in(Path::new("/home/u"), Some(Path::new("/xdg"))),
+            PathBuf::from("/xdg/fish/completions/agr.fish")
+        );
+    }
+
+    #[test]
+    fn fish_completion_path_ignores_relative_xdg_config_home() {
+        assert_eq!(
+            fish_completion_path_in(Path::new("/home/u"), Some(Path::new("relative"))),
+            PathBuf::from("/home/u/.config/fish/completions/agr.fish")
+        );
+    }
+
+    #[test]
+    fn powershell_profile_path_on_windows_uses_documents() {
+        let home = Path::new("C:/Users/u");
+        let documents = Path::new("D:/Docs");
+        assert_eq!(
+            powershell_profile_path_in(home, Some(documents), None, true, true),
+            PathBuf::from("D:/Docs/PowerShell/Microsoft.PowerShell_profile.ps1")
+        );
+        assert_eq!(
+            powershell_profile_path_in(home, Some(documents), None, true, false),
+            PathBuf::from("D:/Docs/WindowsPowerShell/Microsoft.PowerShell_profile.ps1")
+        );
+        assert_eq!(
+            powershell_profile_path_in(home, None, None, true, true),
+            PathBuf::from("C:/Users/u/Documents/PowerShell/Microsoft.PowerShell_profile.ps1")
+        );
+    }
+
+    #[test]
+    fn powershell_profile_path_elsewhere_follows_xdg() {
+        let home = Path::new("/home/u");
+        assert_eq!(
+            powershell_profile_path_in(home, None, None, false, true),
+            PathBuf::from("/home/u/.config/powershell/Microsoft.PowerShell_profile.ps1")
+        );
+        assert_eq!(
+            powershell_profile_path_in(home, None, Some(Path::new("/xdg")), false, true),
+            PathBuf::from("/xdg/powershell/Microsoft.PowerShell_profile.ps1")
+        );
+        assert_eq!(
+            powershell_profile_path_in(home, None, Some(Path::new("rel")), false, true),
+            PathBuf::from("/home/u/.config/powershell/Microsoft.PowerShell_profile.ps1")
+        );
+    }
+}
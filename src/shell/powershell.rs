@@ -0,0 +1,52 @@
+//! PowerShell profile integration
+//!
+//! PowerShell has no POSIX-style RC file; the integration section is
+//! written to the `$PROFILE` script instead, using `#region`/`#endregion`
+//! markers. The section is not minified: the minifier only understands
+//! POSIX shell syntax.
+
+use clap::CommandFactory;
+
+use crate::cli::Cli;
+
+/// The embedded PowerShell script content (wrapper functions for agents)
+pub const POWERSHELL_SCRIPT: &str = include_str!("assets/agr.ps1");
+
+/// Generate the PowerShell integration code.
+///
+/// Combines the agent wrapper functions with the argument completer that
+/// `clap_complete` generates for PowerShell.
+pub fn generate_powershell_init() -> String {
+    let mut completions = Vec::new();
+    clap_complete::generate(
+        clap_complete::Shell::PowerShell,
+        &mut Cli::command(),
+        "agr",
+        &mut completions,
+    );
+    format!(
+        "{}\n{}",
+        POWERSHELL_SCRIPT.trim_end(),
+        String::from_utf8_lossy(&completions).trim_end()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_contains_wrappers_and_completer() {
+        let init = generate_powershell_init();
+        assert!(init.contains("function global:_agr_setup_wrappers"));
+        assert!(init.contains("$env:_AGR_LOADED = '1'"));
+        assert!(init.contains("Register-ArgumentCompleter"));
+        assert!(init.contains("'agr;shell'"));
+    }
+
+    #[test]
+    fn wrapper_template_forwards_original_argv() {
+        assert!(POWERSHELL_SCRIPT.contains("agr record '__AGENT__' -- @args"));
+        assert!(POWERSHELL_SCRIPT.contains(".Replace('__AGENT__', $agent)"));
+    }
+}
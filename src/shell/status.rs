@@ -5,14 +5,20 @@
 
 use std::fs;
 use std::io;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
+use super::install::{detect_shell_from_rc, Markers};
 use super::paths::all_shell_rcs;
 
 /// Marker comments for shell integration sections
 pub const MARKER_START: &str = "# >>> AGR (Agent Session Recorder) >>>";
 pub const MARKER_END: &str = "# <<< AGR (Agent Session Recorder) <<<";
 
+/// Marker comments for the PowerShell profile section (foldable regions)
+pub const PS_MARKER_START: &str = "#region AGR (Agent Session Recorder)";
+pub const PS_MARKER_END: &str = "#endregion AGR (Agent Session Recorder)";
+
 /// Information about shell integration status
 #[derive(Debug, Clone)]
 pub struct ShellStatus {
@@ -60,21 +66,49 @@ impl ShellStatus {
     }
 }
 
-/// Check if shell integration is installed in an RC file
-pub fn is_installed_in(rc_file: &Path) -> io::Result<bool> {
+/// Byte spans of the complete integration blocks in `content`.
+///
+/// Each span runs from the start of a `start` marker line to the end of
+/// the matching `end` marker line, excluding its line ending. A start
+/// marker that is followed by another start marker before any end marker
+/// is unterminated and not part of a block.
+pub(crate) fn block_spans(content: &str, (start_marker, end_marker): Markers) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text.contains(start_marker) {
+            start = Some(offset);
+        } else if text.contains(end_marker) {
+            if let Some(s) = start.take() {
+                spans.push(s..offset + text.len());
+            }
+        }
+        offset += line.len();
+    }
+    spans
+}
+
+/// Count the shell integration blocks installed in an RC file
+///
+/// Returns 0 when the file does not exist or has no complete block. More
+/// than one block means the section was duplicated.
+pub fn is_installed_in(rc_file: &Path) -> io::Result<usize> {
     if !rc_file.exists() {
-        return Ok(false);
+        return Ok(0);
     }
 
     let content = fs::read_to_string(rc_file)?;
-    Ok(content.contains(MARKER_START) && content.contains(MARKER_END))
+    Ok(block_spans(&content, detect_shell_from_rc(rc_file).markers()).len())
 }
 
-/// Find which RC file has shell integration installed
-pub fn find_installed_rc() -> Option<PathBuf> {
-    all_shell_rcs()
-        .into_iter()
-        .find(|rc| is_installed_in(rc).unwrap_or(false))
+/// Find which RC file has shell integration installed, with its block count
+pub fn find_installed_rc() -> Option<(PathBuf, usize)> {
+    all_shell_rcs().into_iter().find_map(|rc| {
+        let count = is_installed_in(&rc).unwrap_or(0);
+        (count > 0).then_some((rc, count))
+    })
 }
 
 /// Extract the script path from an installed RC file (for old-style installations)
@@ -117,7 +151,7 @@ pub fn extract_script_path(rc_file: &Path) -> io::Result<Option<PathBuf>> {
 
 /// Get the shell integration status
 pub fn get_status(auto_wrap_enabled: bool) -> ShellStatus {
-    let rc_file = find_installed_rc();
+    let rc_file = find_installed_rc().map(|(rc, _)| rc);
     let script_path = rc_file
         .as_ref()
         .and_then(|rc| extract_script_path(rc).ok().flatten());
@@ -132,3 +166,30 @@ pub fn get_status(auto_wrap_enabled: bool) -> ShellStatus {
         is_active,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SH: Markers = (MARKER_START, MARKER_END);
+
+    #[test]
+    fn block_spans_find_every_block() {
+        let block = format!("{MARKER_START}\nx\n{MARKER_END}");
+        let content = format!("a\n{block}\nb\r\n{block}\r\n");
+        let spans = block_spans(&content, SH);
+        assert_eq!(spans.len(), 2);
+        for span in spans {
+            assert_eq!(&content[span], block);
+        }
+    }
+
+    #[test]
+    fn block_spans_skip_unterminated_start() {
+        let content = format!("{MARKER_START}\nstale\n{MARKER_START}\nx\n{MARKER_END}\n");
+        let spans = block_spans(&content, SH);
+        assert_eq!(spans.len(), 1);
+        assert!(content[spans[0].clone()].starts_with(&format!("{MARKER_START}\nx\n")));
+        assert!(block_spans(&format!("{MARKER_START}\nrest of file\n"), SH).is_empty());
+    }
+}
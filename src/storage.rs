@@ -1,13 +1,23 @@
 //! Storage management for recorded sessions
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
 use humansize::{format_size, BINARY};
-use std::collections::HashMap;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::hash::Hasher;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
+use crate::asciicast::AsciicastFile;
 use crate::config::Config;
+use crate::files::index::{self, IndexEntry, SessionIndex};
+use crate::files::lock;
+use crate::files::notes::{has_notes, NOTES_SUFFIX};
+use crate::files::trash::{self, Trash};
 
 /// Information about a recorded session
 #[derive(Debug, Clone)]
@@ -20,9 +30,23 @@ pub struct SessionInfo {
     pub age_days: i64,
     pub age_hours: i64,
     pub age_minutes: i64,
+    /// Protected from cleanup by a `.pinned` sidecar (see [`set_pinned`])
+    pub pinned: bool,
+    /// Has freeform notes in a `.md` sidecar (see [`crate::files::notes`])
+    pub has_notes: bool,
 }
 
 impl SessionInfo {
+    /// Recording title from the cast header, if meaningful (see [`read_display_title`]).
+    pub fn title(&self) -> Option<String> {
+        read_display_title(&self.path, &self.agent)
+    }
+
+    /// Title if present, else the filename.
+    pub fn display_name(&self) -> String {
+        self.title().unwrap_or_else(|| self.filename.clone())
+    }
+
     /// Get human-readable size
     pub fn size_human(&self) -> String {
         format_size(self.size, BINARY)
@@ -46,17 +70,126 @@ impl SessionInfo {
     }
 }
 
+/// Machine-readable session entry for `agr list --json`.
+///
+/// Field names are part of the output format; add fields rather than
+/// renaming them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionJson {
+    pub filename: String,
+    pub agent: String,
+    pub path: String,
+    /// Recording title, when it differs from the default
+    pub title: Option<String>,
+    /// Last modification time (RFC 3339)
+    pub modified: String,
+    pub age_days: i64,
+    /// File size in bytes
+    pub size: u64,
+    /// Recording length in seconds, or null if the file could not be read
+    pub duration: Option<f64>,
+}
+
+impl From<&SessionInfo> for SessionJson {
+    fn from(session: &SessionInfo) -> Self {
+        Self {
+            filename: session.filename.clone(),
+            agent: session.agent.clone(),
+            path: session.path.to_string_lossy().to_string(),
+            title: session.title(),
+            modified: session.modified.to_rfc3339(),
+            age_days: session.age_days,
+            size: session.size,
+            duration: AsciicastFile::read_header_and_duration(&session.path)
+                .ok()
+                .map(|(_, duration)| duration),
+        }
+    }
+}
+
+impl SessionJson {
+    /// Build an entry from indexed details instead of reading the file.
+    pub fn from_indexed(session: &SessionInfo, entry: &IndexEntry) -> Self {
+        Self {
+            filename: session.filename.clone(),
+            agent: session.agent.clone(),
+            path: session.path.to_string_lossy().to_string(),
+            title: entry.title.clone(),
+            modified: session.modified.to_rfc3339(),
+            age_days: session.age_days,
+            size: session.size,
+            duration: entry.duration,
+        }
+    }
+}
+
+/// Machine-readable storage statistics for `agr status --json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsJson {
+    /// Total size of all recordings in bytes
+    pub total_size: u64,
+    pub session_count: usize,
+    /// Session counts keyed by agent, sorted by name
+    pub sessions_by_agent: BTreeMap<String, usize>,
+    /// Recording bytes keyed by agent, sorted by name
+    pub sizes_by_agent: BTreeMap<String, u64>,
+    pub disk_percentage: f64,
+    pub oldest_session: Option<SessionJson>,
+}
+
+impl From<&StorageStats> for StatsJson {
+    fn from(stats: &StorageStats) -> Self {
+        Self {
+            total_size: stats.total_size,
+            session_count: stats.session_count,
+            sessions_by_agent: stats
+                .sessions_by_agent
+                .iter()
+                .map(|(agent, count)| (agent.clone(), *count))
+                .collect(),
+            sizes_by_agent: stats
+                .sizes_by_agent
+                .iter()
+                .map(|(agent, size)| (agent.clone(), *size))
+                .collect(),
+            disk_percentage: stats.disk_percentage,
+            oldest_session: stats.oldest_session.as_ref().map(SessionJson::from),
+        }
+    }
+}
+
 /// Storage statistics
 #[derive(Debug, Clone)]
 pub struct StorageStats {
     pub total_size: u64,
     pub session_count: usize,
     pub sessions_by_agent: HashMap<String, usize>,
+    /// Total recording bytes per agent
+    pub sizes_by_agent: HashMap<String, u64>,
     pub oldest_session: Option<SessionInfo>,
     pub disk_percentage: f64,
 }
 
 impl StorageStats {
+    /// Aggregate statistics over a set of sessions (sorted oldest first).
+    pub fn from_sessions(sessions: &[SessionInfo], disk_percentage: f64) -> Self {
+        let mut sessions_by_agent: HashMap<String, usize> = HashMap::new();
+        let mut sizes_by_agent: HashMap<String, u64> = HashMap::new();
+        for session in sessions {
+            *sessions_by_agent.entry(session.agent.clone()).or_insert(0) += 1;
+            *sizes_by_agent.entry(session.agent.clone()).or_insert(0) += session.size;
+        }
+
+        Self {
+            total_size: sessions.iter().map(|s| s.size).sum(),
+            session_count: sessions.len(),
+            sessions_by_agent,
+            sizes_by_agent,
+            oldest_session: sessions.first().cloned(),
+            disk_percentage,
+        }
+    }
+
     /// Get human-readable total size
     pub fn size_human(&self) -> String {
         format_size(self.total_size, BINARY)
@@ -87,6 +220,17 @@ impl StorageStats {
             agents_display
         );
 
+        // Largest agents first
+        let mut sizes: Vec<_> = self.sizes_by_agent.iter().collect();
+        sizes.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        if !sizes.is_empty() {
+            let sizes_display: Vec<String> = sizes
+                .iter()
+                .map(|(agent, size)| format!("{}: {}", agent, format_size(**size, BINARY)))
+                .collect();
+            summary.push_str(&format!("\n   Size: {}", sizes_display.join(", ")));
+        }
+
         if let Some(oldest) = &self.oldest_session {
             summary.push_str(&format!(
                 "\n   Oldest: {} ({} days ago)",
@@ -99,15 +243,450 @@ impl StorageStats {
     }
 }
 
+/// Name of the daily total-size history file in the storage directory.
+pub const SIZE_HISTORY_FILE: &str = ".size_history";
+
+/// Number of daily points kept in the size history.
+pub const SIZE_HISTORY_DAYS: usize = 365;
+
+/// Parse size history lines (`YYYY-MM-DD <bytes>`), sorted by date.
+///
+/// Malformed lines are skipped; if a date appears twice the last one wins.
+pub fn parse_size_history(content: &str) -> Vec<(NaiveDate, u64)> {
+    let mut points: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(date), Some(size)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if let (Ok(date), Ok(size)) = (
+            NaiveDate::parse_from_str(date, "%Y-%m-%d"),
+            size.parse::<u64>(),
+        ) {
+            points.insert(date, size);
+        }
+    }
+    points.into_iter().collect()
+}
+
+/// Render values as a sparkline of block characters, lowest to highest.
+///
+/// A flat series renders at the lowest level.
+pub fn sparkline(values: &[u64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min) as f64;
+    values
+        .iter()
+        .map(|&v| {
+            if range == 0.0 {
+                BLOCKS[0]
+            } else {
+                let level = ((v - min) as f64 / range * (BLOCKS.len() - 1) as f64).round();
+                BLOCKS[level as usize]
+            }
+        })
+        .collect()
+}
+
+/// Suffixes of companion sidecar files kept next to a recording
+/// (`<file>.cast<suffix>`), which follow the recording when it is renamed.
+pub const SIDECAR_SUFFIXES: &[&str] = &[
+    ".bak",
+    ".bookmarks",
+    ".tags",
+    PIN_SUFFIX,
+    NOTES_SUFFIX,
+    SUMMARY_SUFFIX,
+];
+
+/// Suffix of the empty sidecar that pins a recording.
+pub const PIN_SUFFIX: &str = ".pinned";
+
+/// Suffix of the sidecar written by `agr summarize --save`.
+pub const SUMMARY_SUFFIX: &str = ".summary";
+
+/// Get the path of a sidecar for a recording (the path with `suffix` appended).
+pub fn sidecar_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(suffix);
+    PathBuf::from(sidecar)
+}
+
+/// Whether a recording is pinned, i.e. protected from cleanup.
+pub fn is_pinned(path: &Path) -> bool {
+    sidecar_path(path, PIN_SUFFIX).exists()
+}
+
+/// Pin or unpin a recording by creating or removing its `.pinned` sidecar.
+///
+/// Returns `false` if the recording was already in the requested state.
+pub fn set_pinned(path: &Path, pinned: bool) -> Result<bool> {
+    let marker = sidecar_path(path, PIN_SUFFIX);
+    if marker.exists() == pinned {
+        return Ok(false);
+    }
+    if pinned {
+        fs::write(&marker, "").with_context(|| format!("Failed to pin: {:?}", path))?;
+    } else {
+        fs::remove_file(&marker).with_context(|| format!("Failed to unpin: {:?}", path))?;
+    }
+    Ok(true)
+}
+
+/// Read a recording's title from its header for display.
+///
+/// Ignores empty titles and the generic `<agent> session` title written by
+/// older versions, which carries no more information than the agent name.
+pub fn read_display_title(path: &Path, agent: &str) -> Option<String> {
+    display_title(AsciicastFile::read_header(path).ok()?.title, agent)
+}
+
+/// The display title for a header title (see [`read_display_title`]).
+pub fn display_title(title: Option<String>, agent: &str) -> Option<String> {
+    let title = title?;
+    let title = title.trim();
+    if title.is_empty() || title == format!("{} session", agent) {
+        None
+    } else {
+        Some(title.to_string())
+    }
+}
+
+/// Open a recording positioned after its header line.
+fn events_reader(path: &Path) -> Result<BufReader<fs::File>> {
+    let file = fs::File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    let mut reader = BufReader::new(file);
+    let mut header = Vec::new();
+    reader
+        .read_until(b'\n', &mut header)
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    Ok(reader)
+}
+
+/// Hash the event stream of a recording, or `None` if it has no events.
+///
+/// The header line is skipped so that copies differing only in their
+/// `timestamp` (e.g. the same session recorded twice) hash the same. The
+/// hash only buckets candidates within one run; [`same_events`] confirms
+/// that recordings are really identical.
+pub fn content_hash(path: &Path) -> Result<Option<u64>> {
+    let mut reader = events_reader(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut line = Vec::new();
+    let mut has_events = false;
+
+    loop {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        if read == 0 {
+            break;
+        }
+        has_events |= line.iter().any(|b| !b.is_ascii_whitespace());
+        hasher.write(&line);
+    }
+
+    Ok(has_events.then(|| hasher.finish()))
+}
+
+/// Whether two recordings have byte-for-byte identical event streams.
+///
+/// Headers are not compared, like in [`content_hash`].
+pub fn same_events(a: &Path, b: &Path) -> Result<bool> {
+    let mut reader_a = events_reader(a)?;
+    let mut reader_b = events_reader(b)?;
+    loop {
+        let chunk_a = reader_a
+            .fill_buf()
+            .with_context(|| format!("Failed to read {:?}", a))?;
+        let chunk_b = reader_b
+            .fill_buf()
+            .with_context(|| format!("Failed to read {:?}", b))?;
+        if chunk_a.is_empty() || chunk_b.is_empty() {
+            return Ok(chunk_a.is_empty() && chunk_b.is_empty());
+        }
+        let len = chunk_a.len().min(chunk_b.len());
+        if chunk_a[..len] != chunk_b[..len] {
+            return Ok(false);
+        }
+        reader_a.consume(len);
+        reader_b.consume(len);
+    }
+}
+
+/// Split sessions with equal hashes into sets of really identical recordings.
+///
+/// Member order is kept; sets with a single member are dropped, as are
+/// files that cannot be compared.
+fn split_identical(group: Vec<SessionInfo>) -> Vec<Vec<SessionInfo>> {
+    let mut sets: Vec<Vec<SessionInfo>> = Vec::new();
+    for session in group {
+        let existing = sets
+            .iter_mut()
+            .find(|set| same_events(&set[0].path, &session.path).unwrap_or(false));
+        match existing {
+            Some(set) => set.push(session),
+            None => sets.push(vec![session]),
+        }
+    }
+    sets.retain(|set| set.len() > 1);
+    sets
+}
+
+/// Group hashed sessions into sets of duplicates, newest first.
+///
+/// Groups are ordered by their newest member (most recent first).
+pub fn group_duplicates(hashed: Vec<(u64, SessionInfo)>) -> Vec<Vec<SessionInfo>> {
+    let mut by_hash: HashMap<u64, Vec<SessionInfo>> = HashMap::new();
+    for (hash, session) in hashed {
+        by_hash.entry(hash).or_default().push(session);
+    }
+
+    let groups = by_hash
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_by(|a, b| b.modified.cmp(&a.modified).then(a.path.cmp(&b.path)));
+            group
+        })
+        .collect();
+    sort_groups(groups)
+}
+
+/// Order duplicate groups by their newest member (most recent first).
+fn sort_groups(mut groups: Vec<Vec<SessionInfo>>) -> Vec<Vec<SessionInfo>> {
+    groups.sort_by(|a, b| {
+        b[0].modified
+            .cmp(&a[0].modified)
+            .then(a[0].path.cmp(&b[0].path))
+    });
+    groups
+}
+
+/// Parse a human-readable size such as `500MB`, `2GB` or `1.5GiB` into bytes.
+///
+/// `KB`/`MB`/`GB`/`TB` are decimal (powers of 1000) and `KiB`/`MiB`/`GiB`/`TiB`
+/// binary (powers of 1024). Units are case-insensitive; a bare number is bytes.
+pub fn parse_size(text: &str) -> Result<u64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number
+        .parse()
+        .with_context(|| format!("Invalid size '{}': expected e.g. 500MB, 2GB, 1.5GiB", text))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" | "k" => 1_000,
+        "mb" | "m" => 1_000_000,
+        "gb" | "g" => 1_000_000_000,
+        "tb" | "t" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => anyhow::bail!(
+            "Invalid size '{}': unknown unit '{}' (use B, KB, MB, GB, TB, KiB, MiB, GiB or TiB)",
+            text,
+            unit.trim()
+        ),
+    };
+    let bytes = value * multiplier as f64;
+    if !bytes.is_finite() || bytes < 1.0 || bytes > u64::MAX as f64 {
+        anyhow::bail!("Invalid size '{}': must be at least 1 byte", text);
+    }
+    Ok(bytes.round() as u64)
+}
+
+/// One end of a `--since`/`--until` date range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateBound {
+    /// A calendar day (`2025-01-31`), which includes the whole day
+    Day(NaiveDate),
+    /// An exact local time (`2025-01-31T14:30`), or one relative to now (`7d`)
+    Instant(DateTime<Local>),
+}
+
+impl std::fmt::Display for DateBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Day(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            Self::Instant(time) => write!(f, "{}", time.format("%Y-%m-%d %H:%M")),
+        }
+    }
+}
+
+/// Parse a date bound (see [`parse_date_bound_at`]) relative to the current time.
+pub fn parse_date_bound(text: &str) -> Result<DateBound> {
+    parse_date_bound_at(text, Local::now())
+}
+
+/// Parse a date bound such as `2025-01-31`, `2025-01-31T14:30` or `7d`.
+///
+/// Relative forms count back from `now` in minutes (`m`), hours (`h`),
+/// days (`d`) or weeks (`w`). Times are in the local time zone and may be
+/// separated from the date by `T` or a space; seconds are optional.
+pub fn parse_date_bound_at(text: &str, now: DateTime<Local>) -> Result<DateBound> {
+    const HINT: &str = "expected e.g. 2025-01-31, 2025-01-31T14:30, 12h, 7d or 2w";
+    let text = text.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Ok(DateBound::Day(date));
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(text, format) {
+            let time = Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .with_context(|| format!("Invalid date '{}': time does not exist locally", text))?;
+            return Ok(DateBound::Instant(time));
+        }
+    }
+
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .filter(|&i| i > 0)
+        .with_context(|| format!("Invalid date '{}': {}", text, HINT))?;
+    let (number, unit) = text.split_at(split);
+    let count: i64 = number
+        .parse()
+        .with_context(|| format!("Invalid date '{}': {}", text, HINT))?;
+    let ago = match unit {
+        "m" => Duration::try_minutes(count),
+        "h" => Duration::try_hours(count),
+        "d" => Duration::try_days(count),
+        "w" => Duration::try_weeks(count),
+        _ => anyhow::bail!("Invalid date '{}': {}", text, HINT),
+    };
+    ago.and_then(|ago| now.checked_sub_signed(ago))
+        .map(DateBound::Instant)
+        .with_context(|| format!("Invalid date '{}': too far in the past", text))
+}
+
+/// Recording start times to include, from `--since` and `--until`.
+///
+/// Both ends are inclusive; a missing end is unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateRange {
+    pub since: Option<DateBound>,
+    pub until: Option<DateBound>,
+}
+
+impl DateRange {
+    pub fn new(since: Option<DateBound>, until: Option<DateBound>) -> Self {
+        Self { since, until }
+    }
+
+    /// Whether the range lets every time through.
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    /// Whether `time` lies within the range.
+    pub fn contains(&self, time: DateTime<Local>) -> bool {
+        let after_since = match self.since {
+            Some(DateBound::Day(date)) => time.date_naive() >= date,
+            Some(DateBound::Instant(since)) => time >= since,
+            None => true,
+        };
+        let before_until = match self.until {
+            Some(DateBound::Day(date)) => time.date_naive() <= date,
+            Some(DateBound::Instant(until)) => time <= until,
+            None => true,
+        };
+        after_since && before_until
+    }
+
+    /// Whether a session was recorded within the range.
+    ///
+    /// Uses the header start time (Unix seconds) when known, else the
+    /// file's modification time.
+    pub fn matches(&self, session: &SessionInfo, timestamp: Option<i64>) -> bool {
+        let started = timestamp
+            .and_then(|secs| Local.timestamp_opt(secs, 0).single())
+            .unwrap_or(session.modified);
+        self.contains(started)
+    }
+
+    /// Short description for filter summaries, e.g. `2025-01-01 to 2025-01-31`.
+    pub fn describe(&self) -> String {
+        match (self.since, self.until) {
+            (Some(since), Some(until)) => format!("{} to {}", since, until),
+            (Some(since), None) => format!("since {}", since),
+            (None, Some(until)) => format!("until {}", until),
+            (None, None) => "any time".to_string(),
+        }
+    }
+}
+
+/// Select the oldest sessions whose combined size reaches `target` bytes.
+///
+/// Sessions are taken oldest first until the target is met; pinned sessions
+/// are never selected. If all of them together are smaller than the target,
+/// all unpinned sessions are returned.
+pub fn select_to_free(sessions: &[SessionInfo], target: u64) -> Vec<SessionInfo> {
+    let mut by_age: Vec<&SessionInfo> = sessions.iter().filter(|s| !s.pinned).collect();
+    by_age.sort_by(|a, b| a.modified.cmp(&b.modified).then(a.path.cmp(&b.path)));
+
+    let mut freed = 0u64;
+    by_age
+        .into_iter()
+        .take_while(|session| {
+            let needed = freed < target;
+            freed += session.size;
+            needed
+        })
+        .cloned()
+        .collect()
+}
+
 /// Storage manager for session recordings
 pub struct StorageManager {
     config: Config,
+    /// Where [`list_sessions_indexed`](Self::list_sessions_indexed) caches
+    /// recording details; `None` disables the cache
+    index_path: Option<PathBuf>,
+    /// Where deleted sessions go when `[storage] use_trash` is set
+    trash_dir: Option<PathBuf>,
 }
 
 impl StorageManager {
     /// Create a new storage manager with the given config
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            index_path: index::default_index_path().ok(),
+            trash_dir: trash::default_trash_dir().ok(),
+        }
+    }
+
+    /// Use a different index file, or none at all.
+    pub fn with_index_path(mut self, index_path: Option<PathBuf>) -> Self {
+        self.index_path = index_path;
+        self
+    }
+
+    /// Use a different trash directory; `None` deletes permanently.
+    pub fn with_trash_dir(mut self, trash_dir: Option<PathBuf>) -> Self {
+        self.trash_dir = trash_dir;
+        self
+    }
+
+    /// The trash deleted sessions are moved to, if `[storage] use_trash` is set.
+    pub fn trash(&self) -> Option<Trash> {
+        self.trash_dir
+            .as_ref()
+            .filter(|_| self.config.storage.use_trash)
+            .map(Trash::new)
     }
 
     /// Get the storage directory path
@@ -142,7 +721,6 @@ impl StorageManager {
             return Ok(Vec::new());
         }
 
-        let mut sessions = Vec::new();
         let now = Local::now();
 
         // If agent specified, only check that directory
@@ -162,6 +740,9 @@ impl StorageManager {
                 .collect()
         };
 
+        // Listing directories is cheap; stat the files in parallel, which
+        // matters on network drives with thousands of recordings.
+        let mut candidates = Vec::new();
         for agent_dir in agent_dirs {
             let agent_name = agent_dir
                 .file_name()
@@ -169,66 +750,107 @@ impl StorageManager {
                 .unwrap_or("unknown")
                 .to_string();
 
-            for entry in fs::read_dir(&agent_dir)? {
-                let entry = entry?;
+            for entry in fs::read_dir(&agent_dir)?.filter_map(|entry| entry.ok()) {
                 let path = entry.path();
-
                 if path.extension().is_some_and(|ext| ext == "cast") {
-                    let metadata = fs::metadata(&path)?;
-                    let modified: DateTime<Local> = metadata.modified()?.into();
-                    let duration = now - modified;
-                    let age_days = duration.num_days();
-                    let age_hours = duration.num_hours();
-                    let age_minutes = duration.num_minutes();
-
-                    sessions.push(SessionInfo {
-                        filename: path
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("")
-                            .to_string(),
-                        agent: agent_name.clone(),
-                        size: metadata.len(),
-                        modified,
-                        age_days,
-                        age_hours,
-                        age_minutes,
-                        path,
-                    });
+                    candidates.push((agent_name.clone(), path));
                 }
             }
         }
 
-        // Sort by modification time (oldest first)
-        sessions.sort_by(|a, b| a.modified.cmp(&b.modified));
+        // A file that vanished or cannot be stat'ed is left out rather than
+        // failing the whole scan
+        let mut sessions: Vec<SessionInfo> = candidates
+            .into_par_iter()
+            .filter_map(|(agent, path)| session_info(path, agent, now).ok())
+            .collect();
+
+        // Sort by modification time (oldest first), by path on ties so the
+        // order never depends on which thread finished first
+        sessions.sort_by(|a, b| a.modified.cmp(&b.modified).then(a.path.cmp(&b.path)));
 
         Ok(sessions)
     }
 
+    /// List sessions together with their duration and title.
+    ///
+    /// The details come from the on-disk [`SessionIndex`]; only files whose
+    /// modification time or size changed since the last listing are read.
+    /// With `refresh`, the index is ignored and every file is read again.
+    /// Failing to save the index is not an error.
+    pub fn list_sessions_indexed(
+        &self,
+        agent: Option<&str>,
+        refresh: bool,
+    ) -> Result<Vec<(SessionInfo, IndexEntry)>> {
+        let sessions = self.list_sessions(agent)?;
+        let mut index = self
+            .index_path
+            .as_deref()
+            .map(SessionIndex::load)
+            .unwrap_or_default();
+        if refresh {
+            index.clear(agent);
+        }
+        index.refresh(&sessions, agent);
+        if let Some(path) = &self.index_path {
+            let _ = index.save(path);
+        }
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| {
+                let entry = index
+                    .get(&session.path)
+                    .cloned()
+                    .unwrap_or_else(|| IndexEntry::read(&session));
+                (session, entry)
+            })
+            .collect())
+    }
+
     /// Get storage statistics
     pub fn get_stats(&self) -> Result<StorageStats> {
         let sessions = self.list_sessions(None)?;
-
         let total_size: u64 = sessions.iter().map(|s| s.size).sum();
-        let session_count = sessions.len();
-
-        let mut sessions_by_agent: HashMap<String, usize> = HashMap::new();
-        for session in &sessions {
-            *sessions_by_agent.entry(session.agent.clone()).or_insert(0) += 1;
-        }
-
-        let oldest_session = sessions.first().cloned();
 
         // Calculate disk percentage (simplified - uses available space)
         let disk_percentage = self.calculate_disk_percentage(total_size);
 
-        Ok(StorageStats {
-            total_size,
-            session_count,
-            sessions_by_agent,
-            oldest_session,
-            disk_percentage,
-        })
+        Ok(StorageStats::from_sessions(&sessions, disk_percentage))
+    }
+
+    /// Record today's total size in the size history and return the history.
+    ///
+    /// The history is a small `.size_history` file in the storage directory
+    /// with one `YYYY-MM-DD <bytes>` line per day; a later call on the same
+    /// day replaces that day's point. Only the last [`SIZE_HISTORY_DAYS`]
+    /// points are kept. Nothing is written if the storage directory does
+    /// not exist yet.
+    pub fn record_size_history(
+        &self,
+        total_size: u64,
+        today: NaiveDate,
+    ) -> Result<Vec<(NaiveDate, u64)>> {
+        let path = self.storage_dir().join(SIZE_HISTORY_FILE);
+        let mut history = match fs::read_to_string(&path) {
+            Ok(content) => parse_size_history(&content),
+            Err(_) => Vec::new(),
+        };
+        history.retain(|(date, _)| *date < today);
+        history.push((today, total_size));
+        let excess = history.len().saturating_sub(SIZE_HISTORY_DAYS);
+        history.drain(..excess);
+
+        if self.storage_dir().is_dir() {
+            let content: String = history
+                .iter()
+                .map(|(date, size)| format!("{} {}\n", date.format("%Y-%m-%d"), size))
+                .collect();
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write size history: {:?}", path))?;
+        }
+        Ok(history)
     }
 
     /// Calculate what percentage of disk the storage uses
@@ -267,7 +889,30 @@ impl StorageManager {
     }
 
     /// Delete sessions by path
+    ///
+    /// With [`trash`](Self::trash) available, the recordings and their
+    /// sidecars are moved there instead; see [`delete_sessions_permanently`](Self::delete_sessions_permanently).
+    /// Returns the bytes freed in the storage directory.
     pub fn delete_sessions(&self, sessions: &[SessionInfo]) -> Result<u64> {
+        let Some(trash) = self.trash() else {
+            return self.delete_sessions_permanently(sessions);
+        };
+        let mut freed_size = 0u64;
+
+        for session in sessions {
+            if session.path.exists() {
+                trash
+                    .move_to_trash(&session.path)
+                    .with_context(|| format!("Failed to move to trash: {:?}", session.path))?;
+                freed_size += session.size;
+            }
+        }
+
+        Ok(freed_size)
+    }
+
+    /// Delete sessions and their sidecars by path without going through the trash
+    pub fn delete_sessions_permanently(&self, sessions: &[SessionInfo]) -> Result<u64> {
         let mut freed_size = 0u64;
 
         for session in sessions {
@@ -275,12 +920,84 @@ impl StorageManager {
                 fs::remove_file(&session.path)
                     .with_context(|| format!("Failed to delete: {:?}", session.path))?;
                 freed_size += session.size;
+                // Stale sidecars would carry over to a new file with the same name
+                for suffix in SIDECAR_SUFFIXES {
+                    let _ = fs::remove_file(sidecar_path(&session.path, suffix));
+                }
             }
         }
 
         Ok(freed_size)
     }
 
+    /// Rename a session within its agent directory, along with its sidecars.
+    ///
+    /// `new_name` is a bare filename; `.cast` is appended if missing. Names
+    /// containing path separators or `..` are rejected, as are names that
+    /// collide with an existing recording or sidecar. Returns the new path.
+    pub fn rename_session(&self, old: &Path, new_name: &str) -> Result<PathBuf> {
+        if !old.is_file() {
+            anyhow::bail!("Session not found: {}", old.display());
+        }
+        lock::check_not_locked(old)?;
+
+        let new_name = validate_session_name(new_name)?;
+        let dir = old
+            .parent()
+            .with_context(|| format!("Session has no parent directory: {:?}", old))?;
+        let new_path = dir.join(&new_name);
+
+        if new_path == old {
+            return Ok(new_path);
+        }
+        if new_path.exists() {
+            anyhow::bail!("A session named '{}' already exists", new_name);
+        }
+
+        // Collect sidecars up front so a collision aborts before anything moves
+        let mut moves = vec![(old.to_path_buf(), new_path.clone())];
+        for suffix in SIDECAR_SUFFIXES {
+            let from = sidecar_path(old, suffix);
+            if from.exists() {
+                let to = sidecar_path(&new_path, suffix);
+                if to.exists() {
+                    anyhow::bail!("Sidecar already exists: {}", to.display());
+                }
+                moves.push((from, to));
+            }
+        }
+
+        for (from, to) in &moves {
+            fs::rename(from, to)
+                .with_context(|| format!("Failed to rename {:?} to {:?}", from, to))?;
+        }
+
+        Ok(new_path)
+    }
+
+    /// Find recordings with identical content.
+    ///
+    /// Sessions are grouped by [`content_hash`], then checked byte for byte
+    /// with [`same_events`]; only groups with more than one member are
+    /// returned. Each group is sorted newest first, so everything after the
+    /// first entry is a redundant copy. Recordings without events and
+    /// unreadable files are skipped.
+    pub fn find_duplicates(&self, agent: Option<&str>) -> Result<Vec<Vec<SessionInfo>>> {
+        let hashed = self
+            .list_sessions(agent)?
+            .into_par_iter()
+            .filter_map(|session| match content_hash(&session.path) {
+                Ok(Some(hash)) => Some((hash, session)),
+                _ => None,
+            })
+            .collect();
+        let groups = group_duplicates(hashed)
+            .into_par_iter()
+            .flat_map_iter(split_identical)
+            .collect();
+        Ok(sort_groups(groups))
+    }
+
     /// Check if storage exceeds threshold
     pub fn exceeds_threshold(&self) -> Result<bool> {
         let stats = self.get_stats()?;
@@ -354,7 +1071,7 @@ impl StorageManager {
         let mut matches: Vec<_> = sessions.iter().filter(|s| s.filename == filename).collect();
 
         // Sort by modification time (newest first) and return the first match
-        matches.sort_by(|a, b| b.modified.cmp(&a.modified));
+        matches.sort_by_key(|s| std::cmp::Reverse(s.modified));
         matches.first().map(|s| s.path.clone())
     }
 
@@ -366,7 +1083,7 @@ impl StorageManager {
         let mut sessions = self.list_sessions(None)?;
 
         // Sort by modification time (most recent first)
-        sessions.sort_by(|a, b| b.modified.cmp(&a.modified));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.modified));
 
         let mut files: Vec<String> = sessions
             .iter()
@@ -382,11 +1099,55 @@ impl StorageManager {
     }
 }
 
+/// Stat one recording for [`StorageManager::list_sessions`].
+fn session_info(path: PathBuf, agent: String, now: DateTime<Local>) -> Result<SessionInfo> {
+    let metadata = fs::metadata(&path)?;
+    let modified: DateTime<Local> = metadata.modified()?.into();
+    let duration = now - modified;
+
+    Ok(SessionInfo {
+        filename: path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_string(),
+        agent,
+        size: metadata.len(),
+        modified,
+        age_days: duration.num_days(),
+        age_hours: duration.num_hours(),
+        age_minutes: duration.num_minutes(),
+        pinned: is_pinned(&path),
+        has_notes: has_notes(&path),
+        path,
+    })
+}
+
+/// Validate a new session filename, appending `.cast` if needed.
+fn validate_session_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty()
+        || name.contains('/')
+        || name.contains('\\')
+        || name.starts_with('.')
+        || name.contains('\0')
+    {
+        anyhow::bail!(
+            "Invalid session name '{}': must be a plain filename without path components",
+            name
+        );
+    }
+    if name.ends_with(".cast") {
+        Ok(name.to_string())
+    } else {
+        Ok(format!("{}.cast", name))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::Write;
-    use std::path::Path;
     use tempfile::TempDir;
 
     /// Create a test config with a custom storage directory.
@@ -396,6 +1157,7 @@ mod tests {
                 directory: storage_dir.to_string_lossy().to_string(),
                 size_threshold_gb: 5.0,
                 age_threshold_days: 30,
+                use_trash: true,
             },
             agents: crate::config::AgentsConfig {
                 enabled: vec!["claude".to_string(), "codex".to_string()],
@@ -541,4 +1303,942 @@ mod tests {
         assert_eq!(codex_sessions.len(), 1);
         assert_eq!(codex_sessions[0].agent, "codex");
     }
+
+    // ========================================================================
+    // Parallel scan tests
+    // ========================================================================
+
+    /// Write `count` recordings spread over three agents, in scrambled order.
+    fn write_many_sessions(storage_dir: &Path, count: usize) {
+        let agents = ["claude", "codex", "gemini"];
+        for i in 0..count {
+            let n = (i * 37) % count;
+            let agent_dir = storage_dir.join(agents[n % agents.len()]);
+            fs::create_dir_all(&agent_dir).unwrap();
+            fs::write(
+                agent_dir.join(format!("session-{:04}.cast", n)),
+                "x".repeat(n + 1),
+            )
+            .unwrap();
+        }
+    }
+
+    /// Serial reference for `list_sessions`: (agent, path, size, modified).
+    fn list_serially(storage_dir: &Path) -> Vec<(String, PathBuf, u64, DateTime<Local>)> {
+        let mut sessions = Vec::new();
+        for agent_dir in fs::read_dir(storage_dir).unwrap() {
+            let agent_dir = agent_dir.unwrap().path();
+            let agent = agent_dir.file_name().unwrap().to_string_lossy().to_string();
+            for entry in fs::read_dir(&agent_dir).unwrap() {
+                let path = entry.unwrap().path();
+                let metadata = fs::metadata(&path).unwrap();
+                let modified: DateTime<Local> = metadata.modified().unwrap().into();
+                sessions.push((agent.clone(), path, metadata.len(), modified));
+            }
+        }
+        sessions.sort_by(|a, b| a.3.cmp(&b.3).then(a.1.cmp(&b.1)));
+        sessions
+    }
+
+    #[test]
+    fn parallel_scan_matches_serial_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        write_many_sessions(temp_dir.path(), 300);
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let sessions = manager.list_sessions(None).unwrap();
+        let listed: Vec<_> = sessions
+            .iter()
+            .map(|s| (s.agent.clone(), s.path.clone(), s.size, s.modified))
+            .collect();
+        assert_eq!(listed, list_serially(temp_dir.path()));
+
+        let stats = manager.get_stats().unwrap();
+        let expected_total: u64 = (1..=300).sum();
+        assert_eq!(stats.session_count, 300);
+        assert_eq!(stats.total_size, expected_total);
+        assert_eq!(stats.sessions_by_agent["claude"], 100);
+        assert_eq!(stats.sessions_by_agent["codex"], 100);
+        assert_eq!(stats.sessions_by_agent["gemini"], 100);
+        assert_eq!(stats.sizes_by_agent.values().sum::<u64>(), expected_total);
+        assert_eq!(
+            stats.oldest_session.map(|s| s.path),
+            sessions.first().map(|s| s.path.clone())
+        );
+    }
+
+    #[test]
+    fn parallel_scan_order_is_stable() {
+        let temp_dir = TempDir::new().unwrap();
+        write_many_sessions(temp_dir.path(), 200);
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let paths = |sessions: Vec<SessionInfo>| -> Vec<PathBuf> {
+            sessions.into_iter().map(|s| s.path).collect()
+        };
+        let first = paths(manager.list_sessions(None).unwrap());
+        for _ in 0..5 {
+            assert_eq!(paths(manager.list_sessions(None).unwrap()), first);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unreadable_file_does_not_abort_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        write_many_sessions(temp_dir.path(), 10);
+        // A dangling symlink cannot be stat'ed
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("missing.cast"),
+            temp_dir.path().join("claude").join("dangling.cast"),
+        )
+        .unwrap();
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let sessions = manager.list_sessions(None).unwrap();
+        assert_eq!(sessions.len(), 10);
+        assert!(sessions.iter().all(|s| s.filename != "dangling.cast"));
+    }
+
+    #[test]
+    fn indexed_listing_refreshes_changed_and_prunes_deleted_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let index_path = temp_dir.path().join("cache").join("index.json");
+        let storage_dir = temp_dir.path().join("sessions");
+        write_cast(&storage_dir, "claude", "a.cast", 1, "[2.0,\"o\",\"a\"]\n");
+        write_cast(&storage_dir, "claude", "b.cast", 2, "[1.0,\"o\",\"b\"]\n");
+        let manager = StorageManager::new(create_test_config(&storage_dir))
+            .with_index_path(Some(index_path.clone()));
+
+        let listed = manager.list_sessions_indexed(None, false).unwrap();
+        let durations: Vec<_> = listed.iter().map(|(_, e)| e.duration).collect();
+        assert_eq!(durations.len(), 2);
+        assert_eq!(SessionIndex::load(&index_path).len(), 2);
+
+        // Grow one file and delete the other
+        write_cast(
+            &storage_dir,
+            "claude",
+            "a.cast",
+            1,
+            "[2.0,\"o\",\"a\"]\n[3.0,\"o\",\"more\"]\n",
+        );
+        fs::remove_file(storage_dir.join("claude/b.cast")).unwrap();
+
+        let listed = manager.list_sessions_indexed(None, false).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].1.duration, Some(5.0));
+        let index = SessionIndex::load(&index_path);
+        assert_eq!(index.len(), 1);
+        assert!(index.get(&storage_dir.join("claude/b.cast")).is_none());
+
+        let refreshed = manager.list_sessions_indexed(None, true).unwrap();
+        assert_eq!(refreshed[0].1, listed[0].1);
+    }
+
+    // ========================================================================
+    // Rename tests
+    // ========================================================================
+
+    /// Create `<storage>/claude/<name>` with some content and return its path.
+    fn create_session(storage_dir: &Path, name: &str) -> PathBuf {
+        let agent_dir = storage_dir.join("claude");
+        fs::create_dir_all(&agent_dir).unwrap();
+        let path = agent_dir.join(name);
+        fs::write(&path, "{\"version\":3}\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn rename_session_moves_file_and_appends_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = create_session(temp_dir.path(), "20250119-100000.cast");
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let new_path = manager.rename_session(&old, "auth-refactor").unwrap();
+
+        assert_eq!(new_path, temp_dir.path().join("claude/auth-refactor.cast"));
+        assert!(new_path.exists());
+        assert!(!old.exists());
+    }
+
+    #[test]
+    fn rename_session_rejects_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = create_session(temp_dir.path(), "a.cast");
+        create_session(temp_dir.path(), "b.cast");
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let err = manager.rename_session(&old, "b.cast").unwrap_err();
+
+        assert!(err.to_string().contains("already exists"));
+        assert!(old.exists());
+    }
+
+    #[test]
+    fn rename_session_rejects_path_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = create_session(temp_dir.path(), "a.cast");
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        for bad in [
+            "../escape.cast",
+            "codex/x.cast",
+            "..",
+            ".hidden",
+            "a\\b",
+            "",
+        ] {
+            assert!(
+                manager.rename_session(&old, bad).is_err(),
+                "should reject {:?}",
+                bad
+            );
+        }
+        assert!(old.exists());
+        assert!(!temp_dir.path().join("escape.cast").exists());
+    }
+
+    #[test]
+    fn rename_session_allows_double_dots_inside_the_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = create_session(temp_dir.path(), "a.cast");
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let new_path = manager.rename_session(&old, "fix..typo").unwrap();
+
+        assert_eq!(new_path, old.with_file_name("fix..typo.cast"));
+        assert!(new_path.exists());
+    }
+
+    #[test]
+    fn rename_session_moves_sidecars() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = create_session(temp_dir.path(), "a.cast");
+        fs::write(sidecar_path(&old, ".bookmarks"), "1.000\n").unwrap();
+        fs::write(sidecar_path(&old, ".bak"), "backup").unwrap();
+        fs::write(sidecar_path(&old, SUMMARY_SUFFIX), "A summary.\n").unwrap();
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let new_path = manager.rename_session(&old, "b").unwrap();
+        assert!(sidecar_path(&new_path, SUMMARY_SUFFIX).exists());
+
+        assert_eq!(
+            fs::read_to_string(sidecar_path(&new_path, ".bookmarks")).unwrap(),
+            "1.000\n"
+        );
+        assert!(sidecar_path(&new_path, ".bak").exists());
+        assert!(!sidecar_path(&old, ".bookmarks").exists());
+        assert!(!sidecar_path(&old, ".bak").exists());
+        assert!(!sidecar_path(&new_path, ".tags").exists());
+    }
+
+    #[test]
+    fn rename_session_aborts_on_sidecar_collision() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = create_session(temp_dir.path(), "a.cast");
+        fs::write(sidecar_path(&old, ".bookmarks"), "1.000\n").unwrap();
+        let orphan = temp_dir.path().join("claude/b.cast.bookmarks");
+        fs::write(&orphan, "2.000\n").unwrap();
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        assert!(manager.rename_session(&old, "b").is_err());
+        assert!(old.exists());
+        assert_eq!(fs::read_to_string(&orphan).unwrap(), "2.000\n");
+    }
+
+    #[test]
+    fn rename_session_missing_file_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+        let missing = temp_dir.path().join("claude/missing.cast");
+        assert!(manager.rename_session(&missing, "x").is_err());
+    }
+
+    // ========================================================================
+    // Dedupe tests
+    // ========================================================================
+
+    /// Write a cast with the given header timestamp and events.
+    fn write_cast(storage_dir: &Path, agent: &str, name: &str, timestamp: i64, events: &str) {
+        let agent_dir = storage_dir.join(agent);
+        fs::create_dir_all(&agent_dir).unwrap();
+        fs::write(
+            agent_dir.join(name),
+            format!(
+                "{{\"version\":3,\"term\":{{\"cols\":80,\"rows\":24}},\"timestamp\":{}}}\n{}",
+                timestamp, events
+            ),
+        )
+        .unwrap();
+    }
+
+    fn info_at(name: &str, modified: DateTime<Local>) -> SessionInfo {
+        SessionInfo {
+            path: PathBuf::from(format!("/mock/claude/{}", name)),
+            agent: "claude".to_string(),
+            filename: name.to_string(),
+            size: 10,
+            modified,
+            age_days: 0,
+            age_hours: 0,
+            age_minutes: 0,
+            pinned: false,
+            has_notes: false,
+        }
+    }
+
+    fn fixed_session(dir: &Path, agent: &str, name: &str, age_days: i64) -> SessionInfo {
+        use chrono::TimeZone;
+
+        let path = dir.join(agent).join(name);
+        SessionInfo {
+            size: fs::metadata(&path).unwrap().len(),
+            path,
+            agent: agent.to_string(),
+            filename: name.to_string(),
+            modified: chrono::FixedOffset::east_opt(0)
+                .unwrap()
+                .with_ymd_and_hms(2025, 1, 15, 10, 30, 0)
+                .unwrap()
+                .with_timezone(&Local),
+            age_days,
+            age_hours: age_days * 24,
+            age_minutes: age_days * 24 * 60,
+            pinned: false,
+            has_notes: false,
+        }
+    }
+
+    #[test]
+    fn session_json_serializes_stable_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "a.cast",
+            1000,
+            "[0.5,\"o\",\"hi\"]\n[1.5,\"o\",\"there\"]\n",
+        );
+        let session = fixed_session(temp_dir.path(), "claude", "a.cast", 3);
+        let modified = session.modified.to_rfc3339();
+
+        let value = serde_json::to_value(SessionJson::from(&session)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "filename": "a.cast",
+                "agent": "claude",
+                "path": session.path.to_string_lossy(),
+                "title": null,
+                "modified": modified,
+                "age_days": 3,
+                "size": session.size,
+                "duration": 2.0,
+            })
+        );
+    }
+
+    #[test]
+    fn session_json_duration_is_null_for_unreadable_file() {
+        let mut session = info_at("gone.cast", Local::now());
+        session.path = PathBuf::from("/nonexistent/gone.cast");
+        assert_eq!(SessionJson::from(&session).duration, None);
+    }
+
+    #[test]
+    fn stats_json_serializes_sorted_agents() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(
+            temp_dir.path(),
+            "codex",
+            "b.cast",
+            1000,
+            "[1.0,\"o\",\"x\"]\n",
+        );
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "a.cast",
+            1000,
+            "[4.0,\"o\",\"y\"]\n",
+        );
+        let oldest = fixed_session(temp_dir.path(), "claude", "a.cast", 10);
+        let stats = StorageStats {
+            total_size: 300,
+            session_count: 3,
+            sessions_by_agent: HashMap::from([("codex".to_string(), 1), ("claude".to_string(), 2)]),
+            sizes_by_agent: HashMap::from([
+                ("codex".to_string(), 100),
+                ("claude".to_string(), 200),
+            ]),
+            oldest_session: Some(oldest.clone()),
+            disk_percentage: 1.5,
+        };
+
+        let value = serde_json::to_value(StatsJson::from(&stats)).unwrap();
+        let expected = serde_json::json!({
+            "total_size": 300,
+            "session_count": 3,
+            "sessions_by_agent": {"claude": 2, "codex": 1},
+            "sizes_by_agent": {"claude": 200, "codex": 100},
+            "disk_percentage": 1.5,
+            "oldest_session": {
+                "filename": "a.cast",
+                "agent": "claude",
+                "path": oldest.path.to_string_lossy(),
+                "title": null,
+                "modified": oldest.modified.to_rfc3339(),
+                "age_days": 10,
+                "size": oldest.size,
+                "duration": 4.0,
+            },
+        });
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn content_hash_ignores_header_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        let events = "[0.1,\"o\",\"hello\"]\n[0.5,\"o\",\"world\"]\n";
+        write_cast(temp_dir.path(), "claude", "a.cast", 1000, events);
+        write_cast(temp_dir.path(), "claude", "b.cast", 2000, events);
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "c.cast",
+            1000,
+            "[0.1,\"o\",\"other\"]\n",
+        );
+
+        let hash = |name: &str| content_hash(&temp_dir.path().join("claude").join(name)).unwrap();
+        assert_eq!(hash("a.cast"), hash("b.cast"));
+        assert_ne!(hash("a.cast"), hash("c.cast"));
+    }
+
+    #[test]
+    fn content_hash_is_none_without_events() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(temp_dir.path(), "claude", "empty.cast", 1000, "");
+
+        let path = temp_dir.path().join("claude").join("empty.cast");
+        assert_eq!(content_hash(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn same_events_compares_event_bytes() {
+        let temp_dir = TempDir::new().unwrap();
+        let events = "[0.1,\"o\",\"hello\"]\n";
+        write_cast(temp_dir.path(), "claude", "a.cast", 1000, events);
+        write_cast(temp_dir.path(), "claude", "b.cast", 2000, events);
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "longer.cast",
+            1000,
+            "[0.1,\"o\",\"hello\"]\n[0.2,\"o\",\"!\"]\n",
+        );
+        let path = |name: &str| temp_dir.path().join("claude").join(name);
+
+        assert!(same_events(&path("a.cast"), &path("b.cast")).unwrap());
+        assert!(!same_events(&path("a.cast"), &path("longer.cast")).unwrap());
+        assert!(!same_events(&path("longer.cast"), &path("a.cast")).unwrap());
+    }
+
+    #[test]
+    fn split_identical_drops_hash_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "a.cast",
+            1,
+            "[0.1,\"o\",\"x\"]\n",
+        );
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "b.cast",
+            2,
+            "[0.1,\"o\",\"y\"]\n",
+        );
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "c.cast",
+            3,
+            "[0.1,\"o\",\"x\"]\n",
+        );
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+        // As if all three had collided on the same hash
+        let group = manager.list_sessions(None).unwrap();
+
+        let sets = split_identical(group);
+
+        assert_eq!(sets.len(), 1);
+        let mut names: Vec<_> = sets[0].iter().map(|s| s.filename.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["a.cast", "c.cast"]);
+    }
+
+    #[test]
+    fn find_duplicates_skips_recordings_without_events() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(temp_dir.path(), "claude", "a.cast", 1000, "");
+        write_cast(temp_dir.path(), "claude", "b.cast", 1000, "");
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        assert!(manager.find_duplicates(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn group_duplicates_keeps_only_shared_hashes_newest_first() {
+        let now = Local::now();
+        let hashed = vec![
+            (1, info_at("old.cast", now - chrono::Duration::hours(2))),
+            (2, info_at("unique.cast", now)),
+            (1, info_at("new.cast", now - chrono::Duration::hours(1))),
+        ];
+
+        let groups = group_duplicates(hashed);
+
+        assert_eq!(groups.len(), 1);
+        let names: Vec<_> = groups[0].iter().map(|s| s.filename.as_str()).collect();
+        assert_eq!(names, vec!["new.cast", "old.cast"]);
+    }
+
+    #[test]
+    fn group_duplicates_orders_groups_by_newest_member() {
+        let now = Local::now();
+        let hashed = vec![
+            (1, info_at("a1.cast", now - chrono::Duration::hours(5))),
+            (1, info_at("a2.cast", now - chrono::Duration::hours(4))),
+            (2, info_at("b1.cast", now - chrono::Duration::hours(3))),
+            (2, info_at("b2.cast", now)),
+        ];
+
+        let groups = group_duplicates(hashed);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0][0].filename, "b2.cast");
+        assert_eq!(groups[1][0].filename, "a2.cast");
+    }
+
+    #[test]
+    fn find_duplicates_groups_identical_recordings() {
+        let temp_dir = TempDir::new().unwrap();
+        let events = "[0.1,\"o\",\"same\"]\n";
+        write_cast(temp_dir.path(), "claude", "first.cast", 1000, events);
+        write_cast(temp_dir.path(), "codex", "second.cast", 5000, events);
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "different.cast",
+            1000,
+            "[0.1,\"o\",\"x\"]\n",
+        );
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        let groups = manager.find_duplicates(None).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        let mut names: Vec<_> = groups[0].iter().map(|s| s.filename.clone()).collect();
+        names.sort();
+        assert_eq!(names, vec!["first.cast", "second.cast"]);
+    }
+
+    #[test]
+    fn find_duplicates_none_when_all_distinct() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "a.cast",
+            1000,
+            "[0.1,\"o\",\"a\"]\n",
+        );
+        write_cast(
+            temp_dir.path(),
+            "claude",
+            "b.cast",
+            1000,
+            "[0.1,\"o\",\"b\"]\n",
+        );
+        let manager = StorageManager::new(create_test_config(temp_dir.path()));
+
+        assert!(manager.find_duplicates(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn display_title_prefers_meaningful_header_title() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("claude");
+        fs::create_dir_all(&dir).unwrap();
+        let header = |title: &str| {
+            format!(
+                "{{\"version\":3,\"term\":{{\"cols\":80,\"rows\":24}},\"title\":\"{}\"}}\n",
+                title
+            )
+        };
+        fs::write(dir.join("a.cast"), header("claude chat --model x")).unwrap();
+        fs::write(dir.join("b.cast"), header("claude session")).unwrap();
+        fs::write(dir.join("c.cast"), "{\"version\":3}\n").unwrap();
+
+        assert_eq!(
+            read_display_title(&dir.join("a.cast"), "claude").as_deref(),
+            Some("claude chat --model x")
+        );
+        assert_eq!(read_display_title(&dir.join("b.cast"), "claude"), None);
+        assert_eq!(read_display_title(&dir.join("c.cast"), "claude"), None);
+        assert_eq!(
+            read_display_title(&dir.join("missing.cast"), "claude"),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_size_accepts_decimal_and_binary_units() {
+        assert_eq!(parse_size("500MB").unwrap(), 500_000_000);
+        assert_eq!(parse_size("2GB").unwrap(), 2_000_000_000);
+        assert_eq!(parse_size("1.5GiB").unwrap(), 1_610_612_736);
+        assert_eq!(parse_size("64 kib").unwrap(), 65_536);
+        assert_eq!(parse_size("4096").unwrap(), 4096);
+        assert_eq!(parse_size(" 1g ").unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn parse_size_rejects_invalid_input() {
+        for text in ["", "GB", "2XB", "-1GB", "0", "0.1B", "1.2.3MB"] {
+            assert!(parse_size(text).is_err(), "{:?} should be rejected", text);
+        }
+    }
+
+    fn local(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn parse_date_bound_accepts_days_times_and_relative_forms() {
+        let now = local(2025, 3, 14, 12, 0);
+        let day = |y, m, d| DateBound::Day(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+
+        assert_eq!(
+            parse_date_bound_at("2025-01-31", now).unwrap(),
+            day(2025, 1, 31)
+        );
+        assert_eq!(
+            parse_date_bound_at("2025-01-31T14:30", now).unwrap(),
+            DateBound::Instant(local(2025, 1, 31, 14, 30))
+        );
+        assert_eq!(
+            parse_date_bound_at(" 2025-01-31 14:30:00 ", now).unwrap(),
+            DateBound::Instant(local(2025, 1, 31, 14, 30))
+        );
+        assert_eq!(
+            parse_date_bound_at("7d", now).unwrap(),
+            DateBound::Instant(local(2025, 3, 7, 12, 0))
+        );
+        assert_eq!(
+            parse_date_bound_at("2w", now).unwrap(),
+            DateBound::Instant(local(2025, 2, 28, 12, 0))
+        );
+        assert_eq!(
+            parse_date_bound_at("36h", now).unwrap(),
+            DateBound::Instant(local(2025, 3, 13, 0, 0))
+        );
+        assert_eq!(
+            parse_date_bound_at("90m", now).unwrap(),
+            DateBound::Instant(local(2025, 3, 14, 10, 30))
+        );
+    }
+
+    #[test]
+    fn parse_date_bound_rejects_invalid_input() {
+        let now = local(2025, 3, 14, 12, 0);
+        for text in [
+            "",
+            "d",
+            "7",
+            "7y",
+            "-7d",
+            "2025-13-01",
+            "2025/01/31",
+            "yesterday",
+        ] {
+            assert!(
+                parse_date_bound_at(text, now).is_err(),
+                "{:?} should be rejected",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn date_range_filters_sessions_by_header_time_then_mtime() {
+        let january = DateRange::new(
+            Some(parse_date_bound_at("2025-01-01", Local::now()).unwrap()),
+            Some(parse_date_bound_at("2025-01-31", Local::now()).unwrap()),
+        );
+        let header = |at: DateTime<Local>| Some(at.timestamp());
+        let sessions = [
+            // Header time wins over the file's mtime
+            (
+                info_at("start.cast", local(2025, 2, 3, 9, 0)),
+                header(local(2025, 1, 1, 0, 0)),
+            ),
+            (info_at("end.cast", local(2025, 1, 31, 23, 59)), None),
+            (
+                info_at("before.cast", local(2025, 1, 10, 9, 0)),
+                header(local(2024, 12, 31, 23, 59)),
+            ),
+            (info_at("after.cast", local(2025, 2, 1, 0, 0)), None),
+        ];
+
+        let kept: Vec<&str> = sessions
+            .iter()
+            .filter(|(session, timestamp)| january.matches(session, *timestamp))
+            .map(|(session, _)| session.filename.as_str())
+            .collect();
+        assert_eq!(kept, vec!["start.cast", "end.cast"]);
+        assert_eq!(january.describe(), "2025-01-01 to 2025-01-31");
+    }
+
+    #[test]
+    fn date_range_with_instants_and_open_ends() {
+        let since = DateRange::new(Some(DateBound::Instant(local(2025, 3, 7, 12, 0))), None);
+        assert!(since.contains(local(2025, 3, 7, 12, 0)));
+        assert!(!since.contains(local(2025, 3, 7, 11, 59)));
+        assert_eq!(since.describe(), "since 2025-03-07 12:00");
+
+        let until = DateRange::new(None, Some(DateBound::Instant(local(2025, 3, 7, 12, 0))));
+        assert!(!until.contains(local(2025, 3, 7, 12, 1)));
+        assert!(until.contains(local(2020, 1, 1, 0, 0)));
+
+        assert!(DateRange::default().is_unbounded());
+        assert!(DateRange::default().contains(local(1999, 1, 1, 0, 0)));
+    }
+
+    fn sized_at(name: &str, hours_ago: i64, size: u64) -> SessionInfo {
+        SessionInfo {
+            size,
+            ..info_at(name, Local::now() - chrono::Duration::hours(hours_ago))
+        }
+    }
+
+    #[test]
+    fn select_to_free_takes_oldest_until_target_is_met() {
+        let sessions = vec![
+            sized_at("new.cast", 1, 500),
+            sized_at("oldest.cast", 30, 300),
+            sized_at("older.cast", 20, 400),
+            sized_at("old.cast", 10, 200),
+        ];
+
+        let names = |target| -> Vec<String> {
+            select_to_free(&sessions, target)
+                .into_iter()
+                .map(|s| s.filename)
+                .collect()
+        };
+        assert_eq!(names(300), vec!["oldest.cast"]);
+        assert_eq!(names(301), vec!["oldest.cast", "older.cast"]);
+        assert_eq!(names(900), vec!["oldest.cast", "older.cast", "old.cast"]);
+    }
+
+    #[test]
+    fn select_to_free_returns_everything_when_target_exceeds_total() {
+        let sessions = vec![sized_at("a.cast", 2, 100), sized_at("b.cast", 1, 100)];
+        assert_eq!(select_to_free(&sessions, 10_000).len(), 2);
+        assert!(select_to_free(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn set_pinned_creates_and_removes_sidecar() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(temp_dir.path(), "claude", "ref.cast", 1000, "");
+        let path = temp_dir.path().join("claude").join("ref.cast");
+
+        assert!(!is_pinned(&path));
+        assert!(set_pinned(&path, true).unwrap());
+        assert!(is_pinned(&path));
+        assert!(!set_pinned(&path, true).unwrap());
+
+        assert!(set_pinned(&path, false).unwrap());
+        assert!(!sidecar_path(&path, PIN_SUFFIX).exists());
+        assert!(!set_pinned(&path, false).unwrap());
+    }
+
+    #[test]
+    fn list_sessions_marks_pinned_sessions() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(temp_dir.path(), "claude", "ref.cast", 1000, "");
+        write_cast(temp_dir.path(), "claude", "other.cast", 1000, "");
+        set_pinned(&temp_dir.path().join("claude").join("ref.cast"), true).unwrap();
+
+        let storage = StorageManager::new(create_test_config(temp_dir.path()));
+        let sessions = storage.list_sessions(None).unwrap();
+
+        assert_eq!(sessions.len(), 2);
+        for session in &sessions {
+            assert_eq!(session.pinned, session.filename == "ref.cast");
+        }
+    }
+
+    #[test]
+    fn delete_sessions_permanently_removes_sidecars() {
+        let temp_dir = TempDir::new().unwrap();
+        write_cast(temp_dir.path(), "claude", "ref.cast", 1000, "");
+        let path = temp_dir.path().join("claude").join("ref.cast");
+        set_pinned(&path, true).unwrap();
+        for suffix in [".bak", ".bookmarks", ".tags", NOTES_SUFFIX] {
+            fs::write(sidecar_path(&path, suffix), "x").unwrap();
+        }
+
+        let storage = StorageManager::new(create_test_config(temp_dir.path()));
+        let sessions = storage.list_sessions(None).unwrap();
+        storage.delete_sessions_permanently(&sessions).unwrap();
+
+        assert!(!path.exists());
+        for suffix in SIDECAR_SUFFIXES {
+            assert!(!sidecar_path(&path, suffix).exists(), "{}", suffix);
+        }
+    }
+
+    #[test]
+    fn delete_sessions_moves_files_to_the_trash() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions_dir = temp_dir.path().join("sessions");
+        write_cast(&sessions_dir, "claude", "ref.cast", 1000, "");
+        let path = sessions_dir.join("claude").join("ref.cast");
+        set_pinned(&path, true).unwrap();
+        let trash_dir = temp_dir.path().join("trash");
+
+        let storage = StorageManager::new(create_test_config(&sessions_dir))
+            .with_trash_dir(Some(trash_dir.clone()));
+        let sessions = storage.list_sessions(None).unwrap();
+        let freed = storage.delete_sessions(&sessions).unwrap();
+
+        assert_eq!(freed, sessions[0].size);
+        assert!(!path.exists());
+        assert!(!is_pinned(&path));
+        assert!(trash_dir.join("ref.cast").exists());
+        let entries = storage.trash().unwrap().entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original_path, path);
+        assert_eq!(entries[0].sidecars, vec![PIN_SUFFIX.to_string()]);
+    }
+
+    #[test]
+    fn delete_sessions_without_use_trash_is_permanent() {
+        let temp_dir = TempDir::new().unwrap();
+        let sessions_dir = temp_dir.path().join("sessions");
+        write_cast(&sessions_dir, "claude", "a.cast", 1000, "");
+        let trash_dir = temp_dir.path().join("trash");
+
+        let mut config = create_test_config(&sessions_dir);
+        config.storage.use_trash = false;
+        let storage = StorageManager::new(config).with_trash_dir(Some(trash_dir.clone()));
+        assert!(storage.trash().is_none());
+        let sessions = storage.list_sessions(None).unwrap();
+        storage.delete_sessions(&sessions).unwrap();
+
+        assert!(!sessions[0].path.exists());
+        assert!(!trash_dir.exists());
+    }
+
+    fn agent_session(agent: &str, name: &str, size: u64) -> SessionInfo {
+        SessionInfo {
+            path: PathBuf::from(format!("/mock/{}/{}", agent, name)),
+            agent: agent.to_string(),
+            size,
+            ..info_at(name, Local::now())
+        }
+    }
+
+    #[test]
+    fn stats_aggregate_size_per_agent() {
+        let sessions = vec![
+            agent_session("claude", "a.cast", 1000),
+            agent_session("codex", "b.cast", 250),
+            agent_session("claude", "c.cast", 500),
+            agent_session("gemini", "d.cast", 0),
+        ];
+
+        let stats = StorageStats::from_sessions(&sessions, 0.0);
+
+        assert_eq!(stats.total_size, 1750);
+        assert_eq!(stats.session_count, 4);
+        assert_eq!(
+            stats.sizes_by_agent,
+            HashMap::from([
+                ("claude".to_string(), 1500),
+                ("codex".to_string(), 250),
+                ("gemini".to_string(), 0),
+            ])
+        );
+        assert_eq!(stats.sessions_by_agent["claude"], 2);
+        assert_eq!(stats.oldest_session.unwrap().filename, "a.cast");
+    }
+
+    #[test]
+    fn stats_summary_lists_sizes_largest_first() {
+        let sessions = vec![
+            agent_session("codex", "b.cast", 2048),
+            agent_session("claude", "a.cast", 4096),
+        ];
+        let summary = StorageStats::from_sessions(&sessions, 0.0).summary();
+        assert!(
+            summary.contains("Size: claude: 4 KiB, codex: 2 KiB"),
+            "{}",
+            summary
+        );
+    }
+
+    #[test]
+    fn parse_size_history_sorts_and_skips_bad_lines() {
+        let history =
+            parse_size_history("2026-01-03 300\ngarbage\n2026-01-01 100\n2026-01-03 350\n");
+        let date = |d| NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+        assert_eq!(history, vec![(date(1), 100), (date(3), 350)]);
+    }
+
+    #[test]
+    fn record_size_history_keeps_one_point_per_day() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = StorageManager::new(create_test_config(temp_dir.path()));
+        let date = |d| NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+
+        storage.record_size_history(100, date(1)).unwrap();
+        storage.record_size_history(150, date(2)).unwrap();
+        let history = storage.record_size_history(175, date(2)).unwrap();
+
+        assert_eq!(history, vec![(date(1), 100), (date(2), 175)]);
+        let content = fs::read_to_string(temp_dir.path().join(SIZE_HISTORY_FILE)).unwrap();
+        assert_eq!(content, "2026-01-01 100\n2026-01-02 175\n");
+        // The history file is not a session
+        assert!(storage.list_sessions(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn record_size_history_trims_to_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = StorageManager::new(create_test_config(temp_dir.path()));
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        let mut history = Vec::new();
+        for day in 0..(SIZE_HISTORY_DAYS as i64 + 5) {
+            history = storage
+                .record_size_history(day as u64, start + chrono::Duration::days(day))
+                .unwrap();
+        }
+        assert_eq!(history.len(), SIZE_HISTORY_DAYS);
+        assert_eq!(history[0].1, 5);
+    }
+
+    #[test]
+    fn sparkline_scales_between_min_and_max() {
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▅█");
+        assert_eq!(sparkline(&[5, 5]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
 }
@@ -12,6 +12,8 @@
 //! - u: Restore Cursor Position
 //! - ESC 7: DEC Save Cursor
 //! - ESC 8: DEC Restore Cursor
+//! - ?6h/?6l: DECOM - Origin Mode
+//! - ESC c: RIS - Full Reset
 
 use super::super::performer::TerminalPerformer;
 
@@ -38,8 +40,9 @@ impl TerminalPerformer<'_> {
 
     /// Set cursor position to row, col (CSI H / CSI f).
     /// Parameters are 1-indexed, converted to 0-indexed internally.
+    /// In origin mode the row is relative to the scroll region.
     pub fn handle_cursor_position(&mut self, row: usize, col: usize) {
-        *self.cursor_row = row.saturating_sub(1).min(self.height.saturating_sub(1));
+        *self.cursor_row = self.absolute_row(row);
         *self.cursor_col = col.saturating_sub(1).min(self.width.saturating_sub(1));
     }
 
@@ -51,8 +54,43 @@ impl TerminalPerformer<'_> {
 
     /// Set cursor row (CSI d).
     /// Parameter is 1-indexed, converted to 0-indexed internally.
+    /// In origin mode the row is relative to the scroll region.
     pub fn handle_cursor_vertical_absolute(&mut self, row: usize) {
-        *self.cursor_row = row.saturating_sub(1).min(self.height.saturating_sub(1));
+        *self.cursor_row = self.absolute_row(row);
+    }
+
+    /// Convert a 1-indexed row parameter to a 0-indexed screen row.
+    ///
+    /// With origin mode on, rows count from `scroll_top` and are clamped to
+    /// the scroll region; otherwise they are clamped to the screen.
+    fn absolute_row(&self, row: usize) -> usize {
+        let row = row.saturating_sub(1);
+        if self.origin_mode {
+            (self.scroll_top + row).min(self.scroll_bottom)
+        } else {
+            row.min(self.height.saturating_sub(1))
+        }
+    }
+
+    /// Set or reset origin mode (DECOM, CSI ? 6 h / CSI ? 6 l).
+    /// Either way the cursor moves to the new home position.
+    pub fn handle_origin_mode(&mut self, enabled: bool) {
+        self.origin_mode = enabled;
+        *self.cursor_row = if enabled { self.scroll_top } else { 0 };
+        *self.cursor_col = 0;
+    }
+
+    /// Reset the terminal to its initial state (RIS, ESC c).
+    /// Clears the screen and resets the cursor, style, scroll region,
+    /// origin mode and tab stops.
+    pub fn handle_full_reset(&mut self) {
+        *self.tab_stops = super::tabs::default_tab_stops(self.width);
+        self.clear_screen();
+        *self.current_style = Default::default();
+        *self.saved_cursor = None;
+        self.scroll_top = 0;
+        self.scroll_bottom = self.height.saturating_sub(1);
+        self.origin_mode = false;
     }
 
     /// Save cursor position (CSI s).
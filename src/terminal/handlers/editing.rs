@@ -63,6 +63,7 @@ impl TerminalPerformer<'_> {
     /// Replaces n characters with spaces starting at cursor, without moving cursor.
     pub fn handle_erase_chars(&mut self, n: usize) {
         if *self.cursor_row < self.height {
+            self.mark_dirty(*self.cursor_row);
             for i in 0..n {
                 let col = *self.cursor_col + i;
                 if col < self.width {
@@ -0,0 +1,72 @@
+//! Inline image handlers.
+//!
+//! Captures graphics sequences instead of dropping them:
+//! - DCS q: Sixel graphics
+//! - OSC 1337 File=: iTerm2 inline images
+//!
+//! Payloads are kept verbatim on the buffer; nothing is drawn into cells.
+
+use super::super::performer::TerminalPerformer;
+use super::super::types::{ImageProtocol, InlineImage};
+
+impl TerminalPerformer<'_> {
+    /// Handle a DCS hook. Starts capturing if the sequence is Sixel.
+    pub fn handle_dcs_hook(&mut self, params: &vte::Params, intermediates: &[u8], action: char) {
+        if action != 'q' || !intermediates.is_empty() {
+            return;
+        }
+        let params: Vec<String> = params
+            .iter()
+            .map(|p| {
+                p.iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join(":")
+            })
+            .collect();
+        let mut sequence = b"\x1bP".to_vec();
+        // The parser reports a bare `ESC P q` as a single 0 parameter
+        if params != ["0"] {
+            sequence.extend_from_slice(params.join(";").as_bytes());
+        }
+        sequence.push(b'q');
+        *self.pending_sixel = Some(sequence);
+    }
+
+    /// Append a byte of DCS data to the Sixel being captured.
+    pub fn handle_dcs_put(&mut self, byte: u8) {
+        if let Some(sequence) = self.pending_sixel.as_mut() {
+            sequence.push(byte);
+        }
+    }
+
+    /// Finish the DCS sequence and store the captured Sixel image.
+    pub fn handle_dcs_unhook(&mut self) {
+        if let Some(mut sequence) = self.pending_sixel.take() {
+            sequence.extend_from_slice(b"\x1b\\");
+            self.push_image(ImageProtocol::Sixel, &sequence);
+        }
+    }
+
+    /// Handle an OSC sequence. Stores iTerm2 inline images, ignores the rest.
+    pub fn handle_osc(&mut self, params: &[&[u8]], bell_terminated: bool) {
+        let is_image = params.first() == Some(&&b"1337"[..])
+            && params.get(1).is_some_and(|p| p.starts_with(b"File="));
+        if !is_image {
+            return;
+        }
+        let mut sequence = b"\x1b]".to_vec();
+        sequence.extend_from_slice(&params.join(&b';'));
+        sequence.extend_from_slice(if bell_terminated { b"\x07" } else { b"\x1b\\" });
+        self.push_image(ImageProtocol::Iterm2, &sequence);
+    }
+
+    fn push_image(&mut self, protocol: ImageProtocol, sequence: &[u8]) {
+        self.images.push(InlineImage {
+            protocol,
+            row: *self.cursor_row,
+            col: *self.cursor_col,
+            sequence: String::from_utf8_lossy(sequence).into_owned(),
+        });
+    }
+}
@@ -3,13 +3,17 @@
 //! This module contains handlers for various escape sequence categories:
 //! - cursor: Cursor movement and positioning
 //! - editing: Erase and delete operations
+//! - image: Sixel and iTerm2 inline image capture
 //! - scroll: Scroll region management
 //! - style: SGR (Select Graphic Rendition) for colors and attributes
+//! - tabs: Tab stops (HT, HTS, TBC)
 
 pub mod cursor;
 pub mod editing;
+pub mod image;
 pub mod scroll;
 pub mod style;
+pub mod tabs;
 
 use tracing::trace;
 
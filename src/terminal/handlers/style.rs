@@ -2,19 +2,30 @@
 //!
 //! Handles CSI m sequences for text styling:
 //! - Colors (foreground and background)
-//! - Attributes (bold, dim, italic, underline, reverse)
-//! - 256-color and RGB color support
+//! - Attributes (bold, dim, italic, underline, reverse); blink, hidden,
+//!   strikethrough and overline are accepted but not rendered
+//! - 256-color and RGB color support, in both the `38;5;n` / `38;2;r;g;b`
+//!   form and the colon-separated ITU form (`38:5:n`, `38:2::r:g:b`)
 
 use super::super::performer::TerminalPerformer;
 use super::super::types::{CellStyle, Color};
+use super::log_unhandled_csi;
 
 impl TerminalPerformer<'_> {
     /// Handle SGR (Select Graphic Rendition) - CSI m.
     /// Parses parameters and updates current style.
-    pub fn handle_sgr(&mut self, params: &[u16]) {
-        let mut iter = params.iter().peekable();
+    ///
+    /// Each parameter is a group of colon-separated subparameters; only
+    /// extended colors use more than the first one.
+    pub fn handle_sgr<'p>(&mut self, params: impl IntoIterator<Item = &'p [u16]>) {
+        let groups: Vec<&[u16]> = params.into_iter().collect();
+        let mut i = 0;
+
+        while i < groups.len() {
+            let group = groups[i];
+            let param = group.first().copied().unwrap_or(0);
+            i += 1;
 
-        while let Some(&param) = iter.next() {
             match param {
                 0 => *self.current_style = CellStyle::default(), // Reset
                 1 => self.current_style.bold = true,
@@ -38,9 +49,18 @@ impl TerminalPerformer<'_> {
                 35 => self.current_style.fg = Color::Magenta,
                 36 => self.current_style.fg = Color::Cyan,
                 37 => self.current_style.fg = Color::White,
-                38 => {
-                    // Extended foreground color
-                    self.parse_extended_color(&mut iter, true);
+                // Extended colors: 38 foreground, 48 background
+                38 | 48 => {
+                    let color = if group.len() > 1 {
+                        parse_colon_color(&group[1..])
+                    } else {
+                        let (color, used) = parse_semicolon_color(&groups[i..]);
+                        i += used;
+                        color
+                    };
+                    if let Some(color) = color {
+                        self.set_color(color, param == 38);
+                    }
                 }
                 39 => self.current_style.fg = Color::Default,
                 // Standard background colors (40-47)
@@ -52,10 +72,6 @@ impl TerminalPerformer<'_> {
                 45 => self.current_style.bg = Color::Magenta,
                 46 => self.current_style.bg = Color::Cyan,
                 47 => self.current_style.bg = Color::White,
-                48 => {
-                    // Extended background color
-                    self.parse_extended_color(&mut iter, false);
-                }
                 49 => self.current_style.bg = Color::Default,
                 // Bright foreground colors (90-97)
                 90 => self.current_style.fg = Color::BrightBlack,
@@ -75,46 +91,53 @@ impl TerminalPerformer<'_> {
                 105 => self.current_style.bg = Color::BrightMagenta,
                 106 => self.current_style.bg = Color::BrightCyan,
                 107 => self.current_style.bg = Color::BrightWhite,
-                _ => {}
+                // Blink, hidden, strikethrough, double underline, overline
+                // and their resets are valid but not rendered
+                5 | 6 | 8 | 9 | 21 | 25 | 28 | 29 | 53 | 55 => {}
+                _ => log_unhandled_csi('m', &[param], &[]),
             }
         }
     }
 
-    /// Parse extended color (256-color or RGB) from SGR parameters.
-    fn parse_extended_color(
-        &mut self,
-        iter: &mut std::iter::Peekable<std::slice::Iter<'_, u16>>,
-        is_foreground: bool,
-    ) {
-        if let Some(&&mode) = iter.peek() {
-            iter.next();
-            match mode {
-                5 => {
-                    // 256-color mode
-                    if let Some(&&idx) = iter.peek() {
-                        iter.next();
-                        let color = Color::Indexed(idx as u8);
-                        if is_foreground {
-                            self.current_style.fg = color;
-                        } else {
-                            self.current_style.bg = color;
-                        }
-                    }
-                }
-                2 => {
-                    // RGB mode
-                    let r = iter.next().copied().unwrap_or(0) as u8;
-                    let g = iter.next().copied().unwrap_or(0) as u8;
-                    let b = iter.next().copied().unwrap_or(0) as u8;
-                    let color = Color::Rgb(r, g, b);
-                    if is_foreground {
-                        self.current_style.fg = color;
-                    } else {
-                        self.current_style.bg = color;
-                    }
-                }
-                _ => {}
-            }
+    /// Set the foreground or background color.
+    fn set_color(&mut self, color: Color, is_foreground: bool) {
+        if is_foreground {
+            self.current_style.fg = color;
+        } else {
+            self.current_style.bg = color;
+        }
+    }
+}
+
+/// Parse a semicolon-form extended color (`5;n` or `2;r;g;b`) from the
+/// parameters after `38`/`48`. Returns the color and how many parameters
+/// it used.
+fn parse_semicolon_color(rest: &[&[u16]]) -> (Option<Color>, usize) {
+    let value = |i: usize| rest.get(i).and_then(|group| group.first()).copied();
+    match value(0) {
+        Some(5) => match value(1) {
+            Some(idx) => (Some(Color::Indexed(idx as u8)), 2),
+            None => (None, 1),
+        },
+        Some(2) => {
+            let used = rest.len().min(4);
+            let channel = |i: usize| value(i).unwrap_or(0) as u8;
+            (Some(Color::Rgb(channel(1), channel(2), channel(3))), used)
         }
+        Some(_) => (None, 1),
+        None => (None, 0),
+    }
+}
+
+/// Parse the subparameters after `38:` / `48:` (colon-separated form).
+///
+/// Accepts `5:n`, `2:r:g:b` and the ITU form with a color-space id,
+/// `2:id:r:g:b` (usually written `2::r:g:b` with the id left empty).
+fn parse_colon_color(sub: &[u16]) -> Option<Color> {
+    match sub {
+        [5, idx, ..] => Some(Color::Indexed(*idx as u8)),
+        [2, _, r, g, b, ..] => Some(Color::Rgb(*r as u8, *g as u8, *b as u8)),
+        [2, r, g, b] => Some(Color::Rgb(*r as u8, *g as u8, *b as u8)),
+        _ => None,
     }
 }
@@ -0,0 +1,50 @@
+//! Tab stop handlers.
+//!
+//! Handles tab control:
+//! - HT (`\t`): Horizontal Tab to the next tab stop
+//! - ESC H: HTS - Horizontal Tab Set at the cursor column
+//! - g: TBC - Tab Clear (0: at cursor, 3: all)
+
+use super::super::performer::TerminalPerformer;
+
+/// Default tab stops for a screen `width` columns wide: every 8 columns.
+pub(crate) fn default_tab_stops(width: usize) -> Vec<bool> {
+    (0..width).map(|col| col > 0 && col % 8 == 0).collect()
+}
+
+/// Resize tab stops, keeping existing ones and defaulting new columns.
+pub(crate) fn resize_tab_stops(tab_stops: &mut Vec<bool>, width: usize) {
+    let old_width = tab_stops.len();
+    tab_stops.truncate(width);
+    tab_stops.extend(default_tab_stops(width).into_iter().skip(old_width));
+}
+
+impl TerminalPerformer<'_> {
+    /// Move to the next tab stop (HT), or to the last column if there is none.
+    pub fn handle_horizontal_tab(&mut self) {
+        let last_col = self.width.saturating_sub(1);
+        let next = (*self.cursor_col + 1..self.width).find(|&col| self.tab_stops[col]);
+        *self.cursor_col = next.unwrap_or(last_col);
+    }
+
+    /// Set a tab stop at the cursor column (HTS, ESC H).
+    pub fn handle_tab_set(&mut self) {
+        if let Some(stop) = self.tab_stops.get_mut(*self.cursor_col) {
+            *stop = true;
+        }
+    }
+
+    /// Clear tab stops (TBC, CSI g).
+    /// Mode 0 clears the stop at the cursor column, mode 3 clears all stops.
+    pub fn handle_tab_clear(&mut self, mode: u16) {
+        match mode {
+            0 => {
+                if let Some(stop) = self.tab_stops.get_mut(*self.cursor_col) {
+                    *stop = false;
+                }
+            }
+            3 => self.tab_stops.fill(false),
+            _ => {}
+        }
+    }
+}
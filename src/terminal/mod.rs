@@ -10,7 +10,7 @@ mod handlers;
 mod performer;
 mod types;
 
-pub use types::{Cell, CellStyle, Color, StyledLine};
+pub use types::{Cell, CellStyle, Color, ImageProtocol, InlineImage, StyledLine};
 
 use std::fmt;
 use vte::Parser;
@@ -41,6 +41,16 @@ pub struct TerminalBuffer {
     scroll_top: usize,
     /// Bottom margin of scroll region (0-indexed, inclusive)
     scroll_bottom: usize,
+    /// Origin mode (DECOM): cursor rows are relative to the scroll region
+    origin_mode: bool,
+    /// Inline images (Sixel, iTerm2) seen in the output
+    images: Vec<InlineImage>,
+    /// Sixel sequence still being received
+    pending_sixel: Option<Vec<u8>>,
+    /// Rows changed since the last [`clear_dirty`](Self::clear_dirty)
+    dirty: Vec<bool>,
+    /// Columns with a tab stop (every 8 columns unless changed by HTS/TBC)
+    tab_stops: Vec<bool>,
 }
 
 impl TerminalBuffer {
@@ -58,6 +68,11 @@ impl TerminalBuffer {
             saved_cursor: None,
             scroll_top: 0,
             scroll_bottom: height.saturating_sub(1),
+            origin_mode: false,
+            images: Vec::new(),
+            pending_sixel: None,
+            dirty: vec![true; height],
+            tab_stops: handlers::tabs::default_tab_stops(width),
         }
     }
 
@@ -75,14 +90,20 @@ impl TerminalBuffer {
             saved_cursor: &mut self.saved_cursor,
             scroll_top: self.scroll_top,
             scroll_bottom: self.scroll_bottom,
+            origin_mode: self.origin_mode,
             scroll_callback: scroll_callback
                 .as_mut()
                 .map(|cb| *cb as &mut dyn FnMut(Vec<Cell>)),
+            images: &mut self.images,
+            pending_sixel: &mut self.pending_sixel,
+            dirty: &mut self.dirty,
+            tab_stops: &mut self.tab_stops,
         };
         self.parser.advance(&mut perf, data.as_bytes());
-        // Update scroll region in case it was changed by DECSTBM
+        // Update scroll region and origin mode in case they were changed
         self.scroll_top = perf.scroll_top;
         self.scroll_bottom = perf.scroll_bottom;
+        self.origin_mode = perf.origin_mode;
     }
 
     /// Resize the terminal buffer to new dimensions.
@@ -109,14 +130,17 @@ impl TerminalBuffer {
         self.buffer = new_buffer;
         self.width = new_width;
         self.height = new_height;
+        self.dirty = vec![true; new_height];
+        handlers::tabs::resize_tab_stops(&mut self.tab_stops, new_width);
 
         // Clamp cursor to new bounds
         self.cursor_col = self.cursor_col.min(new_width.saturating_sub(1));
         self.cursor_row = self.cursor_row.min(new_height.saturating_sub(1));
 
-        // Reset scroll region to full screen on resize
+        // Reset scroll region to full screen (and origin mode) on resize
         self.scroll_top = 0;
         self.scroll_bottom = new_height.saturating_sub(1);
+        self.origin_mode = false;
 
         // Invalidate saved cursor if it's now out of bounds
         if let Some((row, col)) = self.saved_cursor {
@@ -165,10 +189,40 @@ impl TerminalBuffer {
             .collect()
     }
 
+    /// Inline images captured from the output, in arrival order.
+    pub fn images(&self) -> &[InlineImage] {
+        &self.images
+    }
+
     /// Get a reference to a specific row's cells (no cloning).
     pub fn row(&self, row_idx: usize) -> Option<&[Cell]> {
         self.buffer.get(row_idx).map(|r| r.as_slice())
     }
+
+    /// Whether a row changed since the last [`clear_dirty`](Self::clear_dirty).
+    ///
+    /// All rows start dirty, and a resize marks every row dirty again.
+    /// Rows outside the buffer are never dirty.
+    pub fn is_row_dirty(&self, row_idx: usize) -> bool {
+        self.dirty.get(row_idx).copied().unwrap_or(false)
+    }
+
+    /// Indices of the rows changed since the last [`clear_dirty`](Self::clear_dirty).
+    pub fn dirty_rows(&self) -> Vec<usize> {
+        (0..self.dirty.len())
+            .filter(|&row| self.dirty[row])
+            .collect()
+    }
+
+    /// Mark every row as rendered.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.fill(false);
+    }
+
+    /// Mark every row as changed, forcing the next render to redraw all rows.
+    pub fn mark_all_dirty(&mut self) {
+        self.dirty.fill(true);
+    }
 }
 
 impl fmt::Display for TerminalBuffer {
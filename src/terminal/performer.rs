@@ -7,7 +7,7 @@ use unicode_width::UnicodeWidthChar;
 use vte::Perform;
 
 use super::handlers::{log_unhandled_csi, log_unhandled_esc};
-use super::types::Cell;
+use super::types::{Cell, InlineImage};
 
 /// Performer that handles VTE callbacks and updates the buffer.
 pub(crate) struct TerminalPerformer<'a> {
@@ -22,11 +22,35 @@ pub(crate) struct TerminalPerformer<'a> {
     pub scroll_top: usize,
     /// Bottom margin of scroll region (0-indexed, inclusive)
     pub scroll_bottom: usize,
+    /// Origin mode (DECOM): cursor rows are relative to the scroll region
+    pub origin_mode: bool,
     /// Optional callback for lines that are scrolled off the screen
     pub scroll_callback: Option<&'a mut dyn FnMut(Vec<Cell>)>,
+    /// Inline images captured so far
+    pub images: &'a mut Vec<InlineImage>,
+    /// Sixel sequence being captured (may span several `process` calls)
+    pub pending_sixel: &'a mut Option<Vec<u8>>,
+    /// Per-row flags set whenever a row's cells change
+    pub dirty: &'a mut Vec<bool>,
+    /// Per-column tab stops (see handlers/tabs.rs)
+    pub tab_stops: &'a mut Vec<bool>,
 }
 
 impl<'a> TerminalPerformer<'a> {
+    /// Flag a row as changed since the last render.
+    pub(crate) fn mark_dirty(&mut self, row: usize) {
+        if let Some(flag) = self.dirty.get_mut(row) {
+            *flag = true;
+        }
+    }
+
+    /// Flag rows `top..=bottom` as changed since the last render.
+    pub(crate) fn mark_rows_dirty(&mut self, top: usize, bottom: usize) {
+        for row in top..=bottom {
+            self.mark_dirty(row);
+        }
+    }
+
     /// Move cursor down one line, scrolling if necessary.
     /// Respects the scroll region (DECSTBM).
     /// Note: This does NOT move to column 0 (that's carriage return).
@@ -58,6 +82,7 @@ impl<'a> TerminalPerformer<'a> {
             if self.scroll_top < self.height && self.scroll_bottom < self.height {
                 // Remove the line at scroll_top
                 let line = self.buffer.remove(self.scroll_top);
+                self.mark_rows_dirty(self.scroll_top, self.scroll_bottom);
 
                 // If a callback is registered, pass the scrolled-off line to it
                 if let Some(ref mut cb) = self.scroll_callback {
@@ -78,6 +103,7 @@ impl<'a> TerminalPerformer<'a> {
             if self.scroll_top < self.height && self.scroll_bottom < self.height {
                 // Remove the line at scroll_bottom
                 self.buffer.remove(self.scroll_bottom);
+                self.mark_rows_dirty(self.scroll_top, self.scroll_bottom);
                 // Insert a new blank line at scroll_top
                 self.buffer
                     .insert(self.scroll_top, vec![Cell::default(); self.width]);
@@ -115,6 +141,7 @@ impl<'a> TerminalPerformer<'a> {
         }
 
         if *self.cursor_row < self.height && *self.cursor_col < self.width {
+            self.mark_dirty(*self.cursor_row);
             self.buffer[*self.cursor_row][*self.cursor_col] = Cell {
                 char: c,
                 style: *self.current_style,
@@ -135,6 +162,7 @@ impl<'a> TerminalPerformer<'a> {
     /// Erase from cursor to end of line.
     pub(crate) fn erase_to_eol(&mut self) {
         if *self.cursor_row < self.height {
+            self.mark_dirty(*self.cursor_row);
             for col in *self.cursor_col..self.width {
                 self.buffer[*self.cursor_row][col] = Cell::default();
             }
@@ -144,6 +172,7 @@ impl<'a> TerminalPerformer<'a> {
     /// Erase entire line.
     pub(crate) fn erase_entire_line(&mut self) {
         if *self.cursor_row < self.height {
+            self.mark_dirty(*self.cursor_row);
             for col in 0..self.width {
                 self.buffer[*self.cursor_row][col] = Cell::default();
             }
@@ -156,6 +185,7 @@ impl<'a> TerminalPerformer<'a> {
             return;
         }
         if *self.cursor_row < self.height {
+            self.mark_dirty(*self.cursor_row);
             let end_col = (*self.cursor_col).min(self.width - 1);
             for col in 0..=end_col {
                 self.buffer[*self.cursor_row][col] = Cell::default();
@@ -167,6 +197,7 @@ impl<'a> TerminalPerformer<'a> {
     pub(crate) fn erase_from_sos(&mut self) {
         // Erase all rows before current
         for row in 0..*self.cursor_row {
+            self.mark_dirty(row);
             for col in 0..self.width {
                 self.buffer[row][col] = Cell::default();
             }
@@ -178,6 +209,7 @@ impl<'a> TerminalPerformer<'a> {
     /// Delete n characters at cursor, shifting remaining left.
     pub(crate) fn delete_chars(&mut self, n: usize) {
         if *self.cursor_row < self.height {
+            self.mark_dirty(*self.cursor_row);
             let row = &mut self.buffer[*self.cursor_row];
             for i in *self.cursor_col..self.width {
                 if i + n < self.width {
@@ -192,6 +224,7 @@ impl<'a> TerminalPerformer<'a> {
     /// Insert n blank characters at cursor, shifting existing right.
     pub(crate) fn insert_chars(&mut self, n: usize) {
         if *self.cursor_row < self.height {
+            self.mark_dirty(*self.cursor_row);
             let row = &mut self.buffer[*self.cursor_row];
             for i in ((*self.cursor_col + n)..self.width).rev() {
                 row[i] = row[i - n];
@@ -211,6 +244,7 @@ impl<'a> TerminalPerformer<'a> {
                 if *self.cursor_row <= self.scroll_bottom {
                     // Remove the line at cursor position
                     self.buffer.remove(*self.cursor_row);
+                    self.mark_rows_dirty(*self.cursor_row, self.scroll_bottom);
                     // Insert a new blank line at scroll_bottom
                     self.buffer
                         .insert(self.scroll_bottom, vec![Cell::default(); self.width]);
@@ -227,6 +261,7 @@ impl<'a> TerminalPerformer<'a> {
                 if *self.cursor_row <= self.scroll_bottom {
                     // Remove the line at scroll_bottom
                     self.buffer.remove(self.scroll_bottom);
+                    self.mark_rows_dirty(*self.cursor_row, self.scroll_bottom);
                     // Insert a new blank line at cursor position
                     self.buffer
                         .insert(*self.cursor_row, vec![Cell::default(); self.width]);
@@ -239,6 +274,7 @@ impl<'a> TerminalPerformer<'a> {
     pub(crate) fn erase_to_eos(&mut self) {
         self.erase_to_eol();
         for row in (*self.cursor_row + 1)..self.height {
+            self.mark_dirty(row);
             for col in 0..self.width {
                 self.buffer[row][col] = Cell::default();
             }
@@ -248,6 +284,7 @@ impl<'a> TerminalPerformer<'a> {
     /// Clear entire screen.
     pub(crate) fn clear_screen(&mut self) {
         for row in 0..self.height {
+            self.mark_dirty(row);
             for col in 0..self.width {
                 self.buffer[row][col] = Cell::default();
             }
@@ -267,32 +304,35 @@ impl Perform for TerminalPerformer<'_> {
             b'\n' => self.line_feed(),
             b'\r' => self.carriage_return(),
             b'\x08' => self.backspace(), // Backspace
-            b'\t' => {
-                // Tab - move to next tab stop (every 8 columns)
-                let next_tab = (*self.cursor_col / 8 + 1) * 8;
-                *self.cursor_col = next_tab.min(self.width - 1);
-            }
+            b'\t' => self.handle_horizontal_tab(),
             _ => {}
         }
     }
 
-    fn hook(&mut self, _params: &vte::Params, _intermediates: &[u8], _ignore: bool, _action: char) {
+    fn hook(&mut self, params: &vte::Params, intermediates: &[u8], _ignore: bool, action: char) {
+        self.handle_dcs_hook(params, intermediates, action);
     }
 
-    fn put(&mut self, _byte: u8) {}
+    fn put(&mut self, byte: u8) {
+        self.handle_dcs_put(byte);
+    }
 
-    fn unhook(&mut self) {}
+    fn unhook(&mut self) {
+        self.handle_dcs_unhook();
+    }
 
-    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {}
+    fn osc_dispatch(&mut self, params: &[&[u8]], bell_terminated: bool) {
+        self.handle_osc(params, bell_terminated);
+    }
 
     fn csi_dispatch(
         &mut self,
-        params: &vte::Params,
+        raw_params: &vte::Params,
         intermediates: &[u8],
         _ignore: bool,
         action: char,
     ) {
-        let params: Vec<u16> = params
+        let params: Vec<u16> = raw_params
             .iter()
             .map(|p| p.first().copied().unwrap_or(0))
             .collect();
@@ -300,7 +340,11 @@ impl Perform for TerminalPerformer<'_> {
         // Handle DEC private mode sequences (ESC[?...h/l) and mouse tracking (ESC[<...)
         // These are safe to ignore for text rendering purposes
         if intermediates.contains(&b'?') || intermediates.contains(&b'<') {
-            // DEC private modes - we don't need to implement them for text rendering
+            // Origin mode (?6h/l) changes where the cursor goes (handlers/cursor.rs)
+            if intermediates.contains(&b'?') && matches!(action, 'h' | 'l') && params.contains(&6) {
+                self.handle_origin_mode(action == 'h');
+            }
+            // Other DEC private modes don't matter for text rendering.
             // Common ones: ?25h/l (cursor visibility), ?2026h/l (synchronized update),
             // ?1049h/l (alternate screen buffer), <... (mouse tracking SGR mode), etc.
             return;
@@ -371,7 +415,18 @@ impl Perform for TerminalPerformer<'_> {
             }
 
             // Style (handlers/style.rs)
-            'm' => self.handle_sgr(&params),
+            // SGR needs the colon-separated subparameters (e.g. 38:2::r:g:b)
+            'm' => self.handle_sgr(raw_params.iter()),
+
+            // Tab stops (handlers/tabs.rs)
+            'g' => {
+                let mode = params.first().copied().unwrap_or(0);
+                self.handle_tab_clear(mode);
+            }
+
+            // Cursor style (DECSCUSR, CSI Ps SP q): only affects the live
+            // cursor's shape, so there is nothing to render
+            'q' if intermediates == b" " => {}
 
             // Scroll region (handlers/scroll.rs)
             'r' => {
@@ -397,6 +452,8 @@ impl Perform for TerminalPerformer<'_> {
             b'7' => self.handle_dec_save_cursor(),
             b'8' => self.handle_dec_restore_cursor(),
             b'M' => self.handle_reverse_index(),
+            b'c' if intermediates.is_empty() => self.handle_full_reset(),
+            b'H' if intermediates.is_empty() => self.handle_tab_set(),
             _ => log_unhandled_esc(byte, intermediates),
         }
     }
@@ -2,9 +2,11 @@
 //!
 //! Contains the core data structures for representing terminal state:
 //! - Color: ANSI color codes (16 colors, 256-color palette, RGB)
-//! - CellStyle: Text attributes (bold, italic, underline, etc.)
+//! - CellStyle: Text attributes (bold, italic, underline, etc.) and their
+//!   ANSI escape sequences
 //! - Cell: A single character with its style
 //! - StyledLine: A line of styled cells for rendering
+//! - InlineImage: A raw Sixel or iTerm2 image sequence seen in the output
 
 /// ANSI color codes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -33,6 +35,44 @@ pub enum Color {
     Rgb(u8, u8, u8),
 }
 
+impl Color {
+    /// Append the SGR sequence selecting this color.
+    ///
+    /// `base` is 30 for foreground and 40 for background; bright colors
+    /// use `base + 60` and extended colors use `base + 8`.
+    fn push_ansi(&self, base: u8, buf: &mut String) -> bool {
+        let code = match self {
+            Color::Default => return false,
+            Color::Black => base,
+            Color::Red => base + 1,
+            Color::Green => base + 2,
+            Color::Yellow => base + 3,
+            Color::Blue => base + 4,
+            Color::Magenta => base + 5,
+            Color::Cyan => base + 6,
+            Color::White => base + 7,
+            Color::BrightBlack => base + 60,
+            Color::BrightRed => base + 61,
+            Color::BrightGreen => base + 62,
+            Color::BrightYellow => base + 63,
+            Color::BrightBlue => base + 64,
+            Color::BrightMagenta => base + 65,
+            Color::BrightCyan => base + 66,
+            Color::BrightWhite => base + 67,
+            Color::Indexed(n) => {
+                buf.push_str(&format!("\x1b[{};5;{}m", base + 8, n));
+                return true;
+            }
+            Color::Rgb(r, g, b) => {
+                buf.push_str(&format!("\x1b[{};2;{};{};{}m", base + 8, r, g, b));
+                return true;
+            }
+        };
+        buf.push_str(&format!("\x1b[{}m", code));
+        true
+    }
+}
+
 /// Style attributes for a terminal cell
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct CellStyle {
@@ -45,6 +85,41 @@ pub struct CellStyle {
     pub reverse: bool,
 }
 
+impl CellStyle {
+    /// Append the ANSI escape code for the foreground color.
+    ///
+    /// Returns `true` if a code was appended, `false` if the color is default.
+    pub fn to_ansi_fg(&self, buf: &mut String) -> bool {
+        self.fg.push_ansi(30, buf)
+    }
+
+    /// Append the ANSI escape code for the background color.
+    ///
+    /// Returns `true` if a code was appended, `false` if the color is default.
+    pub fn to_ansi_bg(&self, buf: &mut String) -> bool {
+        self.bg.push_ansi(40, buf)
+    }
+
+    /// Append ANSI codes for text attributes (bold, dim, italic, underline, reverse).
+    pub fn to_ansi_attrs(&self, buf: &mut String) {
+        if self.bold {
+            buf.push_str("\x1b[1m");
+        }
+        if self.dim {
+            buf.push_str("\x1b[2m");
+        }
+        if self.italic {
+            buf.push_str("\x1b[3m");
+        }
+        if self.underline {
+            buf.push_str("\x1b[4m");
+        }
+        if self.reverse {
+            buf.push_str("\x1b[7m");
+        }
+    }
+}
+
 /// A single cell in the terminal buffer
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Cell {
@@ -66,3 +141,321 @@ impl Default for Cell {
 pub struct StyledLine {
     pub cells: Vec<Cell>,
 }
+
+/// Graphics protocol of an inline image sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// DEC Sixel graphics (`ESC P ... q ... ST`)
+    Sixel,
+    /// iTerm2 inline images (`ESC ] 1337 ; File=... BEL`)
+    Iterm2,
+}
+
+/// An inline image captured from the output stream.
+///
+/// The emulator does not decode images; it keeps the raw escape sequence so
+/// it can be re-emitted for terminals that support the protocol. The anchor
+/// is the cursor position when the sequence arrived and is not adjusted when
+/// the screen scrolls afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineImage {
+    pub protocol: ImageProtocol,
+    /// Cursor row when the image was received (0-indexed)
+    pub row: usize,
+    /// Cursor column when the image was received (0-indexed)
+    pub col: usize,
+    /// The complete escape sequence, including introducer and terminator
+    pub sequence: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Color as TermColor;
+
+    #[test]
+    fn to_ansi_fg_default_returns_false() {
+        let style = CellStyle::default();
+        let mut buf = String::new();
+        assert!(!style.to_ansi_fg(&mut buf));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn to_ansi_fg_red_appends_code() {
+        let style = CellStyle {
+            fg: TermColor::Red,
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        assert!(style.to_ansi_fg(&mut buf));
+        assert_eq!(buf, "\x1b[31m");
+    }
+
+    #[test]
+    fn to_ansi_fg_all_basic_colors() {
+        let test_cases = [
+            (TermColor::Black, "\x1b[30m"),
+            (TermColor::Red, "\x1b[31m"),
+            (TermColor::Green, "\x1b[32m"),
+            (TermColor::Yellow, "\x1b[33m"),
+            (TermColor::Blue, "\x1b[34m"),
+            (TermColor::Magenta, "\x1b[35m"),
+            (TermColor::Cyan, "\x1b[36m"),
+            (TermColor::White, "\x1b[37m"),
+        ];
+
+        for (color, expected) in test_cases {
+            let style = CellStyle {
+                fg: color,
+                ..Default::default()
+            };
+            let mut buf = String::new();
+            assert!(style.to_ansi_fg(&mut buf));
+            assert_eq!(buf, expected, "Failed for {:?}", color);
+        }
+    }
+
+    #[test]
+    fn to_ansi_fg_all_bright_colors() {
+        let test_cases = [
+            (TermColor::BrightBlack, "\x1b[90m"),
+            (TermColor::BrightRed, "\x1b[91m"),
+            (TermColor::BrightGreen, "\x1b[92m"),
+            (TermColor::BrightYellow, "\x1b[93m"),
+            (TermColor::BrightBlue, "\x1b[94m"),
+            (TermColor::BrightMagenta, "\x1b[95m"),
+            (TermColor::BrightCyan, "\x1b[96m"),
+            (TermColor::BrightWhite, "\x1b[97m"),
+        ];
+
+        for (color, expected) in test_cases {
+            let style = CellStyle {
+                fg: color,
+                ..Default::default()
+            };
+            let mut buf = String::new();
+            assert!(style.to_ansi_fg(&mut buf));
+            assert_eq!(buf, expected, "Failed for {:?}", color);
+        }
+    }
+
+    #[test]
+    fn to_ansi_fg_indexed_color() {
+        let style = CellStyle {
+            fg: TermColor::Indexed(196),
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        assert!(style.to_ansi_fg(&mut buf));
+        assert_eq!(buf, "\x1b[38;5;196m");
+    }
+
+    #[test]
+    fn to_ansi_fg_rgb_color() {
+        let style = CellStyle {
+            fg: TermColor::Rgb(255, 128, 64),
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        assert!(style.to_ansi_fg(&mut buf));
+        assert_eq!(buf, "\x1b[38;2;255;128;64m");
+    }
+
+    #[test]
+    fn to_ansi_bg_default_returns_false() {
+        let style = CellStyle::default();
+        let mut buf = String::new();
+        assert!(!style.to_ansi_bg(&mut buf));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn to_ansi_bg_all_basic_colors() {
+        let test_cases = [
+            (TermColor::Black, "\x1b[40m"),
+            (TermColor::Red, "\x1b[41m"),
+            (TermColor::Green, "\x1b[42m"),
+            (TermColor::Yellow, "\x1b[43m"),
+            (TermColor::Blue, "\x1b[44m"),
+            (TermColor::Magenta, "\x1b[45m"),
+            (TermColor::Cyan, "\x1b[46m"),
+            (TermColor::White, "\x1b[47m"),
+        ];
+
+        for (color, expected) in test_cases {
+            let style = CellStyle {
+                bg: color,
+                ..Default::default()
+            };
+            let mut buf = String::new();
+            assert!(style.to_ansi_bg(&mut buf));
+            assert_eq!(buf, expected, "Failed for {:?}", color);
+        }
+    }
+
+    #[test]
+    fn to_ansi_bg_indexed_color() {
+        let style = CellStyle {
+            bg: TermColor::Indexed(236),
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        assert!(style.to_ansi_bg(&mut buf));
+        assert_eq!(buf, "\x1b[48;5;236m");
+    }
+
+    #[test]
+    fn to_ansi_bg_rgb_color() {
+        let style = CellStyle {
+            bg: TermColor::Rgb(0, 128, 255),
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        assert!(style.to_ansi_bg(&mut buf));
+        assert_eq!(buf, "\x1b[48;2;0;128;255m");
+    }
+
+    #[test]
+    fn to_ansi_attrs_bold() {
+        let style = CellStyle {
+            bold: true,
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        style.to_ansi_attrs(&mut buf);
+        assert_eq!(buf, "\x1b[1m");
+    }
+
+    #[test]
+    fn to_ansi_attrs_multiple() {
+        let style = CellStyle {
+            bold: true,
+            italic: true,
+            underline: true,
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        style.to_ansi_attrs(&mut buf);
+        assert_eq!(buf, "\x1b[1m\x1b[3m\x1b[4m");
+    }
+
+    #[test]
+    fn to_ansi_bg_all_bright_colors() {
+        let test_cases = [
+            (TermColor::BrightBlack, "\x1b[100m"),
+            (TermColor::BrightRed, "\x1b[101m"),
+            (TermColor::BrightGreen, "\x1b[102m"),
+            (TermColor::BrightYellow, "\x1b[103m"),
+            (TermColor::BrightBlue, "\x1b[104m"),
+            (TermColor::BrightMagenta, "\x1b[105m"),
+            (TermColor::BrightCyan, "\x1b[106m"),
+            (TermColor::BrightWhite, "\x1b[107m"),
+        ];
+
+        for (color, expected) in test_cases {
+            let style = CellStyle {
+                bg: color,
+                ..Default::default()
+            };
+            let mut buf = String::new();
+            assert!(style.to_ansi_bg(&mut buf));
+            assert_eq!(buf, expected, "Failed for {:?}", color);
+        }
+    }
+
+    #[test]
+    fn to_ansi_indexed_color_bounds() {
+        let style = CellStyle {
+            fg: TermColor::Indexed(0),
+            bg: TermColor::Indexed(255),
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        assert!(style.to_ansi_fg(&mut buf));
+        assert!(style.to_ansi_bg(&mut buf));
+        assert_eq!(buf, "\x1b[38;5;0m\x1b[48;5;255m");
+    }
+
+    #[test]
+    fn to_ansi_rgb_color_bounds() {
+        let style = CellStyle {
+            fg: TermColor::Rgb(0, 0, 0),
+            bg: TermColor::Rgb(255, 255, 255),
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        assert!(style.to_ansi_fg(&mut buf));
+        assert!(style.to_ansi_bg(&mut buf));
+        assert_eq!(buf, "\x1b[38;2;0;0;0m\x1b[48;2;255;255;255m");
+    }
+
+    #[test]
+    fn to_ansi_attrs_each_flag() {
+        let cases = [
+            (
+                CellStyle {
+                    bold: true,
+                    ..Default::default()
+                },
+                "\x1b[1m",
+            ),
+            (
+                CellStyle {
+                    dim: true,
+                    ..Default::default()
+                },
+                "\x1b[2m",
+            ),
+            (
+                CellStyle {
+                    italic: true,
+                    ..Default::default()
+                },
+                "\x1b[3m",
+            ),
+            (
+                CellStyle {
+                    underline: true,
+                    ..Default::default()
+                },
+                "\x1b[4m",
+            ),
+            (
+                CellStyle {
+                    reverse: true,
+                    ..Default::default()
+                },
+                "\x1b[7m",
+            ),
+        ];
+
+        for (style, expected) in cases {
+            let mut buf = String::new();
+            style.to_ansi_attrs(&mut buf);
+            assert_eq!(buf, expected, "Failed for {:?}", style);
+        }
+    }
+
+    #[test]
+    fn to_ansi_attrs_all_flags_in_order() {
+        let style = CellStyle {
+            bold: true,
+            dim: true,
+            italic: true,
+            underline: true,
+            reverse: true,
+            ..Default::default()
+        };
+        let mut buf = String::new();
+        style.to_ansi_attrs(&mut buf);
+        assert_eq!(buf, "\x1b[1m\x1b[2m\x1b[3m\x1b[4m\x1b[7m");
+    }
+
+    #[test]
+    fn to_ansi_attrs_default_is_empty() {
+        let mut buf = String::new();
+        CellStyle::default().to_ansi_attrs(&mut buf);
+        assert!(buf.is_empty());
+    }
+}
@@ -3,6 +3,11 @@
 //! Provides ANSI escape codes for CLI output, color conversion from
 //! ratatui colors, themed text wrappers, and help text colorization.
 
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+
 use ratatui::style::Color;
 
 use super::Theme;
@@ -24,9 +29,97 @@ pub mod ansi {
     pub const RESET: &str = "\x1b[0m";
 }
 
+/// When to emit ANSI colors in CLI output (`--color`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /// Decide whether to color given the environment.
+    ///
+    /// An explicit `always`/`never` wins. In `auto` mode a non-empty
+    /// `NO_COLOR` disables color, a non-empty `FORCE_COLOR` enables it, and
+    /// otherwise color follows whether stdout is a terminal.
+    pub fn resolve(self, no_color: bool, force_color: bool, stdout_is_tty: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && (force_color || stdout_is_tty),
+        }
+    }
+
+    /// Resolve against the current process environment.
+    pub fn enabled(self) -> bool {
+        let env_set = |name| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+        self.resolve(
+            env_set("NO_COLOR"),
+            env_set("FORCE_COLOR"),
+            std::io::stdout().is_terminal(),
+        )
+    }
+}
+
+/// Process-wide color state: 0 = not initialized (colors on), 1 = on, 2 = off.
+static COLOR_STATE: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    /// Per-thread override set by [`with_color_choice`].
+    static COLOR_OVERRIDE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Resolve `choice` once at startup and use it for all CLI output.
+pub fn set_color_choice(choice: ColorChoice) {
+    let state = if choice.enabled() { 1 } else { 2 };
+    COLOR_STATE.store(state, Ordering::Relaxed);
+}
+
+/// Whether CLI output should contain ANSI colors.
+///
+/// Defaults to on until [`set_color_choice`] is called.
+pub fn colors_enabled() -> bool {
+    COLOR_OVERRIDE
+        .with(Cell::get)
+        .unwrap_or_else(|| COLOR_STATE.load(Ordering::Relaxed) != 2)
+}
+
+/// Run `f` with colors forced on or off for the current thread.
+pub fn with_color_choice<R>(choice: ColorChoice, f: impl FnOnce() -> R) -> R {
+    let previous = COLOR_OVERRIDE.with(|o| o.replace(Some(choice.enabled())));
+    let result = f();
+    COLOR_OVERRIDE.with(|o| o.set(previous));
+    result
+}
+
+/// Pass an escape code through, or an empty string when colors are off.
+pub fn ansi_code(code: &'static str) -> &'static str {
+    if colors_enabled() {
+        code
+    } else {
+        ""
+    }
+}
+
+/// The reset sequence, or an empty string when colors are off.
+pub fn ansi_reset() -> &'static str {
+    ansi_code(ANSI_RESET)
+}
+
 /// Convert a ratatui Color to an ANSI escape code.
-pub fn color_to_ansi(color: Color) -> &'static str {
-    match color {
+///
+/// Indexed and RGB colors (e.g. from a custom theme) use the 256-color and
+/// truecolor sequences. Returns an empty string when colors are off.
+pub fn color_to_ansi(color: Color) -> Cow<'static, str> {
+    if !colors_enabled() {
+        return Cow::Borrowed("");
+    }
+    let code = match color {
         Color::Black => "\x1b[30m",
         Color::Red => "\x1b[31m",
         Color::Green => "\x1b[32m",
@@ -44,20 +137,26 @@ pub fn color_to_ansi(color: Color) -> &'static str {
         Color::LightCyan => "\x1b[96m",
         Color::White => "\x1b[97m",
         Color::Reset => "\x1b[0m",
-        // For RGB and indexed colors, fall back to reset (no color)
-        _ => "",
-    }
+        Color::Indexed(index) => return Cow::Owned(format!("\x1b[38;5;{}m", index)),
+        Color::Rgb(r, g, b) => return Cow::Owned(format!("\x1b[38;2;{};{};{}m", r, g, b)),
+    };
+    Cow::Borrowed(code)
 }
 
 impl Theme {
     /// Format text with the accent color (for CLI output).
     pub fn accent_text(&self, text: &str) -> String {
-        format!("{}{}{}", color_to_ansi(self.accent), text, ANSI_RESET)
+        format!("{}{}{}", color_to_ansi(self.accent), text, ansi_reset())
     }
 
     /// Format text with the primary color (for CLI output).
     pub fn primary_text(&self, text: &str) -> String {
-        format!("{}{}{}", color_to_ansi(self.text_primary), text, ANSI_RESET)
+        format!(
+            "{}{}{}",
+            color_to_ansi(self.text_primary),
+            text,
+            ansi_reset()
+        )
     }
 
     /// Format text with the secondary color (for CLI output).
@@ -66,18 +165,18 @@ impl Theme {
             "{}{}{}",
             color_to_ansi(self.text_secondary),
             text,
-            ANSI_RESET
+            ansi_reset()
         )
     }
 
     /// Format text with the error color (for CLI output).
     pub fn error_text(&self, text: &str) -> String {
-        format!("{}{}{}", color_to_ansi(self.error), text, ANSI_RESET)
+        format!("{}{}{}", color_to_ansi(self.error), text, ansi_reset())
     }
 
     /// Format text with the success color (for CLI output).
     pub fn success_text(&self, text: &str) -> String {
-        format!("{}{}{}", color_to_ansi(self.success), text, ANSI_RESET)
+        format!("{}{}{}", color_to_ansi(self.success), text, ansi_reset())
     }
 }
 
@@ -89,6 +188,9 @@ impl Theme {
 ///
 /// This post-processes clap's output to apply consistent theming.
 pub fn colorize_help(text: &str) -> String {
+    if !colors_enabled() {
+        return text.to_string();
+    }
     let theme = super::current_theme();
     let green = color_to_ansi(theme.accent);
     let gray = color_to_ansi(theme.text_primary);
@@ -96,7 +198,7 @@ pub fn colorize_help(text: &str) -> String {
     let reset = ANSI_RESET;
 
     text.lines()
-        .map(|line| colorize_help_line(line, green, gray, dark_gray, reset))
+        .map(|line| colorize_help_line(line, &green, &gray, &dark_gray, reset))
         .collect::<Vec<_>>()
         .join("\n")
 }
@@ -219,4 +321,73 @@ mod tests {
         assert_eq!(color_to_ansi(Color::DarkGray), "\x1b[90m");
         assert_eq!(color_to_ansi(Color::Reset), "\x1b[0m");
     }
+
+    #[test]
+    fn color_to_ansi_maps_indexed_and_rgb_colors() {
+        assert_eq!(color_to_ansi(Color::Indexed(208)), "\x1b[38;5;208m");
+        assert_eq!(
+            color_to_ansi(Color::Rgb(0, 255, 136)),
+            "\x1b[38;2;0;255;136m"
+        );
+    }
+
+    #[test]
+    fn color_to_ansi_is_empty_under_never() {
+        with_color_choice(ColorChoice::Never, || {
+            assert_eq!(color_to_ansi(Color::Green), "");
+            assert_eq!(color_to_ansi(Color::Reset), "");
+            assert_eq!(ansi_reset(), "");
+            assert_eq!(ansi_code(ansi::RED), "");
+        });
+    }
+
+    #[test]
+    fn text_helpers_are_plain_under_never() {
+        let theme = Theme::claude_code();
+        with_color_choice(ColorChoice::Never, || {
+            assert_eq!(theme.accent_text("test"), "test");
+            assert_eq!(theme.primary_text("hello"), "hello");
+            assert_eq!(theme.error_text("bad"), "bad");
+        });
+    }
+
+    #[test]
+    fn colorize_help_has_no_escapes_under_never() {
+        let help = "\u{2588}\u{2588}\u{2557}\n \u{23FA} REC \u{2500}\u{2500}\n    agr list    List all recordings";
+        let output = with_color_choice(ColorChoice::Never, || colorize_help(help));
+        assert!(!output.contains('\x1b'));
+        assert_eq!(output, help);
+    }
+
+    #[test]
+    fn colorize_help_colors_under_always() {
+        let output = with_color_choice(ColorChoice::Always, || {
+            colorize_help("    agr list    List all recordings")
+        });
+        assert!(output.contains('\x1b'));
+    }
+
+    #[test]
+    fn with_color_choice_restores_previous_state() {
+        with_color_choice(ColorChoice::Never, || {
+            with_color_choice(ColorChoice::Always, || assert!(colors_enabled()));
+            assert!(!colors_enabled());
+        });
+    }
+
+    #[test]
+    fn resolve_explicit_choices_ignore_environment() {
+        assert!(ColorChoice::Always.resolve(true, false, false));
+        assert!(!ColorChoice::Never.resolve(false, true, true));
+    }
+
+    #[test]
+    fn resolve_auto_follows_tty_and_env() {
+        assert!(ColorChoice::Auto.resolve(false, false, true));
+        assert!(!ColorChoice::Auto.resolve(false, false, false));
+        assert!(!ColorChoice::Auto.resolve(true, false, true));
+        assert!(ColorChoice::Auto.resolve(false, true, false));
+        // NO_COLOR beats FORCE_COLOR
+        assert!(!ColorChoice::Auto.resolve(true, true, true));
+    }
 }
@@ -3,9 +3,12 @@
 //! Logos are embedded at compile time from the assets directory.
 //! Uses the theme system for consistent colors across TUI and CLI.
 
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use unicode_width::UnicodeWidthStr;
 
-use super::cli::{color_to_ansi, ANSI_RESET};
+use super::cli::{ansi_code, ansi_reset, color_to_ansi};
 use super::current_theme;
 use super::Theme;
 
@@ -24,17 +27,157 @@ pub const BOX_WIDTH: usize = 39;
 /// Bottom border of the box
 pub const BOX_BOTTOM: &str = "╚═══════════════════════════════════════╝";
 
+/// Environment variable that suppresses banners when non-empty
+pub const NO_BANNER_ENV: &str = "AGR_NO_BANNER";
+
+/// Process-wide switch set from `--no-banner` / `AGR_NO_BANNER`.
+static BANNERS_OFF: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Per-thread override set by [`with_banners`].
+    static BANNER_OVERRIDE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Whether banners are suppressed by the flag or a non-empty `AGR_NO_BANNER`.
+pub fn no_banner_requested(flag: bool) -> bool {
+    flag || std::env::var_os(NO_BANNER_ENV).is_some_and(|v| !v.is_empty())
+}
+
+/// Turn the banners, recording box and full logo on or off for all output.
+pub fn set_banners_enabled(enabled: bool) {
+    BANNERS_OFF.store(!enabled, Ordering::Relaxed);
+}
+
+/// Whether banners are printed.
+pub fn banners_enabled() -> bool {
+    BANNER_OVERRIDE
+        .with(|o| o.get())
+        .unwrap_or_else(|| !BANNERS_OFF.load(Ordering::Relaxed))
+}
+
+/// Run `f` with banners forced on or off for the current thread.
+pub fn with_banners<R>(enabled: bool, f: impl FnOnce() -> R) -> R {
+    let previous = BANNER_OVERRIDE.with(|o| o.replace(Some(enabled)));
+    let result = f();
+    BANNER_OVERRIDE.with(|o| o.set(previous));
+    result
+}
+
+/// Narrowest box inner width; terminals narrower than this wrap the box.
+pub const MIN_BOX_WIDTH: usize = 12;
+
+/// Box size chosen for the terminal the banners are printed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoxLayout {
+    /// Inner content width (excluding the two border columns)
+    pub width: usize,
+    /// Whether the banners use the one-line compact title instead of the
+    /// boxed logo assets (which are `BOX_WIDTH` wide)
+    pub compact: bool,
+}
+
+impl BoxLayout {
+    /// Pick the box for a terminal `columns` wide (`None` if unknown).
+    ///
+    /// The full-size box is used when it fits or the width is unknown.
+    /// Otherwise the box shrinks to the terminal, down to [`MIN_BOX_WIDTH`].
+    pub fn for_terminal(columns: Option<u16>) -> Self {
+        match columns.map(usize::from) {
+            Some(columns) if columns < BOX_WIDTH + 2 => Self {
+                width: columns.saturating_sub(2).max(MIN_BOX_WIDTH),
+                compact: true,
+            },
+            _ => Self {
+                width: BOX_WIDTH,
+                compact: false,
+            },
+        }
+    }
+
+    /// The box for the current terminal.
+    pub fn current() -> Self {
+        Self::for_terminal(terminal_columns())
+    }
+
+    /// Bottom border at this width.
+    pub fn bottom(&self) -> String {
+        format!("╚{}╝", "═".repeat(self.width))
+    }
+
+    /// A content line padded (by display width) or truncated to fit.
+    pub fn line(&self, content: &str) -> String {
+        format!(
+            "║{}║",
+            pad_to_width(&truncate_str(content, self.width), self.width)
+        )
+    }
+
+    /// Compact banner: top border, `title` centered, separator.
+    pub fn compact_banner(&self, title: &str) -> String {
+        let title = truncate_str(title, self.width);
+        let left = (self.width - title.width()) / 2;
+        let border = "═".repeat(self.width);
+        format!(
+            "╔{}╗\n║{}║\n╠{}╣\n",
+            border,
+            pad_to_width(&format!("{}{}", " ".repeat(left), title), self.width),
+            border
+        )
+    }
+}
+
+/// Whether the block-letter logo fits in a terminal `columns` wide
+/// (`None` if unknown, which assumes it does).
+pub fn full_logo_fits(columns: Option<u16>) -> bool {
+    let logo_width = LOGO_FULL.lines().map(|l| l.width()).max().unwrap_or(0);
+    columns.map_or(true, |columns| usize::from(columns) >= logo_width)
+}
+
+fn terminal_columns() -> Option<u16> {
+    crossterm::terminal::size().ok().map(|(columns, _)| columns)
+}
+
+/// Pad `s` with spaces to `width` display columns.
+fn pad_to_width(s: &str, width: usize) -> String {
+    format!("{}{}", s, " ".repeat(width.saturating_sub(s.width())))
+}
+
 /// Print the start banner with theme colors.
 pub fn print_start_banner() {
-    let theme = current_theme();
-    let colored = colorize_recording_banner(LOGO_START, &theme);
-    print!("{}", colored);
+    print!("{}", start_banner());
+}
+
+/// The colored start banner, or an empty string when banners are off.
+pub fn start_banner() -> String {
+    if !banners_enabled() {
+        return String::new();
+    }
+    let layout = BoxLayout::current();
+    let banner = if layout.compact {
+        layout.compact_banner("AGR ⏺ REC")
+    } else {
+        LOGO_START.to_string()
+    };
+    colorize_recording_banner(&banner, &current_theme())
 }
 
 /// Print the done banner with theme colors.
 pub fn print_done_banner() {
-    let theme = current_theme();
-    print!("{}", theme.success_text(LOGO_DONE));
+    print!("{}", done_banner());
+}
+
+/// The colored done banner, or an empty string when banners are off.
+pub fn done_banner() -> String {
+    if !banners_enabled() {
+        return String::new();
+    }
+    let layout = BoxLayout::current();
+    let banner = if layout.compact {
+        layout.compact_banner("AGR ■ DONE")
+    } else {
+        LOGO_DONE.to_string()
+    };
+    current_theme().success_text(&banner)
 }
 
 /// Colorize the recording banner with bold REC.
@@ -45,11 +188,11 @@ fn colorize_recording_banner(text: &str, theme: &Theme) -> String {
 
     for line in text.lines() {
         if line.contains("REC") {
-            colorize_rec_banner_line(line, accent, &mut result);
+            colorize_rec_banner_line(line, &accent, &mut result);
         } else {
-            result.push_str(accent);
+            result.push_str(&accent);
             result.push_str(line);
-            result.push_str(ANSI_RESET);
+            result.push_str(ansi_reset());
             result.push('\n');
         }
     }
@@ -59,7 +202,7 @@ fn colorize_recording_banner(text: &str, theme: &Theme) -> String {
 
 /// Colorize a single line that contains "REC" with bold formatting.
 fn colorize_rec_banner_line(line: &str, accent: &str, result: &mut String) {
-    const BOLD: &str = "\x1b[1m";
+    let bold = ansi_code("\x1b[1m");
     let mut chars = line.chars().peekable();
     let mut in_rec = false;
 
@@ -69,9 +212,9 @@ fn colorize_rec_banner_line(line: &str, accent: &str, result: &mut String) {
         if c == 'R' && !in_rec {
             let rest: String = chars.clone().take(2).collect();
             if rest == "EC" {
-                result.push_str(BOLD);
+                result.push_str(bold);
                 result.push_str("REC");
-                result.push_str(ANSI_RESET);
+                result.push_str(ansi_reset());
                 result.push_str(accent);
                 chars.next(); // skip E
                 chars.next(); // skip C
@@ -83,48 +226,79 @@ fn colorize_rec_banner_line(line: &str, accent: &str, result: &mut String) {
             result.push(c);
         }
     }
-    result.push_str(ANSI_RESET);
+    result.push_str(ansi_reset());
     result.push('\n');
 }
 
 /// Print the full logo with theme colors.
 pub fn print_full_logo() {
+    print!("{}", full_logo());
+}
+
+/// The colored full logo, a one-line title if the terminal is too narrow
+/// for the block letters, or an empty string when banners are off.
+pub fn full_logo() -> String {
+    if !banners_enabled() {
+        return String::new();
+    }
     let theme = current_theme();
-    print!("{}", theme.accent_text(LOGO_FULL));
+    if full_logo_fits(terminal_columns()) {
+        theme.accent_text(LOGO_FULL)
+    } else {
+        format!("{}\n", theme.accent_text("AGR ⏺ REC"))
+    }
 }
 
 /// Print a line inside the box, padded to fit (with accent color).
 pub fn print_box_line(content: &str) {
-    let theme = current_theme();
-    let truncated = truncate_str(content, BOX_WIDTH);
-    println!(
-        "{}",
-        theme.accent_text(&format!("║{:width$}║", truncated, width = BOX_WIDTH))
-    );
+    println!("{}", box_line(content));
+}
+
+/// A colored box line, or just the trimmed content when banners are off
+/// (the box has no header to belong to then).
+pub fn box_line(content: &str) -> String {
+    if !banners_enabled() {
+        return content.trim_start().to_string();
+    }
+    current_theme().accent_text(&BoxLayout::current().line(content))
 }
 
 /// Print the bottom border of the box (with accent color).
+///
+/// Prints nothing when banners are off.
 pub fn print_box_bottom() {
-    let theme = current_theme();
-    println!("{}", theme.accent_text(BOX_BOTTOM));
+    if banners_enabled() {
+        let theme = current_theme();
+        println!("{}", theme.accent_text(&BoxLayout::current().bottom()));
+    }
 }
 
 /// Print a prompt line inside the box (no trailing border).
+///
+/// Without banners the prompt is printed as-is.
 pub fn print_box_prompt(content: &str) {
+    if !banners_enabled() {
+        print!("{}", content.trim_start());
+        return;
+    }
     let theme = current_theme();
+    let width = BoxLayout::current().width;
     print!(
-        "{}║{:width$}",
+        "{}║{}",
         color_to_ansi(theme.accent),
-        content,
-        width = BOX_WIDTH
+        pad_to_width(content, width)
     );
-    print!("{}", ANSI_RESET);
+    print!("{}", ansi_reset());
 }
 
 /// Print just the closing border character (after user input).
+///
+/// Prints nothing when banners are off.
 pub fn print_box_line_end() {
-    let theme = current_theme();
-    println!("{}", theme.accent_text("║"));
+    if banners_enabled() {
+        let theme = current_theme();
+        println!("{}", theme.accent_text("║"));
+    }
 }
 
 /// Truncate a string to fit within max_width display columns, adding ellipsis if needed.
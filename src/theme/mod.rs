@@ -4,21 +4,32 @@
 //! Provides ratatui styles (for TUI), ANSI escape codes (for CLI),
 //! and logo/banner assets (for branding).
 
+use std::sync::RwLock;
+
 use ratatui::style::Color;
 
 pub mod cli;
 pub mod logo;
+pub mod player;
 pub mod tui;
 
 // Re-exports from cli.rs
 pub use cli::ansi;
-pub use cli::{color_to_ansi, colorize_help, ANSI_RESET};
+pub use cli::{
+    ansi_code, ansi_reset, color_to_ansi, colorize_help, colors_enabled, set_color_choice,
+    with_color_choice, ColorChoice, ANSI_RESET,
+};
+
+// Re-exports from player.rs
+pub use player::PlayerColors;
 
 // Re-exports from logo.rs
 pub use logo::{
+    banners_enabled, box_line, done_banner, full_logo, full_logo_fits, no_banner_requested,
     print_box_bottom, print_box_line, print_box_line_end, print_box_prompt, print_done_banner,
-    print_full_logo, print_start_banner, truncate_str, BOX_BOTTOM, BOX_WIDTH, LOGO_DONE, LOGO_FULL,
-    LOGO_START,
+    print_full_logo, print_start_banner, set_banners_enabled, start_banner, truncate_str,
+    with_banners, BoxLayout, BOX_BOTTOM, BOX_WIDTH, LOGO_DONE, LOGO_FULL, LOGO_START,
+    MIN_BOX_WIDTH, NO_BANNER_ENV,
 };
 
 /// Theme configuration for the TUI and CLI.
@@ -36,6 +47,8 @@ pub struct Theme {
     pub error: Color,
     /// Success color
     pub success: Color,
+    /// Marker color (player progress bar)
+    pub marker: Color,
     /// Background color (usually default/transparent)
     pub background: Color,
 }
@@ -47,6 +60,19 @@ impl Default for Theme {
 }
 
 impl Theme {
+    /// Names of the built-in presets, as used by `[theme] name`.
+    pub const NAMES: &'static [&'static str] = &["claude_code", "classic", "ocean"];
+
+    /// Look up a built-in preset by name.
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "claude_code" => Some(Self::claude_code()),
+            "classic" => Some(Self::classic()),
+            "ocean" => Some(Self::ocean()),
+            _ => None,
+        }
+    }
+
     /// AGR theme - light gray text with green logo accent.
     /// Uses standard ANSI colors for consistent terminal rendering.
     pub fn claude_code() -> Self {
@@ -56,6 +82,7 @@ impl Theme {
             accent: Color::LightGreen,
             error: Color::Red,
             success: Color::LightGreen,
+            marker: Color::Yellow,
             background: Color::Reset,
         }
     }
@@ -68,6 +95,7 @@ impl Theme {
             accent: Color::Yellow,
             error: Color::Red,
             success: Color::Green,
+            marker: Color::LightMagenta,
             background: Color::Reset,
         }
     }
@@ -80,16 +108,50 @@ impl Theme {
             accent: Color::LightCyan,
             error: Color::Red,
             success: Color::Green,
+            marker: Color::Yellow,
             background: Color::Reset,
         }
     }
 }
 
-/// Global theme instance.
+/// Theme in use, loaded from config on first access.
+static CURRENT_THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// The theme selected by the `[theme]` config section.
 ///
-/// In the future, this could be loaded from config.
+/// The config is read once and the result kept for the process. If it
+/// cannot be loaded the default theme is used; the command reports the
+/// config error itself when it loads the config.
 pub fn current_theme() -> Theme {
-    Theme::default()
+    if let Some(theme) = CURRENT_THEME.read().ok().and_then(|t| t.clone()) {
+        return theme;
+    }
+    let theme = crate::config::Config::load()
+        .map(|config| config.theme.theme())
+        .unwrap_or_default();
+    set_current_theme(theme.clone());
+    theme
+}
+
+/// Use `theme` for the rest of the process.
+pub fn set_current_theme(theme: Theme) {
+    if let Ok(mut current) = CURRENT_THEME.write() {
+        *current = Some(theme);
+    }
+}
+
+/// Forget the loaded theme so the next access reads the config again
+/// (e.g. after `--config` or `--profile` changed which config applies).
+pub fn reload_current_theme() {
+    if let Ok(mut current) = CURRENT_THEME.write() {
+        *current = None;
+    }
+}
+
+/// Parse a color name (`"cyan"`, `"light-green"`), 256-color index
+/// (`"208"`) or hex RGB value (`"#00ff88"`).
+pub fn parse_color(value: &str) -> Option<Color> {
+    value.trim().parse().ok()
 }
 
 #[cfg(test)]
@@ -109,6 +171,40 @@ mod tests {
         assert_eq!(theme.text_primary, Color::White);
     }
 
+    #[test]
+    fn by_name_finds_every_preset() {
+        for name in Theme::NAMES {
+            assert!(Theme::by_name(name).is_some(), "{}", name);
+        }
+        assert_eq!(
+            Theme::by_name("ocean").unwrap().text_primary,
+            Theme::ocean().text_primary
+        );
+        assert!(Theme::by_name("solarized").is_none());
+    }
+
+    #[test]
+    fn parse_color_accepts_names() {
+        assert_eq!(parse_color("cyan"), Some(Color::Cyan));
+        assert_eq!(parse_color("Light-Green"), Some(Color::LightGreen));
+        assert_eq!(parse_color("dark_grey"), Some(Color::DarkGray));
+        assert_eq!(parse_color(" reset "), Some(Color::Reset));
+    }
+
+    #[test]
+    fn parse_color_accepts_hex_and_index() {
+        assert_eq!(parse_color("#00ff88"), Some(Color::Rgb(0, 255, 136)));
+        assert_eq!(parse_color("#FFA500"), Some(Color::Rgb(255, 165, 0)));
+        assert_eq!(parse_color("208"), Some(Color::Indexed(208)));
+    }
+
+    #[test]
+    fn parse_color_rejects_invalid_values() {
+        for value in ["", "#00ff8", "#gg0000", "00ff88", "256", "chartreuse"] {
+            assert_eq!(parse_color(value), None, "{:?}", value);
+        }
+    }
+
     #[test]
     fn ocean_theme_uses_cyan() {
         let theme = Theme::ocean();
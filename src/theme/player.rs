@@ -0,0 +1,97 @@
+//! ANSI colors for the native player's chrome
+//!
+//! The player writes escape codes directly rather than going through
+//! ratatui, so the theme is resolved to ANSI strings once per session.
+
+use std::borrow::Cow;
+
+use super::{color_to_ansi, Theme};
+
+/// Escape codes for the separator, progress bar and status bar.
+///
+/// Strings are empty when colors are off (see [`color_to_ansi`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerColors {
+    /// Played part of the progress bar
+    pub progress: Cow<'static, str>,
+    /// Marker diamonds on the progress bar and the marker count
+    pub marker: Cow<'static, str>,
+    /// Playhead, time display and status values
+    pub text: Cow<'static, str>,
+    /// Unplayed track, separator line and status labels
+    pub dim: Cow<'static, str>,
+    /// Key hints in the status bar and the viewport mode badge
+    pub key: Cow<'static, str>,
+    /// Free mode badge
+    pub free_mode: Cow<'static, str>,
+}
+
+impl PlayerColors {
+    /// Resolve the player colors of `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            progress: color_to_ansi(theme.accent),
+            marker: color_to_ansi(theme.marker),
+            text: color_to_ansi(theme.text_primary),
+            dim: color_to_ansi(theme.text_secondary),
+            key: color_to_ansi(theme.accent),
+            free_mode: color_to_ansi(theme.success),
+        }
+    }
+}
+
+impl Default for PlayerColors {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{with_color_choice, ColorChoice};
+
+    #[test]
+    fn claude_code_theme_maps_to_ansi() {
+        let colors = with_color_choice(ColorChoice::Always, || {
+            PlayerColors::from_theme(&Theme::claude_code())
+        });
+        assert_eq!(
+            colors,
+            PlayerColors {
+                progress: "\x1b[92m".into(),
+                marker: "\x1b[33m".into(),
+                text: "\x1b[37m".into(),
+                dim: "\x1b[90m".into(),
+                key: "\x1b[92m".into(),
+                free_mode: "\x1b[92m".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn classic_and_ocean_themes_map_to_ansi() {
+        let (classic, ocean) = with_color_choice(ColorChoice::Always, || {
+            (
+                PlayerColors::from_theme(&Theme::classic()),
+                PlayerColors::from_theme(&Theme::ocean()),
+            )
+        });
+        assert_eq!(classic.progress, "\x1b[33m");
+        assert_eq!(classic.marker, "\x1b[95m");
+        assert_eq!(classic.text, "\x1b[97m");
+        assert_eq!(ocean.progress, "\x1b[96m");
+        assert_eq!(ocean.text, "\x1b[36m");
+        assert_eq!(ocean.free_mode, "\x1b[32m");
+    }
+
+    #[test]
+    fn colors_are_empty_when_disabled() {
+        let colors = with_color_choice(ColorChoice::Never, || {
+            PlayerColors::from_theme(&Theme::ocean())
+        });
+        assert_eq!(colors.progress, "");
+        assert_eq!(colors.marker, "");
+        assert_eq!(colors.dim, "");
+    }
+}
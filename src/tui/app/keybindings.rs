@@ -16,7 +16,7 @@ use super::shared_state::SharedState;
 pub enum SharedMode {
     /// Normal browsing mode
     Normal,
-    /// Search mode -- typing filters by filename
+    /// Search mode -- typing fuzzy-filters by filename, agent or title
     Search,
     /// Agent filter mode -- cycling through agents
     AgentFilter,
@@ -59,12 +59,16 @@ pub fn handle_shared_key(mode: &SharedMode, key: KeyEvent, state: &mut SharedSta
 
 /// Handle keys in search mode.
 ///
-/// Enter commits the search filter, Escape cancels, Backspace deletes
+/// Enter commits the search filter, Escape clears it, Backspace deletes
 /// a character, and printable characters append to the search input.
-/// All keystrokes perform live filtering as the user types.
+/// All keystrokes perform live (fuzzy) filtering as the user types.
 fn handle_search_key(key: KeyEvent, state: &mut SharedState) -> KeyResult {
     match key.code {
-        KeyCode::Esc => KeyResult::EnterMode(SharedMode::Normal),
+        KeyCode::Esc => {
+            state.search_input.clear();
+            apply_search_filter(state);
+            KeyResult::EnterMode(SharedMode::Normal)
+        }
         KeyCode::Enter => {
             apply_search_filter(state);
             KeyResult::EnterMode(SharedMode::Normal)
@@ -163,6 +167,16 @@ fn handle_normal_navigation(key: KeyEvent, state: &mut SharedState) -> KeyResult
             KeyResult::Consumed
         }
 
+        // Preview scrubbing
+        KeyCode::Left => {
+            state.scrub_preview(false);
+            KeyResult::Consumed
+        }
+        KeyCode::Right => {
+            state.scrub_preview(true);
+            KeyResult::Consumed
+        }
+
         // Mode transitions (shared across both apps)
         KeyCode::Char('/') => {
             state.search_input.clear();
@@ -196,7 +210,7 @@ fn handle_normal_navigation(key: KeyEvent, state: &mut SharedState) -> KeyResult
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tui::widgets::FileItem;
+    use crate::tui::widgets::{FileItem, PREVIEW_STEPS};
     use chrono::Local;
 
     fn make_state(agents: &[&str]) -> SharedState {
@@ -210,7 +224,9 @@ mod tests {
                 size: 1024,
                 modified: Local::now(),
                 has_backup: false,
+                has_notes: false,
                 lock_info: None,
+                title: None,
             })
             .collect();
         SharedState::new(items, None)
@@ -253,6 +269,27 @@ mod tests {
         assert!(debug.contains("AgentFilter"));
     }
 
+    // --- Preview scrubbing tests ---
+
+    #[test]
+    fn normal_right_and_left_scrub_preview_within_bounds() {
+        let mut state = make_state(&["claude"]);
+        let start = state.preview_step;
+        let result = handle_shared_key(&SharedMode::Normal, key(KeyCode::Right), &mut state);
+        assert_eq!(result, KeyResult::Consumed);
+        assert_eq!(state.preview_step, start + 1);
+
+        for _ in 0..10 {
+            handle_shared_key(&SharedMode::Normal, key(KeyCode::Right), &mut state);
+        }
+        assert_eq!(state.preview_step, PREVIEW_STEPS.len() - 1);
+
+        for _ in 0..10 {
+            handle_shared_key(&SharedMode::Normal, key(KeyCode::Left), &mut state);
+        }
+        assert_eq!(state.preview_step, 0);
+    }
+
     // --- Search mode tests ---
 
     #[test]
@@ -262,6 +299,16 @@ mod tests {
         assert_eq!(result, KeyResult::EnterMode(SharedMode::Normal));
     }
 
+    #[test]
+    fn search_esc_clears_live_filter() {
+        let mut state = make_state(&["claude"]);
+        handle_shared_key(&SharedMode::Search, key(KeyCode::Char('x')), &mut state);
+        assert_eq!(state.explorer.search_filter(), Some("x"));
+        handle_shared_key(&SharedMode::Search, key(KeyCode::Esc), &mut state);
+        assert!(state.search_input.is_empty());
+        assert_eq!(state.explorer.search_filter(), None);
+    }
+
     #[test]
     fn search_enter_applies_filter_and_returns_normal() {
         let mut state = make_state(&["claude"]);
@@ -13,6 +13,7 @@ use crate::tui::widgets::{FileExplorer, FileExplorerWidget, SessionPreview};
 /// Both `list_app` and `cleanup_app` use this with different flag combinations:
 /// - `show_checkboxes`: true for cleanup (multi-select), false for list
 /// - `has_backup`: true when the selected file has a backup (list only)
+/// - `preview_step`: preview position, an index into `PREVIEW_STEPS`
 pub fn render_explorer_list(
     frame: &mut Frame,
     area: Rect,
@@ -20,10 +21,12 @@ pub fn render_explorer_list(
     preview: Option<&SessionPreview>,
     show_checkboxes: bool,
     has_backup: bool,
+    preview_step: usize,
 ) {
     let widget = FileExplorerWidget::new(explorer)
         .show_checkboxes(show_checkboxes)
         .session_preview(preview)
-        .has_backup(has_backup);
+        .has_backup(has_backup)
+        .preview_step(preview_step);
     frame.render_widget(widget, area);
 }
@@ -3,12 +3,17 @@
 //! Contains the fields that are common across all TUI explorer apps
 //! (search input, agent filter, explorer, status message, preview cache).
 
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use crate::config::Config;
-use crate::storage::StorageManager;
+use anyhow::{Context, Result};
+
+use crate::config::{Config, TuiConfig};
+use crate::storage::{SessionInfo, StorageManager};
 use crate::tui::lru_cache::{new_preview_cache, PreviewCache};
-use crate::tui::widgets::{FileExplorer, FileItem, SessionPreview};
+use crate::tui::widgets::{
+    FileExplorer, FileItem, SessionPreview, DEFAULT_PREVIEW_STEP, PREVIEW_STEPS,
+};
 
 /// Shared state fields used by all TUI explorer applications.
 ///
@@ -31,6 +36,8 @@ pub struct SharedState {
     pub storage: Option<StorageManager>,
     /// Tracks when locks/files were last refreshed
     pub last_lock_refresh: Instant,
+    /// Terminal preview position (index into `PREVIEW_STEPS`)
+    pub preview_step: usize,
 }
 
 impl SharedState {
@@ -38,6 +45,10 @@ impl SharedState {
     ///
     /// Collects unique agent names from the items and prepends "All".
     pub fn new(items: Vec<FileItem>, config: Option<Config>) -> Self {
+        let cache_size = config
+            .as_ref()
+            .map(|config| config.tui.preview_cache_capacity())
+            .unwrap_or_else(|| TuiConfig::default().preview_cache_capacity());
         let storage = config.map(StorageManager::new);
 
         let mut available_agents: Vec<String> = vec!["All".to_string()];
@@ -47,7 +58,7 @@ impl SharedState {
         available_agents.extend(agents);
 
         let explorer = FileExplorer::new(items);
-        let mut preview_cache = new_preview_cache();
+        let mut preview_cache = new_preview_cache(cache_size);
 
         // Synchronously load the first preview so it's available on
         // the very first draw() — no async round-trip needed.
@@ -66,6 +77,7 @@ impl SharedState {
             preview_cache,
             storage,
             last_lock_refresh: Instant::now(),
+            preview_step: DEFAULT_PREVIEW_STEP,
         }
     }
 
@@ -81,6 +93,72 @@ impl SharedState {
         }
     }
 
+    /// Move the terminal preview one step earlier or later.
+    pub fn scrub_preview(&mut self, forward: bool) {
+        self.preview_step = if forward {
+            (self.preview_step + 1).min(PREVIEW_STEPS.len() - 1)
+        } else {
+            self.preview_step.saturating_sub(1)
+        };
+    }
+
+    /// Drop a deleted session from the explorer and the preview cache.
+    ///
+    /// The explorer keeps the selection on the same row (clamped to the new
+    /// end of the list) so the next session is selected after a delete.
+    pub fn remove_deleted(&mut self, path: &str) {
+        self.explorer.remove_item(path);
+        self.preview_cache.invalidate(&path.to_string());
+        self.update_available_agents();
+    }
+
+    /// Delete the selected session through [`StorageManager::delete_sessions`].
+    ///
+    /// The recording and its sidecars go to the trash unless
+    /// `[storage] use_trash` is off. The session is then dropped from the
+    /// explorer. Returns whether it was moved to the trash.
+    pub fn delete_selected(&mut self) -> Result<bool> {
+        let storage = self
+            .storage
+            .as_ref()
+            .context("Deleting requires a loaded config")?;
+        let item = self
+            .explorer
+            .selected_item()
+            .context("No session selected")?;
+        let path = item.path.clone();
+        let session = SessionInfo::from(item);
+
+        storage.delete_sessions(std::slice::from_ref(&session))?;
+        let trashed = storage.trash().is_some();
+        self.remove_deleted(&path);
+        Ok(trashed)
+    }
+
+    /// Rename the selected session on disk and update the explorer in place.
+    ///
+    /// `new_name` is passed to [`StorageManager::rename_session`]. Preview
+    /// cache entries for both the old and new path are invalidated so the
+    /// preview is reloaded from the renamed file. Returns the new path.
+    pub fn rename_selected(&mut self, new_name: &str) -> Result<PathBuf> {
+        let storage = self
+            .storage
+            .as_ref()
+            .context("Renaming requires a loaded config")?;
+        let old_path = self
+            .explorer
+            .selected_item()
+            .map(|item| item.path.clone())
+            .context("No session selected")?;
+
+        let new_path = storage.rename_session(Path::new(&old_path), new_name)?;
+        let new_path_str = new_path.to_string_lossy().to_string();
+        self.explorer.update_item_path(&old_path, &new_path_str);
+        self.preview_cache.invalidate(&old_path);
+        self.preview_cache.invalidate(&new_path_str);
+        Ok(new_path)
+    }
+
     /// Periodic tick handler — refreshes lock states and file list.
     ///
     /// Called on every `Event::Tick` from the shared event loop.
@@ -140,3 +218,197 @@ impl SharedState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CAST: &str = "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n[0.1,\"o\",\"hi\"]\n";
+
+    /// State over three recordings in `<tmp>/claude`, with a loaded config.
+    fn state_with_files(dir: &TempDir, names: &[&str]) -> SharedState {
+        let agent_dir = dir.path().join("claude");
+        fs::create_dir_all(&agent_dir).unwrap();
+        for name in names {
+            fs::write(agent_dir.join(name), CAST).unwrap();
+        }
+        let mut config = Config::default();
+        config.storage.directory = dir.path().to_string_lossy().to_string();
+        let storage = StorageManager::new(config.clone());
+        let items = storage
+            .list_sessions(None)
+            .unwrap()
+            .into_iter()
+            .map(FileItem::from)
+            .collect();
+        SharedState::new(items, Some(config))
+    }
+
+    fn selected_path(state: &SharedState) -> String {
+        state.explorer.selected_item().unwrap().path.clone()
+    }
+
+    #[test]
+    fn remove_deleted_keeps_selection_on_same_row() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast", "b.cast", "c.cast"]);
+        state.explorer.down();
+        let deleted = selected_path(&state);
+        state.remove_deleted(&deleted);
+
+        assert_eq!(state.explorer.len(), 2);
+        assert_eq!(state.explorer.selected(), 1);
+        assert_ne!(selected_path(&state), deleted);
+    }
+
+    #[test]
+    fn remove_deleted_clamps_selection_at_end() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast", "b.cast", "c.cast"]);
+        state.explorer.end();
+        let deleted = selected_path(&state);
+        state.remove_deleted(&deleted);
+
+        assert_eq!(state.explorer.selected(), 1);
+    }
+
+    #[test]
+    fn remove_deleted_last_item_leaves_empty_list() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast"]);
+        let deleted = selected_path(&state);
+        state.remove_deleted(&deleted);
+
+        assert!(state.explorer.is_empty());
+        assert!(state.explorer.selected_item().is_none());
+        assert!(state.explorer.empty_message().is_some());
+        assert_eq!(state.available_agents, vec!["All".to_string()]);
+    }
+
+    #[test]
+    fn remove_deleted_invalidates_preview() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast", "b.cast"]);
+        let path = selected_path(&state);
+        // The first preview is loaded synchronously on construction
+        assert!(state.preview_cache.get(&path).is_some());
+
+        state.remove_deleted(&path);
+        assert!(state.preview_cache.get(&path).is_none());
+    }
+
+    /// Point the state's storage at a trash in `<tmp>/trash`.
+    fn with_trash(state: &mut SharedState, dir: &TempDir, use_trash: bool) {
+        let mut config = Config::default();
+        config.storage.directory = dir.path().to_string_lossy().to_string();
+        config.storage.use_trash = use_trash;
+        state.storage =
+            Some(StorageManager::new(config).with_trash_dir(Some(dir.path().join("trash"))));
+    }
+
+    #[test]
+    fn delete_selected_moves_recording_and_sidecars_to_trash() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast", "b.cast"]);
+        with_trash(&mut state, &dir, true);
+        let path = selected_path(&state);
+        let bookmarks = format!("{}.bookmarks", path);
+        fs::write(&bookmarks, "[]").unwrap();
+
+        assert!(state.delete_selected().unwrap());
+
+        assert!(!Path::new(&path).exists());
+        assert!(!Path::new(&bookmarks).exists());
+        let name = Path::new(&path).file_name().unwrap();
+        assert!(dir.path().join("trash").join(name).exists());
+        assert_eq!(state.explorer.len(), 1);
+    }
+
+    #[test]
+    fn delete_selected_without_use_trash_removes_sidecars() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast"]);
+        with_trash(&mut state, &dir, false);
+        let path = selected_path(&state);
+        let tags = format!("{}.tags", path);
+        fs::write(&tags, "x").unwrap();
+
+        assert!(!state.delete_selected().unwrap());
+
+        assert!(!Path::new(&path).exists());
+        assert!(!Path::new(&tags).exists());
+        assert!(!dir.path().join("trash").exists());
+        assert!(state.explorer.is_empty());
+    }
+
+    #[test]
+    fn rename_selected_moves_file_and_updates_item() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast"]);
+        let old = selected_path(&state);
+
+        let new_path = state.rename_selected("renamed").unwrap();
+
+        assert!(!Path::new(&old).exists());
+        assert!(new_path.exists());
+        let item = state.explorer.selected_item().unwrap();
+        assert_eq!(item.name, "renamed.cast");
+        assert_eq!(item.path, new_path.to_string_lossy());
+    }
+
+    #[test]
+    fn rename_selected_invalidates_preview_cache() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast"]);
+        let old = selected_path(&state);
+        assert!(state.preview_cache.get(&old).is_some());
+
+        let new_path = state.rename_selected("renamed.cast").unwrap();
+
+        assert!(state.preview_cache.get(&old).is_none());
+        let new_key = new_path.to_string_lossy().to_string();
+        assert!(state.preview_cache.get(&new_key).is_none());
+    }
+
+    #[test]
+    fn rename_selected_rejects_collision_and_keeps_state() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast", "b.cast"]);
+        let old = selected_path(&state);
+        let other = if old.ends_with("a.cast") { "b" } else { "a" };
+
+        assert!(state.rename_selected(other).is_err());
+        assert_eq!(selected_path(&state), old);
+        assert!(state.preview_cache.get(&old).is_some());
+    }
+
+    #[test]
+    fn rename_selected_without_config_fails() {
+        let mut state = SharedState::new(vec![], None);
+        assert!(state.rename_selected("x").is_err());
+    }
+
+    #[test]
+    fn preview_cache_capacity_comes_from_config() {
+        let mut config = Config::default();
+        config.tui.preview_cache_size = 50;
+        assert_eq!(
+            SharedState::new(vec![], Some(config))
+                .preview_cache
+                .capacity(),
+            50
+        );
+
+        let mut config = Config::default();
+        config.tui.preview_cache_size = 0;
+        assert_eq!(
+            SharedState::new(vec![], Some(config))
+                .preview_cache
+                .capacity(),
+            crate::config::MIN_PREVIEW_CACHE_SIZE
+        );
+        assert_eq!(SharedState::new(vec![], None).preview_cache.capacity(), 20);
+    }
+}
@@ -23,6 +23,7 @@ use super::app::{handle_shared_key, App, KeyResult, SharedMode, SharedState, Tui
 use super::widgets::preview::prefetch_adjacent_previews;
 use super::widgets::FileItem;
 use crate::config::Config;
+use crate::storage::{SessionInfo, StorageManager};
 use crate::theme::current_theme;
 
 /// UI mode for the cleanup application
@@ -104,7 +105,7 @@ impl CleanupApp {
     ///
     /// Navigation (up/down/pgup/pgdn/home/end) and mode transitions
     /// (`/`, `f`, `?`) are handled by `handle_shared_key`. This only
-    /// handles app-specific keys: Space, a, g, Enter, Esc, q.
+    /// handles app-specific keys: Space, a, g, d/Enter, Esc, q.
     fn handle_normal_key(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
             // Selection
@@ -120,10 +121,8 @@ impl CleanupApp {
             }
 
             // Actions
-            KeyCode::Enter => {
-                if self.shared.explorer.selected_count() > 0 {
-                    self.mode = Mode::ConfirmDelete;
-                }
+            KeyCode::Char('d') | KeyCode::Enter if self.shared.explorer.selected_count() > 0 => {
+                self.mode = Mode::ConfirmDelete;
             }
 
             // Clear/Cancel
@@ -166,10 +165,10 @@ impl CleanupApp {
             KeyCode::Backspace => {
                 self.glob_input.pop();
             }
-            KeyCode::Char(c) => {
-                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT {
-                    self.glob_input.push(c);
-                }
+            KeyCode::Char(c)
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                self.glob_input.push(c);
             }
             _ => {}
         }
@@ -246,48 +245,45 @@ impl CleanupApp {
 
     /// Delete all selected sessions.
     fn delete_selected(&mut self) -> Result<()> {
-        let selected_items = self.shared.explorer.selected_items();
-        if selected_items.is_empty() {
+        let sessions: Vec<SessionInfo> = self
+            .shared
+            .explorer
+            .selected_items()
+            .into_iter()
+            .map(SessionInfo::from)
+            .collect();
+        if sessions.is_empty() {
             return Ok(());
         }
+        let Some(storage) = &self.shared.storage else {
+            self.shared.status_message = Some("Storage is not available".to_string());
+            return Ok(());
+        };
 
-        // Collect paths to delete
-        let paths: Vec<String> = selected_items.iter().map(|i| i.path.clone()).collect();
-        let count = paths.len();
-
-        // Delete files
-        let mut deleted = 0;
-        let mut total_freed: u64 = 0;
-        for path in &paths {
-            if let Ok(metadata) = std::fs::metadata(path) {
-                total_freed += metadata.len();
-            }
-            if std::fs::remove_file(path).is_ok() {
-                deleted += 1;
-            }
-        }
+        let count = sessions.len();
+        let (deleted, total_freed) = delete_each(storage, &sessions);
 
-        // Remove from explorer
-        for path in &paths {
-            self.shared.explorer.remove_item(path);
+        // Remove from explorer and drop cached previews
+        for path in &deleted {
+            self.shared.remove_deleted(path);
         }
 
         // Update status
-        if deleted == count {
+        if deleted.len() == count {
             self.shared.status_message = Some(format!(
                 "Deleted {} sessions (freed {})",
-                deleted,
+                count,
                 format_size(total_freed)
             ));
-            self.files_deleted = true;
         } else {
             self.shared.status_message = Some(format!(
                 "Deleted {}/{} sessions (some files could not be removed)",
-                deleted, count
+                deleted.len(),
+                count
             ));
-            if deleted > 0 {
-                self.files_deleted = true;
-            }
+        }
+        if !deleted.is_empty() {
+            self.files_deleted = true;
         }
 
         Ok(())
@@ -299,7 +295,7 @@ impl CleanupApp {
 
         // Center the modal
         let modal_width = 65.min(area.width.saturating_sub(4));
-        let modal_height = 20.min(area.height.saturating_sub(4));
+        let modal_height = 21.min(area.height.saturating_sub(4));
         let x = (area.width - modal_width) / 2;
         let y = (area.height - modal_height) / 2;
         let modal_area = Rect::new(x, y, modal_width, modal_height);
@@ -331,6 +327,10 @@ impl CleanupApp {
                 Span::styled("  Home/End", Style::default().fg(theme.accent)),
                 Span::raw("       Go to first/last"),
             ]),
+            Line::from(vec![
+                Span::styled("  left/right", Style::default().fg(theme.accent)),
+                Span::raw("     Scrub preview (0-100%)"),
+            ]),
             Line::from(""),
             Line::from(vec![Span::styled(
                 "Selection",
@@ -355,7 +355,7 @@ impl CleanupApp {
             )]),
             Line::from(vec![
                 Span::styled("  /", Style::default().fg(theme.accent)),
-                Span::raw("              Search by filename"),
+                Span::raw("              Fuzzy search name, agent, title"),
             ]),
             Line::from(vec![
                 Span::styled("  f", Style::default().fg(theme.accent)),
@@ -363,8 +363,8 @@ impl CleanupApp {
             ]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("  Enter", Style::default().fg(theme.error)),
-                Span::raw("          Delete selected (with confirmation)"),
+                Span::styled("  d/Enter", Style::default().fg(theme.error)),
+                Span::raw("        Delete selected (with confirmation)"),
             ]),
             Line::from(vec![
                 Span::styled("  Esc", Style::default().fg(theme.accent)),
@@ -438,6 +438,7 @@ impl TuiApp for CleanupApp {
         prefetch_adjacent_previews(&self.shared.explorer, &mut self.shared.preview_cache);
 
         // Extract shared fields into local variables before closure
+        let preview_step = self.shared.preview_step;
         let explorer = &mut self.shared.explorer;
         let mode = self.mode;
         let search_input = &self.shared.search_input;
@@ -447,7 +448,7 @@ impl TuiApp for CleanupApp {
         let available_agents = &self.shared.available_agents;
 
         // Calculate selected size for status bar
-        let selected_size: u64 = explorer.selected_items().iter().map(|i| i.size).sum();
+        let selected_size = explorer.selected_size();
         let selected_count = explorer.selected_count();
 
         // Get preview for current selection from cache
@@ -463,7 +464,7 @@ impl TuiApp for CleanupApp {
             let chunks = build_explorer_layout(area);
 
             // Render file explorer with checkboxes (cleanup uses multi-select)
-            render_explorer_list(frame, chunks[0], explorer, preview, true, false);
+            render_explorer_list(frame, chunks[0], explorer, preview, true, false, preview_step);
 
             // Render status line
             let status_text = if let Some(msg) = &status {
@@ -515,20 +516,26 @@ impl TuiApp for CleanupApp {
 
             // Render footer with keybindings
             let footer_text = match mode {
-                Mode::Search => "Esc: cancel | Enter: apply | Backspace: delete",
-                Mode::GlobSelect => "Esc: cancel | Enter: select matching | Backspace: delete",
-                Mode::AgentFilter => "left/right: change | Enter: apply | Esc: cancel",
-                Mode::ConfirmDelete => "y: confirm | n/Esc: cancel",
-                Mode::Help => "Press any key to close",
+                Mode::Search => "Esc: clear | Enter: apply | Backspace: delete".to_string(),
+                Mode::GlobSelect => {
+                    "Esc: cancel | Enter: select matching | Backspace: delete".to_string()
+                }
+                Mode::AgentFilter => "left/right: change | Enter: apply | Esc: cancel".to_string(),
+                Mode::ConfirmDelete => "y: confirm | n/Esc: cancel".to_string(),
+                Mode::Help => "Press any key to close".to_string(),
                 Mode::Normal => {
                     if selected_count > 0 {
-                        "Space: toggle | a: toggle all | Enter: delete selected | Esc: clear | ?: help"
+                        format!(
+                            "Space: toggle | a: toggle all | d: delete (frees {}) | Esc: clear | ?: help",
+                            format_size(selected_size)
+                        )
                     } else {
                         "Space: select | a: all | g: glob | /: search | f: filter | ?: help | q: quit"
+                            .to_string()
                     }
                 }
             };
-            render_footer_text(frame, chunks[2], footer_text);
+            render_footer_text(frame, chunks[2], &footer_text);
 
             // Render modal overlays
             match mode {
@@ -549,6 +556,21 @@ impl TuiApp for CleanupApp {
     }
 }
 
+/// Delete sessions one at a time so a failure does not stop the rest.
+///
+/// Returns the paths that are gone and the bytes freed.
+fn delete_each(storage: &StorageManager, sessions: &[SessionInfo]) -> (Vec<String>, u64) {
+    let mut deleted = Vec::new();
+    let mut freed = 0;
+    for session in sessions {
+        if let Ok(size) = storage.delete_sessions(std::slice::from_ref(session)) {
+            freed += size;
+            deleted.push(session.path.to_string_lossy().into_owned());
+        }
+    }
+    (deleted, freed)
+}
+
 /// Simple glob pattern matching.
 /// Supports * (match any) and ? (match single char).
 fn glob_match(text: &str, pattern: &str) -> bool {
@@ -614,6 +636,35 @@ fn format_size(bytes: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn delete_each_frees_the_size_of_deleted_sessions() {
+        let dir = TempDir::new().unwrap();
+        let agent_dir = dir.path().join("claude");
+        fs::create_dir_all(&agent_dir).unwrap();
+        fs::write(agent_dir.join("a.cast"), vec![b'a'; 100]).unwrap();
+        fs::write(agent_dir.join("b.cast"), vec![b'b'; 30]).unwrap();
+        fs::write(agent_dir.join("keep.cast"), "keep").unwrap();
+
+        let mut config = Config::default();
+        config.storage.directory = dir.path().to_string_lossy().to_string();
+        config.storage.use_trash = false;
+        let storage = StorageManager::new(config);
+        let sessions: Vec<SessionInfo> = storage
+            .list_sessions(None)
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.filename != "keep.cast")
+            .collect();
+
+        let (deleted, freed) = delete_each(&storage, &sessions);
+        assert_eq!(deleted.len(), 2);
+        assert_eq!(freed, 130);
+        assert!(!agent_dir.join("a.cast").exists());
+        assert!(agent_dir.join("keep.cast").exists());
+    }
 
     #[test]
     fn mode_default_is_normal() {
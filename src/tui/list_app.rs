@@ -1,13 +1,15 @@
 //! List command TUI application
 //!
 //! Interactive file explorer for browsing and managing session recordings.
-//! Features: search, agent filter, play, delete, add marker.
+//! Features: fuzzy search, agent filter, play, delete, rename, add marker.
+//! Enter plays the selected session in the native player and returns to
+//! the list afterwards; `o` opens the context menu with all actions.
 
 use std::path::Path;
 use std::time::Duration;
 
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Modifier, Style},
@@ -25,8 +27,9 @@ use super::widgets::preview::prefetch_adjacent_previews;
 use super::widgets::FileItem;
 use crate::asciicast::{apply_transforms, TransformResult};
 use crate::config::Config;
-use crate::files::backup::{backup_path_for, create_backup, has_backup, restore_from_backup};
+use crate::files::backup::{create_backup, has_backup, restore_from_backup};
 use crate::files::lock;
+use crate::player::{PlayOptions, PlaybackResult};
 use crate::theme::current_theme;
 
 /// UI mode for the list application
@@ -49,6 +52,8 @@ pub enum Mode {
     OptimizeResult,
     /// Confirm unlock mode - asking user to confirm force-unlock
     ConfirmUnlock,
+    /// Rename mode - typing a new name for the selected session
+    Rename,
 }
 
 impl Mode {
@@ -59,7 +64,7 @@ impl Mode {
             Mode::AgentFilter => Some(SharedMode::AgentFilter),
             Mode::Help => Some(SharedMode::Help),
             Mode::ConfirmDelete => Some(SharedMode::ConfirmDelete),
-            Mode::ContextMenu | Mode::OptimizeResult | Mode::ConfirmUnlock => None,
+            Mode::ContextMenu | Mode::OptimizeResult | Mode::ConfirmUnlock | Mode::Rename => None,
         }
     }
 
@@ -134,6 +139,37 @@ pub struct OptimizeResultState {
     pub result: Result<TransformResult, String>,
 }
 
+/// A playback started from the list, held while the player owns the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlaybackLaunch {
+    /// Path of the session being played
+    path: String,
+}
+
+impl PlaybackLaunch {
+    /// Start playing the selected session, if any.
+    fn begin(shared: &SharedState) -> Option<Self> {
+        shared.explorer.selected_item().map(|item| Self {
+            path: item.path.clone(),
+        })
+    }
+
+    /// Restore the list after the player returns.
+    ///
+    /// Re-selects the played session (a refresh may have moved it), reloads
+    /// its metadata and preview since markers may have been added during
+    /// playback, and reports the outcome in the status bar.
+    fn finish(self, shared: &mut SharedState, result: Result<PlaybackResult>) {
+        shared.explorer.select_path(&self.path);
+        shared.explorer.update_item_metadata(&self.path);
+        shared.preview_cache.invalidate(&self.path);
+        shared.status_message = Some(match result {
+            Ok(result) => result.message(),
+            Err(e) => PlaybackResult::Error(format!("{:#}", e)).message(),
+        });
+    }
+}
+
 /// List application state
 pub struct ListApp {
     /// Base app for terminal handling
@@ -146,12 +182,17 @@ pub struct ListApp {
     context_menu_idx: usize,
     /// Optimize result for modal display
     optimize_result: Option<OptimizeResultState>,
+    /// Player options (key bindings) from config
+    play_options: PlayOptions,
+    /// New name being typed in rename mode
+    rename_input: String,
 }
 
 impl ListApp {
     /// Create a new list application with the given sessions.
     pub fn new(items: Vec<FileItem>, config: Config) -> Result<Self> {
         let app = App::new(Duration::from_millis(250))?;
+        let play_options = PlayOptions::from_config(&config)?;
         let shared = SharedState::new(items, Some(config));
 
         Ok(Self {
@@ -160,6 +201,8 @@ impl ListApp {
             mode: Mode::Normal,
             context_menu_idx: 0,
             optimize_result: None,
+            play_options,
+            rename_input: String::new(),
         })
     }
 
@@ -180,7 +223,7 @@ impl ListApp {
     fn handle_normal_key(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
             // Actions
-            KeyCode::Enter => {
+            KeyCode::Char('o') => {
                 if self.is_selected_locked() {
                     self.mode = Mode::ConfirmUnlock;
                     return Ok(());
@@ -192,7 +235,7 @@ impl ListApp {
             }
 
             // Direct shortcuts (bypass context menu)
-            KeyCode::Char('p') => {
+            KeyCode::Enter | KeyCode::Char('p') => {
                 if self.is_selected_locked() {
                     self.mode = Mode::ConfirmUnlock;
                     return Ok(());
@@ -230,6 +273,22 @@ impl ListApp {
                 }
                 self.add_marker()?;
             }
+            KeyCode::Char('r') => {
+                if self.is_selected_locked() {
+                    self.mode = Mode::ConfirmUnlock;
+                    return Ok(());
+                }
+                if let Some(item) = self.shared.explorer.selected_item() {
+                    // Start from the current name without the extension
+                    self.rename_input = item
+                        .name
+                        .strip_suffix(".cast")
+                        .unwrap_or(&item.name)
+                        .to_string();
+                    self.shared.status_message = None;
+                    self.mode = Mode::Rename;
+                }
+            }
 
             // Clear filters
             KeyCode::Esc => {
@@ -259,6 +318,29 @@ impl ListApp {
         Ok(())
     }
 
+    /// Handle keys in rename mode.
+    fn handle_rename_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.rename_session();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Esc => {
+                self.rename_input.clear();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.rename_input.pop();
+            }
+            KeyCode::Char(c)
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                self.rename_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
     /// Handle keys in context menu mode.
     fn handle_context_menu_key(&mut self, key: KeyEvent) -> Result<()> {
         match key.code {
@@ -416,23 +498,23 @@ impl ListApp {
         Ok(())
     }
 
-    /// Play the selected session with asciinema.
+    /// Play the selected session in the native player, then return to the list.
     fn play_session(&mut self) -> Result<()> {
         use crate::player;
 
-        if let Some(item) = self.shared.explorer.selected_item() {
-            let path = Path::new(&item.path);
+        let Some(launch) = PlaybackLaunch::begin(&self.shared) else {
+            return Ok(());
+        };
 
-            // Suspend TUI - restores normal terminal mode
-            self.app.suspend()?;
+        // Suspend TUI - restores normal terminal mode
+        self.app.suspend()?;
 
-            // Play the session
-            let result = player::play_session(path)?;
+        // Play the session; resume even if the player failed
+        let result = player::play_session_native(Path::new(&launch.path), &self.play_options);
 
-            // Resume TUI - re-enters alternate screen and raw mode
-            self.app.resume()?;
-            self.shared.status_message = Some(result.message());
-        }
+        // Resume TUI - re-enters alternate screen and raw mode
+        self.app.resume()?;
+        launch.finish(&mut self.shared, result);
         Ok(())
     }
 
@@ -461,34 +543,39 @@ impl ListApp {
         Ok(())
     }
 
-    /// Delete the selected session.
+    /// Delete the selected session (to the trash unless `use_trash` is off).
     fn delete_session(&mut self) -> Result<()> {
         if let Some(item) = self.shared.explorer.selected_item() {
-            let path = item.path.clone();
             let name = item.name.clone();
-
-            // Delete the file
-            if let Err(e) = std::fs::remove_file(&path) {
-                self.shared.status_message = Some(format!("Failed to delete: {}", e));
-            } else {
-                // Also delete backup if it exists (remove_file returns Err if not found)
-                let backup = backup_path_for(std::path::Path::new(&path));
-                let backup_deleted = std::fs::remove_file(&backup).is_ok();
-
-                // Remove from explorer to keep UI in sync
-                self.shared.explorer.remove_item(&path);
-
-                // Update status message
-                self.shared.status_message = Some(if backup_deleted {
-                    format!("Deleted: {} (and backup)", name)
-                } else {
-                    format!("Deleted: {}", name)
-                });
-            }
+            self.shared.status_message = Some(match self.shared.delete_selected() {
+                Ok(true) => format!("Moved to trash: {}", name),
+                Ok(false) => format!("Deleted: {}", name),
+                Err(e) => format!("Failed to delete: {}", e),
+            });
         }
         Ok(())
     }
 
+    /// Rename the selected session to the name typed in rename mode.
+    fn rename_session(&mut self) {
+        let new_name = std::mem::take(&mut self.rename_input);
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            self.shared.status_message = Some("Rename cancelled: empty name".to_string());
+            return;
+        }
+        self.shared.status_message = Some(match self.shared.rename_selected(new_name) {
+            Ok(new_path) => format!(
+                "Renamed to: {}",
+                new_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy())
+                    .unwrap_or_default()
+            ),
+            Err(e) => format!("Failed to rename: {:#}", e),
+        });
+    }
+
     /// Restore the selected session from its backup.
     fn restore_session(&mut self) -> Result<()> {
         if let Some(item) = self.shared.explorer.selected_item() {
@@ -640,7 +727,7 @@ impl ListApp {
 
         // Center the modal
         let modal_width = 60.min(area.width.saturating_sub(4));
-        let modal_height = 28.min(area.height.saturating_sub(4)); // Updated: added a for analyze
+        let modal_height = 30.min(area.height.saturating_sub(4));
         let x = (area.width - modal_width) / 2;
         let y = (area.height - modal_height) / 2;
         let modal_area = Rect::new(x, y, modal_width, modal_height);
@@ -673,6 +760,10 @@ impl ListApp {
                 Span::styled("  Home/End", Style::default().fg(theme.accent)),
                 Span::raw("   First/last"),
             ]),
+            Line::from(vec![
+                Span::styled("  ←/→", Style::default().fg(theme.accent)),
+                Span::raw("        Scrub preview (0-100%)"),
+            ]),
             Line::from(""),
             // Actions section
             Line::from(Span::styled(
@@ -680,12 +771,12 @@ impl ListApp {
                 Style::default().fg(theme.text_secondary),
             )),
             Line::from(vec![
-                Span::styled("  Enter", Style::default().fg(theme.accent)),
-                Span::raw("       Context menu"),
+                Span::styled("  Enter/p", Style::default().fg(theme.accent)),
+                Span::raw("     Play session"),
             ]),
             Line::from(vec![
-                Span::styled("  p", Style::default().fg(theme.accent)),
-                Span::raw("           Play session"),
+                Span::styled("  o", Style::default().fg(theme.accent)),
+                Span::raw("           Context menu"),
             ]),
             Line::from(vec![
                 Span::styled("  c", Style::default().fg(theme.accent)),
@@ -699,6 +790,10 @@ impl ListApp {
                 Span::styled("  a", Style::default().fg(theme.accent)),
                 Span::raw("           Analyze session"),
             ]),
+            Line::from(vec![
+                Span::styled("  r", Style::default().fg(theme.accent)),
+                Span::raw("           Rename session"),
+            ]),
             Line::from(vec![
                 Span::styled("  d", Style::default().fg(theme.accent)),
                 Span::raw("           Delete session"),
@@ -711,7 +806,7 @@ impl ListApp {
             )),
             Line::from(vec![
                 Span::styled("  /", Style::default().fg(theme.accent)),
-                Span::raw("           Search by filename"),
+                Span::raw("           Fuzzy search name, agent, title"),
             ]),
             Line::from(vec![
                 Span::styled("  f", Style::default().fg(theme.accent)),
@@ -982,6 +1077,7 @@ impl TuiApp for ListApp {
             Mode::ContextMenu => self.handle_context_menu_key(key)?,
             Mode::OptimizeResult => self.handle_optimize_result_key(key)?,
             Mode::ConfirmUnlock => self.handle_confirm_unlock_key(key)?,
+            Mode::Rename => self.handle_rename_key(key),
             _ => {}
         }
         Ok(())
@@ -999,6 +1095,7 @@ impl TuiApp for ListApp {
         prefetch_adjacent_previews(&self.shared.explorer, &mut self.shared.preview_cache);
 
         // Extract shared fields into local variables before closure
+        let preview_step = self.shared.preview_step;
         let explorer = &mut self.shared.explorer;
         let mode = self.mode;
         let search_input = &self.shared.search_input;
@@ -1007,6 +1104,7 @@ impl TuiApp for ListApp {
         let available_agents = &self.shared.available_agents;
         let context_menu_idx = self.context_menu_idx;
         let optimize_result = self.optimize_result.clone();
+        let rename_input = &self.rename_input;
 
         // Get preview for current selection from cache
         let current_path = explorer.selected_item().map(|i| i.path.clone());
@@ -1027,7 +1125,15 @@ impl TuiApp for ListApp {
             let chunks = build_explorer_layout(area);
 
             // Render file explorer (no checkboxes in list view - it's single-select)
-            render_explorer_list(frame, chunks[0], explorer, preview, false, backup_exists);
+            render_explorer_list(
+                frame,
+                chunks[0],
+                explorer,
+                preview,
+                false,
+                backup_exists,
+                preview_step,
+            );
 
             // Render status line
             let status_text = if let Some(msg) = &status {
@@ -1035,6 +1141,7 @@ impl TuiApp for ListApp {
             } else {
                 match mode {
                     Mode::Search => format!("Search: {}_", search_input),
+                    Mode::Rename => format!("Rename to: {}_", rename_input),
                     Mode::AgentFilter => {
                         let agent = &available_agents[agent_filter_idx];
                         format!("Filter by agent: {} (←/→ to change, Enter to apply)", agent)
@@ -1067,15 +1174,16 @@ impl TuiApp for ListApp {
 
             // Render footer with keybindings
             let footer_text = match mode {
-                Mode::Search => "Esc: cancel | Enter: apply search | Backspace: delete char",
+                Mode::Search => "Esc: clear | Enter: apply search | Backspace: delete char",
                 Mode::AgentFilter => "←/→: change agent | Enter: apply | Esc: cancel",
                 Mode::ConfirmDelete => "y: confirm delete | n/Esc: cancel",
                 Mode::ConfirmUnlock => "y: force unlock | n/Esc: cancel",
+                Mode::Rename => "Enter: rename | Esc: cancel | Backspace: delete char",
                 Mode::Help => "Press any key to close help",
                 Mode::ContextMenu => "↑↓: navigate | Enter: select | Esc: cancel",
                 Mode::OptimizeResult => "Enter/Esc: dismiss",
                 Mode::Normal => {
-                    "↑↓: navigate | Enter: menu | p: play | c: copy | t: optimize | a: analyze | d: delete | ?: help | q: quit"
+                    "↑↓: navigate | Enter: play | o: menu | c: copy | t: optimize | a: analyze | r: rename | d: delete | ?: help | q: quit"
                 }
             };
             render_footer_text(frame, chunks[2], footer_text);
@@ -1138,6 +1246,98 @@ fn format_duration(seconds: f64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::StorageManager;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CAST: &str = "{\"version\":3,\"term\":{\"cols\":80,\"rows\":24}}\n[0.1,\"o\",\"hi\"]\n";
+
+    fn state_with_files(dir: &TempDir, names: &[&str]) -> SharedState {
+        let agent_dir = dir.path().join("claude");
+        fs::create_dir_all(&agent_dir).unwrap();
+        for name in names {
+            fs::write(agent_dir.join(name), CAST).unwrap();
+        }
+        let mut config = Config::default();
+        config.storage.directory = dir.path().to_string_lossy().to_string();
+        let items = StorageManager::new(config.clone())
+            .list_sessions(None)
+            .unwrap()
+            .into_iter()
+            .map(FileItem::from)
+            .collect();
+        SharedState::new(items, Some(config))
+    }
+
+    fn selected_path(state: &SharedState) -> String {
+        state.explorer.selected_item().unwrap().path.clone()
+    }
+
+    #[test]
+    fn playback_launch_requires_a_selection() {
+        let state = SharedState::new(Vec::new(), None);
+        assert!(PlaybackLaunch::begin(&state).is_none());
+    }
+
+    #[test]
+    fn playback_return_keeps_the_played_session_selected() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast", "b.cast", "c.cast"]);
+        state.explorer.down();
+        let launch = PlaybackLaunch::begin(&state).unwrap();
+        assert_eq!(launch.path, selected_path(&state));
+
+        // The selection moves while the player runs (e.g. a refresh)
+        state.explorer.home();
+        assert_ne!(selected_path(&state), launch.path);
+
+        let played = launch.path.clone();
+        launch.finish(
+            &mut state,
+            Ok(PlaybackResult::Success("b.cast".to_string())),
+        );
+        assert_eq!(selected_path(&state), played);
+        assert_eq!(state.status_message.as_deref(), Some("Played: b.cast"));
+    }
+
+    #[test]
+    fn playback_return_reinitializes_the_preview() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast"]);
+        let launch = PlaybackLaunch::begin(&state).unwrap();
+        assert!(state.preview_cache.get(&launch.path).is_some());
+
+        let path = launch.path.clone();
+        launch.finish(&mut state, Ok(PlaybackResult::Interrupted));
+        assert!(state.preview_cache.get(&path).is_none());
+        assert_eq!(
+            state.status_message.as_deref(),
+            Some("Playback interrupted")
+        );
+    }
+
+    #[test]
+    fn playback_errors_are_reported_not_propagated() {
+        let dir = TempDir::new().unwrap();
+        let mut state = state_with_files(&dir, &["a.cast"]);
+
+        let launch = PlaybackLaunch::begin(&state).unwrap();
+        launch.finish(&mut state, Err(anyhow::anyhow!("terminal too small")));
+        assert_eq!(
+            state.status_message.as_deref(),
+            Some("Failed to play: terminal too small")
+        );
+
+        let launch = PlaybackLaunch::begin(&state).unwrap();
+        launch.finish(
+            &mut state,
+            Ok(PlaybackResult::Error("bad file".to_string())),
+        );
+        assert_eq!(
+            state.status_message.as_deref(),
+            Some("Failed to play: bad file")
+        );
+    }
 
     #[test]
     fn mode_default_is_normal() {
@@ -1203,6 +1403,11 @@ mod tests {
         assert_eq!(ContextMenuItem::ALL[6], ContextMenuItem::AddMarker);
     }
 
+    #[test]
+    fn rename_mode_is_not_shared() {
+        assert_eq!(Mode::Rename.to_shared(), None);
+    }
+
     #[test]
     fn context_menu_mode_is_context_menu() {
         assert_eq!(Mode::ContextMenu, Mode::ContextMenu);
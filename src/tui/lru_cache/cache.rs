@@ -2,7 +2,8 @@
 //!
 //! Provides a bounded cache that loads values on a background thread.
 //! Callers request keys and poll for results. LRU eviction keeps
-//! the cache within its configured size limit.
+//! the cache within its configured size limit. An optional validator
+//! drops entries that no longer match their source so they reload.
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
@@ -28,8 +29,16 @@ pub struct AsyncLruCache<K, V> {
     request_tx: Sender<K>,
     /// Channel receiver for load results
     result_rx: Receiver<LoadResult<K, V>>,
+    /// Whether a cached value is still current (see `with_validator`)
+    validator: Option<Validator<K, V>>,
+    /// `get` calls that found a value
+    hits: u64,
+    /// `get` calls that found nothing (not loaded, evicted or stale)
+    misses: u64,
 }
 
+type Validator<K, V> = Box<dyn Fn(&K, &V) -> bool + Send>;
+
 impl<K, V> AsyncLruCache<K, V>
 where
     K: Hash + Eq + Clone + Send + 'static,
@@ -53,9 +62,21 @@ where
             pending: HashSet::new(),
             request_tx,
             result_rx,
+            validator: None,
+            hits: 0,
+            misses: 0,
         }
     }
 
+    /// Re-check cached values on access with `is_current`.
+    ///
+    /// A value that is no longer current is dropped: `get` misses and the
+    /// next `request` loads it again.
+    pub fn with_validator(mut self, is_current: impl Fn(&K, &V) -> bool + Send + 'static) -> Self {
+        self.validator = Some(Box::new(is_current));
+        self
+    }
+
     /// Poll for completed loads and add them to cache
     pub fn poll(&mut self) {
         while let Ok(result) = self.result_rx.try_recv() {
@@ -68,14 +89,32 @@ where
 
     /// Get a value from cache, returning None if not cached yet
     pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.drop_if_stale(key);
         if self.cache.contains_key(key) {
+            self.hits += 1;
             self.touch(key);
             self.cache.get(key)
         } else {
+            self.misses += 1;
             None
         }
     }
 
+    /// Number of `get` calls that returned a cached value.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of `get` calls that returned `None`.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Maximum number of cached entries.
+    pub fn capacity(&self) -> usize {
+        self.max_size
+    }
+
     /// Check if a key is currently being loaded
     pub fn is_pending(&self, key: &K) -> bool {
         self.pending.contains(key)
@@ -85,6 +124,7 @@ where
     ///
     /// Skips the request if the key is already cached or pending.
     pub fn request(&mut self, key: K) {
+        self.drop_if_stale(&key);
         if self.cache.contains_key(&key) || self.pending.contains(&key) {
             return;
         }
@@ -133,6 +173,18 @@ where
         self.lru_order.push_back(key);
     }
 
+    /// Remove the cached value for `key` if the validator rejects it.
+    fn drop_if_stale(&mut self, key: &K) {
+        let stale = match (&self.validator, self.cache.get(key)) {
+            (Some(is_current), Some(value)) => !is_current(key, value),
+            _ => false,
+        };
+        if stale {
+            self.cache.remove(key);
+            self.lru_order.retain(|k| k != key);
+        }
+    }
+
     /// Move a key to the back of the LRU queue (most recently used)
     fn touch(&mut self, key: &K) {
         self.lru_order.retain(|k| k != key);
@@ -236,6 +288,91 @@ mod tests {
         assert!(cache.get(&"ccc".to_string()).is_some());
     }
 
+    #[test]
+    fn eviction_follows_least_recent_use() {
+        let mut cache = test_cache(3);
+        for key in ["a", "b", "c"] {
+            cache.insert(key.to_string(), key.len());
+        }
+        // Reading "a" makes "b" the least recently used
+        assert!(cache.get(&"a".to_string()).is_some());
+
+        cache.insert("d".to_string(), 1);
+        assert!(cache.get(&"b".to_string()).is_none());
+        cache.insert("e".to_string(), 1);
+        assert!(cache.get(&"c".to_string()).is_none());
+
+        for key in ["a", "d", "e"] {
+            assert!(cache.get(&key.to_string()).is_some(), "{}", key);
+        }
+        assert_eq!(cache.capacity(), 3);
+    }
+
+    #[test]
+    fn get_counts_hits_and_misses() {
+        let mut cache = test_cache(1).with_validator(|key, len| key.len() == *len);
+        assert_eq!((cache.hits(), cache.misses()), (0, 0));
+
+        cache.insert("a".to_string(), 1);
+        cache.get(&"a".to_string());
+        cache.get(&"a".to_string());
+        cache.get(&"b".to_string());
+        assert_eq!((cache.hits(), cache.misses()), (2, 1));
+
+        // Evicted and stale entries count as misses
+        cache.insert("bb".to_string(), 2);
+        cache.get(&"a".to_string());
+        cache.invalidate(&"bb".to_string());
+        cache.insert("cc".to_string(), 7);
+        cache.get(&"cc".to_string());
+        assert_eq!((cache.hits(), cache.misses()), (2, 3));
+
+        // request/prefetch/is_pending do not count
+        cache.request("dd".to_string());
+        cache.is_pending(&"dd".to_string());
+        assert_eq!((cache.hits(), cache.misses()), (2, 3));
+    }
+
+    #[test]
+    fn stale_entries_miss_and_reload() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Values record the source version they were loaded from
+        let version = Arc::new(AtomicUsize::new(1));
+        let loader_version = Arc::clone(&version);
+        let current_version = Arc::clone(&version);
+        let mut cache = AsyncLruCache::new(10, move |_: &String| {
+            Some(loader_version.load(Ordering::SeqCst))
+        })
+        .with_validator(move |_, loaded| *loaded == current_version.load(Ordering::SeqCst));
+
+        let key = "/sessions/a.cast".to_string();
+        cache.insert(key.clone(), 1);
+        assert_eq!(cache.get(&key), Some(&1));
+
+        version.store(2, Ordering::SeqCst);
+        assert!(cache.get(&key).is_none());
+
+        cache.request(key.clone());
+        assert!(cache.is_pending(&key));
+        sleep(Duration::from_millis(50));
+        cache.poll();
+        assert_eq!(cache.get(&key), Some(&2));
+    }
+
+    #[test]
+    fn request_reloads_stale_entry() {
+        let mut cache = test_cache(10).with_validator(|key, len| key.len() == *len);
+        let key = "abc".to_string();
+        cache.insert(key.clone(), 99);
+
+        cache.request(key.clone());
+        assert!(cache.is_pending(&key));
+        sleep(Duration::from_millis(50));
+        cache.poll();
+        assert_eq!(cache.get(&key), Some(&3));
+    }
+
     #[test]
     fn prefetch_requests_multiple_keys() {
         let mut cache = test_cache(10);
@@ -8,6 +8,8 @@ pub mod worker;
 
 pub use cache::AsyncLruCache;
 
+use std::path::Path;
+
 use super::widgets::SessionPreview;
 
 /// Preview cache specialized for session preview loading.
@@ -15,9 +17,13 @@ use super::widgets::SessionPreview;
 /// Drop-in replacement for the former `preview_cache::PreviewCache`.
 pub type PreviewCache = AsyncLruCache<String, SessionPreview>;
 
-/// Create a new `PreviewCache` with the default capacity (20 entries).
+/// Create a new `PreviewCache` holding up to `capacity` previews
+/// (see `[tui] preview_cache_size`).
 ///
-/// Uses `SessionPreview::load` as the background loader.
-pub fn new_preview_cache() -> PreviewCache {
-    AsyncLruCache::new(20, |path| SessionPreview::load(path))
+/// Uses `SessionPreview::load` as the background loader. Previews of files
+/// modified since they were loaded (e.g. re-recorded under the same name)
+/// are dropped on access and reloaded.
+pub fn new_preview_cache(capacity: usize) -> PreviewCache {
+    AsyncLruCache::new(capacity, |path| SessionPreview::load(path))
+        .with_validator(|path, preview| preview.is_current(Path::new(path)))
 }
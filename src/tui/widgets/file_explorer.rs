@@ -11,6 +11,7 @@
 
 use std::collections::HashSet;
 use std::path::Path;
+use std::time::SystemTime;
 
 use chrono::{DateTime, Local};
 use ratatui::{
@@ -24,9 +25,12 @@ use ratatui::{
 use crate::asciicast::EventType;
 use crate::files::backup::has_backup;
 use crate::files::lock::{self, LockInfo};
-use crate::storage::SessionInfo;
+use crate::files::notes::has_notes;
+use crate::storage::{is_pinned, read_display_title, SessionInfo};
 use crate::theme::current_theme;
 
+use super::fuzzy;
+
 /// A file item in the explorer
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileItem {
@@ -42,8 +46,12 @@ pub struct FileItem {
     pub modified: DateTime<Local>,
     /// Whether a backup file exists for this item (cached)
     pub has_backup: bool,
+    /// Whether the item has a notes sidecar (cached)
+    pub has_notes: bool,
     /// Lock info if file is actively being recorded (cached)
     pub lock_info: Option<LockInfo>,
+    /// Recording title from the cast header (cached), if meaningful
+    pub title: Option<String>,
 }
 
 impl FileItem {
@@ -57,21 +65,33 @@ impl FileItem {
     ) -> Self {
         let path_str = path.into();
         let has_backup = has_backup(std::path::Path::new(&path_str));
+        let has_notes = has_notes(std::path::Path::new(&path_str));
         let lock_info = lock::read_lock(std::path::Path::new(&path_str));
+        let agent = agent.into();
+        let title = read_display_title(Path::new(&path_str), &agent);
         Self {
             path: path_str,
             name: name.into(),
-            agent: agent.into(),
+            agent,
             size,
             modified,
             has_backup,
+            has_notes,
             lock_info,
+            title,
         }
     }
+
+    /// Name to show in lists: the recording title if present, else the filename.
+    pub fn display_name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
 }
 
-impl From<SessionInfo> for FileItem {
-    fn from(session: SessionInfo) -> Self {
+impl FileItem {
+    /// Build an item from a session whose display title is already known
+    /// (e.g. from the storage index), without reading the file header.
+    pub fn with_title(session: SessionInfo, title: Option<String>) -> Self {
         let path_str = session.path.to_string_lossy().to_string();
         let has_backup = has_backup(std::path::Path::new(&path_str));
         let lock_info = lock::read_lock(std::path::Path::new(&path_str));
@@ -82,12 +102,49 @@ impl From<SessionInfo> for FileItem {
             size: session.size,
             modified: session.modified,
             has_backup,
+            has_notes: session.has_notes,
             lock_info,
+            title,
         }
     }
 }
 
-use crate::terminal::{Color, StyledLine};
+impl From<SessionInfo> for FileItem {
+    fn from(session: SessionInfo) -> Self {
+        let title = session.title();
+        Self::with_title(session, title)
+    }
+}
+
+impl From<&FileItem> for SessionInfo {
+    /// Session details from the cached item; ages are relative to now.
+    fn from(item: &FileItem) -> Self {
+        let path = std::path::PathBuf::from(&item.path);
+        let age = Local::now() - item.modified;
+        Self {
+            pinned: is_pinned(&path),
+            has_notes: item.has_notes,
+            path,
+            agent: item.agent.clone(),
+            filename: item.name.clone(),
+            size: item.size,
+            modified: item.modified,
+            age_days: age.num_days(),
+            age_hours: age.num_hours(),
+            age_minutes: age.num_minutes(),
+        }
+    }
+}
+
+use std::cell::OnceCell;
+
+use crate::terminal::{CellStyle, Color, StyledLine};
+
+/// Preview positions selectable with left/right, as percent of the recording.
+pub const PREVIEW_STEPS: [u8; 5] = [0, 25, 50, 75, 100];
+
+/// Index into [`PREVIEW_STEPS`] shown before the user scrubs (25%).
+pub const DEFAULT_PREVIEW_STEP: usize = 1;
 
 /// Enhanced preview information for a session file.
 ///
@@ -98,42 +155,65 @@ pub struct SessionPreview {
     pub duration_secs: f64,
     /// Number of marker events
     pub marker_count: usize,
-    /// Terminal snapshot at 10% of recording (with color info)
-    pub styled_preview: Vec<StyledLine>,
+    /// Terminal snapshots (with color info), one per [`PREVIEW_STEPS`] entry
+    pub snapshots: Vec<Vec<StyledLine>>,
+    /// Rendered lines per snapshot, converted the first time each is shown
+    rendered: Vec<OnceCell<Vec<Line<'static>>>>,
+    /// Modification time of the file when it was loaded, if read from disk
+    pub modified: Option<SystemTime>,
 }
 
 impl SessionPreview {
+    /// Create a preview from already captured snapshots.
+    pub fn new(duration_secs: f64, marker_count: usize, snapshots: Vec<Vec<StyledLine>>) -> Self {
+        let rendered = snapshots.iter().map(|_| OnceCell::new()).collect();
+        Self {
+            duration_secs,
+            marker_count,
+            snapshots,
+            rendered,
+            modified: None,
+        }
+    }
+
+    /// Whether the file at `path` is unchanged since this preview was loaded.
+    ///
+    /// Previews not loaded from disk are always current; a file that can no
+    /// longer be read is not.
+    pub fn is_current(&self, path: &Path) -> bool {
+        match self.modified {
+            Some(loaded) => std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified == loaded),
+            None => true,
+        }
+    }
+
     /// Load preview information from an asciicast file using streaming parsing.
     ///
     /// This is optimized to avoid loading the entire file into memory:
-    /// - Parses header for terminal size
-    /// - Streams events, only processing terminal output for first ~10%
-    /// - Counts markers and sums times for full duration
+    /// - Reads the total duration first (header or a cheap timestamp scan)
+    /// - Streams events through a terminal buffer, capturing a snapshot at
+    ///   each [`PREVIEW_STEPS`] position
+    /// - Counts markers along the way
     ///
     /// Returns None if the file cannot be parsed.
     pub fn load<P: AsRef<Path>>(path: P) -> Option<Self> {
         Self::load_streaming(path)
     }
 
-    /// Streaming loader that minimizes memory usage and processing time.
-    ///
-    /// Single-pass approach:
-    /// - Process terminal output for first ~30 seconds (enough for preview)
-    /// - Continue scanning for total duration and marker count
-    /// - Never stores all events in memory
+    /// Streaming loader that never stores all events in memory.
     fn load_streaming<P: AsRef<Path>>(path: P) -> Option<Self> {
-        use crate::asciicast::{EventType, Header};
+        use crate::asciicast::AsciicastFile;
         use crate::terminal::TerminalBuffer;
         use std::fs::File;
         use std::io::{BufRead, BufReader};
 
-        let file = File::open(path.as_ref()).ok()?;
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
-
-        // Parse header
-        let header_line = lines.next()?.ok()?;
-        let header: Header = serde_json::from_str(&header_line).ok()?;
+        // Taken before reading, so a write during loading makes it stale
+        let modified = std::fs::metadata(path.as_ref())
+            .and_then(|m| m.modified())
+            .ok();
+        let (header, total) = AsciicastFile::read_header_and_duration(path.as_ref()).ok()?;
         if header.version != 3 {
             return None;
         }
@@ -142,16 +222,19 @@ impl SessionPreview {
         let cols = header.term.as_ref().and_then(|t| t.cols).unwrap_or(80) as usize;
         let rows = header.term.as_ref().and_then(|t| t.rows).unwrap_or(24) as usize;
 
+        let targets: Vec<f64> = PREVIEW_STEPS
+            .iter()
+            .map(|&pct| total * f64::from(pct) / 100.0)
+            .collect();
+
+        let file = File::open(path.as_ref()).ok()?;
+        let mut lines = BufReader::new(file).lines();
+        lines.next()?.ok()?; // header, already parsed
+
         let mut buffer = TerminalBuffer::new(cols, rows);
-        let mut total_duration = 0.0;
+        let mut elapsed = 0.0;
         let mut marker_count = 0;
-        let mut preview_captured = false;
-        let mut styled_preview = Vec::new();
-
-        // Single pass: process events
-        // - Process terminal output until 30 seconds (capture preview snapshot)
-        // - Continue counting markers and duration
-        const PREVIEW_THRESHOLD_SECS: f64 = 30.0;
+        let mut snapshots = Vec::with_capacity(targets.len());
 
         for line_result in lines {
             let line = match line_result {
@@ -161,39 +244,52 @@ impl SessionPreview {
 
             // Quick parse for time, type, and optionally data
             if let Some((time, event_type, data)) = Self::parse_event_minimal(&line) {
-                total_duration += time;
+                elapsed += time;
+
+                // Capture every step this event moves past, before applying it
+                while snapshots.len() < targets.len() && elapsed > targets[snapshots.len()] {
+                    snapshots.push(buffer.styled_lines());
+                }
 
                 if event_type == EventType::Marker {
                     marker_count += 1;
                 }
-
-                // Only process terminal output before threshold
-                if !preview_captured {
-                    if event_type == EventType::Output {
-                        if let Some(output) = data {
-                            buffer.process(&output, None);
-                        }
-                    }
-
-                    // Capture preview at threshold
-                    if total_duration >= PREVIEW_THRESHOLD_SECS {
-                        styled_preview = buffer.styled_lines();
-                        preview_captured = true;
-                        // Don't need buffer anymore, drop it
-                    }
+                if let Some(output) = data {
+                    buffer.process(&output, None);
                 }
             }
         }
 
-        // If file was shorter than threshold, capture final state
-        if !preview_captured {
-            styled_preview = buffer.styled_lines();
+        // Remaining steps (at least 100%) show the final state
+        while snapshots.len() < targets.len() {
+            snapshots.push(buffer.styled_lines());
         }
 
         Some(Self {
-            duration_secs: total_duration,
-            marker_count,
-            styled_preview,
+            modified,
+            ..Self::new(elapsed, marker_count, snapshots)
+        })
+    }
+
+    /// Snapshot at a step index, clamped to the available snapshots.
+    pub fn snapshot(&self, step: usize) -> &[StyledLine] {
+        match self.snapshots.len() {
+            0 => &[],
+            n => &self.snapshots[step.min(n - 1)],
+        }
+    }
+
+    /// Rendered lines for a step index, converted once and then reused.
+    pub fn rendered_lines(&self, step: usize) -> &[Line<'static>] {
+        if self.snapshots.is_empty() {
+            return &[];
+        }
+        let step = step.min(self.snapshots.len() - 1);
+        self.rendered[step].get_or_init(|| {
+            self.snapshots[step]
+                .iter()
+                .map(Self::styled_line_to_ratatui)
+                .collect()
         })
     }
 
@@ -244,78 +340,56 @@ impl SessionPreview {
         }
     }
 
+    /// Convert a terminal cell style to a ratatui style.
+    pub fn cell_style_to_ratatui(style: &CellStyle) -> Style {
+        let mut ratatui_style = Style::default();
+        if style.fg != Color::Default {
+            ratatui_style = ratatui_style.fg(Self::to_ratatui_color(style.fg));
+        }
+        if style.bg != Color::Default {
+            ratatui_style = ratatui_style.bg(Self::to_ratatui_color(style.bg));
+        }
+        let modifiers = [
+            (style.bold, Modifier::BOLD),
+            (style.dim, Modifier::DIM),
+            (style.italic, Modifier::ITALIC),
+            (style.underline, Modifier::UNDERLINED),
+            (style.reverse, Modifier::REVERSED),
+        ];
+        for (enabled, modifier) in modifiers {
+            if enabled {
+                ratatui_style = ratatui_style.add_modifier(modifier);
+            }
+        }
+        ratatui_style
+    }
+
     /// Convert a StyledLine to a ratatui Line with colors
     pub fn styled_line_to_ratatui(line: &StyledLine) -> Line<'static> {
         // Group consecutive cells with same style into spans
         let mut spans: Vec<Span<'static>> = Vec::new();
         let mut current_text = String::new();
-        let mut current_style: Option<crate::terminal::CellStyle> = None;
+        let mut current_style: Option<CellStyle> = None;
 
         for cell in &line.cells {
-            if Some(cell.style) == current_style {
-                current_text.push(cell.char);
-            } else {
-                // Flush previous span
-                if !current_text.is_empty() {
-                    if let Some(style) = current_style {
-                        let mut ratatui_style = Style::default();
-                        if style.fg != Color::Default {
-                            ratatui_style = ratatui_style.fg(Self::to_ratatui_color(style.fg));
-                        }
-                        if style.bg != Color::Default {
-                            ratatui_style = ratatui_style.bg(Self::to_ratatui_color(style.bg));
-                        }
-                        if style.bold {
-                            ratatui_style = ratatui_style.add_modifier(Modifier::BOLD);
-                        }
-                        if style.dim {
-                            ratatui_style = ratatui_style.add_modifier(Modifier::DIM);
-                        }
-                        if style.italic {
-                            ratatui_style = ratatui_style.add_modifier(Modifier::ITALIC);
-                        }
-                        if style.underline {
-                            ratatui_style = ratatui_style.add_modifier(Modifier::UNDERLINED);
-                        }
-                        spans.push(Span::styled(
-                            std::mem::take(&mut current_text),
-                            ratatui_style,
-                        ));
-                    } else {
-                        spans.push(Span::raw(std::mem::take(&mut current_text)));
-                    }
+            if Some(cell.style) != current_style {
+                if let Some(style) = current_style {
+                    spans.push(Span::styled(
+                        std::mem::take(&mut current_text),
+                        Self::cell_style_to_ratatui(&style),
+                    ));
                 }
                 current_style = Some(cell.style);
-                current_text.push(cell.char);
             }
+            current_text.push(cell.char);
         }
 
         // Flush final span
-        if !current_text.is_empty() {
-            if let Some(style) = current_style {
-                let mut ratatui_style = Style::default();
-                if style.fg != Color::Default {
-                    ratatui_style = ratatui_style.fg(Self::to_ratatui_color(style.fg));
-                }
-                if style.bg != Color::Default {
-                    ratatui_style = ratatui_style.bg(Self::to_ratatui_color(style.bg));
-                }
-                if style.bold {
-                    ratatui_style = ratatui_style.add_modifier(Modifier::BOLD);
-                }
-                if style.dim {
-                    ratatui_style = ratatui_style.add_modifier(Modifier::DIM);
-                }
-                if style.italic {
-                    ratatui_style = ratatui_style.add_modifier(Modifier::ITALIC);
-                }
-                if style.underline {
-                    ratatui_style = ratatui_style.add_modifier(Modifier::UNDERLINED);
-                }
-                spans.push(Span::styled(current_text, ratatui_style));
-            } else {
-                spans.push(Span::raw(current_text));
-            }
+        if let Some(style) = current_style {
+            spans.push(Span::styled(
+                current_text,
+                Self::cell_style_to_ratatui(&style),
+            ));
         }
 
         Line::from(spans)
@@ -377,7 +451,7 @@ pub struct FileExplorer {
     sort_direction: SortDirection,
     /// Agent filter (None = show all)
     agent_filter: Option<String>,
-    /// Search filter - matches filename (case-insensitive)
+    /// Search filter - fuzzy matches filename, agent or title
     search_filter: Option<String>,
     /// List state for ratatui
     list_state: ListState,
@@ -427,6 +501,17 @@ impl FileExplorer {
         self.visible_indices.is_empty()
     }
 
+    /// Message to show in place of the list when nothing is visible.
+    pub fn empty_message(&self) -> Option<&'static str> {
+        if !self.visible_indices.is_empty() {
+            None
+        } else if self.items.is_empty() {
+            Some("No sessions left. Record one with: agr record <agent>")
+        } else {
+            Some("No sessions match the current filter (Esc to clear)")
+        }
+    }
+
     /// Get the currently selected index (in visible list)
     pub fn selected(&self) -> usize {
         self.selected
@@ -452,6 +537,14 @@ impl FileExplorer {
         self.multi_selected.len()
     }
 
+    /// Total size in bytes of all multi-selected items
+    pub fn selected_size(&self) -> u64 {
+        self.multi_selected
+            .iter()
+            .map(|&idx| self.items[idx].size)
+            .sum()
+    }
+
     /// Check if an item is multi-selected (by visible index)
     pub fn is_selected(&self, visible_idx: usize) -> bool {
         self.visible_indices
@@ -644,7 +737,7 @@ impl FileExplorer {
         self.search_filter.as_deref()
     }
 
-    /// Set the search filter (case-insensitive substring match on filename)
+    /// Set the search filter (fuzzy match on filename, agent or title)
     pub fn set_search_filter(&mut self, search: Option<String>) {
         self.search_filter = search;
         self.apply_filter();
@@ -711,11 +804,17 @@ impl FileExplorer {
                     .map(|f| item.agent == *f)
                     .unwrap_or(true);
 
-                // Search filter (case-insensitive substring match on filename)
+                // Search filter (fuzzy match on filename, agent or title).
+                // Matches keep the order of the current sort field.
                 let search_match = self
                     .search_filter
                     .as_ref()
-                    .map(|s| item.name.to_lowercase().contains(&s.to_lowercase()))
+                    .map(|s| {
+                        let fields = [item.name.as_str(), item.agent.as_str()]
+                            .into_iter()
+                            .chain(item.title.as_deref());
+                        fuzzy::best_score(s, fields).is_some()
+                    })
                     .unwrap_or(true);
 
                 agent_match && search_match
@@ -741,6 +840,7 @@ impl FileExplorer {
                 self.items[idx].size = metadata.len();
                 // Also update cached has_backup and lock status
                 self.items[idx].has_backup = has_backup(std::path::Path::new(path));
+                self.items[idx].has_notes = has_notes(std::path::Path::new(path));
                 self.items[idx].lock_info = lock::read_lock(std::path::Path::new(path));
                 return true;
             }
@@ -764,6 +864,7 @@ impl FileExplorer {
                 self.items[idx].size = metadata.len();
             }
             self.items[idx].has_backup = has_backup(new_p);
+            self.items[idx].has_notes = has_notes(new_p);
             true
         } else {
             false
@@ -814,6 +915,21 @@ impl FileExplorer {
         self.sync_list_state();
     }
 
+    /// Select the visible item with the given path.
+    /// Returns false (leaving the selection alone) if it is not visible.
+    pub fn select_path(&mut self, path: &str) -> bool {
+        let Some(position) = self
+            .visible_indices
+            .iter()
+            .position(|&idx| self.items[idx].path == path)
+        else {
+            return false;
+        };
+        self.selected = position;
+        self.sync_list_state();
+        true
+    }
+
     /// Restore the selected index to the item with the given path.
     fn restore_selection_by_path(&mut self, path: Option<&str>) {
         let Some(target) = path else {
@@ -856,6 +972,8 @@ pub struct FileExplorerWidget<'a> {
     session_preview: Option<&'a SessionPreview>,
     /// Whether a backup exists for the selected file
     has_backup: bool,
+    /// Index into [`PREVIEW_STEPS`] for the terminal preview
+    preview_step: usize,
 }
 
 impl<'a> FileExplorerWidget<'a> {
@@ -867,6 +985,7 @@ impl<'a> FileExplorerWidget<'a> {
             show_checkboxes: true,
             session_preview: None,
             has_backup: false,
+            preview_step: DEFAULT_PREVIEW_STEP,
         }
     }
 
@@ -893,6 +1012,12 @@ impl<'a> FileExplorerWidget<'a> {
         self.has_backup = has_backup;
         self
     }
+
+    /// Set which [`PREVIEW_STEPS`] position the terminal preview shows
+    pub fn preview_step(mut self, step: usize) -> Self {
+        self.preview_step = step.min(PREVIEW_STEPS.len() - 1);
+        self
+    }
 }
 
 impl Widget for FileExplorerWidget<'_> {
@@ -913,7 +1038,7 @@ impl Widget for FileExplorerWidget<'_> {
             .visible_items()
             .map(|(_, item, is_checked)| {
                 (
-                    item.name.clone(),
+                    item.display_name().to_string(),
                     item.agent.clone(),
                     format_size(item.size),
                     is_checked,
@@ -970,6 +1095,7 @@ impl Widget for FileExplorerWidget<'_> {
                     item.modified,
                     item.path.clone(),
                     item.lock_info.clone(),
+                    item.has_notes,
                 )
             })
         } else {
@@ -977,11 +1103,16 @@ impl Widget for FileExplorerWidget<'_> {
         };
 
         // Clone session preview data to avoid lifetime issues
+        let preview_step = self.preview_step;
         let session_preview_data = self.session_preview.map(|p| {
             (
                 p.format_duration(),
                 p.marker_count,
-                p.styled_preview.clone(),
+                p.rendered_lines(preview_step)
+                    .iter()
+                    .take(12)
+                    .cloned()
+                    .collect::<Vec<_>>(),
             )
         });
 
@@ -1005,12 +1136,19 @@ impl Widget for FileExplorerWidget<'_> {
             .highlight_symbol("> ");
 
         // Render with state (mutable borrow here)
+        let empty_message = self.explorer.empty_message();
         ratatui::widgets::StatefulWidget::render(list, chunks[0], buf, self.explorer.list_state());
+        if let Some(message) = empty_message {
+            let inner = chunks[0].inner(ratatui::layout::Margin::new(2, 1));
+            Paragraph::new(message)
+                .style(theme.text_secondary_style())
+                .render(inner, buf);
+        }
 
         // Render preview panel if enabled
         if self.show_preview && chunks.len() > 1 {
             let preview_text =
-                if let Some((name, agent, size, modified, path, lock_data)) = preview_data {
+                if let Some((name, agent, size, modified, path, lock_data, notes)) = preview_data {
                     let mut lines = vec![
                         Line::from(vec![
                             Span::styled("Name: ", theme.text_secondary_style()),
@@ -1027,7 +1165,7 @@ impl Widget for FileExplorerWidget<'_> {
                     ];
 
                     // Add duration and markers if session preview is available
-                    if let Some((duration, markers, styled_preview)) = session_preview_data {
+                    if let Some((duration, markers, preview_lines)) = session_preview_data {
                         lines.push(Line::from(vec![
                             Span::styled("Duration: ", theme.text_secondary_style()),
                             Span::styled(duration, theme.text_style()),
@@ -1048,6 +1186,12 @@ impl Widget for FileExplorerWidget<'_> {
                                 ),
                             ]));
                         }
+                        if notes {
+                            lines.push(Line::from(vec![
+                                Span::styled("Notes: ", theme.text_secondary_style()),
+                                Span::styled("Yes (agr note show)", theme.accent_style()),
+                            ]));
+                        }
                         // Show lock/recording status
                         if let Some(ref lock) = lock_data {
                             lines.push(Line::from(vec![
@@ -1067,18 +1211,18 @@ impl Widget for FileExplorerWidget<'_> {
                         ]));
 
                         // Add terminal preview section if not empty
-                        if !styled_preview.is_empty() {
+                        if !preview_lines.is_empty() {
                             lines.push(Line::from("")); // Empty line separator
                             lines.push(Line::from(vec![Span::styled(
-                                "Preview",
+                                format!(
+                                    "Preview at {}% (\u{2190}/\u{2192})",
+                                    PREVIEW_STEPS[preview_step]
+                                ),
                                 theme.text_secondary_style(),
                             )]));
 
                             // Add terminal preview lines with colors (limited to fit)
-                            for styled_line in styled_preview.iter().take(12) {
-                                // Prepend a space and convert to ratatui Line with colors
-                                let mut ratatui_line =
-                                    SessionPreview::styled_line_to_ratatui(styled_line);
+                            for mut ratatui_line in preview_lines {
                                 // Insert space at start
                                 if let Some(first_span) = ratatui_line.spans.first_mut() {
                                     *first_span = Span::styled(
@@ -1178,6 +1322,21 @@ mod tests {
         assert!(!explorer.is_empty());
     }
 
+    #[test]
+    fn empty_message_distinguishes_no_items_from_filtered_out() {
+        let mut explorer = FileExplorer::new(create_test_items());
+        assert_eq!(explorer.empty_message(), None);
+
+        explorer.set_search_filter(Some("nonexistent".to_string()));
+        assert!(explorer.empty_message().unwrap().contains("filter"));
+
+        let empty = FileExplorer::new(vec![]);
+        assert!(empty
+            .empty_message()
+            .unwrap()
+            .starts_with("No sessions left"));
+    }
+
     #[test]
     fn empty_explorer_is_empty() {
         let explorer = FileExplorer::new(vec![]);
@@ -1310,6 +1469,60 @@ mod tests {
         assert_eq!(explorer.selected_count(), 0);
     }
 
+    #[test]
+    fn selected_size_tracks_toggled_session_sizes() {
+        use std::path::PathBuf;
+
+        let session = |name: &str, size: u64| SessionInfo {
+            path: PathBuf::from(format!("/sessions/claude/{}", name)),
+            agent: "claude".to_string(),
+            filename: name.to_string(),
+            size,
+            modified: Local.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap(),
+            age_days: 0,
+            age_hours: 0,
+            age_minutes: 0,
+            pinned: false,
+            has_notes: false,
+        };
+        let items = vec![
+            FileItem::from(session("a.cast", 1000)),
+            FileItem::from(session("b.cast", 250)),
+            FileItem::from(session("c.cast", 4)),
+        ];
+        let mut explorer = FileExplorer::new(items);
+        explorer.set_sort(SortField::Name); // c, b, a
+        assert_eq!(explorer.selected_size(), 0);
+
+        explorer.toggle_select(); // c
+        explorer.down();
+        explorer.toggle_select(); // b
+        assert_eq!(explorer.selected_count(), 2);
+        assert_eq!(explorer.selected_size(), 254);
+
+        explorer.up();
+        explorer.toggle_select(); // c again
+        assert_eq!(explorer.selected_count(), 1);
+        assert_eq!(explorer.selected_size(), 250);
+
+        explorer.toggle_all();
+        assert_eq!(explorer.selected_size(), 1254);
+        explorer.select_none();
+        assert_eq!(explorer.selected_size(), 0);
+    }
+
+    #[test]
+    fn session_info_from_item_keeps_path_and_size() {
+        let items = create_test_items();
+        let info = SessionInfo::from(&items[1]);
+        assert_eq!(info.path, Path::new("/sessions/codex/session2.cast"));
+        assert_eq!(info.filename, "session2.cast");
+        assert_eq!(info.agent, "codex");
+        assert_eq!(info.size, 2048);
+        assert_eq!(info.modified, items[1].modified);
+        assert!(info.age_days > 0);
+    }
+
     #[test]
     fn toggle_all_toggles_between_all_and_none() {
         let mut explorer = FileExplorer::new(create_test_items());
@@ -1480,6 +1693,53 @@ mod tests {
         assert!(explorer.search_filter().is_none());
     }
 
+    fn visible_names(explorer: &FileExplorer) -> Vec<&str> {
+        explorer
+            .visible_items()
+            .map(|(_, item, _)| item.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn search_filter_fuzzy_matches_subsequence() {
+        let mut explorer = FileExplorer::new(create_test_items());
+        explorer.set_search_filter(Some("ssn3".to_string()));
+        assert_eq!(visible_names(&explorer), vec!["session3.cast"]);
+    }
+
+    #[test]
+    fn search_filter_matches_agent() {
+        let mut explorer = FileExplorer::new(create_test_items());
+        explorer.set_search_filter(Some("cdx".to_string()));
+        assert_eq!(visible_names(&explorer), vec!["session2.cast"]);
+    }
+
+    #[test]
+    fn search_filter_matches_title() {
+        let mut items = create_test_items();
+        items[2].title = Some("fix login bug".to_string());
+        let mut explorer = FileExplorer::new(items);
+        explorer.set_search_filter(Some("lgnbug".to_string()));
+        assert_eq!(visible_names(&explorer), vec!["session3.cast"]);
+    }
+
+    #[test]
+    fn search_filter_keeps_sort_order() {
+        let mut explorer = FileExplorer::new(create_test_items());
+        explorer.set_sort(SortField::Size);
+        explorer.set_search_filter(Some("cast".to_string()));
+        assert_eq!(
+            visible_names(&explorer),
+            vec!["session2.cast", "session1.cast", "session3.cast"]
+        );
+        // Narrowing the query keeps the remaining items in size order
+        explorer.set_search_filter(Some("claude".to_string()));
+        assert_eq!(
+            visible_names(&explorer),
+            vec!["session1.cast", "session3.cast"]
+        );
+    }
+
     #[test]
     fn search_filter_getter_returns_value() {
         let mut explorer = FileExplorer::new(create_test_items());
@@ -1502,6 +1762,8 @@ mod tests {
             age_days: 0,
             age_hours: 0,
             age_minutes: 0,
+            pinned: false,
+            has_notes: false,
         };
 
         let item = FileItem::from(session);
@@ -1515,31 +1777,210 @@ mod tests {
 
     #[test]
     fn session_preview_format_duration_seconds() {
-        let preview = SessionPreview {
-            duration_secs: 45.0,
-            marker_count: 0,
-            styled_preview: Vec::new(),
-        };
+        let preview = SessionPreview::new(45.0, 0, Vec::new());
         assert_eq!(preview.format_duration(), "45s");
     }
 
     #[test]
     fn session_preview_format_duration_minutes() {
-        let preview = SessionPreview {
-            duration_secs: 332.0, // 5m 32s
-            marker_count: 0,
-            styled_preview: Vec::new(),
-        };
+        let preview = SessionPreview::new(332.0, 0, Vec::new()); // 5m 32s
         assert_eq!(preview.format_duration(), "5m 32s");
     }
 
     #[test]
     fn session_preview_format_duration_hours() {
-        let preview = SessionPreview {
-            duration_secs: 3732.0, // 1h 2m 12s
-            marker_count: 0,
-            styled_preview: Vec::new(),
-        };
+        let preview = SessionPreview::new(3732.0, 0, Vec::new()); // 1h 2m 12s
         assert_eq!(preview.format_duration(), "1h 2m 12s");
     }
+
+    fn line_of(text: &str, style: CellStyle) -> StyledLine {
+        StyledLine {
+            cells: text
+                .chars()
+                .map(|c| crate::terminal::Cell { char: c, style })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn cell_style_default_maps_to_default_style() {
+        let style = SessionPreview::cell_style_to_ratatui(&CellStyle::default());
+        assert_eq!(style, Style::default());
+    }
+
+    #[test]
+    fn cell_style_maps_fg_and_bg() {
+        let style = SessionPreview::cell_style_to_ratatui(&CellStyle {
+            fg: Color::Red,
+            bg: Color::Rgb(1, 2, 3),
+            ..CellStyle::default()
+        });
+        assert_eq!(style.fg, Some(ratatui::style::Color::Red));
+        assert_eq!(style.bg, Some(ratatui::style::Color::Rgb(1, 2, 3)));
+        assert!(style.add_modifier.is_empty());
+    }
+
+    #[test]
+    fn cell_style_maps_bright_and_indexed_colors() {
+        let style = SessionPreview::cell_style_to_ratatui(&CellStyle {
+            fg: Color::BrightBlack,
+            bg: Color::Indexed(208),
+            ..CellStyle::default()
+        });
+        assert_eq!(style.fg, Some(ratatui::style::Color::DarkGray));
+        assert_eq!(style.bg, Some(ratatui::style::Color::Indexed(208)));
+    }
+
+    #[test]
+    fn cell_style_maps_bold_and_reverse() {
+        let style = SessionPreview::cell_style_to_ratatui(&CellStyle {
+            bold: true,
+            reverse: true,
+            ..CellStyle::default()
+        });
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+        assert!(style.add_modifier.contains(Modifier::REVERSED));
+        assert!(!style.add_modifier.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn styled_line_groups_cells_by_style() {
+        let mut line = line_of("ab", CellStyle::default());
+        line.cells.extend(
+            line_of(
+                "cd",
+                CellStyle {
+                    fg: Color::Green,
+                    ..CellStyle::default()
+                },
+            )
+            .cells,
+        );
+        let converted = SessionPreview::styled_line_to_ratatui(&line);
+        assert_eq!(converted.spans.len(), 2);
+        assert_eq!(converted.spans[0].content, "ab");
+        assert_eq!(converted.spans[1].content, "cd");
+        assert_eq!(
+            converted.spans[1].style.fg,
+            Some(ratatui::style::Color::Green)
+        );
+    }
+
+    #[test]
+    fn rendered_lines_are_cached_per_step() {
+        let preview = SessionPreview::new(
+            1.0,
+            0,
+            vec![
+                vec![line_of("start", CellStyle::default())],
+                vec![line_of("later", CellStyle::default())],
+            ],
+        );
+        let first = preview.rendered_lines(1).as_ptr();
+        assert_eq!(preview.rendered_lines(1).as_ptr(), first);
+        assert_eq!(preview.rendered_lines(0)[0].spans[0].content, "start");
+        assert_eq!(preview.rendered_lines(1)[0].spans[0].content, "later");
+    }
+
+    #[test]
+    fn snapshot_step_is_clamped() {
+        let preview =
+            SessionPreview::new(1.0, 0, vec![vec![line_of("only", CellStyle::default())]]);
+        assert_eq!(preview.snapshot(4).len(), 1);
+        assert_eq!(preview.rendered_lines(4)[0].spans[0].content, "only");
+        assert!(SessionPreview::new(1.0, 0, Vec::new())
+            .rendered_lines(0)
+            .is_empty());
+    }
+
+    fn write_preview_cast(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        let path = dir.path().join("rerecorded.cast");
+        std::fs::write(
+            &path,
+            "{\"version\":3,\"term\":{\"cols\":20,\"rows\":2}}\n[0.5,\"o\",\"a\"]\n",
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn preview_is_stale_once_file_mtime_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_preview_cast(&dir);
+
+        let mut preview = SessionPreview::load(&path).unwrap();
+        assert!(preview.modified.is_some());
+        assert!(preview.is_current(&path));
+
+        // As if the file had been rewritten since the preview was loaded
+        preview.modified = Some(SystemTime::UNIX_EPOCH);
+        assert!(!preview.is_current(&path));
+
+        let loaded = SessionPreview::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!loaded.is_current(&path));
+        assert!(SessionPreview::new(1.0, 0, Vec::new()).is_current(&path));
+    }
+
+    #[test]
+    fn preview_cache_reloads_after_mtime_change() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_preview_cast(&dir);
+        let key = path.to_string_lossy().to_string();
+        let mut cache = crate::tui::lru_cache::new_preview_cache(20);
+
+        let mut stale = SessionPreview::load(&path).unwrap();
+        stale.modified = Some(SystemTime::UNIX_EPOCH);
+        cache.insert(key.clone(), stale);
+        assert!(cache.get(&key).is_none(), "stale preview must miss");
+
+        cache.request(key.clone());
+        for _ in 0..100 {
+            cache.poll();
+            if !cache.is_pending(&key) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let reloaded = cache.get(&key).expect("preview reloaded");
+        assert_ne!(reloaded.modified, Some(SystemTime::UNIX_EPOCH));
+        assert!(reloaded.is_current(&path));
+    }
+
+    #[test]
+    fn load_captures_snapshot_per_step() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("steps.cast");
+        // Each letter is printed one second apart over a 4 second recording
+        std::fs::write(
+            &path,
+            "{\"version\":3,\"term\":{\"cols\":20,\"rows\":2}}\n\
+             [0.5,\"o\",\"a\"]\n[1.0,\"o\",\"b\"]\n[1.0,\"m\",\"mark\"]\n\
+             [1.0,\"o\",\"c\"]\n[1.0,\"o\",\"d\"]\n",
+        )
+        .unwrap();
+
+        let preview = SessionPreview::load(&path).unwrap();
+        assert_eq!(preview.snapshots.len(), PREVIEW_STEPS.len());
+        assert_eq!(preview.marker_count, 1);
+        assert!((preview.duration_secs - 4.5).abs() < 1e-9);
+
+        let first_row = |step: usize| -> String {
+            preview
+                .snapshot(step)
+                .first()
+                .map(|l| l.cells.iter().map(|c| c.char).collect::<String>())
+                .unwrap_or_default()
+                .trim_end()
+                .to_string()
+        };
+        assert_eq!(first_row(0), "");
+        // 25% of 4.5s = 1.125s: only "a" (t=0.5) has been printed
+        assert_eq!(first_row(1), "a");
+        // 50% = 2.25s: "a", "b" (t=1.5); marker at t=2.5 not yet
+        assert_eq!(first_row(2), "ab");
+        // 75% = 3.375s: "c" arrives at t=3.5
+        assert_eq!(first_row(3), "ab");
+        assert_eq!(first_row(4), "abcd");
+    }
 }
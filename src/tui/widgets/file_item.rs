@@ -105,6 +105,8 @@ mod tests {
             age_days: 0,
             age_hours: 0,
             age_minutes: 0,
+            pinned: false,
+            has_notes: false,
         };
 
         let item = FileItem::from(session);
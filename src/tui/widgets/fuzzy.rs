@@ -0,0 +1,126 @@
+//! Fuzzy matching for the file explorer search
+//!
+//! A query matches when its characters appear in order in the text
+//! (case-insensitive), not necessarily next to each other. Matches are
+//! scored so that tighter matches rank higher: consecutive characters and
+//! characters at the start of a word earn bonuses, skipped characters cost
+//! a small penalty.
+
+/// Points for each matched character.
+const MATCH_SCORE: i64 = 16;
+/// Bonus when a matched character directly follows the previous match.
+const CONSECUTIVE_BONUS: i64 = 8;
+/// Bonus when a matched character starts the text or a word.
+const WORD_START_BONUS: i64 = 10;
+/// Penalty per unmatched character between two matches.
+const GAP_PENALTY: i64 = 1;
+
+/// Score how well `query` fuzzy-matches `text`.
+///
+/// Returns `None` if the query is not a subsequence of the text, otherwise
+/// a score where higher means a better match. An empty query matches
+/// everything with a score of 0.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut last_match: Option<usize> = None;
+    let mut prev: Option<char> = None;
+
+    for (i, c) in text.chars().flat_map(char::to_lowercase).enumerate() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        if c == wanted {
+            score += MATCH_SCORE;
+            match last_match {
+                Some(last) if last + 1 == i => score += CONSECUTIVE_BONUS,
+                Some(last) => score -= (i - last - 1) as i64 * GAP_PENALTY,
+                None => {}
+            }
+            if prev.map_or(true, is_separator) {
+                score += WORD_START_BONUS;
+            }
+            last_match = Some(i);
+            query_chars.next();
+        }
+        prev = Some(c);
+    }
+
+    query_chars.peek().is_none().then_some(score)
+}
+
+/// Best score of `query` against any of `fields`.
+pub fn best_score<'a>(query: &str, fields: impl IntoIterator<Item = &'a str>) -> Option<i64> {
+    fields
+        .into_iter()
+        .filter_map(|field| fuzzy_score(query, field))
+        .max()
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, ' ' | '-' | '_' | '.' | '/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_with_zero_score() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+        assert_eq!(fuzzy_score("", ""), Some(0));
+    }
+
+    #[test]
+    fn subsequence_matches() {
+        assert!(fuzzy_score("sss", "session.cast").is_some());
+        assert!(fuzzy_score("ss1", "session1.cast").is_some());
+    }
+
+    #[test]
+    fn out_of_order_does_not_match() {
+        assert_eq!(fuzzy_score("ba", "ab"), None);
+        assert_eq!(fuzzy_score("abc", "ab"), None);
+    }
+
+    #[test]
+    fn matching_is_case_insensitive() {
+        assert_eq!(
+            fuzzy_score("SESS", "session"),
+            fuzzy_score("sess", "session")
+        );
+        assert!(fuzzy_score("sess", "SESSION").is_some());
+    }
+
+    #[test]
+    fn consecutive_beats_scattered() {
+        let tight = fuzzy_score("abc", "abcxxx").unwrap();
+        let loose = fuzzy_score("abc", "axbxcx").unwrap();
+        assert!(tight > loose);
+    }
+
+    #[test]
+    fn word_start_beats_mid_word() {
+        let start = fuzzy_score("fix", "bug-fix.cast").unwrap();
+        let middle = fuzzy_score("fix", "prefixed.cast").unwrap();
+        assert!(start > middle);
+    }
+
+    #[test]
+    fn exact_score_for_simple_match() {
+        // 'a' at start: 16 + 10; 'b' consecutive: 16 + 8
+        assert_eq!(fuzzy_score("ab", "ab"), Some(50));
+        // 'a' at start: 16 + 10; 'c' after one gap: 16 - 1
+        assert_eq!(fuzzy_score("ac", "abc"), Some(41));
+    }
+
+    #[test]
+    fn best_score_picks_highest_field() {
+        let fields = ["xxclaude", "claude"];
+        assert_eq!(
+            best_score("claude", fields),
+            fuzzy_score("claude", "claude")
+        );
+        assert_eq!(best_score("zzz", fields), None);
+    }
+}
@@ -3,10 +3,12 @@
 //! Reusable UI components for the terminal interface.
 
 pub mod file_explorer;
+pub mod fuzzy;
 pub mod logo;
 pub mod preview;
 
 pub use file_explorer::{
     FileExplorer, FileExplorerWidget, FileItem, SessionPreview, SortDirection, SortField,
+    DEFAULT_PREVIEW_STEP, PREVIEW_STEPS,
 };
 pub use logo::Logo;
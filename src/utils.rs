@@ -1,3 +1,4 @@
 //! Shared utilities for cross-cutting concerns.
 
+pub mod log_file;
 pub mod process_guard;
@@ -0,0 +1,406 @@
+//! Opt-in diagnostic log file for `tracing` output.
+//!
+//! Enabled by `[recording] log_file` or the `AGR_LOG` environment variable
+//! (which takes precedence). When neither is set no subscriber is installed,
+//! so `tracing` macros stay no-ops.
+//!
+//! Each line is `<time> <LEVEL> <span chain>: <message> <fields>`, e.g.
+//!
+//! ```text
+//! 2026-01-29T14:30:00.125 INFO record{agent=claude}:spawn{command="claude"}: asciinema started
+//! ```
+//!
+//! Spans also log a `close` line with their final fields and elapsed time,
+//! so values recorded after creation (exit status, byte counts) are kept.
+//! When the file grows past [`MAX_LOG_BYTES`] it is rotated to `<path>.1`
+//! on the next start.
+
+use std::collections::HashMap;
+use std::fmt::{self, Write as _};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Environment variable naming the log file (overrides the config).
+pub const LOG_ENV: &str = "AGR_LOG";
+
+/// Size after which the log is rotated on startup.
+pub const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Resolve the log file path: `AGR_LOG` first, then the config value.
+///
+/// Empty values count as unset; `~/` is expanded.
+pub fn log_path(configured: Option<&str>) -> Option<PathBuf> {
+    let env = std::env::var(LOG_ENV).ok();
+    let raw = env
+        .as_deref()
+        .or(configured)
+        .map(str::trim)
+        .filter(|p| !p.is_empty())?;
+    if let Some(stripped) = raw.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return Some(home.join(stripped));
+        }
+    }
+    Some(PathBuf::from(raw))
+}
+
+/// Install the file subscriber as the global default if a log path is set.
+///
+/// Returns the path being logged to. Does nothing (and costs nothing) when
+/// logging is not configured.
+pub fn init(configured: Option<&str>) -> Result<Option<PathBuf>> {
+    let Some(path) = log_path(configured) else {
+        return Ok(None);
+    };
+    let subscriber = FileSubscriber::open(&path)?;
+    tracing::subscriber::set_global_default(subscriber)
+        .context("A tracing subscriber is already installed")?;
+    Ok(Some(path))
+}
+
+/// Move `path` to `<path>.1` if it has grown past `max_bytes`.
+fn rotate(path: &Path, max_bytes: u64) -> Result<()> {
+    let Ok(metadata) = fs::metadata(path) else {
+        return Ok(());
+    };
+    if metadata.len() > max_bytes {
+        let mut rotated = path.as_os_str().to_owned();
+        rotated.push(".1");
+        fs::rename(path, &rotated)
+            .with_context(|| format!("Failed to rotate log file: {:?}", path))?;
+    }
+    Ok(())
+}
+
+/// Open span state.
+struct SpanData {
+    name: &'static str,
+    fields: String,
+    refs: usize,
+    opened: Instant,
+}
+
+/// Minimal `tracing` subscriber that appends formatted lines to a file.
+pub struct FileSubscriber {
+    file: Mutex<File>,
+    spans: Mutex<HashMap<u64, SpanData>>,
+    /// Entered spans per thread, innermost last
+    stacks: Mutex<HashMap<ThreadId, Vec<u64>>>,
+    next_id: AtomicU64,
+}
+
+impl FileSubscriber {
+    /// Open (appending) the log file, creating parent directories and
+    /// rotating an oversized previous log.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create log directory: {:?}", parent))?;
+        }
+        rotate(path, MAX_LOG_BYTES)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open log file: {:?}", path))?;
+        Ok(Self {
+            file: Mutex::new(file),
+            spans: Mutex::new(HashMap::new()),
+            stacks: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    /// `outer{fields}:inner{fields}` for the current thread's span stack.
+    fn span_chain(&self, extra: Option<u64>) -> String {
+        let stacks = self.stacks.lock().unwrap();
+        let spans = self.spans.lock().unwrap();
+        let stack = stacks.get(&thread::current().id());
+        let ids = stack.into_iter().flatten().copied().chain(extra);
+
+        let mut chain = String::new();
+        for id in ids {
+            if let Some(span) = spans.get(&id) {
+                if !chain.is_empty() {
+                    chain.push(':');
+                }
+                let _ = write!(chain, "{}{{{}}}", span.name, span.fields);
+            }
+        }
+        chain
+    }
+
+    fn write_line(&self, level: &str, chain: &str, body: &str) {
+        let mut line = format!(
+            "{} {}",
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f"),
+            level
+        );
+        if !chain.is_empty() {
+            line.push(' ');
+            line.push_str(chain);
+            line.push(':');
+        }
+        if !body.is_empty() {
+            line.push(' ');
+            line.push_str(body);
+        }
+        line.push('\n');
+        // Logging must never take a recording down; drop lines on I/O errors
+        let _ = self.file.lock().unwrap().write_all(line.as_bytes());
+    }
+}
+
+impl Subscriber for FileSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= tracing::Level::INFO
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::INFO)
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        self.spans.lock().unwrap().insert(
+            id,
+            SpanData {
+                name: attrs.metadata().name(),
+                fields: visitor.fields,
+                refs: 1,
+                opened: Instant::now(),
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Some(data) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            let mut visitor = FieldVisitor {
+                fields: std::mem::take(&mut data.fields),
+                message: None,
+            };
+            values.record(&mut visitor);
+            data.fields = visitor.fields;
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let mut body = visitor.message.unwrap_or_default();
+        if !visitor.fields.is_empty() {
+            if !body.is_empty() {
+                body.push(' ');
+            }
+            body.push_str(&visitor.fields);
+        }
+        let chain = self.span_chain(None);
+        self.write_line(event.metadata().level().as_str(), &chain, &body);
+    }
+
+    fn enter(&self, span: &Id) {
+        self.stacks
+            .lock()
+            .unwrap()
+            .entry(thread::current().id())
+            .or_default()
+            .push(span.into_u64());
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut stacks = self.stacks.lock().unwrap();
+        if let Some(stack) = stacks.get_mut(&thread::current().id()) {
+            if let Some(pos) = stack.iter().rposition(|&id| id == span.into_u64()) {
+                stack.remove(pos);
+            }
+        }
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(data) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            data.refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let id = span.into_u64();
+        let elapsed = {
+            let mut spans = self.spans.lock().unwrap();
+            match spans.get_mut(&id) {
+                Some(data) if data.refs > 1 => {
+                    data.refs -= 1;
+                    return false;
+                }
+                Some(data) => data.opened.elapsed(),
+                None => return false,
+            }
+        };
+
+        let chain = self.span_chain(Some(id));
+        self.write_line(
+            "INFO",
+            &chain,
+            &format!("close elapsed_ms={}", elapsed.as_millis()),
+        );
+        self.spans.lock().unwrap().remove(&id);
+        true
+    }
+}
+
+/// Collects fields as `key=value` pairs, keeping `message` separate.
+#[derive(Default)]
+struct FieldVisitor {
+    fields: String,
+    message: Option<String>,
+}
+
+impl FieldVisitor {
+    fn push(&mut self, field: &Field, value: fmt::Arguments<'_>) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+            return;
+        }
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={}", field.name(), value);
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" || is_bare(value) {
+            self.push(field, format_args!("{}", value));
+        } else {
+            self.push(field, format_args!("{:?}", value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.push(field, format_args!("{:?}", value));
+    }
+}
+
+/// Whether a string value can be written without quotes.
+fn is_bare(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Emit the spans `Recorder::record` produces, without asciinema.
+    fn simulate_recording() {
+        let record = tracing::info_span!("record", agent = "claude");
+        let _record = record.enter();
+        {
+            let spawn = tracing::info_span!("spawn", command = "claude --model x");
+            let _spawn = spawn.enter();
+            tracing::info!("asciinema started");
+        }
+        {
+            let wait = tracing::info_span!(
+                "wait",
+                exit_status = tracing::field::Empty,
+                bytes = tracing::field::Empty
+            );
+            let _wait = wait.enter();
+            wait.record("exit_status", 0);
+            wait.record("bytes", 2048u64);
+        }
+        {
+            let rename = tracing::info_span!("rename", from = "a.cast", to = "b.cast");
+            let _rename = rename.enter();
+        }
+        tracing::trace!("too verbose to log");
+    }
+
+    fn log_simulated(path: &Path) -> String {
+        let subscriber = FileSubscriber::open(path).unwrap();
+        tracing::subscriber::with_default(subscriber, simulate_recording);
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn simulated_recording_writes_span_fields() {
+        let dir = TempDir::new().unwrap();
+        let log = log_simulated(&dir.path().join("logs/record.log"));
+
+        assert!(log.contains(
+            r#"INFO record{agent=claude}:spawn{command="claude --model x"}: asciinema started"#
+        ));
+        assert!(log.contains("record{agent=claude}:wait{exit_status=0 bytes=2048}: close"));
+        assert!(log.contains("record{agent=claude}:rename{from=a.cast to=b.cast}: close"));
+        assert!(log.contains("record{agent=claude}: close elapsed_ms="));
+        assert!(!log.contains("too verbose"));
+    }
+
+    #[test]
+    fn log_appends_across_sessions() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("record.log");
+        log_simulated(&path);
+        let log = log_simulated(&path);
+        assert_eq!(log.matches("asciinema started").count(), 2);
+    }
+
+    #[test]
+    fn oversized_log_is_rotated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("record.log");
+        fs::write(&path, "old\n").unwrap();
+
+        rotate(&path, 1).unwrap();
+
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("record.log.1")).unwrap(),
+            "old\n"
+        );
+    }
+
+    #[test]
+    fn small_log_is_not_rotated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("record.log");
+        fs::write(&path, "old\n").unwrap();
+        rotate(&path, MAX_LOG_BYTES).unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn log_path_uses_config_value() {
+        // AGR_LOG is not set in the test environment
+        if std::env::var(LOG_ENV).is_ok() {
+            return;
+        }
+        assert_eq!(log_path(None), None);
+        assert_eq!(log_path(Some("  ")), None);
+        assert_eq!(
+            log_path(Some("/tmp/agr.log")),
+            Some(PathBuf::from("/tmp/agr.log"))
+        );
+    }
+}
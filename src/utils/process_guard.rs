@@ -18,6 +18,12 @@ use std::time::Duration;
 
 use anyhow::Result;
 
+/// How often `wait_or_kill` checks the child and termination conditions.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long a stopped child gets to exit after SIGTERM before it is killed.
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
 /// Guards a child process against becoming an orphan.
 ///
 /// Create before spawning, register signals, then use `wait_or_kill` instead of `.status()`.
@@ -68,18 +74,55 @@ impl ProcessGuard {
     /// - Signal handler set the interrupted flag (SIGINT/SIGHUP)
     /// - Parent process died (detected via ppid change)
     pub fn wait_or_kill(&self, child: &mut Child) -> Result<ExitStatus> {
+        self.wait_or_stop(child, || false).map(|(status, _)| status)
+    }
+
+    /// Like [`wait_or_kill`](Self::wait_or_kill), but also stops the child
+    /// when `should_stop` returns true.
+    ///
+    /// `should_stop` is checked on every poll. A stop asks the child to exit
+    /// with SIGTERM so it can finish writing its output, and only kills it
+    /// if it is still running after a grace period. Returns the exit status
+    /// and whether `should_stop` triggered the stop.
+    pub fn wait_or_stop(
+        &self,
+        child: &mut Child,
+        mut should_stop: impl FnMut() -> bool,
+    ) -> Result<(ExitStatus, bool)> {
         loop {
             match child.try_wait()? {
-                Some(status) => return Ok(status),
+                Some(status) => return Ok((status, false)),
                 None => {
                     if self.should_terminate() {
                         let _ = child.kill();
-                        return child.wait().map_err(Into::into);
+                        return Ok((child.wait()?, false));
+                    }
+                    if should_stop() {
+                        return Ok((Self::terminate_gracefully(child)?, true));
                     }
-                    std::thread::sleep(Duration::from_millis(100));
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+        }
+    }
+
+    /// Send SIGTERM, wait up to `STOP_GRACE_PERIOD`, then kill.
+    fn terminate_gracefully(child: &mut Child) -> Result<ExitStatus> {
+        #[cfg(unix)]
+        {
+            unsafe {
+                libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+            }
+            let deadline = std::time::Instant::now() + STOP_GRACE_PERIOD;
+            while std::time::Instant::now() < deadline {
+                if let Some(status) = child.try_wait()? {
+                    return Ok(status);
                 }
+                std::thread::sleep(POLL_INTERVAL);
             }
         }
+        let _ = child.kill();
+        child.wait().map_err(Into::into)
     }
 
     /// Check all termination conditions: signal flag or parent death.
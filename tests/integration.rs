@@ -66,6 +66,9 @@ mod terminal_editing_test;
 #[path = "integration/terminal_style_test.rs"]
 mod terminal_style_test;
 
+#[path = "integration/terminal_image_test.rs"]
+mod terminal_image_test;
+
 #[path = "integration/snapshot_player_test.rs"]
 mod snapshot_player_test;
 
@@ -89,3 +92,6 @@ mod file_explorer_test;
 
 #[path = "integration/process_guard_test.rs"]
 mod process_guard_test;
+
+#[path = "integration/validate_test.rs"]
+mod validate_test;
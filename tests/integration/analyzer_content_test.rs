@@ -4,8 +4,8 @@
 //! on realistic input data derived from actual agent sessions.
 
 use agr::analyzer::{
-    ContentCleaner, DeduplicateProgressLines, ExtractionConfig, FilterEmptyEvents,
-    NormalizeWhitespace,
+    ContentCleaner, ContentExtractor, DeduplicateProgressLines, ExtractionConfig, ExtractionLevel,
+    FilterEmptyEvents, NormalizeWhitespace,
 };
 use agr::asciicast::{Event, Transform};
 
@@ -300,3 +300,87 @@ fn filter_empty_preserves_all_non_output_events() {
     assert_eq!(events[2].event_type, agr::EventType::Input);
     assert!(events[3].is_output());
 }
+
+// ============================================================================
+// Full Pipeline Regression Tests
+// ============================================================================
+
+#[test]
+fn snapshot_extraction_stats_for_resize_stress_excerpt() {
+    let path = crate::helpers::fixtures_dir().join("resize_stress_excerpt.cast");
+    let mut cast = agr::AsciicastFile::parse(&path).unwrap();
+    let (cols, rows) = cast.terminal_size();
+
+    let content =
+        ContentExtractor::default().extract(&mut cast.events, cols as usize, rows as usize);
+
+    // Guards the whole extraction pipeline: any change to a transform that
+    // alters what `agr analyze` sends shows up as a snapshot diff here.
+    insta::assert_debug_snapshot!(
+        "extraction_stats_resize_stress_excerpt",
+        (content.segments.len(), content.total_tokens, &content.stats)
+    );
+}
+
+/// A noisy build session: similar compile lines, a warning repeated in
+/// every build and wide file dumps large enough to be truncated.
+fn noisy_build_session() -> Vec<Event> {
+    let mut events = Vec::new();
+    for round in 0..30 {
+        events.push(Event::output(
+            1.0,
+            format!("$ cargo build --round {}\r\n", round),
+        ));
+        for i in 0..20 {
+            events.push(Event::output(
+                0.05,
+                format!("   Compiling crate-{:03} v0.{}.{}\r\n", i, round, i % 7),
+            ));
+        }
+        events.push(Event::output(
+            0.05,
+            "warning: unused variable `config` in src/main.rs\r\n",
+        ));
+    }
+    for round in 0..3 {
+        events.push(Event::output(
+            3.0,
+            format!("$ cat src/generated_{}.rs\r\n", round),
+        ));
+        let dump: String = (0..120)
+            .map(|i| {
+                format!(
+                    "pub const VALUE_{}_{}: &str = \"{}\";\r\n",
+                    round,
+                    i,
+                    "x".repeat(150)
+                )
+            })
+            .collect();
+        events.push(Event::output(0.1, dump));
+    }
+    events
+}
+
+#[test]
+fn extraction_levels_produce_monotonically_smaller_output() {
+    let extracted_bytes = |level: ExtractionLevel| {
+        let mut events = noisy_build_session();
+        ContentExtractor::new(ExtractionConfig::preset(level))
+            .extract(&mut events, 200, 50)
+            .stats
+            .extracted_bytes
+    };
+
+    let light = extracted_bytes(ExtractionLevel::Light);
+    let normal = extracted_bytes(ExtractionLevel::Normal);
+    let aggressive = extracted_bytes(ExtractionLevel::Aggressive);
+
+    assert!(
+        light > normal && normal > aggressive,
+        "expected light > normal > aggressive, got {} / {} / {}",
+        light,
+        normal,
+        aggressive
+    );
+}
@@ -2,6 +2,7 @@
 
 use super::helpers::{load_fixture, temp_fixture};
 
+use agr::asciicast::{SnapshotAt, SnapshotFormat};
 use agr::{AsciicastFile, Event, EventType};
 
 // === Fixture-based tests (existing) ===
@@ -193,3 +194,80 @@ fn rejects_non_v3_files() {
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("v3"));
 }
+
+// === Tolerant parsing ===
+
+#[test]
+fn parse_tolerant_skips_one_garbage_line_in_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("corrupt.cast");
+    let mut content = load_fixture("sample.cast");
+    let garbage_line = content.lines().count() + 1;
+    content.push_str("\u{0}\u{0}[0.1, \"o\"\n[0.1,\"o\",\"tail\"]\n");
+    std::fs::write(&path, &content).unwrap();
+
+    assert!(AsciicastFile::parse(&path).is_err());
+
+    let (cast, report) = AsciicastFile::parse_tolerant(&path).unwrap();
+    let expected = AsciicastFile::parse_str(&load_fixture("sample.cast"))
+        .unwrap()
+        .events
+        .len();
+    assert_eq!(cast.events.len(), expected + 1);
+    assert_eq!(cast.events.last().unwrap().data, "tail");
+    assert_eq!(report.skipped(), 1);
+    assert_eq!(report.skipped_lines, vec![garbage_line]);
+}
+
+#[test]
+fn parse_tolerant_rejects_broken_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broken.cast");
+    std::fs::write(&path, "{\"version\":3,\n[0.1,\"o\",\"x\"]\n").unwrap();
+
+    let err = AsciicastFile::parse_tolerant(&path).unwrap_err();
+    assert!(err.to_string().contains("header"));
+}
+
+// === Snapshot rendering ===
+
+#[test]
+fn snapshot_fixture_at_end_as_text() {
+    let content = load_fixture("player_snapshot.cast");
+    let cast = AsciicastFile::parse_str(&content).unwrap();
+    let at: SnapshotAt = "100%".parse().unwrap();
+
+    let snapshot = cast.snapshot_at(at.resolve(cast.duration()), SnapshotFormat::Text);
+    insta::assert_snapshot!("snapshot_player_fixture_end_text", snapshot);
+}
+
+#[test]
+fn snapshot_fixture_at_end_as_ansi() {
+    let content = load_fixture("player_snapshot.cast");
+    let cast = AsciicastFile::parse_str(&content).unwrap();
+    let at: SnapshotAt = "100%".parse().unwrap();
+
+    let snapshot = cast.snapshot_at(at.resolve(cast.duration()), SnapshotFormat::Ansi);
+    insta::assert_snapshot!(
+        "snapshot_player_fixture_end_ansi",
+        snapshot.replace('\x1b', "\\e")
+    );
+}
+
+#[test]
+fn snapshot_at_percentage_and_seconds() {
+    let content = load_fixture("sample.cast");
+    let cast = AsciicastFile::parse_str(&content).unwrap();
+    let duration = cast.duration();
+    let render = |at: SnapshotAt| cast.snapshot_at(at.resolve(duration), SnapshotFormat::Text);
+
+    // sample.cast is 0.8s long: output at 0.5s, 0.6s and 0.8s
+    assert_eq!(render(SnapshotAt::Percent(50.0)), "");
+    assert_eq!(render(SnapshotAt::Seconds(0.55)), "$ echo hello");
+    assert_eq!(render(SnapshotAt::Percent(70.0)), "$ echo hello");
+    assert_eq!(
+        render(SnapshotAt::Seconds(60.0)),
+        render(SnapshotAt::Percent(100.0))
+    );
+    assert_eq!(render(SnapshotAt::Percent(100.0)), "$ echo hello\nhello\n$");
+}
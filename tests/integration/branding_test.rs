@@ -1,10 +1,12 @@
 //! Unit tests for theme logo/branding
 
 use agr::theme::{
-    print_box_bottom, print_box_line, print_box_line_end, print_box_prompt, print_done_banner,
-    print_full_logo, print_start_banner, truncate_str, BOX_BOTTOM, BOX_WIDTH, LOGO_DONE, LOGO_FULL,
-    LOGO_START,
+    box_line, done_banner, full_logo, full_logo_fits, print_box_bottom, print_box_line,
+    print_box_line_end, print_box_prompt, print_done_banner, print_full_logo, print_start_banner,
+    start_banner, truncate_str, with_banners, BoxLayout, BOX_BOTTOM, BOX_WIDTH, LOGO_DONE,
+    LOGO_FULL, LOGO_START, MIN_BOX_WIDTH,
 };
+use unicode_width::UnicodeWidthStr;
 
 #[test]
 fn logo_full_is_not_empty() {
@@ -78,6 +80,91 @@ fn box_width_matches_bottom_border() {
     assert!(border_inner.chars().all(|c| c == '═'));
 }
 
+#[test]
+fn box_layout_is_full_size_when_it_fits_or_width_is_unknown() {
+    for columns in [None, Some(200), Some(80), Some(BOX_WIDTH as u16 + 2)] {
+        let layout = BoxLayout::for_terminal(columns);
+        assert_eq!(layout.width, BOX_WIDTH, "{:?}", columns);
+        assert!(!layout.compact, "{:?}", columns);
+    }
+    assert_eq!(BoxLayout::for_terminal(Some(80)).bottom(), BOX_BOTTOM);
+}
+
+#[test]
+fn box_layout_shrinks_to_narrow_terminal() {
+    let layout = BoxLayout::for_terminal(Some(BOX_WIDTH as u16 + 1));
+    assert_eq!(layout.width, BOX_WIDTH - 1);
+    assert!(layout.compact);
+
+    let layout = BoxLayout::for_terminal(Some(30));
+    assert_eq!(layout.width, 28);
+    assert!(layout.compact);
+}
+
+#[test]
+fn box_layout_never_shrinks_below_minimum() {
+    for columns in [0, 5, MIN_BOX_WIDTH as u16 + 1] {
+        let layout = BoxLayout::for_terminal(Some(columns));
+        assert_eq!(layout.width, MIN_BOX_WIDTH, "{}", columns);
+        assert!(layout.compact);
+    }
+}
+
+#[test]
+fn box_lines_are_padded_by_display_width() {
+    let layout = BoxLayout::for_terminal(Some(30));
+    for content in [
+        "  ⏺ claude/session.cast",
+        "日本語",
+        "a much longer line than the box can hold",
+    ] {
+        let line = layout.line(content);
+        assert_eq!(line.width(), layout.width + 2, "{}", content);
+        assert!(line.starts_with('║') && line.ends_with('║'));
+    }
+    assert_eq!(layout.bottom().width(), 30);
+}
+
+#[test]
+fn compact_banner_centers_title_in_shrunk_box() {
+    let layout = BoxLayout::for_terminal(Some(20));
+    let banner = layout.compact_banner("AGR ⏺ REC");
+    let lines: Vec<&str> = banner.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().all(|line| line.width() == 20));
+    assert_eq!(lines[1], "║    AGR ⏺ REC     ║");
+    assert!(lines[2].starts_with('╠'));
+}
+
+#[test]
+fn full_logo_falls_back_below_its_width() {
+    let logo_width = LOGO_FULL.lines().map(|l| l.width()).max().unwrap() as u16;
+    assert!(full_logo_fits(None));
+    assert!(full_logo_fits(Some(logo_width)));
+    assert!(!full_logo_fits(Some(logo_width - 1)));
+}
+
+#[test]
+fn banners_are_empty_when_suppressed() {
+    with_banners(false, || {
+        assert_eq!(start_banner(), "");
+        assert_eq!(done_banner(), "");
+        assert_eq!(full_logo(), "");
+        // The session line survives without its box
+        assert_eq!(box_line("  ⏺ claude/session.cast"), "⏺ claude/session.cast");
+    });
+}
+
+#[test]
+fn banners_are_printed_by_default() {
+    with_banners(true, || {
+        assert!(start_banner().contains("REC"));
+        assert!(done_banner().contains("DONE"));
+        assert!(!full_logo().is_empty());
+        assert!(box_line("  ⏺ claude/session.cast").contains('║'));
+    });
+}
+
 #[test]
 fn print_functions_do_not_panic() {
     // Simple coverage tests - just verify they run without panicking
@@ -90,3 +177,14 @@ fn print_functions_do_not_panic() {
     print_box_prompt("prompt: ");
     print_box_line_end();
 }
+
+#[test]
+fn print_functions_are_silent_without_banners() {
+    with_banners(false, || {
+        print_start_banner();
+        print_done_banner();
+        print_full_logo();
+        print_box_bottom();
+        print_box_line_end();
+    });
+}
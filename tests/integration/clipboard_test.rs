@@ -426,6 +426,68 @@ mod copy_tests {
         // Just verify it doesn't return FileTooLarge for small files
         assert!(!matches!(result, Err(ClipboardError::FileTooLarge { .. })));
     }
+
+    #[test]
+    fn select_tool_reports_first_available_in_order() {
+        use agr::clipboard::select_tool;
+
+        // Only wl-copy installed (Wayland without X11 tools)
+        let tools: Vec<Box<dyn CopyTool>> = vec![
+            Box::new(MockTool::new(CopyMethod::Xclip).available(false)),
+            Box::new(MockTool::new(CopyMethod::Xsel).available(false)),
+            Box::new(MockTool::new(CopyMethod::WlCopy)),
+        ];
+        assert_eq!(select_tool(&tools), Some("wl-copy"));
+
+        // xclip wins over later tools when both are installed
+        let tools: Vec<Box<dyn CopyTool>> = vec![
+            Box::new(MockTool::new(CopyMethod::Xclip)),
+            Box::new(MockTool::new(CopyMethod::WlCopy)),
+        ];
+        assert_eq!(select_tool(&tools), Some("xclip"));
+    }
+
+    #[test]
+    fn select_tool_returns_none_when_nothing_is_available() {
+        use agr::clipboard::select_tool;
+
+        let tools: Vec<Box<dyn CopyTool>> = vec![
+            Box::new(MockTool::new(CopyMethod::Pbcopy).available(false)),
+            Box::new(MockTool::new(CopyMethod::Xsel).available(false)),
+        ];
+        assert_eq!(select_tool(&tools), None);
+        assert_eq!(select_tool(&[]), None);
+    }
+
+    #[test]
+    fn text_copies_transcript_of_fixture() {
+        let cast =
+            agr::AsciicastFile::parse(crate::helpers::fixtures_dir().join("sample.cast")).unwrap();
+        let transcript = cast.transcript();
+        assert_eq!(transcript, "$ echo hello\nhello\n$");
+
+        let tool = MockTool::new(CopyMethod::Xclip).can_files(false);
+        let copy = Copy::with_tools(vec![Box::new(tool)]);
+        let result = copy.text(&transcript).unwrap();
+        assert_eq!(
+            result,
+            CopyResult::content_copied(CopyMethod::Xclip, transcript.len())
+        );
+    }
+
+    #[test]
+    fn text_returns_file_too_large_over_limit() {
+        let tool = MockTool::new(CopyMethod::Xclip);
+        let copy = Copy::with_tools(vec![Box::new(tool)]);
+
+        let text = "x".repeat(10 * 1024 * 1024 + 1);
+        let result = copy.text(&text);
+        assert!(matches!(
+            result,
+            Err(ClipboardError::FileTooLarge { max_mb: 10, .. })
+        ));
+        assert!(copy.text(&text[..1024]).is_ok());
+    }
 }
 
 // =============================================================================
@@ -192,6 +192,20 @@ directory = "~/custom"
 "#;
     let config: Config = toml::from_str(toml_str).unwrap();
     assert!(!config.recording.auto_analyze);
+    assert!(!config.recording.capture_input);
+    assert!(config.recording.no_input_echo);
+}
+
+#[test]
+fn recording_input_capture_config_parses_from_toml() {
+    let toml_str = r#"
+[recording]
+capture_input = true
+no_input_echo = false
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    assert!(config.recording.capture_input);
+    assert!(!config.recording.no_input_echo);
 }
 
 #[test]
@@ -243,3 +257,341 @@ fn storage_directory_handles_relative_path() {
     let path = config.storage_directory();
     assert_eq!(path, std::path::PathBuf::from("relative/path"));
 }
+
+#[test]
+fn analysis_extraction_config_parses_from_toml() {
+    let toml_str = r#"
+[analysis.extraction]
+window_size = 20
+similarity_threshold = 0.9
+max_block_size = 4096
+binary_threshold = 0.5
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    assert_eq!(config.analysis.extraction.window_size, Some(20));
+    assert_eq!(config.analysis.extraction.similarity_threshold, Some(0.9));
+    assert_eq!(config.analysis.extraction.max_block_size, Some(4096));
+    assert_eq!(config.analysis.extraction.binary_threshold, Some(0.5));
+    assert!(config.analysis.validate().is_ok());
+}
+
+#[test]
+fn analysis_extraction_config_defaults_to_unset() {
+    let config: Config = toml::from_str("[analysis]\ntimeout = 60\n").unwrap();
+    assert_eq!(config.analysis.extraction, Default::default());
+}
+
+#[test]
+fn analysis_extraction_config_rejects_out_of_range_values() {
+    for toml_str in [
+        "[analysis.extraction]\nsimilarity_threshold = 1.5\n",
+        "[analysis.extraction]\nsimilarity_threshold = -0.1\n",
+        "[analysis.extraction]\nwindow_size = 0\n",
+        "[analysis.extraction]\nmax_block_size = 10\n",
+        "[analysis.extraction]\nbinary_threshold = 2.0\n",
+        "[analysis.extraction]\nlevel = \"extreme\"\n",
+    ] {
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let err = config.analysis.validate().unwrap_err();
+        assert!(err.starts_with("analysis.extraction."), "{}", err);
+    }
+}
+
+#[test]
+fn analysis_prompt_template_parses_and_expands_home() {
+    let config: Config =
+        toml::from_str("[analysis]\nprompt_template = \"~/prompts/security.txt\"\n").unwrap();
+    assert!(config.analysis.validate().is_ok());
+    let path = config.analysis.prompt_template_path().unwrap();
+    assert!(path.ends_with("prompts/security.txt"));
+    assert!(!path.starts_with("~"));
+
+    let config: Config = toml::from_str("[analysis]\ntimeout = 60\n").unwrap();
+    assert_eq!(config.analysis.prompt_template_path(), None);
+}
+
+#[test]
+fn analysis_prompt_template_rejects_empty_path() {
+    let config: Config = toml::from_str("[analysis]\nprompt_template = \" \"\n").unwrap();
+    assert_eq!(
+        config.analysis.validate().unwrap_err(),
+        "analysis.prompt_template must not be empty"
+    );
+}
+
+#[test]
+fn per_agent_timeout_overrides_global_timeout() {
+    let config = Config::from_toml(
+        "[analysis]\ntimeout = 90\n[agents.codex]\ntimeout = 300\n",
+        None,
+    )
+    .unwrap();
+    assert_eq!(config.analysis_timeout("codex"), Some(300));
+    assert_eq!(config.analysis_timeout("claude"), Some(90));
+}
+
+#[test]
+fn per_agent_timeout_must_be_positive() {
+    for toml_str in [
+        "[agents.codex]\ntimeout = 0\n",
+        "[agents.gemini]\ntimeout = 7200\n",
+    ] {
+        let err = Config::from_toml(toml_str, None).unwrap_err();
+        assert!(format!("{:#}", err).contains(".timeout"), "{:#}", err);
+    }
+}
+
+#[test]
+fn recording_filename_template_accepts_context_tags() {
+    let config = Config::from_toml(
+        "[recording]\nfilename_template = \"{project}-{agent}-{counter}_{pid}\"\n",
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        config.recording.filename_template,
+        "{project}-{agent}-{counter}_{pid}"
+    );
+}
+
+#[test]
+fn recording_filename_template_rejects_unknown_tag_on_load() {
+    let err = Config::from_toml("[recording]\nfilename_template = \"{user}_{date}\"\n", None)
+        .unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(
+        message.contains("recording.filename_template: Unknown template tag: user"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn recording_window_size_parses_from_toml() {
+    let config = Config::from_toml("[recording]\ncols = 120\nrows = 40\n", None).unwrap();
+    assert_eq!(config.recording.cols, Some(120));
+    assert_eq!(config.recording.rows, Some(40));
+
+    let config = Config::from_toml("[recording]\n", None).unwrap();
+    assert!(config.recording.cols.is_none());
+    assert!(config.recording.rows.is_none());
+}
+
+#[test]
+fn recording_window_size_rejects_zero_on_load() {
+    let err = Config::from_toml("[recording]\nrows = 0\n", None).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(
+        message.contains("recording.rows must be greater than 0"),
+        "{}",
+        message
+    );
+}
+
+// === Diff from defaults ===
+
+fn two_field_config() -> Config {
+    Config::from_toml(
+        "[storage]\nage_threshold_days = 7\n\n[player]\ndefault_speed = 2.0\n",
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn diff_from_default_lists_only_customized_fields() {
+    use agr::config::diff::diff_from_default;
+
+    let diffs = diff_from_default(&two_field_config()).unwrap();
+
+    let paths: Vec<String> = diffs.iter().map(|d| d.path()).collect();
+    assert_eq!(
+        paths,
+        vec!["player.default_speed", "storage.age_threshold_days"]
+    );
+    assert_eq!(diffs[0].value, Some(toml::Value::Float(2.0)));
+    assert_eq!(diffs[0].default, Some(toml::Value::Float(1.0)));
+    assert_eq!(diffs[1].value, Some(toml::Value::Integer(7)));
+    assert_eq!(diffs[1].default, Some(toml::Value::Integer(30)));
+}
+
+#[test]
+fn diff_from_default_is_empty_for_defaults() {
+    use agr::config::diff::diff_from_default;
+
+    assert!(diff_from_default(&Config::default()).unwrap().is_empty());
+    assert!(diff_from_default(&Config::from_toml("", None).unwrap())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn diff_from_default_reports_set_optional_and_array_fields() {
+    use agr::config::diff::diff_from_default;
+
+    let config = Config::from_toml(
+        "[agents]\nenabled = [\"claude\"]\n\n[analysis]\nworkers = 2\n",
+        None,
+    )
+    .unwrap();
+    let diffs = diff_from_default(&config).unwrap();
+
+    let paths: Vec<String> = diffs.iter().map(|d| d.path()).collect();
+    assert_eq!(paths, vec!["agents.enabled", "analysis.workers"]);
+    assert_eq!(diffs[1].default, None);
+}
+
+#[test]
+fn format_diff_groups_by_section_with_defaults() {
+    use agr::config::diff::{diff_from_default, format_diff};
+
+    let diffs = diff_from_default(&two_field_config()).unwrap();
+    assert_eq!(
+        format_diff(&diffs),
+        "[player]\ndefault_speed = 2.0  # default: 1.0\n\n[storage]\nage_threshold_days = 7  # default: 30\n"
+    );
+}
+
+#[test]
+fn mark_defaults_tags_only_default_lines() {
+    use agr::config::diff::{diff_from_default, mark_defaults};
+
+    let config = two_field_config();
+    let toml_str = toml::to_string_pretty(&config).unwrap();
+    let marked = mark_defaults(&toml_str, &diff_from_default(&config).unwrap());
+
+    let line = |prefix: &str| {
+        marked
+            .lines()
+            .find(|l| l.starts_with(prefix))
+            .unwrap_or_else(|| panic!("no line starting with {:?} in\n{}", prefix, marked))
+            .to_string()
+    };
+    assert_eq!(line("age_threshold_days"), "age_threshold_days = 7");
+    assert_eq!(line("default_speed"), "default_speed = 2.0");
+    assert_eq!(
+        line("size_threshold_gb"),
+        "size_threshold_gb = 5.0  # (default)"
+    );
+    assert!(line("enabled = [").ends_with("# (default)"));
+    // Array items and section headers are never tagged
+    assert!(marked
+        .lines()
+        .filter(|l| l.starts_with('[') || l.starts_with("    "))
+        .all(|l| !l.contains("# (default)")));
+}
+
+// === Explicit config path ===
+
+/// Write a config under `$HOME/.config/agr` and an alternate one elsewhere.
+fn home_and_alternate_configs() -> (tempfile::TempDir, std::path::PathBuf) {
+    let home = tempfile::tempdir().unwrap();
+    let default_dir = home.path().join(".config").join("agr");
+    std::fs::create_dir_all(&default_dir).unwrap();
+    std::fs::write(
+        default_dir.join("config.toml"),
+        "[storage]\nage_threshold_days = 99\n",
+    )
+    .unwrap();
+    let alternate = home.path().join("alt.toml");
+    std::fs::write(&alternate, "[storage]\nage_threshold_days = 7\n").unwrap();
+    (home, alternate)
+}
+
+fn run_agr_with_home(home: &std::path::Path, args: &[&str], env: &[(&str, &str)]) -> String {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_agr"))
+        .args(args)
+        .env("HOME", home)
+        .env("NO_COLOR", "1")
+        .env_remove(agr::config::CONFIG_ENV)
+        .env_remove(agr::config::profile::PROFILE_ENV)
+        .envs(env.iter().copied())
+        .output()
+        .expect("Failed to execute agr");
+    assert!(
+        output.status.success(),
+        "agr {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn load_from_reads_explicit_path() {
+    let (_home, alternate) = home_and_alternate_configs();
+    let config = Config::load_from(&alternate).unwrap();
+    assert_eq!(config.storage.age_threshold_days, 7);
+}
+
+#[test]
+fn load_from_missing_path_gives_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = Config::load_from(&dir.path().join("missing.toml")).unwrap();
+    assert_eq!(config.storage.age_threshold_days, 30);
+}
+
+#[test]
+fn save_to_writes_explicit_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("alt.toml");
+    let mut config = Config::default();
+    config.storage.age_threshold_days = 12;
+
+    config.save_to(&path).unwrap();
+
+    assert_eq!(
+        Config::load_from(&path).unwrap().storage.age_threshold_days,
+        12
+    );
+}
+
+#[test]
+fn config_flag_overrides_default_location() {
+    let (home, alternate) = home_and_alternate_configs();
+    let alternate = alternate.to_str().unwrap();
+
+    let default = run_agr_with_home(home.path(), &["config", "show", "--diff"], &[]);
+    assert!(default.contains("age_threshold_days = 99"), "{}", default);
+
+    let flag = run_agr_with_home(
+        home.path(),
+        &["--config", alternate, "config", "show", "--diff"],
+        &[],
+    );
+    assert!(flag.contains("age_threshold_days = 7"), "{}", flag);
+
+    let env = run_agr_with_home(
+        home.path(),
+        &["config", "show", "--diff"],
+        &[(agr::config::CONFIG_ENV, alternate)],
+    );
+    assert!(env.contains("age_threshold_days = 7"), "{}", env);
+}
+
+#[test]
+fn config_flag_saves_to_alternate_file() {
+    let home = tempfile::tempdir().unwrap();
+    let alternate = home.path().join("alt.toml");
+
+    run_agr_with_home(
+        home.path(),
+        &[
+            "agents",
+            "add",
+            "my-agent",
+            "--config",
+            alternate.to_str().unwrap(),
+        ],
+        &[],
+    );
+
+    let saved = Config::load_from(&alternate).unwrap();
+    assert!(saved.agents.enabled.contains(&"my-agent".to_string()));
+    assert!(!home
+        .path()
+        .join(".config")
+        .join("agr")
+        .join("config.toml")
+        .exists());
+}
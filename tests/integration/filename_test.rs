@@ -2,7 +2,9 @@
 //!
 //! These tests are written BEFORE implementation (TDD approach).
 
-use agr::files::filename::{self, Config, FilenameError, Template, TemplateError};
+use agr::files::filename::{
+    self, Config, FilenameError, RenderContext, Segment, Template, TemplateError,
+};
 
 // ============================================================================
 // Space Replacement Tests
@@ -667,6 +669,152 @@ fn template_render_preserves_literal_separators() {
     assert!(result.contains("--"));
 }
 
+fn context() -> RenderContext {
+    RenderContext {
+        directory: "src".to_string(),
+        agent: "claude".to_string(),
+        project: "My Project".to_string(),
+        pid: 4242,
+        counter: 7,
+    }
+}
+
+#[test]
+fn template_parse_context_tags() {
+    let template = Template::parse("{agent}{project}{pid}{counter}").unwrap();
+    assert_eq!(
+        template.segments(),
+        &[
+            Segment::Agent,
+            Segment::Project,
+            Segment::Pid,
+            Segment::Counter
+        ]
+    );
+}
+
+#[test]
+fn template_parse_context_tags_reject_format() {
+    for tag in ["{agent:x}", "{project:x}", "{pid:x}", "{counter:03}"] {
+        assert!(
+            matches!(Template::parse(tag), Err(TemplateError::InvalidFormat(_))),
+            "{}",
+            tag
+        );
+    }
+}
+
+#[test]
+fn template_render_agent_tag() {
+    let template = Template::parse("{agent}").unwrap();
+    let result = template.render_with(&context(), &Config::default());
+    assert_eq!(result, "claude");
+}
+
+#[test]
+fn template_render_project_tag_sanitized() {
+    let template = Template::parse("{project}").unwrap();
+    let result = template.render_with(&context(), &Config::default());
+    assert_eq!(result, "My-Project");
+}
+
+#[test]
+fn template_render_pid_tag() {
+    let template = Template::parse("{pid}").unwrap();
+    let result = template.render_with(&context(), &Config::default());
+    assert_eq!(result, "4242");
+}
+
+#[test]
+fn template_render_counter_tag() {
+    let template = Template::parse("{agent}-{counter}").unwrap();
+    let result = template.render_with(&context(), &Config::default());
+    assert_eq!(result, "claude-7");
+}
+
+#[test]
+fn template_render_default_context_uses_directory_as_project() {
+    let template = Template::parse("{project}_{pid}_{counter}").unwrap();
+    let result = template.render("my-project", &Config::default());
+    assert_eq!(result, format!("my-project_{}_1", std::process::id()));
+}
+
+// ============================================================================
+// Collision and Context Helper Tests
+// ============================================================================
+
+#[test]
+fn unique_filename_keeps_free_name() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        filename::unique_filename(dir.path(), "session.cast"),
+        "session.cast"
+    );
+}
+
+#[test]
+fn unique_filename_appends_incrementing_suffix() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("session.cast"), "").unwrap();
+    assert_eq!(
+        filename::unique_filename(dir.path(), "session.cast"),
+        "session-1.cast"
+    );
+
+    std::fs::write(dir.path().join("session-1.cast"), "").unwrap();
+    assert_eq!(
+        filename::unique_filename(dir.path(), "session.cast"),
+        "session-2.cast"
+    );
+}
+
+#[test]
+fn unique_filename_treats_locked_name_as_taken() {
+    // Two sessions rendering the same name: the first has only locked its
+    // file so far, the second must still get a different one
+    let dir = tempfile::tempdir().unwrap();
+    let config = Config::default();
+    let first = filename::generate("proj", "{directory}", &config).unwrap();
+    let first = filename::unique_filename(dir.path(), &first);
+    agr::files::lock::create_lock(&dir.path().join(&first)).unwrap();
+
+    let second = filename::generate("proj", "{directory}", &config).unwrap();
+    let second = filename::unique_filename(dir.path(), &second);
+
+    assert_eq!(first, "proj.cast");
+    assert_eq!(second, "proj-1.cast");
+}
+
+#[test]
+fn next_counter_counts_existing_recordings() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(filename::next_counter(dir.path()), 1);
+    std::fs::write(dir.path().join("a.cast"), "").unwrap();
+    std::fs::write(dir.path().join("b.cast"), "").unwrap();
+    std::fs::write(dir.path().join("b.cast.bak"), "").unwrap();
+    assert_eq!(filename::next_counter(dir.path()), 3);
+    assert_eq!(filename::next_counter(&dir.path().join("missing")), 1);
+}
+
+#[test]
+fn project_name_finds_repository_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("my-repo");
+    let nested = repo.join("src").join("bin");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir(repo.join(".git")).unwrap();
+
+    assert_eq!(filename::project_name(&nested).as_deref(), Some("my-repo"));
+}
+
+#[test]
+fn project_name_falls_back_to_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let plain = dir.path().join("scratch");
+    std::fs::create_dir(&plain).unwrap();
+    assert_eq!(filename::project_name(&plain).as_deref(), Some("scratch"));
+}
+
 // ============================================================================
 // Generate Function Tests
 // ============================================================================
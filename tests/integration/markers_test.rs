@@ -2,6 +2,10 @@
 
 use super::helpers::temp_fixture;
 
+use agr::asciicast::marker_import::{parse_markers_csv, parse_markers_json};
+use agr::asciicast::{
+    markers_to_subtitles, FlattenMarkers, MarkerFlattening, SubtitleFormat, Transform,
+};
 use agr::{AsciicastFile, MarkerInfo, MarkerManager};
 use std::io::Write;
 use tempfile::NamedTempFile;
@@ -210,3 +214,153 @@ fn marker_display_format() {
     };
     assert_eq!(format!("{}", marker), "45.2s: Build error");
 }
+
+// === Subtitle export ===
+
+fn three_marker_fixture() -> Vec<MarkerInfo> {
+    let (_temp_dir, path) = temp_fixture("three_markers.cast");
+    MarkerManager::list_markers(&path).unwrap()
+}
+
+#[test]
+fn export_markers_as_webvtt() {
+    let vtt = markers_to_subtitles(&three_marker_fixture(), SubtitleFormat::Vtt);
+    assert_eq!(
+        vtt,
+        "WEBVTT
+
+00:00:01.500 --> 00:00:04.000
+Tests started
+
+00:00:04.000 --> 00:01:05.500
+Tests &lt;failed&gt; &amp; retried
+
+00:01:05.500 --> 00:01:08.500
+All green --&gt; ship it
+"
+    );
+}
+
+#[test]
+fn export_markers_as_srt() {
+    let srt = markers_to_subtitles(&three_marker_fixture(), SubtitleFormat::Srt);
+    assert_eq!(
+        srt,
+        "1
+00:00:01,500 --> 00:00:04,000
+Tests started
+
+2
+00:00:04,000 --> 00:01:05,500
+Tests ‹failed› & retried
+
+3
+00:01:05,500 --> 00:01:08,500
+All green → ship it
+"
+    );
+}
+
+// === Import tests ===
+
+fn labels(markers: &[MarkerInfo]) -> Vec<(f64, &str)> {
+    markers
+        .iter()
+        .map(|m| ((m.timestamp * 1000.0).round() / 1000.0, m.label.as_str()))
+        .collect()
+}
+
+#[test]
+fn imported_json_markers_list_in_time_order() {
+    let (_temp_dir, path) = temp_fixture("three_markers.cast");
+    let imported = parse_markers_json(
+        r#"{"markers": [
+            {"timestamp": 30.0, "label": "Retry", "category": "impl"},
+            {"timestamp": 0.25, "label": "Session start"},
+            {"timestamp": 1.5, "label": "Tests started"},
+            {"timestamp": 90.0, "label": "After the end"}
+        ]}"#,
+    )
+    .unwrap();
+
+    let report = MarkerManager::import_markers(&path, &imported).unwrap();
+    assert_eq!(report.added, 2);
+    assert_eq!(report.duplicates, 1);
+    assert_eq!(report.out_of_range.len(), 1);
+    assert_eq!(report.out_of_range[0].label, "After the end");
+
+    let markers = MarkerManager::list_markers(&path).unwrap();
+    assert_eq!(
+        labels(&markers),
+        vec![
+            (0.25, "Session start"),
+            (1.5, "Tests started"),
+            (4.0, "Tests <failed> & retried"),
+            (30.0, "[IMPL] Retry"),
+            (65.5, "All green\n--> ship it"),
+        ]
+    );
+    // Output timing is unchanged
+    let cast = AsciicastFile::parse(&path).unwrap();
+    assert!((cast.duration() - 66.5).abs() < 1e-9);
+}
+
+#[test]
+fn imported_csv_markers_list_in_time_order() {
+    let (_temp_dir, path) = temp_fixture("sample.cast");
+    let imported =
+        parse_markers_csv("timestamp,label\n0.7,\"Second, later\"\n0.3,First\n0.3,First\n")
+            .unwrap();
+
+    let report = MarkerManager::import_markers(&path, &imported).unwrap();
+    assert_eq!(report.added, 2);
+    assert_eq!(report.duplicates, 1);
+
+    let markers = MarkerManager::list_markers(&path).unwrap();
+    assert_eq!(
+        labels(&markers),
+        vec![(0.3, "First"), (0.7, "Second, later")]
+    );
+}
+
+#[test]
+fn reimporting_markers_is_a_no_op() {
+    let (_temp_dir, path) = temp_fixture("three_markers.cast");
+    let original = std::fs::read_to_string(&path).unwrap();
+    let imported = parse_markers_json(r#"[{"timestamp": 1.5, "label": "Tests started"}]"#).unwrap();
+    let report = MarkerManager::import_markers(&path, &imported).unwrap();
+    assert_eq!(report.added, 0);
+    assert_eq!(report.duplicates, 1);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+}
+
+fn flattened_lines(mode: MarkerFlattening) -> Vec<String> {
+    let mut cast = AsciicastFile::parse_str(cast_with_markers()).unwrap();
+    FlattenMarkers::new(mode).transform(&mut cast.events);
+    let written = cast.to_string().unwrap();
+    written.lines().skip(1).map(str::to_string).collect()
+}
+
+#[test]
+fn export_with_flattened_markers_writes_comment_output_events() {
+    assert_eq!(
+        flattened_lines(MarkerFlattening::Comment),
+        vec![
+            r#"[0.5,"o","$ make build\r\n"]"#,
+            r#"[1.0,"o","\u001b[2m# marker: Build started\u001b[0m\r\n"]"#,
+            r#"[2.5,"o","Build complete\r\n"]"#,
+            r#"[0.1,"o","\u001b[2m# marker: Build finished\u001b[0m\r\n"]"#,
+        ]
+    );
+}
+
+#[test]
+fn export_without_markers_moves_marker_time_to_next_event() {
+    assert_eq!(
+        flattened_lines(MarkerFlattening::Strip),
+        vec![
+            r#"[0.5,"o","$ make build\r\n"]"#,
+            r#"[3.6,"o","Build complete\r\n"]"#,
+        ]
+    );
+}
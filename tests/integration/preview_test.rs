@@ -39,11 +39,11 @@ fn session_preview_returns_none_for_invalid_file() {
 
 #[test]
 fn session_preview_format_duration_formats_correctly() {
-    let preview = SessionPreview {
-        duration_secs: 3661.5, // 1h 1m 1.5s
-        marker_count: 0,
-        styled_preview: Vec::new(),
-    };
+    let preview = SessionPreview::new(
+        3661.5, // 1h 1m 1.5s
+        0,
+        vec![Vec::new()],
+    );
 
     // Should format as "1h 1m 1s"
     let formatted = preview.format_duration();
@@ -64,18 +64,16 @@ fn session_preview_generates_styled_preview() {
     let (temp_dir, path) = temp_fixture("sample.cast");
     let preview = SessionPreview::load(&path).expect("Should load preview");
 
-    // At 10% of 0.8 seconds = 0.08 seconds, which is before the first event (0.5s)
-    // So the preview should be empty or contain only blank lines at this point.
-    // The important thing is that styled_preview is a valid Vec that we can inspect.
-    // For sample.cast, which has 3 lines of terminal output, we expect 24 lines
-    // (default terminal height) but they may all be empty at 10%.
+    // The 0% snapshot is taken before the first event (0.5s), so its lines
+    // may all be blank. The important thing is that it is a valid Vec that we
+    // can inspect: 24 lines (default terminal height).
     assert!(
-        !preview.styled_preview.is_empty(),
-        "styled_preview should have at least one line (terminal height)"
+        !preview.snapshot(0).is_empty(),
+        "snapshot should have at least one line (terminal height)"
     );
 
     // Each styled line should have cells
-    for line in &preview.styled_preview {
+    for line in preview.snapshot(0) {
         // Lines should have width matching terminal dimensions
         // (default is 80 cols, but styled_lines trims trailing spaces)
         assert!(
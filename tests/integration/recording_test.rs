@@ -6,12 +6,32 @@ use agr::{Config, Recorder};
 fn generate_filename_has_correct_format() {
     let config = Config::default();
     let recorder = Recorder::new(config);
-    let filename = recorder.generate_filename();
+    let dir = tempfile::tempdir().unwrap();
+    let filename = recorder.generate_filename("claude", dir.path());
     assert!(filename.ends_with(".cast"));
     // New format: {directory}_{date}_{time}.cast
     assert!(filename.contains('_'));
 }
 
+#[test]
+fn generate_filename_fills_agent_and_counter() {
+    let mut config = Config::default();
+    config.recording.filename_template = "{agent}-{counter}".to_string();
+    let recorder = Recorder::new(config);
+    let dir = tempfile::tempdir().unwrap();
+
+    assert_eq!(
+        recorder.generate_filename("claude", dir.path()),
+        "claude-1.cast"
+    );
+    std::fs::write(dir.path().join("claude-1.cast"), "").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+    assert_eq!(
+        recorder.generate_filename("claude", dir.path()),
+        "claude-2.cast"
+    );
+}
+
 #[test]
 fn sanitize_filename_preserves_valid_chars() {
     assert_eq!(Recorder::sanitize_filename("my-session"), "my-session.cast");
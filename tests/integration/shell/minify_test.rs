@@ -14,6 +14,7 @@
 //! - 1.7 Integration
 //! - 1.8 Functional Preservation
 //! - 1.9 Debug Mode
+//! - 1.10 Preserved Constructs
 
 use agr::shell::completions::{generate_bash_init, generate_zsh_init};
 use agr::shell::minify;
@@ -716,3 +717,82 @@ mod debug_mode {
         );
     }
 }
+
+// ============================================================================
+// 1.10 Preserved Construct Tests
+// ============================================================================
+
+mod preserved_constructs {
+    use super::*;
+    use std::process::Command;
+
+    /// Assert the script parses with `bash -n`, if bash is available.
+    fn assert_bash_parses(script: &str) {
+        let Ok(output) = Command::new("bash").args(["-n", "-c", script]).output() else {
+            return;
+        };
+        assert!(
+            output.status.success(),
+            "bash -n rejected minified script:\n{}\n{}",
+            script,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_command_spacing_preserved() {
+        let input = "f() {\n  if (( $1 > 10 && $1 < 20 )); then\n    echo mid\n  fi\n}";
+        let output = minify_aggressive(input);
+        assert!(output.contains("(( $1 > 10 && $1 < 20 ))"), "{}", output);
+        assert_bash_parses(&output);
+    }
+
+    #[test]
+    fn test_arithmetic_expansion_spacing_preserved() {
+        let input = "x=$(( (y >> 1) | 4 ))\necho \"$x\" > /tmp/out";
+        let output = minify_aggressive(input);
+        assert!(output.contains("$(( (y >> 1) | 4 ))"), "{}", output);
+        assert!(output.contains("\"$x\"> /tmp/out"), "{}", output);
+        assert_bash_parses(&output);
+    }
+
+    #[test]
+    fn test_conditional_expression_spacing_preserved() {
+        let input = "if [[ \"$a\" < \"$b\" || -z $c ]]; then\n  echo less\nfi";
+        let output = minify_aggressive(input);
+        assert!(
+            output.contains("[[ \"$a\" < \"$b\" || -z $c ]]"),
+            "{}",
+            output
+        );
+        assert_bash_parses(&output);
+    }
+
+    #[test]
+    fn test_single_line_array_literal_preserved() {
+        let input = "declare -A m=( [a]=1 [b]=$(( 2 | 1 )) )\necho \"${m[a]}\"";
+        let output = minify_aggressive(input);
+        assert!(
+            output.contains("m=( [a]=1 [b]=$(( 2 | 1 )) )"),
+            "{}",
+            output
+        );
+        assert_bash_parses(&output);
+    }
+
+    #[test]
+    fn test_multiline_array_literal_joined_with_spaces() {
+        let input = "declare -A m=(\n  [a]=1\n  [b]=\"two words\"\n)\necho \"${m[b]}\"";
+        let output = minify_aggressive(input);
+        assert_eq!(
+            output,
+            "declare -A m=( [a]=1 [b]=\"two words\" );echo \"${m[b]}\""
+        );
+        assert_bash_parses(&output);
+    }
+
+    #[test]
+    fn test_generated_init_scripts_parse() {
+        assert_bash_parses(&generate_bash_init(false));
+    }
+}
@@ -1,7 +1,8 @@
 //! Unit tests for shell module
 
 use agr::shell::{
-    extract_script_path, generate_section, install, is_installed_in, uninstall, Shell, SHELL_SCRIPT,
+    diagnose_in, extract_script_path, generate_section, install, is_installed_in, uninstall, Shell,
+    ShellIssue, PS_MARKER_END, PS_MARKER_START, SHELL_SCRIPT, VERSION_COMMENT,
 };
 use agr::ShellStatus;
 use std::fs;
@@ -113,6 +114,232 @@ fn test_uninstall_returns_false_when_not_installed() -> io::Result<()> {
     Ok(())
 }
 
+/// RC content with the integration section duplicated by two installs
+fn duplicated_rc_content() -> String {
+    let section = generate_section(Shell::Zsh);
+    format!("export FOO=bar\n\n{section}\nalias ll='ls -l'\n\n{section}\n")
+}
+
+#[test]
+fn test_uninstall_removes_all_duplicate_sections() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let rc_file = temp.path().join(".zshrc");
+    fs::write(&rc_file, duplicated_rc_content())?;
+    assert_eq!(is_installed_in(&rc_file)?, 2);
+
+    assert!(uninstall(&rc_file)?);
+
+    let content = fs::read_to_string(&rc_file)?;
+    assert!(!content.contains(MARKER_START));
+    assert!(!content.contains(MARKER_END));
+    assert!(!content.contains("_agr_setup_wrappers"));
+    assert!(content.contains("export FOO=bar"));
+    assert!(content.contains("alias ll='ls -l'"));
+    assert_eq!(is_installed_in(&rc_file)?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_uninstall_keeps_content_after_unterminated_marker() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let rc_file = temp.path().join(".zshrc");
+    fs::write(
+        &rc_file,
+        format!(
+            "{MARKER_START}\nexport FOO=bar\n\n{}\n",
+            generate_section(Shell::Zsh)
+        ),
+    )?;
+
+    assert!(uninstall(&rc_file)?);
+
+    assert_eq!(fs::read_to_string(&rc_file)?, "export FOO=bar\n");
+    Ok(())
+}
+
+#[test]
+fn test_reinstall_updates_section_in_place() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let rc_file = temp.path().join(".zshrc");
+    let before = "# My config\r\nexport FOO=bar\n\n";
+    let after = "\nalias ll='ls -l'   \n# trailing, no newline";
+    fs::write(
+        &rc_file,
+        format!("{before}{MARKER_START}\n# agr 0.0.1\nold_wrapper() {{ :; }}\n{MARKER_END}{after}"),
+    )?;
+
+    install(&rc_file)?;
+    install(&rc_file)?;
+
+    let content = fs::read_to_string(&rc_file)?;
+    assert_eq!(is_installed_in(&rc_file)?, 1);
+    assert_eq!(
+        content,
+        format!("{before}{}{after}", generate_section(Shell::Zsh))
+    );
+    assert!(content.contains(VERSION_COMMENT));
+    assert!(!content.contains("old_wrapper"));
+    assert!(!temp.path().join("..zshrc.agr-tmp").exists());
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_install_keeps_symlinked_rc_file() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let real = temp.path().join("dotfiles-zshrc");
+    let rc_file = temp.path().join(".zshrc");
+    fs::write(&real, "export FOO=bar\n")?;
+    std::os::unix::fs::symlink(&real, &rc_file)?;
+
+    install(&rc_file)?;
+
+    assert!(rc_file.is_symlink());
+    assert_eq!(is_installed_in(&real)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_powershell_profile_install_and_uninstall() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let profile = temp
+        .path()
+        .join("PowerShell")
+        .join("Microsoft.PowerShell_profile.ps1");
+    fs::create_dir_all(profile.parent().unwrap())?;
+    fs::write(&profile, "Set-PSReadLineOption -EditMode Emacs\r\n")?;
+
+    install(&profile)?;
+    install(&profile)?;
+
+    let content = fs::read_to_string(&profile)?;
+    assert_eq!(is_installed_in(&profile)?, 1);
+    assert!(content.contains(PS_MARKER_START));
+    assert!(content.contains(PS_MARKER_END));
+    assert!(!content.contains(MARKER_START));
+    assert!(content.contains("Register-ArgumentCompleter"));
+    assert!(content.starts_with("Set-PSReadLineOption -EditMode Emacs\r\n"));
+
+    assert!(uninstall(&profile)?);
+    let content = fs::read_to_string(&profile)?;
+    assert_eq!(content, "Set-PSReadLineOption -EditMode Emacs\n");
+    assert_eq!(is_installed_in(&profile)?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_powershell_profile_ignores_posix_markers() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let profile = temp.path().join("profile.ps1");
+    fs::write(
+        &profile,
+        format!("{MARKER_START}\n# not ours\n{MARKER_END}\n"),
+    )?;
+
+    assert_eq!(is_installed_in(&profile)?, 0);
+    assert!(!uninstall(&profile)?);
+    Ok(())
+}
+
+#[test]
+fn test_install_collapses_duplicate_sections() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let rc_file = temp.path().join(".zshrc");
+    fs::write(&rc_file, duplicated_rc_content())?;
+
+    install(&rc_file)?;
+
+    assert_eq!(is_installed_in(&rc_file)?, 1);
+    let content = fs::read_to_string(&rc_file)?;
+    assert!(content.contains("alias ll='ls -l'"));
+    Ok(())
+}
+
+#[test]
+fn test_doctor_reports_duplicate_sections_and_repairs() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let rc_file = temp.path().join(".zshrc");
+    fs::write(&rc_file, duplicated_rc_content())?;
+
+    let issues = diagnose_in(std::slice::from_ref(&rc_file), &[])?;
+    assert_eq!(
+        issues,
+        vec![ShellIssue::DuplicateBlocks {
+            rc_file: rc_file.clone(),
+            count: 2
+        }]
+    );
+
+    issues[0].repair()?;
+    assert_eq!(is_installed_in(&rc_file)?, 1);
+    assert!(diagnose_in(&[rc_file], &[])?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_doctor_detects_orphaned_script_reference() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let rc_file = temp.path().join(".zshrc");
+    let script_path = temp.path().join("gone").join("agr.sh");
+    fs::write(
+        &rc_file,
+        format!(
+            "{MARKER_START}\n[ -f \"{p}\" ] && source \"{p}\"\n{MARKER_END}\n",
+            p = script_path.display()
+        ),
+    )?;
+
+    let issues = diagnose_in(std::slice::from_ref(&rc_file), &[])?;
+    assert_eq!(
+        issues,
+        vec![ShellIssue::MissingScript {
+            rc_file: rc_file.clone(),
+            script_path: script_path.clone(),
+        }]
+    );
+    assert!(issues[0].to_string().contains("sources a missing script"));
+
+    // An existing script is not a problem
+    fs::create_dir_all(script_path.parent().unwrap())?;
+    fs::write(&script_path, SHELL_SCRIPT)?;
+    assert!(diagnose_in(std::slice::from_ref(&rc_file), &[])?.is_empty());
+
+    // Repairing replaces the old-style section with an embedded one
+    fs::remove_file(&script_path)?;
+    issues[0].repair()?;
+    assert_eq!(extract_script_path(&rc_file)?, None);
+    assert_eq!(is_installed_in(&rc_file)?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_doctor_detects_orphaned_completion() -> io::Result<()> {
+    let temp = TempDir::new()?;
+    let rc_file = temp.path().join(".zshrc");
+    let completion = temp.path().join("agr.fish");
+    let missing = temp.path().join("_agr");
+    fs::write(&rc_file, "export FOO=bar\n")?;
+    fs::write(&completion, "complete -c agr\n")?;
+
+    let completions = [completion.clone(), missing];
+    let issues = diagnose_in(std::slice::from_ref(&rc_file), &completions)?;
+    assert_eq!(
+        issues,
+        vec![ShellIssue::OrphanedCompletion {
+            path: completion.clone()
+        }]
+    );
+
+    // Completions are expected while the integration is installed
+    install(&rc_file)?;
+    assert!(diagnose_in(&[rc_file], &completions)?.is_empty());
+
+    issues[0].repair()?;
+    assert!(!completion.exists());
+    Ok(())
+}
+
 #[test]
 fn test_is_installed_in_detects_markers() -> io::Result<()> {
     let temp = TempDir::new()?;
@@ -120,11 +347,11 @@ fn test_is_installed_in_detects_markers() -> io::Result<()> {
 
     // Not installed
     fs::write(&rc_file, "# Normal config\n")?;
-    assert!(!is_installed_in(&rc_file)?);
+    assert_eq!(is_installed_in(&rc_file)?, 0);
 
     // With markers
     install(&rc_file)?;
-    assert!(is_installed_in(&rc_file)?);
+    assert_eq!(is_installed_in(&rc_file)?, 1);
 
     Ok(())
 }
@@ -205,6 +205,31 @@ fn snapshot_cli_help_main_colored() {
     insta::assert_snapshot!("cli_help_main_colored", output);
 }
 
+#[test]
+fn color_never_overrides_force_color() {
+    let (stdout, _, exit_code) = run_agr_with_colors(&["--color", "never", "--help"]);
+    assert_eq!(exit_code, 0);
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn color_always_overrides_no_color() {
+    let (stdout, _, exit_code) = run_agr(&["--color=always", "--help"]);
+    assert_eq!(exit_code, 0);
+    assert!(stdout.contains('\x1b'));
+}
+
+#[test]
+fn color_auto_is_plain_when_piped() {
+    let output = Command::new(env!("CARGO_BIN_EXE_agr"))
+        .arg("--help")
+        .env_remove("NO_COLOR")
+        .env_remove("FORCE_COLOR")
+        .output()
+        .expect("Failed to execute agr");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains('\x1b'));
+}
+
 /// Escape ANSI codes to make them visible in snapshots
 fn escape_ansi_for_snapshot(s: &str) -> String {
     s.replace("\x1b[", "ESC[")
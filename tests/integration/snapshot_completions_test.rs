@@ -83,6 +83,34 @@ fn snapshot_completions_shell_init_bash() {
     });
 }
 
+#[test]
+fn snapshot_completions_shell_init_fish() {
+    let (stdout, stderr, exit_code) = run_agr(&["completions", "--shell-init", "fish"]);
+    let output = format!(
+        "=== agr completions --shell-init fish ===\nExit code: {}\n\n--- stdout ---\n{}\n--- stderr ---\n{}",
+        exit_code, stdout, stderr
+    );
+    insta::with_settings!({
+        snapshot_path => "../integration/snapshots/completions"
+    }, {
+        insta::assert_snapshot!("completions_shell_init_fish", output);
+    });
+}
+
+#[test]
+fn snapshot_completions_shell_init_powershell() {
+    let (stdout, stderr, exit_code) = run_agr(&["completions", "--shell-init", "powershell"]);
+    let output = format!(
+        "=== agr completions --shell-init powershell ===\nExit code: {}\n\n--- stdout ---\n{}\n--- stderr ---\n{}",
+        exit_code, stdout, stderr
+    );
+    insta::with_settings!({
+        snapshot_path => "../integration/snapshots/completions"
+    }, {
+        insta::assert_snapshot!("completions_shell_init_powershell", output);
+    });
+}
+
 // ============================================================================
 // Completions Help
 // ============================================================================
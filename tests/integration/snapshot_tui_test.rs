@@ -582,10 +582,10 @@ fn snapshot_file_explorer_with_session_preview() {
     let mut explorer = FileExplorer::new(create_test_file_items());
 
     // Create a mock session preview
-    let preview = SessionPreview {
-        duration_secs: 125.5, // 2m 5s
-        marker_count: 3,
-        styled_preview: vec![
+    let preview = SessionPreview::new(
+        125.5, // 2m 5s
+        3,
+        vec![vec![
             StyledLine {
                 cells: "$ cargo build"
                     .chars()
@@ -612,8 +612,8 @@ fn snapshot_file_explorer_with_session_preview() {
                     })
                     .collect(),
             },
-        ],
-    };
+        ]],
+    );
 
     let output = render_explorer_with_preview(&mut explorer, Some(&preview), 100, 20);
     insta::assert_snapshot!("file_explorer_with_session_preview", output);
@@ -627,10 +627,10 @@ fn snapshot_file_explorer_preview_with_backup() {
     let mut explorer = FileExplorer::new(create_test_file_items());
 
     // Create a mock session preview
-    let preview = SessionPreview {
-        duration_secs: 300.0, // 5m 0s
-        marker_count: 2,
-        styled_preview: vec![StyledLine {
+    let preview = SessionPreview::new(
+        300.0, // 5m 0s
+        2,
+        vec![vec![StyledLine {
             cells: "$ echo hello"
                 .chars()
                 .map(|c| Cell {
@@ -638,8 +638,8 @@ fn snapshot_file_explorer_preview_with_backup() {
                     style: CellStyle::default(),
                 })
                 .collect(),
-        }],
-    };
+        }]],
+    );
 
     // Render with backup indicator
     let output =
@@ -655,10 +655,10 @@ fn snapshot_file_explorer_preview_without_backup() {
     let mut explorer = FileExplorer::new(create_test_file_items());
 
     // Create a mock session preview
-    let preview = SessionPreview {
-        duration_secs: 300.0, // 5m 0s
-        marker_count: 2,
-        styled_preview: vec![StyledLine {
+    let preview = SessionPreview::new(
+        300.0, // 5m 0s
+        2,
+        vec![vec![StyledLine {
             cells: "$ echo hello"
                 .chars()
                 .map(|c| Cell {
@@ -666,8 +666,8 @@ fn snapshot_file_explorer_preview_without_backup() {
                     style: CellStyle::default(),
                 })
                 .collect(),
-        }],
-    };
+        }]],
+    );
 
     // Render without backup indicator
     let output =
@@ -833,7 +833,7 @@ fn snapshot_optimize_result_error() {
 /// Render the help modal to a buffer and return as string.
 fn render_help_modal_to_string() -> String {
     let width = 70u16;
-    let height = 30u16;
+    let height = 34u16;
     let area = Rect::new(0, 0, width, height);
 
     // Create a mock terminal backend
@@ -881,7 +881,9 @@ fn create_test_file_items_with_lock() -> Vec<FileItem> {
             size: 1024 * 50,
             modified: Local.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
             has_backup: false,
+            has_notes: false,
             lock_info: None,
+            title: None,
         },
         FileItem {
             path: "/sessions/claude/20240116-recording.cast".to_string(),
@@ -890,10 +892,12 @@ fn create_test_file_items_with_lock() -> Vec<FileItem> {
             size: 1024 * 10,
             modified: Local.with_ymd_and_hms(2024, 1, 16, 14, 0, 0).unwrap(),
             has_backup: false,
+            has_notes: false,
             lock_info: Some(LockInfo {
                 pid: 12345,
                 started: "2024-01-16T14:00:00".to_string(),
             }),
+            title: None,
         },
         FileItem {
             path: "/sessions/codex/20240114-session3.cast".to_string(),
@@ -902,11 +906,13 @@ fn create_test_file_items_with_lock() -> Vec<FileItem> {
             size: 1024 * 100,
             modified: Local.with_ymd_and_hms(2024, 1, 14, 9, 0, 0).unwrap(),
             has_backup: false,
+            has_notes: false,
             lock_info: None,
+            title: None,
         },
     ];
     // Sort by date descending (default) - the locked item should be first
-    items.sort_by(|a, b| b.modified.cmp(&a.modified));
+    items.sort_by_key(|i| std::cmp::Reverse(i.modified));
     items
 }
 
@@ -926,10 +932,10 @@ fn snapshot_file_explorer_locked_item_preview() {
     let mut explorer = FileExplorer::new(create_test_file_items_with_lock());
 
     // Create a preview for the locked item (first item = selected by default)
-    let preview = SessionPreview {
-        duration_secs: 42.0,
-        marker_count: 0,
-        styled_preview: vec![StyledLine {
+    let preview = SessionPreview::new(
+        42.0,
+        0,
+        vec![vec![StyledLine {
             cells: "$ claude"
                 .chars()
                 .map(|c| Cell {
@@ -937,8 +943,8 @@ fn snapshot_file_explorer_locked_item_preview() {
                     style: CellStyle::default(),
                 })
                 .collect(),
-        }],
-    };
+        }]],
+    );
 
     let output = render_explorer_with_preview(&mut explorer, Some(&preview), 100, 15);
     insta::assert_snapshot!("file_explorer_locked_item_preview", output);
@@ -18,7 +18,8 @@ fn create_test_manager() -> (TempDir, StorageManager) {
     let mut config = Config::default();
     config.storage.directory = sessions_dir.to_string_lossy().to_string();
 
-    let manager = StorageManager::new(config);
+    let trash_dir = temp_dir.path().join("trash");
+    let manager = StorageManager::new(config).with_trash_dir(Some(trash_dir));
     (temp_dir, manager)
 }
 
@@ -185,6 +186,30 @@ fn list_sessions_ignores_non_cast_files() {
     assert_eq!(sessions.len(), 1);
 }
 
+#[test]
+fn list_sessions_detects_notes_sidecar() {
+    let temp = TempDir::new().unwrap();
+    let config = create_test_config(&temp);
+    let manager = StorageManager::new(config);
+
+    let noted = create_test_session(temp.path(), "claude", "noted.cast", "content");
+    create_test_session(temp.path(), "claude", "plain.cast", "content");
+    agr::files::notes::append_note(&noted, "Check the retry logic", Local::now()).unwrap();
+
+    let sessions = manager.list_sessions(None).unwrap();
+    // The .cast.md sidecar is not listed as a session of its own
+    assert_eq!(sessions.len(), 2);
+    let has_notes = |name: &str| {
+        sessions
+            .iter()
+            .find(|s| s.filename == name)
+            .map(|s| s.has_notes)
+            .unwrap()
+    };
+    assert!(has_notes("noted.cast"));
+    assert!(!has_notes("plain.cast"));
+}
+
 #[test]
 fn get_stats_calculates_correctly() {
     let temp = TempDir::new().unwrap();
@@ -205,7 +230,7 @@ fn get_stats_calculates_correctly() {
 fn delete_sessions_removes_files_inline() {
     let temp = TempDir::new().unwrap();
     let config = create_test_config(&temp);
-    let manager = StorageManager::new(config);
+    let manager = StorageManager::new(config).with_trash_dir(None);
 
     create_test_session(temp.path(), "claude", "session.cast", "content");
 
@@ -251,6 +276,8 @@ fn session_info_size_human_formats_correctly() {
         age_days: 0,
         age_hours: 0,
         age_minutes: 0,
+        pinned: false,
+        has_notes: false,
     };
 
     let human = session.size_human();
@@ -269,6 +296,8 @@ fn session_info_format_age_minutes_only() {
         age_days: 0,
         age_hours: 0,
         age_minutes: 45,
+        pinned: false,
+        has_notes: false,
     };
     assert_eq!(session.format_age(), "  45m");
 }
@@ -285,6 +314,8 @@ fn session_info_format_age_same_day_shows_hours() {
         age_days: 0,
         age_hours: 5,
         age_minutes: 300,
+        pinned: false,
+        has_notes: false,
     };
     assert_eq!(session.format_age(), "   5h");
 }
@@ -301,6 +332,8 @@ fn session_info_format_age_older_shows_days_only() {
         age_days: 3,
         age_hours: 75,
         age_minutes: 4500,
+        pinned: false,
+        has_notes: false,
     };
     assert_eq!(session.format_age(), "   3d");
 }
@@ -317,6 +350,8 @@ fn session_info_format_age_just_created() {
         age_days: 0,
         age_hours: 0,
         age_minutes: 0,
+        pinned: false,
+        has_notes: false,
     };
     assert_eq!(session.format_age(), "   0m");
 }
@@ -132,3 +132,127 @@ fn tab_moves_to_next_tab_stop() {
     assert!(output.contains("B"));
     assert!(output.len() >= 8);
 }
+
+// === Origin mode (DECOM) ===
+
+/// Row index of the first line containing `needle`.
+fn row_of(buf: &TerminalBuffer, needle: &str) -> Option<usize> {
+    buf.to_string()
+        .lines()
+        .position(|line| line.contains(needle))
+}
+
+#[test]
+fn origin_mode_positions_relative_to_scroll_region() {
+    let mut buf = TerminalBuffer::new(20, 10);
+    // Scroll region rows 3..=8 (1-indexed), origin mode on
+    buf.process("\x1b[3;8r\x1b[?6h", None);
+    assert_eq!(buf.cursor_row(), 2, "origin mode homes to the region top");
+
+    buf.process("\x1b[1;1HTOP\x1b[2;5HX", None);
+    assert_eq!(row_of(&buf, "TOP"), Some(2));
+    assert_eq!(row_of(&buf, "X"), Some(3));
+    assert_eq!(buf.cursor_col(), 5);
+}
+
+#[test]
+fn origin_mode_clamps_to_scroll_region() {
+    let mut buf = TerminalBuffer::new(20, 10);
+    buf.process("\x1b[3;6r\x1b[?6h\x1b[9;1HLOW", None);
+    assert_eq!(
+        row_of(&buf, "LOW"),
+        Some(5),
+        "row 9 clamps to region bottom"
+    );
+
+    buf.process("\x1b[20dV", None);
+    assert_eq!(buf.cursor_row(), 5, "VPA also clamps to the region");
+}
+
+#[test]
+fn origin_mode_off_uses_absolute_rows() {
+    let mut buf = TerminalBuffer::new(20, 10);
+    buf.process("\x1b[3;8r\x1b[?6h\x1b[?6l", None);
+    assert_eq!(buf.cursor_row(), 0, "resetting origin mode homes to row 0");
+
+    buf.process("\x1b[2;1HABS", None);
+    assert_eq!(row_of(&buf, "ABS"), Some(1));
+}
+
+#[test]
+fn full_reset_clears_origin_mode_and_screen() {
+    let mut buf = TerminalBuffer::new(20, 10);
+    buf.process("\x1b[3;8r\x1b[?6hjunk\x1bc", None);
+    assert_eq!(buf.to_string(), "");
+    assert_eq!((buf.cursor_row(), buf.cursor_col()), (0, 0));
+
+    buf.process("\x1b[2;1HR", None);
+    assert_eq!(row_of(&buf, "R"), Some(1));
+}
+
+#[test]
+fn resize_clears_origin_mode() {
+    let mut buf = TerminalBuffer::new(20, 10);
+    buf.process("\x1b[3;8r\x1b[?6h", None);
+    buf.resize(20, 12);
+
+    buf.process("\x1b[1;1HZ", None);
+    assert_eq!(row_of(&buf, "Z"), Some(0));
+}
+
+// === Tab stops (HT, HTS, TBC) ===
+
+#[test]
+fn tab_uses_default_stops_every_eight_columns() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process("A\t", None);
+    assert_eq!(buf.cursor_col(), 8);
+    buf.process("\t", None);
+    assert_eq!(buf.cursor_col(), 16);
+}
+
+#[test]
+fn tab_lands_on_custom_tab_stop() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    // Clear all stops, set one at column 20, then tab from column 5
+    buf.process("\x1b[3g\x1b[1;21H\x1bH\r\x1b[5CX\tY", None);
+    assert_eq!(buf.cursor_col(), 21);
+    assert_eq!(
+        buf.to_string(),
+        format!("{}X{}Y", " ".repeat(5), " ".repeat(14))
+    );
+}
+
+#[test]
+fn tab_clear_at_cursor_removes_one_stop() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    // Clear the stop at column 8; the next tab goes to 16
+    buf.process("\x1b[1;9H\x1b[g\r\t", None);
+    assert_eq!(buf.cursor_col(), 16);
+}
+
+#[test]
+fn tab_without_stops_goes_to_last_column() {
+    let mut buf = TerminalBuffer::new(40, 5);
+    buf.process("\x1b[3g\t", None);
+    assert_eq!(buf.cursor_col(), 39);
+}
+
+#[test]
+fn full_reset_restores_default_tab_stops() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process("\x1b[3g\x1bc\t", None);
+    assert_eq!(buf.cursor_col(), 8);
+}
+
+#[test]
+fn resize_keeps_custom_tab_stops() {
+    let mut buf = TerminalBuffer::new(40, 5);
+    buf.process("\x1b[3g\x1b[1;21H\x1bH", None);
+    buf.resize(100, 5);
+
+    buf.process("\r\t", None);
+    assert_eq!(buf.cursor_col(), 20);
+    buf.process("\t", None);
+    assert_eq!(buf.cursor_col(), 40, "new columns get default stops");
+}
@@ -0,0 +1,77 @@
+//! Inline image (Sixel, iTerm2) capture tests.
+
+use agr::terminal::{ImageProtocol, TerminalBuffer};
+
+const SIXEL: &str = "\x1bP0;0;0q\"1;1;4;4#0;2;100;0;0#0~~~~\x1b\\";
+const ITERM_IMAGE: &str = "\x1b]1337;File=name=Y2hhcnQ=;size=4;inline=1:AAAA\x07";
+
+#[test]
+fn sixel_is_captured_without_corrupting_text() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process(&format!("before{}after\r\nnext", SIXEL), None);
+
+    assert_eq!(buf.to_string(), "beforeafter\nnext");
+    let images = buf.images();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].protocol, ImageProtocol::Sixel);
+    assert_eq!(images[0].sequence, SIXEL);
+    assert_eq!((images[0].row, images[0].col), (0, 6));
+}
+
+#[test]
+fn sixel_split_across_process_calls_is_captured() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    let (head, tail) = SIXEL.split_at(12);
+    buf.process(&format!("x{}", head), None);
+    assert!(buf.images().is_empty());
+    buf.process(&format!("{}y", tail), None);
+
+    assert_eq!(buf.to_string(), "xy");
+    assert_eq!(buf.images().len(), 1);
+    assert_eq!(buf.images()[0].sequence, SIXEL);
+}
+
+#[test]
+fn iterm_inline_image_is_captured() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process(&format!("$ imgcat chart.png\r\n{}done", ITERM_IMAGE), None);
+
+    assert_eq!(buf.to_string(), "$ imgcat chart.png\ndone");
+    let images = buf.images();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].protocol, ImageProtocol::Iterm2);
+    assert_eq!(images[0].sequence, ITERM_IMAGE);
+    assert_eq!((images[0].row, images[0].col), (1, 0));
+}
+
+#[test]
+fn iterm_image_with_st_terminator_keeps_terminator() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    let sequence = "\x1b]1337;File=inline=1:AAAA\x1b\\";
+    buf.process(sequence, None);
+
+    assert_eq!(buf.images().len(), 1);
+    assert_eq!(buf.images()[0].sequence, sequence);
+}
+
+#[test]
+fn other_osc_and_dcs_sequences_are_not_images() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    // Window title, iTerm2 non-image command, DECRQSS
+    buf.process(
+        "\x1b]0;title\x07\x1b]1337;SetMark\x07\x1bP$qm\x1b\\text",
+        None,
+    );
+
+    assert!(buf.images().is_empty());
+    assert_eq!(buf.to_string(), "text");
+}
+
+#[test]
+fn multiple_images_are_kept_in_order() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process(&format!("{}\r\n{}", SIXEL, ITERM_IMAGE), None);
+
+    let protocols: Vec<_> = buf.images().iter().map(|i| i.protocol).collect();
+    assert_eq!(protocols, vec![ImageProtocol::Sixel, ImageProtocol::Iterm2]);
+}
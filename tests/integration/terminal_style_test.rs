@@ -433,3 +433,65 @@ fn reverse_video_combined_with_colors() {
     assert!(cell.style.reverse);
     assert_eq!(cell.style.fg, Color::Red);
 }
+
+// === Extended color forms ===
+
+/// Style of the first cell after processing `input`.
+fn first_cell_style(input: &str) -> CellStyle {
+    let mut buf = TerminalBuffer::new(20, 2);
+    buf.process(input, None);
+    buf.row(0).unwrap()[0].style
+}
+
+#[test]
+fn sgr_256_color_semicolon_form() {
+    assert_eq!(first_cell_style("\x1b[38;5;42mX").fg, Color::Indexed(42));
+    assert_eq!(first_cell_style("\x1b[48;5;7mX").bg, Color::Indexed(7));
+}
+
+#[test]
+fn sgr_256_color_colon_form() {
+    assert_eq!(first_cell_style("\x1b[38:5:42mX").fg, Color::Indexed(42));
+    assert_eq!(first_cell_style("\x1b[48:5:7mX").bg, Color::Indexed(7));
+}
+
+#[test]
+fn sgr_truecolor_semicolon_form() {
+    let style = first_cell_style("\x1b[38;2;1;2;3;48;2;4;5;6mX");
+    assert_eq!(style.fg, Color::Rgb(1, 2, 3));
+    assert_eq!(style.bg, Color::Rgb(4, 5, 6));
+}
+
+#[test]
+fn sgr_truecolor_colon_form_with_empty_color_space() {
+    let style = first_cell_style("\x1b[38:2::10:20:30;48:2::40:50:60mX");
+    assert_eq!(style.fg, Color::Rgb(10, 20, 30));
+    assert_eq!(style.bg, Color::Rgb(40, 50, 60));
+}
+
+#[test]
+fn sgr_truecolor_colon_form_without_color_space() {
+    assert_eq!(
+        first_cell_style("\x1b[38:2:10:20:30mX").fg,
+        Color::Rgb(10, 20, 30)
+    );
+}
+
+#[test]
+fn sgr_colon_color_does_not_swallow_following_params() {
+    // In the colon form the color is one parameter; ";1" still means bold
+    let style = first_cell_style("\x1b[38:2::10:20:30;1mX");
+    assert_eq!(style.fg, Color::Rgb(10, 20, 30));
+    assert!(style.bold);
+}
+
+#[test]
+fn sgr_default_colors_reset_extended_colors() {
+    let mut buf = TerminalBuffer::new(20, 2);
+    buf.process("\x1b[38:2::1:2:3;48;5;9mA\x1b[39;49mB", None);
+    let row = buf.row(0).unwrap();
+    assert_eq!(row[0].style.fg, Color::Rgb(1, 2, 3));
+    assert_eq!(row[0].style.bg, Color::Indexed(9));
+    assert_eq!(row[1].style.fg, Color::Default);
+    assert_eq!(row[1].style.bg, Color::Default);
+}
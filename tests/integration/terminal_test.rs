@@ -212,3 +212,90 @@ fn scroll_region_fixture_test() {
     println!("Terminal size: {}x{}", cols, rows);
     println!("Events processed: {}", cast.events.len());
 }
+
+// === Dirty row tracking ===
+
+#[test]
+fn new_buffer_marks_every_row_dirty() {
+    let buf = TerminalBuffer::new(10, 4);
+    assert_eq!(buf.dirty_rows(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn process_marks_only_written_rows_dirty() {
+    let mut buf = TerminalBuffer::new(20, 5);
+    buf.clear_dirty();
+    buf.process("\x1b[3;1Hhello", None);
+    assert_eq!(buf.dirty_rows(), vec![2]);
+    assert!(buf.is_row_dirty(2));
+    assert!(!buf.is_row_dirty(0));
+    assert!(!buf.is_row_dirty(99));
+}
+
+#[test]
+fn cursor_movement_alone_is_not_dirty() {
+    let mut buf = TerminalBuffer::new(20, 5);
+    buf.clear_dirty();
+    buf.process("\x1b[4;7H\x1b[1m", None);
+    assert!(buf.dirty_rows().is_empty());
+}
+
+#[test]
+fn scrolling_marks_scroll_region_dirty() {
+    let mut buf = TerminalBuffer::new(20, 4);
+    buf.process("a\r\nb\r\nc\r\nd", None);
+    buf.clear_dirty();
+    buf.process("\r\n", None);
+    assert_eq!(buf.dirty_rows(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn erase_in_line_marks_row_dirty() {
+    let mut buf = TerminalBuffer::new(20, 4);
+    buf.process("one\r\ntwo", None);
+    buf.clear_dirty();
+    buf.process("\x1b[2K", None);
+    assert_eq!(buf.dirty_rows(), vec![1]);
+}
+
+#[test]
+fn clear_screen_and_resize_mark_all_rows_dirty() {
+    let mut buf = TerminalBuffer::new(20, 3);
+    buf.clear_dirty();
+    buf.process("\x1b[2J", None);
+    assert_eq!(buf.dirty_rows(), vec![0, 1, 2]);
+
+    buf.clear_dirty();
+    buf.resize(10, 2);
+    assert_eq!(buf.dirty_rows(), vec![0, 1]);
+
+    buf.clear_dirty();
+    buf.mark_all_dirty();
+    assert_eq!(buf.dirty_rows(), vec![0, 1]);
+}
+
+// === Sequences consumed without visible effect ===
+
+#[test]
+fn cursor_style_sequence_leaves_no_garbage() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process("before\x1b[2 qafter\x1b[0 q\x1b[ q!", None);
+    assert_eq!(buf.to_string(), "beforeafter!");
+}
+
+#[test]
+fn decrqss_request_leaves_no_garbage() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process("a\x1bP$q q\x1b\\b\x1bP$qm\x1b\\c", None);
+    assert_eq!(buf.to_string(), "abc");
+    assert!(buf.images().is_empty());
+}
+
+#[test]
+fn unrendered_sgr_attributes_leave_text_and_style_intact() {
+    let mut buf = TerminalBuffer::new(80, 24);
+    buf.process("\x1b[1;5;9;53mX\x1b[25;29;55mY\x1b[77mZ", None);
+    assert_eq!(buf.to_string(), "XYZ");
+    let row = buf.row(0).unwrap();
+    assert!(row.iter().take(3).all(|cell| cell.style.bold));
+}
@@ -773,3 +773,23 @@ fn transform_requires_transform_flag() {
         combined
     );
 }
+
+#[test]
+fn transform_preview_json_prints_stats_without_modifying_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let original_content = sample_cast_with_long_pauses();
+    let cast_path = create_cast_file(&temp_dir, "preview.cast", original_content);
+
+    let (stdout, stderr, exit_code) = run_agr(&[
+        "transform",
+        "--preview",
+        "--json",
+        cast_path.to_str().unwrap(),
+    ]);
+
+    assert_eq!(exit_code, 0, "Exit code should be 0. stderr: {}", stderr);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["stats"]["events_processed"], 3);
+    assert!(json["total_tokens"].as_u64().unwrap() > 0);
+    assert_eq!(fs::read_to_string(&cast_path).unwrap(), original_content);
+}
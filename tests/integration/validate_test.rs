@@ -0,0 +1,113 @@
+//! Integration tests for `agr validate`
+
+use std::process::Command;
+
+use agr::asciicast::{validate_file, Severity};
+
+use crate::helpers::fixtures_dir;
+
+fn fixture(name: &str) -> std::path::PathBuf {
+    fixtures_dir().join("validate").join(name)
+}
+
+/// Run `agr validate` on a fixture, returning stdout and the exit code.
+fn run_validate(name: &str) -> (String, i32) {
+    let output = Command::new(env!("CARGO_BIN_EXE_agr"))
+        .arg("validate")
+        .arg(fixture(name))
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("Failed to execute agr");
+    (
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        output.status.code().unwrap_or(-1),
+    )
+}
+
+/// Severity and message of every problem in a fixture.
+fn problems(name: &str) -> Vec<(Severity, String)> {
+    validate_file(fixture(name))
+        .unwrap()
+        .problems
+        .into_iter()
+        .map(|p| (p.severity, p.message))
+        .collect()
+}
+
+#[test]
+fn clean_recording_passes() {
+    let (stdout, code) = run_validate("clean.cast");
+    assert_eq!(code, 0);
+    assert_eq!(stdout.trim(), "No problems found (3 events)");
+}
+
+#[test]
+fn invalid_header_is_not_playable() {
+    let (stdout, code) = run_validate("bad_header.cast");
+    assert_eq!(code, 1);
+    assert!(stdout.contains("[fail] line 1: header is invalid"));
+    assert!(stdout.contains("Not playable: 1 error(s)"));
+}
+
+#[test]
+fn missing_terminal_size_is_a_warning() {
+    let (stdout, code) = run_validate("missing_size.cast");
+    assert_eq!(code, 0);
+    assert!(stdout.contains("[warn] line 1: header has no term.cols/term.rows"));
+    assert!(stdout.contains("Playable, 1 warning(s)"));
+}
+
+#[test]
+fn time_going_backwards_is_not_playable() {
+    let (stdout, code) = run_validate("backwards.cast");
+    assert_eq!(code, 1);
+    assert!(stdout.contains("[fail] line 3: time goes backwards (interval -0.500s)"));
+}
+
+#[test]
+fn truncated_final_line_is_not_playable() {
+    let (stdout, code) = run_validate("truncated.cast");
+    assert_eq!(code, 1);
+    assert!(stdout.contains("[fail] line 3: final line is truncated"));
+}
+
+#[test]
+fn markers_out_of_order_are_reported() {
+    assert_eq!(
+        problems("markers_out_of_order.cast"),
+        vec![
+            (
+                Severity::Error,
+                "time goes backwards (interval -1.000s)".to_string()
+            ),
+            (
+                Severity::Warning,
+                "marker 'First' at 1.0s is before the previous marker at 2.0s".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unterminated_escape_sequence_is_a_warning() {
+    let (stdout, code) = run_validate("unterminated_osc.cast");
+    assert_eq!(code, 0);
+    assert!(stdout.contains("[warn] line 2: OSC sequence is never terminated"));
+}
+
+#[test]
+fn out_of_range_color_is_a_warning() {
+    assert_eq!(
+        problems("bad_color.cast"),
+        vec![(
+            Severity::Warning,
+            "SGR color index 300 is out of range (0-255)".to_string()
+        )]
+    );
+}
+
+#[test]
+fn missing_file_fails() {
+    let (_, code) = run_validate("does_not_exist.cast");
+    assert_ne!(code, 0);
+}
@@ -14,3 +14,6 @@ mod silence_removal_test;
 
 #[path = "performance/transform_cli_test.rs"]
 mod transform_cli_test;
+
+#[path = "performance/render_dirty_test.rs"]
+mod render_dirty_test;
@@ -0,0 +1,93 @@
+//! Performance tests for the player's dirty-region renderer.
+//!
+//! A spinner updating one line should redraw one row per frame, not the
+//! whole screen, so fast playback on large terminals stays responsive.
+//!
+//! Run with: `cargo test --test performance`
+
+use std::time::Instant;
+
+use agr::player::render::{render_dirty_rows, render_viewport};
+use agr::terminal::TerminalBuffer;
+
+const COLS: usize = 200;
+const ROWS: usize = 60;
+const FRAMES: usize = 1_000;
+
+/// A full screen of output followed by a spinner on the last line.
+fn spinner_frames() -> (TerminalBuffer, Vec<String>) {
+    let mut buffer = TerminalBuffer::new(COLS, ROWS);
+    for row in 0..ROWS - 1 {
+        buffer.process(
+            &format!("\x1b[32mline {} of build output\x1b[0m\r\n", row),
+            None,
+        );
+    }
+    let frames = (0..FRAMES)
+        .map(|i| format!("\r\x1b[2K{} Thinking... {}", ['⠋', '⠙', '⠹', '⠸'][i % 4], i))
+        .collect();
+    (buffer, frames)
+}
+
+#[test]
+fn localized_updates_emit_one_row_per_frame() {
+    let (mut buffer, frames) = spinner_frames();
+    let mut out = Vec::new();
+    render_viewport(&mut out, &buffer, 0, 0, ROWS, COLS, None).unwrap();
+    buffer.clear_dirty();
+
+    let mut emitted = 0;
+    let mut diff_bytes = 0;
+    let mut full_bytes = 0;
+    for frame in &frames {
+        buffer.process(frame, None);
+
+        let mut out = Vec::new();
+        let rows = render_dirty_rows(&mut out, &buffer, 0, 0, ROWS, COLS, None).unwrap();
+        assert_eq!(rows, 1, "a spinner frame should redraw a single row");
+        emitted += rows;
+        diff_bytes += out.len();
+
+        let mut out = Vec::new();
+        render_viewport(&mut out, &buffer, 0, 0, ROWS, COLS, None).unwrap();
+        full_bytes += out.len();
+
+        buffer.clear_dirty();
+    }
+
+    assert_eq!(emitted, FRAMES);
+    println!(
+        "{} frames: dirty renderer wrote {} bytes, full renderer {} bytes",
+        FRAMES, diff_bytes, full_bytes
+    );
+    assert!(
+        diff_bytes * 20 < full_bytes,
+        "dirty renderer should write far less than full redraws"
+    );
+}
+
+#[test]
+fn dirty_rendering_is_faster_than_full_redraws() {
+    let (mut buffer, frames) = spinner_frames();
+    buffer.clear_dirty();
+
+    let start = Instant::now();
+    for frame in &frames {
+        buffer.process(frame, None);
+        let mut out = Vec::new();
+        render_dirty_rows(&mut out, &buffer, 0, 0, ROWS, COLS, None).unwrap();
+        buffer.clear_dirty();
+    }
+    let dirty = start.elapsed();
+
+    let start = Instant::now();
+    for frame in &frames {
+        buffer.process(frame, None);
+        let mut out = Vec::new();
+        render_viewport(&mut out, &buffer, 0, 0, ROWS, COLS, None).unwrap();
+    }
+    let full = start.elapsed();
+
+    println!("{} frames: dirty {:?}, full {:?}", FRAMES, dirty, full);
+    assert!(dirty < full, "dirty: {:?}, full: {:?}", dirty, full);
+}